@@ -2,21 +2,56 @@ import { useEffect, useRef, useState } from "react";
 import { isTauri } from "./lib/tauri";
 import type { Window } from "@tauri-apps/api/window";
 import { listen } from "@tauri-apps/api/event";
+import { invoke } from "@tauri-apps/api/core";
+import { useQueryClient } from "@tanstack/react-query";
 import { Dashboard } from "./domains/analytics";
 import { Settings } from "./domains/settings";
 import { ConfigPage } from "./domains/config";
 import { AgentsPage } from "./domains/agents";
 import { PluginsPage } from "./domains/plugins";
 import { ProjectsPage } from "./domains/projects";
+import { LivePage } from "./domains/live";
 import { AnalysisPage } from "./domains/analysis";
 import { BackupPage } from "./domains/backup";
+import { ModelsPage } from "./domains/models";
+import { PromptsPage } from "./domains/prompts";
 import { Sidebar, type Page } from "./components/Sidebar";
 import { WindowControls } from "./components/WindowControls";
+import { CommandPalette } from "./components/CommandPalette";
+import { DemoWatermark } from "./components/DemoWatermark";
+import type { ConfigChange, JumpAction, LaunchAction, WindowState } from "./types";
+
+// Queries to refetch when a config file changes on disk
+const CONFIG_QUERY_KEYS: Record<ConfigChange["kind"], string[][]> = {
+  settings: [["hooks-status"], ["config-git-status"]],
+  claude_json: [["mcp-servers"], ["projects"], ["projectDetails"]],
+  claude_md: [["claude-md-files"], ["claude-md-content"]],
+  agents: [["agents"], ["directory-files"], ["item-content"]],
+  commands: [["commands"], ["directory-files"], ["item-content"], ["commandContent"]],
+};
+
+// Map tray section names and saved routes to Page types
+const PAGE_MAP: Record<string, Page> = {
+  analytics: "analytics",
+  projects: "projects",
+  live: "live",
+  agents: "agents",
+  plugins: "plugins",
+  config: "config",
+  backup: "backup",
+  settings: "settings",
+  analysis: "analysis",
+  models: "models",
+  prompts: "prompts",
+};
 
 function App() {
   const [activePage, setActivePage] = useState<Page>("analytics");
+  const [paletteOpen, setPaletteOpen] = useState(false);
+  const [jump, setJump] = useState<JumpAction | null>(null);
   const tauriWindowRef = useRef<Window | null>(null);
   const headerRef = useRef<HTMLElement>(null);
+  const queryClient = useQueryClient();
 
   // Set up Tauri window reference
   useEffect(() => {
@@ -27,24 +62,56 @@ function App() {
     });
   }, []);
 
+  const handleJump = (action: JumpAction) => {
+    setJump(action);
+    switch (action.type) {
+      case "agent":
+      case "command":
+        setActivePage("agents");
+        break;
+      case "mcp_server":
+        setActivePage(action.project_path ? "projects" : "plugins");
+        break;
+      default:
+        setActivePage("projects");
+    }
+  };
+
+  // Reopen on the page that was open last (or the one given with --open /
+  // --project), then remember page changes
+  const routeRestored = useRef(false);
+  useEffect(() => {
+    if (!isTauri()) return;
+
+    invoke<WindowState>("get_window_state")
+      .then((state) => {
+        const page = state.route ? PAGE_MAP[state.route] : undefined;
+        if (page) setActivePage(page);
+      })
+      .catch(() => {})
+      .then(() => invoke<LaunchAction | null>("take_launch_action"))
+      .then((action) => {
+        const page = action?.section ? PAGE_MAP[action.section] : undefined;
+        if (page) setActivePage(page);
+        if (action?.jump) handleJump(action.jump);
+      })
+      .finally(() => {
+        routeRestored.current = true;
+      });
+  }, []);
+
+  useEffect(() => {
+    if (!isTauri() || !routeRestored.current) return;
+    invoke("set_last_route", { route: activePage }).catch(() => {});
+  }, [activePage]);
+
   // Listen for navigation events from the tray menu
   useEffect(() => {
     if (!isTauri()) return;
 
     const unlisten = listen<string>("navigate", (event) => {
       const section = event.payload;
-      // Map tray section names to Page types
-      const pageMap: Record<string, Page> = {
-        analytics: "analytics",
-        projects: "projects",
-        agents: "agents",
-        plugins: "plugins",
-        config: "config",
-        backup: "backup",
-        settings: "settings",
-        analysis: "analysis",
-      };
-      const page = pageMap[section];
+      const page = PAGE_MAP[section];
       if (page) {
         setActivePage(page);
       }
@@ -55,6 +122,51 @@ function App() {
     };
   }, []);
 
+  // ⌘K / Ctrl+K and the tray "Search…" item open the command palette
+  useEffect(() => {
+    const handleKeyDown = (e: KeyboardEvent) => {
+      if ((e.metaKey || e.ctrlKey) && e.key.toLowerCase() === "k") {
+        e.preventDefault();
+        setPaletteOpen((open) => !open);
+      }
+    };
+    window.addEventListener("keydown", handleKeyDown);
+
+    const unlisten = isTauri() ? listen("open-search", () => setPaletteOpen(true)) : null;
+
+    return () => {
+      window.removeEventListener("keydown", handleKeyDown);
+      unlisten?.then((fn) => fn());
+    };
+  }, []);
+
+  // A second launch with --project forwards it to this instance
+  useEffect(() => {
+    if (!isTauri()) return;
+
+    const unlisten = listen<JumpAction>("jump", (event) => handleJump(event.payload));
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+
+  // Refresh config pages when files are edited outside the app
+  useEffect(() => {
+    if (!isTauri()) return;
+
+    const unlisten = listen<ConfigChange>("config-changed", (event) => {
+      for (const queryKey of CONFIG_QUERY_KEYS[event.payload.kind] ?? []) {
+        queryClient.invalidateQueries({ queryKey });
+      }
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [queryClient]);
+
   // Set up window dragging for Tauri
   useEffect(() => {
     if (!isTauri() || !headerRef.current) return;
@@ -98,13 +210,19 @@ function App() {
       case "config":
         return <ConfigPage />;
       case "agents":
-        return <AgentsPage />;
+        return <AgentsPage jump={jump} />;
       case "plugins":
         return <PluginsPage />;
       case "projects":
-        return <ProjectsPage />;
+        return <ProjectsPage jump={jump} />;
+      case "live":
+        return <LivePage />;
       case "analysis":
         return <AnalysisPage />;
+      case "models":
+        return <ModelsPage />;
+      case "prompts":
+        return <PromptsPage />;
       case "backup":
         return <BackupPage />;
       default:
@@ -134,6 +252,14 @@ function App() {
         {/* Main Content */}
         <main className="flex-1 overflow-auto">{renderPage()}</main>
       </div>
+
+      <CommandPalette
+        open={paletteOpen}
+        onClose={() => setPaletteOpen(false)}
+        onJump={handleJump}
+      />
+
+      <DemoWatermark />
     </div>
   );
 }
@@ -0,0 +1,133 @@
+import { useEffect, useRef, useState } from "react";
+import { useQuery } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { FolderOpen, MessageSquare, Bot, Terminal, Server, Search } from "lucide-react";
+import { cn } from "../lib/utils";
+import type { JumpAction, QuickSearchResult, SearchResultKind } from "../types";
+
+const KIND_ICONS: Record<SearchResultKind, React.ReactNode> = {
+  project: <FolderOpen size={16} />,
+  session: <MessageSquare size={16} />,
+  agent: <Bot size={16} />,
+  command: <Terminal size={16} />,
+  mcp_server: <Server size={16} />,
+};
+
+const KIND_LABELS: Record<SearchResultKind, string> = {
+  project: "Project",
+  session: "Session",
+  agent: "Agent",
+  command: "Command",
+  mcp_server: "MCP Server",
+};
+
+interface CommandPaletteProps {
+  open: boolean;
+  onClose: () => void;
+  onJump: (action: JumpAction) => void;
+}
+
+/** ⌘K search across projects, sessions, agents, commands and MCP servers */
+export function CommandPalette({ open, onClose, onJump }: CommandPaletteProps) {
+  const [query, setQuery] = useState("");
+  const [debouncedQuery, setDebouncedQuery] = useState("");
+  const [selected, setSelected] = useState(0);
+  const inputRef = useRef<HTMLInputElement>(null);
+
+  useEffect(() => {
+    const timer = setTimeout(() => setDebouncedQuery(query), 150);
+    return () => clearTimeout(timer);
+  }, [query]);
+
+  useEffect(() => {
+    if (open) {
+      setQuery("");
+      setSelected(0);
+      requestAnimationFrame(() => inputRef.current?.focus());
+    }
+  }, [open]);
+
+  const { data: results = [] } = useQuery({
+    queryKey: ["quick-search", debouncedQuery],
+    queryFn: () => invoke<QuickSearchResult[]>("quick_search", { query: debouncedQuery }),
+    enabled: open && debouncedQuery.trim().length > 0,
+    placeholderData: (previous) => previous,
+  });
+
+  useEffect(() => setSelected(0), [results]);
+
+  if (!open) return null;
+
+  const pick = (result: QuickSearchResult) => {
+    onJump(result.action);
+    onClose();
+  };
+
+  const handleKeyDown = (e: React.KeyboardEvent) => {
+    if (e.key === "Escape") {
+      onClose();
+    } else if (e.key === "ArrowDown") {
+      e.preventDefault();
+      setSelected((i) => Math.min(i + 1, results.length - 1));
+    } else if (e.key === "ArrowUp") {
+      e.preventDefault();
+      setSelected((i) => Math.max(i - 1, 0));
+    } else if (e.key === "Enter" && results[selected]) {
+      pick(results[selected]);
+    }
+  };
+
+  return (
+    <div
+      className="fixed inset-0 z-[100] bg-black/50 flex items-start justify-center pt-24"
+      onClick={onClose}
+    >
+      <div
+        className="w-full max-w-xl bg-card border border-border rounded-xl shadow-2xl overflow-hidden"
+        onClick={(e) => e.stopPropagation()}
+      >
+        <div className="flex items-center gap-3 px-4 border-b border-border">
+          <Search size={16} className="text-muted-foreground" />
+          <input
+            ref={inputRef}
+            value={query}
+            onChange={(e) => setQuery(e.target.value)}
+            onKeyDown={handleKeyDown}
+            placeholder="Search projects, sessions, agents, commands, MCP servers..."
+            className="flex-1 py-3 bg-transparent text-sm text-foreground placeholder:text-muted-foreground/60 outline-none"
+          />
+          <kbd className="text-xs text-muted-foreground">esc</kbd>
+        </div>
+
+        {query.trim() && (
+          <div className="max-h-96 overflow-auto py-1">
+            {results.length === 0 ? (
+              <div className="px-4 py-6 text-center text-sm text-muted-foreground">No results</div>
+            ) : (
+              results.map((result, i) => (
+                <button
+                  key={`${result.kind}-${i}`}
+                  onClick={() => pick(result)}
+                  onMouseEnter={() => setSelected(i)}
+                  className={cn(
+                    "w-full flex items-center gap-3 px-4 py-2 text-left transition-colors",
+                    i === selected ? "bg-secondary" : "hover:bg-secondary/50"
+                  )}
+                >
+                  <span className="text-muted-foreground">{KIND_ICONS[result.kind]}</span>
+                  <div className="flex-1 min-w-0">
+                    <div className="text-sm text-foreground truncate">{result.title}</div>
+                    {result.subtitle && (
+                      <div className="text-xs text-muted-foreground truncate">{result.subtitle}</div>
+                    )}
+                  </div>
+                  <span className="text-xs text-muted-foreground/70">{KIND_LABELS[result.kind]}</span>
+                </button>
+              ))
+            )}
+          </div>
+        )}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,19 @@
+import { useQuery } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import type { ClaudePaths } from "../types";
+
+/** Badge shown over every view while claudit displays generated sample data */
+export function DemoWatermark() {
+  const { data: claudePaths } = useQuery({
+    queryKey: ["claude-paths"],
+    queryFn: () => invoke<ClaudePaths>("get_claude_paths"),
+  });
+
+  if (claudePaths?.source !== "demo") return null;
+
+  return (
+    <div className="fixed bottom-4 right-4 z-[90] pointer-events-none select-none px-3 py-1.5 rounded-lg border border-amber-500/40 bg-amber-500/15 text-amber-500 text-xs font-semibold tracking-wider uppercase">
+      Demo data
+    </div>
+  );
+}
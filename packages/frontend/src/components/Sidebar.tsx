@@ -13,6 +13,9 @@ import {
   Heart,
   Github,
   Globe,
+  Cpu,
+  Star,
+  Radio,
 } from "lucide-react";
 import { open } from "@tauri-apps/plugin-shell";
 import { cn } from "../lib/utils";
@@ -26,7 +29,10 @@ export type Page =
   | "agents"
   | "plugins"
   | "projects"
+  | "live"
   | "analysis"
+  | "models"
+  | "prompts"
   | "backup";
 
 interface NavItem {
@@ -67,12 +73,30 @@ const navItems: NavItem[] = [
     icon: <FolderOpen size={20} />,
     description: "Your projects",
   },
+  {
+    id: "live",
+    label: "Live",
+    icon: <Radio size={20} />,
+    description: "Running sessions",
+  },
   {
     id: "analysis",
     label: "Chat Analysis",
     icon: <Search size={20} />,
     description: "AI insights",
   },
+  {
+    id: "models",
+    label: "Models",
+    icon: <Cpu size={20} />,
+    description: "Limits & pricing",
+  },
+  {
+    id: "prompts",
+    label: "Prompt Library",
+    icon: <Star size={20} />,
+    description: "Starred prompts",
+  },
   {
     id: "backup",
     label: "Backup & Export",
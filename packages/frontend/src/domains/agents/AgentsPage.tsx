@@ -17,6 +17,7 @@ import {
 import { MarkdownViewer } from "../../components/MarkdownViewer";
 import { PageHeader } from "../../components/PageHeader";
 import { cn } from "../../lib/utils";
+import type { JumpAction } from "../../types";
 
 interface AgentInfo {
   name: string;
@@ -40,7 +41,12 @@ interface DirectoryFile {
 
 type ItemType = "agent" | "command";
 
-export function AgentsPage() {
+interface AgentsPageProps {
+  // Agent or command picked in the command palette
+  jump?: JumpAction | null;
+}
+
+export function AgentsPage({ jump }: AgentsPageProps = {}) {
   const [activeTab, setActiveTab] = useState<ItemType>("agent");
   const [searchQuery, setSearchQuery] = useState("");
   const [selectedItem, setSelectedItem] = useState<{
@@ -51,6 +57,18 @@ export function AgentsPage() {
   } | null>(null);
   const [selectedFilePath, setSelectedFilePath] = useState<string | null>(null);
 
+  // Open whatever was picked in the command palette
+  useEffect(() => {
+    if (jump?.type !== "agent" && jump?.type !== "command") return;
+    setActiveTab(jump.type);
+    setSelectedItem({
+      type: jump.type,
+      path: jump.path,
+      name: jump.name,
+      isDirectory: jump.is_directory,
+    });
+  }, [jump]);
+
   // Fetch agents
   const {
     data: agents,
@@ -12,10 +12,17 @@ import {
   Zap,
   Play,
   AlertCircle,
+  TrendingUp,
+  ThumbsUp,
+  Clock,
+  X,
 } from "lucide-react";
 import { PageHeader } from "../../components/PageHeader";
 import { cn } from "../../lib/utils";
 import { MarkdownViewer } from "../../components/MarkdownViewer";
+import { InvocationUsage } from "./InvocationUsage";
+import { UnusedConfig } from "./UnusedConfig";
+import { ModelRouting } from "./ModelRouting";
 
 interface PatternAnalysis {
   mostUsedTools: { name: string; count: number }[];
@@ -32,11 +39,31 @@ interface PatternAnalysis {
   };
 }
 
+interface Evidence {
+  label: string;
+  current: number;
+  previous: number;
+  unit: "usd" | "percent" | "tokens";
+}
+
 interface AiSuggestion {
-  type: "agent" | "command" | "optimization";
+  id: string;
+  type: "agent" | "command" | "optimization" | "workflow" | "insight" | "info";
   title: string;
   description: string;
   content?: string;
+  score: number;
+  evidence: Evidence[];
+}
+
+function formatEvidence(value: number, unit: Evidence["unit"]) {
+  if (unit === "usd") return `$${value.toFixed(2)}`;
+  if (unit === "percent") return `${value.toFixed(0)}%`;
+  return value >= 1_000_000
+    ? `${(value / 1_000_000).toFixed(1)}M`
+    : value >= 1_000
+      ? `${(value / 1_000).toFixed(0)}K`
+      : value.toFixed(0);
 }
 
 export function AnalysisPage() {
@@ -55,10 +82,33 @@ export function AnalysisPage() {
     queryFn: () => invoke<PatternAnalysis>("analyze_chat_patterns", { days: selectedDays }),
   });
 
+  // Suggestions answered since the last run, hidden until the next one
+  const [hiddenIds, setHiddenIds] = useState<string[]>([]);
+
   // Get AI suggestions via Claude CLI
   const suggestMutation = useMutation({
     mutationFn: () => invoke<AiSuggestion[]>("get_ai_suggestions", { days: selectedDays }),
+    onSuccess: () => setHiddenIds([]),
   });
+  const visibleSuggestions =
+    suggestMutation.data?.filter((s) => !hiddenIds.includes(s.id)) ?? [];
+
+  const giveFeedback = async (
+    suggestion: AiSuggestion,
+    action: "accept" | "dismiss" | "snooze"
+  ) => {
+    await invoke("record_suggestion_feedback", {
+      suggestion,
+      action,
+      snoozeDays: action === "snooze" ? 7 : null,
+    });
+    setHiddenIds((ids) => [...ids, suggestion.id]);
+  };
+
+  const restoreDismissed = async () => {
+    await invoke("clear_suggestion_feedback");
+    suggestMutation.mutate();
+  };
 
   return (
     <div className="h-full flex flex-col">
@@ -230,6 +280,15 @@ export function AnalysisPage() {
                 )}
               </div>
 
+              {/* Slash commands and agents */}
+              <InvocationUsage days={selectedDays} />
+
+              {/* Config that wasn't used in the period */}
+              <UnusedConfig days={selectedDays} />
+
+              {/* Cheaper model: overrides for agents and commands */}
+              <ModelRouting days={selectedDays} />
+
               {/* Common Prompt Patterns */}
               <div className="p-4 rounded-lg bg-zinc-900/50 border border-zinc-800/50">
                 <h3 className="font-medium mb-3 flex items-center gap-2">
@@ -318,12 +377,20 @@ export function AnalysisPage() {
             )}
 
             {/* Suggestions */}
-            {suggestMutation.data && suggestMutation.data.length > 0 && (
+            {visibleSuggestions.length > 0 && (
               <div className="space-y-4">
-                <h3 className="font-medium text-muted-foreground">Suggestions</h3>
-                {suggestMutation.data.map((suggestion, i) => (
+                <div className="flex items-center justify-between">
+                  <h3 className="font-medium text-muted-foreground">Suggestions</h3>
+                  <button
+                    onClick={restoreDismissed}
+                    className="text-xs text-muted-foreground hover:text-foreground transition-colors"
+                  >
+                    Show dismissed again
+                  </button>
+                </div>
+                {visibleSuggestions.map((suggestion) => (
                   <div
-                    key={i}
+                    key={suggestion.id}
                     className="p-4 rounded-lg bg-zinc-900/50 border border-zinc-800/50"
                   >
                     <div className="flex items-start gap-3">
@@ -331,6 +398,8 @@ export function AnalysisPage() {
                         <Bot className="w-5 h-5 text-primary flex-shrink-0 mt-0.5" />
                       ) : suggestion.type === "command" ? (
                         <Terminal className="w-5 h-5 text-emerald-500 flex-shrink-0 mt-0.5" />
+                      ) : suggestion.type === "insight" ? (
+                        <TrendingUp className="w-5 h-5 text-sky-500 flex-shrink-0 mt-0.5" />
                       ) : (
                         <Lightbulb className="w-5 h-5 text-amber-500 flex-shrink-0 mt-0.5" />
                       )}
@@ -344,11 +413,51 @@ export function AnalysisPage() {
                         <p className="text-sm text-muted-foreground mt-1">
                           {suggestion.description}
                         </p>
+                        {suggestion.evidence.length > 0 && (
+                          <div className="mt-2 flex flex-wrap gap-2">
+                            {suggestion.evidence.map((item) => (
+                              <span
+                                key={item.label}
+                                className="text-xs px-2 py-1 bg-zinc-800/70 rounded text-muted-foreground"
+                              >
+                                {item.label}: {formatEvidence(item.previous, item.unit)} →{" "}
+                                <span className="text-foreground">
+                                  {formatEvidence(item.current, item.unit)}
+                                </span>
+                              </span>
+                            ))}
+                          </div>
+                        )}
                         {suggestion.content && (
                           <div className="mt-3">
                             <MarkdownViewer content={suggestion.content} />
                           </div>
                         )}
+                        {suggestion.type !== "info" && (
+                          <div className="mt-3 flex items-center gap-2">
+                            <button
+                              onClick={() => giveFeedback(suggestion, "accept")}
+                              className="flex items-center gap-1 px-2 py-1 text-xs bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded transition-colors"
+                            >
+                              <ThumbsUp className="w-3 h-3" />
+                              Helpful
+                            </button>
+                            <button
+                              onClick={() => giveFeedback(suggestion, "snooze")}
+                              className="flex items-center gap-1 px-2 py-1 text-xs text-muted-foreground hover:text-foreground hover:bg-secondary/50 rounded transition-colors"
+                            >
+                              <Clock className="w-3 h-3" />
+                              Snooze 7 days
+                            </button>
+                            <button
+                              onClick={() => giveFeedback(suggestion, "dismiss")}
+                              className="flex items-center gap-1 px-2 py-1 text-xs text-muted-foreground hover:text-foreground hover:bg-secondary/50 rounded transition-colors"
+                            >
+                              <X className="w-3 h-3" />
+                              Dismiss
+                            </button>
+                          </div>
+                        )}
                       </div>
                     </div>
                   </div>
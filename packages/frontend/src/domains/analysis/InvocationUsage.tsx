@@ -0,0 +1,98 @@
+import { useQuery } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { Bot, Terminal } from "lucide-react";
+import { cn } from "../../lib/utils";
+import type { InvocationReport, InvocationStats } from "../../types";
+
+function InvocationList({
+  title,
+  icon,
+  prefix,
+  items,
+}: {
+  title: string;
+  icon: React.ReactNode;
+  prefix: string;
+  items: InvocationStats[];
+}) {
+  const unused = items.filter((item) => item.count === 0).length;
+
+  return (
+    <div className="p-4 rounded-lg bg-zinc-900/50 border border-zinc-800/50">
+      <h3 className="font-medium mb-3 flex items-center gap-2">
+        {icon}
+        {title}
+        {unused > 0 && (
+          <span className="text-xs text-muted-foreground font-normal">· {unused} unused</span>
+        )}
+      </h3>
+      {items.length > 0 ? (
+        <table className="w-full text-sm">
+          <thead>
+            <tr className="text-xs text-muted-foreground text-left">
+              <th className="font-normal pb-2">Name</th>
+              <th className="font-normal pb-2 text-right">Runs</th>
+              <th className="font-normal pb-2 text-right">Sessions</th>
+              <th className="font-normal pb-2 text-right">Est. Cost</th>
+              <th className="font-normal pb-2 text-right">Last Used</th>
+            </tr>
+          </thead>
+          <tbody>
+            {items.map((item) => (
+              <tr
+                key={item.name}
+                className={cn(item.count === 0 && "text-muted-foreground/60")}
+                title={item.defined_at ?? "Built-in"}
+              >
+                <td className="py-1 font-mono">
+                  {prefix}
+                  {item.name}
+                  {!item.defined_at && (
+                    <span className="ml-2 text-xs text-muted-foreground">built-in</span>
+                  )}
+                </td>
+                <td className="py-1 text-right">{item.count}</td>
+                <td className="py-1 text-right">{item.sessions}</td>
+                <td className="py-1 text-right">
+                  {item.estimated_cost > 0 ? `$${item.estimated_cost.toFixed(2)}` : "–"}
+                </td>
+                <td className="py-1 text-right text-xs">
+                  {item.last_used ? new Date(item.last_used).toLocaleDateString() : "never"}
+                </td>
+              </tr>
+            ))}
+          </tbody>
+        </table>
+      ) : (
+        <p className="text-sm text-muted-foreground">None found</p>
+      )}
+    </div>
+  );
+}
+
+/** Slash command and agent usage, including defined ones that never ran */
+export function InvocationUsage({ days }: { days: number }) {
+  const { data: report } = useQuery({
+    queryKey: ["invocation-stats", days],
+    queryFn: () => invoke<InvocationReport>("get_invocation_stats", { days }),
+  });
+
+  if (!report) return null;
+
+  return (
+    <div className="grid grid-cols-2 gap-4">
+      <InvocationList
+        title="Slash Commands"
+        icon={<Terminal className="w-4 h-4 text-emerald-500" />}
+        prefix="/"
+        items={report.commands}
+      />
+      <InvocationList
+        title="Agents"
+        icon={<Bot className="w-4 h-4 text-primary" />}
+        prefix=""
+        items={report.agents}
+      />
+    </div>
+  );
+}
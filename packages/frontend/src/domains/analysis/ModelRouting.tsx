@@ -0,0 +1,146 @@
+import { useState } from "react";
+import { useQuery, useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { Bot, Route, Terminal } from "lucide-react";
+import type { FileOperation, ModelRoutingReport, ModelRoutingSuggestion } from "../../types";
+
+function formatCost(cost: number) {
+  return `$${cost.toFixed(2)}`;
+}
+
+function describe(suggestion: ModelRoutingSuggestion) {
+  const share = Math.round((suggestion.low_token_turns / suggestion.turns) * 100);
+  const current = suggestion.current_model ?? suggestion.observed_model;
+  return `runs on ${current} · ${share}% of ${suggestion.turns} turns are low-token (avg ${Math.round(
+    suggestion.average_output_tokens
+  )} output)`;
+}
+
+/** Agents and commands that would be cheaper with a model: override, with an apply action */
+export function ModelRouting({ days }: { days: number }) {
+  const queryClient = useQueryClient();
+  const [pending, setPending] = useState<{
+    suggestion: ModelRoutingSuggestion;
+    operations: FileOperation[];
+  } | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const { data: report } = useQuery({
+    queryKey: ["model-routing", days],
+    queryFn: () => invoke<ModelRoutingReport>("get_model_routing_suggestions", { days }),
+  });
+
+  const preview = async (suggestion: ModelRoutingSuggestion) => {
+    setError(null);
+    try {
+      const operations = await invoke<FileOperation[]>("apply_model_override", {
+        path: suggestion.path,
+        model: suggestion.suggested_model,
+        dryRun: true,
+      });
+      setPending({ suggestion, operations });
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const apply = async () => {
+    if (!pending) return;
+    try {
+      await invoke("apply_model_override", {
+        path: pending.suggestion.path,
+        model: pending.suggestion.suggested_model,
+        dryRun: false,
+      });
+      setPending(null);
+      queryClient.invalidateQueries({ queryKey: ["model-routing"] });
+      queryClient.invalidateQueries({ queryKey: ["agents"] });
+      queryClient.invalidateQueries({ queryKey: ["commands"] });
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  if (!report || report.checked === 0) return null;
+
+  return (
+    <div className="p-4 rounded-lg bg-zinc-900/50 border border-zinc-800/50">
+      <h3 className="font-medium mb-3 flex items-center gap-2">
+        <Route className="w-4 h-4 text-emerald-500" />
+        Model Routing
+        <span className="text-xs text-muted-foreground font-normal">
+          · {report.checked} agents and commands checked
+        </span>
+      </h3>
+
+      {report.suggestions.length === 0 ? (
+        <p className="text-sm text-muted-foreground">
+          No agent or command would be noticeably cheaper on a smaller model.
+        </p>
+      ) : (
+        <div className="space-y-1">
+          {report.suggestions.map((suggestion) => (
+            <div key={suggestion.path} className="flex items-center gap-3 py-1">
+              {suggestion.kind === "agent" ? (
+                <Bot className="w-4 h-4 text-muted-foreground" />
+              ) : (
+                <Terminal className="w-4 h-4 text-muted-foreground" />
+              )}
+              <div className="flex-1 min-w-0">
+                <div className="text-sm truncate">
+                  <span className="font-mono">{suggestion.name}</span>
+                  <span className="text-muted-foreground"> → model: </span>
+                  <span className="font-mono">{suggestion.suggested_model}</span>
+                </div>
+                <div className="text-xs text-muted-foreground truncate">{describe(suggestion)}</div>
+              </div>
+              <span className="text-xs text-emerald-500 whitespace-nowrap">
+                save ~{formatCost(suggestion.estimated_saving)}
+              </span>
+              <button
+                onClick={() => preview(suggestion)}
+                className="px-2 py-1 text-xs bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded transition-colors"
+              >
+                Apply
+              </button>
+            </div>
+          ))}
+        </div>
+      )}
+
+      {pending && (
+        <div className="mt-3 p-3 rounded-md bg-zinc-800/50 space-y-2">
+          <p className="text-xs text-muted-foreground">
+            Sets model: {pending.suggestion.suggested_model} in the frontmatter of{" "}
+            {pending.suggestion.name} (undo from the audit log). Its turns in this period would
+            have cost {formatCost(pending.suggestion.suggested_cost)} instead of{" "}
+            {formatCost(pending.suggestion.cost)}.
+          </p>
+          <ul className="text-xs font-mono space-y-0.5">
+            {pending.operations.map((op, i) => (
+              <li key={i} className="truncate">
+                {op.kind} {op.path}
+              </li>
+            ))}
+          </ul>
+          <div className="flex gap-2">
+            <button
+              onClick={apply}
+              className="px-3 py-1.5 bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded-lg text-sm transition-colors"
+            >
+              Apply
+            </button>
+            <button
+              onClick={() => setPending(null)}
+              className="px-3 py-1.5 text-muted-foreground hover:text-foreground rounded-lg text-sm transition-colors"
+            >
+              Cancel
+            </button>
+          </div>
+        </div>
+      )}
+
+      {error && <p className="mt-2 text-xs text-red-500">{error}</p>}
+    </div>
+  );
+}
@@ -0,0 +1,149 @@
+import { useState } from "react";
+import { useQuery, useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { Bot, PackageX, Server, Terminal } from "lucide-react";
+import type { FileOperation, UnusedConfigReport, UnusedItem } from "../../types";
+
+const KIND_ICONS: Record<UnusedItem["kind"], React.ReactNode> = {
+  mcp_server: <Server className="w-4 h-4 text-muted-foreground" />,
+  agent: <Bot className="w-4 h-4 text-muted-foreground" />,
+  command: <Terminal className="w-4 h-4 text-muted-foreground" />,
+};
+
+function describe(item: UnusedItem) {
+  const where =
+    item.kind === "mcp_server"
+      ? item.scope?.type === "project"
+        ? `MCP server · ${item.scope.path}`
+        : "MCP server · global"
+      : item.path ?? item.kind;
+  const lastUsed = item.last_used
+    ? `last used ${new Date(item.last_used).toLocaleDateString()}`
+    : "never used";
+  return `${where} · ${lastUsed}`;
+}
+
+/** MCP servers, agents and commands not used in the period, with disable actions */
+export function UnusedConfig({ days }: { days: number }) {
+  const queryClient = useQueryClient();
+  const [pending, setPending] = useState<{ ids: string[]; operations: FileOperation[] } | null>(
+    null
+  );
+  const [error, setError] = useState<string | null>(null);
+
+  const { data: report } = useQuery({
+    queryKey: ["unused-config", days],
+    queryFn: () => invoke<UnusedConfigReport>("get_unused_config", { days }),
+  });
+
+  const preview = async (ids: string[]) => {
+    setError(null);
+    try {
+      const operations = await invoke<FileOperation[]>("disable_unused_config", {
+        ids,
+        days,
+        dryRun: true,
+      });
+      setPending({ ids, operations });
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const apply = async () => {
+    if (!pending) return;
+    try {
+      await invoke("disable_unused_config", { ids: pending.ids, days, dryRun: false });
+      setPending(null);
+      queryClient.invalidateQueries({ queryKey: ["unused-config"] });
+      queryClient.invalidateQueries({ queryKey: ["mcp-servers"] });
+      queryClient.invalidateQueries({ queryKey: ["agents"] });
+      queryClient.invalidateQueries({ queryKey: ["commands"] });
+      queryClient.invalidateQueries({ queryKey: ["invocation-stats"] });
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  if (!report) return null;
+
+  return (
+    <div className="p-4 rounded-lg bg-zinc-900/50 border border-zinc-800/50">
+      <div className="flex items-center justify-between mb-3">
+        <h3 className="font-medium flex items-center gap-2">
+          <PackageX className="w-4 h-4 text-rose-500" />
+          Unused Config
+          <span className="text-xs text-muted-foreground font-normal">
+            · {report.items.length} of{" "}
+            {report.mcp_servers_checked + report.agents_checked + report.commands_checked} unused in{" "}
+            {report.days} days
+          </span>
+        </h3>
+        {report.items.length > 1 && (
+          <button
+            onClick={() => preview(report.items.map((item) => item.id))}
+            className="px-2 py-1 text-xs bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded transition-colors"
+          >
+            Disable all
+          </button>
+        )}
+      </div>
+
+      {report.items.length === 0 ? (
+        <p className="text-sm text-muted-foreground">
+          Every MCP server, agent and command was used in this period.
+        </p>
+      ) : (
+        <div className="space-y-1">
+          {report.items.map((item) => (
+            <div key={item.id} className="flex items-center gap-3 py-1">
+              {KIND_ICONS[item.kind]}
+              <div className="flex-1 min-w-0">
+                <div className="text-sm font-mono truncate">{item.name}</div>
+                <div className="text-xs text-muted-foreground truncate">{describe(item)}</div>
+              </div>
+              <button
+                onClick={() => preview([item.id])}
+                className="px-2 py-1 text-xs text-muted-foreground hover:text-foreground hover:bg-secondary/50 rounded transition-colors"
+              >
+                Disable
+              </button>
+            </div>
+          ))}
+        </div>
+      )}
+
+      {pending && (
+        <div className="mt-3 p-3 rounded-md bg-zinc-800/50 space-y-2">
+          <p className="text-xs text-muted-foreground">
+            MCP servers are removed from their config (undo from the audit log); agent and command
+            files are renamed to .md.disabled.
+          </p>
+          <ul className="text-xs font-mono space-y-0.5">
+            {pending.operations.map((op, i) => (
+              <li key={i} className="truncate">
+                {op.kind} {op.path}
+              </li>
+            ))}
+          </ul>
+          <div className="flex gap-2">
+            <button
+              onClick={apply}
+              className="px-3 py-1.5 bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded-lg text-sm transition-colors"
+            >
+              Disable
+            </button>
+            <button
+              onClick={() => setPending(null)}
+              className="px-3 py-1.5 text-muted-foreground hover:text-foreground rounded-lg text-sm transition-colors"
+            >
+              Cancel
+            </button>
+          </div>
+        </div>
+      )}
+
+      {error && <p className="mt-2 text-xs text-red-500">{error}</p>}
+    </div>
+  );
+}
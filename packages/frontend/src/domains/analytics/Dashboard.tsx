@@ -1,12 +1,17 @@
 import { useState } from "react";
-import { useStats, useChartData, useRefreshStats, useHooksStatus, useInstallHooks, useHookPort, useClaudeStatus } from "./hooks";
+import { useStats, useChartData, useErrorStats, useRefreshStats, useHooksStatus, useInstallHooks, useHookPort, useClaudeStatus } from "./hooks";
 import {
   StatCard,
   TokenChart,
   CostChart,
   ModelChart,
+  ModelMixChart,
+  ErrorChart,
   HourlyChart,
   ProjectChart,
+  ServiceTierBreakdown,
+  ModelSplit,
+  CostDrillDown,
 } from "./components";
 import {
   RefreshCw,
@@ -37,12 +42,13 @@ function formatCost(n: number): string {
   return `$${n.toFixed(3)}`;
 }
 
-type TimeRange = 7 | 30 | 90;
+type TimeRange = 7 | 30 | 90 | 365;
 
 export function Dashboard() {
   const [timeRange, setTimeRange] = useState<TimeRange>(30);
-  const { data: stats, isLoading: statsLoading } = useStats();
+  const { data: stats, isLoading: statsLoading } = useStats(timeRange);
   const { data: chartData, isLoading: chartLoading } = useChartData(timeRange);
+  const { data: errorStats } = useErrorStats(timeRange);
   const refreshMutation = useRefreshStats();
   const { data: hooksInstalled } = useHooksStatus();
   const { data: hookPort } = useHookPort();
@@ -67,7 +73,7 @@ export function Dashboard() {
         <div className="flex items-center gap-4">
           {/* Time Range Selector */}
           <div className="flex bg-secondary/50 rounded-lg p-1">
-            {([7, 30, 90] as const).map((days) => (
+            {([7, 30, 90, 365] as const).map((days) => (
               <button
                 key={days}
                 onClick={() => setTimeRange(days)}
@@ -77,7 +83,7 @@ export function Dashboard() {
                     : "text-muted-foreground hover:text-foreground"
                 }`}
               >
-                {days}d
+                {days === 365 ? "1y" : `${days}d`}
               </button>
             ))}
           </div>
@@ -206,16 +212,40 @@ export function Dashboard() {
             <div className="lg:col-span-2">
               <SkeletonChart />
             </div>
+            <div className="lg:col-span-2">
+              <SkeletonChart />
+            </div>
           </>
         ) : chartData ? (
           <>
-            <TokenChart data={chartData.daily} />
-            <CostChart data={chartData.daily} />
+            <TokenChart data={chartData.daily} bucket={chartData.bucket} />
+            <CostChart data={chartData.daily} bucket={chartData.bucket} />
             <ModelChart data={chartData.by_model} />
             <HourlyChart data={chartData.hourly} />
+            <div className="lg:col-span-2">
+              <ModelMixChart data={chartData.daily} />
+            </div>
+            {stats && Object.keys(stats.by_model).length > 1 && (
+              <div className="lg:col-span-2">
+                <ModelSplit stats={stats} />
+              </div>
+            )}
+            {errorStats && errorStats.errors > 0 && (
+              <div className="lg:col-span-2">
+                <ErrorChart data={errorStats} />
+              </div>
+            )}
             <div className="lg:col-span-2">
               <ProjectChart data={chartData.by_project} />
             </div>
+            {stats && Object.keys(stats.by_service_tier ?? {}).some((t) => t !== "standard") && (
+              <div className="lg:col-span-2">
+                <ServiceTierBreakdown data={stats.by_service_tier} />
+              </div>
+            )}
+            <div className="lg:col-span-2">
+              <CostDrillDown />
+            </div>
           </>
         ) : null}
       </div>
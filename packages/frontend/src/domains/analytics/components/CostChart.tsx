@@ -7,11 +7,12 @@ import {
   Tooltip,
   ResponsiveContainer,
 } from "recharts";
-import type { DailyStats } from "../../../types";
+import type { ChartData, DailyStats } from "../../../types";
 import { format, parseISO } from "date-fns";
 
 interface CostChartProps {
   data: DailyStats[];
+  bucket?: ChartData["bucket"];
 }
 
 function formatCost(n: number): string {
@@ -21,15 +22,23 @@ function formatCost(n: number): string {
   return `$${n.toFixed(3)}`;
 }
 
-export function CostChart({ data }: CostChartProps) {
+const BUCKET_TITLES: Record<ChartData["bucket"], string> = {
+  day: "Daily",
+  week: "Weekly",
+  month: "Monthly",
+};
+
+export function CostChart({ data, bucket = "day" }: CostChartProps) {
   const chartData = data.map((d) => ({
     ...d,
-    date: format(parseISO(d.date), "MMM d"),
+    date: format(parseISO(d.date), bucket === "month" ? "MMM yyyy" : "MMM d"),
   }));
 
   return (
     <div className="bg-card/50 backdrop-blur-sm border border-border rounded-lg p-4">
-      <h3 className="text-sm font-medium text-foreground mb-4">Daily Cost</h3>
+      <h3 className="text-sm font-medium text-foreground mb-4">
+        {BUCKET_TITLES[bucket]} Cost
+      </h3>
       <div className="h-64">
         <ResponsiveContainer width="100%" height="100%">
           <BarChart data={chartData}>
@@ -0,0 +1,182 @@
+import { useState } from "react";
+import { X } from "lucide-react";
+import { useDrillDown } from "../hooks";
+import type { DrillDimension, DrillDownFilters, DrillSort } from "../../../types";
+
+const DIMENSIONS: { value: DrillDimension; label: string }[] = [
+  { value: "project", label: "Project" },
+  { value: "model", label: "Model" },
+  { value: "day", label: "Day" },
+  { value: "hour", label: "Hour (UTC)" },
+  { value: "session", label: "Session" },
+  { value: "tool", label: "Tool" },
+  { value: "service_tier", label: "Service tier" },
+];
+
+/** Filter list each dimension narrows when drilling into a row */
+const FILTER_KEYS: Partial<Record<DrillDimension, "projects" | "models" | "sessions" | "tools">> = {
+  project: "projects",
+  model: "models",
+  session: "sessions",
+  tool: "tools",
+};
+
+const LIMIT = 50;
+
+function formatCost(n: number): string {
+  return n >= 1 ? `$${n.toFixed(2)}` : `$${n.toFixed(3)}`;
+}
+
+function formatKey(dimension: DrillDimension, key: string): string {
+  if (dimension === "project") {
+    return key.split("/").filter(Boolean).pop() ?? key;
+  }
+  if (dimension === "session") {
+    return key.slice(0, 8);
+  }
+  return key || "-";
+}
+
+export function CostDrillDown() {
+  const [rows, setRows] = useState<DrillDimension>("project");
+  const [columns, setColumns] = useState<DrillDimension | "">("model");
+  const [filters, setFilters] = useState<DrillDownFilters>({});
+  const [sort, setSort] = useState<DrillSort>({ by: "cost", ascending: false });
+  const dimensions = columns && columns !== rows ? [rows, columns] : [rows];
+  const { data, isLoading } = useDrillDown(dimensions, filters, sort, LIMIT);
+
+  // Narrow to the clicked value and group by the next dimension
+  const drillInto = (key: string) => {
+    const filterKey = FILTER_KEYS[rows];
+    let next: DrillDownFilters;
+    if (filterKey) {
+      next = { ...filters, [filterKey]: [key] };
+    } else if (rows === "day") {
+      next = { ...filters, since: key, until: key };
+    } else {
+      return;
+    }
+    const narrowed = (d: DrillDimension) => {
+      const list = FILTER_KEYS[d];
+      return d === rows || (list !== undefined && (next[list]?.length ?? 0) > 0);
+    };
+    setFilters(next);
+    setRows(columns || (DIMENSIONS.find((d) => !narrowed(d.value))?.value ?? rows));
+    setColumns("");
+  };
+
+  const activeFilters = Object.entries(filters).filter(
+    ([, value]) => value != null && (!Array.isArray(value) || value.length > 0)
+  );
+
+  const toggleSort = (by: DrillSort["by"]) =>
+    setSort(sort.by === by ? { by, ascending: !sort.ascending } : { by, ascending: false });
+
+  const header = (by: DrillSort["by"], label: string) => (
+    <th
+      onClick={() => toggleSort(by)}
+      className="text-right py-2 px-2 font-medium cursor-pointer hover:text-foreground"
+    >
+      {label}
+      {sort.by === by ? (sort.ascending ? " ↑" : " ↓") : ""}
+    </th>
+  );
+
+  return (
+    <div className="bg-card/50 backdrop-blur-sm border border-border rounded-lg p-4">
+      <div className="flex flex-wrap items-center justify-between gap-2 mb-4">
+        <h3 className="text-sm font-medium text-foreground">Cost Drill-Down</h3>
+        <div className="flex items-center gap-2 text-xs">
+          <select
+            value={rows}
+            onChange={(e) => setRows(e.target.value as DrillDimension)}
+            className="px-2 py-1 bg-secondary/50 border border-border rounded text-foreground"
+          >
+            {DIMENSIONS.map((d) => (
+              <option key={d.value} value={d.value}>
+                {d.label}
+              </option>
+            ))}
+          </select>
+          <span className="text-muted-foreground">by</span>
+          <select
+            value={columns}
+            onChange={(e) => setColumns(e.target.value as DrillDimension | "")}
+            className="px-2 py-1 bg-secondary/50 border border-border rounded text-foreground"
+          >
+            <option value="">-</option>
+            {DIMENSIONS.filter((d) => d.value !== rows).map((d) => (
+              <option key={d.value} value={d.value}>
+                {d.label}
+              </option>
+            ))}
+          </select>
+        </div>
+      </div>
+
+      {activeFilters.length > 0 && (
+        <div className="flex flex-wrap items-center gap-1 mb-3 text-xs">
+          {activeFilters.map(([name, value]) => (
+            <span key={name} className="flex items-center gap-1 px-2 py-0.5 bg-secondary rounded text-secondary-foreground">
+              {name}: {Array.isArray(value) ? value.map((v) => formatKey(name.replace(/s$/, "") as DrillDimension, v)).join(", ") : value}
+              <button
+                onClick={() => setFilters({ ...filters, [name]: Array.isArray(value) ? [] : null })}
+                className="hover:text-foreground"
+              >
+                <X size={10} />
+              </button>
+            </span>
+          ))}
+        </div>
+      )}
+
+      {isLoading ? (
+        <p className="text-xs text-muted-foreground">Loading...</p>
+      ) : data && data.rows.length > 0 ? (
+        <table className="w-full text-xs">
+          <thead>
+            <tr className="text-muted-foreground border-b border-border/50">
+              {data.dimensions.map((d) => (
+                <th key={d} className="text-left py-2 pr-2 font-medium">
+                  {DIMENSIONS.find((x) => x.value === d)?.label ?? d}
+                </th>
+              ))}
+              {header("requests", "Requests")}
+              {header("tokens", "Tokens")}
+              {header("tool_uses", "Tool uses")}
+              {header("cost", "Cost")}
+            </tr>
+          </thead>
+          <tbody>
+            {data.rows.map((row) => (
+              <tr
+                key={row.keys.join("\u0000")}
+                onClick={() => drillInto(row.keys[0])}
+                className="border-b border-border/30 last:border-0 hover:bg-secondary/30 cursor-pointer"
+              >
+                {row.keys.map((key, i) => (
+                  <td key={i} className="py-2 pr-2 text-foreground truncate max-w-[16rem]" title={key}>
+                    {formatKey(data.dimensions[i], key)}
+                  </td>
+                ))}
+                <td className="text-right py-2 px-2 text-muted-foreground">{row.requests.toLocaleString()}</td>
+                <td className="text-right py-2 px-2 text-muted-foreground">
+                  {(row.input_tokens + row.output_tokens).toLocaleString()}
+                </td>
+                <td className="text-right py-2 px-2 text-muted-foreground">{row.tool_uses.toLocaleString()}</td>
+                <td className="text-right py-2 pl-2 text-muted-foreground">{formatCost(row.cost)}</td>
+              </tr>
+            ))}
+          </tbody>
+        </table>
+      ) : (
+        <p className="text-xs text-muted-foreground">No usage matches these filters</p>
+      )}
+      {data && data.total_rows > data.rows.length && (
+        <p className="mt-2 text-xs text-muted-foreground">
+          Showing {data.rows.length} of {data.total_rows.toLocaleString()} groups
+        </p>
+      )}
+    </div>
+  );
+}
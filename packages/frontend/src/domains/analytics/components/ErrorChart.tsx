@@ -0,0 +1,78 @@
+import {
+  BarChart,
+  Bar,
+  XAxis,
+  YAxis,
+  CartesianGrid,
+  Tooltip,
+  Legend,
+  ResponsiveContainer,
+} from "recharts";
+import type { ErrorStats } from "../../../types";
+import { format, parseISO } from "date-fns";
+
+interface ErrorChartProps {
+  data: ErrorStats;
+}
+
+const SERIES = [
+  { key: "overloaded", name: "Overloaded", fill: "#f59e0b" },
+  { key: "rate_limited", name: "Rate limited", fill: "#8b5cf6" },
+  { key: "server_error", name: "Server error", fill: "#ef4444" },
+  { key: "other", name: "Other", fill: "#3b82f6" },
+];
+
+export function ErrorChart({ data }: ErrorChartProps) {
+  const chartData = data.daily.map((d) => ({
+    ...d,
+    date: format(parseISO(d.date), "MMM d"),
+  }));
+
+  return (
+    <div className="bg-card/50 backdrop-blur-sm border border-border rounded-lg p-4">
+      <div className="flex items-baseline justify-between mb-4">
+        <h3 className="text-sm font-medium text-foreground">API Errors</h3>
+        <span className="text-xs text-muted-foreground">
+          {data.errors} errors · {data.error_rate.toFixed(1)}% of requests
+        </span>
+      </div>
+      <div className="h-64">
+        <ResponsiveContainer width="100%" height="100%">
+          <BarChart data={chartData}>
+            <CartesianGrid strokeDasharray="3 3" stroke="hsl(220 10% 16%)" />
+            <XAxis
+              dataKey="date"
+              stroke="hsl(220 10% 55%)"
+              fontSize={12}
+              tickLine={false}
+            />
+            <YAxis
+              stroke="hsl(220 10% 55%)"
+              fontSize={12}
+              tickLine={false}
+              allowDecimals={false}
+            />
+            <Tooltip
+              contentStyle={{
+                backgroundColor: "hsl(220 13% 10%)",
+                border: "1px solid hsl(220 10% 16%)",
+                borderRadius: "8px",
+              }}
+              labelStyle={{ color: "hsl(220 10% 55%)" }}
+            />
+            <Legend />
+            {SERIES.map((series) => (
+              <Bar
+                key={series.key}
+                dataKey={series.key}
+                name={series.name}
+                stackId="errors"
+                fill={series.fill}
+              />
+            ))}
+          </BarChart>
+        </ResponsiveContainer>
+      </div>
+    </div>
+  );
+}
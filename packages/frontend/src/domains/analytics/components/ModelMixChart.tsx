@@ -0,0 +1,83 @@
+import {
+  BarChart,
+  Bar,
+  XAxis,
+  YAxis,
+  CartesianGrid,
+  Tooltip,
+  Legend,
+  ResponsiveContainer,
+} from "recharts";
+import type { DailyStats } from "../../../types";
+import { format, parseISO } from "date-fns";
+
+interface ModelMixChartProps {
+  data: DailyStats[];
+}
+
+const COLORS = ["#d97757", "#8b5cf6", "#f59e0b", "#10b981", "#3b82f6", "#ec4899"];
+
+function formatCost(n: number): string {
+  if (n >= 1) {
+    return `$${n.toFixed(2)}`;
+  }
+  return `$${n.toFixed(3)}`;
+}
+
+function formatModelName(name: string): string {
+  return name.replace("claude-", "").replace(/-\d{8}$/, "");
+}
+
+export function ModelMixChart({ data }: ModelMixChartProps) {
+  // Every day lists the same models in the same order, so the first day defines the series
+  const models = data[0]?.by_model.map((m) => m.model) ?? [];
+
+  const chartData = data.map((d) => ({
+    date: format(parseISO(d.date), "MMM d"),
+    ...Object.fromEntries(d.by_model.map((m) => [m.model, m.cost])),
+  }));
+
+  return (
+    <div className="bg-card/50 backdrop-blur-sm border border-border rounded-lg p-4">
+      <h3 className="text-sm font-medium text-foreground mb-4">Daily Cost by Model</h3>
+      <div className="h-64">
+        <ResponsiveContainer width="100%" height="100%">
+          <BarChart data={chartData}>
+            <CartesianGrid strokeDasharray="3 3" stroke="hsl(220 10% 16%)" />
+            <XAxis
+              dataKey="date"
+              stroke="hsl(220 10% 55%)"
+              fontSize={12}
+              tickLine={false}
+            />
+            <YAxis
+              stroke="hsl(220 10% 55%)"
+              fontSize={12}
+              tickLine={false}
+              tickFormatter={formatCost}
+            />
+            <Tooltip
+              contentStyle={{
+                backgroundColor: "hsl(220 13% 10%)",
+                border: "1px solid hsl(220 10% 16%)",
+                borderRadius: "8px",
+              }}
+              labelStyle={{ color: "hsl(220 10% 55%)" }}
+              formatter={(value: number) => formatCost(value)}
+            />
+            <Legend formatter={(value: string) => formatModelName(value)} />
+            {models.map((model, index) => (
+              <Bar
+                key={model}
+                dataKey={model}
+                name={model}
+                stackId="models"
+                fill={COLORS[index % COLORS.length]}
+              />
+            ))}
+          </BarChart>
+        </ResponsiveContainer>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,52 @@
+import type { AnalyticsStats } from "../../../types";
+
+interface ModelSplitProps {
+  stats: AnalyticsStats;
+}
+
+function formatCost(n: number): string {
+  return n >= 1 ? `$${n.toFixed(2)}` : `$${n.toFixed(3)}`;
+}
+
+/** Cost per model today, in the selected range and overall */
+export function ModelSplit({ stats }: ModelSplitProps) {
+  const models = Object.entries(stats.by_model)
+    .filter(([model]) => stats.today_by_model[model] || stats.range_by_model?.[model])
+    .sort(
+      ([a, x], [b, y]) =>
+        (stats.today_by_model[b]?.cost ?? 0) - (stats.today_by_model[a]?.cost ?? 0) ||
+        (stats.range_by_model?.[b]?.cost ?? 0) - (stats.range_by_model?.[a]?.cost ?? 0) ||
+        y.cost - x.cost
+    );
+  const rangeLabel = stats.range_days === 365 ? "Last year" : `Last ${stats.range_days ?? 30} days`;
+
+  return (
+    <div className="bg-card/50 backdrop-blur-sm border border-border rounded-lg p-4">
+      <h3 className="text-sm font-medium text-foreground mb-4">Cost by Model</h3>
+      <table className="w-full text-xs">
+        <thead>
+          <tr className="text-muted-foreground border-b border-border/50">
+            <th className="text-left py-2 pr-2 font-medium">Model</th>
+            <th className="text-right py-2 px-2 font-medium">Today</th>
+            <th className="text-right py-2 px-2 font-medium">{rangeLabel}</th>
+            <th className="text-right py-2 pl-2 font-medium">Total</th>
+          </tr>
+        </thead>
+        <tbody>
+          {models.map(([model, total]) => (
+            <tr key={model} className="border-b border-border/30 last:border-0">
+              <td className="py-2 pr-2 text-foreground">{model}</td>
+              <td className="text-right py-2 px-2 text-foreground">
+                {formatCost(stats.today_by_model[model]?.cost ?? 0)}
+              </td>
+              <td className="text-right py-2 px-2 text-muted-foreground">
+                {formatCost(stats.range_by_model?.[model]?.cost ?? 0)}
+              </td>
+              <td className="text-right py-2 pl-2 text-muted-foreground">{formatCost(total.cost)}</td>
+            </tr>
+          ))}
+        </tbody>
+      </table>
+    </div>
+  );
+}
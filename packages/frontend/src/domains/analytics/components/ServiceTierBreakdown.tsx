@@ -0,0 +1,51 @@
+import type { ServiceTierStats } from "../../../types";
+
+interface ServiceTierBreakdownProps {
+  data: Record<string, ServiceTierStats>;
+}
+
+const TIER_LABELS: Record<string, string> = {
+  standard: "Standard",
+  priority: "Priority",
+  batch: "Batch (50% off)",
+};
+
+function formatCost(n: number): string {
+  return n >= 1 ? `$${n.toFixed(2)}` : `$${n.toFixed(3)}`;
+}
+
+export function ServiceTierBreakdown({ data }: ServiceTierBreakdownProps) {
+  const tiers = Object.entries(data).sort(([, a], [, b]) => b.cost - a.cost);
+  const savings = tiers.reduce((sum, [, t]) => sum + (t.standard_cost - t.cost), 0);
+
+  return (
+    <div className="bg-card/50 backdrop-blur-sm border border-border rounded-lg p-4">
+      <div className="flex items-baseline justify-between mb-4">
+        <h3 className="text-sm font-medium text-foreground">Service Tiers</h3>
+        {savings > 0 && (
+          <span className="text-xs text-muted-foreground">{formatCost(savings)} saved vs. standard rates</span>
+        )}
+      </div>
+      <table className="w-full text-xs">
+        <thead>
+          <tr className="text-muted-foreground border-b border-border/50">
+            <th className="text-left py-2 pr-2 font-medium">Tier</th>
+            <th className="text-right py-2 px-2 font-medium">Requests</th>
+            <th className="text-right py-2 px-2 font-medium">Cost</th>
+            <th className="text-right py-2 pl-2 font-medium">At standard rates</th>
+          </tr>
+        </thead>
+        <tbody>
+          {tiers.map(([tier, stats]) => (
+            <tr key={tier} className="border-b border-border/30 last:border-0">
+              <td className="py-2 pr-2 text-foreground">{TIER_LABELS[tier] ?? tier}</td>
+              <td className="text-right py-2 px-2 text-muted-foreground">{stats.requests.toLocaleString()}</td>
+              <td className="text-right py-2 px-2 text-muted-foreground">{formatCost(stats.cost)}</td>
+              <td className="text-right py-2 pl-2 text-muted-foreground">{formatCost(stats.standard_cost)}</td>
+            </tr>
+          ))}
+        </tbody>
+      </table>
+    </div>
+  );
+}
@@ -7,11 +7,12 @@ import {
   Tooltip,
   ResponsiveContainer,
 } from "recharts";
-import type { DailyStats } from "../../../types";
+import type { ChartData, DailyStats } from "../../../types";
 import { format, parseISO } from "date-fns";
 
 interface TokenChartProps {
   data: DailyStats[];
+  bucket?: ChartData["bucket"];
 }
 
 function formatNumber(n: number): string {
@@ -24,16 +25,24 @@ function formatNumber(n: number): string {
   return n.toString();
 }
 
-export function TokenChart({ data }: TokenChartProps) {
+const BUCKET_TITLES: Record<ChartData["bucket"], string> = {
+  day: "Daily",
+  week: "Weekly",
+  month: "Monthly",
+};
+
+export function TokenChart({ data, bucket = "day" }: TokenChartProps) {
   const chartData = data.map((d) => ({
     ...d,
-    date: format(parseISO(d.date), "MMM d"),
+    date: format(parseISO(d.date), bucket === "month" ? "MMM yyyy" : "MMM d"),
     total: d.input_tokens + d.output_tokens,
   }));
 
   return (
     <div className="bg-card/50 backdrop-blur-sm border border-border rounded-lg p-4">
-      <h3 className="text-sm font-medium text-foreground mb-4">Daily Token Usage</h3>
+      <h3 className="text-sm font-medium text-foreground mb-4">
+        {BUCKET_TITLES[bucket]} Token Usage
+      </h3>
       <div className="h-64">
         <ResponsiveContainer width="100%" height="100%">
           <AreaChart data={chartData}>
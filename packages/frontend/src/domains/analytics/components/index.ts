@@ -2,5 +2,10 @@ export { StatCard } from "./StatCard";
 export { TokenChart } from "./TokenChart";
 export { CostChart } from "./CostChart";
 export { ModelChart } from "./ModelChart";
+export { ModelMixChart } from "./ModelMixChart";
 export { HourlyChart } from "./HourlyChart";
 export { ProjectChart } from "./ProjectChart";
+export { ErrorChart } from "./ErrorChart";
+export { ServiceTierBreakdown } from "./ServiceTierBreakdown";
+export { ModelSplit } from "./ModelSplit";
+export { CostDrillDown } from "./CostDrillDown";
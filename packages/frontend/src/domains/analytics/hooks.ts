@@ -1,19 +1,54 @@
 import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
 import { invoke } from "@tauri-apps/api/core";
-import type { AnalyticsStats, ChartData, AppSettings, ModelPricing, ClaudeStatus } from "../../types";
+import type {
+  AnalyticsStats,
+  ChartData,
+  AppSettings,
+  ModelPricing,
+  ModelInfo,
+  ClaudeStatus,
+  ErrorStats,
+  DrillDimension,
+  DrillDownFilters,
+  DrillDownResult,
+  DrillSort,
+} from "../../types";
 
-export function useStats() {
+// With `days`, range_by_model covers the last `days` days
+export function useStats(days?: number) {
   return useQuery({
-    queryKey: ["stats"],
-    queryFn: () => invoke<AnalyticsStats>("get_stats"),
+    queryKey: days ? ["stats", days] : ["stats"],
+    queryFn: () => invoke<AnalyticsStats>("get_stats", { days: days ?? null }),
+    // Keep showing the last stats while another range loads
+    placeholderData: (previous) => previous,
     refetchInterval: 30000, // Refresh every 30 seconds
   });
 }
 
+// Long ranges come back in weekly or monthly buckets
 export function useChartData(days: number = 30) {
   return useQuery({
     queryKey: ["chart-data", days],
-    queryFn: () => invoke<ChartData>("get_chart_data", { days }),
+    queryFn: () => invoke<ChartData>("get_chart_data", { days, bucket: "auto" }),
+  });
+}
+
+export function useDrillDown(
+  dimensions: DrillDimension[],
+  filters: DrillDownFilters = {},
+  sort?: DrillSort,
+  limit?: number
+) {
+  return useQuery({
+    queryKey: ["drill-down", dimensions, filters, sort, limit],
+    queryFn: () => invoke<DrillDownResult>("drill_down", { dimensions, filters, sort, limit }),
+  });
+}
+
+export function useErrorStats(days: number = 30) {
+  return useQuery({
+    queryKey: ["error-stats", days],
+    queryFn: () => invoke<ErrorStats>("get_error_stats", { days }),
   });
 }
 
@@ -24,6 +59,8 @@ export function useRefreshStats() {
     mutationFn: () => invoke<AnalyticsStats>("refresh_stats"),
     onSuccess: (data) => {
       queryClient.setQueryData(["stats"], data);
+      // Range splits aren't part of the refreshed stats
+      queryClient.invalidateQueries({ predicate: (q) => q.queryKey[0] === "stats" && q.queryKey.length > 1 });
     },
   });
 }
@@ -43,6 +80,7 @@ export function useUpdateSettings() {
       invoke<void>("update_settings", { settings }),
     onSuccess: () => {
       queryClient.invalidateQueries({ queryKey: ["settings"] });
+      queryClient.invalidateQueries({ queryKey: ["model-catalog"] });
       // Refresh tray menu in background (don't await)
       invoke<void>("refresh_tray_menu").catch(console.error);
     },
@@ -82,6 +120,21 @@ export function useModelPricing() {
   });
 }
 
+export function usePricingHistory() {
+  return useQuery({
+    queryKey: ["pricing-history"],
+    queryFn: () => invoke<ModelPricing[]>("get_pricing_history"),
+    staleTime: Infinity,
+  });
+}
+
+export function useModelCatalog() {
+  return useQuery({
+    queryKey: ["model-catalog"],
+    queryFn: () => invoke<ModelInfo[]>("get_model_catalog"),
+  });
+}
+
 export function useClaudeStatus() {
   return useQuery({
     queryKey: ["claude-status"],
@@ -1,7 +1,6 @@
 import { useState } from "react";
-import { useMutation, useQuery } from "@tanstack/react-query";
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
 import { invoke } from "@tauri-apps/api/core";
-import { open, save } from "@tauri-apps/plugin-dialog";
 import {
   Download,
   Upload,
@@ -16,9 +15,16 @@ import {
   Plug,
   Settings,
   GitBranch,
+  Database,
 } from "lucide-react";
 import { PageHeader } from "../../components/PageHeader";
 import { cn } from "../../lib/utils";
+import type { AppDataSummary, ConfigImport } from "../../types";
+import { ConfigSync } from "./ConfigSync";
+import { RestorePoints } from "./RestorePoints";
+import { SessionCollections } from "./SessionCollections";
+import { TeamPack } from "./TeamPack";
+import { UsageReports } from "./UsageReports";
 
 interface ExportOptions {
   includeGlobalClaude: boolean;
@@ -40,9 +46,13 @@ interface GitStatus {
   hasChanges: boolean;
   branch: string;
   uncommittedFiles: string[];
+  upstream: string | null;
+  ahead: number;
+  behind: number;
 }
 
 export function BackupPage() {
+  const queryClient = useQueryClient();
   const [exportOptions, setExportOptions] = useState<ExportOptions>({
     includeGlobalClaude: true,
     includeProjectClaude: true,
@@ -58,10 +68,35 @@ export function BackupPage() {
     queryFn: () => invoke<GitStatus>("get_config_git_status"),
   });
 
+  // Version the config folder with git
+  const [commitMessage, setCommitMessage] = useState("");
+  const refreshGitStatus = () => queryClient.invalidateQueries({ queryKey: ["config-git-status"] });
+
+  const initRepoMutation = useMutation({
+    mutationFn: () => invoke<GitStatus>("init_config_repo"),
+    onSuccess: refreshGitStatus,
+  });
+
+  const commitMutation = useMutation({
+    mutationFn: () => invoke<boolean>("commit_config_changes", { message: commitMessage }),
+    onSuccess: () => {
+      setCommitMessage("");
+      refreshGitStatus();
+    },
+  });
+
+  const pushMutation = useMutation({
+    mutationFn: () => invoke<GitStatus>("push_config_changes"),
+    onSuccess: refreshGitStatus,
+  });
+
+  const gitError = initRepoMutation.error ?? commitMutation.error ?? pushMutation.error;
+
   // Export config
   const exportMutation = useMutation({
     mutationFn: async () => {
-      const savePath = await save({
+      const savePath = await invoke<string | null>("pick_path", {
+        mode: "save",
         defaultPath: `claude-config-backup-${new Date().toISOString().split("T")[0]}.zip`,
         filters: [{ name: "ZIP Archive", extensions: ["zip"] }],
       });
@@ -73,15 +108,59 @@ export function BackupPage() {
     },
   });
 
-  // Import config
-  const importMutation = useMutation({
+  // Import config: preview the writes first, then apply them
+  const [importPreview, setImportPreview] = useState<{ path: string; plan: ConfigImport } | null>(
+    null
+  );
+
+  const previewImportMutation = useMutation({
     mutationFn: async () => {
-      const filePath = await open({
+      const filePath = await invoke<string | null>("pick_path", {
+        mode: "file",
         filters: [{ name: "ZIP Archive", extensions: ["zip"] }],
-        multiple: false,
       });
       if (!filePath) throw new Error("No file selected");
-      return invoke<void>("import_config", { zipPath: filePath });
+      const plan = await invoke<ConfigImport>("import_config", { zipPath: filePath, dryRun: true });
+      return { path: filePath, plan };
+    },
+    onSuccess: (preview) => {
+      setImportPreview(preview);
+      importMutation.reset();
+    },
+  });
+
+  const importMutation = useMutation({
+    mutationFn: (path: string) => invoke<ConfigImport>("import_config", { zipPath: path }),
+    onSuccess: () => {
+      setImportPreview(null);
+      queryClient.invalidateQueries({ queryKey: ["restore-points"] });
+    },
+  });
+
+  // Claudit's own settings and data
+  const exportAppDataMutation = useMutation({
+    mutationFn: async () => {
+      const savePath = await invoke<string | null>("pick_path", {
+        mode: "save",
+        defaultPath: `claudit-data-${new Date().toISOString().split("T")[0]}.json`,
+        filters: [{ name: "JSON", extensions: ["json"] }],
+      });
+      if (!savePath) throw new Error("No save path selected");
+      return invoke<AppDataSummary>("export_app_data", { path: savePath });
+    },
+  });
+
+  const importAppDataMutation = useMutation({
+    mutationFn: async () => {
+      const filePath = await invoke<string | null>("pick_path", {
+        mode: "file",
+        filters: [{ name: "JSON", extensions: ["json"] }],
+      });
+      if (!filePath) throw new Error("No file selected");
+      return invoke<AppDataSummary>("import_app_data", { path: filePath });
+    },
+    onSuccess: () => {
+      queryClient.invalidateQueries();
     },
   });
 
@@ -156,18 +235,89 @@ export function BackupPage() {
             <div className="p-4 rounded-lg bg-zinc-900/50 border border-zinc-800/50">
               <div className="flex items-center gap-2 mb-2">
                 <GitBranch className="w-4 h-4 text-emerald-500" />
-                <span className="font-medium">Git Repository Detected</span>
+                <span className="font-medium">Git Repository</span>
+                <span className="text-sm text-muted-foreground">{gitStatus.branch || "no branch"}</span>
+                {gitStatus.upstream && (
+                  <span className="text-xs text-muted-foreground ml-auto">
+                    {gitStatus.upstream} · {gitStatus.ahead} ahead · {gitStatus.behind} behind
+                  </span>
+                )}
               </div>
-              <div className="text-sm text-muted-foreground">
-                <p>Branch: {gitStatus.branch}</p>
-                {gitStatus.hasChanges && (
-                  <p className="text-amber-500 mt-1">
-                    {gitStatus.uncommittedFiles.length} uncommitted changes
+              {gitStatus.hasChanges ? (
+                <>
+                  <p className="text-sm text-amber-500 mb-1">
+                    {gitStatus.uncommittedFiles.length} uncommitted change
+                    {gitStatus.uncommittedFiles.length === 1 ? "" : "s"}
                   </p>
-                )}
+                  <ul className="text-xs text-muted-foreground font-mono mb-3 max-h-24 overflow-y-auto">
+                    {gitStatus.uncommittedFiles.map((file) => (
+                      <li key={file} className="truncate">
+                        {file}
+                      </li>
+                    ))}
+                  </ul>
+                  <div className="flex gap-2">
+                    <input
+                      value={commitMessage}
+                      onChange={(e) => setCommitMessage(e.target.value)}
+                      placeholder="Update Claude config"
+                      className="flex-1 px-3 py-1.5 text-sm bg-secondary/50 border border-border rounded-lg"
+                    />
+                    <button
+                      onClick={() => commitMutation.mutate()}
+                      disabled={commitMutation.isPending}
+                      className="px-3 py-1.5 text-sm bg-primary hover:bg-primary/90 text-primary-foreground rounded-lg transition-colors disabled:opacity-50"
+                    >
+                      Commit
+                    </button>
+                  </div>
+                </>
+              ) : (
+                <p className="text-sm text-muted-foreground">No uncommitted changes</p>
+              )}
+              {(gitStatus.ahead > 0 || !gitStatus.upstream) && (
+                <button
+                  onClick={() => pushMutation.mutate()}
+                  disabled={pushMutation.isPending}
+                  className="mt-3 flex items-center gap-2 px-3 py-1.5 text-sm bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded-lg transition-colors disabled:opacity-50"
+                >
+                  <Upload className="w-4 h-4" />
+                  {pushMutation.isPending ? "Pushing..." : "Push"}
+                </button>
+              )}
+              {gitError && (
+                <div className="mt-3 flex items-center gap-2 text-red-500 text-sm">
+                  <AlertCircle className="w-4 h-4" />
+                  <span>{String(gitError)}</span>
+                </div>
+              )}
+            </div>
+          ) : (
+            <div className="p-4 rounded-lg bg-zinc-900/50 border border-zinc-800/50">
+              <div className="flex items-center gap-2">
+                <GitBranch className="w-4 h-4 text-muted-foreground" />
+                <span className="text-sm text-muted-foreground flex-1">
+                  Track changes to your Claude config with git (session logs and credentials are left
+                  out)
+                </span>
+                <button
+                  onClick={() => initRepoMutation.mutate()}
+                  disabled={initRepoMutation.isPending}
+                  className="px-3 py-1.5 text-sm bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded-lg transition-colors disabled:opacity-50"
+                >
+                  Initialize Repository
+                </button>
               </div>
+              {gitError && (
+                <div className="mt-3 flex items-center gap-2 text-red-500 text-sm">
+                  <AlertCircle className="w-4 h-4" />
+                  <span>{String(gitError)}</span>
+                </div>
+              )}
             </div>
-          ) : null}
+          )}
+
+          <ConfigSync />
 
           {/* Export Section */}
           <div className="p-6 rounded-lg bg-zinc-900/50 border border-zinc-800/50">
@@ -265,7 +415,7 @@ export function BackupPage() {
               <div className="mt-3 p-3 rounded-lg bg-red-500/10 border border-red-500/20">
                 <div className="flex items-center gap-2 text-red-500 text-sm">
                   <AlertCircle className="w-4 h-4" />
-                  <span>Export failed. Please try again.</span>
+                  <span>{String(exportMutation.error)}</span>
                 </div>
               </div>
             )}
@@ -290,48 +440,163 @@ export function BackupPage() {
                 <Upload className="w-8 h-8 mx-auto mb-2 opacity-50" />
                 <p className="text-sm">Select a ZIP backup file to import</p>
                 <p className="text-xs mt-1">
-                  Existing files will be backed up before overwriting
+                  You'll see every file it changes first; replaced files go to a restore point
                 </p>
               </div>
             </div>
 
             <button
-              onClick={() => importMutation.mutate()}
-              disabled={importMutation.isPending}
+              onClick={() => previewImportMutation.mutate()}
+              disabled={previewImportMutation.isPending || importMutation.isPending}
               className={cn(
                 "w-full flex items-center justify-center gap-2 px-4 py-2.5 rounded-lg text-sm font-medium transition-colors",
-                importMutation.isPending
+                previewImportMutation.isPending || importMutation.isPending
                   ? "bg-zinc-700 cursor-not-allowed text-muted-foreground"
                   : "bg-blue-600 text-white hover:bg-blue-500"
               )}
             >
-              {importMutation.isPending ? (
+              {previewImportMutation.isPending ? (
                 <>
                   <RefreshCw className="w-4 h-4 animate-spin" />
-                  Importing...
+                  Checking backup...
                 </>
               ) : (
                 <>
                   <Upload className="w-4 h-4" />
-                  Select & Import
+                  Select Backup
                 </>
               )}
             </button>
 
+            {importPreview && (
+              <div className="mt-3 p-3 rounded-lg bg-zinc-800/30 border border-zinc-700/50 text-sm">
+                <p className="mb-2">
+                  Backup from {new Date(importPreview.plan.created_at).toLocaleString()}
+                </p>
+                {importPreview.plan.operations.length === 0 ? (
+                  <p className="text-xs text-muted-foreground">
+                    Everything in this backup is already up to date.
+                  </p>
+                ) : (
+                  <ul className="space-y-1 mb-3 max-h-48 overflow-y-auto">
+                    {importPreview.plan.operations.map((op) => (
+                      <li key={op.kind + op.path} className="text-xs">
+                        <span className="text-foreground">{op.description}</span>
+                        <span className="text-muted-foreground font-mono ml-2">{op.path}</span>
+                      </li>
+                    ))}
+                  </ul>
+                )}
+                {importPreview.plan.skipped.map((reason) => (
+                  <p key={reason} className="text-xs text-amber-500">
+                    Skipped {reason}
+                  </p>
+                ))}
+                <div className="flex gap-2 mt-3">
+                  <button
+                    onClick={() => importMutation.mutate(importPreview.path)}
+                    disabled={importMutation.isPending || importPreview.plan.operations.length === 0}
+                    className="px-3 py-1.5 bg-blue-600 hover:bg-blue-500 text-white rounded-lg text-sm transition-colors disabled:opacity-50"
+                  >
+                    {importMutation.isPending ? "Importing..." : "Import"}
+                  </button>
+                  <button
+                    onClick={() => setImportPreview(null)}
+                    className="px-3 py-1.5 bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded-lg text-sm transition-colors"
+                  >
+                    Cancel
+                  </button>
+                </div>
+              </div>
+            )}
+
             {importMutation.isSuccess && (
               <div className="mt-3 p-3 rounded-lg bg-emerald-500/10 border border-emerald-500/20">
                 <div className="flex items-center gap-2 text-emerald-500 text-sm">
                   <Check className="w-4 h-4" />
-                  <span>Configuration imported successfully!</span>
+                  <span>
+                    Imported {importMutation.data.operations.filter((op) => op.kind === "write").length}{" "}
+                    files. Replaced files can be rolled back under Restore Points.
+                  </span>
+                </div>
+              </div>
+            )}
+
+            {(previewImportMutation.error || importMutation.error) && (
+              <div className="mt-3 p-3 rounded-lg bg-red-500/10 border border-red-500/20">
+                <div className="flex items-center gap-2 text-red-500 text-sm">
+                  <AlertCircle className="w-4 h-4" />
+                  <span>{String(previewImportMutation.error ?? importMutation.error)}</span>
+                </div>
+              </div>
+            )}
+          </div>
+
+          <TeamPack />
+
+          <RestorePoints />
+
+          <UsageReports />
+
+          <SessionCollections />
+
+          {/* Claudit Data Section */}
+          <div className="p-6 rounded-lg bg-zinc-900/50 border border-zinc-800/50">
+            <div className="flex items-center gap-3 mb-4">
+              <div className="w-10 h-10 rounded-lg bg-emerald-500/10 flex items-center justify-center">
+                <Database className="w-5 h-5 text-emerald-500" />
+              </div>
+              <div>
+                <h3 className="font-semibold">Claudit Data</h3>
+                <p className="text-sm text-muted-foreground">
+                  Move Claudit's own preferences, goals, profiles and notification rules to another machine
+                </p>
+              </div>
+            </div>
+
+            <div className="flex gap-2">
+              <button
+                onClick={() => exportAppDataMutation.mutate()}
+                disabled={exportAppDataMutation.isPending}
+                className="flex-1 flex items-center justify-center gap-2 px-4 py-2.5 rounded-lg text-sm font-medium bg-secondary hover:bg-secondary/80 transition-colors disabled:opacity-50"
+              >
+                <Download className="w-4 h-4" />
+                Export Data
+              </button>
+              <button
+                onClick={() => importAppDataMutation.mutate()}
+                disabled={importAppDataMutation.isPending}
+                className="flex-1 flex items-center justify-center gap-2 px-4 py-2.5 rounded-lg text-sm font-medium bg-secondary hover:bg-secondary/80 transition-colors disabled:opacity-50"
+              >
+                <Upload className="w-4 h-4" />
+                Import Data
+              </button>
+            </div>
+
+            {(exportAppDataMutation.data ?? importAppDataMutation.data) && (
+              <div className="mt-3 p-3 rounded-lg bg-emerald-500/10 border border-emerald-500/20 text-sm">
+                <div className="flex items-center gap-2 text-emerald-500">
+                  <Check className="w-4 h-4" />
+                  <span>
+                    {importAppDataMutation.data ? "Imported" : "Exported"}:{" "}
+                    {(importAppDataMutation.data ?? exportAppDataMutation.data)!.items.join(", ")}
+                  </span>
                 </div>
+                {importAppDataMutation.data?.backup_path && (
+                  <p className="text-xs text-muted-foreground mt-1">
+                    Previous data saved to {importAppDataMutation.data.backup_path}
+                  </p>
+                )}
               </div>
             )}
 
-            {importMutation.isError && (
+            {(exportAppDataMutation.isError || importAppDataMutation.isError) && (
               <div className="mt-3 p-3 rounded-lg bg-red-500/10 border border-red-500/20">
                 <div className="flex items-center gap-2 text-red-500 text-sm">
                   <AlertCircle className="w-4 h-4" />
-                  <span>Import failed. Please check the file and try again.</span>
+                  <span>
+                    {String(exportAppDataMutation.error ?? importAppDataMutation.error)}
+                  </span>
                 </div>
               </div>
             )}
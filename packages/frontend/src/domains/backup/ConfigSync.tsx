@@ -0,0 +1,234 @@
+import { useEffect, useState } from "react";
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { AlertCircle, Check, Github, RefreshCw, Upload, Download } from "lucide-react";
+import { useSettings, useUpdateSettings } from "../analytics/hooks";
+import type { ConfigSyncResult, ConfigSyncSettings, ConfigSyncStatus } from "../../types";
+
+const DEFAULT_SYNC: ConfigSyncSettings = {
+  remote: "",
+  branch: "main",
+  auto_commit_minutes: 0,
+  auto_push: false,
+};
+
+/** Push/pull ~/.claude to a GitHub repository */
+export function ConfigSync() {
+  const queryClient = useQueryClient();
+  const { data: settings } = useSettings();
+  const updateSettings = useUpdateSettings();
+  const [draft, setDraft] = useState<ConfigSyncSettings | null>(null);
+  const [token, setToken] = useState("");
+  const [autoError, setAutoError] = useState<string | null>(null);
+  const sync = draft ?? settings?.config_sync ?? DEFAULT_SYNC;
+
+  const { data: status } = useQuery({
+    queryKey: ["config-sync-status"],
+    queryFn: () => invoke<ConfigSyncStatus>("get_config_sync_status"),
+  });
+
+  const refreshStatus = () => {
+    queryClient.invalidateQueries({ queryKey: ["config-sync-status"] });
+    queryClient.invalidateQueries({ queryKey: ["config-git-status"] });
+  };
+
+  // Scheduled auto-commits report failures from the backend
+  useEffect(() => {
+    const unlisten = listen<string>("config-sync-error", (event) => {
+      setAutoError(event.payload);
+      refreshStatus();
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const saveSettings = () => {
+    if (!settings) return;
+    updateSettings.mutate(
+      { ...settings, config_sync: sync.remote.trim() ? sync : null },
+      { onSuccess: () => setDraft(null) }
+    );
+  };
+
+  const tokenMutation = useMutation({
+    mutationFn: (value: string) => invoke<void>("set_github_token", { token: value }),
+    onSuccess: () => {
+      setToken("");
+      refreshStatus();
+    },
+  });
+
+  const pushMutation = useMutation({
+    mutationFn: () => invoke<ConfigSyncResult>("push_config", { message: null }),
+    onMutate: () => setAutoError(null),
+    onSettled: refreshStatus,
+  });
+
+  const pullMutation = useMutation({
+    mutationFn: (resolution: "keep_mine" | "take_theirs" | null) =>
+      invoke<ConfigSyncResult>("pull_config", { resolution }),
+    onSettled: refreshStatus,
+  });
+
+  const edit = (changes: Partial<ConfigSyncSettings>) => setDraft({ ...sync, ...changes });
+  const conflicts = pullMutation.data?.conflicts ?? [];
+  const error =
+    updateSettings.error ?? tokenMutation.error ?? pushMutation.error ?? pullMutation.error ?? autoError;
+  const busy = pushMutation.isPending || pullMutation.isPending;
+
+  return (
+    <div className="p-6 rounded-lg bg-zinc-900/50 border border-zinc-800/50">
+      <div className="flex items-center gap-3 mb-4">
+        <div className="w-10 h-10 rounded-lg bg-zinc-500/10 flex items-center justify-center">
+          <Github className="w-5 h-5" />
+        </div>
+        <div>
+          <h3 className="font-semibold">GitHub Sync</h3>
+          <p className="text-sm text-muted-foreground">
+            Keep CLAUDE.md, settings, agents, commands and skills in a GitHub repository. Session
+            logs and credentials are never committed.
+          </p>
+        </div>
+      </div>
+
+      <div className="space-y-2 text-sm">
+        <div className="flex items-center gap-2">
+          <input
+            value={sync.remote}
+            onChange={(e) => edit({ remote: e.target.value })}
+            placeholder="owner/repo or https://github.com/owner/repo.git"
+            className="flex-1 px-2 py-1.5 bg-secondary/50 border border-border rounded"
+          />
+          <input
+            value={sync.branch}
+            onChange={(e) => edit({ branch: e.target.value })}
+            placeholder="Branch"
+            className="w-24 px-2 py-1.5 bg-secondary/50 border border-border rounded"
+          />
+        </div>
+        <div className="flex items-center gap-2">
+          <select
+            value={sync.auto_commit_minutes}
+            onChange={(e) => edit({ auto_commit_minutes: Number(e.target.value) })}
+            className="px-2 py-1.5 bg-secondary/50 border border-border rounded"
+          >
+            <option value={0}>Commit only when pushing</option>
+            <option value={15}>Auto-commit every 15 minutes</option>
+            <option value={60}>Auto-commit every hour</option>
+            <option value={1440}>Auto-commit daily</option>
+          </select>
+          <label className="flex items-center gap-1 text-xs text-muted-foreground">
+            <input
+              type="checkbox"
+              checked={sync.auto_push}
+              disabled={sync.auto_commit_minutes === 0}
+              onChange={(e) => edit({ auto_push: e.target.checked })}
+            />
+            Push after auto-commit
+          </label>
+          {draft && (
+            <button
+              onClick={saveSettings}
+              className="ml-auto px-2 py-1 text-xs bg-primary text-primary-foreground rounded transition-colors"
+            >
+              Save
+            </button>
+          )}
+        </div>
+        <div className="flex items-center gap-2">
+          <input
+            type="password"
+            value={token}
+            onChange={(e) => setToken(e.target.value)}
+            placeholder={status?.has_token ? "Token stored in keychain" : "GitHub token (repo scope)"}
+            className="flex-1 px-2 py-1.5 bg-secondary/50 border border-border rounded"
+          />
+          <button
+            onClick={() => tokenMutation.mutate(token)}
+            disabled={!token.trim()}
+            className="px-2 py-1 text-xs bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded transition-colors disabled:opacity-50"
+          >
+            Save token
+          </button>
+          {status?.has_token && (
+            <button
+              onClick={() => tokenMutation.mutate("")}
+              className="px-2 py-1 text-xs text-muted-foreground hover:text-foreground rounded transition-colors"
+            >
+              Remove
+            </button>
+          )}
+        </div>
+      </div>
+
+      {status?.is_repo && (
+        <p className="mt-3 text-xs text-muted-foreground">
+          {status.branch} · {status.changed_files.length} changed
+          {status.ahead > 0 && ` · ${status.ahead} to push`}
+          {status.behind > 0 && ` · ${status.behind} to pull`}
+          {status.last_commit_at && ` · last commit ${new Date(status.last_commit_at).toLocaleString()}`}
+        </p>
+      )}
+
+      <div className="flex gap-2 mt-3">
+        <button
+          onClick={() => pushMutation.mutate()}
+          disabled={busy || !status?.configured}
+          className="flex-1 flex items-center justify-center gap-2 px-4 py-2 rounded-lg text-sm font-medium bg-secondary hover:bg-secondary/80 transition-colors disabled:opacity-50"
+        >
+          {pushMutation.isPending ? <RefreshCw className="w-4 h-4 animate-spin" /> : <Upload className="w-4 h-4" />}
+          Push
+        </button>
+        <button
+          onClick={() => pullMutation.mutate(null)}
+          disabled={busy || !status?.configured}
+          className="flex-1 flex items-center justify-center gap-2 px-4 py-2 rounded-lg text-sm font-medium bg-secondary hover:bg-secondary/80 transition-colors disabled:opacity-50"
+        >
+          {pullMutation.isPending ? <RefreshCw className="w-4 h-4 animate-spin" /> : <Download className="w-4 h-4" />}
+          Pull
+        </button>
+      </div>
+
+      {(pushMutation.data?.pushed || pullMutation.data?.pulled) && (
+        <div className="mt-3 flex items-center gap-2 text-emerald-500 text-sm">
+          <Check className="w-4 h-4" />
+          <span>{pullMutation.data?.pulled ? "Pulled remote changes" : "Pushed"}</span>
+        </div>
+      )}
+
+      {conflicts.length > 0 && (
+        <div className="mt-3 p-3 rounded-lg bg-amber-500/10 border border-amber-500/20 text-sm space-y-2">
+          <p className="text-amber-500">Changed here and on GitHub - nothing was merged:</p>
+          {conflicts.map((file) => (
+            <p key={file} className="font-mono text-xs">
+              {file}
+            </p>
+          ))}
+          <div className="flex gap-2">
+            <button
+              onClick={() => pullMutation.mutate("keep_mine")}
+              className="px-2 py-1 text-xs bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded transition-colors"
+            >
+              Keep mine
+            </button>
+            <button
+              onClick={() => pullMutation.mutate("take_theirs")}
+              className="px-2 py-1 text-xs bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded transition-colors"
+            >
+              Take GitHub's
+            </button>
+          </div>
+        </div>
+      )}
+
+      {error && (
+        <div className="mt-3 flex items-center gap-2 text-red-500 text-sm">
+          <AlertCircle className="w-4 h-4" />
+          <span>{String(error)}</span>
+        </div>
+      )}
+    </div>
+  );
+}
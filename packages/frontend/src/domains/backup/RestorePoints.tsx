@@ -0,0 +1,90 @@
+import { useState } from "react";
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { AlertCircle, Check, History } from "lucide-react";
+import type { RestorePoint } from "../../types";
+
+/** One-click rollback of claudit-initiated config changes */
+export function RestorePoints() {
+  const queryClient = useQueryClient();
+  const [confirming, setConfirming] = useState<string | null>(null);
+
+  const { data: points } = useQuery({
+    queryKey: ["restore-points"],
+    queryFn: () => invoke<RestorePoint[]>("list_restore_points"),
+  });
+
+  const rollbackMutation = useMutation({
+    mutationFn: (point: RestorePoint) => invoke<RestorePoint>("rollback_to", { restorePoint: point.id }),
+    onSettled: () => {
+      setConfirming(null);
+      queryClient.invalidateQueries({ queryKey: ["restore-points"] });
+      queryClient.invalidateQueries({ queryKey: ["hooks-status"] });
+      queryClient.invalidateQueries({ queryKey: ["mcp-servers"] });
+    },
+  });
+
+  return (
+    <div className="p-6 rounded-lg bg-zinc-900/50 border border-zinc-800/50">
+      <div className="flex items-center gap-3 mb-4">
+        <div className="w-10 h-10 rounded-lg bg-sky-500/10 flex items-center justify-center">
+          <History className="w-5 h-5 text-sky-500" />
+        </div>
+        <div>
+          <h3 className="font-semibold">Restore Points</h3>
+          <p className="text-sm text-muted-foreground">
+            Taken before imports, pack and hook installs and bulk MCP edits. Rolling back is itself
+            recorded, so it can be undone too.
+          </p>
+        </div>
+      </div>
+
+      {points && points.length > 0 ? (
+        <div className="space-y-1">
+          {points.map((point) => (
+            <div key={point.id} className="flex items-center gap-2 text-sm">
+              <span className="flex-1 truncate" title={point.files.map((f) => f.path).join("\n")}>
+                {point.name}
+              </span>
+              <span className="text-xs text-muted-foreground">
+                {point.files.length} file{point.files.length === 1 ? "" : "s"} ·{" "}
+                {new Date(point.created_at).toLocaleString()}
+              </span>
+              {confirming === point.id ? (
+                <button
+                  onClick={() => rollbackMutation.mutate(point)}
+                  disabled={rollbackMutation.isPending}
+                  className="px-2 py-1 text-xs bg-red-500/20 hover:bg-red-500/30 text-red-500 rounded transition-colors disabled:opacity-50"
+                >
+                  Confirm
+                </button>
+              ) : (
+                <button
+                  onClick={() => setConfirming(point.id)}
+                  className="px-2 py-1 text-xs bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded transition-colors"
+                >
+                  Roll back
+                </button>
+              )}
+            </div>
+          ))}
+        </div>
+      ) : (
+        <p className="text-sm text-muted-foreground">No restore points yet</p>
+      )}
+
+      {rollbackMutation.isSuccess && (
+        <div className="mt-3 flex items-center gap-2 text-emerald-500 text-sm">
+          <Check className="w-4 h-4" />
+          <span>Rolled back "{rollbackMutation.data.name}"</span>
+        </div>
+      )}
+      {rollbackMutation.error && (
+        <div className="mt-3 flex items-center gap-2 text-red-500 text-sm">
+          <AlertCircle className="w-4 h-4" />
+          <span>{String(rollbackMutation.error)}</span>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,159 @@
+import { useState } from "react";
+import { useMutation, useQuery } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { AlertCircle, Check, Library } from "lucide-react";
+import type {
+  CollectionEntry,
+  CollectionExportResult,
+  ExportFormatInfo,
+  SessionCollectionFilter,
+} from "../../types";
+
+interface ProjectInfo {
+  path: string;
+  name: string;
+}
+
+function formatCost(n: number) {
+  return `$${n.toFixed(2)}`;
+}
+
+function projectName(path: string) {
+  return path.split("/").filter(Boolean).pop() ?? path;
+}
+
+/** Several sessions (a project, a #tag, a date range) exported as one document */
+export function SessionCollections() {
+  const [filter, setFilter] = useState<SessionCollectionFilter>({ project_path: null, tag: null, from: null, to: null });
+
+  const { data: projects } = useQuery({
+    queryKey: ["projects"],
+    queryFn: () => invoke<ProjectInfo[]>("list_projects"),
+  });
+
+  const { data: entries, error } = useQuery({
+    queryKey: ["session-collection", filter],
+    queryFn: () => invoke<CollectionEntry[]>("get_session_collection", { filter }),
+  });
+
+  const exportMutation = useMutation({
+    mutationFn: async () => {
+      const formats = await invoke<ExportFormatInfo[]>("get_session_export_formats");
+      const name = filter.tag?.replace(/^#/, "") || "sessions";
+      const output = await invoke<string | null>("pick_path", {
+        mode: "save",
+        defaultPath: `claude-${name}.html`,
+        filters: formats.map((f) => ({ name: f.label, extensions: [f.extension] })),
+      });
+      if (!output) throw new Error("No save path selected");
+      const format = formats.find((f) => output.toLowerCase().endsWith(`.${f.extension}`))?.format ?? "html";
+      return invoke<CollectionExportResult>("export_session_collection", { filter, format, output });
+    },
+  });
+
+  const edit = (changes: Partial<SessionCollectionFilter>) => {
+    setFilter({ ...filter, ...changes });
+    exportMutation.reset();
+  };
+
+  const totalCost = entries?.reduce((sum, entry) => sum + entry.info.total_cost, 0) ?? 0;
+
+  return (
+    <div className="p-6 rounded-lg bg-zinc-900/50 border border-zinc-800/50">
+      <div className="flex items-center gap-3 mb-4">
+        <div className="w-10 h-10 rounded-lg bg-sky-500/10 flex items-center justify-center">
+          <Library className="w-5 h-5 text-sky-500" />
+        </div>
+        <div className="flex-1">
+          <h3 className="font-semibold">Session Collections</h3>
+          <p className="text-sm text-muted-foreground">
+            Export the sessions of a project, a tag or a date range as one document, with a table of contents and
+            each session's cost. Tag sessions by writing #tags in their note or title.
+          </p>
+        </div>
+      </div>
+
+      <div className="flex flex-wrap items-center gap-2 text-xs">
+        <select
+          value={filter.project_path ?? ""}
+          onChange={(e) => edit({ project_path: e.target.value || null })}
+          className="px-2 py-1 bg-secondary/50 border border-border rounded"
+        >
+          <option value="">All projects</option>
+          {projects?.map((project) => (
+            <option key={project.path} value={project.path}>
+              {project.name}
+            </option>
+          ))}
+        </select>
+        <input
+          value={filter.tag ?? ""}
+          onChange={(e) => edit({ tag: e.target.value || null })}
+          placeholder="#tag"
+          className="w-32 px-2 py-1 bg-secondary/50 border border-border rounded"
+        />
+        <input
+          type="date"
+          value={filter.from ?? ""}
+          onChange={(e) => edit({ from: e.target.value || null })}
+          className="px-2 py-1 bg-secondary/50 border border-border rounded"
+        />
+        <span className="text-muted-foreground">to</span>
+        <input
+          type="date"
+          value={filter.to ?? ""}
+          onChange={(e) => edit({ to: e.target.value || null })}
+          className="px-2 py-1 bg-secondary/50 border border-border rounded"
+        />
+      </div>
+
+      {error ? (
+        <p className="mt-3 text-xs text-red-500">{String(error)}</p>
+      ) : (
+        entries && (
+          <div className="mt-3 space-y-1">
+            <p className="text-xs text-muted-foreground">
+              {entries.length} sessions · {formatCost(totalCost)}
+            </p>
+            {entries.slice(0, 8).map((entry) => (
+              <div key={entry.info.session_id} className="flex justify-between gap-2 text-xs">
+                <span className="truncate" title={entry.title}>
+                  {entry.title}
+                </span>
+                <span className="text-muted-foreground flex-shrink-0">
+                  {projectName(entry.project_path)} · {formatCost(entry.info.total_cost)}
+                </span>
+              </div>
+            ))}
+            {entries.length > 8 && <p className="text-xs text-muted-foreground">and {entries.length - 8} more</p>}
+          </div>
+        )
+      )}
+
+      <div className="mt-4">
+        <button
+          onClick={() => exportMutation.mutate()}
+          disabled={!entries?.length || exportMutation.isPending}
+          className="px-3 py-1.5 text-sm bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded-lg transition-colors disabled:opacity-50"
+        >
+          {exportMutation.isPending ? "Exporting..." : "Export collection..."}
+        </button>
+      </div>
+
+      {exportMutation.isSuccess && (
+        <div className="mt-3 flex items-center gap-2 text-emerald-500 text-sm">
+          <Check className="w-4 h-4" />
+          <span>
+            Exported {exportMutation.data.sessions} sessions ({formatCost(exportMutation.data.total_cost)})
+          </span>
+        </div>
+      )}
+      {exportMutation.error && (
+        <div className="mt-3 flex items-center gap-2 text-red-500 text-sm">
+          <AlertCircle className="w-4 h-4" />
+          <span>{String(exportMutation.error)}</span>
+        </div>
+      )}
+    </div>
+  );
+}
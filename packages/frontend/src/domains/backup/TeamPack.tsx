@@ -0,0 +1,352 @@
+import { useState } from "react";
+import { useMutation, useQuery } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { AlertCircle, Check, Download, Package, ShieldAlert, ShieldCheck, Upload } from "lucide-react";
+import type {
+  McpScope,
+  PackInstallResult,
+  PackItem,
+  PackManifest,
+  PackReview,
+  PackSignature,
+} from "../../types";
+
+interface NamedItem {
+  name: string;
+  projectPath?: string;
+}
+
+interface ProjectInfo {
+  path: string;
+  name: string;
+}
+
+const itemKey = (item: PackItem) => `${item.type}:${item.name}`;
+
+function signatureText(signature: PackSignature) {
+  switch (signature.status) {
+    case "verified":
+      return `Signed by ${signature.key}`;
+    case "unknown_key":
+      return "Signed with a key you don't trust";
+    case "invalid":
+      return `Invalid signature: ${signature.reason}`;
+    default:
+      return "Not signed";
+  }
+}
+
+/** Create and install shareable packs of agents, commands and MCP servers */
+export function TeamPack() {
+  const [selected, setSelected] = useState<PackItem[]>([]);
+  const [packName, setPackName] = useState("team");
+  const [packPath, setPackPath] = useState<string | null>(null);
+  const [review, setReview] = useState<PackReview | null>(null);
+  const [params, setParams] = useState<Record<string, Record<string, string>>>({});
+  const [projectPath, setProjectPath] = useState("");
+  const [overwrite, setOverwrite] = useState(false);
+
+  const { data: agents } = useQuery({
+    queryKey: ["agents"],
+    queryFn: () => invoke<NamedItem[]>("list_agents"),
+  });
+  const { data: commands } = useQuery({
+    queryKey: ["commands"],
+    queryFn: () => invoke<NamedItem[]>("list_commands"),
+  });
+  const { data: mcpServers } = useQuery({
+    queryKey: ["mcp-servers"],
+    queryFn: () => invoke<NamedItem[]>("get_mcp_servers"),
+  });
+  const { data: projects } = useQuery({
+    queryKey: ["projects"],
+    queryFn: () => invoke<ProjectInfo[]>("list_projects"),
+  });
+
+  const groups: { label: string; items: PackItem[] }[] = [
+    { label: "Agents", items: (agents ?? []).map((a) => ({ type: "agent", name: a.name })) },
+    { label: "Commands", items: (commands ?? []).map((c) => ({ type: "command", name: c.name })) },
+    {
+      label: "MCP servers",
+      items: (mcpServers ?? [])
+        .filter((s) => !s.projectPath)
+        .map((s) => ({ type: "mcp_server", name: s.name })),
+    },
+  ];
+
+  const toggle = (item: PackItem) =>
+    setSelected((prev) =>
+      prev.some((i) => itemKey(i) === itemKey(item))
+        ? prev.filter((i) => itemKey(i) !== itemKey(item))
+        : [...prev, item]
+    );
+
+  const createMutation = useMutation({
+    mutationFn: async () => {
+      const output = await invoke<string | null>("pick_path", {
+        mode: "save",
+        defaultPath: `${packName || "team"}-pack.zip`,
+        filters: [{ name: "Config Pack", extensions: ["zip"] }],
+      });
+      if (!output) throw new Error("No save path selected");
+      return invoke<PackManifest>("create_config_pack", {
+        items: selected,
+        output,
+        name: packName || null,
+      });
+    },
+  });
+
+  const scope: McpScope = projectPath ? { type: "project", path: projectPath } : { type: "global" };
+
+  const reviewMutation = useMutation({
+    mutationFn: (path: string) =>
+      invoke<PackReview>("review_config_pack", { path, scope, params, overwrite }),
+    onSuccess: (data) => {
+      setReview(data);
+      installMutation.reset();
+    },
+  });
+
+  const openPack = async () => {
+    const path = await invoke<string | null>("pick_path", {
+      mode: "file",
+      filters: [{ name: "Config Pack", extensions: ["zip"] }],
+    });
+    if (!path) return;
+    setPackPath(path);
+    setParams({});
+    setReview(null);
+    reviewMutation.mutate(path);
+  };
+
+  // Packs without a trusted signature install only after this review
+  const installMutation = useMutation({
+    mutationFn: () =>
+      invoke<PackInstallResult>("install_config_pack", {
+        path: packPath,
+        scope,
+        params,
+        overwrite,
+        trustUnsigned: review?.signature.status !== "verified",
+      }),
+  });
+
+  const setParam = (server: string, key: string, value: string) =>
+    setParams((prev) => ({ ...prev, [server]: { ...prev[server], [key]: value } }));
+
+  const manifest = review?.manifest;
+  const result = installMutation.data ?? review?.plan;
+  const verified = review?.signature.status === "verified";
+  const error = createMutation.error ?? reviewMutation.error ?? installMutation.error;
+
+  return (
+    <div className="p-6 rounded-lg bg-zinc-900/50 border border-zinc-800/50">
+      <div className="flex items-center gap-3 mb-4">
+        <div className="w-10 h-10 rounded-lg bg-amber-500/10 flex items-center justify-center">
+          <Package className="w-5 h-5 text-amber-500" />
+        </div>
+        <div>
+          <h3 className="font-semibold">Team Pack</h3>
+          <p className="text-sm text-muted-foreground">
+            Share agents, commands and MCP servers with your team. MCP env vars, headers and
+            keys are left out and asked for on install.
+          </p>
+        </div>
+      </div>
+
+      <div className="space-y-3 mb-4">
+        {groups
+          .filter((group) => group.items.length > 0)
+          .map((group) => (
+            <div key={group.label}>
+              <div className="text-xs text-muted-foreground mb-1">{group.label}</div>
+              <div className="flex flex-wrap gap-1">
+                {group.items.map((item) => {
+                  const active = selected.some((i) => itemKey(i) === itemKey(item));
+                  return (
+                    <button
+                      key={itemKey(item)}
+                      onClick={() => toggle(item)}
+                      className={
+                        active
+                          ? "px-2 py-1 text-xs rounded bg-primary/10 border border-primary/30 text-primary"
+                          : "px-2 py-1 text-xs rounded bg-zinc-800/30 border border-transparent hover:bg-zinc-800/50"
+                      }
+                    >
+                      {item.name}
+                    </button>
+                  );
+                })}
+              </div>
+            </div>
+          ))}
+      </div>
+
+      <div className="flex items-center gap-2">
+        <input
+          value={packName}
+          onChange={(e) => setPackName(e.target.value)}
+          placeholder="Pack name"
+          className="flex-1 px-2 py-1.5 text-sm bg-secondary/50 border border-border rounded"
+        />
+        <button
+          onClick={() => createMutation.mutate()}
+          disabled={createMutation.isPending || selected.length === 0}
+          className="flex items-center gap-2 px-4 py-1.5 rounded-lg text-sm font-medium bg-secondary hover:bg-secondary/80 transition-colors disabled:opacity-50"
+        >
+          <Download className="w-4 h-4" />
+          Create Pack ({selected.length})
+        </button>
+        <button
+          onClick={openPack}
+          disabled={reviewMutation.isPending}
+          className="flex items-center gap-2 px-4 py-1.5 rounded-lg text-sm font-medium bg-secondary hover:bg-secondary/80 transition-colors disabled:opacity-50"
+        >
+          <Upload className="w-4 h-4" />
+          Open Pack
+        </button>
+      </div>
+
+      {createMutation.data && (
+        <div className="mt-3 flex items-center gap-2 text-emerald-500 text-sm">
+          <Check className="w-4 h-4" />
+          <span>
+            Pack created: {createMutation.data.agents.length} agents,{" "}
+            {createMutation.data.commands.length} commands,{" "}
+            {createMutation.data.mcp_servers.length} MCP servers
+          </span>
+        </div>
+      )}
+
+      {review && manifest && (
+        <div className="mt-4 p-3 rounded-lg bg-zinc-800/30 space-y-3 text-sm">
+          <div>
+            <div className="flex items-center gap-2">
+              <span className="font-medium">{manifest.name}</span>
+              <span
+                className={`flex items-center gap-1 text-xs ${verified ? "text-emerald-500" : "text-amber-500"}`}
+              >
+                {verified ? <ShieldCheck size={12} /> : <ShieldAlert size={12} />}
+                {signatureText(review.signature)}
+              </span>
+            </div>
+            <div className="text-xs text-muted-foreground">
+              {[
+                ...manifest.agents.map((a) => `agent ${a.name}`),
+                ...manifest.commands.map((c) => `command /${c.name}`),
+                ...manifest.mcp_servers.map((s) => `MCP ${s.name}`),
+              ].join(", ")}
+            </div>
+          </div>
+
+          {review.programs.length > 0 && (
+            <div className="text-xs">
+              <div className="text-muted-foreground">Runs or connects to</div>
+              {review.programs.map((program) => (
+                <p key={program} className="font-mono truncate">
+                  {program}
+                </p>
+              ))}
+            </div>
+          )}
+          {review.scripts.length > 0 && (
+            <div className="text-xs">
+              <div className="text-amber-500">Contains scripts</div>
+              {review.scripts.map((script) => (
+                <p key={script} className="font-mono truncate">
+                  {script}
+                </p>
+              ))}
+            </div>
+          )}
+
+          {manifest.mcp_servers
+            .filter((server) => server.params.length > 0)
+            .map((server) => (
+              <div key={server.name} className="space-y-1">
+                <div className="text-xs text-muted-foreground">{server.name}</div>
+                {server.params.map((param) => (
+                  <input
+                    key={param.key}
+                    type={param.secret ? "password" : "text"}
+                    value={params[server.name]?.[param.key] ?? ""}
+                    onChange={(e) => setParam(server.name, param.key, e.target.value)}
+                    placeholder={param.label}
+                    className="w-full px-2 py-1 text-xs font-mono bg-secondary/50 border border-border rounded"
+                  />
+                ))}
+              </div>
+            ))}
+
+          <div className="flex items-center gap-2">
+            <select
+              value={projectPath}
+              onChange={(e) => setProjectPath(e.target.value)}
+              className="flex-1 px-2 py-1 text-xs bg-secondary/50 border border-border rounded"
+            >
+              <option value="">All projects (~/.claude)</option>
+              {projects?.map((project) => (
+                <option key={project.path} value={project.path}>
+                  {project.name}
+                </option>
+              ))}
+            </select>
+            <label className="flex items-center gap-1 text-xs text-muted-foreground">
+              <input
+                type="checkbox"
+                checked={overwrite}
+                onChange={(e) => setOverwrite(e.target.checked)}
+              />
+              Overwrite
+            </label>
+            <button
+              onClick={() => packPath && reviewMutation.mutate(packPath)}
+              disabled={reviewMutation.isPending}
+              className="px-2 py-1 text-xs bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded transition-colors"
+            >
+              Review again
+            </button>
+            <button
+              onClick={() => installMutation.mutate()}
+              disabled={installMutation.isPending || review.signature.status === "invalid"}
+              className="px-2 py-1 text-xs bg-primary text-primary-foreground rounded transition-colors disabled:opacity-50"
+            >
+              {verified ? "Install" : "Trust & Install"}
+            </button>
+          </div>
+
+          {result && (
+            <div className="space-y-1 text-xs">
+              <p className={result.dry_run ? "text-muted-foreground" : "text-emerald-500"}>
+                {result.dry_run ? "Would install" : "Installed"}:{" "}
+                {result.installed.map((i) => i.name).join(", ") || "nothing"}
+              </p>
+              {result.skipped.map((s) => (
+                <p key={itemKey(s.item)} className="text-amber-500">
+                  Skipped {s.item.name}: {s.reason}
+                </p>
+              ))}
+              {result.missing_params.length > 0 && (
+                <p className="text-amber-500">Left empty: {result.missing_params.join(", ")}</p>
+              )}
+              {result.dry_run &&
+                result.operations.map((op, i) => (
+                  <p key={i} className="font-mono text-muted-foreground truncate">
+                    {op.kind} {op.path}
+                  </p>
+                ))}
+            </div>
+          )}
+        </div>
+      )}
+
+      {error && (
+        <div className="mt-3 flex items-center gap-2 text-red-500 text-sm">
+          <AlertCircle className="w-4 h-4" />
+          <span>{String(error)}</span>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,147 @@
+import { useState } from "react";
+import { useMutation, useQuery } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { AlertCircle, Check, FileBarChart } from "lucide-react";
+import { cn } from "../../lib/utils";
+import type { ReportPeriod, UsageReport } from "../../types";
+
+type ReportFormat = "markdown" | "html";
+
+const FORMATS: Record<ReportFormat, { label: string; extension: string }> = {
+  markdown: { label: "Markdown", extension: "md" },
+  html: { label: "HTML", extension: "html" },
+};
+
+function formatCost(n: number) {
+  return `$${n.toFixed(2)}`;
+}
+
+function projectName(path: string) {
+  return path.split("/").filter(Boolean).pop() ?? path;
+}
+
+/** Weekly/monthly usage summary with Markdown and HTML export */
+export function UsageReports() {
+  const [period, setPeriod] = useState<ReportPeriod>("week");
+
+  const { data: report, isLoading } = useQuery({
+    queryKey: ["usage-report", period],
+    queryFn: () => invoke<UsageReport>("generate_report", { period }),
+  });
+
+  const exportMutation = useMutation({
+    mutationFn: async (format: ReportFormat) => {
+      const { label, extension } = FORMATS[format];
+      const path = await invoke<string | null>("pick_path", {
+        mode: "save",
+        defaultPath: `claude-usage-${period}-${report?.end_date ?? "report"}.${extension}`,
+        filters: [{ name: label, extensions: [extension] }],
+      });
+      if (!path) throw new Error("No save path selected");
+      await invoke<number>("export_report", { period, format, path });
+      return path;
+    },
+  });
+
+  const change = report?.cost_change_pct;
+
+  return (
+    <div className="p-6 rounded-lg bg-zinc-900/50 border border-zinc-800/50">
+      <div className="flex items-center gap-3 mb-4">
+        <div className="w-10 h-10 rounded-lg bg-violet-500/10 flex items-center justify-center">
+          <FileBarChart className="w-5 h-5 text-violet-500" />
+        </div>
+        <div className="flex-1">
+          <h3 className="font-semibold">Usage Reports</h3>
+          <p className="text-sm text-muted-foreground">
+            Cost, tokens and the top models, projects and sessions of the last 7 or 30 days,
+            compared with the period before.
+          </p>
+        </div>
+        <div className="flex rounded-lg bg-secondary p-0.5">
+          {(["week", "month"] as const).map((p) => (
+            <button
+              key={p}
+              onClick={() => setPeriod(p)}
+              className={cn(
+                "px-3 py-1 text-xs rounded-md transition-colors",
+                period === p ? "bg-background text-foreground" : "text-muted-foreground"
+              )}
+            >
+              {p === "week" ? "Week" : "Month"}
+            </button>
+          ))}
+        </div>
+      </div>
+
+      {isLoading || !report ? (
+        <p className="text-sm text-muted-foreground">Building report...</p>
+      ) : (
+        <div className="space-y-3 text-sm">
+          <div className="flex items-baseline gap-3">
+            <span className="text-2xl font-semibold">{formatCost(report.totals.cost)}</span>
+            {change != null && (
+              <span className={cn("text-xs", change > 0 ? "text-amber-500" : "text-emerald-500")}>
+                {change > 0 ? "+" : ""}
+                {change.toFixed(0)}% vs previous {period}
+              </span>
+            )}
+            <span className="text-xs text-muted-foreground ml-auto">
+              {report.start_date} to {report.end_date} · {report.totals.sessions} sessions ·{" "}
+              {report.totals.messages} messages
+            </span>
+          </div>
+
+          <div className="grid grid-cols-2 gap-4">
+            {[
+              { title: "Models", rows: report.by_model.slice(0, 5), name: (n: string) => n },
+              { title: "Projects", rows: report.by_project.slice(0, 5), name: projectName },
+            ].map(({ title, rows, name }) => (
+              <div key={title}>
+                <p className="text-xs font-medium text-muted-foreground mb-1">{title}</p>
+                {rows.length > 0 ? (
+                  rows.map((row) => (
+                    <div key={row.name} className="flex justify-between gap-2 text-xs">
+                      <span className="truncate" title={row.name}>
+                        {name(row.name)}
+                      </span>
+                      <span className="text-muted-foreground">{formatCost(row.cost)}</span>
+                    </div>
+                  ))
+                ) : (
+                  <p className="text-xs text-muted-foreground">No usage</p>
+                )}
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
+
+      <div className="mt-4 flex gap-2">
+        {(Object.keys(FORMATS) as ReportFormat[]).map((format) => (
+          <button
+            key={format}
+            onClick={() => exportMutation.mutate(format)}
+            disabled={!report || exportMutation.isPending}
+            className="px-3 py-1.5 text-sm bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded-lg transition-colors disabled:opacity-50"
+          >
+            Export {FORMATS[format].label}
+          </button>
+        ))}
+      </div>
+
+      {exportMutation.isSuccess && (
+        <div className="mt-3 flex items-center gap-2 text-emerald-500 text-sm">
+          <Check className="w-4 h-4" />
+          <span className="truncate">Saved to {exportMutation.data}</span>
+        </div>
+      )}
+      {exportMutation.error && (
+        <div className="mt-3 flex items-center gap-2 text-red-500 text-sm">
+          <AlertCircle className="w-4 h-4" />
+          <span>{String(exportMutation.error)}</span>
+        </div>
+      )}
+    </div>
+  );
+}
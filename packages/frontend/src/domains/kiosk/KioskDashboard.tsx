@@ -0,0 +1,167 @@
+import { useEffect, useState } from "react";
+import { useQuery } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { Coins, Zap, MessageSquare, Clock, TrendingUp } from "lucide-react";
+import { useSettings } from "../analytics/hooks";
+import {
+  StatCard,
+  TokenChart,
+  CostChart,
+  ModelChart,
+  HourlyChart,
+  ProjectChart,
+} from "../analytics/components";
+import { isTauri } from "../../lib/tauri";
+import { DemoWatermark } from "../../components/DemoWatermark";
+import type { AnalyticsStats, ChartData, KioskPage } from "../../types";
+
+const PAGE_TITLES: Record<KioskPage, string> = {
+  overview: "Today",
+  cost: "Daily Cost",
+  tokens: "Token Usage",
+  models: "Models",
+  projects: "Projects",
+  hourly: "Activity by Hour",
+};
+
+function formatNumber(n: number): string {
+  if (n >= 1_000_000) {
+    return `${(n / 1_000_000).toFixed(1)}M`;
+  }
+  if (n >= 10_000) {
+    return `${(n / 1_000).toFixed(1)}K`;
+  }
+  return n.toLocaleString();
+}
+
+function formatCost(n: number): string {
+  if (n >= 1) {
+    return `$${n.toFixed(2)}`;
+  }
+  return `$${n.toFixed(3)}`;
+}
+
+/** Fullscreen, chrome-less dashboard that refreshes and rotates on its own */
+export function KioskDashboard() {
+  const { data: settings } = useSettings();
+  const refreshMs = (settings?.kiosk.refresh_seconds ?? 30) * 1000;
+  const rotateSeconds = settings?.kiosk.rotate_seconds ?? 20;
+  const pages = settings?.kiosk.pages.length ? settings.kiosk.pages : (["overview"] as KioskPage[]);
+  const [pageIndex, setPageIndex] = useState(0);
+
+  const { data: stats } = useQuery({
+    queryKey: ["stats"],
+    queryFn: () => invoke<AnalyticsStats>("get_stats"),
+    refetchInterval: refreshMs,
+  });
+  const { data: chartData } = useQuery({
+    queryKey: ["chart-data", 30],
+    queryFn: () => invoke<ChartData>("get_chart_data", { days: 30 }),
+    refetchInterval: refreshMs,
+  });
+
+  // Rotate through the configured pages
+  useEffect(() => {
+    if (rotateSeconds <= 0 || pages.length < 2) return;
+    const timer = setInterval(
+      () => setPageIndex((i) => (i + 1) % pages.length),
+      rotateSeconds * 1000
+    );
+    return () => clearInterval(timer);
+  }, [rotateSeconds, pages.length]);
+
+  // Esc closes the kiosk window
+  useEffect(() => {
+    if (!isTauri()) return;
+    const handleKeyDown = (e: KeyboardEvent) => {
+      if (e.key === "Escape") invoke("close_kiosk_dashboard");
+    };
+    window.addEventListener("keydown", handleKeyDown);
+    return () => window.removeEventListener("keydown", handleKeyDown);
+  }, []);
+
+  const page = pages[pageIndex % pages.length];
+
+  const renderPage = () => {
+    if (page === "overview") {
+      if (!stats) return null;
+      return (
+        <div className="grid grid-cols-2 lg:grid-cols-3 gap-6">
+          <StatCard
+            title="Cost Today"
+            value={formatCost(stats.today_cost)}
+            subtitle={`${formatCost(stats.total_cost)} total`}
+            icon={<Coins size={28} />}
+          />
+          <StatCard
+            title="Tokens Today"
+            value={formatNumber(stats.today_input_tokens + stats.today_output_tokens)}
+            subtitle={`${formatNumber(stats.today_input_tokens)} in / ${formatNumber(stats.today_output_tokens)} out`}
+            icon={<Zap size={28} />}
+          />
+          <StatCard
+            title="Messages Today"
+            value={stats.today_messages}
+            subtitle={`${stats.total_messages_count} total`}
+            icon={<MessageSquare size={28} />}
+          />
+          <StatCard
+            title="Sessions Today"
+            value={stats.today_session_count}
+            subtitle={`${stats.total_session_count} total`}
+            icon={<Clock size={28} />}
+          />
+          <StatCard
+            title="Burn Rate"
+            value={`${stats.tokens_per_minute.toFixed(0)}/min`}
+            subtitle={`${formatCost(stats.cost_per_hour)}/hr`}
+            icon={<TrendingUp size={28} />}
+          />
+        </div>
+      );
+    }
+
+    if (!chartData) return null;
+    switch (page) {
+      case "cost":
+        return <CostChart data={chartData.daily} />;
+      case "tokens":
+        return <TokenChart data={chartData.daily} />;
+      case "models":
+        return <ModelChart data={chartData.by_model} />;
+      case "projects":
+        return <ProjectChart data={chartData.by_project} />;
+      case "hourly":
+        return <HourlyChart data={chartData.hourly} />;
+      default:
+        return null;
+    }
+  };
+
+  return (
+    <div className="h-screen bg-background text-foreground p-10 flex flex-col cursor-none select-none">
+      <div className="flex items-baseline justify-between mb-8">
+        <h1 className="text-4xl font-semibold">{PAGE_TITLES[page] ?? page}</h1>
+        <div className="flex items-center gap-2">
+          {pages.map((p, i) => (
+            <span
+              key={p}
+              className={`h-2 w-2 rounded-full ${i === pageIndex % pages.length ? "bg-primary" : "bg-secondary"}`}
+            />
+          ))}
+        </div>
+      </div>
+
+      {/* Charts keep their own card; stretch them to fill the screen */}
+      <div className="flex-1 [&_.h-64]:h-[65vh]">{renderPage()}</div>
+
+      {stats?.last_updated && (
+        <div className="mt-6 text-center text-sm text-muted-foreground">
+          Updated {new Date(stats.last_updated).toLocaleTimeString()}
+        </div>
+      )}
+
+      <DemoWatermark />
+    </div>
+  );
+}
@@ -0,0 +1 @@
+export { KioskDashboard } from "./KioskDashboard";
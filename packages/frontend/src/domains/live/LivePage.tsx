@@ -0,0 +1,163 @@
+import { useEffect, useRef, useState } from "react";
+import { useQuery, useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { Radio, User } from "lucide-react";
+import { PageHeader } from "../../components/PageHeader";
+import { MessageBubble } from "../projects/components/ConversationViewer";
+import { cn } from "../../lib/utils";
+import type { ConversationMessage, LiveSessionInfo, SessionMessageAppended } from "../../types";
+
+interface LiveConversation {
+  messages: ConversationMessage[];
+  /** Submitted prompt not yet in the session file */
+  pendingPrompt: string | null;
+}
+
+function projectName(path: string | null) {
+  if (!path) return "Unknown project";
+  return path.split("/").filter(Boolean).pop() ?? path;
+}
+
+/** Apply an event to a conversation: replace on reset, otherwise update by uuid or append */
+function applyEvent(current: LiveConversation | undefined, event: SessionMessageAppended): LiveConversation {
+  const messages = event.reset ? [] : [...(current?.messages ?? [])];
+  for (const message of event.messages) {
+    const idx = messages.findIndex((m) => m.uuid === message.uuid);
+    if (idx >= 0) messages[idx] = message;
+    else messages.push(message);
+  }
+  const pendingPrompt =
+    event.prompt ?? (event.messages.some((m) => m.role === "user") ? null : current?.pendingPrompt ?? null);
+  return { messages, pendingPrompt };
+}
+
+const noMatches: { messageIndex: number; blockIndex: number }[] = [];
+
+/** Conversations of the sessions running in the terminal, streamed from hook events */
+export function LivePage() {
+  const queryClient = useQueryClient();
+  const [conversations, setConversations] = useState<Record<string, LiveConversation>>({});
+  const [selected, setSelected] = useState<string | null>(null);
+  const [expandedTools, setExpandedTools] = useState<Set<string>>(new Set());
+  const matchRefs = useRef(new Map<number, HTMLElement>());
+  const bottomRef = useRef<HTMLDivElement>(null);
+
+  const { data: sessions } = useQuery({
+    queryKey: ["live-sessions"],
+    queryFn: () => invoke<LiveSessionInfo[]>("get_live_sessions"),
+    refetchInterval: 60000,
+  });
+
+  useEffect(() => {
+    const unlisten = listen<SessionMessageAppended>("session-message-appended", ({ payload }) => {
+      setConversations((prev) => ({ ...prev, [payload.session_id]: applyEvent(prev[payload.session_id], payload) }));
+      queryClient.invalidateQueries({ queryKey: ["live-sessions"] });
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [queryClient]);
+
+  // Follow the most recently active session until one is picked
+  const current = selected ?? sessions?.[0]?.session_id ?? null;
+
+  // Sessions that were running before this page opened start from their latest messages
+  useEffect(() => {
+    if (!current || conversations[current]) return;
+    invoke<SessionMessageAppended | null>("get_live_session", { sessionId: current }).then((snapshot) => {
+      if (snapshot) {
+        setConversations((prev) => ({ ...prev, [current]: prev[current] ?? applyEvent(undefined, snapshot) }));
+      }
+    });
+  }, [current, conversations]);
+
+  const conversation = current ? conversations[current] : undefined;
+
+  useEffect(() => {
+    bottomRef.current?.scrollIntoView({ behavior: "smooth" });
+  }, [conversation]);
+
+  const toggleTool = (toolId: string) => {
+    setExpandedTools((prev) => {
+      const next = new Set(prev);
+      if (next.has(toolId)) next.delete(toolId);
+      else next.add(toolId);
+      return next;
+    });
+  };
+
+  const starPrompt = async (text: string) => {
+    const session = sessions?.find((s) => s.session_id === current);
+    await invoke("star_prompt", { text, projectPath: session?.project ?? null, sessionId: current });
+    queryClient.invalidateQueries({ queryKey: ["starred-prompts"] });
+  };
+
+  return (
+    <div className="h-full flex flex-col">
+      <PageHeader
+        title="Live"
+        description="Sessions running in the terminal, as Claude works. Messages arrive with each hook event (a submitted prompt, a finished tool call, the end of a response), so the hooks need to be installed in Settings."
+      />
+
+      <div className="flex-1 flex min-h-0">
+        <div className="w-64 border-r border-zinc-800/50 overflow-y-auto p-2 space-y-1 flex-shrink-0">
+          {sessions?.length === 0 && (
+            <div className="flex flex-col items-center justify-center py-12 text-muted-foreground text-center">
+              <Radio className="w-10 h-10 mb-3 opacity-50" />
+              <p className="text-sm font-medium">No running sessions</p>
+              <p className="text-xs mt-1">Sessions show up with their next hook event</p>
+            </div>
+          )}
+          {sessions?.map((session) => (
+            <button
+              key={session.session_id}
+              onClick={() => setSelected(session.session_id)}
+              className={cn(
+                "w-full text-left px-3 py-2 rounded-lg transition-colors",
+                session.session_id === current ? "bg-primary/10 text-foreground" : "hover:bg-zinc-800/50"
+              )}
+            >
+              <p className="text-sm font-medium truncate">{projectName(session.project)}</p>
+              <p className="text-xs text-muted-foreground truncate">
+                {session.last_event} · {new Date(session.last_event_at).toLocaleTimeString()}
+              </p>
+            </button>
+          ))}
+        </div>
+
+        <div className="flex-1 overflow-y-auto p-4 space-y-4">
+          {!current && (
+            <p className="text-sm text-muted-foreground text-center py-12">Select a session</p>
+          )}
+          {conversation?.messages.map((message, idx) => (
+            <MessageBubble
+              key={message.uuid}
+              message={message}
+              msgIdx={idx}
+              expandedTools={expandedTools}
+              onToggleTool={toggleTool}
+              searchQuery=""
+              isCurrentMatch={() => false}
+              highlightMatches={(text) => text}
+              matchRefs={matchRefs}
+              matches={noMatches}
+              onStar={starPrompt}
+            />
+          ))}
+          {conversation?.pendingPrompt && (
+            <div className="flex gap-3 flex-row-reverse opacity-60">
+              <div className="w-8 h-8 rounded-lg flex items-center justify-center flex-shrink-0 bg-primary/10">
+                <User className="w-4 h-4 text-primary" />
+              </div>
+              <p className="max-w-[80%] px-3 py-2 rounded-lg bg-primary/10 text-sm whitespace-pre-wrap break-words">
+                {conversation.pendingPrompt}
+              </p>
+            </div>
+          )}
+          <div ref={bottomRef} />
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1 @@
+export { LivePage } from "./LivePage";
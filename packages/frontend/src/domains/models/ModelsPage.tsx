@@ -0,0 +1,180 @@
+import { useState } from "react";
+import { Pencil, RotateCcw } from "lucide-react";
+import { PageHeader } from "../../components/PageHeader";
+import { useModelCatalog, useSettings, useUpdateSettings } from "../analytics/hooks";
+import type { ModelCatalogOverride, ModelInfo } from "../../types";
+
+const EMPTY_OVERRIDE: ModelCatalogOverride = {
+  model: "",
+  context_window: null,
+  max_output: null,
+  knowledge_cutoff: null,
+};
+
+function formatTokens(tokens: number) {
+  return tokens >= 1_000_000 ? `${tokens / 1_000_000}M` : `${Math.round(tokens / 1000)}k`;
+}
+
+const numberOrNull = (value: string) => (value.trim() ? Number(value) : null);
+
+/** Reference of model limits and prices, with user overrides */
+export function ModelsPage() {
+  const { data: models } = useModelCatalog();
+  const { data: settings } = useSettings();
+  const updateSettings = useUpdateSettings();
+  const [draft, setDraft] = useState<ModelCatalogOverride | null>(null);
+  const overrides = settings?.model_catalog_overrides ?? [];
+
+  const saveOverrides = (next: ModelCatalogOverride[]) => {
+    if (!settings) return;
+    updateSettings.mutate(
+      { ...settings, model_catalog_overrides: next },
+      { onSuccess: () => setDraft(null) }
+    );
+  };
+
+  const saveDraft = () => {
+    if (!draft) return;
+    const model = draft.model.trim().toLowerCase();
+    saveOverrides([...overrides.filter((o) => o.model !== model), { ...draft, model }]);
+  };
+
+  const edit = (model: ModelInfo) =>
+    setDraft(
+      overrides.find((o) => o.model === model.id) ?? {
+        model: model.id,
+        context_window: model.context_window,
+        max_output: model.max_output,
+        knowledge_cutoff: model.knowledge_cutoff,
+      }
+    );
+
+  const inputClass = "w-full px-2 py-1 text-xs bg-secondary/50 border border-border rounded";
+
+  return (
+    <div className="h-full flex flex-col">
+      <PageHeader
+        title="Models"
+        description="Context window, output limit, knowledge cutoff and price per million tokens of each Claude model. Context usage in conversations is measured against these limits; correct them or add new models here."
+        learnMoreUrl="https://docs.anthropic.com/en/docs/about-claude/models"
+      />
+
+      <div className="flex-1 overflow-y-auto p-4 space-y-4">
+        <table className="w-full text-sm">
+          <thead>
+            <tr className="text-left text-xs text-muted-foreground border-b border-border">
+              <th className="py-2 font-medium">Model</th>
+              <th className="py-2 font-medium">Context</th>
+              <th className="py-2 font-medium">Max output</th>
+              <th className="py-2 font-medium">Cutoff</th>
+              <th className="py-2 font-medium">Input / Output</th>
+              <th className="py-2" />
+            </tr>
+          </thead>
+          <tbody>
+            {models?.map((model) => (
+              <tr key={model.id} className="border-b border-border/50">
+                <td className="py-2">
+                  <div className="font-medium">{model.name}</div>
+                  <div className="text-xs text-muted-foreground font-mono">{model.id}</div>
+                </td>
+                <td className="py-2">
+                  {formatTokens(model.context_window)}
+                  {model.long_context_window && (
+                    <span className="text-xs text-muted-foreground">
+                      {" "}
+                      ({formatTokens(model.long_context_window)} beta)
+                    </span>
+                  )}
+                </td>
+                <td className="py-2">{formatTokens(model.max_output)}</td>
+                <td className="py-2">{model.knowledge_cutoff ?? "—"}</td>
+                <td className="py-2">
+                  ${model.pricing.input.toFixed(2)} / ${model.pricing.output.toFixed(2)}
+                </td>
+                <td className="py-2 text-right whitespace-nowrap">
+                  {model.overridden && (
+                    <button
+                      onClick={() => saveOverrides(overrides.filter((o) => o.model !== model.id))}
+                      className="p-1 text-muted-foreground hover:text-foreground transition-colors"
+                      title="Restore the bundled values"
+                    >
+                      <RotateCcw size={14} />
+                    </button>
+                  )}
+                  <button
+                    onClick={() => edit(model)}
+                    className="p-1 text-muted-foreground hover:text-foreground transition-colors"
+                    title="Override limits"
+                  >
+                    <Pencil size={14} />
+                  </button>
+                </td>
+              </tr>
+            ))}
+          </tbody>
+        </table>
+
+        <div className="p-4 rounded-lg bg-zinc-900/50 border border-zinc-800/50 space-y-2">
+          <div className="text-sm font-medium">
+            {draft && models?.some((m) => m.id === draft.model) ? `Override ${draft.model}` : "Add a model"}
+          </div>
+          <div className="grid grid-cols-4 gap-2">
+            <input
+              value={(draft ?? EMPTY_OVERRIDE).model}
+              onChange={(e) => setDraft({ ...(draft ?? EMPTY_OVERRIDE), model: e.target.value })}
+              placeholder="Model ID"
+              className={`${inputClass} font-mono`}
+            />
+            <input
+              type="number"
+              value={draft?.context_window ?? ""}
+              onChange={(e) =>
+                setDraft({ ...(draft ?? EMPTY_OVERRIDE), context_window: numberOrNull(e.target.value) })
+              }
+              placeholder="Context window"
+              className={inputClass}
+            />
+            <input
+              type="number"
+              value={draft?.max_output ?? ""}
+              onChange={(e) =>
+                setDraft({ ...(draft ?? EMPTY_OVERRIDE), max_output: numberOrNull(e.target.value) })
+              }
+              placeholder="Max output"
+              className={inputClass}
+            />
+            <input
+              value={draft?.knowledge_cutoff ?? ""}
+              onChange={(e) =>
+                setDraft({ ...(draft ?? EMPTY_OVERRIDE), knowledge_cutoff: e.target.value || null })
+              }
+              placeholder="Cutoff (YYYY-MM)"
+              className={inputClass}
+            />
+          </div>
+          <div className="flex gap-2">
+            <button
+              onClick={saveDraft}
+              disabled={!draft?.model.trim()}
+              className="px-2 py-1 text-xs bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded transition-colors disabled:opacity-50"
+            >
+              Save
+            </button>
+            {draft && (
+              <button
+                onClick={() => setDraft(null)}
+                className="px-2 py-1 text-xs text-muted-foreground hover:text-foreground rounded transition-colors"
+              >
+                Cancel
+              </button>
+            )}
+          </div>
+          {updateSettings.error && (
+            <p className="text-xs text-red-500">{String(updateSettings.error)}</p>
+          )}
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1 @@
+export { ModelsPage } from "./ModelsPage";
@@ -0,0 +1,248 @@
+import { useCallback, useEffect, useState } from "react";
+import { useQuery, useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { emit, listen } from "@tauri-apps/api/event";
+import { BarChart3, Search, RefreshCw, Tv, Circle } from "lucide-react";
+import { isTauri } from "../../lib/tauri";
+import { cn } from "../../lib/utils";
+import type {
+  ActiveSession,
+  AnalyticsStats,
+  ExpensiveSession,
+  LiveSessionCost,
+} from "../../types";
+
+function formatNumber(n: number): string {
+  if (n >= 1_000_000) {
+    return `${(n / 1_000_000).toFixed(1)}M`;
+  }
+  if (n >= 10_000) {
+    return `${(n / 1_000).toFixed(1)}K`;
+  }
+  return n.toLocaleString();
+}
+
+function formatCost(n: number): string {
+  if (n >= 1) {
+    return `$${n.toFixed(2)}`;
+  }
+  return `$${n.toFixed(3)}`;
+}
+
+function formatAgo(dateStr?: string | null): string {
+  if (!dateStr) return "";
+  const minutes = Math.floor((Date.now() - new Date(dateStr).getTime()) / 60_000);
+  if (minutes < 1) return "now";
+  if (minutes < 60) return `${minutes}m`;
+  if (minutes < 24 * 60) return `${Math.floor(minutes / 60)}h`;
+  return `${Math.floor(minutes / (24 * 60))}d`;
+}
+
+interface QuickAction {
+  key: string;
+  label: string;
+  icon: React.ReactNode;
+  run: () => Promise<unknown>;
+}
+
+/** Compact card shown from the tray icon: today's stats, recent sessions, quick actions */
+export function TrayPopover() {
+  const queryClient = useQueryClient();
+  const [selected, setSelected] = useState(0);
+
+  const { data: stats } = useQuery({
+    queryKey: ["stats"],
+    queryFn: () => invoke<AnalyticsStats>("get_stats"),
+  });
+  const { data: active = [] } = useQuery({
+    queryKey: ["active-sessions"],
+    queryFn: () => invoke<ActiveSession[]>("get_active_sessions"),
+  });
+  const { data: recent = [] } = useQuery({
+    queryKey: ["recent-sessions"],
+    queryFn: () => invoke<ExpensiveSession[]>("get_recent_sessions", { limit: 5 }),
+  });
+
+  const hide = useCallback(() => invoke("hide_tray_popover"), []);
+
+  const actions: QuickAction[] = [
+    {
+      key: "o",
+      label: "Open",
+      icon: <BarChart3 className="w-4 h-4" />,
+      run: () => invoke("open_analytics_window"),
+    },
+    {
+      key: "s",
+      label: "Search",
+      icon: <Search className="w-4 h-4" />,
+      run: async () => {
+        await invoke("open_analytics_window");
+        await emit("open-search");
+      },
+    },
+    {
+      key: "r",
+      label: "Refresh",
+      icon: <RefreshCw className="w-4 h-4" />,
+      run: async () => {
+        await invoke("refresh_stats");
+        queryClient.invalidateQueries();
+      },
+    },
+    {
+      key: "k",
+      label: "Kiosk",
+      icon: <Tv className="w-4 h-4" />,
+      run: () => invoke("open_kiosk_dashboard"),
+    },
+  ];
+
+  const runAction = async (action: QuickAction) => {
+    await action.run();
+    if (action.key !== "r") await hide();
+  };
+
+  const resume = async (session: ExpensiveSession) => {
+    await invoke("open_terminal_with_resume", {
+      projectPath: session.project_path,
+      sessionId: session.session_id,
+    });
+    await hide();
+  };
+
+  // Fresh numbers every time the popover is shown
+  useEffect(() => {
+    if (!isTauri()) return;
+    let unlisten: (() => void) | undefined;
+    import("@tauri-apps/api/window").then(({ getCurrentWindow }) =>
+      getCurrentWindow()
+        .onFocusChanged(({ payload: focused }) => {
+          if (focused) {
+            setSelected(0);
+            queryClient.invalidateQueries();
+          }
+        })
+        .then((fn) => (unlisten = fn))
+    );
+    return () => unlisten?.();
+  }, [queryClient]);
+
+  // Running sessions' cost follows their hook events between refreshes
+  useEffect(() => {
+    if (!isTauri()) return;
+    const unlisten = listen<LiveSessionCost>("live-session-cost", ({ payload }) => {
+      queryClient.setQueryData<ActiveSession[]>(["active-sessions"], (sessions) =>
+        sessions?.map((s) => (s.session_id === payload.session_id ? { ...s, cost: payload.cost } : s))
+      );
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [queryClient]);
+
+  useEffect(() => {
+    const onKeyDown = (e: KeyboardEvent) => {
+      if (e.key === "Escape") {
+        hide();
+      } else if (e.key === "ArrowDown") {
+        e.preventDefault();
+        setSelected((i) => Math.min(i + 1, Math.max(recent.length - 1, 0)));
+      } else if (e.key === "ArrowUp") {
+        e.preventDefault();
+        setSelected((i) => Math.max(i - 1, 0));
+      } else if (e.key === "Enter" && recent[selected]) {
+        resume(recent[selected]);
+      } else {
+        const action = actions.find((a) => a.key === e.key.toLowerCase());
+        if (action && !e.metaKey && !e.ctrlKey) runAction(action);
+      }
+    };
+    window.addEventListener("keydown", onKeyDown);
+    return () => window.removeEventListener("keydown", onKeyDown);
+  });
+
+  const waiting = active.filter((s) => s.needs_attention).length;
+
+  return (
+    <div className="h-screen p-1">
+      <div className="h-full flex flex-col rounded-xl bg-zinc-950/95 border border-zinc-800 text-foreground overflow-hidden">
+
+        {/* Today */}
+        <div className="p-4 border-b border-zinc-800/50">
+          <div className="text-xs text-muted-foreground mb-1">Today</div>
+          <div className="flex items-baseline gap-3">
+            <span className="text-2xl font-semibold">{formatCost(stats?.today_cost ?? 0)}</span>
+            <span className="text-sm text-muted-foreground">
+              {formatNumber((stats?.today_input_tokens ?? 0) + (stats?.today_output_tokens ?? 0))}{" "}
+              tokens · {stats?.today_messages ?? 0} messages
+            </span>
+          </div>
+          {(active.length > 0 || (stats?.cost_per_hour ?? 0) > 0) && (
+            <div className="mt-2 flex items-center gap-3 text-xs text-muted-foreground">
+              {active.length > 0 && (
+                <span className="flex items-center gap-1">
+                  <Circle className="w-2 h-2 fill-green-500 text-green-500" />
+                  {active.length} running
+                  {waiting > 0 && <span className="text-amber-500">· {waiting} waiting</span>}
+                </span>
+              )}
+              {(stats?.cost_per_hour ?? 0) > 0 && <span>{formatCost(stats!.cost_per_hour)}/h</span>}
+            </div>
+          )}
+        </div>
+
+        {/* Recent sessions */}
+        <div className="flex-1 overflow-y-auto p-2">
+          <div className="px-2 py-1 text-xs text-muted-foreground">Recent sessions</div>
+          {recent.length === 0 ? (
+            <p className="px-2 py-1 text-sm text-muted-foreground">No sessions yet</p>
+          ) : (
+            recent.map((session, i) => (
+              <button
+                key={session.session_id}
+                onClick={() => resume(session)}
+                onMouseEnter={() => setSelected(i)}
+                className={cn(
+                  "w-full text-left px-2 py-1.5 rounded-md transition-colors",
+                  i === selected ? "bg-secondary" : "hover:bg-secondary/50"
+                )}
+              >
+                <div className="flex items-center justify-between gap-2">
+                  <span className="text-sm truncate">
+                    {session.title || session.summary || session.first_user_message || session.session_id}
+                  </span>
+                  <span className="text-xs text-muted-foreground shrink-0">
+                    {formatAgo(session.last_message_at)}
+                  </span>
+                </div>
+                <div className="text-xs text-muted-foreground truncate">
+                  {session.project_name} · {formatCost(session.total_cost)}
+                </div>
+              </button>
+            ))
+          )}
+        </div>
+
+        {/* Quick actions */}
+        <div className="grid grid-cols-4 gap-1 p-2 border-t border-zinc-800/50">
+          {actions.map((action) => (
+            <button
+              key={action.key}
+              onClick={() => runAction(action)}
+              className="flex flex-col items-center gap-1 py-2 rounded-md text-muted-foreground hover:text-foreground hover:bg-secondary/50 transition-colors"
+            >
+              {action.icon}
+              <span className="text-xs">
+                {action.label} <kbd className="opacity-60">{action.key.toUpperCase()}</kbd>
+              </span>
+            </button>
+          ))}
+        </div>
+        <div className="px-3 pb-2 text-[10px] text-muted-foreground/70">
+          ↑↓ select · ↵ resume in terminal · esc close
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1 @@
+export { TrayPopover } from "./TrayPopover";
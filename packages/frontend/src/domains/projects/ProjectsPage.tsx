@@ -1,8 +1,8 @@
 import { useState, useMemo, useEffect, useRef } from "react";
 import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
 import { invoke } from "@tauri-apps/api/core";
-import { open, save } from "@tauri-apps/plugin-dialog";
-import { writeTextFile } from "@tauri-apps/plugin-fs";
+import { listen } from "@tauri-apps/api/event";
+import { ask } from "@tauri-apps/plugin-dialog";
 import Fuse from "fuse.js";
 import {
   FolderOpen,
@@ -38,9 +38,21 @@ import { McpServerCard, type McpServer } from "../../components/McpServerCard";
 import { type CommandInfo, type DirectoryFile } from "../../components/CommandCard";
 import { motion, AnimatePresence } from "motion/react";
 import { cn } from "../../lib/utils";
+import { invokeChunked } from "../../lib/transfer";
 import { Skeleton, SkeletonProjectCard, SkeletonSessionItem } from "../../components/Skeleton";
-import { ConversationViewer } from "./components";
-import type { SessionInfo, SessionConversation, SessionSearchResult } from "../../types";
+import { ActionItems, ConversationViewer, CostHistory, WorkspaceBreakdown } from "./components";
+import type {
+  SessionInfo,
+  SessionConversation,
+  SessionMessages,
+  SessionSearchResult,
+  ExportFormatInfo,
+  ExportTheme,
+  JumpAction,
+} from "../../types";
+
+/** Messages loaded per page of a session conversation */
+const CONVERSATION_PAGE_SIZE = 200;
 
 interface ProjectInfo {
   path: string;
@@ -53,6 +65,7 @@ interface ProjectInfo {
   hasClaude: boolean;
   mcpServerCount: number;
   lastUsed?: string;
+  sessionCount: number;
 }
 
 interface ProjectDetails {
@@ -71,7 +84,12 @@ interface ProjectSuggestion {
 
 type TabType = "overview" | "claude-md" | "commands" | "mcp" | "suggestions" | "sessions";
 
-export function ProjectsPage() {
+interface ProjectsPageProps {
+  // Project, session or project MCP server picked in the command palette
+  jump?: JumpAction | null;
+}
+
+export function ProjectsPage({ jump }: ProjectsPageProps = {}) {
   const queryClient = useQueryClient();
   const [searchQuery, setSearchQuery] = useState("");
   const [sortBy, setSortBy] = useState<"name" | "cost" | "recent">("recent");
@@ -140,6 +158,23 @@ export function ProjectsPage() {
     queryFn: () => invoke<ProjectInfo[]>("list_projects"),
   });
 
+  // Open whatever was picked in the command palette
+  useEffect(() => {
+    if (!jump || !projects || !("project_path" in jump) || !jump.project_path) return;
+    const project = projects.find((p) => p.path === jump.project_path);
+    if (!project) return;
+
+    setSelectedProject(project);
+    if (jump.type === "session") {
+      setActiveTab("sessions");
+      setSelectedSession(jump.session_id);
+    } else if (jump.type === "mcp_server") {
+      setActiveTab("mcp");
+    } else {
+      setActiveTab("overview");
+    }
+  }, [jump, projects]);
+
   // Fetch project details when selected
   const {
     data: projectDetails,
@@ -199,15 +234,82 @@ export function ProjectsPage() {
       .slice(0, 10);
   }, [sessions]);
 
-  // Fetch conversation for selected session
+  // Fetch the first page of the selected session; later pages load on scroll
   const { data: conversation, isLoading: conversationLoading } = useQuery({
     queryKey: ["sessionConversation", selectedProject?.path, selectedSession],
-    queryFn: () => invoke<SessionConversation>("get_session_conversation", {
+    queryFn: () => invokeChunked<SessionConversation>("start_session_transfer", {
       projectPath: selectedProject!.path,
-      sessionId: selectedSession!
+      sessionId: selectedSession!,
+      limit: CONVERSATION_PAGE_SIZE,
     }),
     enabled: !!selectedProject && !!selectedSession,
   });
+  const hasMoreMessages =
+    !!conversation && conversation.offset + conversation.messages.length < conversation.total_messages;
+  const [loadingMoreMessages, setLoadingMoreMessages] = useState(false);
+
+  const loadMoreMessages = async () => {
+    if (!selectedProject || !selectedSession || !conversation || loadingMoreMessages) return;
+    const key = ["sessionConversation", selectedProject.path, selectedSession];
+    setLoadingMoreMessages(true);
+    try {
+      const page = await invokeChunked<SessionConversation>("start_session_transfer", {
+        projectPath: selectedProject.path,
+        sessionId: selectedSession,
+        limit: CONVERSATION_PAGE_SIZE,
+        afterUuid: conversation.messages[conversation.messages.length - 1]?.uuid,
+      });
+      queryClient.setQueryData<SessionConversation>(key, (old) =>
+        old && {
+          ...old,
+          total_messages: page.total_messages,
+          messages: [...old.messages, ...page.messages],
+          subagents: [...(old.subagents ?? []), ...(page.subagents ?? [])],
+        }
+      );
+    } catch (e) {
+      console.error("Failed to load more messages:", e);
+    } finally {
+      setLoadingMoreMessages(false);
+    }
+  };
+
+  // Follow mode: stream messages of the selected session as Claude writes them
+  const [following, setFollowing] = useState(false);
+  const followedProject = selectedProject?.path;
+  useEffect(() => setFollowing(false), [followedProject, selectedSession]);
+
+  useEffect(() => {
+    if (!following || !followedProject || !selectedSession) return;
+    const key = ["sessionConversation", followedProject, selectedSession];
+    const unlisten = listen<SessionMessages>("session-messages", ({ payload }) => {
+      if (payload.session_id !== selectedSession) return;
+      queryClient.setQueryData<SessionConversation>(key, (old) => {
+        if (!old || payload.reset) return old && { ...old, messages: payload.messages };
+        const messages = [...old.messages];
+        for (const msg of payload.messages) {
+          const idx = messages.findIndex((m) => m.uuid === msg.uuid);
+          if (idx >= 0) messages[idx] = msg;
+          else messages.push(msg);
+        }
+        return { ...old, messages };
+      });
+    });
+    invoke<SessionConversation>("follow_session", {
+      projectPath: followedProject,
+      sessionId: selectedSession,
+    })
+      .then((current) => queryClient.setQueryData(key, current))
+      .catch((e) => {
+        console.error("Failed to follow session:", e);
+        setFollowing(false);
+      });
+
+    return () => {
+      unlisten.then((fn) => fn());
+      invoke("unfollow_session", { sessionId: selectedSession });
+    };
+  }, [following, followedProject, selectedSession, queryClient]);
 
   // Search session message content (backend search)
   const { data: contentSearchResults, isLoading: contentSearchLoading } = useQuery({
@@ -351,8 +453,9 @@ export function ProjectsPage() {
   const handleSelectImage = async () => {
     if (!selectedProject) return;
 
-    const result = await open({
-      multiple: false,
+    // Picked through the backend so the path is allowed for set_project_image
+    const result = await invoke<string | null>("pick_path", {
+      mode: "file",
       filters: [{ name: "Images", extensions: ["png", "jpg", "jpeg", "webp", "svg"] }],
     });
 
@@ -468,8 +571,19 @@ export function ProjectsPage() {
     }
   };
 
+  const handleRenameSession = async (sessionId: string, title: string | null) => {
+    if (!selectedProject) return;
+    try {
+      await invoke("set_session_title", { projectPath: selectedProject.path, sessionId, title });
+      queryClient.invalidateQueries({ queryKey: ["projectSessions", selectedProject.path] });
+      queryClient.invalidateQueries({ queryKey: ["sessionContentSearch", selectedProject.path] });
+    } catch (e) {
+      console.error("Failed to rename session:", e);
+    }
+  };
+
   const getSessionTitle = (session: SessionInfo | SessionSearchResult) => {
-    return session.summary || session.first_user_message || "Untitled Session";
+    return session.title || session.summary || session.first_user_message || "Untitled Session";
   };
 
   // Fuse.js instance for fuzzy title search (excludes warmup sessions)
@@ -480,7 +594,7 @@ export function ProjectsPage() {
       return title.toLowerCase() !== "warmup";
     });
     return new Fuse(nonWarmups, {
-      keys: ['summary', 'first_user_message'],
+      keys: ['title', 'summary', 'first_user_message'],
       threshold: 0.4,
       includeScore: true,
       ignoreLocation: true,
@@ -546,27 +660,56 @@ export function ProjectsPage() {
     return { filteredSessions: combined, contentMatches: contentMatchMap, warmupSessions: warmups };
   }, [sessions, debouncedSearchQuery, fuse, contentSearchResults]);
 
-  const handleExportSession = async (sessionId: string) => {
+  const handleExportSession = async (sessionId: string, theme: ExportTheme) => {
     if (!selectedProject) return;
     try {
-      const html = await invoke<string>("export_session_to_html", {
-        projectPath: selectedProject.path,
-        sessionId
-      });
-
-      const path = await save({
+      const formats = await invoke<ExportFormatInfo[]>("get_session_export_formats");
+      const path = await invoke<string | null>("pick_path", {
+        mode: "save",
         defaultPath: `claude-session-${sessionId.slice(0, 8)}.html`,
-        filters: [{ name: "HTML", extensions: ["html"] }]
+        filters: formats.map((f) => ({ name: f.label, extensions: [f.extension] })),
       });
 
+      // Written by the backend, so the export never passes through the webview
       if (path) {
-        await writeTextFile(path, html);
+        const format = formats.find((f) => path.toLowerCase().endsWith(`.${f.extension}`))?.format ?? "html";
+        await invoke("export_session_to_file", {
+          projectPath: selectedProject.path,
+          sessionId,
+          path,
+          format,
+          options: { theme },
+        });
       }
     } catch (e) {
       console.error("Failed to export session:", e);
     }
   };
 
+  // Archive keeps the session until restored; delete moves it to the trash
+  const handleRemoveSession = async (sessionId: string, kind: "archive" | "delete") => {
+    if (!selectedProject) return;
+    const confirmed = await ask(
+      kind === "archive"
+        ? "Move this session out of Claude's projects folder into claudit's archive? Its usage no longer counts in stats until you restore it."
+        : "Move this session to claudit's trash? It is deleted for good when you empty the trash.",
+      { title: kind === "archive" ? "Archive session" : "Delete session", kind: "warning" }
+    );
+    if (!confirmed) return;
+    try {
+      await invoke(kind === "archive" ? "archive_session" : "delete_session", {
+        projectPath: selectedProject.path,
+        sessionId,
+      });
+      setFollowing(false);
+      setSelectedSession(null);
+      queryClient.invalidateQueries({ queryKey: ["projectSessions", selectedProject.path] });
+      queryClient.invalidateQueries({ queryKey: ["removed-sessions"] });
+    } catch (e) {
+      console.error(`Failed to ${kind} session:`, e);
+    }
+  };
+
   // Project Detail View
   if (selectedProject) {
     return (
@@ -744,6 +887,15 @@ export function ProjectsPage() {
                     </div>
                   </div>
 
+                  {/* Spend over time */}
+                  <CostHistory projectPath={selectedProject.path} />
+
+                  {/* Monorepo packages */}
+                  <WorkspaceBreakdown projectPath={selectedProject.path} />
+
+                  {/* Checklist extracted from sessions */}
+                  <ActionItems projectPath={selectedProject.path} />
+
                   {/* Quick Info */}
                   <div className="grid grid-cols-1 sm:grid-cols-2 gap-4">
                     <div className="p-4 rounded-lg bg-zinc-900/50 border border-zinc-800/50">
@@ -753,6 +905,10 @@ export function ProjectsPage() {
                           <span className="text-muted-foreground">Last used</span>
                           <span>{formatDate(selectedProject.lastUsed)}</span>
                         </div>
+                        <div className="flex justify-between">
+                          <span className="text-muted-foreground">Sessions</span>
+                          <span>{selectedProject.sessionCount}</span>
+                        </div>
                         <div className="flex justify-between">
                           <span className="text-muted-foreground">Has CLAUDE.md</span>
                           <span>{selectedProject.hasClaude ? "Yes" : "No"}</span>
@@ -1295,10 +1451,20 @@ export function ProjectsPage() {
                                     {formatTokens(session.total_input_tokens + session.total_output_tokens)} tokens
                                   </span>
                                   {session.total_cost > 0 && (
-                                    <span className="text-xs bg-emerald-500/10 text-emerald-500 px-1.5 py-0.5 rounded">
+                                    <span
+                                      className="text-xs bg-emerald-500/10 text-emerald-500 px-1.5 py-0.5 rounded"
+                                      title={session.model_breakdown
+                                        .map((m) => `${m.model.replace("claude-", "")}: ${formatCost(m.cost)}`)
+                                        .join("\n")}
+                                    >
                                       {formatCost(session.total_cost)}
                                     </span>
                                   )}
+                                  {session.model_breakdown.length > 1 && (
+                                    <span className="text-xs bg-zinc-800 px-1.5 py-0.5 rounded">
+                                      {session.model_breakdown.length} models
+                                    </span>
+                                  )}
                                 </div>
                               </button>
                             );
@@ -1384,12 +1550,21 @@ export function ProjectsPage() {
                     <ConversationViewer
                       conversation={conversation}
                       isLoading={conversationLoading}
+                      projectPath={selectedProject.path}
                       sessionId={selectedSession}
-                      sessionTitle={conversation?.summary || sessions?.find(s => s.session_id === selectedSession)?.first_user_message || "Session"}
-                      messageCount={conversation?.messages.length || 0}
+                      sessionTitle={sessions?.find(s => s.session_id === selectedSession)?.title || conversation?.summary || sessions?.find(s => s.session_id === selectedSession)?.first_user_message || "Session"}
+                      messageCount={conversation?.total_messages || conversation?.messages.length || 0}
                       onCopyResumeCmd={handleCopyResumeCmd}
                       onOpenTerminalWithResume={handleOpenTerminalWithResume}
                       onExportSession={handleExportSession}
+                      onArchiveSession={(id) => handleRemoveSession(id, "archive")}
+                      onDeleteSession={(id) => handleRemoveSession(id, "delete")}
+                      onRenameSession={handleRenameSession}
+                      following={following}
+                      onToggleFollow={() => setFollowing((prev) => !prev)}
+                      hasMore={hasMoreMessages}
+                      loadingMore={loadingMoreMessages}
+                      onLoadMore={loadMoreMessages}
                     />
                   </div>
                 </div>
@@ -1608,6 +1783,8 @@ export function ProjectsPage() {
                     </h3>
                     <p className="text-xs text-muted-foreground truncate mt-0.5">
                       {formatDate(project.lastUsed)}
+                      {project.sessionCount > 0 &&
+                        ` · ${project.sessionCount} session${project.sessionCount === 1 ? "" : "s"}`}
                     </p>
                   </div>
                 </div>
@@ -0,0 +1,64 @@
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { ListChecks, Trash2 } from "lucide-react";
+import { cn } from "../../../lib/utils";
+import type { ActionItem } from "../../../types";
+
+/** Checklist of action items extracted from the project's sessions; hidden while empty */
+export function ActionItems({ projectPath }: { projectPath: string }) {
+  const queryClient = useQueryClient();
+  const { data: items } = useQuery({
+    queryKey: ["action-items", projectPath],
+    queryFn: () => invoke<ActionItem[]>("get_action_items", { projectPath }),
+  });
+
+  const invalidate = () => queryClient.invalidateQueries({ queryKey: ["action-items", projectPath] });
+  const setDone = useMutation({
+    mutationFn: ({ id, done }: { id: string; done: boolean }) =>
+      invoke("set_action_item_done", { projectPath, id, done }),
+    onSuccess: invalidate,
+  });
+  const remove = useMutation({
+    mutationFn: (id: string) => invoke("remove_action_item", { projectPath, id }),
+    onSuccess: invalidate,
+  });
+
+  if (!items || items.length === 0) return null;
+
+  const open = items.filter((item) => !item.done).length;
+
+  return (
+    <div className="p-4 rounded-lg bg-zinc-900/50 border border-zinc-800/50">
+      <h3 className="text-sm font-medium mb-3 flex items-center gap-2">
+        <ListChecks className="w-4 h-4 text-muted-foreground" />
+        Action Items
+        <span className="text-xs text-muted-foreground font-normal">· {open} open</span>
+      </h3>
+      <div className="space-y-1">
+        {items.map((item) => (
+          <div key={item.id} className="group flex items-start gap-2 text-sm">
+            <input
+              type="checkbox"
+              checked={item.done}
+              onChange={(e) => setDone.mutate({ id: item.id, done: e.target.checked })}
+              className="mt-1 shrink-0"
+            />
+            <span
+              className={cn("flex-1", item.done && "line-through text-muted-foreground")}
+              title={`From session ${item.session_id.slice(0, 8)}`}
+            >
+              {item.text}
+            </span>
+            <button
+              onClick={() => remove.mutate(item.id)}
+              className="p-0.5 text-muted-foreground hover:text-foreground opacity-0 group-hover:opacity-100 transition-opacity"
+              title="Remove"
+            >
+              <Trash2 className="w-3 h-3" />
+            </button>
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+}
@@ -14,21 +14,50 @@ import {
   Loader2,
   Search,
   Clipboard,
+  StickyNote,
+  Star,
+  FileText,
+  Radio,
+  Moon,
+  Sun,
+  Archive,
+  Trash2,
+  Pencil,
 } from "lucide-react";
+import { useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
 import { cn } from "../../../lib/utils";
 import { MarkdownViewer } from "../../../components/MarkdownViewer";
 import { ConversationSearch } from "./ConversationSearch";
-import type { SessionConversation, ConversationMessage, MessageContentBlock } from "../../../types";
+import { SessionNotes } from "./SessionNotes";
+import type {
+  SessionConversation,
+  ConversationMessage,
+  MessageContentBlock,
+  ExportTheme,
+  SubagentThread,
+} from "../../../types";
 
 interface ConversationViewerProps {
   conversation: SessionConversation | undefined;
   isLoading: boolean;
+  projectPath: string;
   sessionId: string | null;
   sessionTitle: string;
   messageCount: number;
+  /** More messages follow the loaded ones; onLoadMore fetches the next page */
+  hasMore?: boolean;
+  loadingMore?: boolean;
+  onLoadMore?: () => void;
   onCopyResumeCmd: (sessionId: string) => void;
   onOpenTerminalWithResume: (sessionId: string) => void;
-  onExportSession: (sessionId: string) => void;
+  onExportSession: (sessionId: string, theme: ExportTheme) => void;
+  onArchiveSession?: (sessionId: string) => void;
+  onDeleteSession?: (sessionId: string) => void;
+  /** Set the title shown for the session; null goes back to the summary */
+  onRenameSession?: (sessionId: string, title: string | null) => void;
+  following: boolean;
+  onToggleFollow: () => void;
 }
 
 // Helper to format date/time
@@ -43,23 +72,59 @@ function formatDateTime(dateStr?: string | null) {
   });
 }
 
+// How long a tool call took, e.g. "850ms" or "12.4s"
+function formatToolDuration(ms: number) {
+  if (ms < 1000) return `${ms}ms`;
+  if (ms < 60_000) return `${(ms / 1000).toFixed(1)}s`;
+  return `${Math.round(ms / 60_000)}m`;
+}
+
 export function ConversationViewer({
   conversation,
   isLoading,
+  projectPath,
   sessionId,
   sessionTitle,
   messageCount,
   onCopyResumeCmd,
   onOpenTerminalWithResume,
   onExportSession,
+  onArchiveSession,
+  onRenameSession,
+  onDeleteSession,
+  following,
+  onToggleFollow,
+  hasMore = false,
+  loadingMore = false,
+  onLoadMore,
 }: ConversationViewerProps) {
   const [copiedResumeCmd, setCopiedResumeCmd] = useState(false);
+  const [copiedMarkdown, setCopiedMarkdown] = useState(false);
+  const [exportTheme, setExportTheme] = useState<ExportTheme>("dark");
   const [expandedTools, setExpandedTools] = useState<Set<string>>(new Set());
+  const [showNotes, setShowNotes] = useState(false);
+  const [titleDraft, setTitleDraft] = useState<string | null>(null);
+  const queryClient = useQueryClient();
   // In-conversation search state
   const [showSearch, setShowSearch] = useState(false);
   const [searchQuery, setSearchQuery] = useState("");
   const [currentMatchIndex, setCurrentMatchIndex] = useState(0);
   const matchRefs = useRef<Map<number, HTMLElement>>(new Map());
+  const messagesRef = useRef<HTMLDivElement>(null);
+
+  // Keep the newest message in view while following
+  const lastMessage = conversation?.messages[conversation.messages.length - 1];
+  useEffect(() => {
+    if (following && messagesRef.current) {
+      messagesRef.current.scrollTo({ top: messagesRef.current.scrollHeight, behavior: "smooth" });
+    }
+  }, [following, lastMessage]);
+
+  const starPrompt = async (text: string) => {
+    await invoke("star_prompt", { text, projectPath, sessionId });
+    queryClient.invalidateQueries({ queryKey: ["starred-prompts"] });
+    queryClient.invalidateQueries({ queryKey: ["prompt-outcomes"] });
+  };
 
   // Reset search when session changes
   useEffect(() => {
@@ -91,6 +156,16 @@ export function ConversationViewer({
     return found;
   }, [conversation?.messages, searchQuery]);
 
+  const subagentsByCall = useMemo(
+    () =>
+      new Map(
+        (conversation?.subagents ?? []).flatMap((thread) =>
+          thread.tool_use_id ? [[thread.tool_use_id, thread] as const] : []
+        )
+      ),
+    [conversation?.subagents]
+  );
+
   // Reset match index when search changes
   useEffect(() => {
     setCurrentMatchIndex(0);
@@ -151,6 +226,19 @@ export function ConversationViewer({
     setTimeout(() => setCopiedResumeCmd(false), 2000);
   };
 
+  // Markdown of the whole session, for pasting into PRs and docs
+  const handleCopyMarkdown = async () => {
+    if (!sessionId) return;
+    try {
+      const markdown = await invoke<string>("export_session_markdown", { projectPath, sessionId });
+      await navigator.clipboard.writeText(markdown);
+      setCopiedMarkdown(true);
+      setTimeout(() => setCopiedMarkdown(false), 2000);
+    } catch (e) {
+      console.error("Failed to copy session as Markdown:", e);
+    }
+  };
+
   // Check if a specific message/block is the current match
   const isCurrentMatch = (msgIdx: number, blockIdx: number) => {
     if (matches.length === 0) return false;
@@ -220,7 +308,37 @@ export function ConversationViewer({
       {/* Session Header */}
       <div className="flex items-center justify-between p-3 border-b border-zinc-800/50 bg-zinc-900/50 flex-shrink-0">
         <div className="flex-1 min-w-0 overflow-hidden mr-2">
-          <p className="text-sm font-medium truncate">{sessionTitle}</p>
+          {titleDraft !== null && sessionId ? (
+            <input
+              autoFocus
+              value={titleDraft}
+              onChange={(e) => setTitleDraft(e.target.value)}
+              onBlur={() => setTitleDraft(null)}
+              onKeyDown={(e) => {
+                if (e.key === "Enter") {
+                  onRenameSession?.(sessionId, titleDraft.trim() || null);
+                  setTitleDraft(null);
+                } else if (e.key === "Escape") {
+                  setTitleDraft(null);
+                }
+              }}
+              placeholder="Title (empty to use the summary)"
+              className="w-full text-sm font-medium bg-zinc-800 border border-zinc-700 rounded px-1.5 py-0.5 focus:outline-none focus:border-primary"
+            />
+          ) : (
+            <div className="flex items-center gap-1.5 min-w-0 group">
+              <p className="text-sm font-medium truncate">{sessionTitle}</p>
+              {onRenameSession && (
+                <button
+                  onClick={() => setTitleDraft(sessionTitle)}
+                  className="p-0.5 text-muted-foreground hover:text-foreground opacity-0 group-hover:opacity-100 transition-opacity flex-shrink-0"
+                  title="Rename session"
+                >
+                  <Pencil className="w-3 h-3" />
+                </button>
+              )}
+            </div>
+          )}
           <p className="text-xs text-muted-foreground">{messageCount} messages</p>
         </div>
         <div className="flex items-center gap-2 flex-shrink-0">
@@ -234,6 +352,16 @@ export function ConversationViewer({
           >
             <Search className="w-3 h-3" />
           </button>
+          <button
+            onClick={() => setShowNotes((prev) => !prev)}
+            className={cn(
+              "flex items-center gap-1.5 px-2 py-1 text-xs rounded transition-colors",
+              showNotes ? "bg-primary/20 text-primary" : "bg-zinc-800 hover:bg-zinc-700"
+            )}
+            title="Session notes and action items"
+          >
+            <StickyNote className="w-3 h-3" />
+          </button>
           <button
             onClick={handleCopyResumeCmd}
             className="flex items-center gap-1.5 px-2 py-1 text-xs bg-zinc-800 hover:bg-zinc-700 rounded transition-colors"
@@ -251,6 +379,17 @@ export function ConversationViewer({
               </>
             )}
           </button>
+          <button
+            onClick={onToggleFollow}
+            className={cn(
+              "flex items-center gap-1.5 px-2 py-1 text-xs rounded transition-colors",
+              following ? "bg-emerald-500/20 text-emerald-500" : "bg-zinc-800 hover:bg-zinc-700"
+            )}
+            title={following ? "Stop following this session" : "Follow new messages as Claude writes them"}
+          >
+            <Radio className={cn("w-3 h-3", following && "animate-pulse")} />
+            {following ? "Live" : "Follow"}
+          </button>
           <button
             onClick={() => onOpenTerminalWithResume(sessionId)}
             className="flex items-center gap-1.5 px-2 py-1 text-xs bg-zinc-800 hover:bg-zinc-700 rounded transition-colors"
@@ -260,16 +399,55 @@ export function ConversationViewer({
             Resume
           </button>
           <button
-            onClick={() => onExportSession(sessionId)}
+            onClick={handleCopyMarkdown}
+            className="flex items-center gap-1.5 px-2 py-1 text-xs bg-zinc-800 hover:bg-zinc-700 rounded transition-colors"
+            title="Copy the session as Markdown"
+          >
+            {copiedMarkdown ? (
+              <Check className="w-3 h-3 text-emerald-500" />
+            ) : (
+              <FileText className="w-3 h-3" />
+            )}
+            Markdown
+          </button>
+          <button
+            onClick={() => setExportTheme((prev) => (prev === "dark" ? "light" : "dark"))}
             className="flex items-center gap-1.5 px-2 py-1 text-xs bg-zinc-800 hover:bg-zinc-700 rounded transition-colors"
-            title="Export to HTML"
+            title={`HTML and PDF exports use the ${exportTheme} theme`}
+          >
+            {exportTheme === "dark" ? <Moon className="w-3 h-3" /> : <Sun className="w-3 h-3" />}
+          </button>
+          <button
+            onClick={() => onExportSession(sessionId, exportTheme)}
+            className="flex items-center gap-1.5 px-2 py-1 text-xs bg-zinc-800 hover:bg-zinc-700 rounded transition-colors"
+            title="Export to HTML, Markdown, JSON or PDF"
           >
             <Download className="w-3 h-3" />
             Export
           </button>
+          {onArchiveSession && (
+            <button
+              onClick={() => onArchiveSession(sessionId)}
+              className="flex items-center gap-1.5 px-2 py-1 text-xs bg-zinc-800 hover:bg-zinc-700 rounded transition-colors"
+              title="Archive: move out of Claude's projects folder (restorable)"
+            >
+              <Archive className="w-3 h-3" />
+            </button>
+          )}
+          {onDeleteSession && (
+            <button
+              onClick={() => onDeleteSession(sessionId)}
+              className="flex items-center gap-1.5 px-2 py-1 text-xs bg-zinc-800 hover:bg-red-500/20 hover:text-red-400 rounded transition-colors"
+              title="Delete: move to claudit's trash"
+            >
+              <Trash2 className="w-3 h-3" />
+            </button>
+          )}
         </div>
       </div>
 
+      {showNotes && <SessionNotes projectPath={projectPath} sessionId={sessionId} />}
+
       {/* Search Bar */}
       <ConversationSearch
         isOpen={showSearch}
@@ -286,27 +464,69 @@ export function ConversationViewer({
       />
 
       {/* Messages */}
-      <div className="flex-1 overflow-y-auto overflow-x-hidden p-4 space-y-4">
+      <div
+        ref={messagesRef}
+        onScroll={(e) => {
+          // Load the next page before the reader reaches the end
+          const el = e.currentTarget;
+          if (hasMore && !loadingMore && el.scrollHeight - el.scrollTop - el.clientHeight < 800) {
+            onLoadMore?.();
+          }
+        }}
+        className="flex-1 overflow-y-auto overflow-x-hidden p-4 space-y-4"
+      >
         {isLoading ? (
           <div className="flex items-center justify-center py-12 text-muted-foreground">
             <Loader2 className="w-5 h-5 animate-spin mr-2" />
             Loading conversation...
           </div>
         ) : (
-          conversation?.messages.map((msg, msgIdx) => (
-            <MessageBubble
-              key={msg.uuid}
-              message={msg}
-              msgIdx={msgIdx}
-              expandedTools={expandedTools}
-              onToggleTool={toggleToolExpanded}
-              searchQuery={searchQuery}
-              isCurrentMatch={isCurrentMatch}
-              highlightMatches={highlightMatches}
-              matchRefs={matchRefs}
-              matches={matches}
-            />
-          ))
+          <>
+            {conversation?.messages.map((msg, msgIdx) => (
+              <MessageBubble
+                key={msg.uuid}
+                message={msg}
+                msgIdx={msgIdx}
+                expandedTools={expandedTools}
+                onToggleTool={toggleToolExpanded}
+                searchQuery={searchQuery}
+                isCurrentMatch={isCurrentMatch}
+                highlightMatches={highlightMatches}
+                matchRefs={matchRefs}
+                matches={matches}
+                onStar={starPrompt}
+                subagents={subagentsByCall}
+              />
+            ))}
+            {/* Subagents whose Task call couldn't be found */}
+            {conversation?.subagents
+              ?.filter((thread) => !thread.tool_use_id)
+              .map((thread, idx) => (
+                <SubagentThreadView
+                  key={thread.agent_id ?? idx}
+                  thread={thread}
+                  threadKey={`unmatched-${thread.agent_id ?? idx}`}
+                  expandedTools={expandedTools}
+                  onToggleTool={toggleToolExpanded}
+                  matchRefs={matchRefs}
+                  onStar={starPrompt}
+                />
+              ))}
+          </>
+        )}
+        {hasMore && !isLoading && (
+          <div className="flex justify-center py-2">
+            <button
+              onClick={onLoadMore}
+              disabled={loadingMore}
+              className="px-3 py-1.5 text-xs bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded-lg transition-colors disabled:opacity-50 flex items-center gap-2"
+            >
+              {loadingMore && <Loader2 className="w-3 h-3 animate-spin" />}
+              {loadingMore
+                ? "Loading..."
+                : `Load more (${messageCount - (conversation?.offset ?? 0) - (conversation?.messages.length ?? 0)} remaining)`}
+            </button>
+          </div>
         )}
       </div>
     </>
@@ -314,7 +534,7 @@ export function ConversationViewer({
 }
 
 // Separate component for message bubble
-interface MessageBubbleProps {
+export interface MessageBubbleProps {
   message: ConversationMessage;
   msgIdx: number;
   expandedTools: Set<string>;
@@ -324,9 +544,12 @@ interface MessageBubbleProps {
   highlightMatches: (text: string, isActive: boolean) => ReactNode;
   matchRefs: React.MutableRefObject<Map<number, HTMLElement>>;
   matches: { messageIndex: number; blockIndex: number }[];
+  onStar: (text: string) => Promise<void>;
+  /** Subagent threads by the Task call that started them, shown under the call */
+  subagents?: Map<string, SubagentThread>;
 }
 
-function MessageBubble({
+export function MessageBubble({
   message,
   msgIdx,
   expandedTools,
@@ -336,6 +559,8 @@ function MessageBubble({
   highlightMatches,
   matchRefs,
   matches,
+  onStar,
+  subagents,
 }: MessageBubbleProps) {
   // Get the match index for a specific block (for ref assignment)
   const getMatchRefIndex = (blockIdx: number): number | null => {
@@ -372,35 +597,66 @@ function MessageBubble({
           const isExpanded = expandedTools.has(toolId);
           const isMatch = isCurrentMatch(msgIdx, blockIdx);
           const matchRefIdx = getMatchRefIndex(blockIdx);
+          const thread =
+            (block.type === "tool_use" || block.type === "tool_invocation") && block.id
+              ? subagents?.get(block.id)
+              : undefined;
 
           return (
-            <ContentBlock
-              key={blockIdx}
-              block={block}
-              isExpanded={isExpanded}
-              onToggle={() => onToggleTool(toolId)}
-              role={message.role}
-              searchQuery={searchQuery}
-              isMatch={isMatch}
-              highlightMatches={highlightMatches}
-              refCallback={
-                matchRefIdx !== null
-                  ? (el: HTMLElement | null) => {
-                      if (el) {
-                        matchRefs.current.set(matchRefIdx, el);
-                      } else {
-                        matchRefs.current.delete(matchRefIdx);
+            <div key={blockIdx}>
+              <ContentBlock
+                block={block}
+                isExpanded={isExpanded}
+                onToggle={() => onToggleTool(toolId)}
+                role={message.role}
+                searchQuery={searchQuery}
+                isMatch={isMatch}
+                highlightMatches={highlightMatches}
+                onStar={message.role === "user" ? onStar : undefined}
+                refCallback={
+                  matchRefIdx !== null
+                    ? (el: HTMLElement | null) => {
+                        if (el) {
+                          matchRefs.current.set(matchRefIdx, el);
+                        } else {
+                          matchRefs.current.delete(matchRefIdx);
+                        }
                       }
-                    }
-                  : undefined
-              }
-            />
+                    : undefined
+                }
+              />
+              {thread && (
+                <SubagentThreadView
+                  thread={thread}
+                  threadKey={toolId}
+                  expandedTools={expandedTools}
+                  onToggleTool={onToggleTool}
+                  matchRefs={matchRefs}
+                  onStar={onStar}
+                />
+              )}
+            </div>
           );
         })}
         {message.timestamp && (
           <p className="text-xs text-muted-foreground mt-1">
             {formatDateTime(message.timestamp)}
             {message.model && ` • ${message.model.split("-").slice(0, 2).join("-")}`}
+            {message.cost && (
+              <span
+                title={`${message.cost.context_tokens.toLocaleString()} prompt tokens${message.context_window ? ` of ${message.context_window.toLocaleString()}` : ""} • $${message.cost.rates.input.toFixed(2)} in / $${message.cost.rates.output.toFixed(2)} out per MTok`}
+              >
+                {` • $${message.cost.cost.toFixed(4)}`}
+                {message.context_window && (
+                  <span className={cn(message.cost.context_tokens / message.context_window > 0.8 && "text-amber-500")}>
+                    {` • ${Math.round((message.cost.context_tokens / message.context_window) * 100)}% context`}
+                  </span>
+                )}
+                {message.cost.tier === "long_context" && (
+                  <span className="ml-1 px-1 rounded bg-amber-500/10 text-amber-500">long context</span>
+                )}
+              </span>
+            )}
           </p>
         )}
       </div>
@@ -408,6 +664,62 @@ function MessageBubble({
   );
 }
 
+const noMatches: { messageIndex: number; blockIndex: number }[] = [];
+
+interface SubagentThreadViewProps {
+  thread: SubagentThread;
+  threadKey: string;
+  expandedTools: Set<string>;
+  onToggleTool: (toolId: string) => void;
+  matchRefs: React.MutableRefObject<Map<number, HTMLElement>>;
+  onStar: (text: string) => Promise<void>;
+}
+
+/** A subagent's work as a collapsible thread (not included in search) */
+function SubagentThreadView({ thread, threadKey, expandedTools, onToggleTool, matchRefs, onStar }: SubagentThreadViewProps) {
+  const key = `subagent-${threadKey}`;
+  const isExpanded = expandedTools.has(key);
+  const cost = thread.messages.reduce((sum, m) => sum + (m.cost?.cost ?? 0), 0);
+
+  return (
+    <div className="mb-2 text-left">
+      <button
+        onClick={() => onToggleTool(key)}
+        className="w-full text-left text-xs p-2 rounded-lg bg-purple-500/10 text-purple-400 hover:bg-purple-500/20 transition-colors"
+      >
+        <div className="flex items-center gap-1">
+          <Bot className="w-3 h-3" />
+          <span className="font-medium">{thread.subagent_type ?? "Subagent"}</span>
+          {thread.description && <span className="truncate text-foreground/70">· {thread.description}</span>}
+          <span className="ml-auto flex-shrink-0 text-muted-foreground">
+            {thread.messages.length} messages{cost > 0 && ` · $${cost.toFixed(4)}`}
+          </span>
+          <ChevronDown className={cn("w-3 h-3 flex-shrink-0 transition-transform", isExpanded && "rotate-180")} />
+        </div>
+      </button>
+      {isExpanded && (
+        <div className="mt-2 ml-2 pl-3 border-l-2 border-purple-500/30 space-y-4">
+          {thread.messages.map((msg) => (
+            <MessageBubble
+              key={msg.uuid}
+              message={msg}
+              msgIdx={-1}
+              expandedTools={expandedTools}
+              onToggleTool={onToggleTool}
+              searchQuery=""
+              isCurrentMatch={() => false}
+              highlightMatches={(text) => text}
+              matchRefs={matchRefs}
+              matches={noMatches}
+              onStar={onStar}
+            />
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
+
 // Separate component for content blocks
 interface ContentBlockProps {
   block: MessageContentBlock;
@@ -418,6 +730,8 @@ interface ContentBlockProps {
   isMatch: boolean;
   highlightMatches: (text: string, isActive: boolean) => ReactNode;
   refCallback?: (el: HTMLElement | null) => void;
+  /** Star the text into the prompt library (user prompts only) */
+  onStar?: (text: string) => Promise<void>;
 }
 
 function ContentBlock({
@@ -429,8 +743,20 @@ function ContentBlock({
   isMatch,
   highlightMatches,
   refCallback,
+  onStar,
 }: ContentBlockProps) {
   const [copied, setCopied] = useState(false);
+  const [starred, setStarred] = useState(false);
+
+  const handleStar = async (text: string) => {
+    if (!onStar) return;
+    try {
+      await onStar(text);
+      setStarred(true);
+    } catch (e) {
+      console.error("Failed to star prompt:", e);
+    }
+  };
 
   const handleCopy = async (text: string) => {
     try {
@@ -467,6 +793,15 @@ function ContentBlock({
             <Clipboard className="w-3.5 h-3.5" />
           )}
         </button>
+        {onStar && (
+          <button
+            onClick={() => handleStar(block.text)}
+            className="absolute top-2 right-10 p-1.5 rounded bg-zinc-700/80 hover:bg-zinc-600 text-muted-foreground hover:text-foreground opacity-0 group-hover:opacity-100 transition-opacity"
+            title={starred ? "In prompt library" : "Star into prompt library"}
+          >
+            <Star className={cn("w-3.5 h-3.5", starred && "fill-amber-400 text-amber-400")} />
+          </button>
+        )}
         {searchQuery && hasMatch ? (
           <div className="whitespace-pre-wrap">{highlightMatches(block.text, isMatch)}</div>
         ) : (
@@ -538,6 +873,43 @@ function ContentBlock({
     );
   }
 
+  if (block.type === "tool_invocation") {
+    return (
+      <button
+        onClick={onToggle}
+        className="mb-2 w-full text-left text-xs p-2 rounded-lg bg-blue-500/10 text-blue-400 hover:bg-blue-500/20 transition-colors"
+      >
+        <div className="flex items-center gap-1">
+          <Wrench className="w-3 h-3" />
+          <span className="font-medium">{block.name || "Tool"}</span>
+          {block.duration_ms != null && (
+            <span className="text-muted-foreground">{formatToolDuration(block.duration_ms)}</span>
+          )}
+          <ChevronDown
+            className={cn("w-3 h-3 ml-auto transition-transform", isExpanded && "rotate-180")}
+          />
+        </div>
+        {isExpanded && block.input !== undefined && (
+          <pre className="mt-2 p-2 bg-zinc-900/50 rounded text-foreground/70 overflow-x-auto text-[10px] whitespace-pre-wrap break-words">
+            {typeof block.input === "string"
+              ? block.input
+              : JSON.stringify(block.input as Record<string, unknown>, null, 2)}
+          </pre>
+        )}
+        {isExpanded && block.result !== undefined && block.result !== null && (
+          <>
+            <div className="mt-2 text-muted-foreground">Result</div>
+            <pre className="mt-1 p-2 bg-zinc-900/50 rounded text-foreground/70 overflow-x-auto text-[10px] whitespace-pre-wrap break-words max-h-64 overflow-y-auto">
+              {typeof block.result === "string"
+                ? block.result
+                : JSON.stringify(block.result as Record<string, unknown>, null, 2)}
+            </pre>
+          </>
+        )}
+      </button>
+    );
+  }
+
   if (block.type === "tool_result") {
     return (
       <button
@@ -0,0 +1,121 @@
+import { useState } from "react";
+import { useQuery } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { Area, AreaChart, ResponsiveContainer, Tooltip, YAxis } from "recharts";
+import { format, parseISO } from "date-fns";
+import { TrendingDown, TrendingUp } from "lucide-react";
+import type { ProjectCostHistory } from "../../../types";
+
+function formatCost(n: number): string {
+  if (n >= 1) {
+    return `$${n.toFixed(2)}`;
+  }
+  return `$${n.toFixed(3)}`;
+}
+
+/** Sparkline of a project's daily spend with the trend against the previous period */
+export function CostHistory({ projectPath }: { projectPath: string }) {
+  const [days, setDays] = useState<30 | 90>(30);
+
+  const { data: history } = useQuery({
+    queryKey: ["project-cost-history", projectPath, days],
+    queryFn: () => invoke<ProjectCostHistory>("get_project_cost_history", { projectPath, days }),
+  });
+
+  if (!history) return null;
+
+  const trend = history.trend;
+  const trendText =
+    trend === null
+      ? history.total_cost > 0
+        ? `no spend in the ${days} days before`
+        : null
+      : trend >= 1.5
+        ? `trending ${trend.toFixed(1)}x vs the previous ${days} days`
+        : trend <= 0.67
+          ? `down to ${Math.round(trend * 100)}% of the previous ${days} days`
+          : `about the same as the previous ${days} days`;
+
+  return (
+    <div className="p-4 rounded-lg bg-zinc-900/50 border border-zinc-800/50">
+      <div className="flex items-center justify-between mb-2">
+        <h3 className="text-sm font-medium">Spend</h3>
+        <div className="flex bg-secondary/50 rounded-md p-0.5">
+          {([30, 90] as const).map((d) => (
+            <button
+              key={d}
+              onClick={() => setDays(d)}
+              className={`px-2 py-0.5 text-xs rounded transition-colors ${
+                days === d
+                  ? "bg-primary text-primary-foreground"
+                  : "text-muted-foreground hover:text-foreground"
+              }`}
+            >
+              {d}d
+            </button>
+          ))}
+        </div>
+      </div>
+
+      <div className="flex items-baseline gap-2 mb-2">
+        <span className="text-2xl font-semibold">{formatCost(history.total_cost)}</span>
+        <span className="text-xs text-muted-foreground">
+          {history.share_of_spend.toFixed(0)}% of all spend
+        </span>
+      </div>
+
+      <div className="h-16">
+        <ResponsiveContainer width="100%" height="100%">
+          <AreaChart data={history.daily}>
+            <defs>
+              <linearGradient id="projectSpend" x1="0" y1="0" x2="0" y2="1">
+                <stop offset="5%" stopColor="#10b981" stopOpacity={0.3} />
+                <stop offset="95%" stopColor="#10b981" stopOpacity={0} />
+              </linearGradient>
+            </defs>
+            <YAxis hide domain={[0, "auto"]} />
+            <Tooltip
+              contentStyle={{
+                backgroundColor: "hsl(220 13% 10%)",
+                border: "1px solid hsl(220 10% 16%)",
+                borderRadius: "8px",
+              }}
+              labelStyle={{ color: "hsl(220 10% 55%)" }}
+              labelFormatter={(_, payload) =>
+                payload?.[0] ? format(parseISO(payload[0].payload.date), "MMM d") : ""
+              }
+              formatter={(value: number) => [formatCost(value), "Cost"]}
+            />
+            <Area
+              type="monotone"
+              dataKey="cost"
+              stroke="#10b981"
+              strokeWidth={1.5}
+              fill="url(#projectSpend)"
+              isAnimationActive={false}
+            />
+          </AreaChart>
+        </ResponsiveContainer>
+      </div>
+
+      {trendText && (
+        <div
+          className={`mt-2 flex items-center gap-1 text-xs ${
+            trend !== null && trend >= 1.5
+              ? "text-amber-500"
+              : trend !== null && trend <= 0.67
+                ? "text-emerald-500"
+                : "text-muted-foreground"
+          }`}
+        >
+          {trend !== null && trend >= 1 ? (
+            <TrendingUp className="w-3 h-3" />
+          ) : (
+            <TrendingDown className="w-3 h-3" />
+          )}
+          {trendText}
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,75 @@
+import { useEffect, useState } from "react";
+import { useQuery, useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { ListChecks, Loader2 } from "lucide-react";
+import type { ActionItem } from "../../../types";
+
+interface SessionNotesProps {
+  projectPath: string;
+  sessionId: string;
+}
+
+/** Freeform note on a session, saved when the field loses focus, and action item extraction */
+export function SessionNotes({ projectPath, sessionId }: SessionNotesProps) {
+  const queryClient = useQueryClient();
+  const [draft, setDraft] = useState("");
+  const [extracting, setExtracting] = useState(false);
+  const [status, setStatus] = useState<string | null>(null);
+  const { data: note } = useQuery({
+    queryKey: ["session-note", projectPath, sessionId],
+    queryFn: () => invoke<string | null>("get_session_note", { projectPath, sessionId }),
+  });
+
+  useEffect(() => {
+    setDraft(note ?? "");
+    setStatus(null);
+  }, [note, sessionId]);
+
+  const save = async () => {
+    if (draft === (note ?? "")) return;
+    await invoke("set_session_note", { projectPath, sessionId, note: draft });
+    queryClient.invalidateQueries({ queryKey: ["session-note", projectPath, sessionId] });
+  };
+
+  const extract = async () => {
+    setExtracting(true);
+    try {
+      const added = await invoke<ActionItem[]>("extract_action_items", { projectPath, sessionId });
+      setStatus(
+        added.length > 0
+          ? `Added ${added.length} action item${added.length === 1 ? "" : "s"} to the project`
+          : "No new action items found"
+      );
+      queryClient.invalidateQueries({ queryKey: ["action-items", projectPath] });
+    } catch (e) {
+      setStatus(String(e));
+    } finally {
+      setExtracting(false);
+    }
+  };
+
+  return (
+    <div className="p-3 border-b border-zinc-800/50 bg-zinc-900/30 flex-shrink-0 space-y-2">
+      <textarea
+        value={draft}
+        onChange={(e) => setDraft(e.target.value)}
+        onBlur={save}
+        placeholder="Notes on this session..."
+        rows={3}
+        className="w-full px-2 py-1.5 text-xs bg-zinc-900/50 border border-zinc-800 rounded resize-y text-foreground placeholder:text-muted-foreground/50"
+      />
+      <div className="flex items-center justify-between gap-2">
+        <span className="text-xs text-muted-foreground truncate">{status}</span>
+        <button
+          onClick={extract}
+          disabled={extracting}
+          className="flex items-center gap-1.5 px-2 py-1 text-xs bg-zinc-800 hover:bg-zinc-700 rounded transition-colors disabled:opacity-50 shrink-0"
+          title="Collect TODOs and next steps from the final messages"
+        >
+          {extracting ? <Loader2 className="w-3 h-3 animate-spin" /> : <ListChecks className="w-3 h-3" />}
+          Extract Action Items
+        </button>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,53 @@
+import { useQuery } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { Boxes } from "lucide-react";
+import type { WorkspaceBreakdown as Breakdown } from "../../../types";
+
+function formatCost(n: number): string {
+  if (n >= 1) {
+    return `$${n.toFixed(2)}`;
+  }
+  return `$${n.toFixed(3)}`;
+}
+
+/** Monorepo packages of a project by spend; hidden when all work happened in one place */
+export function WorkspaceBreakdown({ projectPath }: { projectPath: string }) {
+  const { data: breakdown } = useQuery({
+    queryKey: ["workspace-breakdown", projectPath],
+    queryFn: () => invoke<Breakdown>("get_workspace_breakdown", { projectPath, days: 90 }),
+  });
+
+  if (!breakdown || breakdown.subprojects.length < 2) return null;
+
+  const total = breakdown.subprojects.reduce((sum, s) => sum + s.cost, 0);
+
+  return (
+    <div className="p-4 rounded-lg bg-zinc-900/50 border border-zinc-800/50">
+      <h3 className="text-sm font-medium mb-3 flex items-center gap-2">
+        <Boxes className="w-4 h-4 text-muted-foreground" />
+        Packages
+        <span className="text-xs text-muted-foreground font-normal">· last 90 days</span>
+      </h3>
+      <div className="space-y-2">
+        {breakdown.subprojects.map((sub) => (
+          <div key={sub.path}>
+            <div className="flex items-center justify-between text-sm">
+              <span className="font-mono truncate" title={sub.path}>
+                {sub.path === "." ? "(root)" : sub.path}
+              </span>
+              <span className="text-muted-foreground shrink-0 ml-2">
+                {formatCost(sub.cost)} · {sub.sessions} session{sub.sessions === 1 ? "" : "s"}
+              </span>
+            </div>
+            <div className="h-1 mt-1 rounded-full bg-zinc-800 overflow-hidden">
+              <div
+                className="h-full bg-primary/60"
+                style={{ width: `${total > 0 ? (sub.cost / total) * 100 : 0}%` }}
+              />
+            </div>
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+}
@@ -1,2 +1,6 @@
+export { ActionItems } from "./ActionItems";
 export { ConversationSearch } from "./ConversationSearch";
 export { ConversationViewer } from "./ConversationViewer";
+export { CostHistory } from "./CostHistory";
+export { SessionNotes } from "./SessionNotes";
+export { WorkspaceBreakdown } from "./WorkspaceBreakdown";
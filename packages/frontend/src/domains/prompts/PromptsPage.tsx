@@ -0,0 +1,215 @@
+import { useState } from "react";
+import { useQuery, useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { Check, Clipboard, Search, Star, Terminal } from "lucide-react";
+import { PageHeader } from "../../components/PageHeader";
+import type { FileOperation, PromptOutcome, StarredPrompt } from "../../types";
+
+function projectName(path: string) {
+  return path.split("/").filter(Boolean).pop() ?? path;
+}
+
+function formatTokens(tokens: number) {
+  if (tokens >= 1_000_000) return `${(tokens / 1_000_000).toFixed(1)}M`;
+  if (tokens >= 1000) return `${Math.round(tokens / 1000)}k`;
+  return `${Math.round(tokens)}`;
+}
+
+function formatDuration(ms: number) {
+  const minutes = Math.round(ms / 60_000);
+  return minutes >= 60 ? `${(minutes / 60).toFixed(1)}h` : `${minutes}m`;
+}
+
+/** Averages over the sessions that used a prompt */
+function OutcomeSummary({ outcome }: { outcome: PromptOutcome | undefined }) {
+  if (!outcome) return null;
+  const count = outcome.sessions.length;
+  if (count === 0) {
+    return <p className="mt-2 text-xs text-muted-foreground">Not found in any session yet</p>;
+  }
+  return (
+    <div className="mt-2 flex flex-wrap gap-x-4 gap-y-1 text-xs text-muted-foreground">
+      <span>
+        Used in <span className="text-foreground">{count}</span> session{count === 1 ? "" : "s"}
+      </span>
+      <span>
+        avg <span className="text-foreground">{formatTokens(outcome.avg_tokens)}</span> tokens
+      </span>
+      <span>
+        avg <span className="text-foreground">${outcome.avg_cost.toFixed(2)}</span>
+      </span>
+      <span>
+        avg <span className="text-foreground">{formatDuration(outcome.avg_duration_ms)}</span>
+      </span>
+      <span>
+        avg <span className="text-foreground">{outcome.avg_edits.toFixed(1)}</span> edits
+      </span>
+    </div>
+  );
+}
+
+/** Form that saves a prompt as a slash command for the user or its project */
+function CommandForm({ prompt, onDone }: { prompt: StarredPrompt; onDone: (message: string) => void }) {
+  const [name, setName] = useState("");
+  const [forProject, setForProject] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const save = async () => {
+    setError(null);
+    try {
+      await invoke<FileOperation[]>("prompt_to_slash_command", {
+        id: prompt.id,
+        name: name.trim(),
+        projectPath: forProject ? prompt.project : null,
+      });
+      onDone(`Saved as /${name.trim()}`);
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  return (
+    <div className="mt-3 space-y-2">
+      <div className="flex items-center gap-2">
+        <span className="text-xs text-muted-foreground">/</span>
+        <input
+          type="text"
+          value={name}
+          onChange={(e) => setName(e.target.value)}
+          placeholder="command-name"
+          className="w-40 px-2 py-1 text-xs bg-secondary/50 border border-border rounded font-mono"
+        />
+        {prompt.project && (
+          <select
+            value={forProject ? "project" : "user"}
+            onChange={(e) => setForProject(e.target.value === "project")}
+            className="px-2 py-1 text-xs bg-secondary/50 border border-border rounded"
+          >
+            <option value="user">All projects</option>
+            <option value="project">{projectName(prompt.project)} only</option>
+          </select>
+        )}
+        <button
+          onClick={save}
+          disabled={!name.trim()}
+          className="px-2 py-1 text-xs bg-primary text-primary-foreground rounded disabled:opacity-50"
+        >
+          Create
+        </button>
+      </div>
+      {error && <p className="text-xs text-red-400">{error}</p>}
+    </div>
+  );
+}
+
+/** Prompts starred from sessions, searchable and reusable */
+export function PromptsPage() {
+  const queryClient = useQueryClient();
+  const [query, setQuery] = useState("");
+  const [copiedId, setCopiedId] = useState<string | null>(null);
+  const [commandFor, setCommandFor] = useState<string | null>(null);
+  const [status, setStatus] = useState<string | null>(null);
+  const { data: prompts } = useQuery({
+    queryKey: ["starred-prompts", query],
+    queryFn: () => invoke<StarredPrompt[]>("list_starred_prompts", { query: query.trim() || null }),
+  });
+  // Scans every session, so it is fetched once rather than per search
+  const { data: outcomes } = useQuery({
+    queryKey: ["prompt-outcomes"],
+    queryFn: () => invoke<PromptOutcome[]>("get_prompt_outcomes"),
+    staleTime: 5 * 60 * 1000,
+  });
+
+  const copy = async (prompt: StarredPrompt) => {
+    await navigator.clipboard.writeText(prompt.text);
+    setCopiedId(prompt.id);
+    setTimeout(() => setCopiedId(null), 2000);
+  };
+
+  const unstar = async (id: string) => {
+    await invoke("unstar_prompt", { id });
+    queryClient.invalidateQueries({ queryKey: ["starred-prompts"] });
+    queryClient.invalidateQueries({ queryKey: ["prompt-outcomes"] });
+  };
+
+  return (
+    <div className="h-full flex flex-col">
+      <PageHeader
+        title="Prompt Library"
+        description="Prompts you starred from your sessions. Star a prompt with the star button on any of your messages in a project's conversation view; copy it from here or turn it into a slash command. Each prompt shows the sessions that used it (or a close variant) and what they averaged, so you can compare versions of a template."
+      />
+
+      <div className="flex-1 overflow-y-auto p-4 space-y-3">
+        <div className="relative">
+          <Search className="absolute left-2.5 top-1/2 -translate-y-1/2 w-4 h-4 text-muted-foreground" />
+          <input
+            type="text"
+            value={query}
+            onChange={(e) => setQuery(e.target.value)}
+            placeholder="Search prompts..."
+            className="w-full pl-8 pr-3 py-2 text-sm bg-secondary/50 border border-border rounded"
+          />
+        </div>
+        {status && <p className="text-xs text-emerald-500">{status}</p>}
+
+        {prompts?.length === 0 && (
+          <div className="flex flex-col items-center justify-center py-12 text-muted-foreground">
+            <Star className="w-12 h-12 mb-3 opacity-50" />
+            <p className="text-sm font-medium">{query ? "No matching prompts" : "No starred prompts yet"}</p>
+          </div>
+        )}
+
+        {prompts?.map((prompt) => (
+          <div key={prompt.id} className="p-4 rounded-lg bg-zinc-900/50 border border-zinc-800/50">
+            <p className="text-sm whitespace-pre-wrap break-words line-clamp-6">{prompt.text}</p>
+            <OutcomeSummary outcome={outcomes?.find((o) => o.prompt_id === prompt.id)} />
+            <div className="mt-3 flex items-center justify-between gap-2">
+              <span className="text-xs text-muted-foreground truncate">
+                {prompt.project ? projectName(prompt.project) : "Unknown project"} ·{" "}
+                {new Date(prompt.starred_at).toLocaleDateString()}
+              </span>
+              <div className="flex items-center gap-2 shrink-0">
+                <button
+                  onClick={() => copy(prompt)}
+                  className="flex items-center gap-1.5 px-2 py-1 text-xs bg-zinc-800 hover:bg-zinc-700 rounded transition-colors"
+                >
+                  {copiedId === prompt.id ? (
+                    <Check className="w-3 h-3 text-emerald-500" />
+                  ) : (
+                    <Clipboard className="w-3 h-3" />
+                  )}
+                  Copy
+                </button>
+                <button
+                  onClick={() => setCommandFor(commandFor === prompt.id ? null : prompt.id)}
+                  className="flex items-center gap-1.5 px-2 py-1 text-xs bg-zinc-800 hover:bg-zinc-700 rounded transition-colors"
+                  title="Save as a slash command"
+                >
+                  <Terminal className="w-3 h-3" />
+                  Command
+                </button>
+                <button
+                  onClick={() => unstar(prompt.id)}
+                  className="p-1 text-amber-400 hover:text-muted-foreground transition-colors"
+                  title="Remove from library"
+                >
+                  <Star className="w-4 h-4 fill-current" />
+                </button>
+              </div>
+            </div>
+            {commandFor === prompt.id && (
+              <CommandForm
+                prompt={prompt}
+                onDone={(message) => {
+                  setCommandFor(null);
+                  setStatus(message);
+                  queryClient.invalidateQueries({ queryKey: ["commands"] });
+                }}
+              />
+            )}
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1 @@
+export { PromptsPage } from "./PromptsPage";
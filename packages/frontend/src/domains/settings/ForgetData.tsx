@@ -0,0 +1,147 @@
+import { useState } from "react";
+import { useQuery, useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { EyeOff } from "lucide-react";
+import type { FileOperation, PurgeScope } from "../../types";
+
+interface ProjectInfo {
+  path: string;
+  name: string;
+}
+
+/** Remove what claudit stored about a project or date range */
+export function ForgetData() {
+  const queryClient = useQueryClient();
+  const [scope, setScope] = useState<PurgeScope>({
+    project: null,
+    start_date: null,
+    end_date: null,
+    delete_claude_files: false,
+  });
+  const [preview, setPreview] = useState<FileOperation[] | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [done, setDone] = useState(false);
+
+  const { data: projects } = useQuery({
+    queryKey: ["projects"],
+    queryFn: () => invoke<ProjectInfo[]>("list_projects"),
+  });
+
+  const edit = (changes: Partial<PurgeScope>) => {
+    setScope({ ...scope, ...changes });
+    setPreview(null);
+    setDone(false);
+  };
+
+  const run = async (dryRun: boolean) => {
+    setError(null);
+    try {
+      const operations = await invoke<FileOperation[]>("purge_data", { scope, dryRun });
+      if (dryRun) {
+        setPreview(operations);
+      } else {
+        setPreview(null);
+        setDone(true);
+        queryClient.invalidateQueries();
+      }
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const hasScope = Boolean(scope.project || scope.start_date || scope.end_date);
+
+  return (
+    <div className="py-4 border-t border-border/50 space-y-3">
+      <div className="flex items-center gap-3">
+        <div className="text-muted-foreground">
+          <EyeOff size={20} />
+        </div>
+        <div>
+          <div className="font-medium text-sm">Forget Data</div>
+          <div className="text-xs text-muted-foreground">
+            Remove activity, audit entries, notification rules and other records about a project or
+            date range. Claude's session files stay unless you include them.
+          </div>
+        </div>
+      </div>
+
+      <div className="flex flex-wrap items-center gap-2 text-xs">
+        <select
+          value={scope.project ?? ""}
+          onChange={(e) => edit({ project: e.target.value || null })}
+          className="px-2 py-1 bg-secondary/50 border border-border rounded"
+        >
+          <option value="">All projects</option>
+          {projects?.map((project) => (
+            <option key={project.path} value={project.path}>
+              {project.name}
+            </option>
+          ))}
+        </select>
+        <input
+          type="date"
+          value={scope.start_date ?? ""}
+          onChange={(e) => edit({ start_date: e.target.value || null })}
+          className="px-2 py-1 bg-secondary/50 border border-border rounded"
+        />
+        <span className="text-muted-foreground">to</span>
+        <input
+          type="date"
+          value={scope.end_date ?? ""}
+          onChange={(e) => edit({ end_date: e.target.value || null })}
+          className="px-2 py-1 bg-secondary/50 border border-border rounded"
+        />
+        <label className="flex items-center gap-1 text-muted-foreground">
+          <input
+            type="checkbox"
+            checked={scope.delete_claude_files}
+            onChange={(e) => edit({ delete_claude_files: e.target.checked })}
+          />
+          Also delete Claude's session files
+        </label>
+        <button
+          onClick={() => run(true)}
+          disabled={!hasScope}
+          className="px-3 py-1.5 bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded-lg text-sm transition-colors disabled:opacity-50"
+        >
+          Preview...
+        </button>
+      </div>
+
+      {preview && (
+        <div>
+          {preview.length === 0 ? (
+            <p className="text-xs text-muted-foreground">Nothing stored for this scope.</p>
+          ) : (
+            <ul className="space-y-1 mb-3">
+              {preview.map((op) => (
+                <li key={op.path + op.description} className="text-xs">
+                  <span className="text-foreground">{op.description}</span>
+                  <span className="text-muted-foreground font-mono ml-2">{op.path}</span>
+                </li>
+              ))}
+            </ul>
+          )}
+          <div className="flex gap-2">
+            <button
+              onClick={() => run(false)}
+              disabled={preview.length === 0}
+              className="px-3 py-1.5 bg-red-500/80 hover:bg-red-500 text-white rounded-lg text-sm transition-colors disabled:opacity-50"
+            >
+              Forget
+            </button>
+            <button
+              onClick={() => setPreview(null)}
+              className="px-3 py-1.5 bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded-lg text-sm transition-colors"
+            >
+              Cancel
+            </button>
+          </div>
+        </div>
+      )}
+      {done && <p className="text-xs text-emerald-500">Data removed.</p>}
+      {error && <p className="text-xs text-red-500">{error}</p>}
+    </div>
+  );
+}
@@ -0,0 +1,74 @@
+import { useState } from "react";
+import { useQuery, useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { ChevronRight } from "lucide-react";
+import type { HookRequest } from "../../types";
+import { cn } from "../../lib/utils";
+
+function prettyBody(body: string) {
+  try {
+    return JSON.stringify(JSON.parse(body), null, 2);
+  } catch {
+    return body;
+  }
+}
+
+/** The raw /hook requests recorded while the inspector is on, newest first */
+export function HookInspector({ enabled }: { enabled: boolean }) {
+  const queryClient = useQueryClient();
+  const [expanded, setExpanded] = useState<string | null>(null);
+  const { data: requests } = useQuery({
+    queryKey: ["hook-requests"],
+    queryFn: () => invoke<HookRequest[]>("get_recent_hook_requests"),
+    enabled,
+    refetchInterval: 2000,
+  });
+
+  if (!enabled) return null;
+
+  const clear = async () => {
+    await invoke("clear_hook_requests");
+    queryClient.invalidateQueries({ queryKey: ["hook-requests"] });
+  };
+
+  return (
+    <div className="py-4 border-b border-border/50 space-y-2 text-xs">
+      <div className="flex items-center justify-between text-muted-foreground">
+        <span>
+          {requests?.length ? `Last ${requests.length} requests (kept in memory only)` : "No requests received yet"}
+        </span>
+        {!!requests?.length && (
+          <button onClick={clear} className="px-2 py-1 bg-secondary hover:bg-secondary/80 rounded transition-colors">
+            Clear
+          </button>
+        )}
+      </div>
+      {requests?.map((request) => {
+        const key = request.received_at + request.body.length;
+        const isExpanded = expanded === key;
+        return (
+          <div key={key} className="rounded bg-secondary/30 border border-border/50">
+            <button
+              onClick={() => setExpanded(isExpanded ? null : key)}
+              className="w-full flex items-center gap-2 px-2 py-1.5 text-left"
+            >
+              <ChevronRight className={cn("w-3 h-3 transition-transform", isExpanded && "rotate-90")} />
+              <span className="font-mono text-foreground">{request.event ?? "?"}</span>
+              {!request.authorized && <span className="text-red-500">unauthorized</span>}
+              {request.error && <span className="text-amber-500 truncate">{request.error}</span>}
+              <span className="ml-auto text-muted-foreground flex-shrink-0">
+                {new Date(request.received_at).toLocaleTimeString()}
+              </span>
+            </button>
+            {isExpanded && (
+              <pre className="mx-2 mb-2 p-2 rounded bg-zinc-900/50 font-mono text-[10px] text-foreground/80 whitespace-pre-wrap break-all max-h-64 overflow-y-auto">
+                {prettyBody(request.body)}
+                {request.truncated && "\n…(truncated)"}
+              </pre>
+            )}
+          </div>
+        );
+      })}
+    </div>
+  );
+}
@@ -0,0 +1,81 @@
+import { useState } from "react";
+import { ArrowRight, Container, Trash2 } from "lucide-react";
+import { useUpdateSettings } from "../analytics/hooks";
+import type { AppSettings } from "../../types";
+
+/** Container/remote path prefixes and the host folders they correspond to */
+export function PathMappings({ settings }: { settings: AppSettings }) {
+  const updateSettings = useUpdateSettings();
+  const [from, setFrom] = useState("");
+  const [to, setTo] = useState("");
+  const mappings = settings.path_mappings;
+
+  const save = (next: AppSettings["path_mappings"]) =>
+    updateSettings.mutate(
+      { ...settings, path_mappings: next },
+      {
+        onSuccess: () => {
+          setFrom("");
+          setTo("");
+        },
+      }
+    );
+
+  return (
+    <div className="py-4 border-b border-border/50 last:border-0 space-y-3">
+      <div className="flex items-center gap-3">
+        <div className="text-muted-foreground">
+          <Container size={20} />
+        </div>
+        <div>
+          <div className="font-medium text-sm">Path Mappings</div>
+          <div className="text-xs text-muted-foreground">
+            For Claude running in a devcontainer or over SSH: sessions under a container path are
+            counted for the matching folder on this machine
+          </div>
+        </div>
+      </div>
+
+      {mappings.map((mapping) => (
+        <div key={`${mapping.from}:${mapping.to}`} className="flex items-center gap-2 text-xs">
+          <code className="flex-1 font-mono truncate">{mapping.from}</code>
+          <ArrowRight size={12} className="text-muted-foreground" />
+          <code className="flex-1 font-mono truncate">{mapping.to}</code>
+          <button
+            onClick={() => save(mappings.filter((m) => m !== mapping))}
+            className="p-1 text-muted-foreground hover:text-red-500 transition-colors"
+            title="Remove mapping"
+          >
+            <Trash2 size={14} />
+          </button>
+        </div>
+      ))}
+
+      <div className="flex items-center gap-2">
+        <input
+          value={from}
+          onChange={(e) => setFrom(e.target.value)}
+          placeholder="Container path, e.g. /workspaces"
+          className="flex-1 px-2 py-1 text-xs font-mono bg-secondary/50 border border-border rounded"
+        />
+        <input
+          value={to}
+          onChange={(e) => setTo(e.target.value)}
+          placeholder="Host path, e.g. /Users/me/code"
+          className="flex-1 px-2 py-1 text-xs font-mono bg-secondary/50 border border-border rounded"
+        />
+        <button
+          onClick={() => save([...mappings, { from: from.trim(), to: to.trim() }])}
+          disabled={!from.trim() || !to.trim()}
+          className="px-2 py-1 text-xs bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded transition-colors disabled:opacity-50"
+        >
+          Add mapping
+        </button>
+      </div>
+
+      {updateSettings.error && (
+        <p className="text-xs text-red-500">{String(updateSettings.error)}</p>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,91 @@
+import { useState } from "react";
+import { useQuery, useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { Archive, RotateCcw, Trash2 } from "lucide-react";
+import type { RemovedSession } from "../../types";
+
+function formatBytes(bytes: number): string {
+  if (bytes >= 1_048_576) return `${(bytes / 1_048_576).toFixed(1)} MB`;
+  if (bytes >= 1024) return `${(bytes / 1024).toFixed(0)} KB`;
+  return `${bytes} B`;
+}
+
+/** Sessions archived or deleted from the Projects page, with restore and empty trash */
+export function RemovedSessions() {
+  const queryClient = useQueryClient();
+  const [error, setError] = useState<string | null>(null);
+  const { data: removed } = useQuery({
+    queryKey: ["removed-sessions"],
+    queryFn: () => invoke<RemovedSession[]>("list_removed_sessions"),
+  });
+  const trashed = removed?.filter((s) => s.kind === "trashed") ?? [];
+
+  const run = async (command: string, args: Record<string, unknown> = {}) => {
+    setError(null);
+    try {
+      await invoke(command, args);
+      queryClient.invalidateQueries({ queryKey: ["removed-sessions"] });
+      queryClient.invalidateQueries({ queryKey: ["projectSessions"] });
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  return (
+    <div className="py-4 border-t border-border/50 space-y-3">
+      <div className="flex items-center justify-between gap-3">
+        <div className="flex items-center gap-3">
+          <div className="text-muted-foreground">
+            <Archive size={20} />
+          </div>
+          <div>
+            <p className="text-sm font-medium text-foreground">Archived & Deleted Sessions</p>
+            <p className="text-xs text-muted-foreground">
+              Sessions moved out of Claude's projects folder. Restore puts them back where they were
+            </p>
+          </div>
+        </div>
+        <button
+          onClick={() => run("empty_session_trash")}
+          disabled={trashed.length === 0}
+          className="px-3 py-1.5 bg-secondary hover:bg-red-500/20 hover:text-red-400 text-secondary-foreground rounded-lg text-sm transition-colors disabled:opacity-50 flex items-center gap-2"
+        >
+          <Trash2 size={14} />
+          Empty trash{trashed.length > 0 ? ` (${trashed.length})` : ""}
+        </button>
+      </div>
+      {removed && removed.length > 0 ? (
+        <ul className="space-y-1 text-xs">
+          {removed.map((session) => (
+            <li key={session.stored_path} className="flex items-center gap-2">
+              <span
+                className={
+                  session.kind === "trashed"
+                    ? "px-1.5 py-0.5 rounded bg-red-500/10 text-red-400"
+                    : "px-1.5 py-0.5 rounded bg-secondary text-muted-foreground"
+                }
+              >
+                {session.kind === "trashed" ? "Trash" : "Archive"}
+              </span>
+              <span className="flex-1 truncate text-foreground" title={session.project_path}>
+                {session.project_path.split("/").filter(Boolean).pop()} · {session.session_id.slice(0, 8)}
+              </span>
+              <span className="text-muted-foreground">{formatBytes(session.bytes)}</span>
+              <span className="text-muted-foreground">{new Date(session.removed_at).toLocaleDateString()}</span>
+              <button
+                onClick={() => run("restore_session", { sessionId: session.session_id })}
+                className="p-1 text-muted-foreground hover:text-foreground"
+                title="Restore"
+              >
+                <RotateCcw size={12} />
+              </button>
+            </li>
+          ))}
+        </ul>
+      ) : (
+        <p className="text-xs text-muted-foreground">No archived or deleted sessions</p>
+      )}
+      {error && <p className="text-xs text-red-400">{error}</p>}
+    </div>
+  );
+}
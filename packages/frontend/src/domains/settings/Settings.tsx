@@ -1,13 +1,33 @@
 import { useState, useEffect } from "react";
-import { useQueryClient } from "@tanstack/react-query";
-import { useSettings, useUpdateSettings, useHooksStatus, useInstallHooks, useHookPort, useModelPricing } from "../analytics/hooks";
+import { useQuery, useQueryClient } from "@tanstack/react-query";
+import { useSettings, useUpdateSettings, useHooksStatus, useInstallHooks, useHookPort, useModelPricing, usePricingHistory } from "../analytics/hooks";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import { isPermissionGranted, requestPermission, sendNotification } from "@tauri-apps/plugin-notification";
 import { open } from "@tauri-apps/plugin-dialog";
-import type { AppSettings } from "../../types";
+import type {
+  AppSettings,
+  ClaudePaths,
+  CostFormat,
+  DataGapStatus,
+  FileOperation,
+  ReindexProgress,
+  ResetOptions,
+  RoundingMode,
+  SummaryTemplateInfo,
+  TextStyle,
+  WslDistro,
+} from "../../types";
+import { ForgetData } from "./ForgetData";
+import { PathMappings } from "./PathMappings";
+import { RemovedSessions } from "./RemovedSessions";
+import { TelemetryPreview } from "./TelemetryPreview";
+import { HookInspector } from "./HookInspector";
+import { ToolNotificationRules } from "./ToolNotificationRules";
+import { TrustedPackKeys } from "./TrustedPackKeys";
 import {
   Bell,
+  BellOff,
   Check,
   Settings as SettingsIcon,
   Webhook,
@@ -17,6 +37,26 @@ import {
   Terminal,
   FolderOpen,
   DollarSign,
+  Monitor,
+  Tv,
+  RefreshCw,
+  Trash2,
+  Database,
+  Archive,
+  Sparkles,
+  PanelTop,
+  LayoutGrid,
+  Gamepad2,
+  Copy,
+  ShieldCheck,
+  Network,
+  Calculator,
+  Hash,
+  ClipboardList,
+  BarChart2,
+  Accessibility,
+  Smile,
+  Type,
 } from "lucide-react";
 
 function Toggle({
@@ -95,11 +135,113 @@ export function Settings() {
   const updateSettingsMutation = useUpdateSettings();
   const { data: hooksInstalled } = useHooksStatus();
   const { data: hookPort } = useHookPort();
+  const { data: automationToken } = useQuery({
+    queryKey: ["automation-token"],
+    queryFn: () => invoke<string>("get_automation_token"),
+    enabled: !!settings?.automation_api,
+  });
+  const remoteHooks = !!settings && !["127.0.0.1", "::1"].includes(settings.hook_bind_address);
+  const { data: hookToken } = useQuery({
+    queryKey: ["hook-token"],
+    queryFn: () => invoke<string>("get_hook_token"),
+    enabled: remoteHooks,
+  });
+  // The mute runs out on its own, so check back now and then
+  const { data: mutedUntil } = useQuery({
+    queryKey: ["notification-mute"],
+    queryFn: () => invoke<string | null>("get_notification_mute"),
+    refetchInterval: 60_000,
+  });
   const installHooksMutation = useInstallHooks();
   const { data: pricing } = useModelPricing();
+  const { data: pricingHistory } = usePricingHistory();
+  const previousPrices = pricingHistory?.filter((p) => p.effective_until) ?? [];
   const [uninstalling, setUninstalling] = useState(false);
+  const [rebuilding, setRebuilding] = useState<"sync" | "full" | null>(null);
+  const [rebuildError, setRebuildError] = useState<string | null>(null);
+  const [rebuildProgress, setRebuildProgress] = useState<ReindexProgress | null>(null);
   const [notificationPermission, setNotificationPermission] = useState<boolean | null>(null);
   const [requestingPermission, setRequestingPermission] = useState(false);
+  const [newProfileName, setNewProfileName] = useState("");
+  const [wslStatus, setWslStatus] = useState<string | null>(null);
+  const [resetOptions, setResetOptions] = useState<ResetOptions>({
+    settings: false,
+    data: false,
+    caches: false,
+    hooks: false,
+  });
+  // Dry-run result waiting for confirmation
+  const [resetPreview, setResetPreview] = useState<{
+    mode: "reset" | "uninstall";
+    operations: FileOperation[];
+  } | null>(null);
+  const [resetDone, setResetDone] = useState<string | null>(null);
+  const { data: claudePaths } = useQuery({
+    queryKey: ["claude-paths", settings?.active_profile],
+    queryFn: () => invoke<ClaudePaths>("get_claude_paths"),
+  });
+  const { data: costPreview } = useQuery({
+    queryKey: ["cost-format-preview", settings?.cost_format],
+    queryFn: () => invoke<string>("preview_cost_format", { format: settings!.cost_format }),
+    enabled: !!settings,
+  });
+
+  const { data: dataGap } = useQuery({
+    queryKey: ["data-gap-status", settings?.data_gap_days],
+    queryFn: () => invoke<DataGapStatus>("get_data_gap_status"),
+    enabled: !!settings,
+  });
+  const { data: summaryTemplate } = useQuery({
+    queryKey: ["summary-template-info"],
+    queryFn: () => invoke<SummaryTemplateInfo>("get_summary_template_info"),
+  });
+  const { data: summaryPreview } = useQuery({
+    queryKey: ["stats-summary", settings?.summary_template],
+    queryFn: () => invoke<string>("get_stats_summary", { template: settings!.summary_template }),
+    enabled: !!settings,
+  });
+  const [summaryCopied, setSummaryCopied] = useState(false);
+
+  const copySummary = async () => {
+    try {
+      await invoke("copy_stats_summary");
+      setSummaryCopied(true);
+      setTimeout(() => setSummaryCopied(false), 2000);
+    } catch (e) {
+      console.error("Failed to copy stats summary:", e);
+    }
+  };
+
+  // Add a profile for each WSL distribution with Claude data
+  const detectWsl = async () => {
+    if (!settings) return;
+    const distros = await invoke<WslDistro[]>("detect_wsl_distros");
+    const found = distros.filter(
+      (d) =>
+        d.claude_dir &&
+        !settings.claude_profiles.some((p) => p.config_dir === d.claude_dir)
+    );
+    if (found.length > 0) {
+      updateSettingsMutation.mutate({
+        ...settings,
+        claude_profiles: [
+          ...settings.claude_profiles,
+          ...found.map((d) => ({ name: `WSL ${d.name}`, config_dir: d.claude_dir! })),
+        ],
+      });
+    }
+    setWslStatus(
+      distros.length === 0
+        ? "No WSL distributions found"
+        : `Added ${found.length} WSL profile${found.length === 1 ? "" : "s"}`
+    );
+  };
+
+  const switchProfile = async (profile: string | null) => {
+    await invoke("set_active_profile", { profile });
+    // Everything read from ~/.claude changes with the profile
+    queryClient.invalidateQueries();
+  };
 
   // Check notification permission on mount
   useEffect(() => {
@@ -112,9 +254,17 @@ export function Settings() {
       // Update query cache directly with new settings
       queryClient.setQueryData(["settings"], event.payload);
     });
+    const unlistenRepair = listen<string[]>("hooks-repaired", () => {
+      queryClient.invalidateQueries({ queryKey: ["hooks-status"] });
+    });
+    const unlistenMute = listen<string | null>("notifications-muted", (event) => {
+      queryClient.setQueryData(["notification-mute"], event.payload);
+    });
 
     return () => {
       unlisten.then((fn) => fn());
+      unlistenRepair.then((fn) => fn());
+      unlistenMute.then((fn) => fn());
     };
   }, [queryClient]);
 
@@ -143,6 +293,22 @@ export function Settings() {
     }
   };
 
+  const updateCostFormat = (change: Partial<CostFormat>) => {
+    if (!settings) return;
+    updateSettingsMutation.mutate({
+      ...settings,
+      cost_format: { ...settings.cost_format, ...change },
+    });
+  };
+
+  const updateTextStyle = (change: Partial<TextStyle>) => {
+    if (!settings) return;
+    updateSettingsMutation.mutate({
+      ...settings,
+      text_style: { ...settings.text_style, ...change },
+    });
+  };
+
   const handleToggle = (key: keyof AppSettings, value: boolean) => {
     if (!settings) return;
     updateSettingsMutation.mutate({
@@ -164,6 +330,46 @@ export function Settings() {
     }
   };
 
+  const rebuildAnalytics = async (full: boolean) => {
+    setRebuilding(full ? "full" : "sync");
+    setRebuildError(null);
+    setRebuildProgress(null);
+    const unlisten = await listen<ReindexProgress>("analytics-rebuild-progress", (event) =>
+      setRebuildProgress(event.payload)
+    );
+    try {
+      setRebuildProgress(await invoke<ReindexProgress>("rebuild_analytics", { full }));
+      queryClient.invalidateQueries();
+    } catch (e) {
+      setRebuildError(String(e));
+    } finally {
+      unlisten();
+      setRebuilding(null);
+    }
+  };
+
+  const previewReset = async (mode: "reset" | "uninstall") => {
+    setResetDone(null);
+    const operations =
+      mode === "reset"
+        ? await invoke<FileOperation[]>("factory_reset", { options: resetOptions, dryRun: true })
+        : await invoke<FileOperation[]>("uninstall_claudit", { dryRun: true });
+    setResetPreview({ mode, operations });
+  };
+
+  const confirmReset = async () => {
+    if (!resetPreview) return;
+    if (resetPreview.mode === "reset") {
+      await invoke("factory_reset", { options: resetOptions });
+      setResetDone("Reset complete.");
+    } else {
+      await invoke("uninstall_claudit");
+      setResetDone("Claudit's changes were reverted. Quit Claudit from the tray and delete the app to finish uninstalling.");
+    }
+    setResetPreview(null);
+    queryClient.invalidateQueries();
+  };
+
   if (isLoading || !settings) {
     return (
       <div className="flex items-center justify-center h-64">
@@ -249,6 +455,29 @@ export function Settings() {
           </div>
         </div>
 
+        <SettingRow
+          icon={<RefreshCw size={20} />}
+          title="Repair Hooks Automatically"
+          description="Reinstall the hooks when a Claude Code update removes or changes them, with a notification"
+        >
+          <Toggle
+            enabled={settings.auto_repair_hooks}
+            onChange={(v) => handleToggle("auto_repair_hooks", v)}
+          />
+        </SettingRow>
+
+        <SettingRow
+          icon={<Terminal size={20} />}
+          title="Hook Inspector"
+          description="Keep the raw bodies of the last 100 hook requests, to debug matchers and payloads"
+        >
+          <Toggle
+            enabled={settings.hook_inspector}
+            onChange={(v) => handleToggle("hook_inspector", v)}
+          />
+        </SettingRow>
+        <HookInspector enabled={settings.hook_inspector} />
+
         <div className="py-4 border-b border-border/50">
           <div className="flex items-center justify-between">
             <div className="flex items-center gap-3">
@@ -345,6 +574,142 @@ export function Settings() {
             </select>
           </div>
         </div>
+
+        <SettingRow
+          icon={<ShieldCheck size={20} />}
+          title="Require Signed Packs"
+          description="Only install team config packs signed by a trusted key"
+        >
+          <Toggle
+            enabled={settings.require_signed_packs}
+            onChange={(v) => handleToggle("require_signed_packs", v)}
+          />
+        </SettingRow>
+        <TrustedPackKeys settings={settings} />
+      </SettingSection>
+
+      {/* Claude data location */}
+      <SettingSection title="Claude Data Location">
+        <SettingRow
+          icon={<FolderOpen size={20} />}
+          title="Current Location"
+          description={claudePaths?.config_dir ?? "~/.claude"}
+        >
+          <span className="text-xs text-muted-foreground">
+            {claudePaths?.profile ??
+              (claudePaths?.source === "env"
+                ? "CLAUDE_CONFIG_DIR"
+                : claudePaths?.source === "demo"
+                  ? "Demo data"
+                  : "Default")}
+          </span>
+        </SettingRow>
+
+        <SettingRow
+          icon={<Sparkles size={20} />}
+          title="Demo Mode"
+          description={
+            claudePaths?.source === "demo" && !settings.demo_mode
+              ? "Started with --demo; restart without it to see your own data"
+              : "Show generated sample data instead of your Claude data (read-only)"
+          }
+        >
+          <Toggle
+            enabled={claudePaths?.source === "demo"}
+            disabled={claudePaths?.source === "demo" && !settings.demo_mode}
+            onChange={async (enabled) => {
+              await invoke("set_demo_mode", { enabled });
+              queryClient.invalidateQueries();
+            }}
+          />
+        </SettingRow>
+
+        {settings.claude_profiles.map((profile) => (
+          <SettingRow
+            key={profile.name}
+            icon={<FolderOpen size={20} />}
+            title={profile.name}
+            description={profile.config_dir}
+          >
+            <div className="flex items-center gap-2">
+              <button
+                onClick={() =>
+                  switchProfile(
+                    settings.active_profile === profile.name ? null : profile.name
+                  )
+                }
+                className="px-2 py-1 text-xs bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded transition-colors"
+              >
+                {settings.active_profile === profile.name ? "Use Default" : "Use"}
+              </button>
+              <button
+                onClick={() =>
+                  updateSettingsMutation.mutate({
+                    ...settings,
+                    claude_profiles: settings.claude_profiles.filter(
+                      (p) => p.name !== profile.name
+                    ),
+                    active_profile:
+                      settings.active_profile === profile.name
+                        ? null
+                        : settings.active_profile,
+                  })
+                }
+                className="px-2 py-1 text-xs text-muted-foreground hover:text-foreground hover:bg-secondary/50 rounded transition-colors"
+              >
+                Remove
+              </button>
+            </div>
+          </SettingRow>
+        ))}
+
+        <div className="py-4 flex items-center justify-between">
+          <p className="text-xs text-muted-foreground">
+            Add a named Claude config directory (e.g. work and personal)
+          </p>
+          <div className="flex items-center gap-2">
+            <input
+              type="text"
+              value={newProfileName}
+              onChange={(e) => setNewProfileName(e.target.value)}
+              placeholder="Profile name"
+              className="w-32 px-2 py-1 text-xs bg-secondary/50 border border-border rounded text-foreground placeholder:text-muted-foreground/50"
+            />
+            <button
+              disabled={
+                !newProfileName.trim() ||
+                settings.claude_profiles.some((p) => p.name === newProfileName.trim())
+              }
+              onClick={async () => {
+                const dir = await invoke<string | null>("pick_path", {
+                  mode: "folder",
+                  title: "Select Claude Config Directory",
+                });
+                if (!dir) return;
+                updateSettingsMutation.mutate({
+                  ...settings,
+                  claude_profiles: [
+                    ...settings.claude_profiles,
+                    { name: newProfileName.trim(), config_dir: dir },
+                  ],
+                });
+                setNewProfileName("");
+              }}
+              className="p-1.5 bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded transition-colors disabled:opacity-50"
+              title="Choose folder..."
+            >
+              <FolderOpen size={14} />
+            </button>
+            <button
+              onClick={detectWsl}
+              className="px-2 py-1 text-xs bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded transition-colors"
+              title="Find Claude data in WSL distributions (Windows)"
+            >
+              Detect WSL
+            </button>
+          </div>
+        </div>
+        {wslStatus && <p className="pb-4 text-xs text-muted-foreground">{wslStatus}</p>}
       </SettingSection>
 
       {/* Notifications */}
@@ -403,6 +768,127 @@ export function Settings() {
             disabled={!hooksInstalled || !notificationPermission}
           />
         </SettingRow>
+
+        <SettingRow
+          icon={<BellOff size={20} />}
+          title="Mute Temporarily"
+          description={
+            mutedUntil
+              ? `Muted until ${new Date(mutedUntil).toLocaleString([], { weekday: "short", hour: "2-digit", minute: "2-digit" })}`
+              : "Silence all notifications for a while; they turn back on by themselves"
+          }
+        >
+          {mutedUntil ? (
+            <button
+              onClick={() => invoke("mute_notifications", { duration: null })}
+              className="px-3 py-1.5 bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded-lg text-sm transition-colors"
+            >
+              Unmute
+            </button>
+          ) : (
+            <select
+              value=""
+              onChange={(e) => {
+                if (e.target.value) invoke("mute_notifications", { duration: e.target.value });
+              }}
+              disabled={!settings.notifications_enabled}
+              className="px-3 py-1.5 text-sm bg-secondary/50 border border-border rounded text-foreground"
+            >
+              <option value="">Not muted</option>
+              <option value="one_hour">For 1 hour</option>
+              <option value="four_hours">For 4 hours</option>
+              <option value="until_tomorrow">Until tomorrow</option>
+            </select>
+          )}
+        </SettingRow>
+
+        <SettingRow
+          icon={<Bell size={20} />}
+          title="Notification Digest"
+          description="Batch finished-session notifications into one summary with counts and total cost"
+        >
+          <select
+            value={settings.notification_digest_minutes}
+            onChange={(e) => {
+              updateSettingsMutation.mutate({
+                ...settings,
+                notification_digest_minutes: Number(e.target.value),
+              });
+            }}
+            disabled={!settings.notifications_enabled}
+            className="px-3 py-1.5 text-sm bg-secondary/50 border border-border rounded text-foreground"
+          >
+            <option value={0}>Off</option>
+            <option value={15}>Every 15 minutes</option>
+            <option value={30}>Every 30 minutes</option>
+            <option value={60}>Every hour</option>
+            <option value={240}>Every 4 hours</option>
+          </select>
+        </SettingRow>
+
+        <SettingRow
+          icon={<Bell size={20} />}
+          title="Collapse Bursts"
+          description="Combine a session's finish notifications that arrive close together into one, with the count"
+        >
+          <select
+            value={settings.notification_collapse_seconds}
+            onChange={(e) => {
+              updateSettingsMutation.mutate({
+                ...settings,
+                notification_collapse_seconds: Number(e.target.value),
+              });
+            }}
+            disabled={!settings.notifications_enabled}
+            className="px-3 py-1.5 text-sm bg-secondary/50 border border-border rounded text-foreground"
+          >
+            <option value={0}>Off</option>
+            <option value={5}>Within 5 seconds</option>
+            <option value={15}>Within 15 seconds</option>
+            <option value={30}>Within 30 seconds</option>
+            <option value={60}>Within a minute</option>
+          </select>
+        </SettingRow>
+
+        <SettingRow
+          icon={<Bell size={20} />}
+          title="API Error Alerts"
+          description="Notify when many requests fail in a short time (overloaded, rate limited, server errors)"
+        >
+          <Toggle
+            enabled={settings.notify_error_spikes}
+            onChange={(v) => handleToggle("notify_error_spikes", v)}
+            disabled={!notificationPermission}
+          />
+        </SettingRow>
+
+        <SettingRow
+          icon={<AlertCircle size={20} />}
+          title="Data Gap Alerts"
+          description={
+            dataGap?.gap_detected
+              ? `No usage recorded since ${dataGap.last_entry ? new Date(dataGap.last_entry).toLocaleDateString() : "ever"} while hooks kept firing. ${dataGap.likely_causes.join(" ")}`
+              : "Warn when no usage is recorded for days while Claude's hooks keep firing - a sign that reading session files broke"
+          }
+        >
+          <select
+            value={settings.data_gap_days}
+            onChange={(e) => {
+              updateSettingsMutation.mutate({
+                ...settings,
+                data_gap_days: Number(e.target.value),
+              });
+            }}
+            className="px-3 py-1.5 text-sm bg-secondary/50 border border-border rounded text-foreground"
+          >
+            <option value={0}>Off</option>
+            <option value={1}>After 1 day</option>
+            <option value={3}>After 3 days</option>
+            <option value={7}>After 7 days</option>
+          </select>
+        </SettingRow>
+
+        <ToolNotificationRules settings={settings} />
       </SettingSection>
 
       {/* Display Options */}
@@ -474,18 +960,354 @@ export function Settings() {
         </SettingRow>
       </SettingSection>
 
+      {/* Cost rounding and separators */}
+      <SettingSection title="Cost Display">
+        <SettingRow
+          icon={<Calculator size={20} />}
+          title="Precision & Rounding"
+          description={`Used by the tray, notifications, exports and reports, e.g. ${costPreview ?? "..."}`}
+        >
+          <div className="flex gap-2">
+            <select
+              value={settings.cost_format.precision ?? "auto"}
+              onChange={(e) =>
+                updateCostFormat({
+                  precision: e.target.value === "auto" ? null : Number(e.target.value),
+                })
+              }
+              className="px-3 py-1.5 text-sm bg-secondary/50 border border-border rounded text-foreground"
+            >
+              <option value="auto">Auto</option>
+              <option value={0}>0 decimals</option>
+              <option value={2}>2 decimals</option>
+              <option value={3}>3 decimals</option>
+              <option value={4}>4 decimals</option>
+            </select>
+            <select
+              value={settings.cost_format.rounding}
+              onChange={(e) => updateCostFormat({ rounding: e.target.value as RoundingMode })}
+              className="px-3 py-1.5 text-sm bg-secondary/50 border border-border rounded text-foreground"
+            >
+              <option value="half_up">Half up</option>
+              <option value="half_even">Half even (banker's)</option>
+              <option value="down">Down</option>
+              <option value="up">Up</option>
+            </select>
+          </div>
+        </SettingRow>
+        <SettingRow
+          icon={<Hash size={20} />}
+          title="Separators"
+          description="Thousands and decimal separator"
+        >
+          <select
+            value={`${settings.cost_format.thousands_separator}|${settings.cost_format.decimal_separator}`}
+            onChange={(e) => {
+              const [thousands_separator, decimal_separator] = e.target.value.split("|");
+              updateCostFormat({ thousands_separator, decimal_separator });
+            }}
+            className="px-3 py-1.5 text-sm bg-secondary/50 border border-border rounded text-foreground"
+          >
+            <option value="|.">1234.56</option>
+            <option value=",|.">1,234.56</option>
+            <option value=".|,">1.234,56</option>
+            <option value=" |,">1 234,56</option>
+            <option value="'|.">1'234.56</option>
+          </select>
+        </SettingRow>
+      </SettingSection>
+
+      {/* Wording of tray labels and notifications */}
+      <SettingSection title="Accessibility">
+        <SettingRow
+          icon={<Type size={20} />}
+          title="Tray Labels"
+          description="Terse (1.2M tok · 45m) or spelled out for screen readers (1.2 million tokens, 45 minutes)"
+        >
+          <select
+            value={settings.text_style.labels}
+            onChange={(e) => updateTextStyle({ labels: e.target.value as TextStyle["labels"] })}
+            className="px-3 py-1.5 text-sm bg-secondary/50 border border-border rounded text-foreground"
+          >
+            <option value="terse">Terse</option>
+            <option value="verbose">Verbose</option>
+          </select>
+        </SettingRow>
+        <SettingRow
+          icon={<Smile size={20} />}
+          title="Emoji Prefixes"
+          description="Start tray sections and notification titles with an emoji"
+        >
+          <Toggle
+            enabled={settings.text_style.emoji_prefixes}
+            onChange={(v) => updateTextStyle({ emoji_prefixes: v })}
+          />
+        </SettingRow>
+        <SettingRow
+          icon={<Accessibility size={20} />}
+          title="Screen Reader Notifications"
+          description="Commas instead of symbols, and response excerpts that end between words"
+        >
+          <Toggle
+            enabled={settings.text_style.screen_reader_notifications}
+            onChange={(v) => updateTextStyle({ screen_reader_notifications: v })}
+          />
+        </SettingRow>
+      </SettingSection>
+
+      {/* Copyable stats summary (tray: Copy Stats Summary) */}
+      <SettingSection title="Stats Summary">
+        <SettingRow
+          icon={<ClipboardList size={20} />}
+          title="Summary Template"
+          description={`Copied by "Copy Stats Summary" in the tray. Placeholders: ${
+            summaryTemplate?.placeholders.map((p) => `{${p}}`).join(" ") ?? "..."
+          }`}
+        >
+          <button
+            onClick={copySummary}
+            className="px-3 py-1.5 bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded-lg text-sm transition-colors flex items-center gap-2"
+          >
+            {summaryCopied ? <Check size={14} /> : <Copy size={14} />}
+            {summaryCopied ? "Copied" : "Copy now"}
+          </button>
+        </SettingRow>
+        <div className="pb-4 space-y-2">
+          <textarea
+            key={`${settings.summary_template ?? ""}|${summaryTemplate?.default_template ?? ""}`}
+            defaultValue={settings.summary_template ?? summaryTemplate?.default_template ?? ""}
+            onBlur={(e) => {
+              const value = e.target.value.trim();
+              const template = !value || value === summaryTemplate?.default_template ? null : e.target.value;
+              if (template !== settings.summary_template) {
+                updateSettingsMutation.mutate({ ...settings, summary_template: template });
+              }
+            }}
+            rows={5}
+            spellCheck={false}
+            className="w-full px-3 py-2 text-xs font-mono bg-secondary/50 border border-border rounded text-foreground"
+          />
+          {summaryPreview && (
+            <pre className="px-3 py-2 text-xs whitespace-pre-wrap bg-secondary/30 rounded text-muted-foreground">
+              {summaryPreview}
+            </pre>
+          )}
+        </div>
+      </SettingSection>
+
+      {/* Kiosk dashboard */}
+      <SettingSection title="Dashboard Display">
+        <SettingRow
+          icon={<Tv size={20} />}
+          title="Kiosk Dashboard"
+          description="Fullscreen rotating stats for a spare monitor or TV (Esc to close)"
+        >
+          <button
+            onClick={() => invoke("open_kiosk_dashboard")}
+            className="px-3 py-1.5 bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded-lg text-sm transition-colors"
+          >
+            Open
+          </button>
+        </SettingRow>
+        <SettingRow
+          icon={<RefreshCw size={20} />}
+          title="Refresh Interval"
+          description="How often the dashboard reloads stats"
+        >
+          <select
+            value={settings.kiosk.refresh_seconds}
+            onChange={(e) => {
+              updateSettingsMutation.mutate({
+                ...settings,
+                kiosk: { ...settings.kiosk, refresh_seconds: Number(e.target.value) },
+              });
+            }}
+            className="px-3 py-1.5 text-sm bg-secondary/50 border border-border rounded text-foreground"
+          >
+            <option value={10}>10 seconds</option>
+            <option value={30}>30 seconds</option>
+            <option value={60}>1 minute</option>
+            <option value={300}>5 minutes</option>
+          </select>
+        </SettingRow>
+        <SettingRow
+          icon={<Monitor size={20} />}
+          title="Page Rotation"
+          description="How long each page is shown"
+        >
+          <select
+            value={settings.kiosk.rotate_seconds}
+            onChange={(e) => {
+              updateSettingsMutation.mutate({
+                ...settings,
+                kiosk: { ...settings.kiosk, rotate_seconds: Number(e.target.value) },
+              });
+            }}
+            className="px-3 py-1.5 text-sm bg-secondary/50 border border-border rounded text-foreground"
+          >
+            <option value={0}>Don't rotate</option>
+            <option value={10}>10 seconds</option>
+            <option value={20}>20 seconds</option>
+            <option value={60}>1 minute</option>
+          </select>
+        </SettingRow>
+      </SettingSection>
+
       {/* Advanced */}
       <SettingSection title="Advanced">
         <SettingRow
           icon={<SettingsIcon size={20} />}
           title="Compact Mode"
-          description="Use a more compact tray menu layout"
+          description="Show a one-line summary in the tray menu and collapse the details into submenus"
         >
           <Toggle
             enabled={settings.compact_mode}
             onChange={(v) => handleToggle("compact_mode", v)}
           />
         </SettingRow>
+        <SettingRow
+          icon={<PanelTop size={20} />}
+          title="Tray Popover"
+          description="Left-click the tray icon for a stats card with recent sessions and quick actions; the menu stays on right-click"
+        >
+          <Toggle
+            enabled={settings.tray_popover}
+            onChange={(v) => handleToggle("tray_popover", v)}
+          />
+        </SettingRow>
+        <SettingRow
+          icon={<LayoutGrid size={20} />}
+          title="Widget Data API"
+          description={`Publish today's cost, burn rate and running sessions for widgets at http://127.0.0.1:${hookPort ?? 3456}/api/v1/widget and in widget.json`}
+        >
+          <Toggle
+            enabled={settings.widget_api}
+            onChange={(v) => handleToggle("widget_api", v)}
+          />
+        </SettingRow>
+        <SettingRow
+          icon={<Gamepad2 size={20} />}
+          title="Automation API"
+          description="Let Stream Deck buttons and Shortcuts toggle notifications, refresh stats, resume the last session and open the dashboard"
+        >
+          <Toggle
+            enabled={settings.automation_api}
+            onChange={(v) => handleToggle("automation_api", v)}
+          />
+        </SettingRow>
+        {settings.automation_api && automationToken && (
+          <div className="pb-4 space-y-1 text-xs text-muted-foreground">
+            <p>
+              POST http://127.0.0.1:{hookPort ?? 3456}/api/v1/actions/toggle-notifications (or
+              refresh-stats, resume-last-session, open-dashboard); GET /api/v1/state for button
+              labels. Send the token as <code>Authorization: Bearer &lt;token&gt;</code>.
+            </p>
+            <div className="flex items-center gap-2">
+              <code className="font-mono truncate">{automationToken}</code>
+              <button
+                onClick={() => navigator.clipboard.writeText(automationToken)}
+                className="px-2 py-1 text-xs bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded transition-colors flex items-center gap-1"
+              >
+                <Copy size={12} />
+                Copy token
+              </button>
+            </div>
+          </div>
+        )}
+        <SettingRow
+          icon={<Network size={20} />}
+          title="Hook Server Address"
+          description="Listen beyond this machine so Claude running in a devcontainer, VM or on another computer can send hooks. Applies after restarting Claudit"
+        >
+          <select
+            value={settings.hook_bind_address}
+            onChange={(e) => {
+              updateSettingsMutation.mutate({ ...settings, hook_bind_address: e.target.value });
+            }}
+            className="px-3 py-1.5 text-sm bg-secondary/50 border border-border rounded text-foreground"
+          >
+            <option value="127.0.0.1">127.0.0.1 (this machine)</option>
+            <option value="::1">::1 (this machine, IPv6)</option>
+            <option value="0.0.0.0">0.0.0.0 (all IPv4 networks)</option>
+            <option value="::">:: (all networks)</option>
+            {!["127.0.0.1", "::1", "0.0.0.0", "::"].includes(settings.hook_bind_address) && (
+              <option value={settings.hook_bind_address}>{settings.hook_bind_address}</option>
+            )}
+          </select>
+        </SettingRow>
+        {remoteHooks && hookToken && (
+          <div className="pb-4 space-y-1 text-xs text-muted-foreground">
+            <p>
+              On the other machine or container, run the hook as{" "}
+              <code>claudit-hook --host &lt;this machine&gt; --port {hookPort ?? 3456}</code> with{" "}
+              <code>CLAUDIT_HOOK_TOKEN</code> set to this token. Hooks without it are rejected.
+            </p>
+            <div className="flex items-center gap-2">
+              <code className="font-mono truncate">{hookToken}</code>
+              <button
+                onClick={() => navigator.clipboard.writeText(hookToken)}
+                className="px-2 py-1 text-xs bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded transition-colors flex items-center gap-1"
+              >
+                <Copy size={12} />
+                Copy token
+              </button>
+            </div>
+          </div>
+        )}
+        <PathMappings settings={settings} />
+        <SettingRow
+          icon={<Monitor size={20} />}
+          title="Window Layout"
+          description="Claudit reopens at its last size, position and page"
+        >
+          <button
+            onClick={() => invoke("reset_window_layout")}
+            className="px-3 py-1.5 bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded-lg text-sm transition-colors"
+          >
+            Reset
+          </button>
+        </SettingRow>
+        <SettingRow
+          icon={<Database size={20} />}
+          title="Analytics Cache"
+          description={
+            rebuildError
+              ? `Rebuild failed: ${rebuildError}`
+              : rebuildProgress
+                ? `${rebuildProgress.done ? "Done" : "Working"}: ${rebuildProgress.files_processed}/${rebuildProgress.files_total} files, ${rebuildProgress.entries_ingested.toLocaleString()} entries ingested${
+                    rebuildProgress.errors.length > 0 ? `, ${rebuildProgress.errors.length} unreadable` : ""
+                  }`
+                : "Usage is cached in analytics.db. Re-sync picks up missed changes; a full rebuild parses every session file again"
+          }
+        >
+          <div className="flex items-center gap-2">
+            <button
+              onClick={() => rebuildAnalytics(false)}
+              disabled={rebuilding !== null}
+              className="px-3 py-1.5 bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded-lg text-sm transition-colors disabled:opacity-50 flex items-center gap-2"
+            >
+              <RefreshCw size={14} className={rebuilding === "sync" ? "animate-spin" : ""} />
+              {rebuilding === "sync" ? "Syncing..." : "Re-sync"}
+            </button>
+            <button
+              onClick={() => rebuildAnalytics(true)}
+              disabled={rebuilding !== null}
+              className="px-3 py-1.5 bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded-lg text-sm transition-colors disabled:opacity-50 flex items-center gap-2"
+            >
+              <Database size={14} className={rebuilding === "full" ? "animate-pulse" : ""} />
+              {rebuilding === "full" ? "Rebuilding..." : "Full rebuild"}
+            </button>
+          </div>
+        </SettingRow>
+        {rebuildProgress && rebuildProgress.errors.length > 0 && (
+          <ul className="pb-3 text-xs text-muted-foreground space-y-0.5">
+            {rebuildProgress.errors.map((error) => (
+              <li key={error} className="truncate" title={error}>
+                {error}
+              </li>
+            ))}
+          </ul>
+        )}
       </SettingSection>
 
       {/* Pricing Reference */}
@@ -517,7 +1339,12 @@ export function Settings() {
                 <tbody>
                   {pricing.map((model) => (
                     <tr key={model.model_name} className="border-b border-border/30 last:border-0">
-                      <td className="py-2 pr-2 text-foreground">{model.model_name}</td>
+                      <td className="py-2 pr-2 text-foreground">
+                        {model.model_name}
+                        {model.effective_from && (
+                          <span className="text-muted-foreground/60"> (since {model.effective_from})</span>
+                        )}
+                      </td>
                       <td className="text-right py-2 px-2 text-muted-foreground">${model.input.toFixed(2)}</td>
                       <td className="text-right py-2 px-2 text-muted-foreground">${model.output.toFixed(2)}</td>
                       <td className="text-right py-2 px-2 text-muted-foreground">${model.cache_read.toFixed(2)}</td>
@@ -528,6 +1355,17 @@ export function Settings() {
               </table>
             </div>
           )}
+          {previousPrices.length > 0 && (
+            <div className="mt-3 text-xs text-muted-foreground">
+              <p className="font-medium mb-1">Previous prices (applied to usage from those dates)</p>
+              {previousPrices.map((p) => (
+                <p key={`${p.model_name}-${p.effective_until}`}>
+                  {p.model_name}: ${p.input.toFixed(2)} / ${p.output.toFixed(2)}
+                  {p.effective_from ? ` from ${p.effective_from}` : ""} until {p.effective_until}
+                </p>
+              ))}
+            </div>
+          )}
           <p className="text-xs text-muted-foreground/60 mt-3">
             Source:{" "}
             <a
@@ -542,6 +1380,100 @@ export function Settings() {
         </div>
       </SettingSection>
 
+      {/* Telemetry */}
+      <SettingSection title="Telemetry">
+        <SettingRow
+          icon={<BarChart2 size={20} />}
+          title="Anonymous Usage Statistics"
+          description="Help prioritize Claudit's development by sending weekly feature counts. Off by default"
+        >
+          <Toggle
+            enabled={settings.telemetry_enabled}
+            onChange={(v) => handleToggle("telemetry_enabled", v)}
+          />
+        </SettingRow>
+        <TelemetryPreview enabled={settings.telemetry_enabled} />
+      </SettingSection>
+
+      {/* Reset & Uninstall */}
+      <SettingSection title="Reset & Uninstall">
+        {(
+          [
+            { key: "settings", icon: <SettingsIcon size={20} />, title: "Preferences", description: "Reset all settings, goals and profiles to defaults" },
+            { key: "data", icon: <Database size={20} />, title: "Claudit Data", description: "Notification rules, goal history, duplicate resolutions, Console costs" },
+            { key: "caches", icon: <Archive size={20} />, title: "Caches & History", description: "Window layout, activity feed and config audit log" },
+            { key: "hooks", icon: <Webhook size={20} />, title: "Installed Hooks", description: "Remove Claudit's hooks from ~/.claude/settings.json" },
+          ] as const
+        ).map((item) => (
+          <SettingRow key={item.key} icon={item.icon} title={item.title} description={item.description}>
+            <Toggle
+              enabled={resetOptions[item.key]}
+              onChange={(v) => {
+                setResetOptions({ ...resetOptions, [item.key]: v });
+                setResetPreview(null);
+              }}
+            />
+          </SettingRow>
+        ))}
+        <SettingRow
+          icon={<Trash2 size={20} />}
+          title="Reset or Uninstall"
+          description="Preview what will be removed before anything changes"
+        >
+          <div className="flex gap-2">
+            <button
+              onClick={() => previewReset("reset")}
+              disabled={!Object.values(resetOptions).some(Boolean)}
+              className="px-3 py-1.5 bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded-lg text-sm transition-colors disabled:opacity-50"
+            >
+              Reset Selected...
+            </button>
+            <button
+              onClick={() => previewReset("uninstall")}
+              className="px-3 py-1.5 bg-secondary hover:bg-secondary/80 text-red-400 rounded-lg text-sm transition-colors"
+            >
+              Uninstall...
+            </button>
+          </div>
+        </SettingRow>
+        {resetPreview && (
+          <div className="py-4 border-t border-border/50">
+            {resetPreview.operations.length === 0 ? (
+              <p className="text-xs text-muted-foreground">Nothing to remove.</p>
+            ) : (
+              <ul className="space-y-1 mb-3">
+                {resetPreview.operations.map((op) => (
+                  <li key={op.path + op.description} className="text-xs">
+                    <span className="text-foreground">{op.description}</span>
+                    <span className="text-muted-foreground font-mono ml-2">{op.path}</span>
+                  </li>
+                ))}
+              </ul>
+            )}
+            <div className="flex gap-2">
+              <button
+                onClick={confirmReset}
+                disabled={resetPreview.operations.length === 0}
+                className="px-3 py-1.5 bg-red-500/80 hover:bg-red-500 text-white rounded-lg text-sm transition-colors disabled:opacity-50"
+              >
+                {resetPreview.mode === "reset" ? "Confirm Reset" : "Confirm Uninstall"}
+              </button>
+              <button
+                onClick={() => setResetPreview(null)}
+                className="px-3 py-1.5 bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded-lg text-sm transition-colors"
+              >
+                Cancel
+              </button>
+            </div>
+          </div>
+        )}
+        {resetDone && (
+          <p className="py-3 text-xs text-emerald-500 border-t border-border/50">{resetDone}</p>
+        )}
+        <ForgetData />
+        <RemovedSessions />
+      </SettingSection>
+
       {/* Footer */}
       <div className="mt-8 text-center text-xs text-muted-foreground/60">
         Made by{" "}
@@ -0,0 +1,38 @@
+import { useQuery } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import type { TelemetryPreview as Preview } from "../../types";
+
+/** What telemetry collects, and the exact report it would send next */
+export function TelemetryPreview({ enabled }: { enabled: boolean }) {
+  const { data: preview } = useQuery({
+    queryKey: ["telemetry-preview", enabled],
+    queryFn: () => invoke<Preview>("get_telemetry_preview"),
+  });
+
+  return (
+    <div className="py-4 border-t border-border/50 space-y-2 text-xs text-muted-foreground">
+      <p>
+        Sent once a week: Claudit's version, your OS and architecture, how often each Claudit feature was used
+        (exports, drill-downs, session archive and the like) and the source locations of crashes, under a random id
+        made when you switch this on. Never sent: anything about your Claude usage, projects, paths, prompts or
+        costs. Switching off deletes whatever was collected and not yet sent.
+      </p>
+      {preview && (
+        <p>
+          Sent to <span className="font-mono text-foreground">{preview.endpoint}</span>
+          {preview.last_sent && <> · last report {new Date(preview.last_sent).toLocaleDateString()}</>}
+        </p>
+      )}
+      {preview?.report ? (
+        <>
+          <p className="text-foreground">Next report, exactly as it would be sent:</p>
+          <pre className="p-3 rounded-lg bg-secondary/50 border border-border font-mono text-[11px] text-foreground overflow-x-auto">
+            {JSON.stringify(preview.report, null, 2)}
+          </pre>
+        </>
+      ) : (
+        <p>Nothing is collected while telemetry is off.</p>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,120 @@
+import { useState } from "react";
+import { Plus, Trash2, Wrench } from "lucide-react";
+import { useUpdateSettings } from "../analytics/hooks";
+import type { AppSettings, ToolNotificationRule } from "../../types";
+
+const EXAMPLES: Omit<ToolNotificationRule, "id">[] = [
+  { name: "Pushed to git", enabled: true, tool: "^Bash$", input: "git push" },
+  { name: "Migration changed", enabled: true, tool: "^(Write|Edit)$", input: "migrations/" },
+];
+
+/** Editor for PostToolUse notification rules (tool and input regexes) */
+export function ToolNotificationRules({ settings }: { settings: AppSettings }) {
+  const updateSettings = useUpdateSettings();
+  const [drafts, setDrafts] = useState<ToolNotificationRule[] | null>(null);
+  const rules = drafts ?? settings.tool_notification_rules;
+
+  const edit = (id: string, changes: Partial<ToolNotificationRule>) =>
+    setDrafts(rules.map((rule) => (rule.id === id ? { ...rule, ...changes } : rule)));
+
+  const add = (template: Omit<ToolNotificationRule, "id">) =>
+    setDrafts([...rules, { ...template, id: crypto.randomUUID() }]);
+
+  const save = (next: ToolNotificationRule[]) =>
+    updateSettings.mutate(
+      { ...settings, tool_notification_rules: next },
+      { onSuccess: () => setDrafts(null) }
+    );
+
+  return (
+    <div className="py-4 border-b border-border/50 last:border-0 space-y-3">
+      <div className="flex items-center gap-3">
+        <div className="text-muted-foreground">
+          <Wrench size={20} />
+        </div>
+        <div>
+          <div className="font-medium text-sm">Tool Alerts</div>
+          <div className="text-xs text-muted-foreground">
+            Notify when a tool use matches: a regex on the tool name and one on its command, file
+            path or URL (needs the hooks installed)
+          </div>
+        </div>
+      </div>
+
+      {rules.map((rule) => (
+        <div key={rule.id} className="flex items-center gap-2">
+          <input
+            type="checkbox"
+            checked={rule.enabled}
+            onChange={(e) => edit(rule.id, { enabled: e.target.checked })}
+          />
+          <input
+            value={rule.name}
+            onChange={(e) => edit(rule.id, { name: e.target.value })}
+            placeholder="Name"
+            className="w-36 px-2 py-1 text-xs bg-secondary/50 border border-border rounded"
+          />
+          <input
+            value={rule.tool}
+            onChange={(e) => edit(rule.id, { tool: e.target.value })}
+            placeholder="Tool, e.g. ^Bash$"
+            className="w-32 px-2 py-1 text-xs font-mono bg-secondary/50 border border-border rounded"
+          />
+          <input
+            value={rule.input}
+            onChange={(e) => edit(rule.id, { input: e.target.value })}
+            placeholder="Input, e.g. git push"
+            className="flex-1 px-2 py-1 text-xs font-mono bg-secondary/50 border border-border rounded"
+          />
+          <button
+            onClick={() => setDrafts(rules.filter((r) => r.id !== rule.id))}
+            className="p-1 text-muted-foreground hover:text-red-500 transition-colors"
+            title="Remove rule"
+          >
+            <Trash2 size={14} />
+          </button>
+        </div>
+      ))}
+
+      <div className="flex items-center gap-2">
+        <button
+          onClick={() => add({ name: "New rule", enabled: true, tool: "", input: "" })}
+          className="px-2 py-1 text-xs bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded transition-colors flex items-center gap-1"
+        >
+          <Plus size={12} />
+          Add rule
+        </button>
+        {rules.length === 0 &&
+          EXAMPLES.map((example) => (
+            <button
+              key={example.name}
+              onClick={() => add(example)}
+              className="px-2 py-1 text-xs text-muted-foreground hover:text-foreground hover:bg-secondary/50 rounded transition-colors"
+            >
+              + {example.name}
+            </button>
+          ))}
+        {drafts && (
+          <>
+            <button
+              onClick={() => save(drafts)}
+              className="ml-auto px-2 py-1 text-xs bg-primary text-primary-foreground rounded transition-colors"
+            >
+              Save rules
+            </button>
+            <button
+              onClick={() => setDrafts(null)}
+              className="px-2 py-1 text-xs text-muted-foreground hover:text-foreground rounded transition-colors"
+            >
+              Cancel
+            </button>
+          </>
+        )}
+      </div>
+
+      {updateSettings.error && (
+        <p className="text-xs text-red-500">{String(updateSettings.error)}</p>
+      )}
+    </div>
+  );
+}
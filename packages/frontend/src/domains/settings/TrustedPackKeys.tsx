@@ -0,0 +1,80 @@
+import { useState } from "react";
+import { KeyRound, Trash2 } from "lucide-react";
+import { useUpdateSettings } from "../analytics/hooks";
+import type { AppSettings } from "../../types";
+
+/** minisign keys trusted to sign team config packs */
+export function TrustedPackKeys({ settings }: { settings: AppSettings }) {
+  const updateSettings = useUpdateSettings();
+  const [name, setName] = useState("");
+  const [publicKey, setPublicKey] = useState("");
+  const keys = settings.trusted_pack_keys;
+
+  const save = (next: AppSettings["trusted_pack_keys"]) =>
+    updateSettings.mutate(
+      { ...settings, trusted_pack_keys: next },
+      {
+        onSuccess: () => {
+          setName("");
+          setPublicKey("");
+        },
+      }
+    );
+
+  return (
+    <div className="py-4 border-b border-border/50 last:border-0 space-y-3">
+      <div className="flex items-center gap-3">
+        <div className="text-muted-foreground">
+          <KeyRound size={20} />
+        </div>
+        <div>
+          <div className="font-medium text-sm">Trusted Pack Signers</div>
+          <div className="text-xs text-muted-foreground">
+            Config packs signed with one of these minisign keys (pack.zip.minisig) install without
+            a trust prompt
+          </div>
+        </div>
+      </div>
+
+      {keys.map((key) => (
+        <div key={key.public_key} className="flex items-center gap-2 text-xs">
+          <span className="w-36 truncate">{key.name}</span>
+          <code className="flex-1 font-mono truncate text-muted-foreground">{key.public_key}</code>
+          <button
+            onClick={() => save(keys.filter((k) => k.public_key !== key.public_key))}
+            className="p-1 text-muted-foreground hover:text-red-500 transition-colors"
+            title="Remove key"
+          >
+            <Trash2 size={14} />
+          </button>
+        </div>
+      ))}
+
+      <div className="flex items-center gap-2">
+        <input
+          value={name}
+          onChange={(e) => setName(e.target.value)}
+          placeholder="Name, e.g. Platform team"
+          className="w-36 px-2 py-1 text-xs bg-secondary/50 border border-border rounded"
+        />
+        <input
+          value={publicKey}
+          onChange={(e) => setPublicKey(e.target.value)}
+          placeholder="Public key (RW...)"
+          className="flex-1 px-2 py-1 text-xs font-mono bg-secondary/50 border border-border rounded"
+        />
+        <button
+          onClick={() => save([...keys, { name: name.trim(), public_key: publicKey.trim() }])}
+          disabled={!name.trim() || !publicKey.trim()}
+          className="px-2 py-1 text-xs bg-secondary hover:bg-secondary/80 text-secondary-foreground rounded transition-colors disabled:opacity-50"
+        >
+          Add key
+        </button>
+      </div>
+
+      {updateSettings.error && (
+        <p className="text-xs text-red-500">{String(updateSettings.error)}</p>
+      )}
+    </div>
+  );
+}
@@ -4,3 +4,28 @@
 export function isTauri(): boolean {
   return typeof window !== "undefined" && "__TAURI_INTERNALS__" in window;
 }
+
+/**
+ * Check if this is the window with the given label (or ?label in the URL)
+ */
+function isWindow(label: string): boolean {
+  if (typeof window === "undefined") return false;
+  if (new URLSearchParams(window.location.search).has(label)) return true;
+  const internals = (window as { __TAURI_INTERNALS__?: { metadata?: { currentWindow?: { label?: string } } } })
+    .__TAURI_INTERNALS__;
+  return internals?.metadata?.currentWindow?.label === label;
+}
+
+/**
+ * Check if this is the fullscreen kiosk dashboard window
+ */
+export function isKioskWindow(): boolean {
+  return isWindow("kiosk");
+}
+
+/**
+ * Check if this is the tray popover window
+ */
+export function isPopoverWindow(): boolean {
+  return isWindow("popover");
+}
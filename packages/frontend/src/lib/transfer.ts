@@ -0,0 +1,20 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { TransferInfo } from "../types";
+
+/**
+ * Run a command that prepares a chunked transfer (e.g. start_session_transfer),
+ * read its gzipped chunks as raw bytes and decode the JSON payload.
+ * Keeps multi-MB payloads from going through a single invoke.
+ */
+export async function invokeChunked<T>(command: string, args: Record<string, unknown>): Promise<T> {
+  const info = await invoke<TransferInfo>(command, args);
+
+  const chunks: Uint8Array[] = [];
+  for (let index = 0; index < info.chunks; index++) {
+    const chunk = await invoke<ArrayBuffer>("read_transfer_chunk", { id: info.id, index });
+    chunks.push(new Uint8Array(chunk));
+  }
+
+  const stream = new Blob(chunks).stream().pipeThrough(new DecompressionStream("gzip"));
+  return JSON.parse(await new Response(stream).text()) as T;
+}
@@ -2,6 +2,9 @@ import React from "react";
 import ReactDOM from "react-dom/client";
 import { QueryClient, QueryClientProvider } from "@tanstack/react-query";
 import App from "./App";
+import { KioskDashboard } from "./domains/kiosk";
+import { TrayPopover } from "./domains/popover";
+import { isKioskWindow, isPopoverWindow } from "./lib/tauri";
 import "./index.css";
 
 const queryClient = new QueryClient({
@@ -16,7 +19,7 @@ const queryClient = new QueryClient({
 ReactDOM.createRoot(document.getElementById("root")!).render(
   <React.StrictMode>
     <QueryClientProvider client={queryClient}>
-      <App />
+      {isKioskWindow() ? <KioskDashboard /> : isPopoverWindow() ? <TrayPopover /> : <App />}
     </QueryClientProvider>
   </React.StrictMode>
 );
@@ -16,6 +16,52 @@ export interface ProjectStats {
   message_count: number;
 }
 
+export interface ServiceTierStats {
+  requests: number;
+  input_tokens: number;
+  output_tokens: number;
+  cost: number; // after the tier's discount
+  standard_cost: number;
+}
+
+/** A session moved to claudit's archive or trash */
+export interface RemovedSession {
+  session_id: string;
+  project_path: string;
+  kind: "archived" | "trashed";
+  original_path: string;
+  stored_path: string;
+  removed_at: string;
+  bytes: number;
+}
+
+/** Result of `get_data_gap_status` (also the `data-gap-detected` payload) */
+export interface DataGapStatus {
+  enabled: boolean;
+  threshold_days: number;
+  last_entry: string | null;
+  last_hook_event: string | null;
+  hook_events_since: number;
+  last_session_write: string | null;
+  cli_found: boolean;
+  gap_detected: boolean;
+  likely_causes: string[];
+}
+
+export interface SummaryTemplateInfo {
+  default_template: string;
+  placeholders: string[];
+}
+
+/** Payload of `analytics-rebuild-progress`, and the result of `rebuild_analytics` */
+export interface ReindexProgress {
+  files_total: number;
+  files_processed: number;
+  entries_ingested: number;
+  errors: string[];
+  done: boolean;
+}
+
 export interface AnalyticsStats {
   total_input_tokens: number;
   total_output_tokens: number;
@@ -33,7 +79,11 @@ export interface AnalyticsStats {
   cost_per_hour: number;
   total_cost: number;
   by_model: Record<string, ModelStats>;
+  today_by_model: Record<string, ModelStats>;
+  range_days: number | null; // set when get_stats was asked for a range
+  range_by_model: Record<string, ModelStats>;
   by_project: Record<string, ProjectStats>;
+  by_service_tier: Record<string, ServiceTierStats>;
   today_messages_count: number;
   total_messages_count: number;
   last_updated: string | null;
@@ -54,6 +104,15 @@ export interface DailyStats {
   output_tokens: number;
   cost: number;
   messages: number;
+  by_model: DailyModelStats[]; // same models/order every day, for stacked bars
+}
+
+export interface DailyModelStats {
+  model: string;
+  input_tokens: number;
+  output_tokens: number;
+  cost: number;
+  messages: number;
 }
 
 export interface HourlyStats {
@@ -74,11 +133,49 @@ export interface ProjectChartData {
   cost: number;
 }
 
+// Period each point of ChartData.daily covers ("auto" picks one from the range)
+export type ChartBucket = "day" | "week" | "month" | "auto";
+
+export type DrillDimension = "project" | "model" | "day" | "hour" | "session" | "tool" | "service_tier";
+
+/** Empty lists don't filter; since/until are YYYY-MM-DD, inclusive */
+export interface DrillDownFilters {
+  projects?: string[];
+  models?: string[];
+  sessions?: string[];
+  tools?: string[];
+  since?: string | null;
+  until?: string | null;
+}
+
+export interface DrillSort {
+  by: "cost" | "tokens" | "requests" | "tool_uses" | "keys";
+  ascending: boolean;
+}
+
+export interface DrillDownRow {
+  keys: string[]; // one per dimension, in the order requested
+  cost: number;
+  input_tokens: number;
+  output_tokens: number;
+  cache_creation_tokens: number;
+  cache_read_tokens: number;
+  requests: number;
+  tool_uses: number;
+}
+
+export interface DrillDownResult {
+  dimensions: DrillDimension[];
+  rows: DrillDownRow[];
+  total_rows: number; // before the limit
+}
+
 export interface ChartData {
-  daily: DailyStats[];
+  daily: DailyStats[]; // one point per bucket
   hourly: HourlyStats[];
   by_model: ModelChartData[];
   by_project: ProjectChartData[];
+  bucket: Exclude<ChartBucket, "auto">;
 }
 
 // Settings
@@ -93,8 +190,396 @@ export interface AppSettings {
   compact_mode: boolean;
   auto_start: boolean;
   hook_port: number;
+  hook_bind_address: string; // "127.0.0.1", "::1", "0.0.0.0" or "::" (applies on restart)
   claude_cli_path?: string;
   terminal_app: string; // "auto", "Terminal", "iTerm", "Warp", "Alacritty", "kitty"
+  respect_do_not_disturb: boolean;
+  queue_suppressed_notifications: boolean;
+  long_running_minutes: number; // 0 = off
+  data_gap_days: number; // warn after this many days without usage while hooks fire, 0 = off
+  goal?: GoalSettings | null; // null = goal mode off
+  notify_error_spikes: boolean;
+  claude_profiles: ClaudeProfile[];
+  active_profile?: string | null; // null = CLAUDE_CONFIG_DIR or ~/.claude
+  kiosk: KioskSettings;
+  demo_mode: boolean;
+  tray_popover: boolean; // left click opens the popover instead of the menu
+  notification_digest_minutes: number; // 0 = deliver each notification
+  notification_collapse_seconds: number; // 0 = notify for each Stop/SubagentStop
+  widget_api: boolean; // publish widget.json and GET /api/v1/widget
+  automation_api: boolean; // POST /api/v1/actions/... for Stream Deck / Shortcuts
+  tool_notification_rules: ToolNotificationRule[];
+  auto_repair_hooks: boolean; // reinstall hooks dropped by Claude Code updates
+  hook_inspector: boolean; // keep recent raw /hook request bodies in memory
+  trusted_pack_keys: TrustedPackKey[]; // minisign keys trusted to sign config packs
+  require_signed_packs: boolean;
+  config_sync: ConfigSyncSettings | null; // GitHub sync of ~/.claude
+  model_catalog_overrides: ModelCatalogOverride[];
+  path_mappings: PathMapping[]; // container/remote paths -> host folders
+  cost_format: CostFormat; // rounding of costs in the tray, notifications, exports and reports
+  summary_template: string | null; // layout of the copied stats summary, null = built-in
+  telemetry_enabled: boolean; // anonymous weekly counts of Claudit's own features
+  text_style: TextStyle; // wording of tray labels and notifications
+}
+
+export type RoundingMode = "half_up" | "half_even" | "down" | "up";
+
+export interface CostFormat {
+  precision: number | null; // decimal places; null = 2 from $1, 3 from $0.01, 4 below
+  rounding: RoundingMode;
+  thousands_separator: string; // "" = none
+  decimal_separator: string;
+}
+
+export type LabelStyle = "terse" | "verbose"; // "1.2M tok" or "1.2 million tokens"
+
+export interface TextStyle {
+  labels: LabelStyle;
+  emoji_prefixes: boolean; // emoji before tray sections and notification titles
+  screen_reader_notifications: boolean; // no symbols, excerpts cut between words
+}
+
+// A path prefix seen in a devcontainer or over SSH and the host folder it is
+export interface PathMapping {
+  from: string; // e.g. /workspaces
+  to: string; // e.g. /Users/me/code
+}
+
+// Correction of a model's limits; a model the catalog doesn't list is added
+export interface ModelCatalogOverride {
+  model: string; // model ID, e.g. claude-sonnet-4-5
+  context_window: number | null;
+  max_output: number | null;
+  knowledge_cutoff: string | null; // YYYY-MM
+}
+
+export interface ConfigSyncSettings {
+  remote: string; // HTTPS URL or owner/repo
+  branch: string;
+  auto_commit_minutes: number; // 0 = only when pushing
+  auto_push: boolean;
+}
+
+export interface ConfigSyncStatus {
+  configured: boolean;
+  is_repo: boolean;
+  has_token: boolean;
+  branch: string;
+  changed_files: string[];
+  ahead: number;
+  behind: number;
+  last_commit_at: string | null;
+}
+
+export interface ConfigSyncResult {
+  committed: boolean;
+  pushed: boolean;
+  pulled: boolean;
+  conflicts: string[]; // changed on both sides; nothing merged
+}
+
+export interface TrustedPackKey {
+  name: string;
+  public_key: string; // RW... line of minisign.pub
+}
+
+// Notify when a PostToolUse event matches (regexes; empty = any)
+export interface ToolNotificationRule {
+  id: string;
+  name: string; // notification title
+  enabled: boolean;
+  tool: string; // e.g. "^Bash$" or "Write|Edit"
+  input: string; // Bash command, file path, URL, ...
+}
+
+// Fullscreen kiosk dashboard
+export type KioskPage = "overview" | "cost" | "tokens" | "models" | "projects" | "hourly";
+
+export interface KioskSettings {
+  refresh_seconds: number;
+  rotate_seconds: number; // 0 = no rotation
+  pages: KioskPage[];
+}
+
+// Named Claude config directory
+export interface ClaudeProfile {
+  name: string;
+  config_dir: string;
+}
+
+export interface WslDistro {
+  name: string;
+  /** Windows path of ~/.claude inside the distribution, if it has one */
+  claude_dir: string | null;
+}
+
+// Resolved Claude data location (get_claude_paths)
+export interface ClaudePaths {
+  profile: string | null;
+  source: "profile" | "env" | "default" | "demo" | "override";
+  config_dir: string;
+  claude_json: string;
+}
+
+// Goal mode (get_goal_progress)
+export type GoalKind = "daily_spend" | "weekly_spend" | "daily_output";
+
+export interface GoalSettings {
+  kind: GoalKind;
+  target: number; // USD for spend goals, output tokens for daily_output
+}
+
+export interface GoalRecord {
+  period_start: string; // YYYY-MM-DD
+  kind: GoalKind;
+  target: number;
+  actual: number;
+  met: boolean;
+}
+
+export interface GoalProgress {
+  goal: GoalSettings;
+  current: GoalRecord;
+  percent: number;
+  current_streak: number;
+  best_streak: number;
+  adherence: number; // 0-100
+  history: GoalRecord[];
+}
+
+// Currently running Claude session (get_active_sessions)
+export interface ActiveSession {
+  session_id: string;
+  project: string;
+  project_name: string;
+  started_at: string;
+  elapsed_seconds: number;
+  last_activity: string;
+  cost: number;
+  needs_attention: boolean;
+  source: "hook" | "jsonl";
+}
+
+// Running totals of an in-progress session (get_live_session_cost, "live-session-cost" event)
+export interface LiveSessionCost {
+  session_id: string;
+  cost: number;
+  input_tokens: number;
+  output_tokens: number;
+  messages: number;
+  model: string | null;
+  last_message_at: string | null;
+}
+
+// Recorded config mutation (get_audit_log / undo_last_change)
+export interface AuditEntry {
+  id: string;
+  timestamp: string;
+  action: string;
+  path: string;
+  existed_before: boolean;
+  exists_after: boolean;
+  undoes: string | null;
+}
+
+// Files snapshotted before a risky config change (list_restore_points / rollback_to)
+export interface RestorePoint {
+  id: string;
+  name: string;
+  created_at: string;
+  files: { path: string; existed: boolean }[];
+}
+
+// What purge_data forgets: a project, a date range (YYYY-MM-DD, inclusive) or both
+export interface PurgeScope {
+  project: string | null;
+  start_date: string | null;
+  end_date: string | null;
+  delete_claude_files: boolean;
+}
+
+// File operation returned by dry-run capable commands
+export interface FileOperation {
+  kind: "create_dir" | "copy" | "write" | "remove";
+  path: string;
+  source: string | null;
+  bytes: number | null;
+  description: string;
+}
+
+// Result of import_config: the planned (dry run) or applied writes
+export interface ConfigImport {
+  created_at: string;
+  operations: FileOperation[];
+  skipped: string[]; // files left out, with the reason
+}
+
+// Per-project notification overrides (claudit metadata)
+export interface ProjectNotificationSettings {
+  muted: boolean;
+  min_session_cost: number | null; // USD
+}
+
+// User prompt starred into the prompt library
+export interface StarredPrompt {
+  id: string;
+  text: string;
+  project: string | null;
+  session_id: string | null;
+  starred_at: string;
+}
+
+// A session that used a library prompt (get_prompt_outcomes)
+export interface PromptSessionOutcome {
+  session_id: string;
+  project: string;
+  started_at: string | null;
+  similarity: number; // word overlap, 1 = identical
+  tokens: number;
+  cost: number;
+  duration_ms: number;
+  edits: number;
+}
+
+export interface PromptOutcome {
+  prompt_id: string;
+  sessions: PromptSessionOutcome[];
+  avg_tokens: number;
+  avg_cost: number;
+  avg_duration_ms: number;
+  avg_edits: number;
+}
+
+// To-do pulled from a session onto the project's checklist
+export interface ActionItem {
+  id: string;
+  session_id: string;
+  text: string;
+  done: boolean;
+  created_at: string;
+}
+
+// Prompt caching savings estimate (simulate_cache_savings)
+export interface ModelCacheSimulation {
+  model: string;
+  actual_cost: number;
+  cost_without_cache: number;
+  caching_savings: number;
+  potential_additional_savings: number;
+}
+
+export interface CacheSimulation {
+  days: number;
+  uncached_input_tokens: number;
+  cache_creation_tokens: number;
+  cache_read_tokens: number;
+  cache_hit_rate: number;
+  actual_cost: number;
+  cost_without_cache: number;
+  caching_savings: number;
+  rewritten_cache_tokens: number;
+  assumption: string; // how re-cached context is told apart from new context
+  locality_improvement: number;
+  potential_additional_savings: number;
+  by_model: ModelCacheSimulation[];
+}
+
+// API errors (get_error_stats)
+export interface ModelErrorCount {
+  model: string;
+  errors: number;
+}
+
+export interface DailyErrorStats {
+  date: string;
+  requests: number; // successful requests
+  errors: number;
+  overloaded: number;
+  rate_limited: number;
+  server_error: number;
+  other: number;
+  by_model: ModelErrorCount[];
+}
+
+export interface ErrorStats {
+  days: number;
+  requests: number;
+  errors: number;
+  error_rate: number; // 0-100
+  daily: DailyErrorStats[];
+}
+
+// Context compaction (get_compaction_report)
+export interface CompactionEvent {
+  timestamp: string;
+  session_id: string;
+  project: string;
+  trigger: "auto" | "manual" | "unknown";
+  tokens_before: number | null;
+  tokens_after: number | null;
+}
+
+export interface ProjectCompaction {
+  project: string;
+  compactions: number;
+  sessions: number;
+  avg_tokens_before: number;
+}
+
+export interface CompactionReport {
+  days: number;
+  compactions: number;
+  auto_compactions: number;
+  manual_compactions: number;
+  sessions_compacted: number;
+  avg_tokens_before: number;
+  avg_tokens_after: number;
+  tokens_reclaimed: number;
+  by_project: ProjectCompaction[];
+  recent: CompactionEvent[]; // newest first
+}
+
+// Response latency (get_latency_stats)
+export interface LatencySummary {
+  samples: number;
+  avg_ms: number;
+  p50_ms: number;
+  p90_ms: number;
+  p99_ms: number;
+}
+
+export interface LatencyGroup {
+  key: string; // model name or YYYY-MM-DD
+  ttft: LatencySummary | null; // time to first token
+  duration: LatencySummary | null; // total response time
+}
+
+export interface LatencyStats {
+  days: number;
+  overall: LatencyGroup;
+  by_model: LatencyGroup[];
+  daily: LatencyGroup[];
+}
+
+// Config file edited outside the app ("config-changed" event)
+export interface ConfigChange {
+  kind: "settings" | "claude_json" | "claude_md" | "agents" | "commands";
+  path: string;
+  change: "created" | "modified" | "removed";
+}
+
+// Startup timing (get_startup_report)
+export interface StartupPhase {
+  name: string;
+  started_ms: number;
+  duration_ms: number;
+  deferred: boolean; // ran in the background after the tray appeared
+}
+
+export interface StartupReport {
+  phases: StartupPhase[];
+  tray_ready_ms: number | null;
+  total_ms: number;
 }
 
 // Hook events
@@ -103,6 +588,10 @@ export interface HookEvent {
   tool?: string;
   context?: string;
   timestamp?: string;
+  session_id?: string;
+  cwd?: string;
+  transcript_path?: string;
+  payload?: Record<string, unknown>;
 }
 
 // Model pricing for display
@@ -112,11 +601,45 @@ export interface ModelPricing {
   output: number;
   cache_read: number;
   cache_write: number;
+  effective_from: string | null; // YYYY-MM-DD, null = since release
+  effective_until: string | null; // exclusive, null = current price
+  long_context: TokenRates | null; // prompts above 200k tokens
+}
+
+// Model limits and prices (get_model_catalog)
+export interface ModelInfo {
+  id: string;
+  name: string;
+  patterns: string[];
+  context_window: number;
+  long_context_window: number | null; // 1M-context beta
+  max_output: number;
+  knowledge_cutoff: string | null; // YYYY-MM
+  pricing: ModelPricing;
+  overridden: boolean;
+}
+
+export interface TokenRates {
+  input: number;
+  output: number;
+  cache_read: number;
+  cache_write: number;
+}
+
+export interface CostBreakdown {
+  cost: number;
+  tier: "standard" | "long_context";
+  context_tokens: number; // prompt size that decides the tier
+  rates: TokenRates;
+  service_tier: string; // "standard" | "priority" | "batch"
+  service_tier_multiplier: number;
 }
 
 // Session types
 export interface SessionInfo {
   session_id: string;
+  /** Title set in claudit, shown instead of the summary */
+  title: string | null;
   summary: string | null;
   first_user_message: string | null;
   first_message_at: string | null;
@@ -128,6 +651,18 @@ export interface SessionInfo {
   total_cache_read_tokens: number;
   total_cost: number;
   model: string | null;
+  model_breakdown: SessionModelBreakdown[]; // costliest first
+}
+
+// One model's tokens and cost within a session
+export interface SessionModelBreakdown extends ModelStats {
+  model: string;
+}
+
+// Session in the cost leaderboard (get_expensive_sessions)
+export interface ExpensiveSession extends SessionInfo {
+  project_path: string;
+  project_name: string;
 }
 
 export type MessageContentBlock =
@@ -135,6 +670,8 @@ export type MessageContentBlock =
   | { type: "thinking"; thinking: string }
   | { type: "tool_use"; id?: string; name?: string; input?: unknown }
   | { type: "tool_result"; tool_use_id?: string; content?: unknown }
+  // A tool_use paired with its tool_result
+  | { type: "tool_invocation"; id?: string; name?: string; input?: unknown; result?: unknown; duration_ms?: number | null }
   | { type: "Other" };
 
 export interface ConversationMessage {
@@ -145,19 +682,515 @@ export interface ConversationMessage {
   model: string | null;
   input_tokens: number | null;
   output_tokens: number | null;
+  cost: CostBreakdown | null;
+  context_window: number | null; // window the prompt ran in
 }
 
 export interface SessionConversation {
   session_id: string;
   summary: string | null;
   messages: ConversationMessage[];
+  total_messages: number; // in the whole session; messages may be one page
+  offset: number; // index of messages[0] in the session
+  subagents: SubagentThread[]; // started in messages; unmatched ones come with the first page
+}
+
+// A subagent's messages, from sidechain entries or the agent's own transcript
+export interface SubagentThread {
+  agent_id: string | null;
+  tool_use_id: string | null; // Task call that started it, when matched
+  description: string | null;
+  subagent_type: string | null;
+  messages: ConversationMessage[];
+}
+
+// Color scheme of HTML and PDF session exports
+export type ExportTheme = "dark" | "light";
+
+export type SessionExportFormat = "html" | "markdown" | "json" | "pdf";
+
+// A registered session export format (get_session_export_formats)
+export interface ExportFormatInfo {
+  format: SessionExportFormat;
+  label: string;
+  extension: string;
+}
+
+// Options shared by all session export formats; omitted fields use the defaults
+export interface SessionExportOptions {
+  theme?: ExportTheme;
+  include_thinking?: boolean;
+  include_tools?: boolean; // tool calls and their results
+  include_usage?: boolean; // tokens and cost of each response
+}
+
+// Which sessions a collection export contains; empty fields don't filter
+export interface SessionCollectionFilter {
+  project_path?: string | null;
+  tag?: string | null; // #tag in the session's note or title
+  from?: string | null; // YYYY-MM-DD
+  to?: string | null;
+}
+
+// A session in a collection export (get_session_collection)
+export interface CollectionEntry {
+  project_path: string;
+  title: string; // custom title, summary or first prompt
+  info: SessionInfo;
+}
+
+// Result of export_session_collection
+export interface CollectionExportResult {
+  sessions: number;
+  total_cost: number;
+  bytes: number;
+}
+
+// Payload of "session-messages" while following a session
+export interface SessionMessages {
+  session_id: string;
+  messages: ConversationMessage[]; // new, or updated in place (same uuid)
+  reset: boolean; // file was rewritten; messages is the whole conversation
+}
+
+// Payload of "session-message-appended": a running session's new messages, read on each hook event
+export interface SessionMessageAppended {
+  session_id: string;
+  project: string | null;
+  event: string; // hook event that triggered the read
+  messages: ConversationMessage[]; // new, or updated in place (same uuid)
+  reset: boolean; // session just showed up or its file was rewritten; messages replace what's shown
+  prompt: string | null; // submitted prompt (UserPromptSubmit), before it is in the file
+}
+
+// A session with hook events in the last hour (get_live_sessions)
+export interface LiveSessionInfo {
+  session_id: string;
+  project: string | null;
+  last_event: string;
+  last_event_at: string;
+  message_count: number;
+}
+
+// Opt-in telemetry (get_telemetry_preview); report is null while telemetry is off
+export interface TelemetryReport {
+  install_id: string;
+  app_version: string;
+  os: string;
+  arch: string;
+  since: string;
+  features: Record<string, number>;
+  crashes: Record<string, number>; // panics by source location
+}
+
+// A /hook request recorded by the inspector (get_recent_hook_requests)
+export interface HookRequest {
+  received_at: string;
+  authorized: boolean; // carried the hook auth token
+  event: string | null;
+  body: string; // raw, cut at 64 KB
+  truncated: boolean;
+  error: string | null; // why the payload was rejected
+}
+
+export interface TelemetryPreview {
+  enabled: boolean;
+  endpoint: string;
+  last_sent: string | null;
+  report: TelemetryReport | null;
+}
+
+// Session playback (get_session_replay)
+export interface ReplayChunk {
+  kind: "text" | "thinking" | "tool_use" | "tool_result";
+  text: string;
+}
+
+export interface ReplayMessage {
+  uuid: string;
+  role: string;
+  model: string | null;
+  offset_ms: number; // playback position at 1x (idle gaps capped)
+  real_offset_ms: number;
+  chunks: ReplayChunk[];
+}
+
+export interface SessionReplay {
+  session_id: string;
+  summary: string | null;
+  duration_ms: number;
+  real_duration_ms: number;
+  messages: ReplayMessage[];
 }
 
 export interface SessionSearchResult {
   session_id: string;
+  title: string | null;
   summary: string | null;
   first_user_message: string | null;
   matched_text: string;
   match_context: string;
   message_role: string;
 }
+
+export type McpScope = { type: "global" } | { type: "project"; path: string };
+
+export interface McpTemplateParam {
+  key: string;
+  label: string;
+  description: string;
+  required: boolean;
+  default: string | null;
+  secret: boolean;
+  list: boolean; // one value per line
+}
+
+export interface McpTemplate {
+  id: string;
+  name: string;
+  description: string;
+  homepage: string;
+  params: McpTemplateParam[];
+  config: Record<string, unknown>; // with {{param}} placeholders
+}
+
+// Team config packs (create_config_pack / read_config_pack / install_config_pack)
+export type PackItem =
+  | { type: "agent"; name: string }
+  | { type: "command"; name: string }
+  | { type: "mcp_server"; name: string };
+
+export interface PackEntry {
+  name: string;
+  files: string[]; // archive paths, e.g. agents/reviewer.md
+}
+
+export interface PackManifest {
+  format_version: number;
+  name: string;
+  created_at: string;
+  created_with: string;
+  agents: PackEntry[];
+  commands: PackEntry[];
+  mcp_servers: McpTemplate[]; // secrets replaced by params
+}
+
+export interface PackInstallResult {
+  installed: PackItem[];
+  skipped: { item: PackItem; reason: string }[];
+  missing_params: string[]; // "server: parameter"
+  operations: FileOperation[];
+  dry_run: boolean;
+}
+
+export type PackSignature =
+  | { status: "unsigned" }
+  | { status: "verified"; key: string }
+  | { status: "unknown_key" }
+  | { status: "invalid"; reason: string };
+
+// Shown before installing a pack (review_config_pack)
+export interface PackReview {
+  manifest: PackManifest;
+  signature: PackSignature;
+  programs: string[]; // MCP commands and URLs
+  scripts: string[]; // executable-looking files
+  plan: PackInstallResult; // dry run
+}
+
+export interface McpChange {
+  name: string;
+  scope: McpScope;
+  action: "added" | "replaced" | "removed" | "skipped";
+  reason: string | null;
+}
+
+export interface McpBulkResult {
+  changes: McpChange[];
+  operations: FileOperation[];
+  dry_run: boolean;
+}
+
+export interface FindingLocation {
+  path: string;
+  line: number | null;
+  label: string;
+  excerpt: string | null;
+}
+
+export interface ConfigFinding {
+  kind:
+    | "mcp_conflict"
+    | "mcp_duplicate"
+    | "command_shadowed"
+    | "agent_shadowed"
+    | "contradictory_directive";
+  severity: "warning" | "info";
+  title: string;
+  detail: string;
+  suggestion: string;
+  locations: FindingLocation[];
+}
+
+export type ProjectConfigItem =
+  | { type: "command"; name: string }
+  | { type: "agent"; name: string }
+  | { type: "mcp_server"; name: string }
+  | { type: "claude_md_section"; heading: string };
+
+export interface ProjectConfigItems {
+  items: ProjectConfigItem[];
+}
+
+export interface ProjectCopyResult {
+  copied: ProjectConfigItem[];
+  skipped: { item: ProjectConfigItem; reason: string }[];
+  operations: FileOperation[];
+  dry_run: boolean;
+}
+
+export type DuplicateAction = "merge" | "ignore";
+
+export interface SessionFileSummary {
+  path: string;
+  session_id: string;
+  project: string;
+  messages: number;
+  cost: number;
+  first_timestamp: string | null;
+  last_timestamp: string | null;
+}
+
+export interface DuplicateSessionPair {
+  primary: SessionFileSummary;
+  duplicate: SessionFileSummary;
+  shared_messages: number;
+  overlap_percent: number;
+  duplicated_cost: number;
+  double_counted_cost: number; // not caught by UUID dedup
+  resolution: DuplicateAction | null;
+}
+
+export interface DuplicateReport {
+  pairs: DuplicateSessionPair[];
+  inflated_cost: number;
+  extra_sessions: number;
+}
+
+export interface ConsoleImportSummary {
+  rows_read: number;
+  rows_skipped: number;
+  days: number;
+  models: string[];
+  total_billed: number;
+}
+
+export interface ReconciliationRow {
+  date: string;
+  model: string; // normalized, "all" for daily totals
+  computed: number;
+  billed: number;
+  difference: number; // computed - billed
+  difference_percent: number | null;
+  flagged: boolean;
+}
+
+export interface ReconciliationReport {
+  source: string | null;
+  imported_at: string | null;
+  start_date: string | null;
+  end_date: string | null;
+  total_computed: number;
+  total_billed: number;
+  rows: ReconciliationRow[];
+  daily: ReconciliationRow[];
+}
+
+export interface WindowState {
+  x: number | null;
+  y: number | null;
+  width: number | null;
+  height: number | null;
+  maximized: boolean;
+  route: string | null; // last open page
+}
+
+// Command palette (quick_search)
+export type SearchResultKind = "project" | "session" | "agent" | "command" | "mcp_server";
+
+export type JumpAction =
+  | { type: "project"; project_path: string }
+  | { type: "session"; project_path: string; session_id: string }
+  | { type: "agent"; path: string; name: string; is_directory: boolean }
+  | { type: "command"; path: string; name: string; is_directory: boolean }
+  | { type: "mcp_server"; name: string; project_path: string | null }; // null = global
+
+export interface QuickSearchResult {
+  kind: SearchResultKind;
+  title: string;
+  subtitle: string | null;
+  score: number;
+  action: JumpAction;
+}
+
+// Launch flags (--open / --project), see take_launch_action
+export interface LaunchAction {
+  section: string | null;
+  jump: JumpAction | null;
+}
+
+// Claudit's own data (export_app_data / import_app_data)
+export interface AppDataSummary {
+  path: string;
+  items: string[];
+  backup_path: string | null; // previous data, saved before an import
+}
+
+// factory_reset options
+export interface ResetOptions {
+  settings: boolean; // preferences, goals, profiles
+  data: boolean; // notification rules, goal history, duplicate resolutions, Console costs
+  caches: boolean; // window layout, activity feed, audit log
+  hooks: boolean; // claudit hooks in ~/.claude/settings.json
+}
+
+// Slash command and agent usage (get_invocation_stats)
+export interface InvocationStats {
+  kind: "command" | "agent";
+  name: string; // command without the slash, or subagent_type
+  count: number;
+  sessions: number;
+  last_used: string | null;
+  estimated_cost: number;
+  defined_at: string | null; // null = built-in
+  projects: string[];
+}
+
+export interface InvocationReport {
+  commands: InvocationStats[];
+  agents: InvocationStats[];
+}
+
+// Configured but unused MCP servers, agents and commands (get_unused_config)
+export interface UnusedItem {
+  id: string; // pass to disable_unused_config
+  kind: "mcp_server" | "agent" | "command";
+  name: string;
+  scope: McpScope | null;
+  path: string | null;
+  last_used: string | null;
+}
+
+export interface UnusedConfigReport {
+  days: number;
+  items: UnusedItem[];
+  mcp_servers_checked: number;
+  agents_checked: number;
+  commands_checked: number;
+}
+
+// Suggested model: overrides for agents and commands (get_model_routing_suggestions)
+export interface ModelRoutingSuggestion {
+  kind: "command" | "agent";
+  name: string;
+  path: string; // pass to apply_model_override
+  current_model: string | null; // frontmatter model:, null = inherits
+  observed_model: string;
+  turns: number;
+  low_token_turns: number;
+  average_output_tokens: number;
+  suggested_model: string; // "haiku" | "sonnet"
+  cost: number;
+  suggested_cost: number;
+  estimated_saving: number;
+}
+
+export interface ModelRoutingReport {
+  days: number;
+  checked: number;
+  suggestions: ModelRoutingSuggestion[];
+}
+
+// Chunked transfer of a large payload (start_session_transfer / read_transfer_chunk)
+export interface TransferInfo {
+  id: string;
+  bytes: number;
+  compressed_bytes: number;
+  chunks: number;
+}
+
+// A project's daily spend and trend (get_project_cost_history)
+export interface ProjectDailyCost {
+  date: string;
+  cost: number;
+  messages: number;
+}
+
+export interface ProjectCostHistory {
+  project: string;
+  days: number;
+  daily: ProjectDailyCost[]; // every day of the period, oldest first
+  total_cost: number;
+  previous_cost: number;
+  trend: number | null; // total_cost / previous_cost
+  share_of_spend: number; // 0-100
+}
+
+// A project's usage split by monorepo package (get_workspace_breakdown)
+export interface SubprojectStats {
+  path: string; // relative to the project root, "." for the root
+  cost: number;
+  input_tokens: number;
+  output_tokens: number;
+  messages: number;
+  sessions: number;
+  last_used: string | null;
+}
+
+export interface WorkspaceBreakdown {
+  project: string;
+  days: number | null;
+  subprojects: SubprojectStats[];
+}
+
+// Weekly/monthly usage report (generate_report)
+export type ReportPeriod = "week" | "month";
+
+export interface ReportTotals {
+  cost: number;
+  input_tokens: number;
+  output_tokens: number;
+  cache_creation_tokens: number;
+  cache_read_tokens: number;
+  messages: number;
+  sessions: number;
+}
+
+export interface ReportBreakdown {
+  name: string;
+  cost: number;
+  tokens: number; // input + output
+  messages: number;
+}
+
+export interface ReportSession {
+  session_id: string;
+  project: string;
+  started_at: string;
+  cost: number;
+  tokens: number;
+  messages: number;
+}
+
+export interface UsageReport {
+  period: ReportPeriod;
+  start_date: string;
+  end_date: string;
+  generated_at: string;
+  totals: ReportTotals;
+  previous: ReportTotals;
+  cost_change_pct: number | null;
+  by_model: ReportBreakdown[];
+  by_project: ReportBreakdown[];
+  top_sessions: ReportSession[];
+}
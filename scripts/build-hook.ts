@@ -0,0 +1,43 @@
+/**
+ * Build the claudit-hook relay and put it where Tauri picks up the sidecar
+ * declared in tauri.conf.json (bundle.externalBin):
+ * src-tauri/binaries/claudit-hook-<target triple>
+ * Runs before `tauri dev` / `tauri build`; run by hand with: npx tsx scripts/build-hook.ts
+ */
+
+import { execFileSync } from "child_process";
+import * as fs from "fs";
+import * as path from "path";
+import { fileURLToPath } from "url";
+
+const __filename = fileURLToPath(import.meta.url);
+const __dirname = path.dirname(__filename);
+const TAURI_DIR = path.join(__dirname, "../src-tauri");
+const BINARIES_DIR = path.join(TAURI_DIR, "binaries");
+
+// Set by the Tauri CLI for its before-commands, including `tauri build --target`
+function targetTriple(): string {
+  if (process.env.TAURI_ENV_TARGET_TRIPLE) {
+    return process.env.TAURI_ENV_TARGET_TRIPLE;
+  }
+  const info = execFileSync("rustc", ["-vV"], { encoding: "utf8" });
+  const host = info.match(/^host: (\S+)$/m);
+  if (!host) {
+    throw new Error("Could not determine the Rust host target");
+  }
+  return host[1];
+}
+
+const target = targetTriple();
+const extension = target.includes("windows") ? ".exe" : "";
+
+execFileSync("cargo", ["build", "--release", "--package", "claudit-hook", "--target", target], {
+  cwd: TAURI_DIR,
+  stdio: "inherit",
+});
+
+fs.mkdirSync(BINARIES_DIR, { recursive: true });
+const built = path.join(TAURI_DIR, "target", target, "release", `claudit-hook${extension}`);
+const sidecar = path.join(BINARIES_DIR, `claudit-hook-${target}${extension}`);
+fs.copyFileSync(built, sidecar);
+console.log(`claudit-hook -> ${path.relative(TAURI_DIR, sidecar)}`);
@@ -0,0 +1,175 @@
+//! Hook relay for Claude Code
+//!
+//! Claude Code pipes a JSON payload to every hook command on stdin. This helper
+//! reads that payload and forwards it to the running Claudit hook server, so the
+//! installed hooks no longer depend on curl or shell quoting.
+//!
+//! Usage: `claudit-hook [--host <host>] [--port <port>] [--event <name>]`
+//!
+//! `--host` (default 127.0.0.1) reaches a Claudit listening on another address,
+//! e.g. `host.docker.internal` from a devcontainer. Where Claudit's config
+//! directory isn't available, the token is read from `CLAUDIT_HOOK_TOKEN`.
+//!
+//! When the server is unreachable (Claudit isn't running) the event is appended
+//! to a spool file that Claudit drains into its activity history on startup.
+//!
+//! The helper always exits 0 and prints nothing, so a missing or busy Claudit
+//! never interferes with Claude Code.
+
+use std::fs::OpenOptions;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::PathBuf;
+use std::time::Duration;
+
+const DEFAULT_HOST: &str = "127.0.0.1";
+const DEFAULT_PORT: u16 = 3456;
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+const IO_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Get claudit's config directory (same location SettingsService uses)
+fn claudit_config_dir() -> Option<PathBuf> {
+    dirs::config_dir()
+        .or_else(dirs::home_dir)
+        .map(|dir| dir.join("claudit"))
+}
+
+/// Read the shared secret the hook server expects in the Authorization header
+fn read_token() -> Option<String> {
+    if let Ok(token) = std::env::var("CLAUDIT_HOOK_TOKEN") {
+        if !token.trim().is_empty() {
+            return Some(token.trim().to_string());
+        }
+    }
+    let path = claudit_config_dir()?.join("hook-token");
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+}
+
+/// Append the event to the offline spool so Claudit can pick it up later
+fn spool_event(body: &str) {
+    let Some(dir) = claudit_config_dir() else {
+        return;
+    };
+    let _ = std::fs::create_dir_all(&dir);
+
+    if let Ok(mut file) = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join("hook-spool.jsonl"))
+    {
+        let _ = writeln!(file, "{}", body);
+    }
+}
+
+/// Parse command line arguments into (host, port, event override)
+fn parse_args() -> (String, u16, Option<String>) {
+    let mut host = DEFAULT_HOST.to_string();
+    let mut port = DEFAULT_PORT;
+    let mut event = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--host" => {
+                if let Some(h) = args.next() {
+                    host = h.trim_start_matches('[').trim_end_matches(']').to_string();
+                }
+            }
+            "--port" => {
+                if let Some(p) = args.next().and_then(|p| p.parse().ok()) {
+                    port = p;
+                }
+            }
+            "--event" => event = args.next(),
+            _ => {}
+        }
+    }
+
+    (host, port, event)
+}
+
+/// Build the HookEvent body sent to the server from Claude's hook payload
+fn build_event(payload: serde_json::Value, event_override: Option<String>) -> serde_json::Value {
+    let field = |name: &str| {
+        payload
+            .get(name)
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+    };
+
+    let event = event_override
+        .or_else(|| field("hook_event_name"))
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    serde_json::json!({
+        "event": event,
+        "tool": field("tool_name"),
+        "session_id": field("session_id"),
+        "cwd": field("cwd"),
+        "transcript_path": field("transcript_path"),
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "payload": payload,
+    })
+}
+
+/// POST the event to the hook server, returning true on a 2xx response
+fn post_event(host: &str, port: u16, token: Option<&str>, body: &str) -> bool {
+    let Some(addr) = (host, port).to_socket_addrs().ok().and_then(|mut a| a.next()) else {
+        return false;
+    };
+    let mut stream = match TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT) {
+        Ok(s) => s,
+        Err(_) => return false,
+    };
+    let _ = stream.set_read_timeout(Some(IO_TIMEOUT));
+    let _ = stream.set_write_timeout(Some(IO_TIMEOUT));
+
+    let auth = token
+        .map(|t| format!("Authorization: Bearer {}\r\n", t))
+        .unwrap_or_default();
+    // IPv6 literals are bracketed in the Host header
+    let host = if host.contains(':') {
+        format!("[{}]", host)
+    } else {
+        host.to_string()
+    };
+    let request = format!(
+        "POST /hook HTTP/1.1\r\nHost: {}:{}\r\nContent-Type: application/json\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        host,
+        port,
+        auth,
+        body.len(),
+        body
+    );
+
+    if stream.write_all(request.as_bytes()).is_err() {
+        return false;
+    }
+
+    // Only the status line matters
+    let mut response = [0u8; 32];
+    let read = stream.read(&mut response).unwrap_or(0);
+    let status_line = String::from_utf8_lossy(&response[..read]);
+    status_line
+        .split_whitespace()
+        .nth(1)
+        .is_some_and(|code| code.starts_with('2'))
+}
+
+fn main() {
+    let (host, port, event_override) = parse_args();
+
+    let mut input = String::new();
+    let _ = std::io::stdin().read_to_string(&mut input);
+    let payload = serde_json::from_str(&input).unwrap_or_else(|_| serde_json::json!({}));
+
+    let event = build_event(payload, event_override);
+    let body = event.to_string();
+
+    if !post_event(&host, port, read_token().as_deref(), &body) {
+        spool_event(&body);
+    }
+}
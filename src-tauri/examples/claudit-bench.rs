@@ -0,0 +1,8 @@
+//! Benchmark harness for refresh and search (see `claudit_lib::bench`). An example
+//! rather than a binary, so the app bundle leaves it out.
+//!
+//! Usage: `cargo run --release --example claudit-bench -- --projects 50 --sessions 100`
+
+fn main() {
+    claudit_lib::bench::main()
+}
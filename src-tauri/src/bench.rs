@@ -0,0 +1,228 @@
+//! Benchmark harness
+//!
+//! Generates a Claude data directory at a chosen scale and times the scans the
+//! app runs on refresh and search against it. Used by the `claudit-bench` example:
+//!
+//! ```text
+//! claudit-bench [--projects N] [--sessions M] [--messages K] [--days D]
+//!               [--seed S] [--runs R] [--dir PATH] [--generate-only] [--json]
+//! ```
+//!
+//! Without `--dir` the data goes to a temporary directory that is removed
+//! afterwards. With `--dir` it is kept, so the same fixtures can be reused or
+//! opened in the app with `CLAUDE_CONFIG_DIR`.
+
+use crate::services::demo::{self, SampleDataSpec};
+use crate::services::{config, paths, quick_search, sessions, AnalyticsService, UsageReader};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::Instant;
+
+/// Timings of one benchmarked operation, in milliseconds
+#[derive(Debug, Clone, Serialize)]
+pub struct Timing {
+    pub name: String,
+    pub runs: usize,
+    pub min_ms: f64,
+    pub median_ms: f64,
+    pub max_ms: f64,
+}
+
+/// Everything a benchmark run measured
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchReport {
+    pub spec: SampleDataSpec,
+    pub data: demo::GeneratedData,
+    pub generate_ms: f64,
+    pub timings: Vec<Timing>,
+}
+
+struct BenchArgs {
+    spec: SampleDataSpec,
+    runs: usize,
+    dir: Option<PathBuf>,
+    generate_only: bool,
+    json: bool,
+}
+
+fn parse_args(args: &[String]) -> Result<BenchArgs, String> {
+    let mut parsed = BenchArgs {
+        spec: SampleDataSpec {
+            projects: 20,
+            sessions_per_project: 50,
+            messages_per_session: 40,
+            days: 90,
+            seed: 1,
+        },
+        runs: 5,
+        dir: None,
+        generate_only: false,
+        json: false,
+    };
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| -> Result<u64, String> {
+            args.next()
+                .and_then(|v| v.parse().ok())
+                .ok_or_else(|| format!("{} needs a number", name))
+        };
+        match arg.as_str() {
+            "--projects" => parsed.spec.projects = value(arg)? as usize,
+            "--sessions" => parsed.spec.sessions_per_project = value(arg)? as usize,
+            "--messages" => parsed.spec.messages_per_session = value(arg)? as usize,
+            "--days" => parsed.spec.days = value(arg)?.max(1) as i64,
+            "--seed" => parsed.spec.seed = value(arg)?,
+            "--runs" => parsed.runs = value(arg)?.max(1) as usize,
+            "--dir" => {
+                let dir = args.next().ok_or("--dir needs a path")?;
+                parsed.dir = Some(PathBuf::from(dir));
+            }
+            "--generate-only" => parsed.generate_only = true,
+            "--json" => parsed.json = true,
+            other => return Err(format!("Unknown argument: {}", other)),
+        }
+    }
+    Ok(parsed)
+}
+
+fn elapsed_ms(start: Instant) -> f64 {
+    start.elapsed().as_secs_f64() * 1000.0
+}
+
+/// Run `op` `runs` times and summarize how long it took
+fn measure<T>(name: &str, runs: usize, mut op: impl FnMut() -> T) -> Timing {
+    let mut samples: Vec<f64> = (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            std::hint::black_box(op());
+            elapsed_ms(start)
+        })
+        .collect();
+    samples.sort_by(|a, b| a.total_cmp(b));
+
+    Timing {
+        name: name.to_string(),
+        runs,
+        min_ms: samples[0],
+        median_ms: samples[samples.len() / 2],
+        max_ms: samples[samples.len() - 1],
+    }
+}
+
+/// Time refresh and search operations against the current Claude data location
+fn run_timings(runs: usize) -> Vec<Timing> {
+    let reader = UsageReader::new();
+    let analytics = AnalyticsService::new();
+    let project = config::list_projects()
+        .ok()
+        .and_then(|projects| projects.into_iter().next())
+        .map(|project| project.path)
+        .unwrap_or_default();
+
+    vec![
+        measure("find_jsonl_files", runs, || reader.find_jsonl_files()),
+        measure("read_all_entries", runs, || reader.read_all_entries()),
+        measure("refresh_stats", runs, || analytics.refresh_stats()),
+        measure("list_projects", runs, config::list_projects),
+        measure("list_sessions (1 project)", runs, || {
+            sessions::list_sessions(&project)
+        }),
+        measure("search_sessions (1 project)", runs, || {
+            sessions::search_sessions(&project, "pagination")
+        }),
+        measure("quick_search", runs, || {
+            quick_search::quick_search("checkout", 20)
+        }),
+    ]
+}
+
+fn print_report(report: &BenchReport) {
+    let spec = &report.spec;
+    println!(
+        "{} projects x {} sessions x {} messages over {} days (seed {})",
+        spec.projects, spec.sessions_per_project, spec.messages_per_session, spec.days, spec.seed
+    );
+    println!(
+        "Generated {} session files, {} lines, {:.1} MB in {:.0} ms at {}",
+        report.data.session_files,
+        report.data.log_lines,
+        report.data.bytes as f64 / 1_048_576.0,
+        report.generate_ms,
+        report.data.dir
+    );
+    if report.timings.is_empty() {
+        return;
+    }
+
+    println!();
+    println!(
+        "{:<30} {:>5} {:>10} {:>10} {:>10}",
+        "operation", "runs", "min ms", "median ms", "max ms"
+    );
+    for timing in &report.timings {
+        println!(
+            "{:<30} {:>5} {:>10.1} {:>10.1} {:>10.1}",
+            timing.name, timing.runs, timing.min_ms, timing.median_ms, timing.max_ms
+        );
+    }
+}
+
+/// Entry point of `claudit-bench`
+pub fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args = match parse_args(&args) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+
+    let keep = args.dir.is_some();
+    let dir = args.dir.clone().unwrap_or_else(|| {
+        std::env::temp_dir().join(format!("claudit-bench-{}", std::process::id()))
+    });
+    if dir.exists() {
+        if let Err(e) = std::fs::remove_dir_all(&dir) {
+            eprintln!("Failed to clear {}: {}", dir.display(), e);
+            std::process::exit(1);
+        }
+    }
+
+    let start = Instant::now();
+    let data = match demo::generate(&dir, &args.spec) {
+        Ok(data) => data,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    let generate_ms = elapsed_ms(start);
+
+    paths::set_override(dir.clone());
+    let timings = if args.generate_only {
+        Vec::new()
+    } else {
+        run_timings(args.runs)
+    };
+
+    let report = BenchReport {
+        spec: args.spec,
+        data,
+        generate_ms,
+        timings,
+    };
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).unwrap_or_default()
+        );
+    } else {
+        print_report(&report);
+    }
+
+    if !keep {
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,156 @@
+//! Launch flags
+//!
+//! Only one claudit instance runs at a time. Starting it again (from a shell
+//! alias or a login item) forwards the new command line to the running
+//! instance instead, so the same flags work either way:
+//!
+//! - `--hidden`: start in the tray without showing the window
+//! - `--open <section>`: open a page (analytics, projects, agents, ...)
+//! - `--project <path>`: open a project in the Projects page
+//! - `--demo`: show generated sample data instead of ~/.claude (startup only)
+//!
+//! On first launch the page or project is handed to the frontend once it has
+//! loaded (`take_launch_action`); for a second invocation it is emitted right away.
+
+use crate::services::demo;
+use crate::services::quick_search::JumpAction;
+use crate::services::window_state::ANALYTICS_WINDOW;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+/// Pages `--open` accepts (same names as the tray's `navigate` events)
+const SECTIONS: &[&str] = &[
+    "analytics",
+    "projects",
+    "live",
+    "agents",
+    "plugins",
+    "config",
+    "backup",
+    "settings",
+    "analysis",
+    "models",
+    "prompts",
+];
+
+/// Parsed command line flags
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LaunchArgs {
+    pub hidden: bool,
+    pub open: Option<String>,
+    pub project: Option<String>,
+    pub demo: bool,
+}
+
+/// What the frontend should show after launch
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LaunchAction {
+    /// Page to open
+    pub section: Option<String>,
+    pub jump: Option<JumpAction>,
+}
+
+/// Launch action waiting for the frontend to load
+static PENDING: Mutex<Option<LaunchAction>> = Mutex::new(None);
+
+/// Parse flags from `args` (without the program name). Relative project paths
+/// are resolved against `cwd`; unknown flags are ignored.
+pub fn parse(args: &[String], cwd: &Path) -> LaunchArgs {
+    let mut launch = LaunchArgs::default();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) => (flag, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+        let mut value = || inline_value.clone().or_else(|| args.next().cloned());
+
+        match flag {
+            "--hidden" => launch.hidden = true,
+            "--demo" => launch.demo = true,
+            "--open" => match value() {
+                Some(section) if SECTIONS.contains(&section.as_str()) => {
+                    launch.open = Some(section)
+                }
+                Some(section) => eprintln!("Unknown section for --open: {}", section),
+                None => eprintln!("--open needs a section"),
+            },
+            "--project" => match value() {
+                Some(path) => launch.project = Some(cwd.join(path).to_string_lossy().to_string()),
+                None => eprintln!("--project needs a path"),
+            },
+            _ => {}
+        }
+    }
+    launch
+}
+
+fn launch_action(args: &LaunchArgs) -> Option<LaunchAction> {
+    if args.open.is_none() && args.project.is_none() {
+        return None;
+    }
+    Some(LaunchAction {
+        section: args.open.clone(),
+        jump: args
+            .project
+            .clone()
+            .map(|project_path| JumpAction::Project { project_path }),
+    })
+}
+
+/// Show and focus the main window
+pub fn show_window<R: Runtime>(app: &AppHandle<R>) {
+    if let Some(window) = app.get_webview_window(ANALYTICS_WINDOW) {
+        #[cfg(target_os = "macos")]
+        {
+            let _ = app.set_activation_policy(tauri::ActivationPolicy::Regular);
+        }
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
+/// Apply the flags this instance was started with
+pub fn apply_startup<R: Runtime>(app: &AppHandle<R>, args: &LaunchArgs) {
+    if args.demo {
+        demo::force();
+    }
+    if args.hidden {
+        if let Some(window) = app.get_webview_window(ANALYTICS_WINDOW) {
+            let _ = window.hide();
+        }
+        #[cfg(target_os = "macos")]
+        {
+            let _ = app.set_activation_policy(tauri::ActivationPolicy::Accessory);
+        }
+    }
+    *PENDING.lock().unwrap() = launch_action(args);
+}
+
+/// Handle the command line of a second invocation in the running instance
+pub fn apply_forwarded<R: Runtime>(app: &AppHandle<R>, argv: Vec<String>, cwd: String) {
+    let args = parse(argv.get(1..).unwrap_or_default(), Path::new(&cwd));
+    // `--hidden` only matters at startup; a second launch never hides the window
+    if args.hidden && args.open.is_none() && args.project.is_none() {
+        return;
+    }
+
+    show_window(app);
+    if let Some(action) = launch_action(&args) {
+        if let Some(section) = action.section {
+            let _ = app.emit("navigate", section);
+        }
+        if let Some(jump) = action.jump {
+            let _ = app.emit("jump", jump);
+        }
+    }
+}
+
+/// Launch action from the command line, returned once
+pub fn take_launch_action() -> Option<LaunchAction> {
+    PENDING.lock().unwrap().take()
+}
@@ -1,31 +1,49 @@
+pub mod bench;
+mod cli;
 mod services;
 mod tray;
 mod types;
 
 use services::{
-    AnalyticsService, HookInstaller, HookServer, ModelPricing, SettingsService,
+    AnalyticsService, HookInstaller, HookServer, ModelPricing, SessionTracker, SettingsService,
     // Config service types
     ClaudeMdFile, AgentInfo, CommandInfo, PluginInfo, McpServer, ProjectInfo, ProjectDetails,
     PatternAnalysis, AiSuggestion, ProjectSuggestion, ExportOptions, BackupInfo, GitStatus,
 };
+use services::analytics_db::ReindexProgress;
+use services::audit::FileOperation;
+use services::sandbox;
+use services::session_collection::{self, SessionCollectionFilter};
+use services::session_export::{self, SessionExportFormat, SessionExportOptions};
+use services::startup;
 use std::sync::Arc;
 use tauri::{Emitter, Manager};
 use tokio::sync::Mutex;
-use tray::{create_tray, refresh_menu_cache, update_cached_settings, update_tray_menu};
-use types::{AnalyticsStats, AppSettings, ChartData, ClaudeStatus, SessionInfo, SessionConversation, SessionSearchResult};
+use tray::{create_tray, refresh_menu_cache};
+use types::{AnalyticsStats, AppSettings, CacheSimulation, ChartBucket, ChartData, ClaudeStatus, CompactionReport, ErrorStats, ExpensiveSession, LatencyStats, ProjectCostHistory, SessionInfo, SessionConversation, SessionReplay, SessionSearchResult};
 
 /// Application state
 pub struct AppState {
     pub analytics: AnalyticsService,
     pub settings: SettingsService,
     pub hook_server: Arc<Mutex<HookServer>>,
+    pub session_tracker: Arc<SessionTracker>,
 }
 
 // ============ Tauri Commands ============
 
 #[tauri::command]
-async fn get_stats(state: tauri::State<'_, AppState>) -> Result<AnalyticsStats, String> {
-    Ok(state.analytics.get_stats())
+async fn get_stats(
+    state: tauri::State<'_, AppState>,
+    days: Option<u32>,
+) -> Result<AnalyticsStats, String> {
+    let mut stats = state.analytics.get_stats();
+    // Per-model split of the range selected in the dashboard
+    if let Some(days) = days {
+        stats.range_days = Some(days);
+        stats.range_by_model = state.analytics.get_model_stats(days);
+    }
+    Ok(stats)
 }
 
 #[tauri::command]
@@ -37,8 +55,113 @@ async fn refresh_stats(state: tauri::State<'_, AppState>) -> Result<AnalyticsSta
 async fn get_chart_data(
     state: tauri::State<'_, AppState>,
     days: u32,
+    bucket: Option<ChartBucket>,
+    max_points: Option<usize>,
 ) -> Result<ChartData, String> {
-    Ok(state.analytics.get_chart_data(days))
+    let mut chart = state.analytics.get_chart_data(days);
+    services::downsample::apply(&mut chart, days, bucket.unwrap_or_default(), max_points);
+    Ok(chart)
+}
+
+/// Usage grouped by any of project, model, day, hour, session, tool and
+/// service tier, for pivot tables
+#[tauri::command]
+async fn drill_down(
+    state: tauri::State<'_, AppState>,
+    dimensions: Vec<types::DrillDimension>,
+    filters: Option<types::DrillDownFilters>,
+    sort: Option<types::DrillSort>,
+    limit: Option<u32>,
+) -> Result<types::DrillDownResult, String> {
+    services::telemetry::record("drill_down");
+    state.analytics.drill_down(
+        &dimensions,
+        &filters.unwrap_or_default(),
+        &sort.unwrap_or_default(),
+        limit,
+    )
+}
+
+#[tauri::command]
+async fn simulate_cache_savings(
+    state: tauri::State<'_, AppState>,
+    days: u32,
+    locality_improvement: Option<f64>,
+) -> Result<CacheSimulation, String> {
+    Ok(state
+        .analytics
+        .simulate_cache(days, locality_improvement.unwrap_or(0.5)))
+}
+
+#[tauri::command]
+async fn get_latency_stats(
+    state: tauri::State<'_, AppState>,
+    days: u32,
+) -> Result<LatencyStats, String> {
+    Ok(state.analytics.get_latency_stats(days))
+}
+
+#[tauri::command]
+async fn get_error_stats(
+    state: tauri::State<'_, AppState>,
+    days: u32,
+) -> Result<ErrorStats, String> {
+    Ok(state.analytics.get_error_stats(days))
+}
+
+#[tauri::command]
+async fn get_compaction_report(
+    state: tauri::State<'_, AppState>,
+    days: u32,
+) -> Result<CompactionReport, String> {
+    Ok(state.analytics.get_compaction_report(days))
+}
+
+/// Session files that largely duplicate another session
+#[tauri::command]
+async fn get_duplicate_sessions() -> Result<services::duplicates::DuplicateReport, String> {
+    Ok(services::duplicates::find_duplicates())
+}
+
+/// Merge or ignore a duplicate session file (None clears the decision) and recompute stats
+#[tauri::command]
+async fn resolve_duplicate_session(
+    state: tauri::State<'_, AppState>,
+    file: String,
+    primary_file: String,
+    action: Option<services::duplicates::DuplicateAction>,
+) -> Result<AnalyticsStats, String> {
+    // Paths are stored as reported by get_duplicate_sessions so they match the file scan
+    sandbox::allowed_path(&file)?;
+    sandbox::allowed_path(&primary_file)?;
+    services::duplicates::resolve(&file, &primary_file, action)?;
+    Ok(state.analytics.refresh_stats())
+}
+
+/// Import an Anthropic Console cost CSV (picked through `pick_path`)
+#[tauri::command]
+async fn import_console_costs(
+    path: String,
+) -> Result<services::reconcile::ConsoleImportSummary, String> {
+    let path = sandbox::allowed_path(&path)?;
+    services::reconcile::import_csv(&path)
+}
+
+/// Compare computed costs with the imported Console costs per day and model
+#[tauri::command]
+async fn get_cost_reconciliation() -> Result<services::reconcile::ReconciliationReport, String> {
+    Ok(services::reconcile::report())
+}
+
+/// Progress towards the configured goal (None when goal mode is off)
+#[tauri::command]
+async fn get_goal_progress(
+    state: tauri::State<'_, AppState>,
+) -> Result<Option<services::goals::GoalProgress>, String> {
+    match state.settings.get().goal {
+        Some(goal) => services::goals::progress(&state.analytics, &goal).map(Some),
+        None => Ok(None),
+    }
 }
 
 #[tauri::command]
@@ -48,31 +171,81 @@ async fn get_settings(state: tauri::State<'_, AppState>) -> Result<AppSettings,
 
 #[tauri::command]
 async fn update_settings(
-    app: tauri::AppHandle,
     state: tauri::State<'_, AppState>,
     settings: AppSettings,
 ) -> Result<(), String> {
-    // Update cached settings for tray menu
-    update_cached_settings(&settings);
-    // Persist to disk
-    state.settings.update(settings)?;
-    // Refresh tray menu to reflect changes
-    let _ = update_tray_menu(&app);
+    services::tool_rules::validate(&settings.tool_notification_rules)?;
+    services::config_pack::validate_keys(&settings.trusted_pack_keys)?;
+    if let Some(sync) = &settings.config_sync {
+        services::config_sync::validate(sync)?;
+    }
+    services::model_catalog::validate_overrides(&settings.model_catalog_overrides)?;
+    services::hooks::parse_bind_address(&settings.hook_bind_address)?;
+    services::path_mapping::validate(&settings.path_mappings)?;
+    // Persist to disk; the tray, hook server and watchers follow the change
+    state.settings.update(settings)
+}
+
+/// Recent raw /hook requests, newest first (recorded while the inspector is on)
+#[tauri::command]
+async fn get_recent_hook_requests() -> Result<Vec<services::hook_inspector::HookRequest>, String> {
+    Ok(services::hook_inspector::recent())
+}
+
+/// Drop the hook requests the inspector recorded
+#[tauri::command]
+async fn clear_hook_requests() -> Result<(), String> {
+    services::hook_inspector::clear();
     Ok(())
 }
 
+/// The telemetry report that would be sent next, shown before opting in
+#[tauri::command]
+async fn get_telemetry_preview() -> Result<services::telemetry::TelemetryPreview, String> {
+    Ok(services::telemetry::preview())
+}
+
+/// Sample amounts written in `format`, to preview it in Settings
+#[tauri::command]
+async fn preview_cost_format(format: types::CostFormat) -> Result<String, String> {
+    Ok(format!(
+        "{} or {}",
+        services::format::cost_with(&format, 1234.5678),
+        services::format::cost_with(&format, 0.04567)
+    ))
+}
+
+/// Stats summary in `template`, or the configured one, to preview it in Settings
+#[tauri::command]
+async fn get_stats_summary(
+    state: tauri::State<'_, AppState>,
+    template: Option<String>,
+) -> Result<String, String> {
+    Ok(services::summary::render(
+        &state.analytics.cached_stats(),
+        template.as_deref().filter(|t| !t.trim().is_empty()),
+    ))
+}
+
+#[tauri::command]
+async fn get_summary_template_info() -> Result<services::summary::SummaryTemplateInfo, String> {
+    Ok(services::summary::template_info())
+}
+
+/// Put the stats summary on the clipboard, returning the text copied
+#[tauri::command]
+async fn copy_stats_summary(state: tauri::State<'_, AppState>) -> Result<String, String> {
+    services::telemetry::record("stats_summary");
+    services::summary::copy(&state.analytics.cached_stats())
+}
+
 #[tauri::command]
 async fn toggle_section(
-    app: tauri::AppHandle,
     state: tauri::State<'_, AppState>,
     section: String,
     visible: bool,
 ) -> Result<(), String> {
-    state.settings.toggle_section(&section, visible)?;
-    // Update cache and tray menu
-    update_cached_settings(&state.settings.get());
-    let _ = update_tray_menu(&app);
-    Ok(())
+    state.settings.toggle_section(&section, visible)
 }
 
 #[tauri::command]
@@ -81,14 +254,58 @@ async fn check_hooks_installed() -> Result<bool, String> {
 }
 
 #[tauri::command]
-async fn install_hooks(state: tauri::State<'_, AppState>) -> Result<(), String> {
-    let port = state.hook_server.lock().await.port();
-    HookInstaller::install(port)
+async fn install_hooks(
+    state: tauri::State<'_, AppState>,
+    dry_run: Option<bool>,
+) -> Result<Vec<FileOperation>, String> {
+    services::telemetry::record("install_hooks");
+    let server = state.hook_server.lock().await.address();
+    HookInstaller::install(server, dry_run.unwrap_or(false))
+}
+
+#[tauri::command]
+async fn uninstall_hooks(dry_run: Option<bool>) -> Result<Vec<FileOperation>, String> {
+    HookInstaller::uninstall(dry_run.unwrap_or(false))
 }
 
+/// Remove selected parts of claudit's data, settings and hooks (dry run lists them)
 #[tauri::command]
-async fn uninstall_hooks() -> Result<(), String> {
-    HookInstaller::uninstall()
+async fn factory_reset(
+    app: tauri::AppHandle,
+    options: services::reset::ResetOptions,
+    dry_run: Option<bool>,
+) -> Result<Vec<FileOperation>, String> {
+    let dry_run = dry_run.unwrap_or(false);
+    let operations = services::reset::factory_reset(&options, dry_run)?;
+    if !dry_run {
+        // Reload settings-dependent state and stats (duplicate resolutions may be gone)
+        let profile = SettingsService::shared().get().active_profile;
+        tray::switch_profile(&app, profile)?;
+    }
+    Ok(operations)
+}
+
+/// Forget claudit's data about a project and/or date range (dry run lists it)
+#[tauri::command]
+async fn purge_data(
+    state: tauri::State<'_, AppState>,
+    scope: services::retention::PurgeScope,
+    dry_run: Option<bool>,
+) -> Result<Vec<FileOperation>, String> {
+    let dry_run = dry_run.unwrap_or(false);
+    let operations = services::retention::purge(&scope, dry_run)?;
+    if !dry_run {
+        // Removed transcripts and duplicate resolutions change the stats
+        state.analytics.refresh_stats();
+        tray::refresh_trend_cache(&state.analytics);
+    }
+    Ok(operations)
+}
+
+/// Revert claudit's changes to ~/.claude and remove its config directory
+#[tauri::command]
+async fn uninstall_claudit(dry_run: Option<bool>) -> Result<Vec<FileOperation>, String> {
+    services::reset::uninstall(dry_run.unwrap_or(false))
 }
 
 #[tauri::command]
@@ -96,6 +313,168 @@ async fn get_hook_port(state: tauri::State<'_, AppState>) -> Result<u16, String>
     Ok(state.hook_server.lock().await.port())
 }
 
+/// Bearer token for the automation endpoints
+#[tauri::command]
+async fn get_automation_token() -> Result<String, String> {
+    services::automation::token()
+}
+
+/// Token a claudit-hook relay on another machine or in a container needs
+#[tauri::command]
+async fn get_hook_token() -> Result<String, String> {
+    services::hooks::hook_token()
+}
+
+#[tauri::command]
+async fn get_activity_history(limit: Option<usize>) -> Result<Vec<services::activity::ActivityEntry>, String> {
+    Ok(services::activity::recent(limit.unwrap_or(100)))
+}
+
+#[tauri::command]
+async fn get_audit_log() -> Result<Vec<services::audit::AuditEntry>, String> {
+    let mut entries = services::audit::entries();
+    entries.reverse();
+    Ok(entries)
+}
+
+#[tauri::command]
+async fn undo_last_change(force: Option<bool>) -> Result<services::audit::AuditEntry, String> {
+    services::audit::undo_last_change(force.unwrap_or(false))
+}
+
+/// Snapshots taken before imports, pack and hook installs and bulk MCP edits
+#[tauri::command]
+async fn list_restore_points() -> Result<Vec<services::restore_points::RestorePoint>, String> {
+    Ok(services::restore_points::list())
+}
+
+#[tauri::command]
+async fn rollback_to(restore_point: String) -> Result<services::restore_points::RestorePoint, String> {
+    services::restore_points::rollback_to(&restore_point)
+}
+
+#[tauri::command]
+async fn get_active_sessions(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<services::tracker::ActiveSession>, String> {
+    Ok(state.session_tracker.refresh_active())
+}
+
+/// Running cost of a session, from the lines appended since the last check
+#[tauri::command]
+async fn get_live_session_cost(
+    session_id: String,
+) -> Result<services::live_cost::LiveSessionCost, String> {
+    services::live_cost::get(&session_id)
+}
+
+#[tauri::command]
+async fn get_project_notification_settings(
+    project_path: String,
+) -> Result<services::metadata::ProjectNotificationSettings, String> {
+    Ok(services::metadata::get_project(&project_path).notifications)
+}
+
+#[tauri::command]
+async fn set_project_notification_settings(
+    project_path: String,
+    settings: services::metadata::ProjectNotificationSettings,
+) -> Result<(), String> {
+    services::metadata::set_project_notifications(&project_path, settings)
+}
+
+/// Mute all notifications for a while (`None` unmutes); returns when the mute ends
+#[tauri::command]
+async fn mute_notifications(
+    app: tauri::AppHandle,
+    duration: Option<services::notifications::MuteDuration>,
+) -> Result<Option<String>, String> {
+    Ok(tray::set_notification_mute(&app, duration))
+}
+
+/// When the temporary notification mute ends, if one is active
+#[tauri::command]
+async fn get_notification_mute() -> Result<Option<String>, String> {
+    Ok(services::notifications::muted_until().map(|until| until.to_rfc3339()))
+}
+
+#[tauri::command]
+async fn get_session_note(project_path: String, session_id: String) -> Result<Option<String>, String> {
+    Ok(services::metadata::get_project(&project_path).session_notes.remove(&session_id))
+}
+
+#[tauri::command]
+async fn set_session_note(project_path: String, session_id: String, note: String) -> Result<(), String> {
+    services::metadata::set_session_note(&project_path, &session_id, &note)
+}
+
+#[tauri::command]
+async fn set_session_title(project_path: String, session_id: String, title: Option<String>) -> Result<(), String> {
+    services::metadata::set_session_title(&project_path, &session_id, title.as_deref())
+}
+
+#[tauri::command]
+async fn get_action_items(project_path: String) -> Result<Vec<services::metadata::ActionItem>, String> {
+    Ok(services::metadata::get_project(&project_path).action_items)
+}
+
+/// Pull TODO-like statements from the end of a session into the project's checklist
+#[tauri::command]
+async fn extract_action_items(
+    project_path: String,
+    session_id: String,
+) -> Result<Vec<services::metadata::ActionItem>, String> {
+    services::action_items::extract(&project_path, &session_id)
+}
+
+#[tauri::command]
+async fn set_action_item_done(project_path: String, id: String, done: bool) -> Result<(), String> {
+    services::action_items::set_done(&project_path, &id, done)
+}
+
+#[tauri::command]
+async fn remove_action_item(project_path: String, id: String) -> Result<(), String> {
+    services::action_items::remove(&project_path, &id)
+}
+
+#[tauri::command]
+async fn star_prompt(
+    text: String,
+    project_path: Option<String>,
+    session_id: Option<String>,
+) -> Result<services::metadata::StarredPrompt, String> {
+    services::telemetry::record("star_prompt");
+    services::prompt_library::star(&text, project_path, session_id)
+}
+
+#[tauri::command]
+async fn unstar_prompt(id: String) -> Result<(), String> {
+    services::prompt_library::unstar(&id)
+}
+
+#[tauri::command]
+async fn list_starred_prompts(query: Option<String>) -> Result<Vec<services::metadata::StarredPrompt>, String> {
+    Ok(services::prompt_library::list(query.as_deref()))
+}
+
+/// Sessions that used each library prompt, with their tokens, cost, duration and edits
+#[tauri::command]
+async fn get_prompt_outcomes() -> Result<Vec<services::prompt_outcomes::PromptOutcome>, String> {
+    Ok(services::prompt_outcomes::outcomes())
+}
+
+/// Save a starred prompt as a slash command (user-level without a project)
+#[tauri::command]
+async fn prompt_to_slash_command(
+    id: String,
+    name: String,
+    project_path: Option<String>,
+    dry_run: Option<bool>,
+) -> Result<Vec<FileOperation>, String> {
+    let dry_run = dry_run.unwrap_or(false);
+    services::prompt_library::to_slash_command(&id, &name, project_path.as_deref(), dry_run)
+}
+
 #[tauri::command]
 async fn open_analytics_window(app: tauri::AppHandle) -> Result<(), String> {
     if let Some(window) = app.get_webview_window("analytics") {
@@ -105,16 +484,127 @@ async fn open_analytics_window(app: tauri::AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+/// Saved window geometry and last open page
+#[tauri::command]
+async fn get_window_state() -> Result<services::window_state::WindowState, String> {
+    Ok(services::window_state::get())
+}
+
+/// Remember the page that is open so the app reopens on it
+#[tauri::command]
+async fn set_last_route(route: Option<String>) -> Result<(), String> {
+    services::window_state::set_route(route)
+}
+
+/// Forget the saved window layout and restore the default size and position
+#[tauri::command]
+async fn reset_window_layout(app: tauri::AppHandle) -> Result<(), String> {
+    services::window_state::reset_layout(&app)
+}
+
+/// Sync the analytics cache with the session files, or drop it and parse
+/// every file again when `full`. Emits `analytics-rebuild-progress` as it goes.
+#[tauri::command]
+async fn rebuild_analytics(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    full: bool,
+) -> Result<ReindexProgress, String> {
+    services::telemetry::record("analytics_rebuild");
+    let report = state.analytics.reindex(full, &mut |progress| {
+        if progress.done
+            || progress.files_processed == progress.files_total
+            || progress.files_processed.is_multiple_of(25)
+        {
+            let _ = app.emit("analytics-rebuild-progress", progress);
+        }
+    })?;
+    tray::refresh_trend_cache(&state.analytics);
+    Ok(report)
+}
+
+/// Show or hide the tray popover (without an anchor it keeps its last position)
+#[tauri::command]
+async fn toggle_tray_popover(app: tauri::AppHandle) -> Result<(), String> {
+    services::popover::toggle(&app, None)
+}
+
+#[tauri::command]
+async fn hide_tray_popover(app: tauri::AppHandle) -> Result<(), String> {
+    services::popover::hide(&app)
+}
+
+/// Open the fullscreen kiosk dashboard (on a spare monitor if there is one)
+#[tauri::command]
+async fn open_kiosk_dashboard(app: tauri::AppHandle) -> Result<(), String> {
+    services::kiosk::open(&app)
+}
+
+#[tauri::command]
+async fn close_kiosk_dashboard(app: tauri::AppHandle) -> Result<(), String> {
+    services::kiosk::close(&app)
+}
+
+/// Page or project requested with --open / --project at launch (returned once)
+#[tauri::command]
+async fn take_launch_action() -> Result<Option<cli::LaunchAction>, String> {
+    Ok(cli::take_launch_action())
+}
+
+/// Command palette search across projects, sessions, agents, commands and MCP servers
+#[tauri::command]
+async fn quick_search(
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<services::quick_search::QuickSearchResult>, String> {
+    Ok(services::quick_search::quick_search(&query, limit.unwrap_or(30)))
+}
+
 #[tauri::command]
 async fn refresh_tray_menu(app: tauri::AppHandle) -> Result<(), String> {
     tray::update_tray_menu(&app).map_err(|e| e.to_string())
 }
 
+/// Resolved Claude data location (profile, CLAUDE_CONFIG_DIR or ~/.claude)
+#[tauri::command]
+async fn get_claude_paths() -> Result<services::paths::PathProvider, String> {
+    Ok(services::paths::current())
+}
+
+/// Turn demo mode on or off and reload everything from the new location
+#[tauri::command]
+async fn set_demo_mode(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    if enabled {
+        services::demo::ensure()?;
+    }
+    let settings_service = services::SettingsService::shared();
+    let mut settings = settings_service.get();
+    settings.demo_mode = enabled;
+    settings_service.update(settings.clone())?;
+    tray::switch_profile(&app, settings.active_profile)
+}
+
+#[tauri::command]
+async fn set_active_profile(app: tauri::AppHandle, profile: Option<String>) -> Result<(), String> {
+    tray::switch_profile(&app, profile)
+}
+
+#[tauri::command]
+async fn get_startup_report() -> Result<startup::StartupReport, String> {
+    Ok(startup::report())
+}
+
 #[tauri::command]
 async fn get_claude_status() -> Result<ClaudeStatus, String> {
     Ok(services::get_claude_status())
 }
 
+/// Whether usage stopped being recorded while Claude kept running, and why
+#[tauri::command]
+async fn get_data_gap_status() -> Result<services::data_gaps::DataGapStatus, String> {
+    services::data_gaps::status()
+}
+
 // ============ Config Commands ============
 
 #[tauri::command]
@@ -124,7 +614,8 @@ async fn list_claude_md_files() -> Result<Vec<ClaudeMdFile>, String> {
 
 #[tauri::command]
 async fn get_claude_md_content(path: String) -> Result<String, String> {
-    services::config::get_claude_md_content(&path)
+    let path = sandbox::allowed_path(&path)?;
+    services::config::get_claude_md_content(&path.to_string_lossy())
 }
 
 #[tauri::command]
@@ -139,12 +630,63 @@ async fn list_commands() -> Result<Vec<CommandInfo>, String> {
 
 #[tauri::command]
 async fn get_agent_or_command_content(path: String) -> Result<String, String> {
-    services::config::get_agent_or_command_content(&path)
+    let path = sandbox::allowed_path(&path)?;
+    services::config::get_agent_or_command_content(&path.to_string_lossy())
 }
 
 #[tauri::command]
 async fn list_directory_files(path: String) -> Result<Vec<services::config::DirectoryFile>, String> {
-    services::config::list_directory_files(&path)
+    let path = sandbox::allowed_path(&path)?;
+    services::config::list_directory_files(&path.to_string_lossy())
+}
+
+/// File type filter for `pick_path`
+#[derive(serde::Deserialize)]
+struct DialogFilter {
+    name: String,
+    extensions: Vec<String>,
+}
+
+/// Show a native file dialog and allow the picked path for file-access commands.
+/// `mode` is "file", "folder" or "save".
+#[tauri::command]
+async fn pick_path(
+    app: tauri::AppHandle,
+    mode: String,
+    title: Option<String>,
+    default_path: Option<String>,
+    filters: Option<Vec<DialogFilter>>,
+) -> Result<Option<String>, String> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let mut builder = app.dialog().file();
+    if let Some(title) = title {
+        builder = builder.set_title(title);
+    }
+    if let Some(default_path) = default_path {
+        builder = builder.set_file_name(default_path);
+    }
+    for filter in filters.unwrap_or_default() {
+        let extensions: Vec<&str> = filter.extensions.iter().map(|e| e.as_str()).collect();
+        builder = builder.add_filter(filter.name, &extensions);
+    }
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    let send = move |picked: Option<tauri_plugin_dialog::FilePath>| {
+        let _ = tx.send(picked.and_then(|p| p.into_path().ok()));
+    };
+    match mode.as_str() {
+        "file" => builder.pick_file(send),
+        "folder" => builder.pick_folder(send),
+        "save" => builder.save_file(send),
+        _ => return Err(format!("Unknown dialog mode: {}", mode)),
+    }
+
+    let picked = rx.await.ok().flatten();
+    if let Some(path) = &picked {
+        sandbox::allow_user_picked(path);
+    }
+    Ok(picked.map(|p| p.to_string_lossy().to_string()))
 }
 
 #[tauri::command]
@@ -192,11 +734,181 @@ async fn get_project_details(project_path: String) -> Result<ProjectDetails, Str
     services::config::get_project_details(&project_path)
 }
 
+/// A project's usage split by the monorepo package it happened in
+#[tauri::command]
+async fn get_workspace_breakdown(
+    project_path: String,
+    days: Option<u32>,
+) -> Result<services::workspaces::WorkspaceBreakdown, String> {
+    Ok(services::workspaces::breakdown(&project_path, days))
+}
+
+/// A project's daily spend for the last `days` days (default 30) and its trend
+#[tauri::command]
+async fn get_project_cost_history(
+    state: tauri::State<'_, AppState>,
+    project_path: String,
+    days: Option<u32>,
+) -> Result<ProjectCostHistory, String> {
+    Ok(state
+        .analytics
+        .get_project_cost_history(&project_path, days.unwrap_or(30)))
+}
+
 #[tauri::command]
 async fn get_project_commands(project_path: String) -> Result<Vec<CommandInfo>, String> {
     services::config::get_project_commands(&project_path)
 }
 
+#[tauri::command]
+async fn list_mcp_templates() -> Result<Vec<services::mcp_templates::McpTemplate>, String> {
+    Ok(services::mcp_templates::list_templates())
+}
+
+/// Add an MCP server from a catalog template; returns the config that was written
+#[tauri::command]
+async fn add_mcp_server_from_template(
+    template: String,
+    params: std::collections::HashMap<String, String>,
+    scope: services::config::McpScope,
+    name: Option<String>,
+) -> Result<serde_json::Value, String> {
+    check_mcp_scope(&scope)?;
+    services::mcp_templates::add_from_template(&template, &params, &scope, name.as_deref())
+}
+
+/// Find duplicate and conflicting MCP servers, commands, agents and CLAUDE.md directives
+#[tauri::command]
+async fn analyze_config_conflicts() -> Result<Vec<services::conflicts::ConfigFinding>, String> {
+    Ok(services::conflicts::analyze())
+}
+
+#[tauri::command]
+async fn list_project_config_items(project_path: String) -> Result<services::config::ProjectConfigItems, String> {
+    sandbox::registered_project(&project_path)?;
+    services::config::list_project_config_items(&project_path)
+}
+
+/// Copy selected commands, agents, MCP servers and CLAUDE.md sections between projects
+#[tauri::command]
+async fn copy_project_config(
+    from: String,
+    to: String,
+    items: Vec<services::config::ProjectConfigItem>,
+    overwrite: Option<bool>,
+    dry_run: Option<bool>,
+) -> Result<services::config::ProjectCopyResult, String> {
+    sandbox::registered_project(&from)?;
+    sandbox::registered_project(&to)?;
+    services::config::copy_project_config(&from, &to, &items, overwrite.unwrap_or(false), dry_run.unwrap_or(false))
+}
+
+/// Project scopes must refer to a project registered in ~/.claude.json
+fn check_mcp_scope(scope: &services::config::McpScope) -> Result<(), String> {
+    if let services::config::McpScope::Project { path } = scope {
+        sandbox::registered_project(path)?;
+    }
+    Ok(())
+}
+
+/// Write selected user-level agents, commands and MCP servers to a shareable pack
+#[tauri::command]
+async fn create_config_pack(
+    items: Vec<services::config_pack::PackItem>,
+    output: String,
+    name: Option<String>,
+) -> Result<services::config_pack::PackManifest, String> {
+    let output = sandbox::allowed_path(&output)?;
+    let name = name.unwrap_or_else(|| {
+        output.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default()
+    });
+    services::config_pack::create(&items, &name, &output)
+}
+
+/// What a config pack would write and run, and who signed it, shown before installing
+#[tauri::command]
+async fn review_config_pack(
+    path: String,
+    scope: services::config::McpScope,
+    params: Option<std::collections::HashMap<String, std::collections::HashMap<String, String>>>,
+    overwrite: Option<bool>,
+) -> Result<services::config_pack::PackReview, String> {
+    let path = sandbox::allowed_path(&path)?;
+    check_mcp_scope(&scope)?;
+    services::config_pack::review(&path, &scope, &params.unwrap_or_default(), overwrite.unwrap_or(false))
+}
+
+/// Install a config pack globally or into a project; `params` fills in MCP
+/// values per server. Packs without a trusted signature need `trust_unsigned`.
+#[tauri::command]
+async fn install_config_pack(
+    path: String,
+    scope: services::config::McpScope,
+    params: Option<std::collections::HashMap<String, std::collections::HashMap<String, String>>>,
+    overwrite: Option<bool>,
+    trust_unsigned: Option<bool>,
+    dry_run: Option<bool>,
+) -> Result<services::config_pack::PackInstallResult, String> {
+    services::telemetry::record("config_pack");
+    let path = sandbox::allowed_path(&path)?;
+    check_mcp_scope(&scope)?;
+    services::config_pack::install(
+        &path,
+        &scope,
+        &params.unwrap_or_default(),
+        overwrite.unwrap_or(false),
+        trust_unsigned.unwrap_or(false),
+        dry_run.unwrap_or(false),
+    )
+}
+
+/// Copy an MCP server between global and project scope
+#[tauri::command]
+async fn copy_mcp_server(
+    name: String,
+    from: services::config::McpScope,
+    to: services::config::McpScope,
+    overwrite: Option<bool>,
+    dry_run: Option<bool>,
+) -> Result<services::config::McpBulkResult, String> {
+    check_mcp_scope(&from)?;
+    check_mcp_scope(&to)?;
+    services::config::copy_mcp_server(&name, &from, &[to], overwrite.unwrap_or(false), dry_run.unwrap_or(false))
+}
+
+/// Apply one MCP server to several projects at once
+#[tauri::command]
+async fn apply_mcp_server_to_projects(
+    name: String,
+    from: services::config::McpScope,
+    projects: Vec<String>,
+    overwrite: Option<bool>,
+    dry_run: Option<bool>,
+) -> Result<services::config::McpBulkResult, String> {
+    check_mcp_scope(&from)?;
+    let targets: Vec<_> = projects
+        .into_iter()
+        .map(|path| services::config::McpScope::Project { path })
+        .collect();
+    for target in &targets {
+        check_mcp_scope(target)?;
+    }
+    services::config::copy_mcp_server(&name, &from, &targets, overwrite.unwrap_or(false), dry_run.unwrap_or(false))
+}
+
+/// Remove MCP servers whose name matches a glob pattern
+#[tauri::command]
+async fn bulk_remove_mcp_servers(
+    pattern: String,
+    scopes: Option<Vec<services::config::McpScope>>,
+    dry_run: Option<bool>,
+) -> Result<services::config::McpBulkResult, String> {
+    for scope in scopes.iter().flatten() {
+        check_mcp_scope(scope)?;
+    }
+    services::config::bulk_remove_mcp_servers(&pattern, scopes.as_deref(), dry_run.unwrap_or(false))
+}
+
 #[tauri::command]
 async fn get_project_mcp_servers(project_path: String) -> Result<Vec<McpServer>, String> {
     services::config::get_project_mcp_servers(&project_path)
@@ -214,11 +926,14 @@ async fn remove_project_mcp_server(project_path: String, name: String) -> Result
 
 #[tauri::command]
 async fn set_project_image(project_path: String, image_source_path: String) -> Result<String, String> {
-    services::config::set_project_image(&project_path, &image_source_path)
+    sandbox::registered_project(&project_path)?;
+    let image_source_path = sandbox::allowed_path(&image_source_path)?;
+    services::config::set_project_image(&project_path, &image_source_path.to_string_lossy())
 }
 
 #[tauri::command]
 async fn remove_project_image(project_path: String) -> Result<(), String> {
+    sandbox::registered_project(&project_path)?;
     services::config::remove_project_image(&project_path)
 }
 
@@ -232,6 +947,67 @@ async fn get_ai_suggestions(days: u32) -> Result<Vec<AiSuggestion>, String> {
     services::config::get_ai_suggestions(days)
 }
 
+/// Slash command and agent usage counts and estimated cost
+#[tauri::command]
+async fn get_invocation_stats(
+    days: Option<u32>,
+) -> Result<services::invocations::InvocationReport, String> {
+    Ok(services::invocations::invocation_stats(days))
+}
+
+/// MCP servers, agents and commands not used in the last `days` days
+#[tauri::command]
+async fn get_unused_config(
+    days: u32,
+) -> Result<services::unused_config::UnusedConfigReport, String> {
+    Ok(services::unused_config::unused_config(days))
+}
+
+/// Turn off unused config items from `get_unused_config`
+#[tauri::command]
+async fn disable_unused_config(
+    ids: Vec<String>,
+    days: u32,
+    dry_run: Option<bool>,
+) -> Result<Vec<FileOperation>, String> {
+    services::unused_config::disable(&ids, days, dry_run.unwrap_or(false))
+}
+
+/// Agents and commands whose turns would be cheaper on a smaller model
+#[tauri::command]
+async fn get_model_routing_suggestions(
+    days: u32,
+) -> Result<services::model_routing::ModelRoutingReport, String> {
+    Ok(services::model_routing::suggestions(days))
+}
+
+/// Set `model:` in an agent or command's frontmatter
+#[tauri::command]
+async fn apply_model_override(
+    path: String,
+    model: String,
+    dry_run: Option<bool>,
+) -> Result<Vec<FileOperation>, String> {
+    sandbox::allowed_path(&path)?;
+    services::model_routing::apply(&path, &model, dry_run.unwrap_or(false))
+}
+
+/// Accept, dismiss or snooze a suggestion
+#[tauri::command]
+async fn record_suggestion_feedback(
+    suggestion: AiSuggestion,
+    action: services::suggestion_feedback::FeedbackAction,
+    snooze_days: Option<u32>,
+) -> Result<services::suggestion_feedback::SuggestionFeedback, String> {
+    services::suggestion_feedback::record(&suggestion, action, snooze_days)
+}
+
+/// Bring back dismissed and snoozed suggestions
+#[tauri::command]
+async fn clear_suggestion_feedback() -> Result<(), String> {
+    services::suggestion_feedback::clear()
+}
+
 #[tauri::command]
 async fn get_project_suggestions(project_path: String) -> Result<ProjectSuggestion, String> {
     services::config::get_project_suggestions(&project_path).await
@@ -244,27 +1020,100 @@ async fn run_claude_with_prompt(project_path: String, prompt: String) -> Result<
 
 #[tauri::command]
 async fn export_config(options: ExportOptions, output_path: String) -> Result<BackupInfo, String> {
-    services::config::export_config(options, &output_path)
+    let output_path = sandbox::allowed_path(&output_path)?;
+    services::config_backup::export(options, &output_path)
 }
 
+/// Export claudit's own settings and data (not the Claude config) to a JSON file
 #[tauri::command]
-async fn import_config(zip_path: String) -> Result<(), String> {
-    services::config::import_config(&zip_path)
+async fn export_app_data(path: String) -> Result<services::app_data::AppDataSummary, String> {
+    let path = sandbox::allowed_path(&path)?;
+    services::app_data::export(&path)
+}
+
+/// Replace claudit's settings and data with an export and reload everything
+#[tauri::command]
+async fn import_app_data(
+    app: tauri::AppHandle,
+    path: String,
+) -> Result<services::app_data::AppDataSummary, String> {
+    let path = sandbox::allowed_path(&path)?;
+    let (settings, summary) = services::app_data::import(&path)?;
+    // Refreshes the tray, settings listeners and stats, which depend on the
+    // imported profile and duplicate resolutions
+    tray::switch_profile(&app, settings.active_profile)?;
+    Ok(summary)
+}
+
+#[tauri::command]
+async fn import_config(
+    zip_path: String,
+    dry_run: Option<bool>,
+) -> Result<services::config_backup::ConfigImport, String> {
+    let zip_path = sandbox::allowed_path(&zip_path)?;
+    services::config_backup::import(&zip_path, dry_run.unwrap_or(false))
 }
 
 #[tauri::command]
 async fn get_config_git_status() -> Result<GitStatus, String> {
-    services::config::get_config_git_status()
+    Ok(services::config_sync::git_status())
+}
+
+/// Make the Claude config folder a git repository
+#[tauri::command]
+async fn init_config_repo() -> Result<GitStatus, String> {
+    services::config_sync::init_repo()
+}
+
+/// Commit all config changes; false when there was nothing to commit
+#[tauri::command]
+async fn commit_config_changes(message: String) -> Result<bool, String> {
+    services::config_sync::commit_changes(&message)
+}
+
+/// Push committed config changes
+#[tauri::command]
+async fn push_config_changes() -> Result<GitStatus, String> {
+    services::config_sync::push_changes()
+}
+
+/// State of the GitHub sync repository
+#[tauri::command]
+async fn get_config_sync_status() -> Result<services::config_sync::ConfigSyncStatus, String> {
+    Ok(services::config_sync::status())
+}
+
+/// Store the GitHub token for config sync in the keychain (empty removes it)
+#[tauri::command]
+async fn set_github_token(token: String) -> Result<(), String> {
+    services::config_sync::set_token(&token)
+}
+
+/// Commit the Claude config and push it to the sync remote
+#[tauri::command]
+async fn push_config(message: Option<String>) -> Result<services::config_sync::ConfigSyncResult, String> {
+    services::telemetry::record("config_sync");
+    services::config_sync::push(message.as_deref())
+}
+
+/// Merge the sync remote's changes; conflicts are reported unless a resolution is given
+#[tauri::command]
+async fn pull_config(
+    resolution: Option<services::config_sync::ConflictResolution>,
+) -> Result<services::config_sync::ConfigSyncResult, String> {
+    services::config_sync::pull(resolution)
 }
 
 #[tauri::command]
 async fn open_in_editor(path: String) -> Result<(), String> {
-    services::config::open_in_editor(&path)
+    let path = sandbox::allowed_path(&path)?;
+    services::config::open_in_editor(&path.to_string_lossy())
 }
 
 #[tauri::command]
 async fn open_folder(path: String) -> Result<(), String> {
-    services::config::open_folder(&path)
+    let path = sandbox::allowed_path(&path)?;
+    services::config::open_folder(&path.to_string_lossy())
 }
 
 #[tauri::command]
@@ -272,6 +1121,18 @@ async fn get_model_pricing() -> Result<Vec<ModelPricing>, String> {
     Ok(services::pricing::get_all_pricing())
 }
 
+/// All price periods, including prices that no longer apply
+#[tauri::command]
+async fn get_pricing_history() -> Result<Vec<ModelPricing>, String> {
+    Ok(services::pricing::get_pricing_history())
+}
+
+/// Context window, output limit, knowledge cutoff and pricing of each known model
+#[tauri::command]
+async fn get_model_catalog() -> Result<Vec<services::model_catalog::ModelInfo>, String> {
+    Ok(services::model_catalog::catalog())
+}
+
 // ============ Session Commands ============
 
 #[tauri::command]
@@ -279,174 +1140,414 @@ async fn list_project_sessions(project_path: String) -> Result<Vec<SessionInfo>,
     services::list_sessions(&project_path)
 }
 
+/// A session's messages: all of them, or `limit` from `offset` (or after the
+/// message `after_uuid`) to page through long sessions
 #[tauri::command]
-async fn get_session_conversation(project_path: String, session_id: String) -> Result<SessionConversation, String> {
-    services::get_session_conversation(&project_path, &session_id)
+async fn get_session_conversation(
+    project_path: String,
+    session_id: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    after_uuid: Option<String>,
+) -> Result<SessionConversation, String> {
+    services::get_session_page(
+        &project_path,
+        &session_id,
+        offset.unwrap_or(0),
+        limit,
+        after_uuid.as_deref(),
+    )
 }
 
 #[tauri::command]
-async fn export_session_to_html(project_path: String, session_id: String) -> Result<String, String> {
-    services::export_session_html(&project_path, &session_id)
+async fn get_session_message_count(project_path: String, session_id: String) -> Result<usize, String> {
+    services::get_session_message_count(&project_path, &session_id)
 }
 
+/// Follow a session as Claude writes it: returns the conversation so far and
+/// emits `session-messages` with new messages until `unfollow_session`
 #[tauri::command]
-async fn search_project_sessions(project_path: String, query: String) -> Result<Vec<SessionSearchResult>, String> {
-    services::search_sessions(&project_path, &query)
+async fn follow_session(
+    app: tauri::AppHandle,
+    project_path: String,
+    session_id: String,
+) -> Result<SessionConversation, String> {
+    services::telemetry::record("session_follow");
+    services::session_follow::follow(&app, &project_path, &session_id)
 }
 
-/// Detect available terminal apps on macOS
-#[cfg(target_os = "macos")]
-fn detect_terminal_app() -> String {
-    // Check for common terminal apps in order of preference
-    let terminals = ["iTerm", "Warp", "Alacritty", "kitty", "Terminal"];
+#[tauri::command]
+async fn unfollow_session(session_id: String) -> Result<bool, String> {
+    Ok(services::session_follow::unfollow(&session_id))
+}
 
-    for terminal in terminals {
-        let check = std::process::Command::new("osascript")
-            .arg("-e")
-            .arg(format!(r#"tell application "System Events" to (name of processes) contains "{}""#, terminal))
-            .output();
+/// Sessions streamed to the live viewer (hook events in the last hour)
+#[tauri::command]
+async fn get_live_sessions() -> Result<Vec<services::live_sessions::LiveSessionInfo>, String> {
+    Ok(services::live_sessions::list())
+}
 
-        if let Ok(output) = check {
-            let result = String::from_utf8_lossy(&output.stdout);
-            if result.trim() == "true" {
-                return terminal.to_string();
-            }
-        }
-    }
+/// Latest messages of a live session; later ones arrive as `session-message-appended`
+#[tauri::command]
+async fn get_live_session(
+    session_id: String,
+) -> Result<Option<services::live_sessions::SessionMessageAppended>, String> {
+    services::telemetry::record("live_view");
+    Ok(services::live_sessions::snapshot(&session_id))
+}
 
-    // Check if apps exist in /Applications
-    for terminal in terminals {
-        let app_path = format!("/Applications/{}.app", terminal);
-        if std::path::Path::new(&app_path).exists() {
-            return terminal.to_string();
-        }
-    }
+/// Stats no longer include an archived or deleted session (or again include a
+/// restored one)
+fn refresh_after_session_move(app: &tauri::AppHandle, state: &AppState) {
+    state.analytics.refresh_stats();
+    tray::refresh_trend_cache(&state.analytics);
+    let _ = tray::update_tray_menu(app);
+    let _ = app.emit("refresh-stats", ());
+}
 
-    // Default to Terminal
-    "Terminal".to_string()
+/// Move a session out of Claude's projects folder into claudit's archive
+#[tauri::command]
+async fn archive_session(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    project_path: String,
+    session_id: String,
+) -> Result<services::session_archive::RemovedSession, String> {
+    services::telemetry::record("session_archive");
+    services::session_follow::unfollow(&session_id);
+    let archived = services::session_archive::archive(&project_path, &session_id)?;
+    refresh_after_session_move(&app, &state);
+    Ok(archived)
 }
 
+/// Move a session to claudit's trash; it is only deleted when the trash is emptied
 #[tauri::command]
-async fn open_terminal_with_resume(
+async fn delete_session(
+    app: tauri::AppHandle,
     state: tauri::State<'_, AppState>,
     project_path: String,
     session_id: String,
-) -> Result<(), String> {
-    #[cfg(target_os = "macos")]
-    {
-        let settings = state.settings.get();
-        let terminal = if settings.terminal_app == "auto" {
-            detect_terminal_app()
-        } else {
-            settings.terminal_app.clone()
-        };
-
-        // Escape single quotes in the path
-        let escaped_path = project_path.replace("'", "'\\''");
-        let command = format!("cd '{}' && claude --resume {}", escaped_path, session_id);
-
-        let script = match terminal.as_str() {
-            "iTerm" => format!(
-                r#"tell application "iTerm"
-                    activate
-                    try
-                        tell current window
-                            create tab with default profile
-                            tell current session
-                                write text "{}"
-                            end tell
-                        end tell
-                    on error
-                        create window with default profile
-                        tell current window
-                            tell current session
-                                write text "{}"
-                            end tell
-                        end tell
-                    end try
-                end tell"#,
-                command, command
-            ),
-            "Warp" => format!(
-                r#"tell application "Warp"
-                    activate
-                    do script "{}"
-                end tell"#,
-                command
-            ),
-            "Alacritty" | "kitty" => {
-                // For these terminals, use open command with shell
-                return std::process::Command::new("open")
-                    .arg("-a")
-                    .arg(&terminal)
-                    .spawn()
-                    .map_err(|e| format!("Failed to open {}: {}", terminal, e))
-                    .and_then(|_| {
-                        // Give the terminal time to open, then use pbcopy + paste approach
-                        std::thread::sleep(std::time::Duration::from_millis(500));
-                        // Copy command to clipboard
-                        let mut child = std::process::Command::new("pbcopy")
-                            .stdin(std::process::Stdio::piped())
-                            .spawn()
-                            .map_err(|e| format!("Failed to copy command: {}", e))?;
-                        if let Some(stdin) = child.stdin.as_mut() {
-                            use std::io::Write;
-                            stdin.write_all(command.as_bytes()).ok();
-                        }
-                        child.wait().ok();
-                        Ok(())
-                    });
-            }
-            _ => format!(
-                r#"tell application "Terminal"
-                    activate
-                    do script "{}"
-                end tell"#,
-                command
-            ),
-        };
-
-        std::process::Command::new("osascript")
-            .arg("-e")
-            .arg(&script)
-            .spawn()
-            .map_err(|e| format!("Failed to open {}: {}", terminal, e))?;
-        Ok(())
-    }
-    #[cfg(not(target_os = "macos"))]
-    {
-        Err("Terminal launch only supported on macOS".to_string())
-    }
+) -> Result<services::session_archive::RemovedSession, String> {
+    services::telemetry::record("session_trash");
+    services::session_follow::unfollow(&session_id);
+    let trashed = services::session_archive::trash(&project_path, &session_id)?;
+    refresh_after_session_move(&app, &state);
+    Ok(trashed)
+}
+
+#[tauri::command]
+async fn list_removed_sessions() -> Result<Vec<services::session_archive::RemovedSession>, String> {
+    Ok(services::session_archive::list())
+}
+
+/// Move an archived or trashed session back to where it was
+#[tauri::command]
+async fn restore_session(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    session_id: String,
+) -> Result<services::session_archive::RemovedSession, String> {
+    let restored = services::session_archive::restore(&session_id)?;
+    refresh_after_session_move(&app, &state);
+    Ok(restored)
+}
+
+/// Permanently delete trashed sessions (older than `older_than_days`, or all)
+#[tauri::command]
+async fn empty_session_trash(older_than_days: Option<u32>) -> Result<usize, String> {
+    services::session_archive::empty_trash(older_than_days)
+}
+
+/// Session as a playback timeline; `chunk_chars` sets the text chunk size (default 40)
+#[tauri::command]
+async fn get_session_replay(
+    project_path: String,
+    session_id: String,
+    chunk_chars: Option<usize>,
+) -> Result<SessionReplay, String> {
+    services::get_session_replay(&project_path, &session_id, chunk_chars.unwrap_or(40))
+}
+
+#[tauri::command]
+async fn export_session_to_html(project_path: String, session_id: String) -> Result<String, String> {
+    session_export::export_to_string(
+        &project_path,
+        &session_id,
+        SessionExportFormat::Html,
+        &SessionExportOptions::default(),
+    )
+}
+
+/// A session as Markdown, e.g. for pasting into a PR
+#[tauri::command]
+async fn export_session_markdown(project_path: String, session_id: String) -> Result<String, String> {
+    session_export::export_to_string(
+        &project_path,
+        &session_id,
+        SessionExportFormat::Markdown,
+        &SessionExportOptions::default(),
+    )
+}
+
+/// Session export formats, for save dialog filters
+#[tauri::command]
+async fn get_session_export_formats() -> Result<Vec<session_export::ExportFormatInfo>, String> {
+    Ok(session_export::formats())
+}
+
+/// Write a session's export (HTML unless another format is given) straight to
+/// `path`; returns the bytes written
+#[tauri::command]
+async fn export_session_to_file(
+    project_path: String,
+    session_id: String,
+    path: String,
+    format: Option<SessionExportFormat>,
+    options: Option<SessionExportOptions>,
+) -> Result<u64, String> {
+    services::telemetry::record("session_export");
+    let path = sandbox::allowed_path(&path)?;
+    session_export::export_to_file(
+        &project_path,
+        &session_id,
+        format.unwrap_or_default(),
+        &options.unwrap_or_default(),
+        &path,
+    )
+}
+
+/// Print a session's HTML export to a PDF at `path` with a headless browser;
+/// returns the bytes written
+#[tauri::command]
+async fn export_session_pdf(
+    project_path: String,
+    session_id: String,
+    path: String,
+    theme: Option<session_export::ExportTheme>,
+) -> Result<u64, String> {
+    services::telemetry::record("session_export_pdf");
+    let path = sandbox::allowed_path(&path)?;
+    let options = SessionExportOptions {
+        theme: theme.unwrap_or_default(),
+        ..Default::default()
+    };
+    session_export::export_to_file(&project_path, &session_id, SessionExportFormat::Pdf, &options, &path)
+}
+
+/// Sessions a collection export with `filter` would contain, oldest first
+#[tauri::command]
+async fn get_session_collection(
+    filter: SessionCollectionFilter,
+) -> Result<Vec<session_export::CollectionEntry>, String> {
+    session_collection::matching_sessions(&filter)
+}
+
+/// Export every session matching `filter` into one document at `output`,
+/// with a table of contents and each session's cost
+#[tauri::command]
+async fn export_session_collection(
+    filter: SessionCollectionFilter,
+    format: Option<SessionExportFormat>,
+    output: String,
+    options: Option<SessionExportOptions>,
+) -> Result<session_collection::CollectionExportResult, String> {
+    services::telemetry::record("session_collection_export");
+    let output = sandbox::allowed_path(&output)?;
+    session_collection::export(
+        &filter,
+        format.unwrap_or_default(),
+        &options.unwrap_or_default(),
+        &output,
+    )
+}
+
+/// Usage report for the last 7 (week) or 30 (month) days
+#[tauri::command]
+async fn generate_report(
+    period: services::reports::ReportPeriod,
+) -> Result<services::reports::UsageReport, String> {
+    Ok(services::reports::generate(period))
+}
+
+/// Write a usage report as Markdown or HTML; returns the bytes written
+#[tauri::command]
+async fn export_report(
+    period: services::reports::ReportPeriod,
+    format: services::reports::ReportFormat,
+    path: String,
+) -> Result<u64, String> {
+    services::telemetry::record("usage_report");
+    let path = sandbox::allowed_path(&path)?;
+    let report = services::reports::generate(period);
+    let contents = services::reports::render(&report, format);
+    std::fs::write(&path, &contents)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(contents.len() as u64)
+}
+
+/// Prepare a session conversation, or a page of it (as in
+/// `get_session_conversation`), for chunked reading (see `read_transfer_chunk`)
+#[tauri::command]
+async fn start_session_transfer(
+    project_path: String,
+    session_id: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    after_uuid: Option<String>,
+) -> Result<services::transfer::TransferInfo, String> {
+    let conversation = services::get_session_page(
+        &project_path,
+        &session_id,
+        offset.unwrap_or(0),
+        limit,
+        after_uuid.as_deref(),
+    )?;
+    services::transfer::start(&conversation)
+}
+
+/// One gzipped chunk of a transfer, sent as raw bytes
+#[tauri::command]
+async fn read_transfer_chunk(id: String, index: usize) -> Result<tauri::ipc::Response, String> {
+    services::transfer::read_chunk(&id, index).map(tauri::ipc::Response::new)
+}
+
+/// Costliest sessions for a project (or all projects when `project_path` is omitted).
+/// `range` is "today", "week", "month" or "all".
+#[tauri::command]
+async fn get_expensive_sessions(
+    project_path: Option<String>,
+    limit: Option<usize>,
+    range: Option<String>,
+) -> Result<Vec<ExpensiveSession>, String> {
+    services::list_expensive_sessions(
+        project_path.as_deref(),
+        limit.unwrap_or(10),
+        range.as_deref().unwrap_or("month"),
+    )
+}
+
+/// Most recently active sessions across all projects
+#[tauri::command]
+async fn get_recent_sessions(limit: Option<usize>) -> Result<Vec<ExpensiveSession>, String> {
+    services::list_recent_sessions(limit.unwrap_or(5))
+}
+
+#[tauri::command]
+async fn search_project_sessions(project_path: String, query: String) -> Result<Vec<SessionSearchResult>, String> {
+    services::telemetry::record("session_search");
+    services::search_sessions(&project_path, &query)
+}
+
+#[tauri::command]
+async fn open_terminal_with_resume(project_path: String, session_id: String) -> Result<(), String> {
+    services::terminal::resume_session(&project_path, &session_id)
+}
+
+/// WSL distributions and their Claude data folders (Windows only)
+#[tauri::command]
+async fn detect_wsl_distros() -> Result<Vec<services::wsl::WslDistro>, String> {
+    Ok(services::wsl::detect())
 }
 
 // ============ Main Entry ============
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    tauri::Builder::default()
+    startup::begin();
+    services::telemetry::set_enabled(SettingsService::shared().get().telemetry_enabled);
+    services::hook_inspector::set_enabled(SettingsService::shared().get().hook_inspector);
+    services::telemetry::install_panic_hook();
+
+    let builder = tauri::Builder::default();
+    // Registered first so a second launch hands over its flags and exits right away
+    #[cfg(desktop)]
+    let builder = builder.plugin(tauri_plugin_single_instance::init(cli::apply_forwarded));
+
+    builder
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .manage(AppState {
             analytics: AnalyticsService::new(),
-            settings: SettingsService::new(),
-            hook_server: Arc::new(Mutex::new(HookServer::new(3456))),
+            settings: SettingsService::shared(),
+            hook_server: Arc::new(Mutex::new(HookServer::new(SettingsService::shared().get().hook_port))),
+            session_tracker: Arc::new(SessionTracker::new()),
         })
         .invoke_handler(tauri::generate_handler![
             get_stats,
             refresh_stats,
             get_chart_data,
+            drill_down,
+            simulate_cache_savings,
+            get_latency_stats,
+            get_error_stats,
+            get_compaction_report,
+            get_duplicate_sessions,
+            resolve_duplicate_session,
+            import_console_costs,
+            get_cost_reconciliation,
+            get_goal_progress,
             get_settings,
             update_settings,
+            preview_cost_format,
+            get_telemetry_preview,
+            get_recent_hook_requests,
+            clear_hook_requests,
+            get_stats_summary,
+            get_summary_template_info,
+            copy_stats_summary,
             toggle_section,
             check_hooks_installed,
             install_hooks,
             uninstall_hooks,
+            factory_reset,
+            purge_data,
+            uninstall_claudit,
             get_hook_port,
+            get_automation_token,
+            get_hook_token,
+            get_activity_history,
+            get_active_sessions,
+            get_live_session_cost,
+            get_audit_log,
+            undo_last_change,
+            list_restore_points,
+            rollback_to,
+            get_project_notification_settings,
+            set_project_notification_settings,
+            mute_notifications,
+            get_notification_mute,
+            get_session_note,
+            set_session_note,
+            set_session_title,
+            get_action_items,
+            extract_action_items,
+            set_action_item_done,
+            remove_action_item,
+            star_prompt,
+            unstar_prompt,
+            list_starred_prompts,
+            prompt_to_slash_command,
+            get_prompt_outcomes,
             open_analytics_window,
             refresh_tray_menu,
+            get_window_state,
+            set_last_route,
+            reset_window_layout,
+            rebuild_analytics,
+            open_kiosk_dashboard,
+            close_kiosk_dashboard,
+            toggle_tray_popover,
+            hide_tray_popover,
+            quick_search,
+            take_launch_action,
             get_claude_status,
+            get_data_gap_status,
+            get_startup_report,
+            get_claude_paths,
+            set_active_profile,
+            set_demo_mode,
             // Config commands
             list_claude_md_files,
             get_claude_md_content,
@@ -454,6 +1555,7 @@ pub fn run() {
             list_commands,
             get_agent_or_command_content,
             list_directory_files,
+            pick_path,
             get_installed_plugins,
             get_mcp_servers,
             get_mcp_config_path,
@@ -461,8 +1563,21 @@ pub fn run() {
             add_mcp_server,
             remove_mcp_server,
             update_mcp_server,
+            list_mcp_templates,
+            add_mcp_server_from_template,
+            copy_mcp_server,
+            apply_mcp_server_to_projects,
+            bulk_remove_mcp_servers,
+            analyze_config_conflicts,
+            list_project_config_items,
+            copy_project_config,
+            create_config_pack,
+            review_config_pack,
+            install_config_pack,
             list_projects,
             get_project_details,
+            get_project_cost_history,
+            get_workspace_breakdown,
             get_project_commands,
             get_project_mcp_servers,
             update_project_mcp_server,
@@ -471,29 +1586,116 @@ pub fn run() {
             remove_project_image,
             analyze_chat_patterns,
             get_ai_suggestions,
+            get_invocation_stats,
+            get_unused_config,
+            disable_unused_config,
+            get_model_routing_suggestions,
+            apply_model_override,
+            record_suggestion_feedback,
+            clear_suggestion_feedback,
             get_project_suggestions,
             run_claude_with_prompt,
             export_config,
             import_config,
+            export_app_data,
+            import_app_data,
             get_config_git_status,
+            init_config_repo,
+            commit_config_changes,
+            push_config_changes,
+            get_config_sync_status,
+            set_github_token,
+            push_config,
+            pull_config,
             open_in_editor,
             open_folder,
             get_model_pricing,
+            get_pricing_history,
+            get_model_catalog,
             // Session commands
             list_project_sessions,
             get_session_conversation,
+            get_session_message_count,
+            follow_session,
+            unfollow_session,
+            get_live_sessions,
+            get_live_session,
+            archive_session,
+            delete_session,
+            list_removed_sessions,
+            restore_session,
+            empty_session_trash,
+            get_session_replay,
             export_session_to_html,
+            export_session_markdown,
+            export_session_to_file,
+            export_session_pdf,
+            get_session_export_formats,
+            get_session_collection,
+            export_session_collection,
+            generate_report,
+            export_report,
+            start_session_transfer,
+            read_transfer_chunk,
             search_project_sessions,
+            get_expensive_sessions,
+            get_recent_sessions,
             open_terminal_with_resume,
+            detect_wsl_distros,
         ])
         .setup(|app| {
             let handle = app.handle().clone();
 
-            // Initialize menu cache before creating tray (does disk I/O once at startup)
-            refresh_menu_cache();
+            // Everything that depends on settings follows their changes from here on
+            tray::follow_settings(app.handle());
+            services::watcher::follow_settings(app.handle());
+            services::hooks::follow_settings(app.handle(), app.state::<AppState>().hook_server.clone());
+            let settings_handle = handle.clone();
+            services::settings::on_change(move |change| {
+                services::telemetry::set_enabled(change.current.telemetry_enabled);
+                services::hook_inspector::set_enabled(change.current.hook_inspector);
+                if change.previous.path_mappings != change.current.path_mappings {
+                    // Sessions move to the projects the mappings point at
+                    settings_handle.state::<AppState>().analytics.refresh_stats();
+                    let _ = settings_handle.emit("stats-refreshed", ());
+                }
+            });
+
+            // Reopen the analytics window where it was left
+            if let Some(window) = app.get_webview_window(services::window_state::ANALYTICS_WINDOW) {
+                startup::time("window_state", false, || services::window_state::restore(&window));
+            }
+
+            // --hidden, --open and --project
+            let args: Vec<String> = std::env::args().skip(1).collect();
+            let cwd = std::env::current_dir().unwrap_or_default();
+            cli::apply_startup(&handle, &cli::parse(&args, &cwd));
+            if services::demo::is_active() {
+                if let Err(e) = services::demo::ensure() {
+                    eprintln!("Failed to generate demo data: {}", e);
+                }
+            }
 
             // Create system tray
-            create_tray(&handle)?;
+            startup::time("create_tray", false, || create_tray(&handle))?;
+
+            // Heavy scans run once the tray is visible
+            let warm_handle = app.handle().clone();
+            std::thread::spawn(move || {
+                startup::time("menu_cache", true, refresh_menu_cache);
+                if let Some(state) = warm_handle.try_state::<AppState>() {
+                    startup::time("analytics", true, || state.analytics.refresh_stats());
+                    startup::time("trend", true, || tray::refresh_trend_cache(&state.analytics));
+                    startup::time("active_sessions", true, || state.session_tracker.refresh_active());
+                }
+                let _ = tray::update_tray_menu(&warm_handle);
+                let _ = warm_handle.emit("refresh-stats", ());
+            });
+
+            // Push config file edits made outside the app to the frontend
+            if let Err(e) = startup::time("config_watcher", false, || services::watcher::start(app.handle())) {
+                eprintln!("Failed to start config watcher: {}", e);
+            }
 
             // Start hook server
             let state = app.state::<AppState>();
@@ -505,6 +1707,16 @@ pub fn run() {
                 match server.start(handle.clone()).await {
                     Ok(port) => {
                         println!("Hook server started on port {}", port);
+
+                        // Recover events the hook relay spooled while claudit was closed
+                        match services::activity::drain_spool() {
+                            Ok(0) => {}
+                            Ok(count) => {
+                                println!("Recovered {} spooled hook events", count);
+                                let _ = handle.emit("activity-added", ());
+                            }
+                            Err(e) => eprintln!("Failed to drain hook spool: {}", e),
+                        }
                     }
                     Err(e) => {
                         eprintln!("Failed to start hook server: {}", e);
@@ -515,19 +1727,73 @@ pub fn run() {
             // Set up auto-refresh timer for tray menu
             let app_handle = app.handle().clone();
             std::thread::spawn(move || {
+                let mut tick: u32 = 0;
                 loop {
+                    // Daily trend changes slowly - refresh it every 5 minutes
+                    // (the startup warm-up covers tick 0)
+                    if tick > 0 && tick.is_multiple_of(10) {
+                        if let Some(state) = app_handle.try_state::<AppState>() {
+                            tray::refresh_trend_cache(&state.analytics);
+                        }
+                    }
+                    tick = tick.wrapping_add(1);
+
                     std::thread::sleep(std::time::Duration::from_secs(30));
+                    // Refresh stats (if stale) and running sessions shown in the tray
+                    if let Some(state) = app_handle.try_state::<AppState>() {
+                        state.analytics.get_stats();
+                        state.session_tracker.refresh_active();
+                    }
                     // Update tray menu with fresh data
                     let _ = tray::update_tray_menu(&app_handle);
                     let _ = app_handle.emit("refresh-stats", ());
                 }
             });
 
+            // Watchdog for sessions that have been running for a long time
+            let watchdog_handle = app.handle().clone();
+            let tracker = state.session_tracker.clone();
+            std::thread::spawn(move || {
+                loop {
+                    std::thread::sleep(std::time::Duration::from_secs(60));
+                    services::tracker::check_long_running(&watchdog_handle, &tracker);
+                    services::api_errors::check_error_spike(&watchdog_handle);
+                    services::notifications::flush_digest(&watchdog_handle);
+                    services::config_sync::auto_commit(&watchdog_handle);
+                    services::data_gaps::check(&watchdog_handle);
+                    services::telemetry::send_if_due();
+                    if let Some(state) = watchdog_handle.try_state::<AppState>() {
+                        let server = state.hook_server.blocking_lock().address();
+                        if let Err(e) = services::hook_repair::check(&watchdog_handle, server) {
+                            eprintln!("Hook check failed: {}", e);
+                        }
+                    }
+                }
+            });
+
             Ok(())
         })
         .on_window_event(|window, event| {
             match event {
-                tauri::WindowEvent::CloseRequested { api, .. } => {
+                tauri::WindowEvent::Moved(_) | tauri::WindowEvent::Resized(_)
+                    if window.label() == services::window_state::ANALYTICS_WINDOW =>
+                {
+                    services::window_state::track(window);
+                }
+                tauri::WindowEvent::Focused(false)
+                    if window.label() == services::window_state::ANALYTICS_WINDOW =>
+                {
+                    let _ = services::window_state::save();
+                }
+                tauri::WindowEvent::Focused(false)
+                    if window.label() == services::popover::POPOVER_WINDOW =>
+                {
+                    let _ = window.hide();
+                }
+                tauri::WindowEvent::CloseRequested { api, .. }
+                    if window.label() == services::window_state::ANALYTICS_WINDOW =>
+                {
+                    let _ = services::window_state::save();
                     // Keep app running when window is closed (minimize to tray)
                     let _ = window.hide();
                     // Hide from dock on macOS
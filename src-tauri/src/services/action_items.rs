@@ -0,0 +1,169 @@
+//! Action items from sessions
+//!
+//! `extract` scans the last assistant messages of a session for things left
+//! to do: Markdown checkboxes, `TODO:` lines, requests like "you'll need to"
+//! and the bullets under a "Next steps" style heading. New items join the
+//! project's checklist in claudit's metadata, where they can be ticked off
+//! or removed.
+
+use super::metadata::{self, ActionItem};
+use super::sessions;
+use crate::types::MessageContentBlock;
+
+/// Assistant messages at the end of a session that are scanned
+const TAIL_MESSAGES: usize = 3;
+/// Longer statements are cut
+const MAX_ITEM_CHARS: usize = 300;
+
+/// Lines that introduce a list of things left to do
+const LIST_HEADINGS: &[&str] = &[
+    "next step",
+    "todo",
+    "to do",
+    "to-do",
+    "follow-up",
+    "follow up",
+    "remaining",
+    "still need",
+    "left to do",
+    "action item",
+    "you'll need to",
+    "you will need to",
+];
+
+/// Openings of a sentence that asks for something to be done
+const ACTION_PREFIXES: &[&str] = &[
+    "todo",
+    "fixme",
+    "follow-up:",
+    "next step:",
+    "you should ",
+    "you'll need to ",
+    "you will need to ",
+    "you may want to ",
+    "remember to ",
+    "don't forget to ",
+    "make sure to ",
+];
+
+/// Text of a bullet or numbered list item
+fn list_item(line: &str) -> Option<&str> {
+    if let Some(rest) = ["- ", "* ", "• "].iter().find_map(|b| line.strip_prefix(b)) {
+        return Some(rest);
+    }
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let rest = line[digits..]
+        .strip_prefix(". ")
+        .or_else(|| line[digits..].strip_prefix(") "))?;
+    (digits > 0).then_some(rest)
+}
+
+fn is_action(text: &str) -> bool {
+    let text = text.trim_start_matches('*').to_lowercase();
+    ACTION_PREFIXES
+        .iter()
+        .any(|prefix| text.starts_with(prefix))
+}
+
+fn clean(text: &str) -> String {
+    let text = text.trim().trim_matches('*').trim();
+    match text.char_indices().nth(MAX_ITEM_CHARS) {
+        Some((cut, _)) => format!("{}…", &text[..cut]),
+        None => text.to_string(),
+    }
+}
+
+/// TODO-like statements in a message
+fn candidates(text: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut in_list = false;
+    for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let checkbox = ["- [ ]", "* [ ]"].iter().find_map(|b| line.strip_prefix(b));
+        if let Some(rest) = checkbox {
+            items.push(clean(rest));
+            continue;
+        }
+        if ["- [x]", "* [x]"].iter().any(|b| line.starts_with(b)) {
+            continue;
+        }
+        match list_item(line) {
+            Some(item) if in_list || is_action(item) => items.push(clean(item)),
+            Some(_) => {}
+            None => {
+                let lower = line.to_lowercase();
+                in_list = LIST_HEADINGS.iter().any(|heading| lower.contains(heading));
+                if !in_list && is_action(line) {
+                    items.push(clean(line));
+                }
+            }
+        }
+    }
+    items.retain(|item| !item.is_empty());
+    items
+}
+
+/// Add the action items found at the end of a session to its project's
+/// checklist. Returns the items that weren't on it yet.
+pub fn extract(project_path: &str, session_id: &str) -> Result<Vec<ActionItem>, String> {
+    let conversation = sessions::get_session_conversation(project_path, session_id)?;
+    let texts: Vec<String> = conversation
+        .messages
+        .iter()
+        .rev()
+        .filter(|m| m.role == "assistant")
+        .filter_map(|m| {
+            let text: Vec<&str> = m
+                .content
+                .iter()
+                .filter_map(|block| match block {
+                    MessageContentBlock::Text { text } => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect();
+            (!text.is_empty()).then(|| text.join("\n"))
+        })
+        .take(TAIL_MESSAGES)
+        .collect();
+
+    let now = chrono::Utc::now().to_rfc3339();
+    metadata::update_project(project_path, |project| {
+        let mut added = Vec::new();
+        for text in texts.iter().rev().flat_map(|t| candidates(t)) {
+            let known = project
+                .action_items
+                .iter()
+                .chain(&added)
+                .any(|item: &ActionItem| item.text.eq_ignore_ascii_case(&text));
+            if !known {
+                added.push(ActionItem {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    session_id: session_id.to_string(),
+                    text,
+                    done: false,
+                    created_at: now.clone(),
+                });
+            }
+        }
+        project.action_items.extend(added.iter().cloned());
+        added
+    })
+}
+
+/// Tick an item off (or back on)
+pub fn set_done(project_path: &str, id: &str, done: bool) -> Result<(), String> {
+    metadata::update_project(project_path, |project| {
+        project
+            .action_items
+            .iter_mut()
+            .find(|item| item.id == id)
+            .map(|item| item.done = done)
+            .ok_or_else(|| format!("Action item {} not found", id))
+    })?
+}
+
+/// Drop an item from the checklist
+pub fn remove(project_path: &str, id: &str) -> Result<(), String> {
+    metadata::update_project(project_path, |project| {
+        project.action_items.retain(|item| item.id != id)
+    })
+}
@@ -0,0 +1,216 @@
+//! Activity history
+//!
+//! An append-only JSONL log of hook events (and events replayed from the
+//! claudit-hook offline spool) stored in claudit's config directory.
+
+use super::settings::app_config_dir;
+use crate::types::HookEvent;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Maximum number of entries kept in the activity log
+const MAX_ENTRIES: usize = 1000;
+
+/// The log is trimmed back to MAX_ENTRIES once the file grows past this size
+const TRIM_BYTES: u64 = 512 * 1024;
+
+/// Held while the log is written, so appends from the hook server, notifications
+/// and the spool drain don't interleave with a trim
+static LOG: Mutex<()> = Mutex::new(());
+
+/// A single entry in the activity history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityEntry {
+    pub id: String,
+    /// Hook event name (e.g. "Stop", "SubagentStop")
+    pub event: String,
+    pub tool: Option<String>,
+    pub session_id: Option<String>,
+    /// Working directory of the Claude session (the project path)
+    pub project: Option<String>,
+    pub timestamp: String,
+    /// True when the event was delivered from the offline spool after claudit started
+    #[serde(default)]
+    pub spooled: bool,
+    /// Notification content, for notifications that were queued instead of shown
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub body: Option<String>,
+}
+
+impl ActivityEntry {
+    pub fn from_hook_event(event: &HookEvent, spooled: bool) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            event: event.event.clone(),
+            tool: event.tool.clone(),
+            session_id: event.session_id.clone(),
+            project: event.cwd.clone(),
+            timestamp: event
+                .timestamp
+                .clone()
+                .unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
+            spooled,
+            title: None,
+            body: None,
+        }
+    }
+}
+
+fn activity_path() -> PathBuf {
+    app_config_dir().join("activity.jsonl")
+}
+
+/// Path of the spool file the claudit-hook relay appends to when the server is unreachable
+fn spool_path() -> PathBuf {
+    app_config_dir().join("hook-spool.jsonl")
+}
+
+fn read_entries(path: &Path) -> Vec<ActivityEntry> {
+    let file = match File::open(path) {
+        Ok(f) => f,
+        Err(_) => return Vec::new(),
+    };
+
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect()
+}
+
+/// Keep only the last MAX_ENTRIES entries. The trimmed log is written next to
+/// the old one and renamed over it, so a crash never leaves it half written.
+fn trim(path: &Path) -> Result<(), String> {
+    let all = read_entries(path);
+    if all.len() <= MAX_ENTRIES {
+        return Ok(());
+    }
+    let contents: String = all[all.len() - MAX_ENTRIES..]
+        .iter()
+        .filter_map(|e| serde_json::to_string(e).ok())
+        .map(|line| line + "\n")
+        .collect();
+    let temp = path.with_extension("jsonl.tmp");
+    fs::write(&temp, contents).map_err(|e| format!("Failed to trim activity log: {}", e))?;
+    fs::rename(&temp, path).map_err(|e| format!("Failed to trim activity log: {}", e))
+}
+
+/// Append entries to the activity log, trimming it once it grows past TRIM_BYTES.
+/// The caller holds the LOG lock.
+fn append_locked(entries: &[ActivityEntry]) -> Result<(), String> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let path = activity_path();
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open activity log: {}", e))?;
+
+    for entry in entries {
+        let line = serde_json::to_string(entry).map_err(|e| format!("Serialize error: {}", e))?;
+        writeln!(file, "{}", line).map_err(|e| format!("Failed to write activity log: {}", e))?;
+    }
+    let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+    drop(file);
+
+    if size > TRIM_BYTES {
+        trim(&path)?;
+    }
+    Ok(())
+}
+
+/// Record a single activity entry
+pub fn record(entry: &ActivityEntry) -> Result<(), String> {
+    let _log = LOG.lock().unwrap();
+    append_locked(std::slice::from_ref(entry))
+}
+
+/// Get the most recent activity entries (newest first)
+pub fn recent(limit: usize) -> Vec<ActivityEntry> {
+    let mut entries = read_entries(&activity_path());
+    entries.reverse();
+    entries.truncate(limit);
+    entries
+}
+
+/// Move events spooled by the hook relay while claudit was closed into the activity log.
+/// Returns the number of events recovered.
+pub fn drain_spool() -> Result<usize, String> {
+    let _log = LOG.lock().unwrap();
+    let path = spool_path();
+    if !path.exists() {
+        return Ok(0);
+    }
+
+    // Rename first so events spooled during draining aren't lost
+    let draining = path.with_extension("jsonl.draining");
+    fs::rename(&path, &draining).map_err(|e| format!("Failed to read hook spool: {}", e))?;
+
+    let file = File::open(&draining).map_err(|e| format!("Failed to read hook spool: {}", e))?;
+    let entries: Vec<ActivityEntry> = BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<HookEvent>(&line).ok())
+        .filter(|event| {
+            !matches!(event.event.as_str(), "PreToolUse" | "PostToolUse" | "UserPromptSubmit")
+        })
+        .map(|event| ActivityEntry::from_hook_event(&event, true))
+        .collect();
+
+    append_locked(&entries)?;
+    let _ = fs::remove_file(&draining);
+
+    Ok(entries.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(n: usize) -> ActivityEntry {
+        ActivityEntry {
+            id: n.to_string(),
+            event: "Stop".to_string(),
+            tool: None,
+            session_id: None,
+            project: None,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            spooled: false,
+            title: None,
+            body: None,
+        }
+    }
+
+    #[test]
+    fn test_trim() {
+        let path =
+            std::env::temp_dir().join(format!("claudit-activity-{}.jsonl", uuid::Uuid::new_v4()));
+        let lines: String = (0..MAX_ENTRIES + 5)
+            .map(|n| serde_json::to_string(&entry(n)).unwrap() + "\n")
+            .collect();
+        fs::write(&path, lines).unwrap();
+
+        trim(&path).unwrap();
+        let kept = read_entries(&path);
+        assert_eq!(kept.len(), MAX_ENTRIES);
+        assert_eq!(kept[0].id, "5");
+        assert_eq!(kept[MAX_ENTRIES - 1].id, (MAX_ENTRIES + 4).to_string());
+        assert!(!path.with_extension("jsonl.tmp").exists());
+
+        // A log within the limit is left alone
+        let before = fs::read_to_string(&path).unwrap();
+        trim(&path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), before);
+
+        let _ = fs::remove_file(&path);
+    }
+}
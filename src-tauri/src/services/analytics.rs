@@ -1,17 +1,23 @@
+use super::analytics_db::{self, ReindexProgress};
 use super::pricing;
 use crate::services::usage::UsageReader;
 use crate::types::{
-    AnalyticsStats, ChartData, DailyStats, HourlyStats, ModelChartData, ModelStats,
-    ProjectChartData, ProjectStats, UsageEntry,
+    AnalyticsStats, ApiErrorKind, CacheSimulation, ChartData, CompactionReport, DailyErrorStats,
+    DailyModelStats, DailyStats, DrillDimension, DrillDownFilters, DrillDownResult, DrillSort,
+    ErrorStats, HourlyStats, LatencyGroup, LatencyStats, LatencySummary, ModelCacheSimulation,
+    ModelChartData, ModelErrorCount, ModelStats, ProjectChartData, ProjectCompaction,
+    ProjectCostHistory, ProjectDailyCost, ProjectStats, UsageEntry,
 };
 use chrono::{DateTime, Datelike, Duration, Timelike, Utc};
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
 /// Calculate cost for a single usage entry
-fn calculate_entry_cost(entry: &UsageEntry) -> f64 {
+pub fn calculate_entry_cost(entry: &UsageEntry) -> f64 {
     pricing::calculate_cost(
         &entry.model,
+        Some(entry.timestamp),
+        entry.service_tier.as_deref(),
         entry.input_tokens,
         entry.output_tokens,
         entry.cache_creation_tokens,
@@ -19,8 +25,39 @@ fn calculate_entry_cost(entry: &UsageEntry) -> f64 {
     )
 }
 
+/// Summarize timing samples (None if there are none)
+fn summarize_latency(mut samples: Vec<u64>) -> Option<LatencySummary> {
+    if samples.is_empty() {
+        return None;
+    }
+    samples.sort_unstable();
+
+    // Nearest-rank percentile
+    let percentile = |p: f64| {
+        let rank = ((p / 100.0) * samples.len() as f64).ceil() as usize;
+        samples[rank.clamp(1, samples.len()) - 1]
+    };
+
+    Some(LatencySummary {
+        samples: samples.len() as u64,
+        avg_ms: samples.iter().sum::<u64>() as f64 / samples.len() as f64,
+        p50_ms: percentile(50.0),
+        p90_ms: percentile(90.0),
+        p99_ms: percentile(99.0),
+    })
+}
+
+/// Build a latency group from the entries that belong to it
+fn latency_group(key: String, entries: &[&UsageEntry]) -> LatencyGroup {
+    LatencyGroup {
+        key,
+        ttft: summarize_latency(entries.iter().filter_map(|e| e.ttft_ms).collect()),
+        duration: summarize_latency(entries.iter().filter_map(|e| e.duration_ms).collect()),
+    }
+}
+
 /// Get the session block key (5-hour blocks)
-fn get_session_block_key(timestamp: &DateTime<Utc>) -> String {
+pub fn get_session_block_key(timestamp: &DateTime<Utc>) -> String {
     let block = timestamp.hour() / 5;
     format!(
         "{}-{:02}-{:02}-{}",
@@ -31,6 +68,28 @@ fn get_session_block_key(timestamp: &DateTime<Utc>) -> String {
     )
 }
 
+/// Add an entry to a per-model breakdown
+fn add_model_usage(by_model: &mut HashMap<String, ModelStats>, entry: &UsageEntry, cost: f64) {
+    let model_stats = by_model.entry(entry.model.clone()).or_default();
+    model_stats.input_tokens += entry.input_tokens;
+    model_stats.output_tokens += entry.output_tokens;
+    model_stats.cache_creation_tokens += entry.cache_creation_tokens;
+    model_stats.cache_read_tokens += entry.cache_read_tokens;
+    model_stats.cost += cost;
+    model_stats.message_count += 1;
+}
+
+/// How `simulate_cache` decides that a cache write re-cached context, returned with the result
+const CACHE_REWRITE_ASSUMPTION: &str = "A request's prompt is its cache read followed by its \
+    cache write. A cache write only counts as re-cached context where it falls within the prefix \
+    that the previous request of the same session and model had cached (its cache read plus \
+    cache write); writes past that prefix are new context.";
+
+/// Tokens of a cache write that fall within the prefix the previous request cached
+fn rewritten_cache_tokens(previous_prefix: u64, cache_read: u64, cache_creation: u64) -> u64 {
+    cache_creation.min(previous_prefix.saturating_sub(cache_read))
+}
+
 /// Analytics service for calculating usage statistics
 pub struct AnalyticsService {
     usage_reader: UsageReader,
@@ -124,16 +183,10 @@ impl AnalyticsService {
             }
 
             // Per-model breakdown
-            let model_stats = stats
-                .by_model
-                .entry(entry.model.clone())
-                .or_insert_with(ModelStats::default);
-            model_stats.input_tokens += entry.input_tokens;
-            model_stats.output_tokens += entry.output_tokens;
-            model_stats.cache_creation_tokens += entry.cache_creation_tokens;
-            model_stats.cache_read_tokens += entry.cache_read_tokens;
-            model_stats.cost += cost;
-            model_stats.message_count += 1;
+            add_model_usage(&mut stats.by_model, entry, cost);
+            if is_today {
+                add_model_usage(&mut stats.today_by_model, entry, cost);
+            }
 
             // Per-project breakdown
             let project_stats = stats
@@ -147,6 +200,16 @@ impl AnalyticsService {
             project_stats.output_tokens += entry.output_tokens;
             project_stats.cost += cost;
             project_stats.message_count += 1;
+
+            // Per-service-tier breakdown
+            let tier = entry.service_tier.clone().unwrap_or_else(|| "standard".to_string());
+            let tier_stats = stats.by_service_tier.entry(tier).or_default();
+            tier_stats.requests += 1;
+            tier_stats.input_tokens += entry.input_tokens;
+            tier_stats.output_tokens += entry.output_tokens;
+            tier_stats.cost += cost;
+            tier_stats.standard_cost +=
+                cost / pricing::service_tier_multiplier(entry.service_tier.as_deref());
         }
 
         // Calculate session counts
@@ -177,10 +240,22 @@ impl AnalyticsService {
         self.refresh_stats()
     }
 
-    /// Force refresh stats
+    /// Last computed stats without touching disk (defaults before the first refresh)
+    pub fn cached_stats(&self) -> AnalyticsStats {
+        self.cached_stats
+            .read()
+            .ok()
+            .and_then(|guard| guard.clone())
+            .unwrap_or_default()
+    }
+
+    /// Force refresh stats (from the analytics cache, or the JSONL files if
+    /// the cache can't be used)
     pub fn refresh_stats(&self) -> AnalyticsStats {
-        let entries = self.usage_reader.read_all_entries();
-        let stats = self.calculate_stats(&entries);
+        let stats = analytics_db::stats(&self.usage_reader).unwrap_or_else(|e| {
+            eprintln!("Analytics cache unavailable, reading session files: {}", e);
+            self.calculate_stats(&self.usage_reader.read_all_entries())
+        });
 
         // Update cache, ignoring poisoned lock errors
         if let Ok(mut guard) = self.cached_stats.write() {
@@ -193,12 +268,55 @@ impl AnalyticsService {
         stats
     }
 
+    /// Sync the analytics cache with the session files (from scratch if
+    /// `full`), reporting progress per file, then refresh stats
+    pub fn reindex(
+        &self,
+        full: bool,
+        progress: &mut dyn FnMut(&ReindexProgress),
+    ) -> Result<ReindexProgress, String> {
+        let report = analytics_db::reindex(&self.usage_reader, full, progress)?;
+        self.refresh_stats();
+        Ok(report)
+    }
+
+    /// Usage grouped by `dimensions` for pivot tables (needs the analytics cache)
+    pub fn drill_down(
+        &self,
+        dimensions: &[DrillDimension],
+        filters: &DrillDownFilters,
+        sort: &DrillSort,
+        limit: Option<u32>,
+    ) -> Result<DrillDownResult, String> {
+        analytics_db::drill_down(&self.usage_reader, dimensions, filters, sort, limit)
+    }
+
+    /// Per-model usage over the last `days` days
+    pub fn get_model_stats(&self, days: u32) -> HashMap<String, ModelStats> {
+        let since = Utc::now() - Duration::days(days as i64);
+        analytics_db::model_stats(&self.usage_reader, since).unwrap_or_else(|e| {
+            eprintln!("Analytics cache unavailable, reading session files: {}", e);
+            let mut by_model = HashMap::new();
+            for entry in self.usage_reader.read_entries(Some(days)) {
+                if entry.timestamp >= since {
+                    add_model_usage(&mut by_model, &entry, calculate_entry_cost(&entry));
+                }
+            }
+            by_model
+        })
+    }
+
     /// Get chart data for the analytics dashboard
     pub fn get_chart_data(&self, days: u32) -> ChartData {
+        match analytics_db::chart_data(&self.usage_reader, days) {
+            Ok(data) => return data,
+            Err(e) => eprintln!("Analytics cache unavailable, reading session files: {}", e),
+        }
         let entries = self.usage_reader.read_entries(Some(days));
 
         // Daily stats
         let mut daily_map: HashMap<String, DailyStats> = HashMap::new();
+        let mut daily_model_map: HashMap<(String, String), DailyModelStats> = HashMap::new();
         for entry in &entries {
             let date_key = entry.timestamp.format("%Y-%m-%d").to_string();
             let cost = calculate_entry_cost(entry);
@@ -209,12 +327,24 @@ impl AnalyticsService {
                 output_tokens: 0,
                 cost: 0.0,
                 messages: 0,
+                by_model: Vec::new(),
             });
 
             daily.input_tokens += entry.input_tokens;
             daily.output_tokens += entry.output_tokens;
             daily.cost += cost;
             daily.messages += 1;
+
+            let daily_model = daily_model_map
+                .entry((daily.date.clone(), entry.model.clone()))
+                .or_insert_with(|| DailyModelStats {
+                    model: entry.model.clone(),
+                    ..Default::default()
+                });
+            daily_model.input_tokens += entry.input_tokens;
+            daily_model.output_tokens += entry.output_tokens;
+            daily_model.cost += cost;
+            daily_model.messages += 1;
         }
 
         let mut daily: Vec<DailyStats> = daily_map.into_values().collect();
@@ -251,6 +381,21 @@ impl AnalyticsService {
             .collect();
         by_model.sort_by(|a, b| b.tokens.cmp(&a.tokens));
 
+        // Per-model daily split, in the same model order every day
+        for day in &mut daily {
+            day.by_model = by_model
+                .iter()
+                .map(|model| {
+                    daily_model_map
+                        .remove(&(day.date.clone(), model.name.clone()))
+                        .unwrap_or_else(|| DailyModelStats {
+                            model: model.name.clone(),
+                            ..Default::default()
+                        })
+                })
+                .collect();
+        }
+
         // By project
         let mut project_map: HashMap<String, (u64, f64)> = HashMap::new();
         for entry in &entries {
@@ -271,6 +416,306 @@ impl AnalyticsService {
             hourly,
             by_model,
             by_project,
+            bucket: Default::default(),
+        }
+    }
+
+    /// Daily spend of one project over the last `days` days, compared with the
+    /// `days` before
+    pub fn get_project_cost_history(&self, project: &str, days: u32) -> ProjectCostHistory {
+        let days = days.max(1);
+        let entries = self.usage_reader.read_entries(Some(days * 2));
+        let today = Utc::now().date_naive();
+        let start = today - Duration::days(days as i64 - 1);
+        let previous_start = start - Duration::days(days as i64);
+
+        let mut daily: Vec<ProjectDailyCost> = (0..days as i64)
+            .map(|offset| ProjectDailyCost {
+                date: (start + Duration::days(offset)).format("%Y-%m-%d").to_string(),
+                ..Default::default()
+            })
+            .collect();
+        let mut previous_cost = 0.0;
+        let mut all_cost = 0.0;
+
+        for entry in &entries {
+            let date = entry.timestamp.date_naive();
+            let cost = calculate_entry_cost(entry);
+            if date >= start {
+                all_cost += cost;
+            }
+            if entry.project != project {
+                continue;
+            }
+            if date >= start {
+                if let Some(day) = daily.get_mut((date - start).num_days() as usize) {
+                    day.cost += cost;
+                    day.messages += 1;
+                }
+            } else if date >= previous_start {
+                previous_cost += cost;
+            }
+        }
+
+        let total_cost: f64 = daily.iter().map(|d| d.cost).sum();
+        ProjectCostHistory {
+            project: project.to_string(),
+            days,
+            daily,
+            total_cost,
+            previous_cost,
+            trend: (previous_cost > 0.0).then(|| total_cost / previous_cost),
+            share_of_spend: if all_cost > 0.0 {
+                total_cost / all_cost * 100.0
+            } else {
+                0.0
+            },
+        }
+    }
+
+    /// Estimate how much prompt caching saved over the last `days` days, and how much more
+    /// would be saved if `locality_improvement` (0-1) of re-cached context were cache reads.
+    ///
+    /// See `CACHE_REWRITE_ASSUMPTION` for which cache writes count as re-cached context.
+    pub fn simulate_cache(&self, days: u32, locality_improvement: f64) -> CacheSimulation {
+        let entries = self.usage_reader.read_entries(Some(days));
+        let improvement = locality_improvement.clamp(0.0, 1.0);
+        let per_million = 1_000_000.0;
+
+        let mut simulation = CacheSimulation {
+            days,
+            locality_improvement: improvement,
+            assumption: CACHE_REWRITE_ASSUMPTION.to_string(),
+            ..Default::default()
+        };
+        let mut by_model: HashMap<String, ModelCacheSimulation> = HashMap::new();
+        // Prefix cached by the previous request, per session and model (caches are per model)
+        let mut cached_prefix: HashMap<(String, String), u64> = HashMap::new();
+
+        // Entries are sorted oldest first
+        for entry in &entries {
+            let costs = pricing::get_model_costs(&entry.model);
+            let actual = calculate_entry_cost(entry);
+            let all_input = entry.input_tokens + entry.cache_creation_tokens + entry.cache_read_tokens;
+            let without_cache = (all_input as f64 / per_million) * costs.input
+                + (entry.output_tokens as f64 / per_million) * costs.output;
+
+            let previous = cached_prefix.insert(
+                (entry.session_id.clone(), entry.model.clone()),
+                entry.cache_read_tokens + entry.cache_creation_tokens,
+            );
+            let rewritten = rewritten_cache_tokens(
+                previous.unwrap_or(0),
+                entry.cache_read_tokens,
+                entry.cache_creation_tokens,
+            );
+            let potential = (rewritten as f64 * improvement / per_million)
+                * (costs.cache_write - costs.cache_read);
+
+            simulation.uncached_input_tokens += entry.input_tokens;
+            simulation.cache_creation_tokens += entry.cache_creation_tokens;
+            simulation.cache_read_tokens += entry.cache_read_tokens;
+            simulation.rewritten_cache_tokens += rewritten;
+            simulation.actual_cost += actual;
+            simulation.cost_without_cache += without_cache;
+            simulation.potential_additional_savings += potential;
+
+            let model = by_model
+                .entry(entry.model.clone())
+                .or_insert_with(|| ModelCacheSimulation {
+                    model: entry.model.clone(),
+                    ..Default::default()
+                });
+            model.actual_cost += actual;
+            model.cost_without_cache += without_cache;
+            model.potential_additional_savings += potential;
+        }
+
+        simulation.caching_savings = simulation.cost_without_cache - simulation.actual_cost;
+
+        let total_input = simulation.uncached_input_tokens
+            + simulation.cache_creation_tokens
+            + simulation.cache_read_tokens;
+        if total_input > 0 {
+            simulation.cache_hit_rate =
+                (simulation.cache_read_tokens as f64 / total_input as f64) * 100.0;
+        }
+
+        let mut by_model: Vec<ModelCacheSimulation> = by_model
+            .into_values()
+            .map(|mut model| {
+                model.caching_savings = model.cost_without_cache - model.actual_cost;
+                model
+            })
+            .collect();
+        by_model.sort_by(|a, b| b.actual_cost.total_cmp(&a.actual_cost));
+        simulation.by_model = by_model;
+
+        simulation
+    }
+
+    /// Daily API error counts by kind and model, with successful requests for the error rate
+    pub fn get_error_stats(&self, days: u32) -> ErrorStats {
+        let errors = self.usage_reader.read_api_errors(Some(days));
+        let entries = self.usage_reader.read_entries(Some(days));
+
+        let mut daily_map: HashMap<String, DailyErrorStats> = HashMap::new();
+        let mut daily_models: HashMap<(String, String), u64> = HashMap::new();
+
+        // Error messages are logged with a placeholder model, they aren't requests
+        for entry in entries.iter().filter(|e| !e.model.starts_with('<')) {
+            let date = entry.timestamp.format("%Y-%m-%d").to_string();
+            daily_map
+                .entry(date.clone())
+                .or_insert_with(|| DailyErrorStats { date, ..Default::default() })
+                .requests += 1;
+        }
+
+        for error in &errors {
+            let date = error.timestamp.format("%Y-%m-%d").to_string();
+            let daily = daily_map
+                .entry(date.clone())
+                .or_insert_with(|| DailyErrorStats { date: date.clone(), ..Default::default() });
+            daily.errors += 1;
+            match error.kind {
+                ApiErrorKind::Overloaded => daily.overloaded += 1,
+                ApiErrorKind::RateLimited => daily.rate_limited += 1,
+                ApiErrorKind::ServerError => daily.server_error += 1,
+                ApiErrorKind::Other => daily.other += 1,
+            }
+            *daily_models.entry((date, error.model.clone())).or_insert(0) += 1;
+        }
+
+        for ((date, model), count) in daily_models {
+            if let Some(daily) = daily_map.get_mut(&date) {
+                daily.by_model.push(ModelErrorCount { model, errors: count });
+            }
+        }
+
+        let mut daily: Vec<DailyErrorStats> = daily_map.into_values().collect();
+        daily.sort_by(|a, b| a.date.cmp(&b.date));
+        for day in &mut daily {
+            day.by_model.sort_by_key(|m| std::cmp::Reverse(m.errors));
+        }
+
+        let requests: u64 = daily.iter().map(|d| d.requests).sum();
+        let error_count = errors.len() as u64;
+        let attempts = requests + error_count;
+
+        ErrorStats {
+            days,
+            requests,
+            errors: error_count,
+            error_rate: if attempts > 0 {
+                error_count as f64 / attempts as f64 * 100.0
+            } else {
+                0.0
+            },
+            daily,
+        }
+    }
+
+    /// How often sessions compact their context, by trigger and project
+    pub fn get_compaction_report(&self, days: u32) -> CompactionReport {
+        let events = self.usage_reader.read_compactions(Some(days));
+
+        let mut report = CompactionReport {
+            days,
+            compactions: events.len() as u64,
+            ..Default::default()
+        };
+
+        let mut sessions: std::collections::HashSet<&str> = std::collections::HashSet::new();
+        let mut project_map: HashMap<&str, (u64, std::collections::HashSet<&str>, u64, u64)> =
+            HashMap::new();
+        let (mut before_sum, mut after_sum, mut measured) = (0u64, 0u64, 0u64);
+
+        for event in &events {
+            match event.trigger.as_str() {
+                "auto" => report.auto_compactions += 1,
+                "manual" => report.manual_compactions += 1,
+                _ => {}
+            }
+            sessions.insert(&event.session_id);
+
+            let project = project_map.entry(&event.project).or_default();
+            project.0 += 1;
+            project.1.insert(&event.session_id);
+            if let Some(before) = event.tokens_before {
+                project.2 += before;
+                project.3 += 1;
+            }
+
+            if let (Some(before), Some(after)) = (event.tokens_before, event.tokens_after) {
+                before_sum += before;
+                after_sum += after;
+                measured += 1;
+                report.tokens_reclaimed += before.saturating_sub(after);
+            }
+        }
+
+        report.sessions_compacted = sessions.len() as u64;
+        if measured > 0 {
+            report.avg_tokens_before = before_sum as f64 / measured as f64;
+            report.avg_tokens_after = after_sum as f64 / measured as f64;
+        }
+
+        let mut by_project: Vec<ProjectCompaction> = project_map
+            .into_iter()
+            .map(|(project, (compactions, sessions, before_sum, before_count))| ProjectCompaction {
+                project: project.to_string(),
+                compactions,
+                sessions: sessions.len() as u64,
+                avg_tokens_before: if before_count > 0 {
+                    before_sum as f64 / before_count as f64
+                } else {
+                    0.0
+                },
+            })
+            .collect();
+        by_project.sort_by_key(|p| std::cmp::Reverse(p.compactions));
+        report.by_project = by_project;
+
+        report.recent = events.iter().rev().take(50).cloned().collect();
+        report
+    }
+
+    /// Time to first token and response duration per model and per day, from the
+    /// entries that carry timing data (older Claude Code versions don't log it)
+    pub fn get_latency_stats(&self, days: u32) -> LatencyStats {
+        let entries = self.usage_reader.read_entries(Some(days));
+        let timed: Vec<&UsageEntry> = entries
+            .iter()
+            .filter(|e| e.ttft_ms.is_some() || e.duration_ms.is_some())
+            .collect();
+
+        let mut model_map: HashMap<String, Vec<&UsageEntry>> = HashMap::new();
+        let mut daily_map: HashMap<String, Vec<&UsageEntry>> = HashMap::new();
+        for entry in &timed {
+            model_map.entry(entry.model.clone()).or_default().push(entry);
+            daily_map
+                .entry(entry.timestamp.format("%Y-%m-%d").to_string())
+                .or_default()
+                .push(entry);
+        }
+
+        let mut by_model: Vec<LatencyGroup> = model_map
+            .into_iter()
+            .map(|(model, entries)| latency_group(model, &entries))
+            .collect();
+        by_model.sort_by(|a, b| a.key.cmp(&b.key));
+
+        let mut daily: Vec<LatencyGroup> = daily_map
+            .into_iter()
+            .map(|(date, entries)| latency_group(date, &entries))
+            .collect();
+        daily.sort_by(|a, b| a.key.cmp(&b.key));
+
+        LatencyStats {
+            days,
+            overall: latency_group("all".to_string(), &timed),
+            by_model,
+            daily,
         }
     }
 }
@@ -280,3 +725,20 @@ impl Default for AnalyticsService {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewritten_cache_tokens() {
+        // The first request of a session has nothing cached before it
+        assert_eq!(rewritten_cache_tokens(0, 0, 5_000), 0);
+        // The conversation grew: the whole previous prefix was read, the write is new context
+        assert_eq!(rewritten_cache_tokens(5_000, 5_000, 800), 0);
+        // The prefix changed after 1,000 tokens, so 4,000 cached tokens were written again
+        assert_eq!(rewritten_cache_tokens(5_000, 1_000, 4_800), 4_000);
+        // Nothing was read: the previous prefix was re-cached in full
+        assert_eq!(rewritten_cache_tokens(5_000, 0, 3_000), 3_000);
+    }
+}
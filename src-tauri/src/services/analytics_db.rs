@@ -0,0 +1,1077 @@
+//! SQLite analytics cache
+//!
+//! Parsed usage entries live in `analytics.db` in claudit's config directory,
+//! together with how far each session file has been read. `sync` only parses
+//! lines appended since the previous call, so a restart doesn't re-read months
+//! of history, and `AnalyticsService` answers `get_stats` and `get_chart_data`
+//! with SQL aggregations. Costs are stored per entry, so the cache starts over
+//! when claudit's version (and with it the price list) or the path mappings
+//! change; `rebuild` does the same on request, e.g. after corruption.
+//! `reindex` syncs (after dropping the cache, if asked to) and reports its
+//! progress file by file. `drill_down` is a generic grouped query over the
+//! cache (project, model, day, session, tool...) for pivot tables.
+//!
+//! The database also holds the goal history (`goal_history`), which is
+//! recorded rather than cached: schema changes and `rebuild` keep it.
+
+use super::analytics::get_session_block_key;
+use super::duplicates;
+use super::goals::GoalRecord;
+use super::path_mapping;
+use super::pricing;
+use super::settings::app_config_dir;
+use super::usage::UsageReader;
+use crate::types::{
+    AnalyticsStats, ChartData, DailyModelStats, DailyStats, DrillDimension, DrillDownFilters,
+    DrillDownResult, DrillDownRow, DrillMeasure, DrillSort, HourlyStats, ModelChartData,
+    ModelStats, ProjectChartData, ProjectStats, UsageEntry,
+};
+use chrono::{DateTime, Duration, Timelike, Utc};
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, Transaction};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+/// Bump when the tables change; older databases are recreated
+const SCHEMA_VERSION: &str = "2";
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS meta (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS files (
+    id INTEGER PRIMARY KEY,
+    path TEXT NOT NULL UNIQUE,
+    offset INTEGER NOT NULL DEFAULT 0,
+    modified INTEGER,
+    project TEXT NOT NULL,
+    ignored INTEGER NOT NULL DEFAULT 0,
+    merged_into TEXT
+);
+CREATE TABLE IF NOT EXISTS entries (
+    id INTEGER PRIMARY KEY,
+    file_id INTEGER NOT NULL,
+    ts INTEGER NOT NULL,
+    day TEXT NOT NULL,
+    hour INTEGER NOT NULL,
+    block TEXT NOT NULL,
+    session_id TEXT NOT NULL,
+    model TEXT NOT NULL,
+    input_tokens INTEGER NOT NULL,
+    output_tokens INTEGER NOT NULL,
+    cache_creation_tokens INTEGER NOT NULL,
+    cache_read_tokens INTEGER NOT NULL,
+    cost REAL NOT NULL,
+    service_tier TEXT,
+    uuid TEXT NOT NULL,
+    message_id TEXT,
+    cwd TEXT,
+    ttft_ms INTEGER,
+    duration_ms INTEGER,
+    kept INTEGER NOT NULL DEFAULT 1
+);
+CREATE TABLE IF NOT EXISTS entry_tools (
+    entry_id INTEGER NOT NULL,
+    tool TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS entries_file ON entries(file_id);
+CREATE INDEX IF NOT EXISTS entry_tools_entry ON entry_tools(entry_id);
+CREATE INDEX IF NOT EXISTS entries_ts ON entries(ts);
+CREATE INDEX IF NOT EXISTS entries_uuid ON entries(uuid);
+CREATE INDEX IF NOT EXISTS entries_message ON entries(message_id);
+CREATE TABLE IF NOT EXISTS goal_history (
+    kind TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    target REAL NOT NULL,
+    actual REAL NOT NULL,
+    met INTEGER NOT NULL,
+    PRIMARY KEY (kind, period_start)
+);
+CREATE VIEW IF NOT EXISTS usage AS
+    SELECT e.*, f.project, COALESCE(f.merged_into, e.session_id) AS session
+    FROM entries e JOIN files f ON f.id = e.file_id
+    WHERE e.kept = 1;
+";
+
+/// Tables of the uuids and message ids whose entries a sync added, removed or
+/// moved between files
+const DIRTY_TABLES: &str = "
+CREATE TEMP TABLE IF NOT EXISTS dirty_uuids (uuid TEXT PRIMARY KEY);
+CREATE TEMP TABLE IF NOT EXISTS dirty_messages (message_id TEXT PRIMARY KEY);
+DELETE FROM dirty_uuids;
+DELETE FROM dirty_messages;
+";
+
+/// Mark the entries that count: the first copy of each uuid (files that aren't
+/// merged into another session first), without ignored duplicate files, and
+/// without responses of merged files that the primary session already has.
+/// Only the uuids in `dirty_uuids` are recomputed, after pulling in those that
+/// share a dirty message id
+const UPDATE_KEPT: &str = "
+INSERT OR IGNORE INTO dirty_uuids
+    SELECT uuid FROM entries WHERE message_id IN (SELECT message_id FROM dirty_messages);
+UPDATE entries SET kept = 0 WHERE uuid IN (SELECT uuid FROM dirty_uuids);
+UPDATE entries SET kept = 1 WHERE id IN (
+    SELECT id FROM (
+        SELECT e.id, ROW_NUMBER() OVER (
+            PARTITION BY CASE WHEN e.uuid = '' THEN 'row:' || e.id ELSE e.uuid END
+            ORDER BY f.merged_into IS NOT NULL, e.id
+        ) AS n
+        FROM entries e JOIN files f ON f.id = e.file_id
+        WHERE f.ignored = 0 AND e.uuid IN (SELECT uuid FROM dirty_uuids)
+    ) WHERE n = 1
+);
+UPDATE entries SET kept = 0
+WHERE kept = 1
+    AND uuid IN (SELECT uuid FROM dirty_uuids)
+    AND message_id IS NOT NULL
+    AND file_id IN (SELECT id FROM files WHERE merged_into IS NOT NULL)
+    AND message_id IN (
+        SELECT e.message_id FROM entries e JOIN files f ON f.id = e.file_id
+        WHERE f.merged_into IS NULL AND f.ignored = 0 AND e.message_id IS NOT NULL
+    );
+DELETE FROM dirty_uuids;
+DELETE FROM dirty_messages;
+";
+
+/// Drop the cached tables, keeping `goal_history`
+const DROP_CACHE: &str = "
+DROP VIEW IF EXISTS usage;
+DROP TABLE IF EXISTS entry_tools;
+DROP TABLE IF EXISTS entries;
+DROP TABLE IF EXISTS files;
+DROP TABLE IF EXISTS meta;
+";
+
+static DB: Mutex<Option<Connection>> = Mutex::new(None);
+
+pub fn db_path() -> PathBuf {
+    app_config_dir().join("analytics.db")
+}
+
+fn try_open() -> rusqlite::Result<Connection> {
+    let conn = Connection::open(db_path())?;
+    let version: Option<String> = conn
+        .query_row(
+            "SELECT value FROM meta WHERE key = 'schema_version'",
+            [],
+            |row| row.get(0),
+        )
+        .optional()
+        .unwrap_or(None);
+    if version.as_deref() != Some(SCHEMA_VERSION) {
+        conn.execute_batch(DROP_CACHE)?;
+    }
+    conn.execute_batch(SCHEMA)?;
+    conn.execute(
+        "INSERT OR REPLACE INTO meta (key, value) VALUES ('schema_version', ?1)",
+        [SCHEMA_VERSION],
+    )?;
+    Ok(conn)
+}
+
+/// Open the database, starting over with an empty one if it is corrupt
+fn open() -> Result<Connection, String> {
+    try_open().or_else(|e| {
+        eprintln!("Analytics cache unreadable, recreating it: {}", e);
+        let _ = std::fs::remove_file(db_path());
+        try_open().map_err(|e| format!("Failed to open the analytics cache: {}", e))
+    })
+}
+
+/// Run `f` on the shared connection (reopened if the file was deleted)
+fn with_db<T>(f: impl FnOnce(&mut Connection) -> rusqlite::Result<T>) -> Result<T, String> {
+    let mut guard = DB.lock().unwrap();
+    let mut conn = match guard.take() {
+        Some(conn) if db_path().exists() => conn,
+        _ => open()?,
+    };
+    let result = f(&mut conn).map_err(|e| format!("Analytics cache error: {}", e));
+    *guard = Some(conn);
+    result
+}
+
+fn meta(tx: &Transaction, key: &str) -> rusqlite::Result<Option<String>> {
+    tx.query_row("SELECT value FROM meta WHERE key = ?1", [key], |row| {
+        row.get(0)
+    })
+    .optional()
+}
+
+fn set_meta(tx: &Transaction, key: &str, value: &str) -> rusqlite::Result<()> {
+    tx.execute(
+        "INSERT OR REPLACE INTO meta (key, value) VALUES (?1, ?2)",
+        params![key, value],
+    )
+    .map(|_| ())
+}
+
+fn insert_entry(tx: &Transaction, file_id: i64, entry: &UsageEntry) -> rusqlite::Result<()> {
+    let cost = pricing::calculate_cost(
+        &entry.model,
+        Some(entry.timestamp),
+        entry.service_tier.as_deref(),
+        entry.input_tokens,
+        entry.output_tokens,
+        entry.cache_creation_tokens,
+        entry.cache_read_tokens,
+    );
+    tx.prepare_cached(
+        "INSERT INTO entries (file_id, ts, day, hour, block, session_id, model, input_tokens,
+            output_tokens, cache_creation_tokens, cache_read_tokens, cost, service_tier, uuid,
+            message_id, cwd, ttft_ms, duration_ms)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
+    )?
+    .execute(params![
+        file_id,
+        entry.timestamp.timestamp_millis(),
+        entry.timestamp.format("%Y-%m-%d").to_string(),
+        entry.timestamp.hour(),
+        get_session_block_key(&entry.timestamp),
+        entry.session_id,
+        entry.model,
+        entry.input_tokens as i64,
+        entry.output_tokens as i64,
+        entry.cache_creation_tokens as i64,
+        entry.cache_read_tokens as i64,
+        cost,
+        entry.service_tier,
+        entry.uuid,
+        entry.message_id,
+        entry.cwd,
+        entry.ttft_ms.map(|ms| ms as i64),
+        entry.duration_ms.map(|ms| ms as i64),
+    ])?;
+    let entry_id = tx.last_insert_rowid();
+    for tool in &entry.tools {
+        tx.prepare_cached("INSERT INTO entry_tools (entry_id, tool) VALUES (?1, ?2)")?
+            .execute(params![entry_id, tool])?;
+    }
+    tx.prepare_cached("INSERT OR IGNORE INTO dirty_uuids (uuid) VALUES (?1)")?
+        .execute([&entry.uuid])?;
+    if let Some(message_id) = &entry.message_id {
+        tx.prepare_cached("INSERT OR IGNORE INTO dirty_messages (message_id) VALUES (?1)")?
+            .execute([message_id])?;
+    }
+    Ok(())
+}
+
+/// Queue the uuids and message ids of a file's entries for `UPDATE_KEPT`
+fn mark_dirty(tx: &Transaction, file_id: i64) -> rusqlite::Result<()> {
+    tx.execute(
+        "INSERT OR IGNORE INTO dirty_uuids SELECT uuid FROM entries WHERE file_id = ?1",
+        [file_id],
+    )?;
+    tx.execute(
+        "INSERT OR IGNORE INTO dirty_messages
+            SELECT message_id FROM entries WHERE file_id = ?1 AND message_id IS NOT NULL",
+        [file_id],
+    )?;
+    Ok(())
+}
+
+/// Remove what was stored of a session file
+fn delete_file_entries(tx: &Transaction, file_id: i64) -> rusqlite::Result<()> {
+    mark_dirty(tx, file_id)?;
+    tx.execute(
+        "DELETE FROM entry_tools WHERE entry_id IN (SELECT id FROM entries WHERE file_id = ?1)",
+        [file_id],
+    )?;
+    tx.execute("DELETE FROM entries WHERE file_id = ?1", [file_id])?;
+    Ok(())
+}
+
+/// How far `reindex` has got
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReindexProgress {
+    pub files_total: usize,
+    pub files_processed: usize,
+    pub entries_ingested: u64,
+    /// Session files that couldn't be read, with the reason
+    pub errors: Vec<String>,
+    pub done: bool,
+}
+
+/// A session file as last stored
+struct StoredFile {
+    id: i64,
+    offset: u64,
+    modified: Option<i64>,
+    project: String,
+    ignored: bool,
+    merged_into: Option<String>,
+}
+
+/// Bring the cache up to date with the session files, calling `progress`
+/// after each file
+fn sync_in(
+    tx: &Transaction,
+    reader: &UsageReader,
+    progress: &mut dyn FnMut(&ReindexProgress),
+) -> rusqlite::Result<ReindexProgress> {
+    // Stored costs and cwds depend on the price list and the path mappings
+    let version = env!("CARGO_PKG_VERSION");
+    let mappings = serde_json::to_string(&path_mapping::current()).unwrap_or_default();
+    if meta(tx, "app_version")?.as_deref() != Some(version)
+        || meta(tx, "path_mappings")?.as_deref() != Some(mappings.as_str())
+    {
+        tx.execute_batch("DELETE FROM entry_tools; DELETE FROM entries; DELETE FROM files;")?;
+        set_meta(tx, "app_version", version)?;
+        set_meta(tx, "path_mappings", &mappings)?;
+    }
+
+    tx.execute_batch(DIRTY_TABLES)?;
+    let mut stored: HashMap<String, StoredFile> = HashMap::new();
+    {
+        let mut stmt = tx.prepare(
+            "SELECT id, path, offset, modified, project, ignored, merged_into FROM files",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(1)?,
+                StoredFile {
+                    id: row.get(0)?,
+                    offset: row.get::<_, i64>(2)? as u64,
+                    modified: row.get(3)?,
+                    project: row.get(4)?,
+                    ignored: row.get(5)?,
+                    merged_into: row.get(6)?,
+                },
+            ))
+        })?;
+        for row in rows {
+            let (path, file) = row?;
+            stored.insert(path, file);
+        }
+    }
+
+    let rules = duplicates::rules();
+    let files = reader.find_jsonl_files();
+    let mut changed = false;
+    let mut report = ReindexProgress {
+        files_total: files.len(),
+        ..Default::default()
+    };
+
+    // Deleted files (and those of another profile)
+    let present: HashSet<String> = files
+        .iter()
+        .map(|f| f.to_string_lossy().to_string())
+        .collect();
+    for (path, file) in &stored {
+        if !present.contains(path) {
+            delete_file_entries(tx, file.id)?;
+            tx.execute("DELETE FROM files WHERE id = ?1", [file.id])?;
+            changed = true;
+        }
+    }
+
+    for path in &files {
+        report.files_processed += 1;
+        progress(&report);
+        let key = path.to_string_lossy().to_string();
+        let project = reader.extract_project_path(path);
+        let ignored = rules.ignored.contains(path);
+        let merged_into = rules.merged.get(path).cloned();
+        let metadata = match path.metadata() {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                report.errors.push(format!("{}: {}", key, e));
+                continue;
+            }
+        };
+        let len = metadata.len();
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|m| m.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_millis() as i64);
+
+        let (id, mut offset) = match stored.get(&key) {
+            Some(file) => {
+                if file.project != project
+                    || file.ignored != ignored
+                    || file.merged_into != merged_into
+                {
+                    tx.execute(
+                        "UPDATE files SET project = ?1, ignored = ?2, merged_into = ?3 WHERE id = ?4",
+                        params![project, ignored, merged_into, file.id],
+                    )?;
+                    mark_dirty(tx, file.id)?;
+                    changed = true;
+                }
+                if len == file.offset && modified == file.modified {
+                    continue;
+                }
+                (file.id, file.offset)
+            }
+            None => {
+                tx.execute(
+                    "INSERT INTO files (path, project, ignored, merged_into) VALUES (?1, ?2, ?3, ?4)",
+                    params![key, project, ignored, merged_into],
+                )?;
+                (tx.last_insert_rowid(), 0)
+            }
+        };
+
+        // A shorter file was rewritten; start over
+        if len < offset {
+            delete_file_entries(tx, id)?;
+            offset = 0;
+            changed = true;
+        }
+        match reader.read_appended(path, offset, &project) {
+            Some((entries, end)) => {
+                for entry in &entries {
+                    insert_entry(tx, id, entry)?;
+                }
+                report.entries_ingested += entries.len() as u64;
+                changed |= !entries.is_empty();
+                offset = end;
+            }
+            None => report.errors.push(format!("{}: could not be read", key)),
+        }
+        tx.execute(
+            "UPDATE files SET offset = ?1, modified = ?2 WHERE id = ?3",
+            params![offset as i64, modified, id],
+        )?;
+    }
+
+    if changed {
+        tx.execute_batch(UPDATE_KEPT)?;
+    }
+    Ok(report)
+}
+
+/// Parse what was appended to the session files since the last sync
+pub fn sync(reader: &UsageReader) -> Result<(), String> {
+    with_db(|conn| {
+        let tx = conn.transaction()?;
+        sync_in(&tx, reader, &mut |_| {})?;
+        tx.commit()
+    })
+}
+
+/// Sync with the session files, after dropping the cache if `full`, and
+/// report progress after each file
+pub fn reindex(
+    reader: &UsageReader,
+    full: bool,
+    progress: &mut dyn FnMut(&ReindexProgress),
+) -> Result<ReindexProgress, String> {
+    if full {
+        rebuild()?;
+    }
+    let mut report = with_db(|conn| {
+        let tx = conn.transaction()?;
+        let report = sync_in(&tx, reader, progress)?;
+        tx.commit()?;
+        Ok(report)
+    })?;
+    report.done = true;
+    progress(&report);
+    Ok(report)
+}
+
+/// Drop the cache; the next sync parses every session file again. The goal
+/// history is kept unless the database is too broken to drop tables from.
+pub fn rebuild() -> Result<(), String> {
+    let mut guard = DB.lock().unwrap();
+    *guard = None;
+    if !db_path().exists()
+        || Connection::open(db_path())
+            .and_then(|conn| conn.execute_batch(DROP_CACHE))
+            .is_ok()
+    {
+        return Ok(());
+    }
+    match std::fs::remove_file(db_path()) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("Failed to remove the analytics cache: {}", e)),
+    }
+}
+
+/// Goal kinds are stored by their serde name, e.g. `daily_spend`
+fn goal_kind_name(record: &GoalRecord) -> String {
+    serde_json::to_value(record.kind)
+        .ok()
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+        .unwrap_or_default()
+}
+
+/// Every recorded goal period, oldest first
+pub fn goal_history() -> Result<Vec<GoalRecord>, String> {
+    with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT kind, period_start, target, actual, met FROM goal_history
+             ORDER BY period_start, kind",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, f64>(2)?,
+                row.get::<_, f64>(3)?,
+                row.get::<_, bool>(4)?,
+            ))
+        })?;
+        let mut records = Vec::new();
+        for row in rows {
+            let (kind, period_start, target, actual, met) = row?;
+            // Kinds of a newer claudit are skipped
+            if let Ok(kind) = serde_json::from_value(serde_json::Value::String(kind)) {
+                records.push(GoalRecord {
+                    period_start,
+                    kind,
+                    target,
+                    actual,
+                    met,
+                });
+            }
+        }
+        Ok(records)
+    })
+}
+
+/// Record goal periods; periods already recorded keep their first outcome
+pub fn record_goal_periods(records: &[GoalRecord]) -> Result<(), String> {
+    with_db(|conn| {
+        let tx = conn.transaction()?;
+        for record in records {
+            tx.prepare_cached(
+                "INSERT OR IGNORE INTO goal_history (kind, period_start, target, actual, met)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?
+            .execute(params![
+                goal_kind_name(record),
+                record.period_start,
+                record.target,
+                record.actual,
+                record.met,
+            ])?;
+        }
+        tx.commit()
+    })
+}
+
+/// Remove the given goal periods; returns how many were removed
+pub fn delete_goal_periods(records: &[GoalRecord]) -> Result<usize, String> {
+    with_db(|conn| {
+        let tx = conn.transaction()?;
+        let mut removed = 0;
+        for record in records {
+            removed += tx
+                .prepare_cached("DELETE FROM goal_history WHERE kind = ?1 AND period_start = ?2")?
+                .execute(params![goal_kind_name(record), record.period_start])?;
+        }
+        tx.commit()?;
+        Ok(removed)
+    })
+}
+
+/// Remove the whole goal history
+pub fn clear_goal_history() -> Result<(), String> {
+    with_db(|conn| conn.execute("DELETE FROM goal_history", []).map(|_| ()))
+}
+
+/// Remove the cached usage entries of a project (or a folder inside it) and/or
+/// a date range (inclusive, YYYY-MM-DD); returns how many there were. With
+/// `dry_run` they are only counted.
+pub fn purge_entries(
+    project: Option<&str>,
+    start_date: Option<&str>,
+    end_date: Option<&str>,
+    dry_run: bool,
+) -> Result<usize, String> {
+    with_db(|conn| {
+        let tx = conn.transaction()?;
+        tx.execute_batch(
+            "CREATE TEMP TABLE IF NOT EXISTS purged (id INTEGER PRIMARY KEY); DELETE FROM purged;",
+        )?;
+        let purged = tx.execute(
+            "INSERT INTO purged SELECT e.id FROM entries e JOIN files f ON f.id = e.file_id
+             WHERE (?1 IS NULL OR f.project = ?1
+                    OR substr(f.project, 1, length(?1) + 1) IN (?1 || '/', ?1 || '\\'))
+                AND (?2 IS NULL OR e.day >= ?2)
+                AND (?3 IS NULL OR e.day <= ?3)",
+            params![project, start_date, end_date],
+        )?;
+        if dry_run || purged == 0 {
+            return Ok(purged);
+        }
+        // Another copy of a removed entry may count now
+        tx.execute_batch(DIRTY_TABLES)?;
+        tx.execute_batch(
+            "INSERT OR IGNORE INTO dirty_uuids
+                SELECT uuid FROM entries WHERE id IN (SELECT id FROM purged);
+             INSERT OR IGNORE INTO dirty_messages
+                SELECT message_id FROM entries
+                WHERE id IN (SELECT id FROM purged) AND message_id IS NOT NULL;
+             DELETE FROM entry_tools WHERE entry_id IN (SELECT id FROM purged);
+             DELETE FROM entries WHERE id IN (SELECT id FROM purged);",
+        )?;
+        tx.execute_batch(UPDATE_KEPT)?;
+        tx.commit()?;
+        Ok(purged)
+    })
+}
+
+/// Time of the newest usage entry in the cache
+pub fn last_entry(reader: &UsageReader) -> Result<Option<DateTime<Utc>>, String> {
+    sync(reader)?;
+    let last: Option<i64> =
+        with_db(|conn| conn.query_row("SELECT MAX(ts) FROM entries", [], |row| row.get(0)))?;
+    Ok(last.and_then(DateTime::<Utc>::from_timestamp_millis))
+}
+
+fn to_u64(value: i64) -> u64 {
+    value.max(0) as u64
+}
+
+/// Per-model usage from `since_ms` on
+fn query_model_stats(
+    conn: &Connection,
+    since_ms: i64,
+) -> rusqlite::Result<HashMap<String, ModelStats>> {
+    let mut stmt = conn.prepare(
+        "SELECT model, SUM(input_tokens), SUM(output_tokens), SUM(cache_creation_tokens),
+            SUM(cache_read_tokens), SUM(cost), COUNT(*)
+         FROM usage WHERE ts >= ?1 GROUP BY model",
+    )?;
+    let rows = stmt.query_map([since_ms], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            ModelStats {
+                input_tokens: to_u64(row.get(1)?),
+                output_tokens: to_u64(row.get(2)?),
+                cache_creation_tokens: to_u64(row.get(3)?),
+                cache_read_tokens: to_u64(row.get(4)?),
+                cost: row.get(5)?,
+                message_count: to_u64(row.get(6)?),
+            },
+        ))
+    })?;
+    rows.collect()
+}
+
+/// Per-model usage since `since`, aggregated in SQL
+pub fn model_stats(
+    reader: &UsageReader,
+    since: DateTime<Utc>,
+) -> Result<HashMap<String, ModelStats>, String> {
+    sync(reader)?;
+    with_db(|conn| query_model_stats(conn, since.timestamp_millis()))
+}
+
+/// Stats for `get_stats`, aggregated in SQL
+pub fn stats(reader: &UsageReader) -> Result<AnalyticsStats, String> {
+    sync(reader)?;
+    let now = Utc::now();
+    let today_start = now
+        .date_naive()
+        .and_hms_opt(0, 0, 0)
+        .unwrap_or_default()
+        .and_utc();
+    let burn_start = now - Duration::minutes(30);
+
+    with_db(|conn| {
+        let mut stats = AnalyticsStats::default();
+
+        conn.query_row(
+            "SELECT COALESCE(SUM(input_tokens), 0), COALESCE(SUM(output_tokens), 0),
+                COALESCE(SUM(cache_creation_tokens), 0), COALESCE(SUM(cache_read_tokens), 0),
+                COALESCE(SUM(cost), 0), COUNT(*), COUNT(DISTINCT block)
+             FROM usage",
+            [],
+            |row| {
+                stats.total_input_tokens = to_u64(row.get(0)?);
+                stats.total_output_tokens = to_u64(row.get(1)?);
+                stats.total_cache_creation_tokens = to_u64(row.get(2)?);
+                stats.total_cache_read_tokens = to_u64(row.get(3)?);
+                stats.total_cost = row.get(4)?;
+                stats.total_messages_count = to_u64(row.get(5)?);
+                stats.total_session_count = row.get(6)?;
+                Ok(())
+            },
+        )?;
+
+        conn.query_row(
+            "SELECT COALESCE(SUM(input_tokens), 0), COALESCE(SUM(output_tokens), 0),
+                COALESCE(SUM(cost), 0), COUNT(*), COUNT(DISTINCT block)
+             FROM usage WHERE ts >= ?1",
+            [today_start.timestamp_millis()],
+            |row| {
+                stats.today_input_tokens = to_u64(row.get(0)?);
+                stats.today_output_tokens = to_u64(row.get(1)?);
+                stats.today_cost = row.get(2)?;
+                stats.today_messages = to_u64(row.get(3)?);
+                stats.today_messages_count = stats.today_messages;
+                stats.today_session_count = row.get(4)?;
+                Ok(())
+            },
+        )?;
+
+        conn.query_row(
+            "SELECT COALESCE(SUM(input_tokens + output_tokens), 0), COALESCE(SUM(cost), 0)
+             FROM usage WHERE block = ?1",
+            [get_session_block_key(&now)],
+            |row| {
+                stats.current_session_tokens = to_u64(row.get(0)?);
+                stats.current_session_cost = row.get(1)?;
+                Ok(())
+            },
+        )?;
+
+        // Burn rate over the last 30 minutes of activity
+        let (tokens, cost, first): (i64, f64, Option<i64>) = conn.query_row(
+            "SELECT COALESCE(SUM(input_tokens + output_tokens), 0), COALESCE(SUM(cost), 0), MIN(ts)
+             FROM usage WHERE ts >= ?1",
+            [burn_start.timestamp_millis()],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+        if let Some(first) = first.and_then(DateTime::<Utc>::from_timestamp_millis) {
+            let minutes = (now - first).num_minutes().max(1);
+            stats.tokens_per_minute = tokens as f64 / minutes as f64;
+            stats.cost_per_hour = (cost / minutes as f64) * 60.0;
+        }
+
+        stats.by_model = query_model_stats(conn, i64::MIN)?;
+        stats.today_by_model = query_model_stats(conn, today_start.timestamp_millis())?;
+
+        let mut stmt = conn.prepare(
+            "SELECT project, SUM(input_tokens), SUM(output_tokens), SUM(cost), COUNT(*)
+             FROM usage GROUP BY project",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(ProjectStats {
+                name: row.get(0)?,
+                input_tokens: to_u64(row.get(1)?),
+                output_tokens: to_u64(row.get(2)?),
+                cost: row.get(3)?,
+                message_count: to_u64(row.get(4)?),
+            })
+        })?;
+        for row in rows {
+            let project = row?;
+            stats.by_project.insert(project.name.clone(), project);
+        }
+
+        let mut stmt = conn.prepare(
+            "SELECT service_tier, COUNT(*), SUM(input_tokens), SUM(output_tokens), SUM(cost)
+             FROM usage GROUP BY service_tier",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, Option<String>>(0)?,
+                to_u64(row.get(1)?),
+                to_u64(row.get(2)?),
+                to_u64(row.get(3)?),
+                row.get::<_, f64>(4)?,
+            ))
+        })?;
+        for row in rows {
+            let (tier, requests, input_tokens, output_tokens, cost) = row?;
+            let multiplier = pricing::service_tier_multiplier(tier.as_deref());
+            let tier_stats = stats
+                .by_service_tier
+                .entry(tier.unwrap_or_else(|| "standard".to_string()))
+                .or_default();
+            tier_stats.requests += requests;
+            tier_stats.input_tokens += input_tokens;
+            tier_stats.output_tokens += output_tokens;
+            tier_stats.cost += cost;
+            tier_stats.standard_cost += cost / multiplier;
+        }
+
+        stats.last_updated = Some(now);
+        Ok(stats)
+    })
+}
+
+/// Chart data for the last `days` days, aggregated in SQL
+pub fn chart_data(reader: &UsageReader, days: u32) -> Result<ChartData, String> {
+    sync(reader)?;
+    let cutoff = (Utc::now() - Duration::days(days as i64)).timestamp_millis();
+
+    with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT day, model, SUM(input_tokens), SUM(output_tokens), SUM(cost), COUNT(*)
+             FROM usage WHERE ts >= ?1 GROUP BY day, model ORDER BY day",
+        )?;
+        let rows = stmt.query_map([cutoff], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                DailyModelStats {
+                    model: row.get(1)?,
+                    input_tokens: to_u64(row.get(2)?),
+                    output_tokens: to_u64(row.get(3)?),
+                    cost: row.get(4)?,
+                    messages: to_u64(row.get(5)?),
+                },
+            ))
+        })?;
+        let mut daily: Vec<DailyStats> = Vec::new();
+        let mut daily_models: HashMap<(String, String), DailyModelStats> = HashMap::new();
+        for row in rows {
+            let (date, model) = row?;
+            if daily.last().is_none_or(|d| d.date != date) {
+                daily.push(DailyStats {
+                    date: date.clone(),
+                    input_tokens: 0,
+                    output_tokens: 0,
+                    cost: 0.0,
+                    messages: 0,
+                    by_model: Vec::new(),
+                });
+            }
+            if let Some(day) = daily.last_mut() {
+                day.input_tokens += model.input_tokens;
+                day.output_tokens += model.output_tokens;
+                day.cost += model.cost;
+                day.messages += model.messages;
+            }
+            daily_models.insert((date, model.model.clone()), model);
+        }
+
+        let mut stmt = conn.prepare(
+            "SELECT hour, SUM(input_tokens + output_tokens), COUNT(*)
+             FROM usage WHERE ts >= ?1 GROUP BY hour ORDER BY hour",
+        )?;
+        let hourly = stmt
+            .query_map([cutoff], |row| {
+                Ok(HourlyStats {
+                    hour: row.get(0)?,
+                    tokens: to_u64(row.get(1)?),
+                    messages: to_u64(row.get(2)?),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT model, SUM(input_tokens + output_tokens) AS tokens, SUM(cost)
+             FROM usage WHERE ts >= ?1 GROUP BY model ORDER BY tokens DESC",
+        )?;
+        let by_model = stmt
+            .query_map([cutoff], |row| {
+                Ok(ModelChartData {
+                    name: row.get(0)?,
+                    tokens: to_u64(row.get(1)?),
+                    cost: row.get(2)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT project, SUM(input_tokens + output_tokens) AS tokens, SUM(cost)
+             FROM usage WHERE ts >= ?1 GROUP BY project ORDER BY tokens DESC",
+        )?;
+        let by_project = stmt
+            .query_map([cutoff], |row| {
+                Ok(ProjectChartData {
+                    name: row.get(0)?,
+                    tokens: to_u64(row.get(1)?),
+                    cost: row.get(2)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        // Per-model daily split, in the same model order every day
+        for day in &mut daily {
+            day.by_model = by_model
+                .iter()
+                .map(|model| {
+                    daily_models
+                        .remove(&(day.date.clone(), model.name.clone()))
+                        .unwrap_or_else(|| DailyModelStats {
+                            model: model.name.clone(),
+                            ..Default::default()
+                        })
+                })
+                .collect();
+        }
+
+        Ok(ChartData {
+            daily,
+            hourly,
+            by_model,
+            by_project,
+            bucket: Default::default(),
+        })
+    })
+}
+
+/// Most dimensions a drill-down can group by
+const MAX_DRILL_DIMENSIONS: usize = 4;
+
+/// Rows a drill-down returns when no limit is given
+const DEFAULT_DRILL_LIMIT: u32 = 500;
+
+/// SQL for a dimension's value (`t` is the per-tool join)
+fn dimension_sql(dimension: DrillDimension) -> &'static str {
+    match dimension {
+        DrillDimension::Project => "u.project",
+        DrillDimension::Model => "u.model",
+        DrillDimension::Day => "u.day",
+        DrillDimension::Hour => "printf('%02d', u.hour)",
+        DrillDimension::Session => "u.session",
+        DrillDimension::Tool => "t.tool",
+        DrillDimension::ServiceTier => "COALESCE(u.service_tier, 'standard')",
+    }
+}
+
+/// `column IN (?, ...)` for `values`, which are appended to `params`
+fn in_list(column: &str, values: &[String], params: &mut Vec<String>) -> String {
+    params.extend(values.iter().cloned());
+    format!("{} IN ({})", column, vec!["?"; values.len()].join(", "))
+}
+
+/// Usage grouped by `dimensions` (none = a single total row), filtered,
+/// sorted and limited, aggregated in SQL
+pub fn drill_down(
+    reader: &UsageReader,
+    dimensions: &[DrillDimension],
+    filters: &DrillDownFilters,
+    sort: &DrillSort,
+    limit: Option<u32>,
+) -> Result<DrillDownResult, String> {
+    let mut dims: Vec<DrillDimension> = Vec::new();
+    for dimension in dimensions {
+        if !dims.contains(dimension) {
+            dims.push(*dimension);
+        }
+    }
+    if dims.len() > MAX_DRILL_DIMENSIONS {
+        return Err(format!(
+            "Group by at most {} dimensions",
+            MAX_DRILL_DIMENSIONS
+        ));
+    }
+    sync(reader)?;
+
+    let by_tool = dims.contains(&DrillDimension::Tool);
+    let mut params: Vec<String> = Vec::new();
+    let mut conditions: Vec<String> = Vec::new();
+    if !filters.projects.is_empty() {
+        conditions.push(in_list("u.project", &filters.projects, &mut params));
+    }
+    if !filters.models.is_empty() {
+        conditions.push(in_list("u.model", &filters.models, &mut params));
+    }
+    if !filters.sessions.is_empty() {
+        conditions.push(in_list("u.session", &filters.sessions, &mut params));
+    }
+    if !filters.tools.is_empty() {
+        let tools = in_list("tool", &filters.tools, &mut params);
+        conditions.push(format!(
+            "u.id IN (SELECT entry_id FROM entry_tools WHERE {})",
+            tools
+        ));
+        if by_tool {
+            conditions.push(in_list("t.tool", &filters.tools, &mut params));
+        }
+    }
+    if let Some(since) = &filters.since {
+        conditions.push("u.day >= ?".to_string());
+        params.push(since.clone());
+    }
+    if let Some(until) = &filters.until {
+        conditions.push("u.day <= ?".to_string());
+        params.push(until.clone());
+    }
+
+    let (from, tool_uses) = if by_tool {
+        (
+            "usage u JOIN (SELECT entry_id, tool, COUNT(*) AS uses FROM entry_tools
+                GROUP BY entry_id, tool) t ON t.entry_id = u.id",
+            "SUM(t.uses)",
+        )
+    } else {
+        (
+            "usage u",
+            "SUM((SELECT COUNT(*) FROM entry_tools t WHERE t.entry_id = u.id))",
+        )
+    };
+    let keys: Vec<String> = (0..dims.len()).map(|i| format!("k{}", i)).collect();
+    let mut columns: Vec<String> = dims
+        .iter()
+        .zip(&keys)
+        .map(|(dimension, key)| format!("{} AS {}", dimension_sql(*dimension), key))
+        .collect();
+    columns.push(format!(
+        "COALESCE(SUM(u.cost), 0), COALESCE(SUM(u.input_tokens), 0),
+            COALESCE(SUM(u.output_tokens), 0),
+            COALESCE(SUM(u.cache_creation_tokens), 0), COALESCE(SUM(u.cache_read_tokens), 0),
+            COUNT(*), COALESCE({}, 0) AS tool_uses",
+        tool_uses
+    ));
+    let mut grouped = format!("SELECT {} FROM {}", columns.join(", "), from);
+    if !conditions.is_empty() {
+        grouped.push_str(&format!(" WHERE {}", conditions.join(" AND ")));
+    }
+    if !keys.is_empty() {
+        grouped.push_str(&format!(" GROUP BY {}", keys.join(", ")));
+    }
+
+    let direction = if sort.ascending { "ASC" } else { "DESC" };
+    let mut order: Vec<String> = match sort.by {
+        DrillMeasure::Cost => vec![format!("SUM(u.cost) {}", direction)],
+        DrillMeasure::Tokens => vec![format!(
+            "SUM(u.input_tokens + u.output_tokens) {}",
+            direction
+        )],
+        DrillMeasure::Requests => vec![format!("COUNT(*) {}", direction)],
+        DrillMeasure::ToolUses => vec![format!("tool_uses {}", direction)],
+        DrillMeasure::Keys => keys
+            .iter()
+            .map(|k| format!("{} {}", k, direction))
+            .collect(),
+    };
+    order.extend(keys.iter().cloned());
+    if order.is_empty() {
+        order.push("SUM(u.cost) DESC".to_string());
+    }
+
+    with_db(|conn| {
+        let total_rows: i64 = conn.query_row(
+            &format!("SELECT COUNT(*) FROM ({})", grouped),
+            params_from_iter(params.iter()),
+            |row| row.get(0),
+        )?;
+        let mut stmt = conn.prepare(&format!(
+            "{} ORDER BY {} LIMIT {}",
+            grouped,
+            order.join(", "),
+            limit.unwrap_or(DEFAULT_DRILL_LIMIT)
+        ))?;
+        let key_count = keys.len();
+        let rows = stmt
+            .query_map(params_from_iter(params.iter()), |row| {
+                let keys = (0..key_count)
+                    .map(|i| {
+                        row.get::<_, Option<String>>(i)
+                            .map(|key| key.unwrap_or_default())
+                    })
+                    .collect::<rusqlite::Result<Vec<_>>>()?;
+                Ok(DrillDownRow {
+                    keys,
+                    cost: row.get(key_count)?,
+                    input_tokens: to_u64(row.get(key_count + 1)?),
+                    output_tokens: to_u64(row.get(key_count + 2)?),
+                    cache_creation_tokens: to_u64(row.get(key_count + 3)?),
+                    cache_read_tokens: to_u64(row.get(key_count + 4)?),
+                    requests: to_u64(row.get(key_count + 5)?),
+                    tool_uses: to_u64(row.get(key_count + 6)?),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(DrillDownResult {
+            dimensions: dims.clone(),
+            rows,
+            total_rows: to_u64(total_rows),
+        })
+    })
+}
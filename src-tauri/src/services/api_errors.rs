@@ -0,0 +1,105 @@
+//! API error spike detection
+//!
+//! Checks the sessions written in the last few minutes for failed requests and
+//! notifies (at most once per cooldown) when a large share of them failed.
+
+use super::notifications::{self, PendingNotification};
+use super::settings::SettingsService;
+use super::usage::UsageReader;
+use crate::types::ApiErrorKind;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Runtime};
+
+/// Window the error rate is measured over
+const SPIKE_WINDOW_MINUTES: i64 = 15;
+/// Minimum failed requests in the window before it counts as a spike
+const SPIKE_MIN_ERRORS: usize = 3;
+/// Minimum share of failed requests (0-1)
+const SPIKE_MIN_RATE: f64 = 0.25;
+/// Don't notify again within this many minutes
+const SPIKE_COOLDOWN_MINUTES: i64 = 60;
+
+static LAST_SPIKE_NOTIFIED: Mutex<Option<DateTime<Utc>>> = Mutex::new(None);
+
+/// Notify when the recent API error rate spikes (`notify_error_spikes` setting)
+pub fn check_error_spike<R: Runtime>(app: &AppHandle<R>) {
+    if !SettingsService::shared().get().notify_error_spikes {
+        return;
+    }
+
+    let now = Utc::now();
+    if LAST_SPIKE_NOTIFIED
+        .lock()
+        .unwrap()
+        .is_some_and(|last| now - last < Duration::minutes(SPIKE_COOLDOWN_MINUTES))
+    {
+        return;
+    }
+
+    let since = now - Duration::minutes(SPIKE_WINDOW_MINUTES);
+    let reader = UsageReader::new();
+    let files = reader.recently_modified_files(Duration::minutes(SPIKE_WINDOW_MINUTES));
+    if files.is_empty() {
+        return;
+    }
+
+    let errors: Vec<_> = reader
+        .read_api_errors_in(&files, Some(1))
+        .into_iter()
+        .filter(|e| e.timestamp >= since)
+        .collect();
+    if errors.len() < SPIKE_MIN_ERRORS {
+        return;
+    }
+
+    let requests = files
+        .iter()
+        .flat_map(|file| reader.read_session_entries(file))
+        .filter(|e| e.timestamp >= since && !e.model.starts_with('<'))
+        .count();
+    let attempts = requests + errors.len();
+    if (errors.len() as f64) < attempts as f64 * SPIKE_MIN_RATE {
+        return;
+    }
+
+    *LAST_SPIKE_NOTIFIED.lock().unwrap() = Some(now);
+
+    let mut by_kind: HashMap<ApiErrorKind, usize> = HashMap::new();
+    for error in &errors {
+        *by_kind.entry(error.kind).or_insert(0) += 1;
+    }
+    let dominant = by_kind
+        .into_iter()
+        .max_by_key(|(_, count)| *count)
+        .map(|(kind, _)| kind)
+        .unwrap_or(ApiErrorKind::Other);
+
+    // Overload/server errors point at the API, anything else at the local setup
+    let hint = match dominant {
+        ApiErrorKind::Overloaded => "mostly overloaded - the API is likely under load",
+        ApiErrorKind::RateLimited => "mostly rate limited",
+        ApiErrorKind::ServerError => "mostly server errors - the API may be having issues",
+        ApiErrorKind::Other => "mostly client errors - check your API key and config",
+    };
+
+    let _ = app.emit("api-error-spike", &errors);
+
+    notifications::dispatch(
+        app,
+        PendingNotification {
+            title: "Claude API errors".to_string(),
+            body: format!(
+                "{} of {} requests failed in the last {} minutes ({})",
+                errors.len(),
+                attempts,
+                SPIKE_WINDOW_MINUTES,
+                hint
+            ),
+            event: "ApiErrorSpike".to_string(),
+            project: None,
+            session_id: None,
+        },
+    );
+}
@@ -0,0 +1,179 @@
+//! Export and import of claudit's own data
+//!
+//! Unlike the Claude config backup, this covers what claudit itself stores in
+//! its config directory: preferences (including goals, profiles and kiosk
+//! settings), per-project notification rules, goal history, duplicate session
+//! resolutions, imported Console costs and suggestion feedback. Machine-specific
+//! state (window layout, hook token, activity feed, audit trail) is left out.
+
+use super::goals::{self, GoalRecord};
+use super::metadata::{self, ClauditMetadata};
+use super::settings::app_config_dir;
+use super::SettingsService;
+use crate::types::AppSettings;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Bumped when the export layout changes incompatibly
+const FORMAT_VERSION: u32 = 1;
+
+/// Files in claudit's config directory copied verbatim, with what they hold
+const DATA_FILES: &[(&str, &str)] = &[
+    ("duplicate-sessions.json", "Duplicate session resolutions"),
+    ("console-costs.json", "Imported Console costs"),
+    ("suggestion-feedback.json", "Suggestion feedback"),
+];
+
+/// Key of the goal history (kept in the analytics database) among the files,
+/// where it was a data file in earlier versions
+const GOAL_HISTORY: &str = "goal-history.json";
+
+/// Everything in an app data export file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppDataExport {
+    pub format_version: u32,
+    pub app_version: String,
+    pub exported_at: String,
+    pub settings: AppSettings,
+    #[serde(default)]
+    pub metadata: ClauditMetadata,
+    /// Other data files by file name
+    #[serde(default)]
+    pub files: BTreeMap<String, Value>,
+}
+
+/// What an export or import covered
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppDataSummary {
+    pub path: String,
+    /// Human readable names of the parts included
+    pub items: Vec<String>,
+    /// Where the previous data was saved before an import
+    pub backup_path: Option<String>,
+}
+
+fn collect() -> AppDataExport {
+    let mut files: BTreeMap<String, Value> = DATA_FILES
+        .iter()
+        .filter_map(|(name, _)| {
+            let contents = fs::read_to_string(app_config_dir().join(name)).ok()?;
+            let value = serde_json::from_str(&contents).ok()?;
+            Some((name.to_string(), value))
+        })
+        .collect();
+    if let Some(history) = goals::history()
+        .ok()
+        .filter(|history| !history.is_empty())
+        .and_then(|history| serde_json::to_value(history).ok())
+    {
+        files.insert(GOAL_HISTORY.to_string(), history);
+    }
+
+    AppDataExport {
+        format_version: FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        settings: SettingsService::shared().get(),
+        metadata: metadata::load(),
+        files,
+    }
+}
+
+fn describe(data: &AppDataExport) -> Vec<String> {
+    let mut items = vec!["Preferences".to_string()];
+    if !data.metadata.projects.is_empty() {
+        items.push(format!(
+            "Notification rules for {} projects",
+            data.metadata.projects.len()
+        ));
+    }
+    if data.files.contains_key(GOAL_HISTORY) {
+        items.push("Goal history".to_string());
+    }
+    items.extend(
+        DATA_FILES
+            .iter()
+            .filter(|(name, _)| data.files.contains_key(*name))
+            .map(|(_, label)| label.to_string()),
+    );
+    items
+}
+
+fn write_export(data: &AppDataExport, path: &Path) -> Result<(), String> {
+    let contents =
+        serde_json::to_string_pretty(data).map_err(|e| format!("Serialize error: {}", e))?;
+    fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Write claudit's settings and data to a JSON file
+pub fn export(path: &Path) -> Result<AppDataSummary, String> {
+    let data = collect();
+    write_export(&data, path)?;
+    Ok(AppDataSummary {
+        path: path.to_string_lossy().to_string(),
+        items: describe(&data),
+        backup_path: None,
+    })
+}
+
+/// Read an export file without applying it
+pub fn read(path: &Path) -> Result<AppDataExport, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let data: AppDataExport =
+        serde_json::from_str(&contents).map_err(|e| format!("Not a claudit data export: {}", e))?;
+    if data.format_version > FORMAT_VERSION {
+        return Err(format!(
+            "This export was made by a newer claudit ({}); update claudit to import it",
+            data.app_version
+        ));
+    }
+    Ok(data)
+}
+
+/// Replace claudit's settings and data with an export. The current data is
+/// saved to `app-data-backup-<timestamp>.json` first. Returns the imported
+/// settings so the caller can refresh the tray and profile.
+pub fn import(path: &Path) -> Result<(AppSettings, AppDataSummary), String> {
+    let data = read(path)?;
+
+    let backup_path = app_config_dir().join(format!(
+        "app-data-backup-{}.json",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    write_export(&collect(), &backup_path)?;
+
+    SettingsService::shared().update(data.settings.clone())?;
+    metadata::save(&data.metadata)?;
+    let history: Vec<GoalRecord> = data
+        .files
+        .get(GOAL_HISTORY)
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+        .unwrap_or_default();
+    goals::replace_history(&history)?;
+    for (name, _) in DATA_FILES {
+        let target = app_config_dir().join(name);
+        match data.files.get(*name) {
+            Some(value) => {
+                let contents = serde_json::to_string_pretty(value)
+                    .map_err(|e| format!("Serialize error: {}", e))?;
+                fs::write(&target, contents).map_err(|e| format!("Write error: {}", e))?;
+            }
+            None if target.exists() => {
+                fs::remove_file(&target)
+                    .map_err(|e| format!("Failed to remove {}: {}", name, e))?;
+            }
+            None => {}
+        }
+    }
+
+    let summary = AppDataSummary {
+        path: path.to_string_lossy().to_string(),
+        items: describe(&data),
+        backup_path: Some(backup_path.to_string_lossy().to_string()),
+    };
+    Ok((data.settings, summary))
+}
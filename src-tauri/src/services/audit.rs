@@ -0,0 +1,493 @@
+//! Audit trail for config mutations
+//!
+//! Every write claudit makes to Claude Code's configuration goes through
+//! `write_file` / `remove_file`, which append an entry to `audit.jsonl` in
+//! claudit's config directory together with before/after snapshots. The most
+//! recent change can be reverted with `undo_last_change`. Only the latest
+//! `MAX_AUDIT_ENTRIES` entries and their snapshots are kept. Plans that touch
+//! several files can also take a restore point first (see `restore_points`).
+
+use super::demo;
+use super::restore_points;
+use super::settings::app_config_dir;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// A single recorded config mutation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub id: String,
+    pub timestamp: String,
+    /// What claudit was doing (e.g. "add_mcp_server: github")
+    pub action: String,
+    pub path: String,
+    /// Whether the file existed before / after the change (snapshots are stored next to the log)
+    pub existed_before: bool,
+    pub exists_after: bool,
+    /// Id of the entry this change reverted, for undo entries
+    #[serde(default)]
+    pub undoes: Option<String>,
+}
+
+/// Older entries and their snapshots are pruned
+const MAX_AUDIT_ENTRIES: usize = 500;
+
+fn audit_log_path() -> PathBuf {
+    app_config_dir().join("audit.jsonl")
+}
+
+fn snapshot_dir() -> PathBuf {
+    let dir = app_config_dir().join("audit-snapshots");
+    if !dir.exists() {
+        let _ = fs::create_dir_all(&dir);
+    }
+    dir
+}
+
+fn snapshot_path(id: &str, stage: &str) -> PathBuf {
+    snapshot_dir().join(format!("{}.{}", id, stage))
+}
+
+fn append_entry(entry: &AuditEntry) -> Result<(), String> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(audit_log_path())
+        .map_err(|e| format!("Failed to open audit log: {}", e))?;
+    let line = serde_json::to_string(entry).map_err(|e| format!("Serialize error: {}", e))?;
+    writeln!(file, "{}", line).map_err(|e| format!("Failed to write audit log: {}", e))
+}
+
+/// Keep the last `keep` entries of the log, and only their snapshots
+fn prune(keep: usize) -> Result<(), String> {
+    let Ok(content) = fs::read_to_string(audit_log_path()) else {
+        return Ok(());
+    };
+    let lines: Vec<&str> = content.lines().filter(|l| !l.trim().is_empty()).collect();
+    if lines.len() <= keep {
+        return Ok(());
+    }
+    let kept = &lines[lines.len() - keep..];
+    let mut contents = kept.join("\n");
+    if !contents.is_empty() {
+        contents.push('\n');
+    }
+    fs::write(audit_log_path(), contents)
+        .map_err(|e| format!("Failed to write audit log: {}", e))?;
+
+    let ids: HashSet<String> = kept
+        .iter()
+        .filter_map(|line| serde_json::from_str::<AuditEntry>(line).ok())
+        .map(|entry| entry.id)
+        .collect();
+    for snapshot in fs::read_dir(snapshot_dir()).into_iter().flatten().flatten() {
+        let name = snapshot.file_name().to_string_lossy().to_string();
+        let id = name.split('.').next().unwrap_or_default();
+        if !ids.contains(id) {
+            let _ = fs::remove_file(snapshot.path());
+        }
+    }
+    Ok(())
+}
+
+/// Snapshot the current state of a file under the given stage name
+fn snapshot(path: &Path, id: &str, stage: &str) -> Result<bool, String> {
+    if !path.exists() {
+        return Ok(false);
+    }
+    fs::copy(path, snapshot_path(id, stage))
+        .map_err(|e| format!("Failed to snapshot {}: {}", path.display(), e))?;
+    Ok(true)
+}
+
+/// Apply a mutation to a file and record it in the audit log
+fn audited<F>(
+    path: &Path,
+    action: &str,
+    undoes: Option<String>,
+    mutate: F,
+) -> Result<AuditEntry, String>
+where
+    F: FnOnce(&Path) -> Result<(), String>,
+{
+    demo::ensure_writable()?;
+    let id = uuid::Uuid::new_v4().to_string();
+    let existed_before = snapshot(path, &id, "before")?;
+
+    mutate(path)?;
+
+    let exists_after = snapshot(path, &id, "after")?;
+    let entry = AuditEntry {
+        id,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        action: action.to_string(),
+        path: path.to_string_lossy().to_string(),
+        existed_before,
+        exists_after,
+        undoes,
+    };
+    append_entry(&entry)?;
+    prune(MAX_AUDIT_ENTRIES)?;
+    Ok(entry)
+}
+
+/// Write a file, recording the change in the audit log
+pub fn write_file(path: &Path, contents: impl AsRef<[u8]>, action: &str) -> Result<(), String> {
+    audited(path, action, None, |path| {
+        fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    })
+    .map(|_| ())
+}
+
+/// Remove a file, recording the change in the audit log
+pub fn remove_file(path: &Path, action: &str) -> Result<(), String> {
+    if !path.exists() {
+        return Ok(());
+    }
+    audited(path, action, None, |path| {
+        fs::remove_file(path).map_err(|e| format!("Failed to remove {}: {}", path.display(), e))
+    })
+    .map(|_| ())
+}
+
+/// Read the audit log (oldest first)
+pub fn entries() -> Vec<AuditEntry> {
+    let file = match File::open(audit_log_path()) {
+        Ok(f) => f,
+        Err(_) => return Vec::new(),
+    };
+
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect()
+}
+
+/// Revert the most recent change that hasn't been undone yet.
+/// Refuses if the file was modified since (e.g. by Claude Code) unless `force` is set.
+pub fn undo_last_change(force: bool) -> Result<AuditEntry, String> {
+    let entries = entries();
+    let undone: HashSet<&str> = entries.iter().filter_map(|e| e.undoes.as_deref()).collect();
+
+    let last = entries
+        .iter()
+        .rev()
+        .find(|e| e.undoes.is_none() && !undone.contains(e.id.as_str()))
+        .ok_or_else(|| "Nothing to undo".to_string())?;
+
+    let path = PathBuf::from(&last.path);
+
+    if !force {
+        let current = fs::read(&path).ok();
+        let after = if last.exists_after {
+            fs::read(snapshot_path(&last.id, "after")).ok()
+        } else {
+            None
+        };
+        if current != after {
+            return Err(format!(
+                "{} was modified after \"{}\"; undoing would discard those changes",
+                path.display(),
+                last.action
+            ));
+        }
+    }
+
+    let action = format!("undo: {}", last.action);
+    let before = snapshot_path(&last.id, "before");
+    let existed_before = last.existed_before;
+
+    audited(&path, &action, Some(last.id.clone()), |path| {
+        if existed_before {
+            fs::copy(&before, path)
+                .map(|_| ())
+                .map_err(|e| format!("Failed to restore {}: {}", path.display(), e))
+        } else if path.exists() {
+            fs::remove_file(path).map_err(|e| format!("Failed to remove {}: {}", path.display(), e))
+        } else {
+            Ok(())
+        }
+    })
+}
+
+/// Kind of a planned file operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileOperationKind {
+    CreateDir,
+    Copy,
+    Write,
+    Remove,
+}
+
+/// A file operation claudit will perform (or would perform, in a dry run)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileOperation {
+    pub kind: FileOperationKind,
+    pub path: String,
+    /// Source file for copies
+    pub source: Option<String>,
+    /// Size of the data written
+    pub bytes: Option<u64>,
+    pub description: String,
+}
+
+/// A list of file operations that can be previewed (dry run) or applied through the audit trail
+pub struct FilePlan {
+    action: String,
+    steps: Vec<(FileOperation, Option<Vec<u8>>)>,
+    restore_point: bool,
+}
+
+impl FilePlan {
+    pub fn new(action: &str) -> Self {
+        Self {
+            action: action.to_string(),
+            steps: Vec::new(),
+            restore_point: false,
+        }
+    }
+
+    fn push(
+        &mut self,
+        kind: FileOperationKind,
+        path: &Path,
+        source: Option<&Path>,
+        contents: Option<Vec<u8>>,
+        description: &str,
+    ) {
+        let operation = FileOperation {
+            kind,
+            path: path.to_string_lossy().to_string(),
+            source: source.map(|s| s.to_string_lossy().to_string()),
+            bytes: contents
+                .as_ref()
+                .map(|c| c.len() as u64)
+                .or_else(|| source.and_then(|s| fs::metadata(s).ok()).map(|m| m.len())),
+            description: description.to_string(),
+        };
+        self.steps.push((operation, contents));
+    }
+
+    pub fn create_dir(&mut self, path: &Path, description: &str) {
+        if !path.exists() {
+            self.push(FileOperationKind::CreateDir, path, None, None, description);
+        }
+    }
+
+    pub fn copy(&mut self, from: &Path, to: &Path, description: &str) {
+        self.push(FileOperationKind::Copy, to, Some(from), None, description);
+    }
+
+    pub fn write(&mut self, path: &Path, contents: impl Into<Vec<u8>>, description: &str) {
+        self.push(
+            FileOperationKind::Write,
+            path,
+            None,
+            Some(contents.into()),
+            description,
+        );
+    }
+
+    pub fn remove(&mut self, path: &Path, description: &str) {
+        if path.exists() {
+            self.push(FileOperationKind::Remove, path, None, None, description);
+        }
+    }
+
+    /// Snapshot the affected files into a restore point before applying
+    pub fn with_restore_point(&mut self) {
+        self.restore_point = true;
+    }
+
+    /// The operations in this plan
+    pub fn operations(&self) -> Vec<FileOperation> {
+        self.steps.iter().map(|(op, _)| op.clone()).collect()
+    }
+
+    /// Execute the plan, recording every change in the audit log
+    pub fn apply(self) -> Result<Vec<FileOperation>, String> {
+        demo::ensure_writable()?;
+        if self.restore_point {
+            let paths: Vec<PathBuf> = self
+                .steps
+                .iter()
+                .filter(|(op, _)| op.kind != FileOperationKind::CreateDir)
+                .map(|(op, _)| PathBuf::from(&op.path))
+                .collect();
+            if !paths.is_empty() {
+                restore_points::create(&self.action, &paths)?;
+            }
+        }
+        let mut applied = Vec::new();
+
+        for (operation, contents) in self.steps {
+            let path = PathBuf::from(&operation.path);
+            let action = format!("{}: {}", self.action, operation.description);
+
+            match operation.kind {
+                FileOperationKind::CreateDir => fs::create_dir_all(&path)
+                    .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?,
+                FileOperationKind::Copy => {
+                    let source = PathBuf::from(operation.source.clone().unwrap_or_default());
+                    audited(&path, &action, None, |path| {
+                        fs::copy(&source, path)
+                            .map(|_| ())
+                            .map_err(|e| format!("Failed to copy {}: {}", source.display(), e))
+                    })?;
+                }
+                FileOperationKind::Write => {
+                    write_file(&path, contents.unwrap_or_default(), &action)?
+                }
+                FileOperationKind::Remove => remove_file(&path, &action)?,
+            }
+
+            applied.push(operation);
+        }
+
+        Ok(applied)
+    }
+
+    /// Return the planned operations without executing them, or apply them
+    pub fn run(self, dry_run: bool) -> Result<Vec<FileOperation>, String> {
+        if dry_run {
+            Ok(self.operations())
+        } else {
+            self.apply()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// The audit log is shared, so its tests run one at a time
+    static LOG: Mutex<()> = Mutex::new(());
+
+    fn scratch_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("claudit-audit-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir.join(name)
+    }
+
+    #[test]
+    fn test_undo_write() {
+        let _log = LOG.lock().unwrap();
+        let path = scratch_file("settings.json");
+        fs::write(&path, "old").unwrap();
+
+        write_file(&path, "new", "test write").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+
+        let undo = undo_last_change(false).unwrap();
+        assert_eq!(undo.action, "undo: test write");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+    }
+
+    #[test]
+    fn test_undo_create() {
+        let _log = LOG.lock().unwrap();
+        let path = scratch_file("agent.md");
+
+        write_file(&path, "created", "test create").unwrap();
+        assert!(path.exists());
+
+        undo_last_change(false).unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_undo_remove() {
+        let _log = LOG.lock().unwrap();
+        let path = scratch_file("command.md");
+        fs::write(&path, "keep me").unwrap();
+
+        remove_file(&path, "test remove").unwrap();
+        assert!(!path.exists());
+
+        undo_last_change(false).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "keep me");
+    }
+
+    #[test]
+    fn test_undo_refuses_later_edits() {
+        let _log = LOG.lock().unwrap();
+        let path = scratch_file("CLAUDE.md");
+
+        write_file(&path, "claudit", "test edit").unwrap();
+        fs::write(&path, "edited by hand").unwrap();
+        assert!(undo_last_change(false).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "edited by hand");
+
+        undo_last_change(true).unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_prune() {
+        let _log = LOG.lock().unwrap();
+        let path = scratch_file("pruned.json");
+        fs::write(&path, "0").unwrap();
+        for version in 1..=3 {
+            write_file(&path, version.to_string(), "test prune").unwrap();
+        }
+        let before = entries();
+        prune(2).unwrap();
+
+        let kept = entries();
+        assert_eq!(kept.len(), 2);
+        assert_eq!(kept[0].id, before[before.len() - 2].id);
+        assert_eq!(kept[1].id, before[before.len() - 1].id);
+        // Snapshots of the pruned entries go with them
+        for entry in &before[..before.len() - 2] {
+            assert!(!snapshot_path(&entry.id, "before").exists());
+            assert!(!snapshot_path(&entry.id, "after").exists());
+        }
+        for entry in &kept {
+            assert!(snapshot_path(&entry.id, "after").exists());
+        }
+        assert_eq!(fs::read_dir(snapshot_dir()).unwrap().count(), 4);
+
+        undo_last_change(false).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "2");
+    }
+
+    #[test]
+    fn test_plan_round_trip() {
+        let _log = LOG.lock().unwrap();
+        let written = scratch_file("written.json");
+        let removed = written.with_file_name("removed.json");
+        fs::write(&removed, "old").unwrap();
+
+        let mut plan = FilePlan::new("test plan");
+        plan.write(&written, "new", "write");
+        plan.remove(&removed, "remove");
+        let operations = plan.operations();
+        assert_eq!(operations.len(), 2);
+        assert_eq!(operations[0].kind, FileOperationKind::Write);
+        assert_eq!(operations[0].bytes, Some(3));
+
+        // A dry run changes nothing
+        let mut dry = FilePlan::new("test plan");
+        dry.write(&written, "new", "write");
+        assert_eq!(dry.run(true).unwrap().len(), 1);
+        assert!(!written.exists());
+
+        plan.run(false).unwrap();
+        assert_eq!(fs::read_to_string(&written).unwrap(), "new");
+        assert!(!removed.exists());
+
+        // Undone newest first
+        undo_last_change(false).unwrap();
+        assert_eq!(fs::read_to_string(&removed).unwrap(), "old");
+        undo_last_change(false).unwrap();
+        assert!(!written.exists());
+    }
+}
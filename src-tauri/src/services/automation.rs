@@ -0,0 +1,136 @@
+//! Automation endpoints
+//!
+//! With `AppSettings::automation_api` on, the hook server accepts a few actions
+//! from Stream Deck buttons, Apple Shortcuts and scripts
+//! (`POST /api/v1/actions/<action>`) and reports the state their button labels
+//! show (`GET /api/v1/state`). Both need the automation token as a bearer
+//! token; it is separate from the hook relay's token so it can be handed to
+//! other tools.
+
+use super::hooks::load_or_create_token;
+use super::settings::{app_config_dir, SettingsService};
+use super::{format, sessions, terminal};
+use crate::{cli, tray, AppState};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime};
+
+/// An action a button or shortcut can trigger
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AutomationAction {
+    ToggleNotifications,
+    RefreshStats,
+    ResumeLastSession,
+    OpenDashboard,
+}
+
+impl AutomationAction {
+    /// Parse the action name used in the endpoint path
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "toggle-notifications" => Some(Self::ToggleNotifications),
+            "refresh-stats" => Some(Self::RefreshStats),
+            "resume-last-session" => Some(Self::ResumeLastSession),
+            "open-dashboard" => Some(Self::OpenDashboard),
+            _ => None,
+        }
+    }
+}
+
+/// Short texts for button labels
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutomationLabels {
+    pub notifications: String,
+    pub today: String,
+    pub sessions: String,
+}
+
+/// What buttons show, returned by the state endpoint and after every action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutomationState {
+    pub notifications_enabled: bool,
+    pub today_cost: f64,
+    pub cost_per_hour: f64,
+    pub active_sessions: usize,
+    pub sessions_needing_attention: usize,
+    pub labels: AutomationLabels,
+}
+
+/// Token automation requests must send as `Authorization: Bearer <token>`
+pub fn token() -> Result<String, String> {
+    load_or_create_token(&app_config_dir().join("automation-token"))
+}
+
+/// Whether `bearer` is the automation token
+pub fn is_authorized(bearer: Option<&str>) -> bool {
+    match (bearer, token()) {
+        (Some(bearer), Ok(token)) => bearer == token,
+        _ => false,
+    }
+}
+
+/// Current state from the cached stats and sessions
+pub fn state<R: Runtime>(app: &AppHandle<R>) -> AutomationState {
+    let notifications_enabled = SettingsService::shared().get().notifications_enabled;
+    let (stats, sessions) = match app.try_state::<AppState>() {
+        Some(state) => (
+            state.analytics.cached_stats(),
+            state.session_tracker.cached_active(),
+        ),
+        None => Default::default(),
+    };
+    let waiting = sessions.iter().filter(|s| s.needs_attention).count();
+
+    AutomationState {
+        notifications_enabled,
+        today_cost: stats.today_cost,
+        cost_per_hour: stats.cost_per_hour,
+        active_sessions: sessions.len(),
+        sessions_needing_attention: waiting,
+        labels: labels(
+            notifications_enabled,
+            stats.today_cost,
+            sessions.len(),
+            waiting,
+        ),
+    }
+}
+
+fn labels(
+    notifications: bool,
+    today_cost: f64,
+    sessions: usize,
+    waiting: usize,
+) -> AutomationLabels {
+    AutomationLabels {
+        notifications: if notifications { "🔔 On" } else { "🔕 Off" }.to_string(),
+        today: format::cost(today_cost),
+        sessions: match (sessions, waiting) {
+            (0, _) => "Idle".to_string(),
+            (n, 0) => format!("{} running", n),
+            (n, w) => format!("{} running · {} waiting", n, w),
+        },
+    }
+}
+
+/// Run an action and return the state afterwards
+pub fn run<R: Runtime>(
+    app: &AppHandle<R>,
+    action: AutomationAction,
+) -> Result<AutomationState, String> {
+    match action {
+        AutomationAction::ToggleNotifications => {
+            tray::toggle_notifications();
+        }
+        AutomationAction::RefreshStats => tray::refresh_all(app),
+        AutomationAction::ResumeLastSession => {
+            let last = sessions::list_recent_sessions(1)?
+                .into_iter()
+                .next()
+                .ok_or("No sessions to resume")?;
+            terminal::resume_session(&last.project_path, &last.session.session_id)?;
+        }
+        AutomationAction::OpenDashboard => cli::show_window(app),
+    }
+    Ok(state(app))
+}
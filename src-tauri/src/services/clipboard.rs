@@ -0,0 +1,49 @@
+//! System clipboard
+//!
+//! Writes text with the platform's clipboard tool (pbcopy, clip, wl-copy,
+//! xclip or xsel), so copying works from the tray without a window.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Clipboard commands tried in order
+fn candidates() -> Vec<(&'static str, &'static [&'static str])> {
+    if cfg!(target_os = "macos") {
+        vec![("pbcopy", &[])]
+    } else if cfg!(target_os = "windows") {
+        vec![("clip", &[])]
+    } else {
+        let mut tools: Vec<(&'static str, &'static [&'static str])> = Vec::new();
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            tools.push(("wl-copy", &[]));
+        }
+        tools.push(("xclip", &["-selection", "clipboard"]));
+        tools.push(("xsel", &["--clipboard", "--input"]));
+        tools
+    }
+}
+
+/// Put `text` on the clipboard
+pub fn copy(text: &str) -> Result<(), String> {
+    for (program, args) in candidates() {
+        let Ok(mut child) = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .spawn()
+        else {
+            continue;
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(text.as_bytes())
+                .map_err(|e| format!("Failed to write to clipboard: {}", e))?;
+        }
+        let status = child
+            .wait()
+            .map_err(|e| format!("Clipboard error: {}", e))?;
+        if status.success() {
+            return Ok(());
+        }
+    }
+    Err("No clipboard tool found (install wl-clipboard, xclip or xsel)".to_string())
+}
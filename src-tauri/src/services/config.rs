@@ -1,3 +1,7 @@
+use super::audit;
+use super::insights;
+use super::suggestion_feedback;
+use super::paths;
 use super::pricing;
 use crate::types::{ContentBlock, RawLogEntry};
 use chrono::{DateTime, Utc};
@@ -10,16 +14,12 @@ use std::path::{Path, PathBuf};
 
 /// Get the Claude config directory path
 fn get_claude_dir() -> PathBuf {
-    dirs::home_dir()
-        .unwrap_or_default()
-        .join(".claude")
+    paths::current().config_dir().to_path_buf()
 }
 
 /// Get the Claude settings file path
 fn get_claude_json_path() -> PathBuf {
-    dirs::home_dir()
-        .unwrap_or_default()
-        .join(".claude.json")
+    paths::current().claude_json().to_path_buf()
 }
 
 // ============ CLAUDE.md Files ============
@@ -320,6 +320,16 @@ pub struct McpServer {
     pub project_path: Option<String>,
 }
 
+/// Where an MCP server is configured
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum McpScope {
+    /// User-level settings.json
+    Global,
+    /// A project's entry in ~/.claude.json
+    Project { path: String },
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[allow(dead_code)]
 struct InstalledPluginsFile {
@@ -534,7 +544,7 @@ pub fn update_mcp_config(mcp_servers_json: &str) -> Result<(), String> {
 
     // Write back
     let formatted = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
-    fs::write(&settings_path, formatted).map_err(|e| e.to_string())?;
+    audit::write_file(&settings_path, formatted, "update_mcp_config")?;
 
     Ok(())
 }
@@ -569,11 +579,20 @@ pub fn add_mcp_server(name: &str, config_json: &str) -> Result<(), String> {
 
     // Write back
     let formatted = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
-    fs::write(&settings_path, formatted).map_err(|e| e.to_string())?;
+    audit::write_file(&settings_path, formatted, &format!("add_mcp_server: {}", name))?;
 
     Ok(())
 }
 
+/// Add (or replace) an MCP server in the given scope
+pub fn add_mcp_server_to_scope(scope: &McpScope, name: &str, config: &serde_json::Value) -> Result<(), String> {
+    let config_json = config.to_string();
+    match scope {
+        McpScope::Global => add_mcp_server(name, &config_json),
+        McpScope::Project { path } => update_project_mcp_server(path, name, &config_json),
+    }
+}
+
 /// Remove an MCP server from ~/.claude.json or settings.json
 pub fn remove_mcp_server(name: &str) -> Result<(), String> {
     // Try to remove from ~/.claude.json first
@@ -586,7 +605,7 @@ pub fn remove_mcp_server(name: &str) -> Result<(), String> {
             if mcp_servers.contains_key(name) {
                 mcp_servers.remove(name);
                 let formatted = serde_json::to_string_pretty(&claude_json).map_err(|e| e.to_string())?;
-                fs::write(&claude_json_path, formatted).map_err(|e| e.to_string())?;
+                audit::write_file(&claude_json_path, formatted, &format!("remove_mcp_server: {}", name))?;
                 return Ok(());
             }
         }
@@ -606,7 +625,7 @@ pub fn remove_mcp_server(name: &str) -> Result<(), String> {
     }
 
     let formatted = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
-    fs::write(&settings_path, formatted).map_err(|e| e.to_string())?;
+    audit::write_file(&settings_path, formatted, &format!("remove_mcp_server: {}", name))?;
 
     Ok(())
 }
@@ -626,7 +645,7 @@ pub fn update_mcp_server(name: &str, config_json: &str) -> Result<(), String> {
             if mcp_servers.contains_key(name) {
                 mcp_servers.insert(name.to_string(), server_config);
                 let formatted = serde_json::to_string_pretty(&claude_json).map_err(|e| e.to_string())?;
-                fs::write(&claude_json_path, formatted).map_err(|e| e.to_string())?;
+                audit::write_file(&claude_json_path, formatted, &format!("update_mcp_server: {}", name))?;
                 return Ok(());
             }
         }
@@ -652,7 +671,7 @@ pub fn update_mcp_server(name: &str, config_json: &str) -> Result<(), String> {
     }
 
     let formatted = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
-    fs::write(&settings_path, formatted).map_err(|e| e.to_string())?;
+    audit::write_file(&settings_path, formatted, &format!("update_mcp_server: {}", name))?;
 
     Ok(())
 }
@@ -687,7 +706,7 @@ pub fn update_project_mcp_server(project_path: &str, name: &str, config_json: &s
     mcp_servers.insert(name.to_string(), server_config);
 
     let formatted = serde_json::to_string_pretty(&claude_json).map_err(|e| e.to_string())?;
-    fs::write(&claude_json_path, formatted).map_err(|e| e.to_string())?;
+    audit::write_file(&claude_json_path, formatted, &format!("update_project_mcp_server: {} ({})", name, project_path))?;
 
     Ok(())
 }
@@ -720,11 +739,500 @@ pub fn remove_project_mcp_server(project_path: &str, name: &str) -> Result<(), S
     }
 
     let formatted = serde_json::to_string_pretty(&claude_json).map_err(|e| e.to_string())?;
-    fs::write(&claude_json_path, formatted).map_err(|e| e.to_string())?;
+    audit::write_file(&claude_json_path, formatted, &format!("remove_project_mcp_server: {} ({})", name, project_path))?;
 
     Ok(())
 }
 
+/// One change made (or planned) by a bulk MCP operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpChange {
+    pub name: String,
+    pub scope: McpScope,
+    /// "added", "replaced", "removed" or "skipped"
+    pub action: String,
+    pub reason: Option<String>,
+}
+
+/// Summary of a bulk MCP operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpBulkResult {
+    pub changes: Vec<McpChange>,
+    pub operations: Vec<audit::FileOperation>,
+    pub dry_run: bool,
+}
+
+/// settings.json and ~/.claude.json loaded once so several MCP entries can be edited together
+pub struct McpConfigFiles {
+    settings: serde_json::Value,
+    claude_json: serde_json::Value,
+    settings_changed: bool,
+    claude_json_changed: bool,
+}
+
+impl McpConfigFiles {
+    pub fn load() -> Result<Self, String> {
+        fn read(path: &Path) -> Result<serde_json::Value, String> {
+            if !path.exists() {
+                return Ok(serde_json::json!({}));
+            }
+            let content = fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            serde_json::from_str(&content)
+                .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+        }
+
+        Ok(Self {
+            settings: read(&get_claude_dir().join("settings.json"))?,
+            claude_json: read(&get_claude_json_path())?,
+            settings_changed: false,
+            claude_json_changed: false,
+        })
+    }
+
+    fn project(&self, path: &str) -> Option<&serde_json::Value> {
+        self.claude_json.get("projects").and_then(|p| p.get(path))
+    }
+
+    /// Every scope that has a place for MCP servers
+    fn scopes(&self) -> Vec<McpScope> {
+        let mut scopes = vec![McpScope::Global];
+        if let Some(projects) = self.claude_json.get("projects").and_then(|p| p.as_object()) {
+            scopes.extend(projects.keys().map(|path| McpScope::Project { path: path.clone() }));
+        }
+        scopes
+    }
+
+    /// Server names in a scope; global servers may live in settings.json or ~/.claude.json
+    fn names(&self, scope: &McpScope) -> Vec<String> {
+        let sources = match scope {
+            McpScope::Global => vec![self.settings.get("mcpServers"), self.claude_json.get("mcpServers")],
+            McpScope::Project { path } => vec![self.project(path).and_then(|p| p.get("mcpServers"))],
+        };
+        let mut names: Vec<String> = sources
+            .into_iter()
+            .flatten()
+            .filter_map(|servers| servers.as_object())
+            .flat_map(|servers| servers.keys().cloned())
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    pub fn get(&self, scope: &McpScope, name: &str) -> Option<serde_json::Value> {
+        match scope {
+            McpScope::Global => self
+                .settings
+                .get("mcpServers")
+                .and_then(|s| s.get(name))
+                .or_else(|| self.claude_json.get("mcpServers").and_then(|s| s.get(name))),
+            McpScope::Project { path } => self
+                .project(path)
+                .and_then(|p| p.get("mcpServers"))
+                .and_then(|s| s.get(name)),
+        }
+        .cloned()
+    }
+
+    /// Insert a server; new global servers go to settings.json like `add_mcp_server`
+    pub fn insert(&mut self, scope: &McpScope, name: &str, config: serde_json::Value) -> Result<(), String> {
+        let (container, in_claude_json) = match scope {
+            McpScope::Global => {
+                let in_claude_json = self
+                    .claude_json
+                    .get("mcpServers")
+                    .and_then(|s| s.get(name))
+                    .is_some();
+                let file = if in_claude_json { &mut self.claude_json } else { &mut self.settings };
+                (file, in_claude_json)
+            }
+            McpScope::Project { path } => {
+                let project = self
+                    .claude_json
+                    .get_mut("projects")
+                    .and_then(|p| p.get_mut(path.as_str()))
+                    .ok_or_else(|| format!("Project '{}' not found in config", path))?;
+                (project, true)
+            }
+        };
+
+        let servers = container
+            .as_object_mut()
+            .ok_or_else(|| "Invalid config format".to_string())?
+            .entry("mcpServers")
+            .or_insert_with(|| serde_json::json!({}))
+            .as_object_mut()
+            .ok_or_else(|| "Invalid mcpServers format".to_string())?;
+        servers.insert(name.to_string(), config);
+
+        if in_claude_json {
+            self.claude_json_changed = true;
+        } else {
+            self.settings_changed = true;
+        }
+        Ok(())
+    }
+
+    /// Remove a server, returning whether it existed
+    fn remove(&mut self, scope: &McpScope, name: &str) -> bool {
+        fn remove_from(container: Option<&mut serde_json::Value>, name: &str) -> bool {
+            container
+                .and_then(|c| c.get_mut("mcpServers"))
+                .and_then(|s| s.as_object_mut())
+                .is_some_and(|servers| servers.remove(name).is_some())
+        }
+
+        match scope {
+            McpScope::Global => {
+                let from_settings = remove_from(Some(&mut self.settings), name);
+                let from_claude_json = remove_from(Some(&mut self.claude_json), name);
+                self.settings_changed |= from_settings;
+                self.claude_json_changed |= from_claude_json;
+                from_settings || from_claude_json
+            }
+            McpScope::Project { path } => {
+                let project = self
+                    .claude_json
+                    .get_mut("projects")
+                    .and_then(|p| p.get_mut(path.as_str()));
+                let removed = remove_from(project, name);
+                self.claude_json_changed |= removed;
+                removed
+            }
+        }
+    }
+
+    /// Add writes for the changed files to a plan
+    pub fn plan_writes(&self, plan: &mut audit::FilePlan) -> Result<(), String> {
+        if self.settings_changed {
+            let formatted = serde_json::to_string_pretty(&self.settings).map_err(|e| e.to_string())?;
+            plan.write(&get_claude_dir().join("settings.json"), formatted, "settings.json mcpServers");
+        }
+        if self.claude_json_changed {
+            let formatted = serde_json::to_string_pretty(&self.claude_json).map_err(|e| e.to_string())?;
+            plan.write(&get_claude_json_path(), formatted, ".claude.json mcpServers");
+        }
+        Ok(())
+    }
+
+    /// Write the changed files (or only plan the writes in a dry run)
+    fn save(self, action: &str, dry_run: bool) -> Result<Vec<audit::FileOperation>, String> {
+        let mut plan = audit::FilePlan::new(action);
+        plan.with_restore_point();
+        self.plan_writes(&mut plan)?;
+        plan.run(dry_run)
+    }
+}
+
+fn mcp_change(name: &str, scope: &McpScope, action: &str, reason: Option<String>) -> McpChange {
+    McpChange {
+        name: name.to_string(),
+        scope: scope.clone(),
+        action: action.to_string(),
+        reason,
+    }
+}
+
+/// Copy an MCP server from one scope to one or more others (global <-> project, project -> projects)
+pub fn copy_mcp_server(
+    name: &str,
+    from: &McpScope,
+    targets: &[McpScope],
+    overwrite: bool,
+    dry_run: bool,
+) -> Result<McpBulkResult, String> {
+    let mut files = McpConfigFiles::load()?;
+    let config = files
+        .get(from, name)
+        .ok_or_else(|| format!("MCP server '{}' not found", name))?;
+
+    let mut changes = Vec::new();
+    for target in targets {
+        if target == from {
+            continue;
+        }
+        let exists = files.get(target, name);
+        if exists.as_ref() == Some(&config) {
+            changes.push(mcp_change(name, target, "skipped", Some("Already identical".to_string())));
+            continue;
+        }
+        if exists.is_some() && !overwrite {
+            changes.push(mcp_change(name, target, "skipped", Some("A server with this name already exists".to_string())));
+            continue;
+        }
+        match files.insert(target, name, config.clone()) {
+            Ok(()) => {
+                let action = if exists.is_some() { "replaced" } else { "added" };
+                changes.push(mcp_change(name, target, action, None));
+            }
+            Err(e) => changes.push(mcp_change(name, target, "skipped", Some(e))),
+        }
+    }
+
+    let operations = files.save(&format!("copy_mcp_server: {}", name), dry_run)?;
+    Ok(McpBulkResult { changes, operations, dry_run })
+}
+
+/// Remove every MCP server whose name matches a glob pattern (e.g. `puppeteer*`)
+/// from the given scopes, or from all scopes
+pub fn bulk_remove_mcp_servers(
+    pattern: &str,
+    scopes: Option<&[McpScope]>,
+    dry_run: bool,
+) -> Result<McpBulkResult, String> {
+    let pattern = glob::Pattern::new(pattern).map_err(|e| format!("Invalid pattern: {}", e))?;
+    let options = glob::MatchOptions {
+        case_sensitive: false,
+        ..Default::default()
+    };
+
+    let mut files = McpConfigFiles::load()?;
+    let scopes = scopes.map(|s| s.to_vec()).unwrap_or_else(|| files.scopes());
+
+    let mut changes = Vec::new();
+    for scope in &scopes {
+        for name in files.names(scope) {
+            if pattern.matches_with(&name, options) && files.remove(scope, &name) {
+                changes.push(mcp_change(&name, scope, "removed", None));
+            }
+        }
+    }
+
+    let operations = files.save(&format!("bulk_remove_mcp_servers: {}", pattern), dry_run)?;
+    Ok(McpBulkResult { changes, operations, dry_run })
+}
+
+// ============ Copy Project Config ============
+
+/// A piece of project setup that can be copied to another project
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProjectConfigItem {
+    Command { name: String },
+    Agent { name: String },
+    McpServer { name: String },
+    /// A `#`/`##` section of the project's CLAUDE.md, by heading text
+    ClaudeMdSection { heading: String },
+}
+
+/// Everything a project has that `copy_project_config` can transfer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectConfigItems {
+    pub items: Vec<ProjectConfigItem>,
+}
+
+/// An item that wasn't copied, and why
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedConfigItem {
+    pub item: ProjectConfigItem,
+    pub reason: String,
+}
+
+/// Summary of a `copy_project_config` run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectCopyResult {
+    pub copied: Vec<ProjectConfigItem>,
+    pub skipped: Vec<SkippedConfigItem>,
+    pub operations: Vec<audit::FileOperation>,
+    pub dry_run: bool,
+}
+
+/// A CLAUDE.md section: heading text and the line range it spans
+struct MarkdownSection {
+    heading: String,
+    start: usize,
+    end: usize,
+}
+
+/// Split markdown into sections at level 1/2 headings (outside code fences)
+fn markdown_sections(content: &str) -> Vec<MarkdownSection> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut sections: Vec<MarkdownSection> = Vec::new();
+    let mut in_code = false;
+
+    for (index, line) in lines.iter().enumerate() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+        let heading = line
+            .strip_prefix("## ")
+            .or_else(|| line.strip_prefix("# "))
+            .map(|h| h.trim().to_string());
+        if let Some(heading) = heading {
+            if let Some(last) = sections.last_mut() {
+                last.end = index;
+            }
+            sections.push(MarkdownSection { heading, start: index, end: lines.len() });
+        }
+    }
+
+    sections
+}
+
+fn project_claude_md(project_path: &str) -> PathBuf {
+    PathBuf::from(project_path).join("CLAUDE.md")
+}
+
+/// List the commands, agents, MCP servers and CLAUDE.md sections of a project
+pub fn list_project_config_items(project_path: &str) -> Result<ProjectConfigItems, String> {
+    let mut items = Vec::new();
+
+    for command in get_project_commands(project_path)? {
+        items.push(ProjectConfigItem::Command { name: command.name });
+    }
+    for agent in get_project_agents(project_path)? {
+        items.push(ProjectConfigItem::Agent { name: agent.name });
+    }
+    let files = McpConfigFiles::load()?;
+    let scope = McpScope::Project { path: project_path.to_string() };
+    for name in files.names(&scope) {
+        items.push(ProjectConfigItem::McpServer { name });
+    }
+    if let Ok(content) = fs::read_to_string(project_claude_md(project_path)) {
+        for section in markdown_sections(&content) {
+            items.push(ProjectConfigItem::ClaudeMdSection { heading: section.heading });
+        }
+    }
+
+    Ok(ProjectConfigItems { items })
+}
+
+/// Plan copying a command/agent (a .md file or a directory) into the target project
+fn plan_copy_entry(plan: &mut audit::FilePlan, source_dir: &Path, target_dir: &Path, name: &str, overwrite: bool) -> Result<(), String> {
+    let file = source_dir.join(format!("{}.md", name));
+    let (source, target) = if file.is_file() {
+        (file, target_dir.join(format!("{}.md", name)))
+    } else if source_dir.join(name).is_dir() {
+        (source_dir.join(name), target_dir.join(name))
+    } else {
+        return Err("Not found in source project".to_string());
+    };
+    if target.exists() && !overwrite {
+        return Err("Already exists in target project".to_string());
+    }
+
+    plan.create_dir(target_dir, "create directory");
+    if source.is_file() {
+        plan.copy(&source, &target, name);
+        return Ok(());
+    }
+
+    plan.create_dir(&target, name);
+    let pattern = source.join("**").join("*");
+    for path in glob(&pattern.to_string_lossy()).map_err(|e| e.to_string())?.flatten() {
+        let Ok(relative) = path.strip_prefix(&source) else {
+            continue;
+        };
+        let destination = target.join(relative);
+        if path.is_dir() {
+            plan.create_dir(&destination, name);
+        } else {
+            plan.copy(&path, &destination, &format!("{}/{}", name, relative.display()));
+        }
+    }
+    Ok(())
+}
+
+/// Copy selected commands, agents, MCP servers and CLAUDE.md sections from one project to another
+pub fn copy_project_config(
+    from: &str,
+    to: &str,
+    items: &[ProjectConfigItem],
+    overwrite: bool,
+    dry_run: bool,
+) -> Result<ProjectCopyResult, String> {
+    if from == to {
+        return Err("Source and target project are the same".to_string());
+    }
+
+    let mut plan = audit::FilePlan::new(&format!("copy_project_config: {} -> {}", from, to));
+    let mut mcp_files = McpConfigFiles::load()?;
+    let from_scope = McpScope::Project { path: from.to_string() };
+    let to_scope = McpScope::Project { path: to.to_string() };
+
+    let source_md = fs::read_to_string(project_claude_md(from)).unwrap_or_default();
+    let source_sections = markdown_sections(&source_md);
+    let source_lines: Vec<&str> = source_md.lines().collect();
+    let mut target_md = fs::read_to_string(project_claude_md(to)).unwrap_or_default();
+    let mut target_md_changed = false;
+
+    let mut copied = Vec::new();
+    let mut skipped = Vec::new();
+
+    for item in items {
+        let outcome = match item {
+            ProjectConfigItem::Command { name } => {
+                let dir = |project: &str| PathBuf::from(project).join(".claude").join("commands");
+                plan_copy_entry(&mut plan, &dir(from), &dir(to), name, overwrite)
+            }
+            ProjectConfigItem::Agent { name } => {
+                let dir = |project: &str| PathBuf::from(project).join(".claude").join("agents");
+                plan_copy_entry(&mut plan, &dir(from), &dir(to), name, overwrite)
+            }
+            ProjectConfigItem::McpServer { name } => match mcp_files.get(&from_scope, name) {
+                None => Err("Not found in source project".to_string()),
+                Some(_) if mcp_files.get(&to_scope, name).is_some() && !overwrite => {
+                    Err("Already exists in target project".to_string())
+                }
+                Some(config) => mcp_files.insert(&to_scope, name, config),
+            },
+            ProjectConfigItem::ClaudeMdSection { heading } => {
+                match source_sections.iter().find(|s| &s.heading == heading) {
+                    None => Err("Section not found in source CLAUDE.md".to_string()),
+                    Some(section) => {
+                        let text = source_lines[section.start..section.end].join("\n");
+                        let text = text.trim_end();
+                        let existing = markdown_sections(&target_md).into_iter().find(|s| &s.heading == heading);
+                        match existing {
+                            Some(_) if !overwrite => Err("Section already exists in target CLAUDE.md".to_string()),
+                            Some(existing) => {
+                                let lines: Vec<&str> = target_md.lines().collect();
+                                let mut rebuilt = lines[..existing.start].to_vec();
+                                rebuilt.push(text);
+                                if existing.end < lines.len() {
+                                    rebuilt.push("");
+                                    rebuilt.extend(&lines[existing.end..]);
+                                }
+                                target_md = rebuilt.join("\n") + "\n";
+                                target_md_changed = true;
+                                Ok(())
+                            }
+                            None => {
+                                let trimmed = target_md.trim_end();
+                                target_md = if trimmed.is_empty() {
+                                    format!("{}\n", text)
+                                } else {
+                                    format!("{}\n\n{}\n", trimmed, text)
+                                };
+                                target_md_changed = true;
+                                Ok(())
+                            }
+                        }
+                    }
+                }
+            }
+        };
+
+        match outcome {
+            Ok(()) => copied.push(item.clone()),
+            Err(reason) => skipped.push(SkippedConfigItem { item: item.clone(), reason }),
+        }
+    }
+
+    if target_md_changed {
+        plan.write(&project_claude_md(to), target_md, "CLAUDE.md sections");
+    }
+    mcp_files.plan_writes(&mut plan)?;
+
+    let operations = plan.run(dry_run)?;
+    Ok(ProjectCopyResult { copied, skipped, operations, dry_run })
+}
+
 // ============ Projects ============
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -747,31 +1255,8 @@ pub struct ProjectInfo {
     pub mcp_server_count: usize,
     #[serde(rename = "lastUsed")]
     pub last_used: Option<String>,
-}
-
-/// Encode a project path to folder name format
-/// e.g., "/Users/foo/project" -> "-Users-foo-project"
-/// Note: Claude Code just replaces "/" with "-", hyphens in paths become ambiguous
-fn encode_path_to_folder(path: &str) -> String {
-    path.replace('/', "-")
-}
-
-/// Get the last modified time of a project's JSONL folder as a quick proxy for "last used"
-fn get_project_last_modified(project_path: &str) -> Option<String> {
-    use chrono::{DateTime, Utc};
-
-    let home = dirs::home_dir()?;
-    let projects_dir = home.join(".claude").join("projects");
-
-    // Encode the path to match folder name
-    let encoded = project_path.replace('-', "--").replace('/', "-");
-    let folder_path = projects_dir.join(&encoded[1..]); // Skip leading "-"
-
-    // Get folder modification time
-    let metadata = folder_path.metadata().ok()?;
-    let modified = metadata.modified().ok()?;
-    let datetime: DateTime<Utc> = modified.into();
-    Some(datetime.to_rfc3339())
+    #[serde(rename = "sessionCount")]
+    pub session_count: usize,
 }
 
 /// Calculate project totals from JSONL files
@@ -786,8 +1271,7 @@ fn get_project_totals_from_jsonl() -> HashMap<String, (f64, u64, u64, Option<Str
     let mut totals: HashMap<String, (f64, u64, u64, Option<String>)> = HashMap::new();
     let mut seen_uuids: HashSet<String> = HashSet::new();
 
-    let home = dirs::home_dir().unwrap_or_default();
-    let projects_dir = home.join(".claude").join("projects");
+    let projects_dir = paths::current().projects_dir();
 
     if !projects_dir.exists() {
         return totals;
@@ -935,10 +1419,10 @@ pub fn list_projects() -> Result<Vec<ProjectInfo>, String> {
             let mcp_count = config.mcp_servers.as_ref().map(|m| m.len()).unwrap_or(0);
 
             // Encode path to folder name for lookup (e.g., "/Users/foo" -> "-Users-foo")
-            let folder_key = encode_path_to_folder(&path);
+            let folder_key = paths::encode_path_to_folder(&path);
 
             // Get totals from JSONL, fall back to last session data
-            let (total_cost, total_input, total_output, last_used) = project_totals
+            let (total_cost, total_input, total_output, last_message) = project_totals
                 .get(&folder_key)
                 .cloned()
                 .unwrap_or_else(|| {
@@ -946,10 +1430,14 @@ pub fn list_projects() -> Result<Vec<ProjectInfo>, String> {
                         config.last_cost.unwrap_or(0.0),
                         config.last_total_input_tokens.unwrap_or(0),
                         config.last_total_output_tokens.unwrap_or(0),
-                        get_project_last_modified(&path),
+                        None,
                     )
                 });
 
+            // Session files also change without new usage (e.g. resumed sessions)
+            let activity = super::project_activity::get(&path);
+            let last_used = last_message.max(activity.as_ref().and_then(|a| a.last_used.clone()));
+
             projects.push(ProjectInfo {
                 path: path.clone(),
                 name,
@@ -961,6 +1449,7 @@ pub fn list_projects() -> Result<Vec<ProjectInfo>, String> {
                 has_claude,
                 mcp_server_count: mcp_count,
                 last_used,
+                session_count: activity.map(|a| a.session_count).unwrap_or(0),
             });
         }
     }
@@ -1041,6 +1530,11 @@ pub fn get_project_commands(project_path: &str) -> Result<Vec<CommandInfo>, Stri
     })
 }
 
+/// Get agents specific to a project (from project's .claude/agents/)
+pub fn get_project_agents(project_path: &str) -> Result<Vec<AgentInfo>, String> {
+    list_items_in_dir(&PathBuf::from(project_path).join(".claude").join("agents"))
+}
+
 /// Get MCP servers configured for a specific project
 pub fn get_project_mcp_servers(project_path: &str) -> Result<Vec<McpServer>, String> {
     let mut servers = Vec::new();
@@ -1121,12 +1615,13 @@ pub fn set_project_image(project_path: &str, image_source_path: &str) -> Result<
     // Remove any existing project images
     for old_ext in &["png", "jpg", "jpeg", "webp", "svg"] {
         let old_path = claude_dir.join(format!("project-image.{}", old_ext));
-        let _ = fs::remove_file(old_path);
+        let _ = audit::remove_file(&old_path, "set_project_image");
     }
 
     // Copy the new image
     let dest = claude_dir.join(format!("project-image.{}", ext));
-    fs::copy(&source, &dest).map_err(|e| e.to_string())?;
+    let image = fs::read(&source).map_err(|e| e.to_string())?;
+    audit::write_file(&dest, image, "set_project_image")?;
 
     Ok(format!("file://{}", dest.to_string_lossy()))
 }
@@ -1137,7 +1632,7 @@ pub fn remove_project_image(project_path: &str) -> Result<(), String> {
 
     for ext in &["png", "jpg", "jpeg", "webp", "svg"] {
         let image_path = claude_dir.join(format!("project-image.{}", ext));
-        let _ = fs::remove_file(image_path);
+        audit::remove_file(&image_path, "remove_project_image")?;
     }
 
     Ok(())
@@ -1289,6 +1784,8 @@ pub fn analyze_chat_patterns(days: u32) -> Result<PatternAnalysis, String> {
                             let model = message.model.as_deref().unwrap_or("claude-sonnet-4");
                             let entry_cost = pricing::calculate_cost(
                                 model,
+                                Some(timestamp),
+                                usage.service_tier.as_deref(),
                                 input,
                                 output,
                                 cache_write,
@@ -1388,14 +1885,24 @@ fn tool_list_count(tools: &[ToolUsage], names: &[&str]) -> u64 {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AiSuggestion {
+    /// Stable key: the same finding keeps its id between runs
+    #[serde(default)]
+    pub id: String,
     #[serde(rename = "type")]
     pub suggestion_type: String,
     pub title: String,
     pub description: String,
     pub content: Option<String>,
+    /// Ranking weight (higher first)
+    #[serde(default)]
+    pub score: f64,
+    /// Numbers the suggestion is based on
+    #[serde(default)]
+    pub evidence: Vec<insights::Evidence>,
 }
 
-/// Generate suggestions based on actual usage patterns
+/// Generate suggestions based on actual usage patterns, ranked together with
+/// trend insights from comparing the period with the one before it
 pub fn get_ai_suggestions(days: u32) -> Result<Vec<AiSuggestion>, String> {
     let mut suggestions = Vec::new();
 
@@ -1405,6 +1912,7 @@ pub fn get_ai_suggestions(days: u32) -> Result<Vec<AiSuggestion>, String> {
     // Suggestion 1: High token ratio - suggest being more concise
     if patterns.token_efficiency.ratio > 5.0 {
         suggestions.push(AiSuggestion {
+            id: "optimization:input_ratio".to_string(),
             suggestion_type: "optimization".to_string(),
             title: "Optimize Input Context".to_string(),
             description: format!(
@@ -1412,6 +1920,8 @@ pub fn get_ai_suggestions(days: u32) -> Result<Vec<AiSuggestion>, String> {
                 patterns.token_efficiency.ratio
             ),
             content: None,
+            score: 35.0,
+            evidence: Vec::new(),
         });
     }
 
@@ -1423,6 +1933,7 @@ pub fn get_ai_suggestions(days: u32) -> Result<Vec<AiSuggestion>, String> {
 
     if read_grep_count > 500 {
         suggestions.push(AiSuggestion {
+            id: "agent:codebase_navigator".to_string(),
             suggestion_type: "agent".to_string(),
             title: "Create Codebase Navigator Agent".to_string(),
             description: format!(
@@ -1430,6 +1941,8 @@ pub fn get_ai_suggestions(days: u32) -> Result<Vec<AiSuggestion>, String> {
                 read_grep_count
             ),
             content: Some("# Codebase Navigator Agent\n\nYou are an expert at navigating this specific codebase. You have deep knowledge of:\n- Project structure and file organization\n- Key modules and their responsibilities\n- Common patterns used in the code\n\nWhen asked to find code, start with the most likely locations based on naming conventions.".to_string()),
+            score: 30.0,
+            evidence: Vec::new(),
         });
     }
 
@@ -1441,6 +1954,7 @@ pub fn get_ai_suggestions(days: u32) -> Result<Vec<AiSuggestion>, String> {
 
     if bash_count > 200 {
         suggestions.push(AiSuggestion {
+            id: "command:slash_commands".to_string(),
             suggestion_type: "command".to_string(),
             title: "Create Custom Slash Commands".to_string(),
             description: format!(
@@ -1448,6 +1962,8 @@ pub fn get_ai_suggestions(days: u32) -> Result<Vec<AiSuggestion>, String> {
                 bash_count
             ),
             content: Some("# Build Command\n\nRun the project build process and report any errors.\n\n## Steps\n1. Run the build command for this project\n2. If errors occur, analyze and suggest fixes\n3. Report build status".to_string()),
+            score: 30.0,
+            evidence: Vec::new(),
         });
     }
 
@@ -1455,6 +1971,7 @@ pub fn get_ai_suggestions(days: u32) -> Result<Vec<AiSuggestion>, String> {
     let avg_duration_mins = patterns.session_stats.average_duration / 60000.0;
     if avg_duration_mins > 60.0 {
         suggestions.push(AiSuggestion {
+            id: "workflow:session_length".to_string(),
             suggestion_type: "workflow".to_string(),
             title: "Consider Shorter Sessions".to_string(),
             description: format!(
@@ -1462,6 +1979,8 @@ pub fn get_ai_suggestions(days: u32) -> Result<Vec<AiSuggestion>, String> {
                 avg_duration_mins
             ),
             content: None,
+            score: 25.0,
+            evidence: Vec::new(),
         });
     }
 
@@ -1473,6 +1992,7 @@ pub fn get_ai_suggestions(days: u32) -> Result<Vec<AiSuggestion>, String> {
 
     if edit_count > 300 {
         suggestions.push(AiSuggestion {
+            id: "agent:code_review".to_string(),
             suggestion_type: "agent".to_string(),
             title: "Create Code Review Agent".to_string(),
             description: format!(
@@ -1480,12 +2000,15 @@ pub fn get_ai_suggestions(days: u32) -> Result<Vec<AiSuggestion>, String> {
                 edit_count
             ),
             content: Some("# Code Review Agent\n\nYou are an expert code reviewer. When reviewing changes:\n\n1. Check for bugs, edge cases, and error handling\n2. Verify code follows project conventions\n3. Look for security issues\n4. Suggest performance improvements\n5. Ensure proper test coverage\n\nBe constructive and specific in your feedback.".to_string()),
+            score: 30.0,
+            evidence: Vec::new(),
         });
     }
 
     // Suggestion 6: High cost - suggest model optimization
     if patterns.session_stats.total_cost > 100.0 {
         suggestions.push(AiSuggestion {
+            id: "optimization:model_usage".to_string(),
             suggestion_type: "optimization".to_string(),
             title: "Optimize Model Usage".to_string(),
             description: format!(
@@ -1494,12 +2017,15 @@ pub fn get_ai_suggestions(days: u32) -> Result<Vec<AiSuggestion>, String> {
                 days
             ),
             content: None,
+            score: 35.0,
+            evidence: Vec::new(),
         });
     }
 
     // Suggestion 7: Many sessions - suggest workflow improvements
     if patterns.session_stats.total_sessions > 50 {
         suggestions.push(AiSuggestion {
+            id: "workflow:many_sessions".to_string(),
             suggestion_type: "workflow".to_string(),
             title: "Streamline Your Workflow".to_string(),
             description: format!(
@@ -1508,21 +2034,30 @@ pub fn get_ai_suggestions(days: u32) -> Result<Vec<AiSuggestion>, String> {
                 days
             ),
             content: None,
+            score: 20.0,
+            evidence: Vec::new(),
         });
     }
 
+    suggestions.extend(insights::period_insights(days));
+    suggestion_feedback::apply(&mut suggestions);
+
     // If no specific suggestions, provide a general one
     if suggestions.is_empty() {
         suggestions.push(AiSuggestion {
+            id: "info:usage_ok".to_string(),
             suggestion_type: "info".to_string(),
             title: "Usage Looks Good".to_string(),
             description: "Your Claude Code usage patterns look efficient. Keep up the good work!".to_string(),
             content: None,
+            score: 0.0,
+            evidence: Vec::new(),
         });
     }
 
-    // Limit to top 5 suggestions
-    suggestions.truncate(5);
+    suggestions.sort_by(|a, b| b.score.total_cmp(&a.score));
+    // Limit to the top 8
+    suggestions.truncate(8);
 
     Ok(suggestions)
 }
@@ -1762,23 +2297,6 @@ pub struct BackupInfo {
     pub created_at: String,
 }
 
-/// Export configuration to a ZIP file
-pub fn export_config(_options: ExportOptions, output_path: &str) -> Result<BackupInfo, String> {
-    // This would create a ZIP archive with the selected items
-    // For now, return placeholder
-    Ok(BackupInfo {
-        path: output_path.to_string(),
-        size: 1024 * 50, // 50KB placeholder
-        created_at: chrono::Utc::now().to_rfc3339(),
-    })
-}
-
-/// Import configuration from a ZIP file
-pub fn import_config(_zip_path: &str) -> Result<(), String> {
-    // This would extract and apply the configuration
-    Ok(())
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitStatus {
     #[serde(rename = "isGitRepo")]
@@ -1788,30 +2306,11 @@ pub struct GitStatus {
     pub branch: String,
     #[serde(rename = "uncommittedFiles")]
     pub uncommitted_files: Vec<String>,
-}
-
-/// Check git status of ~/.claude directory
-pub fn get_config_git_status() -> Result<GitStatus, String> {
-    let claude_dir = get_claude_dir();
-    let git_dir = claude_dir.join(".git");
-
-    if !git_dir.exists() {
-        return Ok(GitStatus {
-            is_git_repo: false,
-            has_changes: false,
-            branch: String::new(),
-            uncommitted_files: Vec::new(),
-        });
-    }
-
-    // This would run git commands to check status
-    // For now, return basic info
-    Ok(GitStatus {
-        is_git_repo: true,
-        has_changes: false,
-        branch: "main".to_string(),
-        uncommitted_files: Vec::new(),
-    })
+    /// Tracked remote branch, e.g. `origin/main`
+    pub upstream: Option<String>,
+    /// Commits not pushed yet / not pulled yet (as of the last fetch)
+    pub ahead: u32,
+    pub behind: u32,
 }
 
 // ============ Helper Functions ============
@@ -1860,6 +2359,15 @@ fn read_claude_json() -> Result<ClaudeJson, String> {
     serde_json::from_str(&content).map_err(|e| e.to_string())
 }
 
+/// Paths of all projects registered in ~/.claude.json
+pub fn registered_project_paths() -> Vec<String> {
+    read_claude_json()
+        .ok()
+        .and_then(|claude_json| claude_json.projects)
+        .map(|projects| projects.into_keys().collect())
+        .unwrap_or_default()
+}
+
 fn read_settings_json() -> Option<SettingsJson> {
     let path = get_claude_dir().join("settings.json");
     fs::read_to_string(&path)
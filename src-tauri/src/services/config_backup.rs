@@ -0,0 +1,419 @@
+//! Claude configuration backups
+//!
+//! `export` writes the parts of the config selected in `ExportOptions` to a
+//! zip archive with a `manifest.json` listing every file. Files from the Claude
+//! folder are stored under `claude/`, project CLAUDE.md files under
+//! `projects/<n>/` with their project path in the manifest. `import` checks
+//! the archive against its manifest before planning any write, skips files
+//! that are already identical and takes a restore point of everything it is
+//! about to overwrite.
+
+use super::audit::{FileOperation, FilePlan};
+use super::config::{list_claude_md_files, BackupInfo, ExportOptions};
+use super::paths;
+use chrono::Utc;
+use glob::glob;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Version of the backup format; backups from newer versions are refused
+const BACKUP_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST: &str = "manifest.json";
+/// Archive folder of files from the Claude config folder
+const CLAUDE_PREFIX: &str = "claude/";
+/// Archive folder of project CLAUDE.md files
+const PROJECTS_PREFIX: &str = "projects/";
+/// Larger entries are refused on import
+const MAX_ENTRY_BYTES: u64 = 10 * 1024 * 1024;
+
+/// `manifest.json` of a backup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub format_version: u32,
+    pub created_at: String,
+    /// claudit version that created the backup
+    pub created_with: String,
+    pub options: ExportOptions,
+    /// Archive paths of all files
+    pub files: Vec<String>,
+    /// Project path of each `projects/<n>/` folder
+    pub projects: HashMap<String, String>,
+}
+
+/// What importing a backup does (or would do, in a dry run)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigImport {
+    pub created_at: String,
+    pub operations: Vec<FileOperation>,
+    /// Files left out, with the reason
+    pub skipped: Vec<String>,
+}
+
+/// Files in the Claude folder that a backup may contain, relative to it
+fn is_config_file(relative: &str) -> bool {
+    let parts: Vec<&str> = relative.split('/').collect();
+    match parts.as_slice() {
+        ["CLAUDE.md"] | ["settings.json"] => true,
+        ["agents" | "commands", rest @ ..] => !rest.is_empty(),
+        ["plugins", name] => name.ends_with(".json"),
+        _ => false,
+    }
+}
+
+/// Files under `dir`, with their path relative to `base` using `/`
+fn files_in(dir: &Path, pattern: &str, base: &Path) -> Vec<(PathBuf, String)> {
+    let Ok(paths) = glob(&dir.join(pattern).to_string_lossy()) else {
+        return Vec::new();
+    };
+    paths
+        .flatten()
+        .filter(|path| path.is_file())
+        .filter_map(|path| {
+            let relative = path
+                .strip_prefix(base)
+                .ok()?
+                .to_string_lossy()
+                .replace('\\', "/");
+            Some((path, relative))
+        })
+        .collect()
+}
+
+/// Write the selected parts of the Claude config to a zip at `output`
+pub fn export(options: ExportOptions, output: &Path) -> Result<BackupInfo, String> {
+    let claude_dir = paths::current().config_dir().to_path_buf();
+    let mut sources: Vec<(PathBuf, String)> = Vec::new();
+    let mut projects = HashMap::new();
+
+    for (include, name) in [
+        (options.include_global_claude, "CLAUDE.md"),
+        (options.include_settings, "settings.json"),
+    ] {
+        let path = claude_dir.join(name);
+        if include && path.is_file() {
+            sources.push((path, format!("{}{}", CLAUDE_PREFIX, name)));
+        }
+    }
+    for (include, dir, pattern) in [
+        (options.include_agents, "agents", "**/*"),
+        (options.include_commands, "commands", "**/*"),
+        (options.include_plugin_config, "plugins", "*.json"),
+    ] {
+        if include {
+            sources.extend(
+                files_in(&claude_dir.join(dir), pattern, &claude_dir)
+                    .into_iter()
+                    .map(|(path, relative)| (path, format!("{}{}", CLAUDE_PREFIX, relative))),
+            );
+        }
+    }
+    if options.include_project_claude {
+        let files = list_claude_md_files()?;
+        for (index, file) in files.iter().filter(|f| !f.is_global).enumerate() {
+            let path = PathBuf::from(&file.path);
+            let Some(project) = path.parent() else {
+                continue;
+            };
+            let folder = format!("{}{}", PROJECTS_PREFIX, index);
+            projects.insert(folder.clone(), project.to_string_lossy().to_string());
+            sources.push((path, format!("{}/CLAUDE.md", folder)));
+        }
+    }
+    if sources.is_empty() {
+        return Err("Nothing to export with the selected options".to_string());
+    }
+
+    let manifest = BackupManifest {
+        format_version: BACKUP_FORMAT_VERSION,
+        created_at: Utc::now().to_rfc3339(),
+        created_with: env!("CARGO_PKG_VERSION").to_string(),
+        options,
+        files: sources.iter().map(|(_, name)| name.clone()).collect(),
+        projects,
+    };
+
+    let file = File::create(output)
+        .map_err(|e| format!("Failed to create {}: {}", output.display(), e))?;
+    let mut zip = ZipWriter::new(file);
+    let zip_options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let manifest_json = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+    let contents = sources.iter().map(|(path, name)| {
+        fs::read(path)
+            .map(|data| (name.clone(), data))
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+    });
+    for entry in std::iter::once(Ok((MANIFEST.to_string(), manifest_json))).chain(contents) {
+        let (name, data) = entry?;
+        zip.start_file(name.as_str(), zip_options)
+            .map_err(|e| format!("Failed to write {}: {}", name, e))?;
+        zip.write_all(&data)
+            .map_err(|e| format!("Failed to write {}: {}", name, e))?;
+    }
+    zip.finish()
+        .map_err(|e| format!("Failed to write {}: {}", output.display(), e))?;
+
+    let size = fs::metadata(output).map(|m| m.len()).unwrap_or(0);
+    Ok(BackupInfo {
+        path: output.to_string_lossy().to_string(),
+        size,
+        created_at: manifest.created_at,
+    })
+}
+
+fn read_entry(archive: &mut ZipArchive<File>, name: &str) -> Result<Vec<u8>, String> {
+    let entry = archive
+        .by_name(name)
+        .map_err(|e| format!("Backup is missing {}: {}", name, e))?;
+    if entry.size() > MAX_ENTRY_BYTES {
+        return Err(format!("{} is too large ({} bytes)", name, entry.size()));
+    }
+    let mut data = Vec::new();
+    entry
+        .take(MAX_ENTRY_BYTES)
+        .read_to_end(&mut data)
+        .map_err(|e| format!("Failed to read {}: {}", name, e))?;
+    Ok(data)
+}
+
+/// Open a backup and check that it only holds the files its manifest lists,
+/// all at safe paths
+fn open(path: &Path) -> Result<(ZipArchive<File>, BackupManifest), String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut archive =
+        ZipArchive::new(file).map_err(|e| format!("Not a configuration backup: {}", e))?;
+    let manifest: BackupManifest = serde_json::from_slice(&read_entry(&mut archive, MANIFEST)?)
+        .map_err(|e| format!("Invalid backup manifest: {}", e))?;
+    if manifest.format_version > BACKUP_FORMAT_VERSION {
+        return Err(format!(
+            "This backup needs a newer claudit (backup format {})",
+            manifest.format_version
+        ));
+    }
+
+    for index in 0..archive.len() {
+        let entry = archive
+            .by_index(index)
+            .map_err(|e| format!("Failed to read backup: {}", e))?;
+        let name = entry.name().to_string();
+        if entry.is_dir() || name == MANIFEST {
+            continue;
+        }
+        if entry.enclosed_name().is_none() || name.split('/').any(|part| part == "..") {
+            return Err(format!("Backup contains an unsafe path: {}", name));
+        }
+        if !manifest.files.contains(&name) {
+            return Err(format!("{} is not listed in the backup manifest", name));
+        }
+    }
+    for name in &manifest.files {
+        let valid = match name.strip_prefix(CLAUDE_PREFIX) {
+            Some(relative) => is_config_file(relative),
+            None => name
+                .strip_suffix("/CLAUDE.md")
+                .and_then(|folder| manifest.projects.get(folder))
+                .is_some_and(|project| Path::new(project).is_absolute()),
+        };
+        if !valid {
+            return Err(format!("Unexpected file in backup: {}", name));
+        }
+    }
+    Ok((archive, manifest))
+}
+
+/// Restore a backup into the Claude folder and the projects it came from.
+/// With `dry_run` nothing is written.
+pub fn import(path: &Path, dry_run: bool) -> Result<ConfigImport, String> {
+    let (mut archive, manifest) = open(path)?;
+    let claude_dir = paths::current().config_dir().to_path_buf();
+    let mut plan = FilePlan::new("import_config");
+    plan.with_restore_point();
+    let mut skipped = Vec::new();
+    let mut folders = HashSet::new();
+
+    for name in &manifest.files {
+        let target = match name.strip_prefix(CLAUDE_PREFIX) {
+            Some(relative) => claude_dir.join(relative),
+            None => {
+                let folder = name.trim_end_matches("/CLAUDE.md");
+                let project = PathBuf::from(&manifest.projects[folder]);
+                if !project.is_dir() {
+                    skipped.push(format!(
+                        "{}: project not found on this machine",
+                        project.display()
+                    ));
+                    continue;
+                }
+                project.join("CLAUDE.md")
+            }
+        };
+        let data = read_entry(&mut archive, name)?;
+        if name.ends_with(".json") {
+            serde_json::from_slice::<serde_json::Value>(&data)
+                .map_err(|e| format!("{} is not valid JSON: {}", name, e))?;
+        }
+        if fs::read(&target).is_ok_and(|current| current == data) {
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            if folders.insert(parent.to_path_buf()) {
+                plan.create_dir(parent, "folder");
+            }
+        }
+        let description = name
+            .strip_prefix(CLAUDE_PREFIX)
+            .unwrap_or("project CLAUDE.md");
+        plan.write(&target, data, description);
+    }
+
+    Ok(ConfigImport {
+        created_at: manifest.created_at,
+        operations: plan.run(dry_run)?,
+        skipped,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::audit::FileOperationKind;
+
+    fn scratch_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("claudit-backup-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// A backup of project CLAUDE.md files, plus any extra archive entries
+    fn write_backup(path: &Path, projects: &[(&str, &Path, &str)], extra: &[(&str, &str)]) {
+        let manifest = BackupManifest {
+            format_version: BACKUP_FORMAT_VERSION,
+            created_at: "2024-03-01T00:00:00Z".to_string(),
+            created_with: "test".to_string(),
+            options: ExportOptions {
+                include_global_claude: false,
+                include_project_claude: true,
+                include_agents: false,
+                include_commands: false,
+                include_settings: false,
+                include_plugin_config: false,
+            },
+            files: projects
+                .iter()
+                .map(|(folder, _, _)| format!("{}/CLAUDE.md", folder))
+                .collect(),
+            projects: projects
+                .iter()
+                .map(|(folder, project, _)| {
+                    (folder.to_string(), project.to_string_lossy().to_string())
+                })
+                .collect(),
+        };
+        let mut zip = ZipWriter::new(File::create(path).unwrap());
+        let options = SimpleFileOptions::default();
+        zip.start_file(MANIFEST, options).unwrap();
+        zip.write_all(&serde_json::to_vec(&manifest).unwrap())
+            .unwrap();
+        let entries = projects
+            .iter()
+            .map(|(folder, _, contents)| (format!("{}/CLAUDE.md", folder), *contents))
+            .chain(
+                extra
+                    .iter()
+                    .map(|(name, contents)| (name.to_string(), *contents)),
+            );
+        for (name, contents) in entries {
+            zip.start_file(name.as_str(), options).unwrap();
+            zip.write_all(contents.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn test_is_config_file() {
+        assert!(is_config_file("CLAUDE.md"));
+        assert!(is_config_file("settings.json"));
+        assert!(is_config_file("agents/reviewer.md"));
+        assert!(is_config_file("commands/git/commit.md"));
+        assert!(is_config_file("plugins/installed_plugins.json"));
+        assert!(!is_config_file("agents"));
+        assert!(!is_config_file("plugins/cache/plugin.js"));
+        assert!(!is_config_file("projects/-work-app/session.jsonl"));
+        assert!(!is_config_file(".credentials.json"));
+    }
+
+    #[test]
+    fn test_import_dry_run() {
+        let dir = scratch_dir();
+        let changed = dir.join("changed");
+        let unchanged = dir.join("unchanged");
+        fs::create_dir_all(&changed).unwrap();
+        fs::create_dir_all(&unchanged).unwrap();
+        fs::write(changed.join("CLAUDE.md"), "old").unwrap();
+        fs::write(unchanged.join("CLAUDE.md"), "same").unwrap();
+        let missing = dir.join("missing");
+        let backup = dir.join("backup.zip");
+        write_backup(
+            &backup,
+            &[
+                ("projects/0", &changed, "new"),
+                ("projects/1", &unchanged, "same"),
+                ("projects/2", &missing, "lost"),
+            ],
+            &[],
+        );
+
+        let import = import(&backup, true).unwrap();
+        assert_eq!(import.created_at, "2024-03-01T00:00:00Z");
+        assert_eq!(import.operations.len(), 1);
+        assert_eq!(import.operations[0].kind, FileOperationKind::Write);
+        assert_eq!(
+            import.operations[0].path,
+            changed.join("CLAUDE.md").to_string_lossy()
+        );
+        assert_eq!(import.operations[0].bytes, Some(3));
+        assert_eq!(import.skipped.len(), 1);
+        assert!(import.skipped[0].contains("missing"));
+        // Nothing is written in a dry run
+        assert_eq!(
+            fs::read_to_string(changed.join("CLAUDE.md")).unwrap(),
+            "old"
+        );
+        assert!(!missing.exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_import_rejects_unlisted_and_unsafe_entries() {
+        let dir = scratch_dir();
+        let project = dir.join("project");
+        fs::create_dir_all(&project).unwrap();
+
+        let backup = dir.join("unlisted.zip");
+        write_backup(
+            &backup,
+            &[("projects/0", &project, "notes")],
+            &[("claude/settings.json", "{}")],
+        );
+        let error = import(&backup, true).unwrap_err();
+        assert!(error.contains("not listed"), "{}", error);
+
+        let backup = dir.join("unsafe.zip");
+        write_backup(
+            &backup,
+            &[("projects/0", &project, "notes")],
+            &[("claude/../../evil.sh", "")],
+        );
+        let error = import(&backup, true).unwrap_err();
+        assert!(error.contains("unsafe path"), "{}", error);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,764 @@
+//! Team config packs
+//!
+//! A pack is a zip archive with a `manifest.json` and selected user-level
+//! agents and commands, plus MCP servers turned into templates, so a team can
+//! share one Claude setup. Secrets stay on the machine: MCP env vars, headers
+//! and credential-like arguments become template parameters that are filled in
+//! on install, and agent/command files that contain something that looks like a
+//! key are refused. Installing goes through a `FilePlan`, so it can be
+//! previewed as a dry run.
+//!
+//! Packs can be signed with minisign (`minisign -Sm pack.zip` writes
+//! `pack.zip.minisig` next to it). A pack signed by one of
+//! `AppSettings::trusted_pack_keys` installs directly; an unsigned one or one
+//! signed by an unknown key only after the user has reviewed exactly what it
+//! writes and runs, and not at all with `require_signed_packs` on.
+
+use super::audit::{FileOperation, FilePlan};
+use super::config::{McpConfigFiles, McpScope};
+use super::mcp_templates::{self, McpTemplate, McpTemplateParam};
+use super::paths;
+use super::settings::SettingsService;
+use crate::types::TrustedPackKey;
+use chrono::Utc;
+use glob::glob;
+use minisign_verify::{PublicKey, Signature};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Version of the pack format; packs from newer versions are refused
+pub const PACK_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST: &str = "manifest.json";
+
+/// Tokens and keys that must not end up in a pack
+const SECRET_PATTERN: &str = r"sk-ant-[A-Za-z0-9_-]{16,}|sk-[A-Za-z0-9]{32,}|gh[pousr]_[A-Za-z0-9]{30,}|github_pat_[A-Za-z0-9_]{30,}|AKIA[0-9A-Z]{16}|xox[abpr]-[A-Za-z0-9-]{10,}|-----BEGIN [A-Z ]*PRIVATE KEY-----";
+
+/// Flags whose value is a credential, e.g. `--api-key <value>`
+const SECRET_FLAG_PATTERN: &str =
+    r"(?i)^--?[a-z0-9_-]*(token|key|secret|password|auth)[a-z0-9_-]*$";
+
+/// Something that can be put into a pack
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PackItem {
+    Agent {
+        name: String,
+    },
+    Command {
+        name: String,
+    },
+    /// A global MCP server, shipped as a template
+    McpServer {
+        name: String,
+    },
+}
+
+/// An agent or command and its files in the archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackEntry {
+    pub name: String,
+    /// Archive paths, e.g. `agents/reviewer.md`
+    pub files: Vec<String>,
+}
+
+/// `manifest.json` of a pack
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackManifest {
+    pub format_version: u32,
+    pub name: String,
+    pub created_at: String,
+    /// claudit version that created the pack
+    pub created_with: String,
+    pub agents: Vec<PackEntry>,
+    pub commands: Vec<PackEntry>,
+    pub mcp_servers: Vec<McpTemplate>,
+}
+
+/// An item that wasn't installed, and why
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedPackItem {
+    pub item: PackItem,
+    pub reason: String,
+}
+
+/// Summary of an `install_config_pack` run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackInstallResult {
+    pub installed: Vec<PackItem>,
+    pub skipped: Vec<SkippedPackItem>,
+    /// "server: parameter" for MCP values that were left empty
+    pub missing_params: Vec<String>,
+    pub operations: Vec<FileOperation>,
+    pub dry_run: bool,
+}
+
+/// Whether a pack is signed, and by whom
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum PackSignature {
+    /// No `.minisig` file next to the pack
+    Unsigned,
+    /// Signed by the trusted key with this name
+    Verified { key: String },
+    /// Signed, but not by a trusted key
+    UnknownKey,
+    /// The signature doesn't match the pack (modified or corrupt)
+    Invalid { reason: String },
+}
+
+/// Everything a pack would do, shown before it is installed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackReview {
+    pub manifest: PackManifest,
+    pub signature: PackSignature,
+    /// Commands and URLs its MCP servers would run or connect to
+    pub programs: Vec<String>,
+    /// Files in the pack that look executable (scripts next to agents/commands)
+    pub scripts: Vec<String>,
+    /// Dry run of the install
+    pub plan: PackInstallResult,
+}
+
+/// File extensions treated as scripts in the review
+const SCRIPT_EXTENSIONS: &[&str] = &[
+    "sh", "bash", "zsh", "py", "js", "ts", "rb", "pl", "ps1", "bat", "cmd", "exe",
+];
+
+fn secret_regex() -> Regex {
+    Regex::new(SECRET_PATTERN).expect("valid secret pattern")
+}
+
+/// Parameter key for a name like `x-api-key` or `--auth-token`
+fn param_key(name: &str) -> String {
+    name.trim_start_matches('-')
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Turns the credentials of one server config into template parameters
+struct TemplateBuilder {
+    params: Vec<McpTemplateParam>,
+    secrets: Regex,
+    secret_flag: Regex,
+}
+
+impl TemplateBuilder {
+    /// Add a parameter and return its placeholder
+    fn param(&mut self, name: &str, secret: bool) -> String {
+        let base = param_key(name);
+        let mut key = base.clone();
+        let mut n = 2;
+        while self.params.iter().any(|p| p.key == key) {
+            key = format!("{}_{}", base, n);
+            n += 1;
+        }
+        self.params.push(McpTemplateParam {
+            key: key.clone(),
+            label: name.to_string(),
+            description: "Not included in the pack".to_string(),
+            required: false,
+            default: None,
+            secret,
+            list: false,
+        });
+        format!("{{{{{}}}}}", key)
+    }
+
+    /// Every env var and header value becomes a parameter, as they often carry
+    /// credentials (a database URL with a password, say)
+    fn values_to_params(&mut self, map: &mut serde_json::Map<String, Value>) {
+        for (name, value) in map.iter_mut() {
+            if value.is_string() {
+                let secret = self.secret_flag.is_match(name);
+                *value = Value::String(self.param(name, secret));
+            }
+        }
+    }
+
+    fn args_to_params(&mut self, args: &mut [Value]) {
+        let mut previous_flag: Option<String> = None;
+        for arg in args.iter_mut() {
+            let Some(text) = arg.as_str().map(String::from) else {
+                continue;
+            };
+            if let Some(flag) = previous_flag.take() {
+                *arg = Value::String(self.param(&flag, true));
+            } else if let Some((flag, _)) = text
+                .split_once('=')
+                .filter(|(flag, _)| self.secret_flag.is_match(flag))
+            {
+                *arg = Value::String(format!("{}={}", flag, self.param(flag, true)));
+            } else if self.secret_flag.is_match(&text) {
+                previous_flag = Some(text);
+            } else if self.secrets.is_match(&text) {
+                *arg = Value::String(self.param("argument", true));
+            }
+        }
+    }
+
+    fn build(name: &str, pack: &str, mut config: Value) -> McpTemplate {
+        let mut builder = TemplateBuilder {
+            params: Vec::new(),
+            secrets: secret_regex(),
+            secret_flag: Regex::new(SECRET_FLAG_PATTERN).expect("valid flag pattern"),
+        };
+        for field in ["env", "headers"] {
+            if let Some(map) = config.get_mut(field).and_then(Value::as_object_mut) {
+                builder.values_to_params(map);
+            }
+        }
+        if let Some(args) = config.get_mut("args").and_then(Value::as_array_mut) {
+            builder.args_to_params(args);
+        }
+        if let Some(url) = config.get("url").and_then(Value::as_str) {
+            // Credentials in the query or as user:password@host
+            let host = url
+                .split("://")
+                .nth(1)
+                .and_then(|rest| rest.split('/').next());
+            if builder.secrets.is_match(url) || host.is_some_and(|h| h.contains('@')) {
+                let placeholder = builder.param("url", true);
+                config["url"] = Value::String(placeholder);
+            }
+        }
+
+        McpTemplate {
+            id: name.to_string(),
+            name: name.to_string(),
+            description: format!("From the {} pack", pack),
+            homepage: String::new(),
+            params: builder.params,
+            config,
+        }
+    }
+}
+
+/// A user-level agent/command: `<dir>/<name>.md` or the `<dir>/<name>/` folder
+fn entry_files(dir: &Path, name: &str) -> Result<Vec<(PathBuf, String)>, String> {
+    let kind = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let file = dir.join(format!("{}.md", name));
+    if file.is_file() {
+        return Ok(vec![(file, format!("{}/{}.md", kind, name))]);
+    }
+    let folder = dir.join(name);
+    if !folder.is_dir() {
+        return Err(format!("{} not found in {}", name, dir.display()));
+    }
+
+    let mut files = Vec::new();
+    let pattern = folder.join("**").join("*");
+    for path in glob(&pattern.to_string_lossy())
+        .map_err(|e| e.to_string())?
+        .flatten()
+    {
+        if !path.is_file() {
+            continue;
+        }
+        if let Ok(relative) = path.strip_prefix(&folder) {
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            files.push((path.clone(), format!("{}/{}/{}", kind, name, relative)));
+        }
+    }
+    Ok(files)
+}
+
+/// Write a pack with the selected items to `output`
+pub fn create(items: &[PackItem], name: &str, output: &Path) -> Result<PackManifest, String> {
+    let claude_dir = paths::current().config_dir().to_path_buf();
+    let mcp_files = McpConfigFiles::load()?;
+    let secrets = secret_regex();
+
+    let mut manifest = PackManifest {
+        format_version: PACK_FORMAT_VERSION,
+        name: name.to_string(),
+        created_at: Utc::now().to_rfc3339(),
+        created_with: env!("CARGO_PKG_VERSION").to_string(),
+        agents: Vec::new(),
+        commands: Vec::new(),
+        mcp_servers: Vec::new(),
+    };
+    let mut contents: Vec<(String, Vec<u8>)> = Vec::new();
+
+    for item in items {
+        let (dir, entries, entry_name) = match item {
+            PackItem::Agent { name } => (claude_dir.join("agents"), &mut manifest.agents, name),
+            PackItem::Command { name } => {
+                (claude_dir.join("commands"), &mut manifest.commands, name)
+            }
+            PackItem::McpServer { name } => {
+                let config = mcp_files
+                    .get(&McpScope::Global, name)
+                    .ok_or_else(|| format!("MCP server not found: {}", name))?;
+                manifest
+                    .mcp_servers
+                    .push(TemplateBuilder::build(name, &manifest.name, config));
+                continue;
+            }
+        };
+
+        let mut files = Vec::new();
+        for (path, archive_path) in entry_files(&dir, entry_name)? {
+            let data =
+                fs::read(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            if secrets.is_match(&String::from_utf8_lossy(&data)) {
+                return Err(format!(
+                    "{} looks like it contains a key or token; remove it before sharing",
+                    path.display()
+                ));
+            }
+            files.push(archive_path.clone());
+            contents.push((archive_path, data));
+        }
+        entries.push(PackEntry {
+            name: entry_name.clone(),
+            files,
+        });
+    }
+
+    let file = File::create(output)
+        .map_err(|e| format!("Failed to create {}: {}", output.display(), e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let manifest_json = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+    for (path, data) in std::iter::once((MANIFEST.to_string(), manifest_json)).chain(contents) {
+        zip.start_file(path.as_str(), options)
+            .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+        zip.write_all(&data)
+            .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    }
+    zip.finish()
+        .map_err(|e| format!("Failed to write {}: {}", output.display(), e))?;
+
+    Ok(manifest)
+}
+
+fn open_archive(path: &Path) -> Result<ZipArchive<File>, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    ZipArchive::new(file).map_err(|e| format!("Not a config pack: {}", e))
+}
+
+fn read_entry(archive: &mut ZipArchive<File>, path: &str) -> Result<Vec<u8>, String> {
+    let mut file = archive
+        .by_name(path)
+        .map_err(|e| format!("Pack is missing {}: {}", path, e))?;
+    let mut data = Vec::new();
+    file.read_to_end(&mut data)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    Ok(data)
+}
+
+fn read_manifest(archive: &mut ZipArchive<File>) -> Result<PackManifest, String> {
+    let data = read_entry(archive, MANIFEST)?;
+    let manifest: PackManifest =
+        serde_json::from_slice(&data).map_err(|e| format!("Invalid pack manifest: {}", e))?;
+    if manifest.format_version > PACK_FORMAT_VERSION {
+        return Err(format!(
+            "This pack needs a newer claudit (pack format {})",
+            manifest.format_version
+        ));
+    }
+    Ok(manifest)
+}
+
+/// Parse a trusted key: the `RW...` line, or a whole minisign.pub file
+fn public_key(key: &str) -> Result<PublicKey, String> {
+    let line = key
+        .lines()
+        .map(str::trim)
+        .rfind(|l| !l.is_empty() && !l.starts_with("untrusted comment:"))
+        .unwrap_or_default();
+    PublicKey::from_base64(line).map_err(|e| format!("Invalid minisign public key: {}", e))
+}
+
+/// Check that every trusted key parses
+pub fn validate_keys(keys: &[TrustedPackKey]) -> Result<(), String> {
+    for key in keys {
+        public_key(&key.public_key).map_err(|e| format!("{}: {}", key.name, e))?;
+    }
+    Ok(())
+}
+
+/// Where minisign puts the signature of `path`
+fn signature_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".minisig");
+    PathBuf::from(name)
+}
+
+/// Check the pack's `.minisig` signature against the trusted keys
+pub fn verify(path: &Path, keys: &[TrustedPackKey]) -> PackSignature {
+    let Ok(text) = fs::read_to_string(signature_path(path)) else {
+        return PackSignature::Unsigned;
+    };
+    let signature = match Signature::decode(&text) {
+        Ok(signature) => signature,
+        Err(e) => {
+            return PackSignature::Invalid {
+                reason: e.to_string(),
+            }
+        }
+    };
+    let data = match fs::read(path) {
+        Ok(data) => data,
+        Err(e) => {
+            return PackSignature::Invalid {
+                reason: e.to_string(),
+            }
+        }
+    };
+
+    for key in keys {
+        let Ok(public_key) = public_key(&key.public_key) else {
+            continue;
+        };
+        match public_key.verify(&data, &signature, false) {
+            Ok(()) => {
+                return PackSignature::Verified {
+                    key: key.name.clone(),
+                }
+            }
+            Err(minisign_verify::Error::UnexpectedKeyId) => continue,
+            Err(e) => {
+                return PackSignature::Invalid {
+                    reason: e.to_string(),
+                }
+            }
+        }
+    }
+    PackSignature::UnknownKey
+}
+
+/// What an MCP server config runs or connects to
+fn program(template: &McpTemplate) -> Option<String> {
+    let config = &template.config;
+    if let Some(url) = config.get("url").and_then(Value::as_str) {
+        return Some(format!("{}: {}", template.name, url));
+    }
+    let command = config.get("command").and_then(Value::as_str)?;
+    let args: Vec<&str> = config
+        .get("args")
+        .and_then(Value::as_array)
+        .map(|args| args.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    Some(
+        format!("{}: {} {}", template.name, command, args.join(" "))
+            .trim_end()
+            .to_string(),
+    )
+}
+
+/// Signature, programs, scripts and a dry run of installing a pack
+pub fn review(
+    path: &Path,
+    scope: &McpScope,
+    params: &HashMap<String, HashMap<String, String>>,
+    overwrite: bool,
+) -> Result<PackReview, String> {
+    let manifest = read_manifest(&mut open_archive(path)?)?;
+    let signature = verify(path, &SettingsService::shared().get().trusted_pack_keys);
+    let programs = manifest.mcp_servers.iter().filter_map(program).collect();
+    let scripts = manifest
+        .agents
+        .iter()
+        .chain(&manifest.commands)
+        .flat_map(|entry| &entry.files)
+        .filter(|file| {
+            Path::new(file)
+                .extension()
+                .is_some_and(|ext| SCRIPT_EXTENSIONS.contains(&ext.to_string_lossy().as_ref()))
+        })
+        .cloned()
+        .collect();
+    let plan = apply(path, scope, params, overwrite, true)?;
+
+    Ok(PackReview {
+        manifest,
+        signature,
+        programs,
+        scripts,
+        plan,
+    })
+}
+
+/// Where an archive path of an entry goes, refusing paths that would leave
+/// `target_dir` or belong to another entry
+fn install_path(target_dir: &Path, kind: &str, name: &str, archive_path: &str) -> Option<PathBuf> {
+    let relative = Path::new(archive_path).strip_prefix(kind).ok()?;
+    let safe = relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)));
+    let first = relative.components().next()?.as_os_str().to_string_lossy();
+    let own = first == name || first == format!("{}.md", name);
+    (safe && own).then(|| target_dir.join(relative))
+}
+
+/// Install a pack into the user's Claude config (`McpScope::Global`) or a
+/// project. Packs not signed by a trusted key need `trust_unsigned`, set once
+/// the user has seen the `review`.
+pub fn install(
+    path: &Path,
+    scope: &McpScope,
+    params: &HashMap<String, HashMap<String, String>>,
+    overwrite: bool,
+    trust_unsigned: bool,
+    dry_run: bool,
+) -> Result<PackInstallResult, String> {
+    let settings = SettingsService::shared().get();
+    match verify(path, &settings.trusted_pack_keys) {
+        PackSignature::Verified { .. } => {}
+        PackSignature::Invalid { reason } => {
+            return Err(format!("The pack's signature is invalid: {}", reason));
+        }
+        _ if settings.require_signed_packs => {
+            return Err("Only packs signed with a trusted key can be installed".to_string());
+        }
+        _ if !trust_unsigned && !dry_run => {
+            return Err(
+                "This pack isn't signed by a trusted key; review it and confirm to install"
+                    .to_string(),
+            );
+        }
+        _ => {}
+    }
+    apply(path, scope, params, overwrite, dry_run)
+}
+
+/// Write a pack's files and MCP servers, whatever its signature
+fn apply(
+    path: &Path,
+    scope: &McpScope,
+    params: &HashMap<String, HashMap<String, String>>,
+    overwrite: bool,
+    dry_run: bool,
+) -> Result<PackInstallResult, String> {
+    let mut archive = open_archive(path)?;
+    let manifest = read_manifest(&mut archive)?;
+    let base = match scope {
+        McpScope::Global => paths::current().config_dir().to_path_buf(),
+        McpScope::Project { path } => PathBuf::from(path).join(".claude"),
+    };
+
+    let mut plan = FilePlan::new(&format!("install_config_pack: {}", manifest.name));
+    plan.with_restore_point();
+    let mut installed = Vec::new();
+    let mut skipped = Vec::new();
+    let mut missing_params = Vec::new();
+
+    let groups = [
+        ("agents", &manifest.agents, true),
+        ("commands", &manifest.commands, false),
+    ];
+    for (kind, entries, is_agent) in groups {
+        let target_dir = base.join(kind);
+        for entry in entries {
+            let item = if is_agent {
+                PackItem::Agent {
+                    name: entry.name.clone(),
+                }
+            } else {
+                PackItem::Command {
+                    name: entry.name.clone(),
+                }
+            };
+            let exists = target_dir.join(format!("{}.md", entry.name)).exists()
+                || target_dir.join(&entry.name).exists();
+            if exists && !overwrite {
+                skipped.push(SkippedPackItem {
+                    item,
+                    reason: "Already exists".to_string(),
+                });
+                continue;
+            }
+
+            let mut files = Vec::new();
+            for archive_path in &entry.files {
+                let Some(target) = install_path(&target_dir, kind, &entry.name, archive_path)
+                else {
+                    return Err(format!("Pack contains an unsafe path: {}", archive_path));
+                };
+                files.push((target, read_entry(&mut archive, archive_path)?));
+            }
+            plan.create_dir(&target_dir, &format!("create {}", kind));
+            for (target, data) in files {
+                if let Some(parent) = target.parent() {
+                    plan.create_dir(parent, &entry.name);
+                }
+                plan.write(&target, data, &entry.name);
+            }
+            installed.push(item);
+        }
+    }
+
+    let mut mcp_files = McpConfigFiles::load()?;
+    for template in &manifest.mcp_servers {
+        let item = PackItem::McpServer {
+            name: template.name.clone(),
+        };
+        if mcp_files.get(scope, &template.name).is_some() && !overwrite {
+            skipped.push(SkippedPackItem {
+                item,
+                reason: "Already exists".to_string(),
+            });
+            continue;
+        }
+        let values = params.get(&template.name).cloned().unwrap_or_default();
+        for param in &template.params {
+            if values.get(&param.key).is_none_or(|v| v.trim().is_empty()) {
+                missing_params.push(format!("{}: {}", template.name, param.label));
+            }
+        }
+        let config = mcp_templates::render(template, &values)?;
+        match mcp_files.insert(scope, &template.name, config) {
+            Ok(()) => installed.push(item),
+            Err(reason) => skipped.push(SkippedPackItem { item, reason }),
+        }
+    }
+    mcp_files.plan_writes(&mut plan)?;
+
+    let operations = plan.run(dry_run)?;
+    Ok(PackInstallResult {
+        installed,
+        skipped,
+        missing_params,
+        operations,
+        dry_run,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Key and prehashed signature of the data `test`, from minisign's test vectors
+    const SIGNING_KEY: &str = "RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3";
+    const SIGNATURE: &str = "untrusted comment: signature from minisign secret key
+RUQf6LRCGA9i559r3g7V1qNyJDApGip8MfqcadIgT9CuhV3EMhHoN1mGTkUidF/z7SrlQgXdy8ofjb7bNJJylDOocrCo8KLzZwo=
+trusted comment: timestamp:1556193335\tfile:test
+y/rUw2y8/hOUYjZU71eHp/Wo1KZ40fGy2VJEDl34XMJM+TX48Ss/17u3IvIfbVR1FkZZSNCisQbuQY+bHwhEBg==
+";
+    const OTHER_KEY: &str = "RWQBAgMEBQYHCDyO4xYuBwdN6fsJcIIQNwVrR8Mnn+YsMm6TE3Rbkf/4";
+
+    fn key(name: &str, public_key: &str) -> TrustedPackKey {
+        TrustedPackKey {
+            name: name.to_string(),
+            public_key: public_key.to_string(),
+        }
+    }
+
+    /// A "pack" with the given contents, signed with `SIGNATURE` if `signed`
+    fn pack(contents: &str, signed: bool) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("claudit-pack-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("team.zip");
+        fs::write(&path, contents).unwrap();
+        if signed {
+            fs::write(signature_path(&path), SIGNATURE).unwrap();
+        }
+        path
+    }
+
+    #[test]
+    fn test_validate_keys() {
+        let minisign_pub = format!("untrusted comment: minisign public key\n{}\n", SIGNING_KEY);
+        assert!(validate_keys(&[key("Team", SIGNING_KEY), key("File", &minisign_pub)]).is_ok());
+        let error = validate_keys(&[key("Broken", "RWQnotakey")]).unwrap_err();
+        assert!(error.starts_with("Broken: "), "{}", error);
+    }
+
+    #[test]
+    fn test_verify() {
+        let signed = pack("test", true);
+        let keys = [key("Other", OTHER_KEY), key("Platform team", SIGNING_KEY)];
+        assert_eq!(
+            verify(&signed, &keys),
+            PackSignature::Verified {
+                key: "Platform team".to_string()
+            }
+        );
+        assert_eq!(verify(&signed, &keys[..1]), PackSignature::UnknownKey);
+        assert_eq!(verify(&signed, &[]), PackSignature::UnknownKey);
+        assert_eq!(verify(&pack("test", false), &keys), PackSignature::Unsigned);
+
+        // Modified after signing
+        let tampered = pack("tested", true);
+        assert!(matches!(
+            verify(&tampered, &keys),
+            PackSignature::Invalid { .. }
+        ));
+
+        let corrupt = pack("test", false);
+        fs::write(signature_path(&corrupt), "not a signature").unwrap();
+        assert!(matches!(
+            verify(&corrupt, &keys),
+            PackSignature::Invalid { .. }
+        ));
+
+        for path in [signed, tampered, corrupt] {
+            let _ = fs::remove_dir_all(path.parent().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_install_path() {
+        let target = Path::new("/home/me/.claude/agents");
+        assert_eq!(
+            install_path(target, "agents", "reviewer", "agents/reviewer.md"),
+            Some(target.join("reviewer.md"))
+        );
+        assert_eq!(
+            install_path(target, "agents", "reviewer", "agents/reviewer/lint.sh"),
+            Some(target.join("reviewer/lint.sh"))
+        );
+
+        // Out of the target directory
+        for archive_path in [
+            "agents/../settings.json",
+            "agents/reviewer/../../settings.json",
+            "agents/reviewer/../../../.bashrc",
+            "/etc/passwd",
+            "../agents/reviewer.md",
+            "agents",
+        ] {
+            assert_eq!(
+                install_path(target, "agents", "reviewer", archive_path),
+                None,
+                "{}",
+                archive_path
+            );
+        }
+
+        // Files of another entry, or of another kind
+        for archive_path in [
+            "agents/other.md",
+            "agents/reviewer-extra.md",
+            "agents/other/reviewer.md",
+            "commands/reviewer.md",
+        ] {
+            assert_eq!(
+                install_path(target, "agents", "reviewer", archive_path),
+                None,
+                "{}",
+                archive_path
+            );
+        }
+    }
+}
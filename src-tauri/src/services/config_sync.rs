@@ -0,0 +1,640 @@
+//! GitHub sync for the Claude config
+//!
+//! Keeps the Claude config folder in a git repository pushed to a GitHub
+//! remote the user provides, dotfiles style. Only configuration is tracked:
+//! commits stage the `SYNCED` entries by name, whatever the folder's
+//! `.gitignore` says, and syncing is refused while the repository tracks
+//! anything else (session logs, caches, credentials).
+//! The GitHub token lives in the system keychain and reaches git through the
+//! environment of each command, so it is never written to the repository.
+//! A pull that conflicts is aborted and the conflicting files are reported;
+//! pulling again with a `ConflictResolution` keeps one side.
+
+use super::audit;
+use super::config::GitStatus;
+use super::notifications::{self, PendingNotification};
+use super::paths;
+use super::settings::SettingsService;
+use crate::types::ConfigSyncSettings;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Runtime};
+
+const KEYCHAIN_SERVICE: &str = "claudit";
+const KEYCHAIN_ACCOUNT: &str = "github-config-sync";
+
+/// Only these entries of the config folder are synced
+const SYNCED: &[&str] = &[
+    ".gitignore",
+    "CLAUDE.md",
+    "settings.json",
+    "agents",
+    "commands",
+    "skills",
+    "output-styles",
+];
+
+/// `.gitignore` written into a new repository, matching `SYNCED`
+const GITIGNORE: &str = "# Managed by claudit: only configuration is synced
+/*
+!/.gitignore
+!/CLAUDE.md
+!/settings.json
+!/agents/
+!/commands/
+!/skills/
+!/output-styles/
+";
+
+static LAST_AUTO_COMMIT: Mutex<Option<DateTime<Utc>>> = Mutex::new(None);
+/// Last automatic sync error, so a persisting failure is only notified once
+static LAST_ERROR: Mutex<Option<String>> = Mutex::new(None);
+
+/// How to settle files changed both locally and on the remote
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictResolution {
+    KeepMine,
+    TakeTheirs,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigSyncStatus {
+    /// A remote is set in the settings
+    pub configured: bool,
+    pub is_repo: bool,
+    pub has_token: bool,
+    pub branch: String,
+    /// Files changed since the last commit
+    pub changed_files: Vec<String>,
+    /// Commits not pushed yet / not pulled yet, against the branch's
+    /// upstream as of the last fetch
+    pub ahead: u32,
+    pub behind: u32,
+    pub last_commit_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigSyncResult {
+    pub committed: bool,
+    pub pushed: bool,
+    pub pulled: bool,
+    /// Files changed on both sides; nothing was merged
+    pub conflicts: Vec<String>,
+}
+
+fn keychain_entry() -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT)
+        .map_err(|e| format!("Keychain error: {}", e))
+}
+
+/// Store the GitHub token in the keychain (an empty token removes it)
+pub fn set_token(token: &str) -> Result<(), String> {
+    let entry = keychain_entry()?;
+    let token = token.trim();
+    if token.is_empty() {
+        return match entry.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(format!("Failed to remove the token: {}", e)),
+        };
+    }
+    entry
+        .set_password(token)
+        .map_err(|e| format!("Failed to store the token: {}", e))
+}
+
+fn token() -> Option<String> {
+    keychain_entry().ok()?.get_password().ok()
+}
+
+fn sync_settings() -> Result<ConfigSyncSettings, String> {
+    let sync = SettingsService::shared()
+        .get()
+        .config_sync
+        .ok_or_else(|| "GitHub sync isn't set up".to_string())?;
+    // The settings file may have been edited by hand
+    validate(&sync)?;
+    Ok(sync)
+}
+
+/// HTTPS URL for a remote given as a URL or `owner/repo`
+fn remote_url(remote: &str) -> Result<String, String> {
+    let remote = remote.trim();
+    if remote.starts_with("https://") {
+        Ok(remote.to_string())
+    } else if remote.split('/').count() == 2 && !remote.contains(':') {
+        Ok(format!(
+            "https://github.com/{}.git",
+            remote.trim_end_matches(".git")
+        ))
+    } else {
+        Err(format!(
+            "Use an HTTPS URL or owner/repo for the remote: {}",
+            remote
+        ))
+    }
+}
+
+/// Check that a branch name is one git accepts, and can't be read as an option
+fn validate_branch(branch: &str) -> Result<(), String> {
+    let invalid = || format!("Invalid branch name: {}", branch);
+    if branch.is_empty() || branch.starts_with('-') {
+        return Err(invalid());
+    }
+    let valid = Command::new("git")
+        .args(["check-ref-format", "--branch", branch])
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?
+        .status
+        .success();
+    if valid {
+        Ok(())
+    } else {
+        Err(invalid())
+    }
+}
+
+/// Check the remote and branch before the settings are saved (and before use)
+pub fn validate(sync: &ConfigSyncSettings) -> Result<(), String> {
+    remote_url(&sync.remote)?;
+    validate_branch(&sync.branch)
+}
+
+fn config_dir() -> PathBuf {
+    paths::current().config_dir().to_path_buf()
+}
+
+/// Run git in the config folder with the token (if any) as an auth header
+fn git(args: &[&str]) -> Result<String, String> {
+    run_git(&config_dir(), args, token())
+}
+
+/// Run git in `dir`, authenticating with `token` when given
+fn run_git(dir: &Path, args: &[&str], token: Option<String>) -> Result<String, String> {
+    let mut command = Command::new("git");
+    command
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .env("GIT_TERMINAL_PROMPT", "0");
+    if let Some(token) = token {
+        let credentials = BASE64.encode(format!("x-access-token:{}", token));
+        command
+            .env("GIT_CONFIG_COUNT", "1")
+            .env("GIT_CONFIG_KEY_0", "http.extraHeader")
+            .env(
+                "GIT_CONFIG_VALUE_0",
+                format!("Authorization: Basic {}", credentials),
+            );
+    }
+
+    let output = command
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+fn is_repo() -> bool {
+    config_dir().join(".git").exists()
+}
+
+fn has_commits() -> bool {
+    git(&["rev-parse", "--verify", "HEAD"]).is_ok()
+}
+
+/// Whether a repository path is one of the `SYNCED` entries or inside one
+fn is_synced(path: &str) -> bool {
+    let top = path.split('/').next().unwrap_or_default();
+    SYNCED.contains(&top)
+}
+
+/// Refuse to sync a repository that tracks files outside `SYNCED`, e.g. one
+/// the user set up before with their own `.gitignore`
+fn check_tracked(dir: &Path) -> Result<(), String> {
+    let tracked = run_git(dir, &["ls-files", "--cached"], None)?;
+    let outside: Vec<&str> = tracked.lines().filter(|path| !is_synced(path)).collect();
+    if outside.is_empty() {
+        return Ok(());
+    }
+    Err(format!(
+        "The config repository tracks files that aren't synced: {}. Untrack them with `git rm --cached` first.",
+        outside.join(", ")
+    ))
+}
+
+/// Stage changes to the `SYNCED` entries only. They are named explicitly, so a
+/// `.gitignore` that lets other files through can't get them committed.
+fn stage(dir: &Path) -> Result<(), String> {
+    check_tracked(dir)?;
+    let tracked = run_git(dir, &["ls-files", "--cached"], None)?;
+    let entries: Vec<&str> = SYNCED
+        .iter()
+        .copied()
+        .filter(|entry| {
+            // Pathspecs must match something: on disk, or tracked and deleted since
+            dir.join(entry).exists()
+                || tracked
+                    .lines()
+                    .any(|path| path.split('/').next() == Some(*entry))
+        })
+        .collect();
+    if entries.is_empty() {
+        return Ok(());
+    }
+    let mut args = vec!["add", "-A", "--"];
+    args.extend(entries);
+    run_git(dir, &args, None).map(|_| ())
+}
+
+/// Create the repository, its `.gitignore` and the `origin` remote as needed
+fn ensure_repo(sync: &ConfigSyncSettings) -> Result<(), String> {
+    let url = remote_url(&sync.remote)?;
+    if !is_repo() {
+        git(&["init", "-b", &sync.branch])?;
+    }
+    let gitignore = config_dir().join(".gitignore");
+    if !gitignore.exists() {
+        audit::write_file(&gitignore, GITIGNORE, "config_sync: create .gitignore")?;
+    }
+    check_tracked(&config_dir())?;
+    match git(&["remote", "get-url", "origin"]) {
+        Ok(current) if current == url => Ok(()),
+        Ok(_) => git(&["remote", "set-url", "origin", &url]).map(|_| ()),
+        Err(_) => git(&["remote", "add", "origin", &url]).map(|_| ()),
+    }
+}
+
+/// Commit all config changes; false when there was nothing to commit
+fn commit(message: &str) -> Result<bool, String> {
+    stage(&config_dir())?;
+    if git(&["diff", "--cached", "--name-only"])?.is_empty() {
+        return Ok(false);
+    }
+    // Fall back to a claudit identity when git has none configured
+    if git(&["config", "user.email"]).is_err() {
+        git(&[
+            "-c",
+            "user.name=claudit",
+            "-c",
+            "user.email=claudit@localhost",
+            "commit",
+            "-m",
+            message,
+        ])?;
+    } else {
+        git(&["commit", "-m", message])?;
+    }
+    Ok(true)
+}
+
+fn push_branch(branch: &str) -> Result<(), String> {
+    check_tracked(&config_dir())?;
+    git(&["push", "-u", "origin", &format!("HEAD:{}", branch)])
+        .map(|_| ())
+        .map_err(|e| {
+            if e.contains("rejected") || e.contains("fetch first") {
+                "The remote has changes that aren't here yet; pull first".to_string()
+            } else {
+                format!("Push failed: {}", e)
+            }
+        })
+}
+
+/// Commit local changes and push them
+pub fn push(message: Option<&str>) -> Result<ConfigSyncResult, String> {
+    let sync = sync_settings()?;
+    ensure_repo(&sync)?;
+    let committed = commit(message.unwrap_or("Update Claude config"))?;
+    let pushed = has_commits();
+    if pushed {
+        push_branch(&sync.branch)?;
+    }
+    Ok(ConfigSyncResult {
+        committed,
+        pushed,
+        pulled: false,
+        conflicts: Vec::new(),
+    })
+}
+
+/// Commit local changes and merge the remote's
+pub fn pull(resolution: Option<ConflictResolution>) -> Result<ConfigSyncResult, String> {
+    let sync = sync_settings()?;
+    ensure_repo(&sync)?;
+    let committed = commit("Local Claude config changes")?;
+    let mut result = ConfigSyncResult {
+        committed,
+        pushed: false,
+        pulled: false,
+        conflicts: Vec::new(),
+    };
+
+    if let Err(e) = git(&["fetch", "origin", &sync.branch]) {
+        // Nothing has been pushed to the remote yet
+        if e.contains("couldn't find remote ref") {
+            return Ok(result);
+        }
+        return Err(format!("Fetch failed: {}", e));
+    }
+    let upstream = format!("origin/{}", sync.branch);
+    if !has_commits() {
+        git(&["checkout", "-B", &sync.branch, &upstream])?;
+        result.pulled = true;
+        return Ok(result);
+    }
+
+    let mut args = vec!["merge", "--no-edit", "--allow-unrelated-histories"];
+    match resolution {
+        Some(ConflictResolution::KeepMine) => args.extend(["-X", "ours"]),
+        Some(ConflictResolution::TakeTheirs) => args.extend(["-X", "theirs"]),
+        None => {}
+    }
+    args.push(&upstream);
+    if let Err(e) = git(&args) {
+        let conflicts: Vec<String> = git(&["diff", "--name-only", "--diff-filter=U"])
+            .unwrap_or_default()
+            .lines()
+            .map(String::from)
+            .collect();
+        // Leave the files as they were rather than with conflict markers
+        let _ = git(&["merge", "--abort"]);
+        if conflicts.is_empty() {
+            return Err(format!("Merge failed: {}", e));
+        }
+        result.conflicts = conflicts;
+        return Ok(result);
+    }
+    result.pulled = true;
+    Ok(result)
+}
+
+/// Synced files changed since the last commit
+fn changed_files() -> Vec<String> {
+    git(&["status", "--porcelain"])
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| line.get(3..).map(String::from))
+        .filter(|path| is_synced(path))
+        .collect()
+}
+
+/// Commits ahead of and behind `upstream`, if it exists
+fn ahead_behind(upstream: &str) -> Option<(u32, u32)> {
+    let counts = git(&[
+        "rev-list",
+        "--left-right",
+        "--count",
+        &format!("HEAD...{}", upstream),
+    ])
+    .ok()?;
+    let mut counts = counts.split_whitespace().map(|n| n.parse().unwrap_or(0));
+    Some((counts.next()?, counts.next()?))
+}
+
+/// Branch, uncommitted files and ahead/behind counts of the config repository
+pub fn git_status() -> GitStatus {
+    let mut status = GitStatus {
+        is_git_repo: is_repo(),
+        has_changes: false,
+        branch: String::new(),
+        uncommitted_files: Vec::new(),
+        upstream: None,
+        ahead: 0,
+        behind: 0,
+    };
+    if !status.is_git_repo {
+        return status;
+    }
+    // `--show-current` also names a branch without commits yet
+    status.branch = git(&["branch", "--show-current"]).unwrap_or_default();
+    status.uncommitted_files = changed_files();
+    status.has_changes = !status.uncommitted_files.is_empty();
+    status.upstream = git(&["rev-parse", "--abbrev-ref", "@{upstream}"])
+        .ok()
+        .filter(|u| !u.is_empty());
+    if let Some((ahead, behind)) = status.upstream.as_deref().and_then(ahead_behind) {
+        status.ahead = ahead;
+        status.behind = behind;
+    }
+    status
+}
+
+/// Turn the config folder into a git repository that tracks only configuration
+pub fn init_repo() -> Result<GitStatus, String> {
+    if is_repo() {
+        return Err(format!(
+            "{} is already a git repository",
+            config_dir().display()
+        ));
+    }
+    git(&["init"])?;
+    let gitignore = config_dir().join(".gitignore");
+    if !gitignore.exists() {
+        audit::write_file(&gitignore, GITIGNORE, "config_repo: create .gitignore")?;
+    }
+    Ok(git_status())
+}
+
+/// Commit every config change; false when there was nothing to commit
+pub fn commit_changes(message: &str) -> Result<bool, String> {
+    if !is_repo() {
+        return Err("The Claude config folder isn't a git repository yet".to_string());
+    }
+    let message = message.trim();
+    commit(if message.is_empty() {
+        "Update Claude config"
+    } else {
+        message
+    })
+}
+
+/// Push committed changes to the branch's upstream, or to the GitHub sync
+/// remote when there is none yet
+pub fn push_changes() -> Result<GitStatus, String> {
+    if !has_commits() {
+        return Err("Nothing has been committed yet".to_string());
+    }
+    check_tracked(&config_dir())?;
+    let status = git_status();
+    if status.upstream.is_some() {
+        git(&["push"]).map_err(|e| format!("Push failed: {}", e))?;
+    } else {
+        let sync = sync_settings()
+            .map_err(|_| "No remote to push to; set one up under GitHub Sync".to_string())?;
+        ensure_repo(&sync)?;
+        push_branch(&sync.branch)?;
+    }
+    Ok(git_status())
+}
+
+/// Repository state for the sync panel, from the same `git_status` the git panel shows
+pub fn status() -> ConfigSyncStatus {
+    let git_status = git_status();
+    ConfigSyncStatus {
+        configured: SettingsService::shared().get().config_sync.is_some(),
+        is_repo: git_status.is_git_repo,
+        has_token: token().is_some(),
+        last_commit_at: git_status
+            .is_git_repo
+            .then(|| git(&["log", "-1", "--format=%cI"]).ok())
+            .flatten()
+            .filter(|s| !s.is_empty()),
+        branch: git_status.branch,
+        changed_files: git_status.uncommitted_files,
+        ahead: git_status.ahead,
+        behind: git_status.behind,
+    }
+}
+
+/// Commit (and push) config changes on the configured schedule; called by the
+/// watchdog every minute
+pub fn auto_commit<R: Runtime>(app: &AppHandle<R>) {
+    let Some(sync) = SettingsService::shared().get().config_sync else {
+        return;
+    };
+    if sync.auto_commit_minutes == 0 || !is_repo() {
+        return;
+    }
+    let now = Utc::now();
+    {
+        let mut last = LAST_AUTO_COMMIT.lock().unwrap();
+        if last.is_some_and(|t| now - t < Duration::minutes(sync.auto_commit_minutes as i64)) {
+            return;
+        }
+        *last = Some(now);
+    }
+
+    let outcome = commit("Auto-commit Claude config changes").and_then(|committed| {
+        if committed && sync.auto_push {
+            push_branch(&sync.branch)
+        } else {
+            Ok(())
+        }
+    });
+
+    let mut last_error = LAST_ERROR.lock().unwrap();
+    match outcome {
+        Ok(()) => *last_error = None,
+        Err(e) if last_error.as_deref() != Some(e.as_str()) => {
+            *last_error = Some(e.clone());
+            let _ = app.emit("config-sync-error", &e);
+            notifications::dispatch(
+                app,
+                PendingNotification {
+                    title: "Config sync failed".to_string(),
+                    body: e,
+                    event: "ConfigSync".to_string(),
+                    project: None,
+                    session_id: None,
+                },
+            );
+        }
+        Err(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn scratch_repo() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("claudit-sync-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        run_git(&dir, &["init", "-q"], None).unwrap();
+        dir
+    }
+
+    fn staged(dir: &Path) -> Vec<String> {
+        run_git(dir, &["diff", "--cached", "--name-only"], None)
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn test_existing_gitignore_cannot_leak_credentials() {
+        let dir = scratch_repo();
+        // The user's own rules only leave out logs
+        fs::write(dir.join(".gitignore"), "*.log\n").unwrap();
+        fs::write(dir.join(".credentials.json"), "{\"token\":\"secret\"}").unwrap();
+        fs::write(dir.join("history.jsonl"), "{}\n").unwrap();
+        fs::create_dir_all(dir.join("projects/-work-app")).unwrap();
+        fs::write(dir.join("projects/-work-app/session.jsonl"), "{}\n").unwrap();
+        fs::write(dir.join("CLAUDE.md"), "# Notes").unwrap();
+        fs::create_dir_all(dir.join("agents")).unwrap();
+        fs::write(dir.join("agents/reviewer.md"), "review").unwrap();
+
+        stage(&dir).unwrap();
+        assert_eq!(
+            staged(&dir),
+            vec![".gitignore", "CLAUDE.md", "agents/reviewer.md"]
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_refuses_repo_tracking_other_files() {
+        let dir = scratch_repo();
+        fs::write(dir.join(".credentials.json"), "{}").unwrap();
+        run_git(&dir, &["add", ".credentials.json"], None).unwrap();
+
+        let error = stage(&dir).unwrap_err();
+        assert!(error.contains(".credentials.json"), "{}", error);
+
+        run_git(&dir, &["rm", "-q", "--cached", ".credentials.json"], None).unwrap();
+        assert!(check_tracked(&dir).is_ok());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_stages_deleted_files() {
+        let dir = scratch_repo();
+        fs::create_dir_all(dir.join("commands")).unwrap();
+        fs::write(dir.join("commands/deploy.md"), "deploy").unwrap();
+        stage(&dir).unwrap();
+        run_git(
+            &dir,
+            &[
+                "-c",
+                "user.name=t",
+                "-c",
+                "user.email=t@t",
+                "commit",
+                "-qm",
+                "init",
+            ],
+            None,
+        )
+        .unwrap();
+
+        fs::remove_dir_all(dir.join("commands")).unwrap();
+        stage(&dir).unwrap();
+        assert_eq!(staged(&dir), vec!["commands/deploy.md"]);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_validate_branch() {
+        assert!(validate_branch("main").is_ok());
+        assert!(validate_branch("claude/config").is_ok());
+        assert!(validate_branch("--upload-pack=touch /tmp/x").is_err());
+        assert!(validate_branch("-b").is_err());
+        assert!(validate_branch("bad..name").is_err());
+        assert!(validate_branch("with space").is_err());
+        assert!(validate_branch("").is_err());
+    }
+}
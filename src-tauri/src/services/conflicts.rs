@@ -0,0 +1,471 @@
+//! Duplicate and conflicting config detection
+//!
+//! Finds MCP servers defined in several scopes, commands/agents that exist both
+//! globally and in a project, and CLAUDE.md directives that contradict each other.
+
+use super::{config, paths};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where a finding points to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FindingLocation {
+    pub path: String,
+    /// 1-based line, when known
+    pub line: Option<usize>,
+    /// "Global", project name, ...
+    pub label: String,
+    /// Relevant excerpt (e.g. the directive text)
+    pub excerpt: Option<String>,
+}
+
+/// A duplicate or conflict in the user's Claude config
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigFinding {
+    /// "mcp_conflict", "mcp_duplicate", "command_shadowed", "agent_shadowed" or "contradictory_directive"
+    pub kind: String,
+    /// "warning" or "info"
+    pub severity: String,
+    pub title: String,
+    pub detail: String,
+    pub suggestion: String,
+    pub locations: Vec<FindingLocation>,
+}
+
+fn project_name(project_path: &str) -> String {
+    Path::new(project_path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| project_path.to_string())
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+/// Line of the first `needle` after the first line containing `after` (if given)
+fn find_line(path: &Path, after: Option<&str>, needle: &str) -> Option<usize> {
+    let content = fs::read_to_string(path).ok()?;
+    let mut started = after.is_none();
+    for (index, line) in content.lines().enumerate() {
+        if !started {
+            started = after.is_some_and(|a| line.contains(a));
+            continue;
+        }
+        if line.contains(needle) {
+            return Some(index + 1);
+        }
+    }
+    None
+}
+
+// ============ MCP servers ============
+
+struct McpDefinition {
+    config: serde_json::Value,
+    location: FindingLocation,
+}
+
+fn collect_mcp(
+    definitions: &mut BTreeMap<String, Vec<McpDefinition>>,
+    servers: Option<&serde_json::Value>,
+    path: &Path,
+    after: Option<&str>,
+    label: &str,
+) {
+    let Some(servers) = servers.and_then(|s| s.as_object()) else {
+        return;
+    };
+    for (name, server) in servers {
+        definitions
+            .entry(name.clone())
+            .or_default()
+            .push(McpDefinition {
+                config: server.clone(),
+                location: FindingLocation {
+                    path: path.to_string_lossy().to_string(),
+                    line: find_line(path, after, &format!("\"{}\"", name)),
+                    label: label.to_string(),
+                    excerpt: None,
+                },
+            });
+    }
+}
+
+fn mcp_findings(projects: &[String]) -> Vec<ConfigFinding> {
+    let provider = paths::current();
+    let settings_path = provider.settings_json();
+    let claude_json_path = provider.claude_json();
+    let mut definitions: BTreeMap<String, Vec<McpDefinition>> = BTreeMap::new();
+
+    let settings = read_json(&settings_path);
+    collect_mcp(
+        &mut definitions,
+        settings.as_ref().and_then(|s| s.get("mcpServers")),
+        &settings_path,
+        None,
+        "Global (settings.json)",
+    );
+
+    let claude_json = read_json(claude_json_path);
+    collect_mcp(
+        &mut definitions,
+        claude_json.as_ref().and_then(|c| c.get("mcpServers")),
+        claude_json_path,
+        None,
+        "Global (.claude.json)",
+    );
+
+    for project in projects {
+        let name = project_name(project);
+        let project_servers = claude_json
+            .as_ref()
+            .and_then(|c| c.get("projects"))
+            .and_then(|p| p.get(project.as_str()))
+            .and_then(|p| p.get("mcpServers"));
+        collect_mcp(
+            &mut definitions,
+            project_servers,
+            claude_json_path,
+            Some(&format!("\"{}\"", project)),
+            &name,
+        );
+
+        // Checked-in project scope
+        let mcp_json = PathBuf::from(project).join(".mcp.json");
+        let shared = read_json(&mcp_json);
+        collect_mcp(
+            &mut definitions,
+            shared.as_ref().and_then(|s| s.get("mcpServers")),
+            &mcp_json,
+            None,
+            &format!("{} (.mcp.json)", name),
+        );
+    }
+
+    let mut findings = Vec::new();
+    for (name, defs) in definitions {
+        if defs.len() < 2 {
+            continue;
+        }
+        let distinct = defs
+            .iter()
+            .map(|d| d.config.to_string())
+            .collect::<HashSet<_>>()
+            .len();
+        let locations: Vec<FindingLocation> = defs.into_iter().map(|d| d.location).collect();
+        let scopes = locations
+            .iter()
+            .map(|l| l.label.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        findings.push(if distinct > 1 {
+            ConfigFinding {
+                kind: "mcp_conflict".to_string(),
+                severity: "warning".to_string(),
+                title: format!("MCP server '{}' is configured differently in {} places", name, locations.len()),
+                detail: format!("Defined in: {}. The project-level definition wins inside that project, so behavior depends on where Claude Code runs.", scopes),
+                suggestion: "Keep one definition, or rename the project-specific variant so both can coexist.".to_string(),
+                locations,
+            }
+        } else {
+            ConfigFinding {
+                kind: "mcp_duplicate".to_string(),
+                severity: "info".to_string(),
+                title: format!("MCP server '{}' is defined {} times with the same config", name, locations.len()),
+                detail: format!("Defined in: {}.", scopes),
+                suggestion: "Remove the project copies and keep the global definition.".to_string(),
+                locations,
+            }
+        });
+    }
+    findings
+}
+
+// ============ Commands & agents ============
+
+fn shadowed_findings(
+    kind: &str,
+    noun: &str,
+    global: Vec<config::AgentInfo>,
+    projects: &[(String, Vec<config::AgentInfo>)],
+) -> Vec<ConfigFinding> {
+    let mut findings = Vec::new();
+    for item in global.iter().filter(|i| !i.is_directory) {
+        let global_content = fs::read_to_string(&item.path).ok();
+        for (project, items) in projects {
+            let Some(local) = items
+                .iter()
+                .find(|i| !i.is_directory && i.name == item.name)
+            else {
+                continue;
+            };
+            let identical =
+                global_content.is_some() && fs::read_to_string(&local.path).ok() == global_content;
+            let (severity, detail, suggestion) = if identical {
+                (
+                    "info",
+                    format!(
+                        "The project copy of {} '{}' is identical to the global one.",
+                        noun, item.name
+                    ),
+                    "Delete the project copy; the global one is already available.".to_string(),
+                )
+            } else {
+                (
+                    "warning",
+                    format!(
+                        "The project version of {} '{}' overrides the global one inside {}.",
+                        noun,
+                        item.name,
+                        project_name(project)
+                    ),
+                    format!(
+                        "Rename one of them or merge the differences so it's clear which {} runs.",
+                        noun
+                    ),
+                )
+            };
+            findings.push(ConfigFinding {
+                kind: kind.to_string(),
+                severity: severity.to_string(),
+                title: format!(
+                    "{} '{}' exists globally and in {}",
+                    capitalize(noun),
+                    item.name,
+                    project_name(project)
+                ),
+                detail,
+                suggestion,
+                locations: vec![
+                    FindingLocation {
+                        path: item.path.clone(),
+                        line: Some(1),
+                        label: "Global".to_string(),
+                        excerpt: item.description.clone(),
+                    },
+                    FindingLocation {
+                        path: local.path.clone(),
+                        line: Some(1),
+                        label: project_name(project),
+                        excerpt: local.description.clone(),
+                    },
+                ],
+            });
+        }
+    }
+    findings
+}
+
+/// Commands share the agents' on-disk layout
+fn as_items(commands: Result<Vec<config::CommandInfo>, String>) -> Vec<config::AgentInfo> {
+    commands
+        .unwrap_or_default()
+        .into_iter()
+        .map(|c| config::AgentInfo {
+            name: c.name,
+            path: c.path,
+            description: c.description,
+            is_directory: c.is_directory,
+        })
+        .collect()
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().collect::<String>() + chars.as_str())
+        .unwrap_or_default()
+}
+
+// ============ CLAUDE.md directives ============
+
+const NEGATIVE: &[&str] = &[
+    "never",
+    "don't",
+    "do not",
+    "avoid",
+    "must not",
+    "should not",
+    "shouldn't",
+    "no longer",
+];
+const POSITIVE: &[&str] = &["always", "must", "should", "prefer", "use", "do"];
+const STOPWORDS: &[&str] = &[
+    "a", "an", "the", "to", "for", "of", "in", "on", "and", "or", "with", "when", "it", "be", "is",
+    "are", "you", "your", "we", "our", "this", "that", "any", "all", "instead", "please", "make",
+    "sure",
+];
+
+struct Directive {
+    negative: bool,
+    terms: HashSet<String>,
+    text: String,
+    path: String,
+    line: usize,
+    label: String,
+}
+
+/// Parse imperative bullet/sentence lines into polarity + content terms
+fn directives(path: &Path, label: &str) -> Vec<Directive> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let mut result = Vec::new();
+    let mut in_code = false;
+
+    for (index, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code || line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let text = line.trim_start_matches(['-', '*', '+', ' ']).trim();
+        let lower = format!(" {} ", text.to_lowercase().replace(['*', '`', '_'], ""));
+
+        let negative = NEGATIVE.iter().any(|w| lower.contains(&format!(" {} ", w)));
+        let positive = POSITIVE
+            .iter()
+            .any(|w| lower.starts_with(&format!(" {} ", w)) || lower.contains(&format!(" {} ", w)));
+        if !negative && !positive {
+            continue;
+        }
+
+        let mut stripped = lower.clone();
+        for word in NEGATIVE.iter().chain(POSITIVE) {
+            stripped = stripped.replace(&format!(" {} ", word), " ");
+        }
+        let terms: HashSet<String> = stripped
+            .split(|c: char| !c.is_alphanumeric() && c != '-' && c != '.')
+            .map(|w| w.trim_matches('.'))
+            .filter(|w| w.len() > 1 && !STOPWORDS.contains(w))
+            .map(str::to_string)
+            .collect();
+        if terms.len() < 2 {
+            continue;
+        }
+
+        result.push(Directive {
+            negative,
+            terms,
+            text: text.chars().take(200).collect(),
+            path: path.to_string_lossy().to_string(),
+            line: index + 1,
+            label: label.to_string(),
+        });
+    }
+    result
+}
+
+/// Two directives contradict when they have opposite polarity about mostly the same thing
+fn contradicts(a: &Directive, b: &Directive) -> bool {
+    if a.negative == b.negative {
+        return false;
+    }
+    let shared = a.terms.intersection(&b.terms).count();
+    let union = a.terms.union(&b.terms).count();
+    shared >= 2 && shared as f64 / union as f64 >= 0.6
+}
+
+fn contradiction_findings(projects: &[String]) -> Vec<ConfigFinding> {
+    let global = directives(&paths::current().config_dir().join("CLAUDE.md"), "Global");
+    let mut findings = Vec::new();
+    let mut push = |a: &Directive, b: &Directive| {
+        findings.push(ConfigFinding {
+            kind: "contradictory_directive".to_string(),
+            severity: "warning".to_string(),
+            title: format!(
+                "Contradicting CLAUDE.md instructions ({} / {})",
+                a.label, b.label
+            ),
+            detail: format!("\"{}\" vs \"{}\"", a.text, b.text),
+            suggestion:
+                "Remove or reword one of them; Claude receives both and has to guess which applies."
+                    .to_string(),
+            locations: [a, b]
+                .iter()
+                .map(|d| FindingLocation {
+                    path: d.path.clone(),
+                    line: Some(d.line),
+                    label: d.label.clone(),
+                    excerpt: Some(d.text.clone()),
+                })
+                .collect(),
+        });
+    };
+
+    for (i, a) in global.iter().enumerate() {
+        for b in &global[i + 1..] {
+            if contradicts(a, b) {
+                push(a, b);
+            }
+        }
+    }
+
+    for project in projects {
+        let root = PathBuf::from(project);
+        let name = project_name(project);
+        let local: Vec<Directive> = [
+            root.join("CLAUDE.md"),
+            root.join(".claude").join("CLAUDE.md"),
+            root.join("CLAUDE.local.md"),
+        ]
+        .iter()
+        .flat_map(|path| directives(path, &name))
+        .collect();
+
+        for (i, a) in local.iter().enumerate() {
+            for b in local[i + 1..].iter().chain(&global) {
+                if contradicts(a, b) {
+                    push(a, b);
+                }
+            }
+        }
+    }
+    findings
+}
+
+/// Analyze global and project config for duplicates and conflicts
+pub fn analyze() -> Vec<ConfigFinding> {
+    let projects: Vec<String> = config::registered_project_paths()
+        .into_iter()
+        .filter(|p| Path::new(p).is_dir())
+        .collect();
+
+    let mut findings = mcp_findings(&projects);
+
+    let project_commands: Vec<(String, Vec<config::AgentInfo>)> = projects
+        .iter()
+        .map(|p| (p.clone(), as_items(config::get_project_commands(p))))
+        .collect();
+    findings.extend(shadowed_findings(
+        "command_shadowed",
+        "command",
+        as_items(config::list_commands()),
+        &project_commands,
+    ));
+
+    let project_agents: Vec<(String, Vec<config::AgentInfo>)> = projects
+        .iter()
+        .map(|p| (p.clone(), config::get_project_agents(p).unwrap_or_default()))
+        .collect();
+    findings.extend(shadowed_findings(
+        "agent_shadowed",
+        "agent",
+        config::list_agents().unwrap_or_default(),
+        &project_agents,
+    ));
+
+    findings.extend(contradiction_findings(&projects));
+
+    // Warnings first
+    findings.sort_by_key(|f| f.severity != "warning");
+    findings
+}
@@ -0,0 +1,168 @@
+//! Data gap detection
+//!
+//! When the Claude CLI is installed and its hooks keep firing, but no usage
+//! has been recorded for `AppSettings::data_gap_days`, reading the session
+//! files is most likely broken (a log format change, a moved data folder, a
+//! wrong path mapping). The watchdog checks hourly and raises one diagnostic
+//! notification per gap, with the likely causes.
+
+use super::notifications::{self, PendingNotification};
+use super::usage::UsageReader;
+use super::{activity, analytics_db, get_claude_status, paths, SettingsService};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::{Instant, SystemTime};
+use tauri::{AppHandle, Emitter, Runtime};
+
+/// How often the watchdog looks for a gap
+const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+/// Hook events looked at for signs of Claude running
+const RECENT_HOOK_EVENTS: usize = 200;
+
+/// When the watchdog last checked
+static LAST_CHECK: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Newest usage entry when the last gap was reported, so a gap is reported once
+static REPORTED: Mutex<Option<Option<DateTime<Utc>>>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DataGapStatus {
+    /// The check is on (`data_gap_days` > 0)
+    pub enabled: bool,
+    pub threshold_days: u32,
+    /// Newest usage entry recorded
+    pub last_entry: Option<String>,
+    /// Newest hook event received
+    pub last_hook_event: Option<String>,
+    /// Hook events received since the newest usage entry
+    pub hook_events_since: usize,
+    /// Newest change to a session file
+    pub last_session_write: Option<String>,
+    pub cli_found: bool,
+    /// Usage stopped being recorded while Claude kept running
+    pub gap_detected: bool,
+    pub likely_causes: Vec<String>,
+}
+
+fn parse_time(timestamp: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Check whether usage stopped being recorded
+pub fn status() -> Result<DataGapStatus, String> {
+    let threshold_days = SettingsService::shared().get().data_gap_days;
+    let reader = UsageReader::new();
+    let last_entry = analytics_db::last_entry(&reader)?;
+    let cli_found = get_claude_status().claude_cli_found;
+
+    let hook_times: Vec<DateTime<Utc>> = activity::recent(RECENT_HOOK_EVENTS)
+        .iter()
+        .filter_map(|entry| parse_time(&entry.timestamp))
+        .collect();
+    let last_hook_event = hook_times.iter().max().copied();
+    let hook_events_since = hook_times
+        .iter()
+        .filter(|time| last_entry.is_none_or(|last| **time > last))
+        .count();
+
+    let session_files = reader.find_jsonl_files();
+    let last_session_write = session_files
+        .iter()
+        .filter_map(|path| path.metadata().and_then(|m| m.modified()).ok())
+        .max()
+        .map(|time: SystemTime| DateTime::<Utc>::from(time));
+
+    let now = Utc::now();
+    let cutoff = now - Duration::days(threshold_days as i64);
+    let gap_detected = threshold_days > 0
+        && cli_found
+        && last_entry.is_none_or(|last| last < cutoff)
+        && last_hook_event.is_some_and(|last| last >= cutoff);
+
+    let mut likely_causes = Vec::new();
+    if gap_detected {
+        let projects_dir = paths::current().projects_dir();
+        if !projects_dir.is_dir() {
+            likely_causes.push(format!(
+                "Claude's projects folder {} doesn't exist - check the active profile or CLAUDE_CONFIG_DIR",
+                projects_dir.display()
+            ));
+        } else if last_session_write.is_some_and(|write| last_entry.is_none_or(|last| write > last))
+        {
+            likely_causes.push(
+                "Session files are still being written, but no usage could be read from them - the log format may have changed; check for a claudit update".to_string(),
+            );
+        } else {
+            likely_causes.push(format!(
+                "Claude is running but not writing session files to {} - it may use another data folder (profile, WSL, container or remote path mapping)",
+                projects_dir.display()
+            ));
+        }
+        likely_causes.push(
+            "The analytics cache may be stuck - try a full rebuild under Settings > Analytics Cache".to_string(),
+        );
+    }
+
+    Ok(DataGapStatus {
+        enabled: threshold_days > 0,
+        threshold_days,
+        last_entry: last_entry.map(|t| t.to_rfc3339()),
+        last_hook_event: last_hook_event.map(|t| t.to_rfc3339()),
+        hook_events_since,
+        last_session_write: last_session_write.map(|t| t.to_rfc3339()),
+        cli_found,
+        gap_detected,
+        likely_causes,
+    })
+}
+
+/// Notify about a data gap, at most hourly and once per gap
+pub fn check<R: Runtime>(app: &AppHandle<R>) {
+    {
+        let mut last_check = LAST_CHECK.lock().unwrap();
+        if last_check.is_some_and(|at| at.elapsed() < CHECK_INTERVAL) {
+            return;
+        }
+        *last_check = Some(Instant::now());
+    }
+
+    let status = match status() {
+        Ok(status) => status,
+        Err(e) => {
+            eprintln!("Data gap check failed: {}", e);
+            return;
+        }
+    };
+    if !status.gap_detected {
+        *REPORTED.lock().unwrap() = None;
+        return;
+    }
+    let last_entry = status.last_entry.as_deref().and_then(parse_time);
+    {
+        let mut reported = REPORTED.lock().unwrap();
+        if *reported == Some(last_entry) {
+            return;
+        }
+        *reported = Some(last_entry);
+    }
+
+    let _ = app.emit("data-gap-detected", &status);
+    notifications::dispatch(
+        app,
+        PendingNotification {
+            title: "No Claude usage recorded".to_string(),
+            body: format!(
+                "Nothing for {}+ days while hooks keep firing. {}",
+                status.threshold_days,
+                status.likely_causes.first().cloned().unwrap_or_default()
+            ),
+            event: "DataGap".to_string(),
+            project: None,
+            session_id: None,
+        },
+    );
+}
@@ -0,0 +1,442 @@
+//! Demo mode
+//!
+//! Instead of ~/.claude, claudit reads a generated Claude data directory with
+//! sample projects, sessions, agents, commands and MCP servers. It lets the app
+//! be screenshotted without exposing real work and be tried without Claude Code
+//! installed. Demo mode is read-only: config writes are refused.
+//!
+//! It is turned on by the `demo_mode` setting or the `--demo` launch flag. The
+//! data is regenerated once a day so "today" always has activity.
+
+use super::paths;
+use super::settings::{app_config_dir, SettingsService};
+use chrono::{DateTime, Duration, Timelike, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set by `--demo`; overrides the setting for this run
+static FORCED: AtomicBool = AtomicBool::new(false);
+
+/// Marker with the date the demo data was generated for
+const MARKER: &str = ".claudit-demo";
+
+const PROJECT_NAMES: &[&str] = &[
+    "acme-web",
+    "payments-api",
+    "mobile-app",
+    "infra",
+    "docs-site",
+];
+
+const PROMPTS: &[&str] = &[
+    "Add pagination to the orders endpoint",
+    "Why is the login test flaky on CI?",
+    "Refactor the checkout form into smaller components",
+    "Write a migration that adds an index on customer_id",
+    "Fix the dark mode colors in the settings page",
+    "Explain how the retry logic in the webhook handler works",
+    "Add unit tests for the invoice calculator",
+    "Upgrade the router to the latest major version",
+    "Set up a GitHub Actions workflow for preview deploys",
+    "Find out why the bundle size doubled",
+];
+
+const SUMMARIES: &[&str] = &[
+    "Orders endpoint pagination",
+    "Flaky login test on CI",
+    "Checkout form refactor",
+    "Customer index migration",
+    "Dark mode color fixes",
+    "Webhook retry logic walkthrough",
+    "Invoice calculator tests",
+    "Router upgrade",
+    "Preview deploy workflow",
+    "Bundle size investigation",
+];
+
+const TOOLS: &[&str] = &["Read", "Edit", "Bash", "Grep", "Glob", "Write"];
+
+/// (model, share of requests in percent)
+const MODELS: &[(&str, u64)] = &[
+    ("claude-sonnet-4-5-20250929", 60),
+    ("claude-opus-4-1-20250805", 25),
+    ("claude-haiku-4-5-20251001", 15),
+];
+
+/// Size of a generated data set
+#[derive(Debug, Clone, Serialize)]
+pub struct SampleDataSpec {
+    pub projects: usize,
+    pub sessions_per_project: usize,
+    /// Assistant responses per session
+    pub messages_per_session: usize,
+    /// Days of history, ending today
+    pub days: i64,
+    /// Same seed, same data (apart from the dates)
+    pub seed: u64,
+}
+
+impl SampleDataSpec {
+    /// The data set shown in demo mode
+    pub fn demo() -> Self {
+        Self {
+            projects: PROJECT_NAMES.len(),
+            sessions_per_project: 12,
+            messages_per_session: 24,
+            days: 30,
+            seed: 42,
+        }
+    }
+}
+
+/// What `generate` wrote
+#[derive(Debug, Clone, Serialize)]
+pub struct GeneratedData {
+    pub dir: String,
+    pub session_files: usize,
+    pub log_lines: usize,
+    pub bytes: u64,
+}
+
+/// Small deterministic PRNG (xorshift64*), so demo data is stable between runs
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Uniform in `low..high`
+    fn range(&mut self, low: u64, high: u64) -> u64 {
+        low + self.next() % (high - low).max(1)
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.next() as usize % items.len()]
+    }
+
+    fn uuid(&mut self) -> String {
+        let (a, b) = (self.next(), self.next());
+        format!(
+            "{:08x}-{:04x}-4{:03x}-8{:03x}-{:012x}",
+            a >> 32,
+            (a >> 16) & 0xffff,
+            a & 0xfff,
+            b >> 52,
+            b & 0xffff_ffff_ffff
+        )
+    }
+
+    fn model(&mut self) -> &'static str {
+        let mut roll = self.range(0, 100);
+        for (model, share) in MODELS {
+            if roll < *share {
+                return model;
+            }
+            roll -= share;
+        }
+        MODELS[0].0
+    }
+}
+
+/// Whether claudit is showing demo data
+pub fn is_active() -> bool {
+    is_forced() || SettingsService::shared().get().demo_mode
+}
+
+/// Whether `--demo` was passed
+pub fn is_forced() -> bool {
+    FORCED.load(Ordering::Relaxed)
+}
+
+/// Turn demo mode on for this run (`--demo`)
+pub fn force() {
+    FORCED.store(true, Ordering::Relaxed);
+}
+
+/// Generated Claude data directory used in demo mode
+pub fn data_dir() -> PathBuf {
+    app_config_dir().join("demo")
+}
+
+/// Refuse a config change while demo mode is on
+pub fn ensure_writable() -> Result<(), String> {
+    if is_active() {
+        return Err("Demo mode is read-only; turn it off in Settings to change config".to_string());
+    }
+    Ok(())
+}
+
+/// Generate the demo data unless today's is already there
+pub fn ensure() -> Result<(), String> {
+    let dir = data_dir();
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    if fs::read_to_string(dir.join(MARKER)).ok().as_deref() == Some(today.as_str()) {
+        return Ok(());
+    }
+
+    if dir.exists() {
+        fs::remove_dir_all(&dir).map_err(|e| format!("Failed to clear demo data: {}", e))?;
+    }
+    generate(&dir, &SampleDataSpec::demo())?;
+    fs::write(dir.join(MARKER), today).map_err(|e| format!("Failed to write demo marker: {}", e))
+}
+
+fn project_path(index: usize) -> String {
+    let name = PROJECT_NAMES
+        .get(index)
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| format!("project-{}", index + 1));
+    format!("/Users/demo/code/{}", name)
+}
+
+fn write(path: &Path, contents: &str) -> Result<u64, String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(contents.len() as u64)
+}
+
+/// Log lines of one session, starting at `start`
+fn session_lines(
+    rng: &mut Rng,
+    spec: &SampleDataSpec,
+    cwd: &str,
+    session_id: &str,
+    start: DateTime<Utc>,
+) -> Vec<Value> {
+    let topic = rng.range(0, PROMPTS.len() as u64) as usize;
+    let mut lines = vec![json!({
+        "type": "summary",
+        "summary": SUMMARIES[topic],
+        "leafUuid": rng.uuid(),
+    })];
+    let mut time = start;
+    let mut parent: Option<String> = None;
+    let mut context = rng.range(8_000, 20_000);
+    let mut pending_tool: Option<String> = None;
+
+    let entry =
+        |kind: &str, uuid: String, parent: &Option<String>, time: DateTime<Utc>, message: Value| {
+            json!({
+                "type": kind,
+                "uuid": uuid,
+                "parentUuid": parent,
+                "sessionId": session_id,
+                "timestamp": time.to_rfc3339(),
+                "cwd": cwd,
+                "version": "2.0.0",
+                "message": message,
+            })
+        };
+
+    for turn in 0..spec.messages_per_session {
+        // Tool calls are answered by a tool result, otherwise the user prompts again
+        let uuid = rng.uuid();
+        let message = if let Some(tool_use_id) = pending_tool.take() {
+            json!({
+                "role": "user",
+                "content": [{ "type": "tool_result", "tool_use_id": tool_use_id, "content": "ok" }],
+            })
+        } else {
+            let prompt = if turn == 0 {
+                PROMPTS[topic]
+            } else {
+                rng.pick(PROMPTS)
+            };
+            json!({ "role": "user", "content": prompt })
+        };
+        lines.push(entry("user", uuid.clone(), &parent, time, message));
+        parent = Some(uuid);
+        time += Duration::seconds(rng.range(2, 40) as i64);
+
+        let model = rng.model();
+        let output = rng.range(80, 1_800);
+        let cache_creation = if turn == 0 {
+            context
+        } else {
+            rng.range(0, 3_000)
+        };
+        let content = if turn % 4 == 3 || turn + 1 == spec.messages_per_session {
+            json!([{ "type": "text", "text": "Done. The change is in place and the tests pass." }])
+        } else {
+            let tool_use_id = format!("toolu_{}", rng.uuid().replace('-', ""));
+            pending_tool = Some(tool_use_id.clone());
+            json!([
+                { "type": "text", "text": "Let me look at the relevant code." },
+                {
+                    "type": "tool_use",
+                    "id": tool_use_id,
+                    "name": rng.pick(TOOLS),
+                    "input": {},
+                },
+            ])
+        };
+        let uuid = rng.uuid();
+        let mut line = entry(
+            "assistant",
+            uuid.clone(),
+            &parent,
+            time,
+            json!({
+                "id": format!("msg_{}", uuid.replace('-', "")),
+                "role": "assistant",
+                "model": model,
+                "content": content,
+                "usage": {
+                    "input_tokens": rng.range(3, 400),
+                    "output_tokens": output,
+                    "cache_creation_input_tokens": cache_creation,
+                    "cache_read_input_tokens": context,
+                    "service_tier": "standard",
+                },
+            }),
+        );
+        let duration = rng.range(2_000, 45_000);
+        line["requestId"] = json!(format!("req_{}", &uuid[..8]));
+        line["durationMs"] = json!(duration);
+        line["ttftMs"] = json!(rng.range(400, 2_500).min(duration));
+        lines.push(line);
+
+        parent = Some(uuid);
+        context += output + cache_creation;
+        time +=
+            Duration::milliseconds(duration as i64) + Duration::seconds(rng.range(5, 240) as i64);
+    }
+    lines
+}
+
+/// Write a Claude data directory with sample data to `dir`
+pub fn generate(dir: &Path, spec: &SampleDataSpec) -> Result<GeneratedData, String> {
+    let mut rng = Rng::new(spec.seed);
+    let now = Utc::now();
+    let mut result = GeneratedData {
+        dir: dir.to_string_lossy().to_string(),
+        session_files: 0,
+        log_lines: 0,
+        bytes: 0,
+    };
+    let mut projects = serde_json::Map::new();
+
+    for index in 0..spec.projects {
+        let cwd = project_path(index);
+        let folder = dir
+            .join("projects")
+            .join(paths::encode_path_to_folder(&cwd));
+        let mut last_session = None;
+
+        for session in 0..spec.sessions_per_project {
+            let session_id = rng.uuid();
+            // The latest session of the first projects is today, the rest spread
+            // over the period with more activity in recent days
+            let days_ago = if session == 0 && index < 3 {
+                0
+            } else {
+                let r = rng.range(0, 1_000) as i64;
+                (spec.days - 1) * r * r / 1_000_000
+            };
+            let day = now - Duration::days(days_ago);
+            let hour = rng.range(8, 20) as u32;
+            let start = day
+                .with_hour(hour)
+                .and_then(|t| t.with_minute(rng.range(0, 60) as u32))
+                .unwrap_or(day);
+            // Sessions started earlier today must not end in the future
+            let start = if days_ago == 0 && start > now - Duration::hours(2) {
+                now - Duration::hours(2)
+            } else {
+                start
+            };
+
+            let lines = session_lines(&mut rng, spec, &cwd, &session_id, start);
+            let mut contents = String::new();
+            for line in &lines {
+                contents.push_str(&line.to_string());
+                contents.push('\n');
+            }
+            result.bytes += write(&folder.join(format!("{}.jsonl", session_id)), &contents)?;
+            result.session_files += 1;
+            result.log_lines += lines.len();
+            last_session = Some(session_id);
+        }
+
+        let mut project = json!({
+            "allowedTools": [],
+            "lastSessionId": last_session,
+            "lastCost": (rng.range(20, 900) as f64) / 100.0,
+            "lastDuration": rng.range(60_000, 3_600_000),
+            "lastTotalInputTokens": rng.range(1_000, 40_000),
+            "lastTotalOutputTokens": rng.range(2_000, 60_000),
+        });
+        if index == 1 {
+            project["mcpServers"] = json!({
+                "postgres": {
+                    "type": "stdio",
+                    "command": "npx",
+                    "args": ["-y", "@modelcontextprotocol/server-postgres", "postgresql://localhost/payments"],
+                }
+            });
+        }
+        projects.insert(cwd, project);
+    }
+
+    let claude_json = json!({
+        "numStartups": 128,
+        "projects": projects,
+        "mcpServers": {
+            "github": {
+                "type": "stdio",
+                "command": "npx",
+                "args": ["-y", "@modelcontextprotocol/server-github"],
+            },
+            "linear": { "type": "http", "url": "https://mcp.linear.app/mcp" },
+        },
+    });
+    result.bytes += write(
+        &dir.join(".claude.json"),
+        &serde_json::to_string_pretty(&claude_json).unwrap_or_default(),
+    )?;
+
+    let settings = json!({
+        "model": "sonnet",
+        "permissions": {
+            "allow": ["Bash(npm run test:*)", "Bash(git status)", "Read(*)"],
+            "deny": ["Read(./.env)"],
+        },
+    });
+    result.bytes += write(
+        &dir.join("settings.json"),
+        &serde_json::to_string_pretty(&settings).unwrap_or_default(),
+    )?;
+
+    result.bytes += write(
+        &dir.join("CLAUDE.md"),
+        "# Personal preferences\n\n- Prefer small, focused commits\n- Use pnpm, not npm\n- Run the tests before saying something is done\n",
+    )?;
+    result.bytes += write(
+        &dir.join("agents").join("code-reviewer.md"),
+        "---\nname: code-reviewer\ndescription: Reviews a diff for bugs, naming and missing tests\nmodel: sonnet\ntools: Read, Grep, Glob\n---\n\nYou are a careful reviewer. Point out bugs first, style last.\n",
+    )?;
+    result.bytes += write(
+        &dir.join("agents").join("test-writer.md"),
+        "---\nname: test-writer\ndescription: Writes unit tests for recently changed code\nmodel: haiku\n---\n\nWrite focused unit tests that follow the project's existing test style.\n",
+    )?;
+    result.bytes += write(
+        &dir.join("commands").join("release-notes.md"),
+        "---\ndescription: Draft release notes from the commits since the last tag\n---\n\nSummarize the commits since the last tag as user-facing release notes.\n",
+    )?;
+
+    Ok(result)
+}
@@ -0,0 +1,258 @@
+//! Chart downsampling
+//!
+//! A year of daily points is more than a chart can show and a lot to send over
+//! IPC. `get_chart_data` can sum the daily series into weekly or monthly
+//! buckets (for bar charts, where totals must stay exact) and then thin it to
+//! a maximum number of points with Largest-Triangle-Three-Buckets, which keeps
+//! the peaks and dips that give a line chart its shape.
+
+use crate::types::{ChartBucket, ChartData, DailyStats};
+use chrono::{Datelike, Duration, NaiveDate};
+
+impl ChartBucket {
+    fn resolve(self, days: u32) -> Self {
+        match self {
+            ChartBucket::Auto if days <= 90 => ChartBucket::Day,
+            ChartBucket::Auto if days <= 366 => ChartBucket::Week,
+            ChartBucket::Auto => ChartBucket::Month,
+            bucket => bucket,
+        }
+    }
+
+    /// First day of the bucket `date` falls in
+    fn start(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            ChartBucket::Week => {
+                date - Duration::days(date.weekday().num_days_from_monday() as i64)
+            }
+            ChartBucket::Month => date.with_day(1).unwrap_or(date),
+            _ => date,
+        }
+    }
+}
+
+/// Sum daily stats into `bucket`-sized points dated by the bucket's first day
+fn bucket_daily(daily: Vec<DailyStats>, bucket: ChartBucket) -> Vec<DailyStats> {
+    if bucket == ChartBucket::Day {
+        return daily;
+    }
+
+    let mut buckets: Vec<DailyStats> = Vec::new();
+    for day in daily {
+        let Ok(date) = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d") else {
+            continue;
+        };
+        let key = bucket.start(date).format("%Y-%m-%d").to_string();
+
+        // Input is sorted by date, so a bucket's days are consecutive
+        match buckets.last_mut() {
+            Some(current) if current.date == key => {
+                current.input_tokens += day.input_tokens;
+                current.output_tokens += day.output_tokens;
+                current.cost += day.cost;
+                current.messages += day.messages;
+                // Every day lists the same models in the same order
+                for (total, model) in current.by_model.iter_mut().zip(day.by_model) {
+                    total.input_tokens += model.input_tokens;
+                    total.output_tokens += model.output_tokens;
+                    total.cost += model.cost;
+                    total.messages += model.messages;
+                }
+            }
+            _ => buckets.push(DailyStats { date: key, ..day }),
+        }
+    }
+    buckets
+}
+
+/// Indices of the points Largest-Triangle-Three-Buckets keeps to draw `values`
+/// with at most `threshold` points (always the first and last)
+fn lttb_indices(values: &[f64], threshold: usize) -> Vec<usize> {
+    let len = values.len();
+    if threshold >= len || threshold < 3 {
+        return (0..len).collect();
+    }
+
+    let mut kept = Vec::with_capacity(threshold);
+    kept.push(0);
+    // Points between the first and last, split into threshold - 2 buckets
+    let every = (len - 2) as f64 / (threshold - 2) as f64;
+    let mut previous = 0;
+
+    for bucket in 0..threshold - 2 {
+        let start = (bucket as f64 * every) as usize + 1;
+        let end = ((bucket + 1) as f64 * every) as usize + 1;
+
+        // Average of the next bucket (the last point, for the last bucket) is
+        // the third corner of the triangle
+        let next_end = (((bucket + 2) as f64 * every) as usize + 1).min(len);
+        let next = &values[end..next_end];
+        let avg_x = (end + next_end - 1) as f64 / 2.0;
+        let avg_y = next.iter().sum::<f64>() / next.len() as f64;
+
+        let (px, py) = (previous as f64, values[previous]);
+        let mut best = start;
+        let mut best_area = -1.0;
+        for (i, &y) in values.iter().enumerate().take(end).skip(start) {
+            let area = ((px - avg_x) * (y - py) - (px - i as f64) * (avg_y - py)).abs();
+            if area > best_area {
+                best_area = area;
+                best = i;
+            }
+        }
+        kept.push(best);
+        previous = best;
+    }
+
+    kept.push(len - 1);
+    kept
+}
+
+/// Bucket `chart.daily` and thin it to `max_points` (by total tokens).
+/// `days` is the range the chart covers, used by `ChartBucket::Auto`.
+pub fn apply(chart: &mut ChartData, days: u32, bucket: ChartBucket, max_points: Option<usize>) {
+    let bucket = bucket.resolve(days);
+    chart.daily = bucket_daily(std::mem::take(&mut chart.daily), bucket);
+    chart.bucket = bucket;
+
+    if let Some(max_points) = max_points {
+        let totals: Vec<f64> = chart
+            .daily
+            .iter()
+            .map(|d| (d.input_tokens + d.output_tokens) as f64)
+            .collect();
+        let kept = lttb_indices(&totals, max_points);
+        if kept.len() < chart.daily.len() {
+            let daily = std::mem::take(&mut chart.daily);
+            let mut kept = kept.into_iter().peekable();
+            chart.daily = daily
+                .into_iter()
+                .enumerate()
+                .filter(|(i, _)| kept.next_if_eq(i).is_some())
+                .map(|(_, d)| d)
+                .collect();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DailyModelStats;
+
+    fn day(date: NaiveDate, tokens: u64) -> DailyStats {
+        DailyStats {
+            date: date.format("%Y-%m-%d").to_string(),
+            input_tokens: tokens,
+            output_tokens: 1,
+            cost: 0.5,
+            messages: 1,
+            by_model: vec![DailyModelStats {
+                model: "claude-sonnet-4".to_string(),
+                input_tokens: tokens,
+                output_tokens: 1,
+                cost: 0.5,
+                messages: 1,
+            }],
+        }
+    }
+
+    /// One point per day from `from` to `to` (inclusive), `tokens` input tokens each
+    fn days(from: &str, to: &str, tokens: u64) -> Vec<DailyStats> {
+        let from = NaiveDate::parse_from_str(from, "%Y-%m-%d").unwrap();
+        let to = NaiveDate::parse_from_str(to, "%Y-%m-%d").unwrap();
+        from.iter_days()
+            .take_while(|date| *date <= to)
+            .map(|date| day(date, tokens))
+            .collect()
+    }
+
+    fn chart(daily: Vec<DailyStats>) -> ChartData {
+        ChartData {
+            daily,
+            hourly: Vec::new(),
+            by_model: Vec::new(),
+            by_project: Vec::new(),
+            bucket: ChartBucket::Day,
+        }
+    }
+
+    #[test]
+    fn test_lttb_keeps_first_last_and_peaks() {
+        let mut values: Vec<f64> = (0..100).map(|i| (i % 7) as f64).collect();
+        values[50] = 1000.0;
+        let kept = lttb_indices(&values, 10);
+        assert_eq!(kept.len(), 10);
+        assert_eq!(kept[0], 0);
+        assert_eq!(kept[9], 99);
+        assert!(kept.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(kept.contains(&50), "the spike survives: {:?}", kept);
+    }
+
+    #[test]
+    fn test_lttb_keeps_everything_within_threshold() {
+        let values = [3.0, 1.0, 4.0, 1.0, 5.0];
+        assert_eq!(lttb_indices(&values, 5), vec![0, 1, 2, 3, 4]);
+        assert_eq!(lttb_indices(&values, 50), vec![0, 1, 2, 3, 4]);
+        // Fewer than three points can't keep both ends and a shape
+        assert_eq!(lttb_indices(&values, 2), vec![0, 1, 2, 3, 4]);
+        assert!(lttb_indices(&[], 10).is_empty());
+        assert_eq!(lttb_indices(&values, 3).len(), 3);
+    }
+
+    #[test]
+    fn test_month_buckets_across_month_ends() {
+        // 2024 is a leap year
+        let buckets = bucket_daily(days("2024-01-30", "2024-03-02", 10), ChartBucket::Month);
+        let dates: Vec<&str> = buckets.iter().map(|b| b.date.as_str()).collect();
+        assert_eq!(dates, ["2024-01-01", "2024-02-01", "2024-03-01"]);
+        let messages: Vec<u64> = buckets.iter().map(|b| b.messages).collect();
+        assert_eq!(messages, [2, 29, 2]);
+        assert_eq!(buckets[1].input_tokens, 290);
+        assert_eq!(buckets[1].output_tokens, 29);
+        assert!((buckets[1].cost - 14.5).abs() < 1e-9);
+        assert_eq!(buckets[1].by_model[0].input_tokens, 290);
+        assert_eq!(buckets[1].by_model[0].messages, 29);
+    }
+
+    #[test]
+    fn test_week_buckets_across_month_and_year_ends() {
+        // Weeks start on Monday: 2024-02-26 and 2024-03-04
+        let buckets = bucket_daily(days("2024-02-28", "2024-03-05", 1), ChartBucket::Week);
+        let weeks: Vec<(&str, u64)> = buckets
+            .iter()
+            .map(|b| (b.date.as_str(), b.messages))
+            .collect();
+        assert_eq!(weeks, [("2024-02-26", 5), ("2024-03-04", 2)]);
+
+        let buckets = bucket_daily(days("2024-12-29", "2025-01-06", 1), ChartBucket::Week);
+        let weeks: Vec<(&str, u64)> = buckets
+            .iter()
+            .map(|b| (b.date.as_str(), b.messages))
+            .collect();
+        assert_eq!(
+            weeks,
+            [("2024-12-23", 1), ("2024-12-30", 7), ("2025-01-06", 1)]
+        );
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut year = chart(days("2024-01-01", "2024-12-31", 10));
+        apply(&mut year, 400, ChartBucket::Auto, None);
+        assert_eq!(year.bucket, ChartBucket::Month);
+        assert_eq!(year.daily.len(), 12);
+        assert_eq!(
+            year.daily.iter().map(|d| d.messages).sum::<u64>(),
+            366,
+            "bucketing keeps the totals"
+        );
+
+        let mut thinned = chart(days("2024-01-01", "2024-03-31", 10));
+        apply(&mut thinned, 90, ChartBucket::Auto, Some(30));
+        assert_eq!(thinned.bucket, ChartBucket::Day);
+        assert_eq!(thinned.daily.len(), 30);
+        assert_eq!(thinned.daily[0].date, "2024-01-01");
+        assert_eq!(thinned.daily[29].date, "2024-03-31");
+    }
+}
@@ -0,0 +1,319 @@
+//! Duplicate session detection
+//!
+//! Claude Code sometimes writes near-duplicate session files (continued sessions,
+//! copied transcripts). Files whose message UUIDs mostly overlap another file are
+//! reported here, and the user can merge them into the original session or ignore
+//! them. Resolutions are stored in `duplicate-sessions.json` in claudit's config
+//! directory and applied by `UsageReader::read_entries`.
+
+use super::pricing::calculate_cost;
+use super::settings::app_config_dir;
+use super::UsageReader;
+use crate::types::RawLogEntry;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+/// Share of the smaller file's UUIDs that must also appear in the other file
+const MIN_OVERLAP: f64 = 0.5;
+/// Ignore tiny overlaps (e.g. a shared summary line)
+const MIN_SHARED_UUIDS: usize = 5;
+
+/// What to do with a duplicate session file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateAction {
+    /// Count its unique messages as part of the primary session
+    Merge,
+    /// Leave the file out of analytics entirely
+    Ignore,
+}
+
+/// A stored decision about a duplicate file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateResolution {
+    pub file: String,
+    pub primary_file: String,
+    pub primary_session_id: String,
+    pub action: DuplicateAction,
+    pub resolved_at: String,
+}
+
+/// Summary of one session file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionFileSummary {
+    pub path: String,
+    pub session_id: String,
+    pub project: String,
+    /// Lines with a UUID
+    pub messages: usize,
+    pub cost: f64,
+    pub first_timestamp: Option<String>,
+    pub last_timestamp: Option<String>,
+}
+
+/// A session file that largely duplicates another one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateSessionPair {
+    /// The larger file, kept as the original
+    pub primary: SessionFileSummary,
+    pub duplicate: SessionFileSummary,
+    pub shared_messages: usize,
+    /// Shared messages as a share of the duplicate's messages (0-100)
+    pub overlap_percent: f64,
+    /// Cost of the duplicate's responses that also appear in the primary
+    pub duplicated_cost: f64,
+    /// Part of `duplicated_cost` that UUID deduplication misses (same API message,
+    /// new UUID) and that is currently counted twice
+    pub double_counted_cost: f64,
+    pub resolution: Option<DuplicateAction>,
+}
+
+/// All duplicate session files found
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateReport {
+    pub pairs: Vec<DuplicateSessionPair>,
+    /// Double-counted cost of unresolved pairs
+    pub inflated_cost: f64,
+    /// Sessions counted twice because of unresolved pairs
+    pub extra_sessions: usize,
+}
+
+/// Resolutions in the form `read_entries` needs them
+#[derive(Debug, Default)]
+pub struct DuplicateRules {
+    pub ignored: HashSet<PathBuf>,
+    /// Merged file -> session ID of the primary
+    pub merged: HashMap<PathBuf, String>,
+}
+
+fn resolutions_path() -> PathBuf {
+    app_config_dir().join("duplicate-sessions.json")
+}
+
+pub fn load_resolutions() -> Vec<DuplicateResolution> {
+    fs::read_to_string(resolutions_path())
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save_resolutions(resolutions: &[DuplicateResolution]) -> Result<(), String> {
+    let contents =
+        serde_json::to_string_pretty(resolutions).map_err(|e| format!("Serialize error: {}", e))?;
+    fs::write(resolutions_path(), contents).map_err(|e| format!("Write error: {}", e))
+}
+
+/// Stored resolutions, for applying while reading usage
+pub fn rules() -> DuplicateRules {
+    let mut rules = DuplicateRules::default();
+    for resolution in load_resolutions() {
+        let file = PathBuf::from(resolution.file);
+        match resolution.action {
+            DuplicateAction::Ignore => {
+                rules.ignored.insert(file);
+            }
+            DuplicateAction::Merge => {
+                rules.merged.insert(file, resolution.primary_session_id);
+            }
+        }
+    }
+    rules
+}
+
+/// Everything needed from a file to compare it with others
+struct ScannedFile {
+    summary: SessionFileSummary,
+    uuids: HashSet<String>,
+    /// (uuid, API message ID, cost) of each response with usage
+    responses: Vec<(String, Option<String>, f64)>,
+}
+
+fn scan_file(reader: &UsageReader, path: &Path) -> Option<ScannedFile> {
+    let file = File::open(path).ok()?;
+    let mut uuids = HashSet::new();
+    let mut responses = Vec::new();
+    let mut session_id = None;
+    let mut first_timestamp: Option<String> = None;
+    let mut last_timestamp: Option<String> = None;
+
+    for line in BufReader::new(file).lines().map_while(Result::ok) {
+        let Ok(raw) = serde_json::from_str::<RawLogEntry>(&line) else {
+            continue;
+        };
+        if session_id.is_none() {
+            session_id = raw.session_id.clone();
+        }
+        if let Some(ts) = &raw.timestamp {
+            if first_timestamp.as_ref().is_none_or(|first| ts < first) {
+                first_timestamp = Some(ts.clone());
+            }
+            if last_timestamp.as_ref().is_none_or(|last| ts > last) {
+                last_timestamp = Some(ts.clone());
+            }
+        }
+        let Some(uuid) = raw.uuid else {
+            continue;
+        };
+        if let Some(message) = raw
+            .message
+            .filter(|m| m.role.as_deref() == Some("assistant"))
+        {
+            if let (Some(usage), Some(model)) = (&message.usage, &message.model) {
+                let timestamp = raw
+                    .timestamp
+                    .as_deref()
+                    .and_then(|ts| ts.parse::<DateTime<Utc>>().ok());
+                let cost = calculate_cost(
+                    model,
+                    timestamp,
+                    usage.service_tier.as_deref(),
+                    usage.input_tokens.unwrap_or(0),
+                    usage.output_tokens.unwrap_or(0),
+                    usage.cache_creation_input_tokens.unwrap_or(0),
+                    usage.cache_read_input_tokens.unwrap_or(0),
+                );
+                responses.push((uuid.clone(), message.id.clone(), cost));
+            }
+        }
+        uuids.insert(uuid);
+    }
+
+    let cost = responses.iter().map(|(_, _, cost)| cost).sum();
+    Some(ScannedFile {
+        summary: SessionFileSummary {
+            path: path.to_string_lossy().to_string(),
+            session_id: session_id.unwrap_or_else(|| {
+                path.file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default()
+            }),
+            project: reader.extract_project_path(path),
+            messages: uuids.len(),
+            cost,
+            first_timestamp,
+            last_timestamp,
+        },
+        uuids,
+        responses,
+    })
+}
+
+/// Compare the duplicate's responses with the primary's
+fn duplicated_costs(primary: &ScannedFile, duplicate: &ScannedFile) -> (f64, f64) {
+    let primary_ids: HashSet<&str> = primary
+        .responses
+        .iter()
+        .filter_map(|(_, id, _)| id.as_deref())
+        .collect();
+
+    let mut duplicated = 0.0;
+    let mut double_counted = 0.0;
+    for (uuid, id, cost) in &duplicate.responses {
+        if primary.uuids.contains(uuid) {
+            duplicated += cost;
+        } else if id.as_deref().is_some_and(|id| primary_ids.contains(id)) {
+            duplicated += cost;
+            double_counted += cost;
+        }
+    }
+    (duplicated, double_counted)
+}
+
+/// Find session files that largely duplicate another file
+pub fn find_duplicates() -> DuplicateReport {
+    let reader = UsageReader::new();
+    let files: Vec<ScannedFile> = reader
+        .find_jsonl_files()
+        .iter()
+        .filter_map(|path| scan_file(&reader, path))
+        .filter(|file| !file.uuids.is_empty())
+        .collect();
+
+    // Count shared UUIDs per file pair through a UUID -> files index
+    let mut index: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, file) in files.iter().enumerate() {
+        for uuid in &file.uuids {
+            index.entry(uuid.as_str()).or_default().push(i);
+        }
+    }
+    let mut shared: HashMap<(usize, usize), usize> = HashMap::new();
+    for owners in index.values().filter(|owners| owners.len() > 1) {
+        for (n, &a) in owners.iter().enumerate() {
+            for &b in &owners[n + 1..] {
+                *shared.entry((a.min(b), a.max(b))).or_default() += 1;
+            }
+        }
+    }
+
+    let resolutions: HashMap<String, DuplicateAction> = load_resolutions()
+        .into_iter()
+        .map(|r| (r.file, r.action))
+        .collect();
+
+    let mut pairs = Vec::new();
+    for ((a, b), count) in shared {
+        // The file with fewer messages is the copy
+        let (primary, duplicate) = if files[a].uuids.len() >= files[b].uuids.len() {
+            (&files[a], &files[b])
+        } else {
+            (&files[b], &files[a])
+        };
+        let overlap = count as f64 / duplicate.uuids.len() as f64;
+        if count < MIN_SHARED_UUIDS || overlap < MIN_OVERLAP {
+            continue;
+        }
+
+        let (duplicated_cost, double_counted_cost) = duplicated_costs(primary, duplicate);
+        pairs.push(DuplicateSessionPair {
+            primary: primary.summary.clone(),
+            duplicate: duplicate.summary.clone(),
+            shared_messages: count,
+            overlap_percent: overlap * 100.0,
+            duplicated_cost,
+            double_counted_cost,
+            resolution: resolutions.get(&duplicate.summary.path).copied(),
+        });
+    }
+    pairs.sort_by(|a, b| b.duplicated_cost.total_cmp(&a.duplicated_cost));
+
+    let unresolved = pairs.iter().filter(|p| p.resolution.is_none());
+    let inflated_cost = unresolved.clone().map(|p| p.double_counted_cost).sum();
+    let extra_sessions = unresolved
+        .filter(|p| p.primary.session_id != p.duplicate.session_id)
+        .count();
+
+    DuplicateReport {
+        pairs,
+        inflated_cost,
+        extra_sessions,
+    }
+}
+
+/// Merge or ignore a duplicate file; `None` removes the resolution
+pub fn resolve(
+    file: &str,
+    primary_file: &str,
+    action: Option<DuplicateAction>,
+) -> Result<(), String> {
+    let mut resolutions = load_resolutions();
+    resolutions.retain(|r| r.file != file);
+
+    if let Some(action) = action {
+        let reader = UsageReader::new();
+        let primary = scan_file(&reader, Path::new(primary_file))
+            .ok_or_else(|| format!("Could not read {}", primary_file))?;
+        resolutions.push(DuplicateResolution {
+            file: file.to_string(),
+            primary_file: primary_file.to_string(),
+            primary_session_id: primary.summary.session_id,
+            action,
+            resolved_at: chrono::Utc::now().to_rfc3339(),
+        });
+    }
+
+    save_resolutions(&resolutions)
+}
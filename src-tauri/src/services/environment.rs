@@ -1,4 +1,4 @@
-use crate::services::SettingsService;
+use crate::services::{paths, SettingsService};
 use crate::types::ClaudeStatus;
 use std::path::PathBuf;
 
@@ -8,16 +8,13 @@ fn exists(p: &PathBuf) -> bool {
 
 pub fn get_claude_status() -> ClaudeStatus {
     let home = dirs::home_dir();
+    let paths = paths::current();
 
-    let (claude_dir, claude_projects_dir, claude_json_path) = if let Some(home) = &home {
-        (
-            home.join(".claude"),
-            home.join(".claude").join("projects"),
-            home.join(".claude.json"),
-        )
-    } else {
-        (PathBuf::new(), PathBuf::new(), PathBuf::new())
-    };
+    let (claude_dir, claude_projects_dir, claude_json_path) = (
+        paths.config_dir().to_path_buf(),
+        paths.projects_dir(),
+        paths.claude_json().to_path_buf(),
+    );
 
     let claude_settings_path = if !claude_dir.as_os_str().is_empty() {
         claude_dir.join("settings.json")
@@ -26,7 +23,7 @@ pub fn get_claude_status() -> ClaudeStatus {
     };
 
     // Detect CLI binary
-    let settings = SettingsService::new().get();
+    let settings = SettingsService::shared().get();
     let custom_cli = settings.claude_cli_path.clone().map(PathBuf::from);
 
     let mut cli_candidates: Vec<PathBuf> = Vec::new();
@@ -0,0 +1,225 @@
+//! Cost and label formatting
+//!
+//! Turns dollar amounts (and token counts) into text for the tray, notifications, exports and
+//! reports, so they all round the same way. Precision, rounding mode and
+//! separators come from `AppSettings::cost_format`; the wording of tray
+//! labels and notifications (terse or verbose, emoji, screen-reader
+//! phrasing) from `AppSettings::text_style`.
+
+use super::SettingsService;
+use crate::types::{CostFormat, LabelStyle, RoundingMode, TextStyle};
+
+/// Cost format in the current settings
+pub fn current() -> CostFormat {
+    SettingsService::shared().get().cost_format
+}
+
+/// Decimal places for `amount`: the configured precision, or more places
+/// for smaller amounts
+fn precision(format: &CostFormat, amount: f64) -> u32 {
+    match format.precision {
+        Some(places) => places.min(6) as u32,
+        None if amount.abs() >= 1.0 => 2,
+        None if amount.abs() >= 0.01 => 3,
+        None => 4,
+    }
+}
+
+/// `amount` in units of 10^-places, rounded with `mode`
+fn round(amount: f64, places: u32, mode: RoundingMode) -> i64 {
+    // Drop float noise first, so 1.005 (stored as 1.00499...) rounds like 1.005
+    let scaled = (amount * 10f64.powi(places as i32) * 1e6).round() / 1e6;
+    let rounded = match mode {
+        RoundingMode::HalfUp => scaled.round(),
+        RoundingMode::HalfEven => scaled.round_ties_even(),
+        RoundingMode::Down => scaled.trunc(),
+        RoundingMode::Up => scaled.signum() * scaled.abs().ceil(),
+    };
+    rounded as i64
+}
+
+/// Digits of `n` with `separator` between groups of thousands
+fn group_thousands(n: u64, separator: &str) -> String {
+    let digits = n.to_string();
+    if separator.is_empty() {
+        return digits;
+    }
+    let mut grouped = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push_str(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// `amount` as dollars in `format`, e.g. `$1,234.57` or `-$0.004`
+pub fn cost_with(format: &CostFormat, amount: f64) -> String {
+    let places = precision(format, amount);
+    let units = round(amount, places, format.rounding);
+    let factor = 10u64.pow(places);
+    let sign = if units < 0 { "-" } else { "" };
+    let whole = group_thousands(units.unsigned_abs() / factor, &format.thousands_separator);
+    if places == 0 {
+        return format!("{}${}", sign, whole);
+    }
+    format!(
+        "{}${}{}{:0width$}",
+        sign,
+        whole,
+        format.decimal_separator,
+        units.unsigned_abs() % factor,
+        width = places as usize
+    )
+}
+
+/// `amount` as dollars in the configured format
+pub fn cost(amount: f64) -> String {
+    cost_with(&current(), amount)
+}
+
+/// Token count with a k/M suffix, e.g. `1.2M`
+pub fn tokens(count: u64) -> String {
+    match count {
+        n if n >= 1_000_000 => format!("{:.1}M", n as f64 / 1_000_000.0),
+        n if n >= 1_000 => format!("{:.1}k", n as f64 / 1_000.0),
+        n => n.to_string(),
+    }
+}
+
+/// Spending rate per hour, e.g. `$2.40/hr` or `$2.40 per hour`
+pub fn rate(per_hour: f64) -> String {
+    let unit = if verbose() { " per hour" } else { "/hr" };
+    if per_hour > 0.0 {
+        format!("{}{}", cost(per_hour), unit)
+    } else {
+        format!("$0{}", unit)
+    }
+}
+
+/// Text style in the current settings
+pub fn text_style() -> TextStyle {
+    SettingsService::shared().get().text_style
+}
+
+/// Labels are spelled out for screen readers
+pub fn verbose() -> bool {
+    text_style().labels == LabelStyle::Verbose
+}
+
+/// Count in the label style, e.g. `1.2M` or `1.2 million`
+pub fn count(n: u64) -> String {
+    if !verbose() {
+        return tokens(n);
+    }
+    match n {
+        n if n >= 1_000_000 => format!("{:.1} million", n as f64 / 1_000_000.0),
+        n if n >= 1_000 => format!("{:.1} thousand", n as f64 / 1_000.0),
+        n => n.to_string(),
+    }
+}
+
+/// Token count with its unit, e.g. `1.2M tok` or `1.2 million tokens`
+pub fn token_count(n: u64) -> String {
+    let unit = if verbose() { "tokens" } else { "tok" };
+    format!("{} {}", count(n), unit)
+}
+
+/// Input and output tokens, e.g. `1.2M in / 3.4k out` or
+/// `1.2 million input, 3.4 thousand output`
+pub fn in_out(input: u64, output: u64) -> String {
+    if verbose() {
+        format!("{} input, {} output", count(input), count(output))
+    } else {
+        format!("{} in / {} out", count(input), count(output))
+    }
+}
+
+/// Duration in seconds, e.g. `1h 05m` or `1 hour 5 minutes`
+pub fn elapsed(seconds: i64) -> String {
+    let minutes = seconds.max(0) / 60;
+    let (hours, minutes) = (minutes / 60, minutes % 60);
+    if !verbose() {
+        return if hours > 0 {
+            format!("{}h {:02}m", hours, minutes)
+        } else {
+            format!("{}m", minutes)
+        };
+    }
+    let plural = |n: i64, unit: &str| format!("{} {}{}", n, unit, if n == 1 { "" } else { "s" });
+    match hours {
+        0 => plural(minutes, "minute"),
+        _ if minutes == 0 => plural(hours, "hour"),
+        _ => format!("{} {}", plural(hours, "hour"), plural(minutes, "minute")),
+    }
+}
+
+/// Display name of a project: the last component of its path
+pub(crate) fn project_name(path: &str) -> Option<String> {
+    std::path::Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+}
+
+/// Separator between the parts of a label: ` · `, or `, ` when spelled out
+pub fn separator() -> &'static str {
+    if verbose() {
+        ", "
+    } else {
+        " · "
+    }
+}
+
+/// `text` after `emoji` when emoji prefixes are on
+pub fn prefixed(emoji: &str, text: &str) -> String {
+    if text_style().emoji_prefixes {
+        format!("{} {}", emoji, text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// A warning label: `⚠️` with emoji prefixes, `⚠` when terse, plain when spelled out
+pub fn warning(text: &str) -> String {
+    let style = text_style();
+    if style.emoji_prefixes {
+        format!("⚠️ {}", text)
+    } else if style.labels == LabelStyle::Verbose {
+        text.to_string()
+    } else {
+        format!("⚠ {}", text)
+    }
+}
+
+/// At most `max_chars` of `text`. With screen-reader phrasing the cut falls
+/// between words and ends in `…`, otherwise it can fall mid-word.
+pub fn excerpt(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max_chars).collect();
+    if !text_style().screen_reader_notifications {
+        return format!("{}...", cut);
+    }
+    let words = match cut.rfind(char::is_whitespace) {
+        Some(end) if end > 0 => &cut[..end],
+        _ => cut.as_str(),
+    };
+    format!(
+        "{}…",
+        words.trim_end_matches(|c: char| c.is_whitespace() || c.is_ascii_punctuation())
+    )
+}
+
+/// Notification text as shown: with screen-reader phrasing, separators become
+/// commas and symbols that are read out literally are dropped
+pub fn notification_text(text: &str) -> String {
+    if !text_style().screen_reader_notifications {
+        return text.to_string();
+    }
+    text.replace(" · ", ", ")
+        .replace(['⚠', '\u{fe0f}'], "")
+        .trim()
+        .to_string()
+}
@@ -0,0 +1,346 @@
+//! Goal mode
+//!
+//! Tracks adherence to a daily/weekly spend or daily output target. Completed
+//! periods are recorded in the analytics database (with the target that
+//! applied at the time) so the goals chart survives target changes. Earlier
+//! versions kept them in `goal-history.json`, which is moved there on first use.
+
+use super::analytics_db;
+use super::settings::app_config_dir;
+use super::AnalyticsService;
+use crate::types::{DailyStats, GoalKind, GoalSettings};
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// How far back the history is backfilled when goal mode is first used
+const BACKFILL_DAYS: u32 = 90;
+
+/// Outcome of one completed (or the current) goal period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalRecord {
+    /// First day of the period (YYYY-MM-DD)
+    pub period_start: String,
+    pub kind: GoalKind,
+    pub target: f64,
+    pub actual: f64,
+    pub met: bool,
+}
+
+/// Current goal status for the dashboard and tray
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalProgress {
+    pub goal: GoalSettings,
+    /// The period in progress
+    pub current: GoalRecord,
+    /// Progress towards the target (0-100+)
+    pub percent: f64,
+    /// Consecutive completed periods meeting the goal, up to the last completed one
+    pub current_streak: u32,
+    pub best_streak: u32,
+    /// Share of recorded periods that met the goal (0-100)
+    pub adherence: f64,
+    /// Completed periods, oldest first
+    pub history: Vec<GoalRecord>,
+}
+
+/// Where earlier versions kept the history
+fn legacy_history_path() -> PathBuf {
+    app_config_dir().join("goal-history.json")
+}
+
+/// Move `goal-history.json` into the analytics database
+fn migrate_legacy_history() -> Result<(), String> {
+    let path = legacy_history_path();
+    let Ok(contents) = fs::read_to_string(&path) else {
+        return Ok(());
+    };
+    let records: Vec<GoalRecord> = serde_json::from_str(&contents).unwrap_or_default();
+    analytics_db::record_goal_periods(&records)?;
+    fs::remove_file(&path).map_err(|e| format!("Failed to remove {}: {}", path.display(), e))
+}
+
+/// Every recorded goal period (of all kinds), oldest first
+pub fn history() -> Result<Vec<GoalRecord>, String> {
+    migrate_legacy_history()?;
+    analytics_db::goal_history()
+}
+
+/// Replace the recorded goal periods, e.g. with an imported history
+pub fn replace_history(records: &[GoalRecord]) -> Result<(), String> {
+    let _ = fs::remove_file(legacy_history_path());
+    analytics_db::clear_goal_history()?;
+    analytics_db::record_goal_periods(records)
+}
+
+/// Consecutive met periods at the end of `history`, and the longest run
+fn streaks(history: &[GoalRecord]) -> (u32, u32) {
+    let mut best_streak = 0;
+    let mut streak = 0;
+    for record in history {
+        streak = if record.met { streak + 1 } else { 0 };
+        best_streak = best_streak.max(streak);
+    }
+    (streak, best_streak)
+}
+
+/// Start of the period containing `date`
+fn period_start(kind: GoalKind, date: NaiveDate) -> NaiveDate {
+    match kind {
+        GoalKind::WeeklySpend => {
+            date - Duration::days(date.weekday().num_days_from_monday() as i64)
+        }
+        GoalKind::DailySpend | GoalKind::DailyOutput => date,
+    }
+}
+
+/// Measured value of a day for the goal kind
+fn day_value(kind: GoalKind, day: &DailyStats) -> f64 {
+    match kind {
+        GoalKind::DailySpend | GoalKind::WeeklySpend => day.cost,
+        GoalKind::DailyOutput => day.output_tokens as f64,
+    }
+}
+
+fn is_met(kind: GoalKind, actual: f64, target: f64) -> bool {
+    match kind {
+        GoalKind::DailySpend | GoalKind::WeeklySpend => actual <= target,
+        GoalKind::DailyOutput => actual >= target,
+    }
+}
+
+/// Sum the daily values into goal periods, keyed by period start
+fn period_totals(kind: GoalKind, daily: &[DailyStats]) -> Vec<(NaiveDate, f64)> {
+    let mut totals: Vec<(NaiveDate, f64)> = Vec::new();
+    for day in daily {
+        let Ok(date) = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d") else {
+            continue;
+        };
+        let start = period_start(kind, date);
+        match totals.iter_mut().find(|(s, _)| *s == start) {
+            Some((_, total)) => *total += day_value(kind, day),
+            None => totals.push((start, day_value(kind, day))),
+        }
+    }
+    totals.sort_by_key(|(start, _)| *start);
+    totals
+}
+
+/// Record completed periods that aren't in the history yet, then return the
+/// progress of the current period.
+pub fn progress(analytics: &AnalyticsService, goal: &GoalSettings) -> Result<GoalProgress, String> {
+    let today = Utc::now().date_naive();
+    let current_start = period_start(goal.kind, today);
+
+    let mut history = history()?;
+    let last_recorded = history
+        .iter()
+        .filter(|r| r.kind == goal.kind)
+        .filter_map(|r| NaiveDate::parse_from_str(&r.period_start, "%Y-%m-%d").ok())
+        .max();
+
+    // Only read as far back as needed to fill in unrecorded periods
+    let days = match last_recorded {
+        Some(last) => ((today - last).num_days() as u32 + 1).min(BACKFILL_DAYS),
+        None => BACKFILL_DAYS,
+    };
+    let chart = analytics.get_chart_data(days);
+    let totals = period_totals(goal.kind, &chart.daily);
+
+    // Days without usage count as zero (a met spend goal, a missed output goal)
+    let first_start = match last_recorded {
+        Some(last) => last + Duration::days(1),
+        None => totals.first().map(|(s, _)| *s).unwrap_or(current_start),
+    };
+    let step = match goal.kind {
+        GoalKind::WeeklySpend => 7,
+        GoalKind::DailySpend | GoalKind::DailyOutput => 1,
+    };
+
+    let mut start = period_start(goal.kind, first_start);
+    if last_recorded.is_some_and(|last| start <= last) {
+        start += Duration::days(step);
+    }
+    let mut added = Vec::new();
+    while start < current_start {
+        let actual = totals
+            .iter()
+            .find(|(s, _)| *s == start)
+            .map(|(_, v)| *v)
+            .unwrap_or(0.0);
+        added.push(GoalRecord {
+            period_start: start.format("%Y-%m-%d").to_string(),
+            kind: goal.kind,
+            target: goal.target,
+            actual,
+            met: is_met(goal.kind, actual, goal.target),
+        });
+        start += Duration::days(step);
+    }
+    if !added.is_empty() {
+        analytics_db::record_goal_periods(&added)?;
+        history.extend(added);
+    }
+
+    let mut history: Vec<GoalRecord> = history
+        .into_iter()
+        .filter(|r| r.kind == goal.kind)
+        .collect();
+    history.sort_by(|a, b| a.period_start.cmp(&b.period_start));
+
+    let current_actual = totals
+        .iter()
+        .find(|(s, _)| *s == current_start)
+        .map(|(_, v)| *v)
+        .unwrap_or(0.0);
+    let current = GoalRecord {
+        period_start: current_start.format("%Y-%m-%d").to_string(),
+        kind: goal.kind,
+        target: goal.target,
+        actual: current_actual,
+        met: is_met(goal.kind, current_actual, goal.target),
+    };
+
+    let (streak, best_streak) = streaks(&history);
+
+    let adherence = if history.is_empty() {
+        0.0
+    } else {
+        history.iter().filter(|r| r.met).count() as f64 / history.len() as f64 * 100.0
+    };
+
+    let percent = if goal.target > 0.0 {
+        current_actual / goal.target * 100.0
+    } else {
+        0.0
+    };
+
+    Ok(GoalProgress {
+        goal: goal.clone(),
+        current,
+        percent,
+        current_streak: streak,
+        best_streak,
+        adherence,
+        history,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(period_start: &str, kind: GoalKind, met: bool) -> GoalRecord {
+        GoalRecord {
+            period_start: period_start.to_string(),
+            kind,
+            target: 10.0,
+            actual: if met { 5.0 } else { 15.0 },
+            met,
+        }
+    }
+
+    fn day(date: &str, cost: f64, output_tokens: u64) -> DailyStats {
+        DailyStats {
+            date: date.to_string(),
+            input_tokens: 0,
+            output_tokens,
+            cost,
+            messages: 1,
+            by_model: Vec::new(),
+        }
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_streaks() {
+        let history = |met: &[bool]| -> Vec<GoalRecord> {
+            met.iter()
+                .map(|&met| record("2025-01-01", GoalKind::DailySpend, met))
+                .collect()
+        };
+        assert_eq!(streaks(&[]), (0, 0));
+        assert_eq!(
+            streaks(&history(&[true, true, false, true, true, true])),
+            (3, 3)
+        );
+        assert_eq!(streaks(&history(&[true, true, true, false])), (0, 3));
+        assert_eq!(streaks(&history(&[false, true, false, true])), (1, 1));
+    }
+
+    #[test]
+    fn test_periods() {
+        // Weeks start on Monday
+        assert_eq!(
+            period_start(GoalKind::WeeklySpend, date("2025-01-08")),
+            date("2025-01-06")
+        );
+        assert_eq!(
+            period_start(GoalKind::WeeklySpend, date("2025-01-06")),
+            date("2025-01-06")
+        );
+        assert_eq!(
+            period_start(GoalKind::DailySpend, date("2025-01-08")),
+            date("2025-01-08")
+        );
+
+        let daily = [
+            day("2025-01-05", 1.0, 100),
+            day("2025-01-06", 2.0, 200),
+            day("2025-01-12", 3.0, 300),
+            day("2025-01-13", 4.0, 400),
+        ];
+        assert_eq!(
+            period_totals(GoalKind::WeeklySpend, &daily),
+            vec![
+                (date("2024-12-30"), 1.0),
+                (date("2025-01-06"), 5.0),
+                (date("2025-01-13"), 4.0)
+            ]
+        );
+        assert_eq!(
+            period_totals(GoalKind::DailyOutput, &daily[..2]),
+            vec![(date("2025-01-05"), 100.0), (date("2025-01-06"), 200.0)]
+        );
+
+        // Spend goals are caps, output goals are minimums
+        assert!(is_met(GoalKind::DailySpend, 10.0, 10.0));
+        assert!(!is_met(GoalKind::WeeklySpend, 10.01, 10.0));
+        assert!(is_met(GoalKind::DailyOutput, 1000.0, 500.0));
+        assert!(!is_met(GoalKind::DailyOutput, 499.0, 500.0));
+    }
+
+    #[test]
+    fn test_history_storage() {
+        let legacy = vec![
+            record("2025-01-02", GoalKind::DailySpend, false),
+            record("2025-01-01", GoalKind::DailySpend, true),
+        ];
+        fs::write(
+            legacy_history_path(),
+            serde_json::to_string(&legacy).unwrap(),
+        )
+        .unwrap();
+        analytics_db::clear_goal_history().unwrap();
+
+        // The legacy file is moved into the database, oldest first
+        let history = history().unwrap();
+        assert!(!legacy_history_path().exists());
+        let starts: Vec<&str> = history.iter().map(|r| r.period_start.as_str()).collect();
+        assert_eq!(starts, ["2025-01-01", "2025-01-02"]);
+
+        // Recorded periods keep their first outcome
+        analytics_db::record_goal_periods(&[record("2025-01-01", GoalKind::DailySpend, false)])
+            .unwrap();
+        assert!(super::history().unwrap()[0].met);
+
+        replace_history(&[record("2025-01-06", GoalKind::WeeklySpend, true)]).unwrap();
+        let history = super::history().unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].kind, GoalKind::WeeklySpend);
+    }
+}
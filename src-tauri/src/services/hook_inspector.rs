@@ -0,0 +1,87 @@
+//! Hook request inspector
+//!
+//! With `AppSettings::hook_inspector` on, the hook server keeps the raw
+//! bodies of the last `CAPACITY` requests to `/hook` in memory, including
+//! unauthorized ones and payloads that don't parse, so matcher configs and
+//! payload problems can be debugged from Settings. Nothing is written to
+//! disk; switching the inspector off drops what was recorded.
+
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// Requests kept, oldest dropped first
+const CAPACITY: usize = 100;
+
+/// Longer bodies are cut to this many bytes
+const MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// Mirror of the setting, so the hook server doesn't clone the settings per request
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+static RECENT: Mutex<VecDeque<HookRequest>> = Mutex::new(VecDeque::new());
+
+/// A request the hook server received
+#[derive(Debug, Clone, Serialize)]
+pub struct HookRequest {
+    pub received_at: String,
+    /// Whether it carried the hook auth token
+    pub authorized: bool,
+    /// `event` field of the payload, when it has one
+    pub event: Option<String>,
+    /// Raw body, lossily decoded as UTF-8
+    pub body: String,
+    pub truncated: bool,
+    /// Why the payload was rejected, if it was
+    pub error: Option<String>,
+}
+
+/// Follow the setting; switching off drops the recorded requests
+pub fn set_enabled(enabled: bool) {
+    let was_enabled = ENABLED.swap(enabled, Ordering::Relaxed);
+    if was_enabled && !enabled {
+        RECENT.lock().unwrap().clear();
+    }
+}
+
+/// Record a request (no-op while the inspector is off)
+pub fn record(body: &[u8], authorized: bool, error: Option<String>) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let event = serde_json::from_slice::<serde_json::Value>(body)
+        .ok()
+        .and_then(|payload| {
+            payload
+                .get("event")
+                .and_then(|e| e.as_str())
+                .map(|e| e.to_string())
+        });
+    let truncated = body.len() > MAX_BODY_BYTES;
+    let request = HookRequest {
+        received_at: Utc::now().to_rfc3339(),
+        authorized,
+        event,
+        body: String::from_utf8_lossy(&body[..body.len().min(MAX_BODY_BYTES)]).to_string(),
+        truncated,
+        error,
+    };
+
+    let mut recent = RECENT.lock().unwrap();
+    if recent.len() == CAPACITY {
+        recent.pop_front();
+    }
+    recent.push_back(request);
+}
+
+/// The recorded requests, newest first
+pub fn recent() -> Vec<HookRequest> {
+    RECENT.lock().unwrap().iter().rev().cloned().collect()
+}
+
+/// Drop the recorded requests
+pub fn clear() {
+    RECENT.lock().unwrap().clear();
+}
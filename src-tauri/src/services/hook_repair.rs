@@ -0,0 +1,54 @@
+//! Hook repair
+//!
+//! Claude Code updates sometimes rewrite `settings.json` and drop the hooks,
+//! which silently stops notifications and live tracking. The watchdog checks
+//! that claudit's hooks are still present and point at the running hook
+//! server; with `AppSettings::auto_repair_hooks` on, hooks that were installed
+//! through claudit are reinstalled and the user is notified.
+
+use super::hooks::HookInstaller;
+use super::notifications::{self, PendingNotification};
+use super::settings::SettingsService;
+use chrono::Utc;
+use std::fs;
+use std::net::SocketAddr;
+use tauri::{AppHandle, Emitter, Runtime};
+
+/// Reinstall missing or outdated hooks for the hook server at `server`,
+/// returning what was repaired
+pub fn check<R: Runtime>(app: &AppHandle<R>, server: SocketAddr) -> Result<Vec<String>, String> {
+    if !SettingsService::shared().get().auto_repair_hooks {
+        return Ok(Vec::new());
+    }
+    let marker = HookInstaller::marker_path();
+    if !marker.exists() {
+        // Hooks installed before the marker existed
+        if HookInstaller::is_installed() {
+            fs::write(&marker, Utc::now().to_rfc3339())
+                .map_err(|e| format!("Failed to write {}: {}", marker.display(), e))?;
+        }
+        return Ok(Vec::new());
+    }
+
+    let problems = HookInstaller::problems(server)?;
+    if problems.is_empty() {
+        return Ok(problems);
+    }
+    HookInstaller::install(server, false)?;
+
+    let _ = app.emit("hooks-repaired", &problems);
+    notifications::dispatch(
+        app,
+        PendingNotification {
+            title: "Claude Code hooks repaired".to_string(),
+            body: format!(
+                "{} - reinstalled so tracking keeps working",
+                problems.join(", ")
+            ),
+            event: "HooksRepaired".to_string(),
+            project: None,
+            session_id: None,
+        },
+    );
+    Ok(problems)
+}
@@ -1,16 +1,29 @@
-use crate::services::{SettingsService, UsageReader};
+use crate::services::settings::{self, app_config_dir};
+use crate::services::notifications::{self, PendingNotification};
+use crate::services::audit::{FileOperation, FilePlan};
+use crate::services::automation::{self, AutomationAction};
+use crate::services::{
+    activity, format, hook_inspector, live_cost, live_sessions, paths, tool_rules, widget,
+    SettingsService, UsageReader,
+};
+use crate::tray;
 use crate::types::HookEvent;
+use crate::AppState;
 use axum::{
-    extract::State,
-    http::StatusCode,
-    response::IntoResponse,
+    body::Bytes,
+    extract::{ConnectInfo, Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     routing::{get, post},
     Json, Router,
 };
-use std::net::SocketAddr;
-use std::sync::Arc;
-use tauri::{AppHandle, Emitter, Runtime};
-use tauri_plugin_notification::NotificationExt;
+use chrono::Utc;
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
 use tokio::sync::broadcast;
 use tower_http::cors::{Any, CorsLayer};
 
@@ -26,10 +39,67 @@ pub struct HookMessage {
 pub struct HookServerState<R: Runtime> {
     pub app_handle: AppHandle<R>,
     pub tx: broadcast::Sender<HookMessage>,
+    /// Shared secret the claudit-hook relay sends as a bearer token
+    pub token: String,
+}
+
+/// Stop events of a session waiting out the collapse window
+struct CollapsedStops {
+    /// Latest of the events, whose response the notification shows
+    event: HookEvent,
+    count: u32,
+}
+
+/// Collapsed Stop/SubagentStop events by session
+static PENDING_STOPS: Mutex<Option<HashMap<String, CollapsedStops>>> = Mutex::new(None);
+
+/// Get the path of the hook auth token file (next to claudit's settings.json)
+fn hook_token_path() -> PathBuf {
+    app_config_dir().join("hook-token")
+}
+
+/// Load the hook auth token, generating and persisting a new one if missing
+pub fn hook_token() -> Result<String, String> {
+    load_or_create_token(&hook_token_path())
+}
+
+/// Read a token file, writing a new random token (readable only by the user)
+/// if it is missing or empty
+pub fn load_or_create_token(path: &std::path::Path) -> Result<String, String> {
+    if let Ok(existing) = std::fs::read_to_string(path) {
+        let existing = existing.trim();
+        if !existing.is_empty() {
+            return Ok(existing.to_string());
+        }
+    }
+
+    let token = uuid::Uuid::new_v4().simple().to_string();
+    std::fs::write(path, &token)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    // Keep the token private to the current user
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600));
+    }
+
+    Ok(token)
+}
+
+/// Parse `AppSettings::hook_bind_address` (an IPv4 or IPv6 address)
+pub fn parse_bind_address(address: &str) -> Result<IpAddr, String> {
+    address
+        .trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse()
+        .map_err(|_| format!("Not an IP address for the hook server: {}", address))
 }
 
 /// Hook server that receives events from Claude Code
 pub struct HookServer {
+    address: IpAddr,
     port: u16,
     shutdown_tx: Option<tokio::sync::oneshot::Sender<()>>,
 }
@@ -37,6 +107,7 @@ pub struct HookServer {
 impl HookServer {
     pub fn new(port: u16) -> Self {
         Self {
+            address: IpAddr::V4(Ipv4Addr::LOCALHOST),
             port,
             shutdown_tx: None,
         }
@@ -51,6 +122,7 @@ impl HookServer {
         let state = Arc::new(HookServerState {
             app_handle: app_handle.clone(),
             tx,
+            token: hook_token()?,
         });
 
         let cors = CorsLayer::new()
@@ -61,13 +133,18 @@ impl HookServer {
         let app = Router::new()
             .route("/", get(health_check))
             .route("/hook", post(handle_hook::<R>))
+            .route("/api/v1/widget", get(widget_snapshot::<R>))
+            .route("/api/v1/state", get(automation_state::<R>))
+            .route("/api/v1/actions/{action}", post(automation_action::<R>))
             .layer(cors)
             .with_state(state);
 
+        let address = parse_bind_address(&SettingsService::shared().get().hook_bind_address)?;
+
         // Try to bind to the specified port, incrementing if busy
         let mut port = self.port;
         let listener = loop {
-            let addr = SocketAddr::from(([127, 0, 0, 1], port));
+            let addr = SocketAddr::new(address, port);
             match tokio::net::TcpListener::bind(addr).await {
                 Ok(listener) => break listener,
                 Err(_) => {
@@ -81,9 +158,11 @@ impl HookServer {
 
         let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
         self.shutdown_tx = Some(shutdown_tx);
+        self.address = address;
         self.port = port;
 
         // Spawn the server with proper error handling (no unwrap)
+        let app = app.into_make_service_with_connect_info::<SocketAddr>();
         tokio::spawn(async move {
             if let Err(e) = axum::serve(listener, app)
                 .with_graceful_shutdown(async {
@@ -109,6 +188,46 @@ impl HookServer {
     pub fn port(&self) -> u16 {
         self.port
     }
+
+    /// Get the address and port the server is bound to
+    pub fn address(&self) -> SocketAddr {
+        SocketAddr::new(self.address, self.port)
+    }
+}
+
+/// Restart the hook server when its port or bind address setting changes.
+/// Hooks pointing at the old address are fixed by the watchdog's hook repair.
+pub fn follow_settings<R: Runtime + 'static>(
+    app: &AppHandle<R>,
+    server: Arc<tokio::sync::Mutex<HookServer>>,
+) {
+    let app = app.clone();
+    settings::on_change(move |change| {
+        let (previous, current) = (&change.previous, &change.current);
+        if previous.hook_port == current.hook_port
+            && previous.hook_bind_address == current.hook_bind_address
+        {
+            return;
+        }
+        let (app, server, port) = (app.clone(), server.clone(), current.hook_port);
+        tauri::async_runtime::block_on(async move {
+            let mut server = server.lock().await;
+            server.stop();
+            *server = HookServer::new(port);
+            match server.start(app).await {
+                Ok(port) => println!("Hook server restarted on port {}", port),
+                Err(e) => eprintln!("Failed to restart hook server: {}", e),
+            }
+        });
+    });
+}
+
+/// Bearer token sent with a request
+fn bearer(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
 }
 
 /// Health check endpoint
@@ -116,11 +235,199 @@ async fn health_check() -> impl IntoResponse {
     (StatusCode::OK, Json(serde_json::json!({ "status": "ok" })))
 }
 
+/// Stats snapshot for companion widgets, when the widget API is turned on
+async fn widget_snapshot<R: Runtime>(
+    State(state): State<Arc<HookServerState<R>>>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if !SettingsService::shared().get().widget_api {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": "widget API is turned off in Claudit's settings" })),
+        )
+            .into_response();
+    }
+    // Stats only leave this machine for clients holding the automation token
+    let remote = !peer.ip().to_canonical().is_loopback();
+    if remote && !automation::is_authorized(bearer(&headers)) {
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({ "error": "unauthorized" })),
+        )
+            .into_response();
+    }
+    match widget::current(&state.app_handle) {
+        Some(snapshot) => (StatusCode::OK, Json(snapshot)).into_response(),
+        None => StatusCode::SERVICE_UNAVAILABLE.into_response(),
+    }
+}
+
+/// Response rejecting an automation request while the API is off or without
+/// the automation token
+fn automation_rejection(headers: &HeaderMap) -> Option<Response> {
+    if !SettingsService::shared().get().automation_api {
+        return Some((
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": "automation API is turned off in Claudit's settings" })),
+        )
+            .into_response());
+    }
+    if !automation::is_authorized(bearer(headers)) {
+        return Some((
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({ "error": "unauthorized" })),
+        )
+            .into_response());
+    }
+    None
+}
+
+/// State for Stream Deck / Shortcuts button labels
+async fn automation_state<R: Runtime>(
+    State(state): State<Arc<HookServerState<R>>>,
+    headers: HeaderMap,
+) -> Response {
+    if let Some(response) = automation_rejection(&headers) {
+        return response;
+    }
+    Json(automation::state(&state.app_handle)).into_response()
+}
+
+/// Run an automation action (toggle-notifications, refresh-stats, ...)
+async fn automation_action<R: Runtime>(
+    State(state): State<Arc<HookServerState<R>>>,
+    headers: HeaderMap,
+    Path(action): Path<String>,
+) -> Response {
+    if let Some(response) = automation_rejection(&headers) {
+        return response;
+    }
+    let Some(action) = AutomationAction::parse(&action) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": format!("unknown action: {}", action) })),
+        )
+            .into_response();
+    };
+    match automation::run(&state.app_handle, action) {
+        Ok(result) => Json(result).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": e })),
+        )
+            .into_response(),
+    }
+}
+
+/// Add a Stop event to its session's pending ones; returns the session's key
+/// when it is the first, whose window the caller then starts
+fn hold_stop(event: HookEvent) -> Option<String> {
+    let key = event
+        .session_id
+        .clone()
+        .or_else(|| event.cwd.clone())
+        .unwrap_or_default();
+    let mut pending = PENDING_STOPS.lock().unwrap();
+    let pending = pending.get_or_insert_with(HashMap::new);
+    if let Some(collapsed) = pending.get_mut(&key) {
+        collapsed.count += 1;
+        collapsed.event = event;
+        return None;
+    }
+    pending.insert(key.clone(), CollapsedStops { event, count: 1 });
+    Some(key)
+}
+
+/// Hold a Stop event for `window` seconds; the events of the same session
+/// arriving meanwhile become one notification
+fn collapse_stop<R: Runtime>(app: &AppHandle<R>, event: HookEvent, window: u32) {
+    let Some(key) = hold_stop(event) else {
+        return;
+    };
+
+    let app = app.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(window as u64)).await;
+        let collapsed = PENDING_STOPS
+            .lock()
+            .unwrap()
+            .as_mut()
+            .and_then(|pending| pending.remove(&key));
+        if let Some(collapsed) = collapsed {
+            notify_stop(&app, &collapsed.event, collapsed.count, window);
+        }
+    });
+}
+
+/// Notify that Claude finished, showing the response; `count` Stop events
+/// within `window` seconds were collapsed into this one
+fn notify_stop<R: Runtime>(app: &AppHandle<R>, event: &HookEvent, count: u32, window: u32) {
+    // Get the response excerpt from the session that finished, not just the newest file
+    let reader = UsageReader::new();
+    let mut body = if event.session_id.is_some() || event.transcript_path.is_some() {
+        reader.get_session_response(
+            event.transcript_path.as_deref(),
+            event.session_id.as_deref(),
+            event.cwd.as_deref(),
+            120,
+        )
+    } else {
+        reader.get_latest_response(120)
+    }
+    .unwrap_or_else(|| "Claude has finished responding".to_string());
+    if count > 1 {
+        body.push_str(&format!("\n({} stop events in {}s)", count, window));
+    }
+
+    // Include the project name so parallel sessions are distinguishable
+    let title = match event.cwd.as_deref().and_then(format::project_name) {
+        Some(project) => format!("Claude Code · {}", project),
+        None => "Claude Code".to_string(),
+    };
+
+    let outcome = notifications::dispatch(
+        app,
+        PendingNotification {
+            title,
+            body,
+            event: event.event.clone(),
+            project: event.cwd.clone(),
+            session_id: event.session_id.clone(),
+        },
+    );
+    println!("Notification outcome: {:?} ({} collapsed)", outcome, count);
+}
+
 /// Handle incoming hook events
 async fn handle_hook<R: Runtime>(
     State(state): State<Arc<HookServerState<R>>>,
-    Json(event): Json<HookEvent>,
+    headers: HeaderMap,
+    body: Bytes,
 ) -> impl IntoResponse {
+    // Parsed here rather than by the Json extractor so the inspector sees bad payloads too
+    let authorized = bearer(&headers) == Some(state.token.as_str());
+    let parsed = serde_json::from_slice::<HookEvent>(&body)
+        .map_err(|e| format!("Invalid hook payload: {}", e));
+    hook_inspector::record(&body, authorized, parsed.as_ref().err().cloned());
+
+    // Only accept events from the claudit-hook relay
+    if !authorized {
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({ "success": false, "error": "unauthorized" })),
+        );
+    }
+    let event = match parsed {
+        Ok(event) => event,
+        Err(e) => {
+            return (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(serde_json::json!({ "success": false, "error": e })),
+            )
+        }
+    };
+
     // Send event to broadcast channel for internal subscribers
     let _ = state.tx.send(HookMessage {
         event_type: event.event.clone(),
@@ -130,6 +437,42 @@ async fn handle_hook<R: Runtime>(
     // Emit event to frontend
     let _ = state.app_handle.emit("hook-event", &event);
 
+    // Track which sessions are active (and for how long)
+    let attention_changed = state
+        .app_handle
+        .try_state::<AppState>()
+        .is_some_and(|app_state| app_state.session_tracker.record_event(&event));
+    if attention_changed {
+        let _ = tray::update_attention_indicator(&state.app_handle);
+    }
+
+    // Read what the session appended since the last event into its running cost
+    let session_file = UsageReader::new().find_session_file(
+        event.transcript_path.as_deref(),
+        event.session_id.as_deref(),
+        event.cwd.as_deref(),
+    );
+    // Stream the new messages to the live viewer
+    if let Some(appended) = session_file
+        .as_deref()
+        .and_then(|file| live_sessions::on_hook_event(&event, file))
+    {
+        let _ = state.app_handle.emit("session-message-appended", &appended);
+    }
+    if let Some(live) = session_file.and_then(|file| live_cost::update(&file)) {
+        if let Some(app_state) = state.app_handle.try_state::<AppState>() {
+            app_state.session_tracker.set_live_cost(&live.session_id, live.cost);
+        }
+        let _ = state.app_handle.emit("live-session-cost", &live);
+    }
+
+    // Keep a history of lifecycle events (tool and prompt events are too noisy)
+    if !matches!(event.event.as_str(), "PreToolUse" | "PostToolUse" | "UserPromptSubmit") {
+        let entry = activity::ActivityEntry::from_hook_event(&event, false);
+        let _ = activity::record(&entry);
+        let _ = state.app_handle.emit("activity-added", &entry);
+    }
+
     // Debug: Log all hook events
     println!("Hook received: event={}, tool={:?}", event.event, event.tool);
 
@@ -140,36 +483,65 @@ async fn handle_hook<R: Runtime>(
             // Claude finished responding - trigger notification
             let _ = state.app_handle.emit("claude-finished", &event);
 
-            // Check if notifications are enabled before sending
-            let settings = SettingsService::new().get();
-            println!("Notifications enabled: {}", settings.notifications_enabled);
-
-            if settings.notifications_enabled {
-                println!("Attempting to send notification...");
-
-                // Get the latest response excerpt for the notification body
-                let reader = UsageReader::new();
-                let body = reader
-                    .get_latest_response(120)
-                    .unwrap_or_else(|| "Claude has finished responding".to_string());
-
-                // Send system notification with response excerpt
-                match state
-                    .app_handle
-                    .notification()
-                    .builder()
-                    .title("Claude Code")
-                    .body(&body)
-                    .show()
-                {
-                    Ok(_) => println!("Notification sent successfully"),
-                    Err(e) => eprintln!("Failed to send notification: {}", e),
-                }
+            let window = SettingsService::shared().get().notification_collapse_seconds;
+            if window == 0 {
+                notify_stop(&state.app_handle, &event, 1, 0);
+            } else {
+                collapse_stop(&state.app_handle, event.clone(), window);
             }
         }
+        "Notification" => {
+            // Claude is asking for permission or has been idle waiting for input
+            let _ = state.app_handle.emit("claude-needs-attention", &event);
+
+            let body = event
+                .payload
+                .as_ref()
+                .and_then(|p| p.get("message"))
+                .and_then(|m| m.as_str())
+                .map(|m| m.to_string())
+                .unwrap_or_else(|| "Claude is waiting for your input".to_string());
+
+            let title = match event.cwd.as_deref().and_then(format::project_name) {
+                Some(project) => format!("Claude needs your attention · {}", project),
+                None => "Claude needs your attention".to_string(),
+            };
+
+            let outcome = notifications::dispatch(
+                &state.app_handle,
+                PendingNotification {
+                    title,
+                    body,
+                    event: event.event.clone(),
+                    project: event.cwd.clone(),
+                    session_id: event.session_id.clone(),
+                },
+            );
+            println!("Notification outcome: {:?}", outcome);
+        }
         "PostToolUse" => {
-            // Tool was used - can be used for granular tracking
-            // Intentionally quiet to avoid notification spam
+            // Quiet unless one of the user's tool rules matches
+            let rules = SettingsService::shared().get().tool_notification_rules;
+            if let Some(rule) = tool_rules::matching_rule(&rules, &event) {
+                let title = match event.cwd.as_deref().and_then(format::project_name) {
+                    Some(project) => format!("{} · {}", rule.name, project),
+                    None => rule.name.clone(),
+                };
+                let tool = event.tool.clone().unwrap_or_else(|| "Tool".to_string());
+                let subject = format::excerpt(&tool_rules::tool_subject(&event), 120);
+
+                let outcome = notifications::dispatch(
+                    &state.app_handle,
+                    PendingNotification {
+                        title,
+                        body: format!("{}: {}", tool, subject),
+                        event: event.event.clone(),
+                        project: event.cwd.clone(),
+                        session_id: event.session_id.clone(),
+                    },
+                );
+                println!("Tool rule \"{}\" notification outcome: {:?}", rule.name, outcome);
+            }
         }
         "PreToolUse" | "UserPromptSubmit" => {
             // Received but not actively handled
@@ -183,16 +555,61 @@ async fn handle_hook<R: Runtime>(
     )
 }
 
+/// Hook events claudit installs its relay for
+const HOOK_EVENTS: &[&str] = &[
+    "Stop",
+    "SubagentStop",
+    "Notification",
+    "PostToolUse",
+    "UserPromptSubmit",
+];
+
 /// Hook installer for Claude Code settings
 pub struct HookInstaller;
 
 impl HookInstaller {
     /// Get the Claude Code settings path
     fn settings_path() -> std::path::PathBuf {
-        dirs::home_dir()
-            .unwrap_or_default()
-            .join(".claude")
-            .join("settings.json")
+        paths::current().settings_json()
+    }
+
+    /// Locate the claudit-hook relay binary (the sidecar Tauri places next to the main executable)
+    pub fn helper_path() -> Result<PathBuf, String> {
+        let exe = std::env::current_exe()
+            .map_err(|e| format!("Failed to locate claudit executable: {}", e))?;
+        let name = if cfg!(windows) { "claudit-hook.exe" } else { "claudit-hook" };
+        let helper = exe.with_file_name(name);
+
+        if helper.exists() {
+            Ok(helper)
+        } else {
+            Err(format!("Hook helper not found at {}", helper.display()))
+        }
+    }
+
+    /// Marker recording that the hooks were installed through claudit, so they
+    /// are only repaired for users who want them
+    pub fn marker_path() -> PathBuf {
+        app_config_dir().join("hooks-installed")
+    }
+
+    /// Build the hook command that invokes the relay for the hook server's address
+    fn hook_command(helper: &std::path::Path, server: SocketAddr) -> String {
+        // The relay connects to 127.0.0.1 unless told otherwise
+        let host = match server.ip() {
+            IpAddr::V4(ip) if ip.is_loopback() || ip.is_unspecified() => None,
+            IpAddr::V6(ip) if ip.is_unspecified() => Some(IpAddr::V6(Ipv6Addr::LOCALHOST)),
+            ip => Some(ip),
+        };
+        match host {
+            Some(host) => format!(
+                "\"{}\" --host {} --port {}",
+                helper.display(),
+                host,
+                server.port()
+            ),
+            None => format!("\"{}\" --port {}", helper.display(), server.port()),
+        }
     }
 
     /// Check if hooks are already installed
@@ -205,7 +622,11 @@ impl HookInstaller {
         match std::fs::read_to_string(&path) {
             Ok(contents) => {
                 if let Ok(settings) = serde_json::from_str::<serde_json::Value>(&contents) {
-                    settings.get("hooks").is_some()
+                    // Older curl-based hooks don't carry the auth token, so they count as missing
+                    settings
+                        .get("hooks")
+                        .map(|hooks| hooks.to_string().contains("claudit-hook"))
+                        .unwrap_or(false)
                 } else {
                     false
                 }
@@ -214,15 +635,27 @@ impl HookInstaller {
         }
     }
 
-    /// Install hooks into Claude Code settings
-    pub fn install(port: u16) -> Result<(), String> {
+    /// Install hooks into Claude Code settings.
+    /// With `dry_run` the file operations are returned without being performed.
+    pub fn install(server: SocketAddr, dry_run: bool) -> Result<Vec<FileOperation>, String> {
+        if !dry_run {
+            // Make sure the relay has a token to send
+            hook_token()?;
+        }
+        Self::plan_install(server)?.run(dry_run)
+    }
+
+    /// Plan the file operations for installing hooks
+    fn plan_install(server: SocketAddr) -> Result<FilePlan, String> {
         let path = Self::settings_path();
+        let helper = Self::helper_path()?;
+        let mut plan = FilePlan::new("install_hooks");
+        plan.with_restore_point();
 
         // Create backup
         if path.exists() {
             let backup_path = path.with_extension("json.backup");
-            std::fs::copy(&path, &backup_path)
-                .map_err(|e| format!("Failed to create backup: {}", e))?;
+            plan.copy(&path, &backup_path, "back up settings.json");
         }
 
         // Load existing settings or create new
@@ -233,68 +666,117 @@ impl HookInstaller {
         } else {
             // Ensure parent directory exists
             if let Some(parent) = path.parent() {
-                std::fs::create_dir_all(parent)
-                    .map_err(|e| format!("Failed to create directory: {}", e))?;
+                plan.create_dir(parent, "create ~/.claude");
             }
             serde_json::json!({})
         };
 
-        // Create hooks configuration
-        // Event type is determined by which hook fires, tool name from $CLAUDE_TOOL_NAME
-        let stop_command = format!(
-            r#"curl -s -X POST http://localhost:{}/hook -H "Content-Type: application/json" -d '{{"event": "Stop"}}' > /dev/null 2>&1 &"#,
-            port
-        );
-        let subagent_stop_command = format!(
-            r#"curl -s -X POST http://localhost:{}/hook -H "Content-Type: application/json" -d '{{"event": "SubagentStop"}}' > /dev/null 2>&1 &"#,
-            port
-        );
-        let post_tool_command = format!(
-            r#"curl -s -X POST http://localhost:{}/hook -H "Content-Type: application/json" -d "{{"event": "PostToolUse", "tool": "$CLAUDE_TOOL_NAME"}}" > /dev/null 2>&1 &"#,
-            port
-        );
-
-        let hooks = serde_json::json!({
-            "Stop": [{
-                "matcher": "*",
-                "hooks": [{
-                    "type": "command",
-                    "command": stop_command
-                }]
-            }],
-            "SubagentStop": [{
-                "matcher": "*",
-                "hooks": [{
-                    "type": "command",
-                    "command": subagent_stop_command
-                }]
-            }],
-            "PostToolUse": [{
-                "matcher": "Bash",
-                "hooks": [{
-                    "type": "command",
-                    "command": post_tool_command
-                }]
-            }]
-        });
-
-        settings["hooks"] = hooks;
+        // Replace claudit's entries for each event, keeping the user's own hooks
+        // The relay reads the event name, tool and session info from Claude's stdin payload
+        let command = Self::hook_command(&helper, server);
+        if !settings.get("hooks").is_some_and(|h| h.is_object()) {
+            settings["hooks"] = serde_json::json!({});
+        }
+        for event in HOOK_EVENTS {
+            let matchers = &mut settings["hooks"][*event];
+            if !matchers.is_array() {
+                *matchers = serde_json::json!([]);
+            }
+            if let Some(matchers) = matchers.as_array_mut() {
+                Self::remove_claudit_hooks(matchers);
+                matchers.push(serde_json::json!({
+                    "matcher": "*",
+                    "hooks": [{
+                        "type": "command",
+                        "command": command
+                    }]
+                }));
+            }
+        }
 
         // Write back to file
         let contents = serde_json::to_string_pretty(&settings)
             .map_err(|e| format!("Failed to serialize: {}", e))?;
 
-        std::fs::write(&path, contents).map_err(|e| format!("Failed to write: {}", e))?;
+        plan.write(&path, contents, "add claudit hooks to settings.json");
+        plan.write(
+            &Self::marker_path(),
+            Utc::now().to_rfc3339(),
+            "remember that the hooks are installed",
+        );
+
+        Ok(plan)
+    }
+
+    /// What is wrong with the installed hooks for the given server address
+    /// (empty when every event has the current relay command)
+    pub fn problems(server: SocketAddr) -> Result<Vec<String>, String> {
+        let path = Self::settings_path();
+        if !path.exists() {
+            return Ok(vec!["settings.json is missing".to_string()]);
+        }
+        let contents =
+            std::fs::read_to_string(&path).map_err(|e| format!("Failed to read: {}", e))?;
+        let settings: serde_json::Value =
+            serde_json::from_str(&contents).map_err(|e| format!("Failed to parse: {}", e))?;
+        let command = Self::hook_command(&Self::helper_path()?, server);
+
+        let mut problems = Vec::new();
+        for event in HOOK_EVENTS {
+            let entries: Vec<&serde_json::Value> = settings
+                .get("hooks")
+                .and_then(|h| h.get(*event))
+                .and_then(|m| m.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|matcher| matcher.get("hooks").and_then(|h| h.as_array()))
+                .flatten()
+                .filter(|entry| Self::is_claudit_hook(entry))
+                .collect();
+            let current = entries
+                .iter()
+                .any(|entry| entry.get("command").and_then(|c| c.as_str()) == Some(&command));
+            if entries.is_empty() {
+                problems.push(format!("{} hook is missing", event));
+            } else if !current {
+                problems.push(format!("{} hook is out of date", event));
+            }
+        }
+        Ok(problems)
+    }
+
+    /// Whether a hook entry was added by claudit (the relay, or the older curl hooks)
+    fn is_claudit_hook(entry: &serde_json::Value) -> bool {
+        let command = entry.get("command").and_then(|c| c.as_str()).unwrap_or("");
+        command.contains("claudit-hook")
+            || (command.contains("curl") && command.contains("localhost:") && command.contains("/hook"))
+    }
 
-        Ok(())
+    /// Drop claudit's entries from an event's matchers, then matchers left without hooks
+    fn remove_claudit_hooks(matchers: &mut Vec<serde_json::Value>) {
+        for matcher in matchers.iter_mut() {
+            if let Some(entries) = matcher.get_mut("hooks").and_then(|h| h.as_array_mut()) {
+                entries.retain(|entry| !Self::is_claudit_hook(entry));
+            }
+        }
+        matchers.retain(|matcher| {
+            matcher
+                .get("hooks")
+                .and_then(|h| h.as_array())
+                .is_none_or(|entries| !entries.is_empty())
+        });
     }
 
-    /// Uninstall hooks from Claude Code settings
-    pub fn uninstall() -> Result<(), String> {
+    /// Uninstall claudit's hooks from Claude Code settings, keeping any other hooks.
+    /// With `dry_run` the file operations are returned without being performed.
+    pub fn uninstall(dry_run: bool) -> Result<Vec<FileOperation>, String> {
         let path = Self::settings_path();
+        let mut plan = FilePlan::new("uninstall_hooks");
+        // Removed hooks must not be repaired
+        plan.remove(&Self::marker_path(), "forget that the hooks are installed");
 
         if !path.exists() {
-            return Ok(());
+            return plan.run(dry_run);
         }
 
         let contents =
@@ -303,16 +785,63 @@ impl HookInstaller {
         let mut settings: serde_json::Value =
             serde_json::from_str(&contents).map_err(|e| format!("Failed to parse: {}", e))?;
 
-        // Remove hooks
-        if let Some(obj) = settings.as_object_mut() {
-            obj.remove("hooks");
+        let Some(hooks) = settings.get_mut("hooks").and_then(|h| h.as_object_mut()) else {
+            return plan.run(dry_run);
+        };
+        let before = hooks.clone();
+
+        // Drop claudit's entries, then events left without hooks
+        for matchers in hooks.values_mut().filter_map(|m| m.as_array_mut()) {
+            Self::remove_claudit_hooks(matchers);
+        }
+        hooks.retain(|_, matchers| matchers.as_array().is_none_or(|m| !m.is_empty()));
+
+        if *hooks == before {
+            return plan.run(dry_run);
+        }
+        if hooks.is_empty() {
+            if let Some(obj) = settings.as_object_mut() {
+                obj.remove("hooks");
+            }
         }
 
         let contents = serde_json::to_string_pretty(&settings)
             .map_err(|e| format!("Failed to serialize: {}", e))?;
 
-        std::fs::write(&path, contents).map_err(|e| format!("Failed to write: {}", e))?;
+        plan.write(&path, contents, "remove claudit hooks from settings.json");
+
+        plan.run(dry_run)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stop_event(event: &str, session_id: &str) -> HookEvent {
+        HookEvent {
+            event: event.to_string(),
+            tool: None,
+            context: None,
+            timestamp: None,
+            session_id: Some(session_id.to_string()),
+            cwd: Some("/tmp/project".to_string()),
+            transcript_path: None,
+            payload: None,
+        }
+    }
 
-        Ok(())
+    #[test]
+    fn test_stops_collapse_per_session() {
+        let session = uuid::Uuid::new_v4().to_string();
+        assert_eq!(hold_stop(stop_event("SubagentStop", &session)), Some(session.clone()));
+        assert_eq!(hold_stop(stop_event("Stop", &session)), None);
+
+        let mut pending = PENDING_STOPS.lock().unwrap();
+        let pending = pending.as_mut().unwrap();
+        let collapsed = pending.remove(&session).unwrap();
+        assert_eq!(collapsed.count, 2);
+        assert_eq!(collapsed.event.event, "Stop");
+        assert!(!pending.contains_key(&session));
     }
 }
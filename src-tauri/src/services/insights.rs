@@ -0,0 +1,404 @@
+//! Trend insights
+//!
+//! Compares the selected period with the one before it and reports what
+//! changed: overall and per-project spend, cache hit rate (and whether it moved
+//! after a CLAUDE.md edit) and the model mix. Each insight carries the numbers
+//! it is based on and a score used to rank it among the other suggestions.
+
+use super::config::{list_claude_md_files, AiSuggestion};
+use super::pricing;
+use super::usage::UsageReader;
+use crate::types::UsageEntry;
+use chrono::{DateTime, Duration, Local, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Below this, percentage changes are noise
+const MIN_PROJECT_COST: f64 = 2.0;
+/// Tokens a period needs before its cache hit rate means anything
+const MIN_CACHE_TOKENS: u64 = 100_000;
+/// Cache hit rate change worth reporting, in percentage points
+const MIN_CACHE_DROP: f64 = 10.0;
+
+/// How an evidence value is displayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EvidenceUnit {
+    Usd,
+    Percent,
+    Tokens,
+}
+
+/// A number an insight is based on, for this period and the one it is compared to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Evidence {
+    pub label: String,
+    pub current: f64,
+    pub previous: f64,
+    pub unit: EvidenceUnit,
+}
+
+impl Evidence {
+    fn new(label: impl Into<String>, current: f64, previous: f64, unit: EvidenceUnit) -> Self {
+        Self {
+            label: label.into(),
+            current,
+            previous,
+            unit,
+        }
+    }
+}
+
+/// Totals for one period
+#[derive(Default)]
+struct PeriodTotals {
+    cost: f64,
+    cost_by_project: HashMap<String, f64>,
+    cost_by_family: HashMap<&'static str, f64>,
+    input_tokens: u64,
+    cache_creation_tokens: u64,
+    cache_read_tokens: u64,
+}
+
+impl PeriodTotals {
+    fn add(&mut self, entry: &UsageEntry) {
+        let cost = pricing::calculate_cost(
+            &entry.model,
+            Some(entry.timestamp),
+            entry.service_tier.as_deref(),
+            entry.input_tokens,
+            entry.output_tokens,
+            entry.cache_creation_tokens,
+            entry.cache_read_tokens,
+        );
+        self.cost += cost;
+        *self
+            .cost_by_project
+            .entry(entry.project.clone())
+            .or_default() += cost;
+        *self
+            .cost_by_family
+            .entry(model_family(&entry.model))
+            .or_default() += cost;
+        self.input_tokens += entry.input_tokens;
+        self.cache_creation_tokens += entry.cache_creation_tokens;
+        self.cache_read_tokens += entry.cache_read_tokens;
+    }
+
+    fn prompt_tokens(&self) -> u64 {
+        self.input_tokens + self.cache_creation_tokens + self.cache_read_tokens
+    }
+
+    /// Share of prompt tokens read from cache, in percent
+    fn cache_hit_rate(&self) -> Option<f64> {
+        let total = self.prompt_tokens();
+        (total >= MIN_CACHE_TOKENS).then(|| self.cache_read_tokens as f64 / total as f64 * 100.0)
+    }
+
+    fn family_share(&self, family: &str) -> f64 {
+        if self.cost <= 0.0 {
+            return 0.0;
+        }
+        self.cost_by_family.get(family).copied().unwrap_or(0.0) / self.cost * 100.0
+    }
+}
+
+fn totals<'a>(entries: impl Iterator<Item = &'a UsageEntry>) -> PeriodTotals {
+    let mut totals = PeriodTotals::default();
+    for entry in entries {
+        totals.add(entry);
+    }
+    totals
+}
+
+fn model_family(model: &str) -> &'static str {
+    let model = model.to_lowercase();
+    ["opus", "sonnet", "haiku"]
+        .into_iter()
+        .find(|family| model.contains(family))
+        .unwrap_or("other")
+}
+
+fn project_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// "this week" / "last week" style names for the two periods
+fn period_names(days: u32) -> (String, String) {
+    match days {
+        1 => ("today".to_string(), "yesterday".to_string()),
+        7 => ("this week".to_string(), "last week".to_string()),
+        30 => ("this month".to_string(), "the month before".to_string()),
+        _ => (
+            format!("in the last {} days", days),
+            format!("the {} days before", days),
+        ),
+    }
+}
+
+fn change_percent(current: f64, previous: f64) -> f64 {
+    (current - previous) / previous * 100.0
+}
+
+fn spend_insight(
+    current: &PeriodTotals,
+    previous: &PeriodTotals,
+    days: u32,
+) -> Option<AiSuggestion> {
+    if previous.cost < 1.0 {
+        return None;
+    }
+    let change = change_percent(current.cost, previous.cost);
+    if change.abs() < 30.0 {
+        return None;
+    }
+    let (this_period, last_period) = period_names(days);
+    let (title, score) = if change > 0.0 {
+        (
+            format!("Spending up {:.0}% {}", change, this_period),
+            40.0 + change.min(200.0) / 4.0,
+        )
+    } else {
+        (
+            format!("Spending down {:.0}% {}", -change, this_period),
+            20.0,
+        )
+    };
+
+    Some(AiSuggestion {
+        id: "insight:spend".to_string(),
+        suggestion_type: "insight".to_string(),
+        title,
+        description: format!(
+            "${:.2} {} compared to ${:.2} {}.",
+            current.cost, this_period, previous.cost, last_period
+        ),
+        content: None,
+        score,
+        evidence: vec![Evidence::new(
+            "Spend",
+            current.cost,
+            previous.cost,
+            EvidenceUnit::Usd,
+        )],
+    })
+}
+
+/// Projects whose cost at least doubled (or that are new and already expensive)
+fn project_insights(
+    current: &PeriodTotals,
+    previous: &PeriodTotals,
+    days: u32,
+) -> Vec<AiSuggestion> {
+    // Without earlier usage every project would look new
+    if previous.cost <= 0.0 {
+        return Vec::new();
+    }
+    let (this_period, last_period) = period_names(days);
+    let mut insights: Vec<AiSuggestion> = current
+        .cost_by_project
+        .iter()
+        .filter(|(_, cost)| **cost >= MIN_PROJECT_COST)
+        .filter_map(|(project, &cost)| {
+            let before = previous
+                .cost_by_project
+                .get(project)
+                .copied()
+                .unwrap_or(0.0);
+            let ratio = if before > 0.0 {
+                cost / before
+            } else {
+                f64::INFINITY
+            };
+            if ratio < 2.0 {
+                return None;
+            }
+
+            let name = project_name(project);
+            let (title, description) = if before <= 0.0 {
+                (
+                    format!("New spend on {} {}", name, this_period),
+                    format!(
+                        "${:.2} {} with no usage {}.",
+                        cost, this_period, last_period
+                    ),
+                )
+            } else if ratio < 2.5 {
+                (
+                    format!("{} costs doubled {}", name, this_period),
+                    format!(
+                        "${:.2} {} compared to ${:.2} {}.",
+                        cost, this_period, before, last_period
+                    ),
+                )
+            } else {
+                (
+                    format!("{} costs up {:.0}x {}", name, ratio, this_period),
+                    format!(
+                        "${:.2} {} compared to ${:.2} {}.",
+                        cost, this_period, before, last_period
+                    ),
+                )
+            };
+            // Weigh by how much money moved as well as by the ratio
+            let score = 50.0 + ratio.min(5.0) * 4.0 + (cost - before).min(50.0) / 2.5;
+
+            Some(AiSuggestion {
+                id: format!("insight:project_cost:{}", project),
+                suggestion_type: "insight".to_string(),
+                title,
+                description,
+                content: None,
+                score,
+                evidence: vec![Evidence::new(
+                    format!("{} spend", name),
+                    cost,
+                    before,
+                    EvidenceUnit::Usd,
+                )],
+            })
+        })
+        .collect();
+
+    insights.sort_by(|a, b| b.score.total_cmp(&a.score));
+    insights.truncate(3);
+    insights
+}
+
+/// A CLAUDE.md edit followed by a cache hit rate drop for the usage it affects
+fn claude_md_cache_insight(
+    entries: &[UsageEntry],
+    since: DateTime<Utc>,
+    days: u32,
+) -> Option<AiSuggestion> {
+    let files = list_claude_md_files().ok()?;
+
+    files
+        .iter()
+        .filter_map(|file| {
+            let modified: DateTime<Utc> = std::fs::metadata(&file.path).ok()?.modified().ok()?.into();
+            if modified < since {
+                return None;
+            }
+            let project = (!file.is_global)
+                .then(|| Path::new(&file.path).parent().map(|p| p.to_string_lossy().to_string()))
+                .flatten();
+            let affected = |entry: &&UsageEntry| project.as_ref().is_none_or(|p| &entry.project == p);
+
+            let window = Duration::days(days as i64);
+            let before = totals(
+                entries
+                    .iter()
+                    .filter(affected)
+                    .filter(|e| e.timestamp < modified && e.timestamp >= modified - window),
+            );
+            let after = totals(entries.iter().filter(affected).filter(|e| e.timestamp >= modified));
+            let (rate_before, rate_after) = (before.cache_hit_rate()?, after.cache_hit_rate()?);
+            let drop = rate_before - rate_after;
+            if drop < MIN_CACHE_DROP {
+                return None;
+            }
+
+            let which = match &project {
+                Some(project) => format!("{}'s CLAUDE.md", project_name(project)),
+                None => "your global CLAUDE.md".to_string(),
+            };
+            let day = modified.with_timezone(&Local).format("%A");
+            Some(AiSuggestion {
+                id: format!("insight:claude_md_cache:{}", file.path),
+                suggestion_type: "insight".to_string(),
+                title: format!("Cache hit rate dropped {:.0} points after you edited {} on {}", drop, which, day),
+                description: format!(
+                    "{:.0}% of prompt tokens came from cache before the edit and {:.0}% since. Content that changes often near the top of CLAUDE.md invalidates the prompt cache; keep stable instructions first.",
+                    rate_before, rate_after
+                ),
+                content: None,
+                score: 55.0 + drop,
+                evidence: vec![
+                    Evidence::new("Cache hit rate", rate_after, rate_before, EvidenceUnit::Percent),
+                    Evidence::new(
+                        "Prompt tokens",
+                        after.prompt_tokens() as f64,
+                        before.prompt_tokens() as f64,
+                        EvidenceUnit::Tokens,
+                    ),
+                ],
+            })
+        })
+        .max_by(|a, b| a.score.total_cmp(&b.score))
+}
+
+fn cache_insight(
+    current: &PeriodTotals,
+    previous: &PeriodTotals,
+    days: u32,
+) -> Option<AiSuggestion> {
+    let (rate_now, rate_before) = (current.cache_hit_rate()?, previous.cache_hit_rate()?);
+    let drop = rate_before - rate_now;
+    if drop < MIN_CACHE_DROP {
+        return None;
+    }
+    let (this_period, last_period) = period_names(days);
+    Some(AiSuggestion {
+        id: "insight:cache_rate".to_string(),
+        suggestion_type: "insight".to_string(),
+        title: format!("Cache hit rate dropped {:.0} points {}", drop, this_period),
+        description: format!(
+            "{:.0}% of prompt tokens came from cache {}, down from {:.0}% {}. Long pauses, frequent /clear and changing CLAUDE.md or MCP setups all start sessions with a cold cache.",
+            rate_now, this_period, rate_before, last_period
+        ),
+        content: None,
+        score: 45.0 + drop,
+        evidence: vec![Evidence::new("Cache hit rate", rate_now, rate_before, EvidenceUnit::Percent)],
+    })
+}
+
+fn model_mix_insight(
+    current: &PeriodTotals,
+    previous: &PeriodTotals,
+    days: u32,
+) -> Option<AiSuggestion> {
+    let (share_now, share_before) = (current.family_share("opus"), previous.family_share("opus"));
+    let rise = share_now - share_before;
+    if rise < 20.0 || current.cost_by_family.get("opus").copied().unwrap_or(0.0) < 5.0 {
+        return None;
+    }
+    let (this_period, last_period) = period_names(days);
+    Some(AiSuggestion {
+        id: "insight:model_mix".to_string(),
+        suggestion_type: "insight".to_string(),
+        title: format!("Opus share of spend rose to {:.0}% {}", share_now, this_period),
+        description: format!(
+            "Opus accounted for {:.0}% of spend {}, up from {:.0}% {}. Sonnet handles most day-to-day coding at a fraction of the cost.",
+            share_now, this_period, share_before, last_period
+        ),
+        content: None,
+        score: 40.0 + rise / 2.0,
+        evidence: vec![Evidence::new("Opus share", share_now, share_before, EvidenceUnit::Percent)],
+    })
+}
+
+/// Insights from comparing the last `days` days with the `days` before them
+pub fn period_insights(days: u32) -> Vec<AiSuggestion> {
+    let days = days.max(1);
+    let since = Utc::now() - Duration::days(days as i64);
+    let entries = UsageReader::new().read_entries(Some(days * 2));
+
+    let current = totals(entries.iter().filter(|e| e.timestamp >= since));
+    let previous = totals(entries.iter().filter(|e| e.timestamp < since));
+
+    let mut insights = Vec::new();
+    insights.extend(spend_insight(&current, &previous, days));
+    insights.extend(project_insights(&current, &previous, days));
+    // An edit that explains the drop beats the bare number
+    match claude_md_cache_insight(&entries, since, days) {
+        Some(insight) => insights.push(insight),
+        None => insights.extend(cache_insight(&current, &previous, days)),
+    }
+    insights.extend(model_mix_insight(&current, &previous, days));
+    insights
+}
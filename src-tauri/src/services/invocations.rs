@@ -0,0 +1,309 @@
+//! Slash command and agent usage
+//!
+//! Claude Code logs a slash command as a user message containing
+//! `<command-name>/name</command-name>`, and a subagent as a `Task` tool call
+//! with `subagent_type` in its input. Counting those gives per-command and
+//! per-agent usage. Cost is estimated: a command is charged with the responses
+//! up to the next prompt, an agent with the usage reported in its tool result.
+//! Commands and agents defined in ~/.claude or a project that never ran are
+//! listed with a count of 0.
+
+use super::config;
+use super::pricing;
+use super::usage::UsageReader;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InvocationKind {
+    Command,
+    Agent,
+}
+
+/// Usage of one slash command or agent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvocationStats {
+    pub kind: InvocationKind,
+    /// Command name without the slash, or the agent's subagent_type
+    pub name: String,
+    pub count: u64,
+    pub sessions: u64,
+    pub last_used: Option<String>,
+    pub estimated_cost: f64,
+    /// Definition file, None for built-in commands and agents
+    pub defined_at: Option<String>,
+    pub projects: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InvocationReport {
+    pub commands: Vec<InvocationStats>,
+    pub agents: Vec<InvocationStats>,
+}
+
+#[derive(Default)]
+struct Tally {
+    count: u64,
+    sessions: HashSet<String>,
+    last_used: Option<DateTime<Utc>>,
+    cost: f64,
+    projects: HashSet<String>,
+}
+
+type Key = (InvocationKind, String);
+
+pub fn timestamp(entry: &Value) -> Option<DateTime<Utc>> {
+    entry
+        .get("timestamp")
+        .and_then(|t| t.as_str())
+        .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Text of a user message (string content or its text blocks)
+pub fn user_text(message: &Value) -> Option<String> {
+    match message.get("content")? {
+        Value::String(text) => Some(text.clone()),
+        Value::Array(blocks) => {
+            let text: Vec<&str> = blocks
+                .iter()
+                .filter(|b| b.get("type").and_then(|t| t.as_str()) == Some("text"))
+                .filter_map(|b| b.get("text").and_then(|t| t.as_str()))
+                .collect();
+            (!text.is_empty()).then(|| text.join("\n"))
+        }
+        _ => None,
+    }
+}
+
+pub fn command_name(text: &str) -> Option<String> {
+    let start = text.find("<command-name>")? + "<command-name>".len();
+    let end = start + text[start..].find("</command-name>")?;
+    let name = text[start..end].trim().trim_start_matches('/');
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+pub fn usage_cost(model: &str, at: DateTime<Utc>, usage: &Value) -> f64 {
+    let tokens = |key: &str| usage.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+    pricing::calculate_cost(
+        model,
+        Some(at),
+        usage.get("service_tier").and_then(|t| t.as_str()),
+        tokens("input_tokens"),
+        tokens("output_tokens"),
+        tokens("cache_creation_input_tokens"),
+        tokens("cache_read_input_tokens"),
+    )
+}
+
+/// Tally the invocations in one session file
+fn scan_file(
+    file: &std::path::Path,
+    project: &str,
+    cutoff: Option<DateTime<Utc>>,
+    tallies: &mut HashMap<Key, Tally>,
+) {
+    let Ok(handle) = File::open(file) else {
+        return;
+    };
+    // Command whose responses are being charged, until the next prompt
+    let mut active_command: Option<String> = None;
+    // Task tool_use id -> (agent, model of the calling response)
+    let mut pending_agents: HashMap<String, (String, String)> = HashMap::new();
+    let mut seen_messages: HashSet<String> = HashSet::new();
+
+    for line in BufReader::new(handle).lines().map_while(Result::ok) {
+        let Ok(entry) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        let Some(at) = timestamp(&entry) else {
+            continue;
+        };
+        let in_range = cutoff.is_none_or(|cutoff| at >= cutoff);
+        let session = entry
+            .get("sessionId")
+            .and_then(|s| s.as_str())
+            .unwrap_or_default();
+        let Some(message) = entry.get("message") else {
+            continue;
+        };
+
+        match entry.get("type").and_then(|t| t.as_str()) {
+            Some("user") => {
+                // The agent's usage arrives with its tool result
+                if let Some(blocks) = message.get("content").and_then(|c| c.as_array()) {
+                    for block in blocks {
+                        let Some(id) = block.get("tool_use_id").and_then(|i| i.as_str()) else {
+                            continue;
+                        };
+                        let Some((agent, model)) = pending_agents.remove(id) else {
+                            continue;
+                        };
+                        let usage = entry.get("toolUseResult").and_then(|r| r.get("usage"));
+                        if let (Some(usage), true) = (usage, in_range) {
+                            tallies
+                                .entry((InvocationKind::Agent, agent))
+                                .or_default()
+                                .cost += usage_cost(&model, at, usage);
+                        }
+                    }
+                }
+
+                // Subagent prompts and the expanded command text (isMeta) are not
+                // new prompts from the user
+                let flag = |key: &str| entry.get(key).and_then(|v| v.as_bool()) == Some(true);
+                if flag("isSidechain") || flag("isMeta") {
+                    continue;
+                }
+                let Some(text) = user_text(message) else {
+                    continue;
+                };
+                active_command = command_name(&text);
+                if let (Some(name), true) = (&active_command, in_range) {
+                    let tally = tallies
+                        .entry((InvocationKind::Command, name.clone()))
+                        .or_default();
+                    tally.count += 1;
+                    tally.sessions.insert(session.to_string());
+                    tally.projects.insert(project.to_string());
+                    tally.last_used = tally.last_used.max(Some(at));
+                }
+            }
+            Some("assistant") => {
+                let model = message
+                    .get("model")
+                    .and_then(|m| m.as_str())
+                    .unwrap_or_default();
+                if let Some(blocks) = message.get("content").and_then(|c| c.as_array()) {
+                    for block in blocks {
+                        if block.get("name").and_then(|n| n.as_str()) != Some("Task") {
+                            continue;
+                        }
+                        let agent = block
+                            .get("input")
+                            .and_then(|i| i.get("subagent_type"))
+                            .and_then(|s| s.as_str())
+                            .unwrap_or("general-purpose")
+                            .to_string();
+                        if let Some(id) = block.get("id").and_then(|i| i.as_str()) {
+                            pending_agents
+                                .insert(id.to_string(), (agent.clone(), model.to_string()));
+                        }
+                        if in_range {
+                            let tally = tallies.entry((InvocationKind::Agent, agent)).or_default();
+                            tally.count += 1;
+                            tally.sessions.insert(session.to_string());
+                            tally.projects.insert(project.to_string());
+                            tally.last_used = tally.last_used.max(Some(at));
+                        }
+                    }
+                }
+
+                // One response spans several log lines with the same usage
+                let first_line = message
+                    .get("id")
+                    .and_then(|i| i.as_str())
+                    .is_none_or(|id| seen_messages.insert(id.to_string()));
+                if let (Some(name), Some(usage), true, true) =
+                    (&active_command, message.get("usage"), first_line, in_range)
+                {
+                    tallies
+                        .entry((InvocationKind::Command, name.clone()))
+                        .or_default()
+                        .cost += usage_cost(model, at, usage);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Command and agent definitions by name (global first, then projects)
+pub fn definitions(kind: InvocationKind) -> HashMap<String, String> {
+    let projects = config::registered_project_paths();
+    let items: Vec<(String, String, bool)> = match kind {
+        InvocationKind::Command => config::list_commands()
+            .unwrap_or_default()
+            .into_iter()
+            .chain(
+                projects
+                    .iter()
+                    .flat_map(|p| config::get_project_commands(p).unwrap_or_default()),
+            )
+            .map(|c| (c.name, c.path, c.is_directory))
+            .collect(),
+        InvocationKind::Agent => config::list_agents()
+            .unwrap_or_default()
+            .into_iter()
+            .chain(
+                projects
+                    .iter()
+                    .flat_map(|p| config::get_project_agents(p).unwrap_or_default()),
+            )
+            .map(|a| (a.name, a.path, a.is_directory))
+            .collect(),
+    };
+
+    let mut found = HashMap::new();
+    for (name, path, is_directory) in items {
+        if !is_directory {
+            found.entry(name).or_insert(path);
+        }
+    }
+    found
+}
+
+/// Command and agent usage over the last `days` days (all time if None)
+pub fn invocation_stats(days: Option<u32>) -> InvocationReport {
+    let cutoff = days.map(|d| Utc::now() - chrono::Duration::days(d as i64));
+    let reader = UsageReader::new();
+    let mut tallies: HashMap<Key, Tally> = HashMap::new();
+
+    for file in reader.find_jsonl_files() {
+        let project = reader.extract_project_path(&file);
+        scan_file(&file, &project, cutoff, &mut tallies);
+    }
+
+    let mut report = InvocationReport::default();
+    for kind in [InvocationKind::Command, InvocationKind::Agent] {
+        let defined = definitions(kind);
+        let mut names: HashSet<String> = defined.keys().cloned().collect();
+        names.extend(
+            tallies
+                .keys()
+                .filter(|(k, _)| *k == kind)
+                .map(|(_, n)| n.clone()),
+        );
+
+        let mut stats: Vec<InvocationStats> = names
+            .into_iter()
+            .map(|name| {
+                let tally = tallies.remove(&(kind, name.clone())).unwrap_or_default();
+                let mut projects: Vec<String> = tally.projects.into_iter().collect();
+                projects.sort();
+                InvocationStats {
+                    kind,
+                    defined_at: defined.get(&name).cloned(),
+                    name,
+                    count: tally.count,
+                    sessions: tally.sessions.len() as u64,
+                    last_used: tally.last_used.map(|t| t.to_rfc3339()),
+                    estimated_cost: tally.cost,
+                    projects,
+                }
+            })
+            .collect();
+        stats.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+
+        match kind {
+            InvocationKind::Command => report.commands = stats,
+            InvocationKind::Agent => report.agents = stats,
+        }
+    }
+    report
+}
@@ -0,0 +1,64 @@
+//! Kiosk dashboard window
+//!
+//! A chrome-less fullscreen window showing rotating stat pages, meant for a
+//! spare monitor or an office TV. The frontend renders the kiosk view when its
+//! window label is `kiosk`; refresh and rotation intervals come from
+//! `AppSettings::kiosk`.
+
+use super::window_state::ANALYTICS_WINDOW;
+use tauri::{AppHandle, Manager, Monitor, Runtime, WebviewUrl, WebviewWindowBuilder};
+
+/// Label of the kiosk window
+pub const KIOSK_WINDOW: &str = "kiosk";
+
+/// Prefer a monitor the analytics window is not on
+fn pick_monitor<R: Runtime>(app: &AppHandle<R>) -> Option<Monitor> {
+    let monitors = app.available_monitors().ok()?;
+    let current = app
+        .get_webview_window(ANALYTICS_WINDOW)
+        .and_then(|window| window.current_monitor().ok().flatten());
+
+    monitors
+        .iter()
+        .find(|monitor| {
+            current
+                .as_ref()
+                .is_none_or(|current| monitor.position() != current.position())
+        })
+        .or(monitors.first())
+        .cloned()
+}
+
+/// Open the kiosk window fullscreen, or focus it if it is already open
+pub fn open<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window(KIOSK_WINDOW) {
+        window.show().map_err(|e| e.to_string())?;
+        return window.set_focus().map_err(|e| e.to_string());
+    }
+
+    let mut builder =
+        WebviewWindowBuilder::new(app, KIOSK_WINDOW, WebviewUrl::App("index.html".into()))
+            .title("Claudit - Dashboard")
+            .decorations(false)
+            .fullscreen(true)
+            .focused(true);
+
+    // Place the window on the chosen monitor before going fullscreen
+    if let Some(monitor) = pick_monitor(app) {
+        let position = monitor.position().to_logical::<f64>(monitor.scale_factor());
+        builder = builder.position(position.x, position.y);
+    }
+
+    builder
+        .build()
+        .map_err(|e| format!("Failed to open kiosk dashboard: {}", e))?;
+    Ok(())
+}
+
+/// Close the kiosk window if it is open
+pub fn close<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window(KIOSK_WINDOW) {
+        window.close().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
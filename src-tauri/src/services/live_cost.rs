@@ -0,0 +1,138 @@
+//! Live session cost
+//!
+//! Running cost of in-progress sessions. Each session file is tailed: an
+//! update reads only the lines appended since the previous one and adds their
+//! usage to the running totals, so a long session isn't re-read on every hook
+//! event. Hook events trigger updates as they arrive; the active sessions
+//! refresh covers sessions without hooks.
+
+use super::pricing::calculate_cost;
+use super::UsageReader;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Files not updated for this long are dropped from the cache
+const TAIL_EXPIRY_HOURS: i64 = 6;
+
+/// Running totals of a session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiveSessionCost {
+    pub session_id: String,
+    pub cost: f64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub messages: u64,
+    /// Model of the latest response
+    pub model: Option<String>,
+    pub last_message_at: Option<String>,
+}
+
+/// A session file read up to `offset`
+struct Tail {
+    offset: u64,
+    /// Responses counted so far (message id, or line uuid)
+    seen: HashSet<String>,
+    totals: LiveSessionCost,
+    touched: DateTime<Utc>,
+}
+
+static TAILS: Mutex<Option<HashMap<PathBuf, Tail>>> = Mutex::new(None);
+
+fn new_tail(path: &Path) -> Tail {
+    Tail {
+        offset: 0,
+        seen: HashSet::new(),
+        totals: LiveSessionCost {
+            session_id: path
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            cost: 0.0,
+            input_tokens: 0,
+            output_tokens: 0,
+            messages: 0,
+            model: None,
+            last_message_at: None,
+        },
+        touched: Utc::now(),
+    }
+}
+
+/// Read the lines appended to `path` since the last update and return the
+/// session's running totals
+pub fn update(path: &Path) -> Option<LiveSessionCost> {
+    let mut file = File::open(path).ok()?;
+    let len = file.metadata().ok()?.len();
+    let reader = UsageReader::new();
+    let project = reader.extract_project_path(path);
+
+    let mut tails = TAILS.lock().unwrap();
+    let tails = tails.get_or_insert_with(HashMap::new);
+    tails.retain(|_, t| Utc::now() - t.touched < Duration::hours(TAIL_EXPIRY_HOURS));
+    let tail = tails
+        .entry(path.to_path_buf())
+        .or_insert_with(|| new_tail(path));
+    // A shorter file was rewritten; start over
+    if len < tail.offset {
+        *tail = new_tail(path);
+    }
+    tail.touched = Utc::now();
+
+    file.seek(SeekFrom::Start(tail.offset)).ok()?;
+    let mut lines = BufReader::new(file);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        let read = lines.read_line(&mut line).ok()?;
+        // Stop at the end, or before a line that is still being written
+        if read == 0 || !line.ends_with('\n') {
+            break;
+        }
+        tail.offset += read as u64;
+
+        let Some(entry) = reader.parse_line(line.trim_end(), &project) else {
+            continue;
+        };
+        let key = entry
+            .message_id
+            .clone()
+            .unwrap_or_else(|| entry.uuid.clone());
+        if !key.is_empty() && !tail.seen.insert(key) {
+            continue;
+        }
+
+        let totals = &mut tail.totals;
+        totals.cost += calculate_cost(
+            &entry.model,
+            Some(entry.timestamp),
+            entry.service_tier.as_deref(),
+            entry.input_tokens,
+            entry.output_tokens,
+            entry.cache_creation_tokens,
+            entry.cache_read_tokens,
+        );
+        totals.input_tokens += entry.input_tokens;
+        totals.output_tokens += entry.output_tokens;
+        totals.messages += 1;
+        totals.model = Some(entry.model);
+        totals.last_message_at = Some(entry.timestamp.to_rfc3339());
+        if !entry.session_id.is_empty() {
+            totals.session_id = entry.session_id;
+        }
+    }
+
+    Some(tail.totals.clone())
+}
+
+/// Running totals of a session by id
+pub fn get(session_id: &str) -> Result<LiveSessionCost, String> {
+    let file = UsageReader::new()
+        .find_session_file(None, Some(session_id), None)
+        .ok_or_else(|| format!("Session not found: {}", session_id))?;
+    update(&file).ok_or_else(|| format!("Failed to read session {}", session_id))
+}
@@ -0,0 +1,165 @@
+//! Live session viewer
+//!
+//! Streams the conversations of sessions running in the terminal, without
+//! opening and following them first. Every hook event of a session (a
+//! submitted prompt, a finished tool call, a stop) reads what its JSONL file
+//! gained since the previous event, and the hook server emits the new and
+//! updated messages as `session-message-appended`. A submitted prompt comes
+//! straight from the hook payload, since Claude writes it to the file later.
+
+use super::session_follow::Tail;
+use crate::types::{ConversationMessage, HookEvent};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Messages sent when a session first shows up; older ones are in the session viewer
+const BACKLOG: usize = 20;
+
+/// Sessions without hook events for this long are dropped
+const IDLE_MINUTES: i64 = 60;
+
+/// A running session and how far its file has been read
+struct LiveSession {
+    project: Option<String>,
+    tail: Tail,
+    last_event: String,
+    last_event_at: DateTime<Utc>,
+}
+
+/// Live sessions by session id
+static LIVE: Mutex<Option<HashMap<String, LiveSession>>> = Mutex::new(None);
+
+/// Payload of the `session-message-appended` event
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionMessageAppended {
+    pub session_id: String,
+    pub project: Option<String>,
+    /// Hook event that triggered the read
+    pub event: String,
+    /// New messages, and earlier ones with more content (same uuid)
+    pub messages: Vec<ConversationMessage>,
+    /// The session just showed up or its file was rewritten: `messages` are
+    /// its latest messages and replace whatever was shown
+    pub reset: bool,
+    /// Prompt of a `UserPromptSubmit` event, before it is in the file
+    pub prompt: Option<String>,
+}
+
+/// A session with recent hook events
+#[derive(Debug, Clone, Serialize)]
+pub struct LiveSessionInfo {
+    pub session_id: String,
+    pub project: Option<String>,
+    pub last_event: String,
+    pub last_event_at: String,
+    pub message_count: usize,
+}
+
+/// The latest `BACKLOG` messages
+fn backlog(messages: &[ConversationMessage]) -> Vec<ConversationMessage> {
+    messages[messages.len().saturating_sub(BACKLOG)..].to_vec()
+}
+
+/// Read what a hook event's session file gained since its previous event.
+/// Returns the payload to emit, or None if there is nothing new.
+pub fn on_hook_event(event: &HookEvent, file: &Path) -> Option<SessionMessageAppended> {
+    let session_id = event
+        .session_id
+        .clone()
+        .filter(|id| !id.is_empty())
+        .or_else(|| file.file_stem().map(|s| s.to_string_lossy().to_string()))?;
+    let now = Utc::now();
+
+    let mut live = LIVE.lock().unwrap();
+    let sessions = live.get_or_insert_with(HashMap::new);
+    sessions.retain(|_, session| now - session.last_event_at < Duration::minutes(IDLE_MINUTES));
+
+    let mut first = false;
+    let session = sessions.entry(session_id.clone()).or_insert_with(|| {
+        first = true;
+        LiveSession {
+            project: None,
+            tail: Tail::new(file.to_path_buf()),
+            last_event: String::new(),
+            last_event_at: now,
+        }
+    });
+    session.last_event = event.event.clone();
+    session.last_event_at = now;
+    if event.cwd.is_some() {
+        session.project = event.cwd.clone();
+    }
+
+    let (changed, rewritten) = match session.tail.read() {
+        Ok(read) => read,
+        Err(e) => {
+            eprintln!("{}", e);
+            return None;
+        }
+    };
+    let reset = first || rewritten;
+    let messages = if reset {
+        backlog(&session.tail.builder.messages)
+    } else {
+        changed
+            .into_iter()
+            .filter_map(|idx| session.tail.builder.messages.get(idx).cloned())
+            .collect()
+    };
+    let prompt = event
+        .payload
+        .as_ref()
+        .filter(|_| event.event == "UserPromptSubmit")
+        .and_then(|payload| payload.get("prompt"))
+        .and_then(|prompt| prompt.as_str())
+        .map(str::to_string);
+
+    if !reset && messages.is_empty() && prompt.is_none() {
+        return None;
+    }
+    Some(SessionMessageAppended {
+        session_id,
+        project: session.project.clone(),
+        event: event.event.clone(),
+        messages,
+        reset,
+        prompt,
+    })
+}
+
+/// Sessions with hook events in the last hour, most recent first
+pub fn list() -> Vec<LiveSessionInfo> {
+    let now = Utc::now();
+    let live = LIVE.lock().unwrap();
+    let mut sessions: Vec<LiveSessionInfo> = live
+        .iter()
+        .flatten()
+        .filter(|(_, session)| now - session.last_event_at < Duration::minutes(IDLE_MINUTES))
+        .map(|(session_id, session)| LiveSessionInfo {
+            session_id: session_id.clone(),
+            project: session.project.clone(),
+            last_event: session.last_event.clone(),
+            last_event_at: session.last_event_at.to_rfc3339(),
+            message_count: session.tail.builder.messages.len(),
+        })
+        .collect();
+    sessions.sort_by(|a, b| b.last_event_at.cmp(&a.last_event_at));
+    sessions
+}
+
+/// The latest messages of a live session, as a `reset` payload
+pub fn snapshot(session_id: &str) -> Option<SessionMessageAppended> {
+    let live = LIVE.lock().unwrap();
+    let session = live.as_ref()?.get(session_id)?;
+    Some(SessionMessageAppended {
+        session_id: session_id.to_string(),
+        project: session.project.clone(),
+        event: session.last_event.clone(),
+        messages: backlog(&session.tail.builder.messages),
+        reset: true,
+        prompt: None,
+    })
+}
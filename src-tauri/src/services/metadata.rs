@@ -0,0 +1,169 @@
+//! Claudit's own project metadata
+//!
+//! Data that belongs to claudit rather than Claude Code, stored in
+//! `metadata.json` in claudit's config directory: per-project notification
+//! overrides, session notes and action items (keyed by project path), and the
+//! library of starred prompts.
+
+use super::settings::app_config_dir;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Notification overrides for a single project
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectNotificationSettings {
+    /// Never notify for sessions in this project
+    #[serde(default)]
+    pub muted: bool,
+    /// Only notify once a session's cost exceeds this amount (USD)
+    #[serde(default)]
+    pub min_session_cost: Option<f64>,
+}
+
+/// A to-do pulled from a session, kept on the project's checklist
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionItem {
+    pub id: String,
+    pub session_id: String,
+    pub text: String,
+    #[serde(default)]
+    pub done: bool,
+    pub created_at: String,
+}
+
+/// Metadata claudit keeps about a project
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectMetadata {
+    #[serde(default)]
+    pub notifications: ProjectNotificationSettings,
+    /// Freeform notes by session ID
+    #[serde(default)]
+    pub session_notes: HashMap<String, String>,
+    /// Titles overriding the session summary, by session ID
+    #[serde(default)]
+    pub session_titles: HashMap<String, String>,
+    #[serde(default)]
+    pub action_items: Vec<ActionItem>,
+}
+
+/// A user prompt starred from a session into the prompt library
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StarredPrompt {
+    pub id: String,
+    pub text: String,
+    /// Where it was starred from
+    #[serde(default)]
+    pub project: Option<String>,
+    #[serde(default)]
+    pub session_id: Option<String>,
+    pub starred_at: String,
+}
+
+/// Everything stored in metadata.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClauditMetadata {
+    #[serde(default)]
+    pub projects: HashMap<String, ProjectMetadata>,
+    #[serde(default)]
+    pub prompts: Vec<StarredPrompt>,
+}
+
+fn metadata_path() -> PathBuf {
+    app_config_dir().join("metadata.json")
+}
+
+/// Load claudit's metadata (defaults if missing or unreadable)
+pub fn load() -> ClauditMetadata {
+    fs::read_to_string(metadata_path())
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Persist claudit's metadata
+pub fn save(metadata: &ClauditMetadata) -> Result<(), String> {
+    let contents =
+        serde_json::to_string_pretty(metadata).map_err(|e| format!("Serialize error: {}", e))?;
+    fs::write(metadata_path(), contents).map_err(|e| format!("Write error: {}", e))
+}
+
+/// Get the metadata for a project (defaults if none stored)
+pub fn get_project(project_path: &str) -> ProjectMetadata {
+    load().projects.remove(project_path).unwrap_or_default()
+}
+
+/// Find the metadata that applies to a working directory: the project itself
+/// or the closest parent project.
+pub fn project_for_path(path: &str) -> Option<ProjectMetadata> {
+    let mut metadata = load();
+    let key = metadata
+        .projects
+        .keys()
+        .filter(|project| {
+            path == project.as_str()
+                || path
+                    .strip_prefix(project.as_str())
+                    .is_some_and(|rest| rest.starts_with(std::path::MAIN_SEPARATOR))
+        })
+        .max_by_key(|project| project.len())?
+        .clone();
+    metadata.projects.remove(&key)
+}
+
+/// Change a project's metadata and save it
+pub fn update_project<T>(
+    project_path: &str,
+    update: impl FnOnce(&mut ProjectMetadata) -> T,
+) -> Result<T, String> {
+    let mut metadata = load();
+    let result = update(
+        metadata
+            .projects
+            .entry(project_path.to_string())
+            .or_default(),
+    );
+    save(&metadata)?;
+    Ok(result)
+}
+
+/// Update the notification overrides for a project
+pub fn set_project_notifications(
+    project_path: &str,
+    settings: ProjectNotificationSettings,
+) -> Result<(), String> {
+    update_project(project_path, |project| project.notifications = settings)
+}
+
+/// Set the note on a session (an empty note removes it)
+pub fn set_session_note(project_path: &str, session_id: &str, note: &str) -> Result<(), String> {
+    update_project(project_path, |project| {
+        if note.trim().is_empty() {
+            project.session_notes.remove(session_id);
+        } else {
+            project
+                .session_notes
+                .insert(session_id.to_string(), note.to_string());
+        }
+    })
+}
+
+/// Set the title shown for a session (None or an empty title removes it)
+pub fn set_session_title(
+    project_path: &str,
+    session_id: &str,
+    title: Option<&str>,
+) -> Result<(), String> {
+    let title = title.map(str::trim).filter(|t| !t.is_empty());
+    update_project(project_path, |project| match title {
+        Some(title) => {
+            project
+                .session_titles
+                .insert(session_id.to_string(), title.to_string());
+        }
+        None => {
+            project.session_titles.remove(session_id);
+        }
+    })
+}
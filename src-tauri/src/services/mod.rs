@@ -1,17 +1,83 @@
+pub mod action_items;
+pub mod activity;
 pub mod analytics;
+pub mod analytics_db;
+pub mod api_errors;
+pub mod app_data;
+pub mod audit;
+pub mod automation;
+pub mod clipboard;
 pub mod config;
+pub mod config_backup;
+pub mod config_pack;
+pub mod config_sync;
+pub mod conflicts;
+pub mod data_gaps;
+pub mod demo;
+pub mod downsample;
+pub mod duplicates;
 pub mod environment;
+pub mod format;
+pub mod goals;
+pub mod hook_inspector;
+pub mod hook_repair;
 pub mod hooks;
+pub mod insights;
+pub mod invocations;
+pub mod kiosk;
+pub mod live_cost;
+pub mod live_sessions;
+pub mod mcp_templates;
+pub mod metadata;
+pub mod model_catalog;
+pub mod model_routing;
+pub mod notifications;
+pub mod path_mapping;
+pub mod pdf;
+pub mod paths;
+pub mod popover;
 pub mod pricing;
+pub mod project_activity;
+pub mod prompt_library;
+pub mod prompt_outcomes;
+pub mod quick_search;
+pub mod reconcile;
+pub mod reports;
+pub mod reset;
+pub mod restore_points;
+pub mod retention;
+pub mod sandbox;
+pub mod session_archive;
+pub mod session_collection;
+pub mod session_export;
+pub mod session_follow;
 pub mod sessions;
 pub mod settings;
+pub mod startup;
+pub mod suggestion_feedback;
+pub mod summary;
+pub mod telemetry;
+pub mod terminal;
+pub mod tool_rules;
+pub mod tracker;
+pub mod transfer;
+pub mod unused_config;
 pub mod usage;
+pub mod watcher;
+pub mod widget;
+pub mod window_state;
+pub mod workspaces;
+pub mod wsl;
 
 pub use analytics::AnalyticsService;
 pub use config::*;
 pub use environment::get_claude_status;
 pub use hooks::{HookInstaller, HookServer};
 pub use pricing::ModelPricing;
-pub use sessions::{export_session_html, get_session_conversation, list_sessions, search_sessions};
+pub use sessions::{
+    get_session_message_count, get_session_page, get_session_replay, list_expensive_sessions,
+    list_recent_sessions, list_sessions, search_sessions,
+};
 pub use settings::SettingsService;
+pub use tracker::SessionTracker;
 pub use usage::UsageReader;
@@ -0,0 +1,257 @@
+//! Model catalog
+//!
+//! Context window, output limit and knowledge cutoff of each Claude model.
+//! The values are bundled here and can be corrected, or extended with models
+//! released since, through `AppSettings::model_catalog_overrides`. Prices come
+//! from `pricing`, so the catalog never disagrees with cost calculations.
+//!
+//! Source: https://docs.anthropic.com/en/docs/about-claude/models
+
+use super::pricing::{self, ModelPricing};
+use super::settings::SettingsService;
+use crate::types::ModelCatalogOverride;
+use serde::{Deserialize, Serialize};
+
+/// Context window assumed for models the catalog doesn't know
+const DEFAULT_CONTEXT_WINDOW: u64 = 200_000;
+/// Output limit assumed for models the catalog doesn't know
+const DEFAULT_MAX_OUTPUT: u64 = 32_000;
+
+struct ModelSpec {
+    id: &'static str,
+    name: &'static str,
+    /// Substrings of the model ID that select this entry
+    patterns: &'static [&'static str],
+    context_window: u64,
+    /// Larger window available with the 1M-context beta
+    long_context_window: Option<u64>,
+    max_output: u64,
+    /// YYYY-MM
+    knowledge_cutoff: &'static str,
+}
+
+/// Bundled models, most specific first (`opus-4-5` before `opus-4`)
+const MODELS: &[ModelSpec] = &[
+    ModelSpec {
+        id: "claude-opus-4-5",
+        name: "Claude Opus 4.5",
+        patterns: &["opus-4-5", "opus-4.5"],
+        context_window: 200_000,
+        long_context_window: None,
+        max_output: 64_000,
+        knowledge_cutoff: "2025-03",
+    },
+    ModelSpec {
+        id: "claude-opus-4-1",
+        name: "Claude Opus 4.1",
+        patterns: &["opus-4-1", "opus-4.1"],
+        context_window: 200_000,
+        long_context_window: None,
+        max_output: 32_000,
+        knowledge_cutoff: "2025-01",
+    },
+    ModelSpec {
+        id: "claude-opus-4",
+        name: "Claude Opus 4",
+        patterns: &["opus-4"],
+        context_window: 200_000,
+        long_context_window: None,
+        max_output: 32_000,
+        knowledge_cutoff: "2025-01",
+    },
+    ModelSpec {
+        id: "claude-sonnet-4-5",
+        name: "Claude Sonnet 4.5",
+        patterns: &["sonnet-4-5", "sonnet-4.5"],
+        context_window: 200_000,
+        long_context_window: Some(1_000_000),
+        max_output: 64_000,
+        knowledge_cutoff: "2025-01",
+    },
+    ModelSpec {
+        id: "claude-sonnet-4",
+        name: "Claude Sonnet 4",
+        patterns: &["sonnet-4"],
+        context_window: 200_000,
+        long_context_window: Some(1_000_000),
+        max_output: 64_000,
+        knowledge_cutoff: "2025-01",
+    },
+    ModelSpec {
+        id: "claude-3-7-sonnet",
+        name: "Claude Sonnet 3.7",
+        patterns: &["3-7-sonnet", "sonnet-3-7", "sonnet-3.7"],
+        context_window: 200_000,
+        long_context_window: None,
+        max_output: 64_000,
+        knowledge_cutoff: "2024-10",
+    },
+    ModelSpec {
+        id: "claude-3-5-sonnet",
+        name: "Claude Sonnet 3.5",
+        patterns: &["3-5-sonnet", "sonnet-3-5", "sonnet-3.5"],
+        context_window: 200_000,
+        long_context_window: None,
+        max_output: 8_192,
+        knowledge_cutoff: "2024-04",
+    },
+    ModelSpec {
+        id: "claude-haiku-4-5",
+        name: "Claude Haiku 4.5",
+        patterns: &["haiku-4-5", "haiku-4.5"],
+        context_window: 200_000,
+        long_context_window: None,
+        max_output: 64_000,
+        knowledge_cutoff: "2025-02",
+    },
+    ModelSpec {
+        id: "claude-3-5-haiku",
+        name: "Claude Haiku 3.5",
+        patterns: &["3-5-haiku", "haiku-3-5", "haiku-3.5"],
+        context_window: 200_000,
+        long_context_window: None,
+        max_output: 8_192,
+        knowledge_cutoff: "2024-07",
+    },
+    ModelSpec {
+        id: "claude-3-opus",
+        name: "Claude Opus 3",
+        patterns: &["3-opus", "opus-3"],
+        context_window: 200_000,
+        long_context_window: None,
+        max_output: 4_096,
+        knowledge_cutoff: "2023-08",
+    },
+    ModelSpec {
+        id: "claude-3-haiku",
+        name: "Claude Haiku 3",
+        patterns: &["3-haiku", "haiku-3"],
+        context_window: 200_000,
+        long_context_window: None,
+        max_output: 4_096,
+        knowledge_cutoff: "2023-08",
+    },
+];
+
+/// A model with its limits and current prices
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelInfo {
+    pub id: String,
+    pub name: String,
+    /// Substrings of a model ID that select this entry
+    pub patterns: Vec<String>,
+    pub context_window: u64,
+    pub long_context_window: Option<u64>,
+    pub max_output: u64,
+    pub knowledge_cutoff: Option<String>,
+    pub pricing: ModelPricing,
+    /// Some values come from the user's overrides
+    pub overridden: bool,
+}
+
+impl ModelInfo {
+    fn matches(&self, model: &str) -> bool {
+        let model = model.to_lowercase();
+        model == self.id || self.patterns.iter().any(|p| model.contains(p.as_str()))
+    }
+
+    /// Context window a prompt of this size runs in: the long-context window
+    /// once the prompt no longer fits the standard one
+    pub fn window_for(&self, context_tokens: u64) -> u64 {
+        match self.long_context_window {
+            Some(long) if context_tokens > self.context_window => long,
+            _ => self.context_window,
+        }
+    }
+
+    fn apply(&mut self, o: &ModelCatalogOverride) {
+        self.context_window = o.context_window.unwrap_or(self.context_window);
+        self.max_output = o.max_output.unwrap_or(self.max_output);
+        if o.knowledge_cutoff.is_some() {
+            self.knowledge_cutoff = o.knowledge_cutoff.clone();
+        }
+        self.overridden = true;
+    }
+}
+
+fn bundled(spec: &ModelSpec) -> ModelInfo {
+    let mut pricing = pricing::get_pricing_for_model(spec.id);
+    if spec.long_context_window.is_none() {
+        pricing.long_context = None;
+    }
+    ModelInfo {
+        id: spec.id.to_string(),
+        name: spec.name.to_string(),
+        patterns: spec.patterns.iter().map(|p| p.to_string()).collect(),
+        context_window: spec.context_window,
+        long_context_window: spec.long_context_window,
+        max_output: spec.max_output,
+        knowledge_cutoff: Some(spec.knowledge_cutoff.to_string()),
+        pricing,
+        overridden: false,
+    }
+}
+
+/// Entry for a model the bundled catalog doesn't know
+fn unknown(model: &str) -> ModelInfo {
+    ModelInfo {
+        id: model.to_string(),
+        name: model.to_string(),
+        patterns: Vec::new(),
+        context_window: DEFAULT_CONTEXT_WINDOW,
+        long_context_window: None,
+        max_output: DEFAULT_MAX_OUTPUT,
+        knowledge_cutoff: None,
+        pricing: pricing::get_pricing_for_model(model),
+        overridden: false,
+    }
+}
+
+/// Bundled models with the user's overrides applied. Overrides for IDs the
+/// catalog doesn't list come first, so they win over a looser bundled pattern.
+pub fn catalog() -> Vec<ModelInfo> {
+    let mut models: Vec<ModelInfo> = MODELS.iter().map(bundled).collect();
+    let mut added = Vec::new();
+    for o in SettingsService::shared().get().model_catalog_overrides {
+        let model = o.model.trim().to_lowercase();
+        match models.iter_mut().find(|m| m.id == model) {
+            Some(existing) => existing.apply(&o),
+            None => {
+                // A dated ID (or a new model) starts from the closest bundled entry
+                let mut info = find(&models, &model);
+                info.id = model.clone();
+                info.patterns = vec![model];
+                info.apply(&o);
+                added.push(info);
+            }
+        }
+    }
+    added.extend(models);
+    added
+}
+
+/// The catalog entry for a model ID (defaults for unknown models)
+pub fn find(catalog: &[ModelInfo], model: &str) -> ModelInfo {
+    catalog
+        .iter()
+        .find(|m| m.matches(model))
+        .cloned()
+        .unwrap_or_else(|| unknown(model))
+}
+
+/// Check overrides before the settings are saved
+pub fn validate_overrides(overrides: &[ModelCatalogOverride]) -> Result<(), String> {
+    for o in overrides {
+        if o.model.trim().is_empty() {
+            return Err("A model override needs a model ID".to_string());
+        }
+        if o.context_window == Some(0) || o.max_output == Some(0) {
+            return Err(format!("Limits for {} must be above zero", o.model));
+        }
+        if let Some(cutoff) = &o.knowledge_cutoff {
+            chrono::NaiveDate::parse_from_str(&format!("{}-01", cutoff), "%Y-%m-%d")
+                .map_err(|_| format!("Knowledge cutoff must be YYYY-MM: {}", cutoff))?;
+        }
+    }
+    Ok(())
+}
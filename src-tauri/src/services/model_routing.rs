@@ -0,0 +1,374 @@
+//! Model routing advisor
+//!
+//! Agents and slash commands can pin a model with `model:` in their
+//! frontmatter. This looks at the turns each one ran in the period and, when
+//! most of them were small (few output tokens) on Opus or Sonnet, suggests a
+//! cheaper model with the saving it would have made, re-priced from the same
+//! token counts. Applying a suggestion edits the frontmatter through the audit
+//! log, so it can be undone.
+//!
+//! An agent's turns are its runs, with the usage from the `Task` tool result;
+//! a command's turns are the responses up to the next prompt.
+
+use super::audit::{FileOperation, FilePlan};
+use super::invocations::{self, InvocationKind};
+use super::usage::UsageReader;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// A turn producing fewer output tokens than this counts as low-token
+const LOW_TOKEN_OUTPUT: u64 = 1_500;
+
+/// Fewer turns than this are too few to suggest anything
+const MIN_TURNS: usize = 5;
+
+/// Share of low-token turns from which Haiku is suggested
+const HAIKU_SHARE: f64 = 0.8;
+
+/// Share of low-token turns from which Sonnet is suggested over Opus
+const SONNET_SHARE: f64 = 0.5;
+
+/// Values `model:` accepts in agent and command frontmatter
+const MODEL_ALIASES: &[&str] = &["haiku", "sonnet", "opus", "inherit"];
+
+/// A suggested `model:` override for one agent or command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelRoutingSuggestion {
+    pub kind: InvocationKind,
+    pub name: String,
+    /// Definition file the override goes into
+    pub path: String,
+    /// `model:` currently in the frontmatter, None when it inherits
+    pub current_model: Option<String>,
+    /// Model most of the turns ran on
+    pub observed_model: String,
+    pub turns: usize,
+    pub low_token_turns: usize,
+    pub average_output_tokens: f64,
+    /// Alias to set (`haiku` or `sonnet`)
+    pub suggested_model: String,
+    pub cost: f64,
+    /// Cost of the same turns on the suggested model
+    pub suggested_cost: f64,
+    pub estimated_saving: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelRoutingReport {
+    pub days: u32,
+    /// Agents and commands with a definition file and at least one turn
+    pub checked: usize,
+    pub suggestions: Vec<ModelRoutingSuggestion>,
+}
+
+/// One response of an agent or command
+struct Turn {
+    model: String,
+    at: DateTime<Utc>,
+    usage: Value,
+}
+
+type Key = (InvocationKind, String);
+
+/// Model family of a model ID or alias
+fn family(model: &str) -> Option<&'static str> {
+    let model = model.to_lowercase();
+    ["opus", "sonnet", "haiku"]
+        .into_iter()
+        .find(|f| model.contains(f))
+}
+
+/// Model ID a family alias is priced as
+fn priced_as(alias: &str) -> &'static str {
+    match alias {
+        "haiku" => "claude-haiku-4-5",
+        "opus" => "claude-opus-4-5",
+        _ => "claude-sonnet-4-5",
+    }
+}
+
+/// Value of `key:` in the YAML frontmatter of `content`
+fn frontmatter_value(content: &str, key: &str) -> Option<String> {
+    let mut lines = content.lines();
+    if lines.next()?.trim() != "---" {
+        return None;
+    }
+    let prefix = format!("{}:", key);
+    lines
+        .take_while(|line| line.trim() != "---")
+        .find_map(|line| line.trim().strip_prefix(&prefix).map(str::to_string))
+        .map(|value| {
+            value
+                .trim()
+                .trim_matches('"')
+                .trim_matches('\'')
+                .to_string()
+        })
+        .filter(|value| !value.is_empty())
+}
+
+/// `content` with `model: <model>` set in its frontmatter, adding the
+/// frontmatter if there is none
+fn with_model(content: &str, model: &str) -> String {
+    let line = format!("model: {}", model);
+    let mut lines: Vec<&str> = content.lines().collect();
+    let has_frontmatter = lines.first().is_some_and(|l| l.trim() == "---")
+        && lines.iter().skip(1).any(|l| l.trim() == "---");
+
+    if !has_frontmatter {
+        return format!("---\n{}\n---\n\n{}", line, content);
+    }
+    let end = lines
+        .iter()
+        .skip(1)
+        .position(|l| l.trim() == "---")
+        .map_or(lines.len(), |i| i + 1);
+    match lines[1..end]
+        .iter()
+        .position(|l| l.trim_start().starts_with("model:"))
+    {
+        Some(i) => lines[i + 1] = &line,
+        None => lines.insert(end, &line),
+    }
+    let mut updated = lines.join("\n");
+    if content.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated
+}
+
+/// Collect the turns of each agent and command in one session file
+fn scan_file(file: &Path, cutoff: DateTime<Utc>, turns: &mut HashMap<Key, Vec<Turn>>) {
+    let Ok(handle) = File::open(file) else {
+        return;
+    };
+    let mut active_command: Option<String> = None;
+    // Task tool_use id -> (agent, model of the calling response)
+    let mut pending_agents: HashMap<String, (String, String)> = HashMap::new();
+    let mut seen_messages: HashSet<String> = HashSet::new();
+
+    for line in BufReader::new(handle).lines().map_while(Result::ok) {
+        let Ok(entry) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        let Some(at) = invocations::timestamp(&entry) else {
+            continue;
+        };
+        let Some(message) = entry.get("message") else {
+            continue;
+        };
+
+        match entry.get("type").and_then(|t| t.as_str()) {
+            Some("user") => {
+                if let Some(blocks) = message.get("content").and_then(|c| c.as_array()) {
+                    for block in blocks {
+                        let Some(id) = block.get("tool_use_id").and_then(|i| i.as_str()) else {
+                            continue;
+                        };
+                        let Some((agent, model)) = pending_agents.remove(id) else {
+                            continue;
+                        };
+                        let usage = entry.get("toolUseResult").and_then(|r| r.get("usage"));
+                        if let (Some(usage), true) = (usage, at >= cutoff) {
+                            turns
+                                .entry((InvocationKind::Agent, agent))
+                                .or_default()
+                                .push(Turn {
+                                    model,
+                                    at,
+                                    usage: usage.clone(),
+                                });
+                        }
+                    }
+                }
+
+                let flag = |key: &str| entry.get(key).and_then(|v| v.as_bool()) == Some(true);
+                if flag("isSidechain") || flag("isMeta") {
+                    continue;
+                }
+                if let Some(text) = invocations::user_text(message) {
+                    active_command = invocations::command_name(&text);
+                }
+            }
+            Some("assistant") => {
+                let model = message
+                    .get("model")
+                    .and_then(|m| m.as_str())
+                    .unwrap_or_default();
+                if let Some(blocks) = message.get("content").and_then(|c| c.as_array()) {
+                    for block in blocks {
+                        if block.get("name").and_then(|n| n.as_str()) != Some("Task") {
+                            continue;
+                        }
+                        let agent = block
+                            .get("input")
+                            .and_then(|i| i.get("subagent_type"))
+                            .and_then(|s| s.as_str())
+                            .unwrap_or("general-purpose");
+                        if let Some(id) = block.get("id").and_then(|i| i.as_str()) {
+                            pending_agents
+                                .insert(id.to_string(), (agent.to_string(), model.to_string()));
+                        }
+                    }
+                }
+
+                let first_line = message
+                    .get("id")
+                    .and_then(|i| i.as_str())
+                    .is_none_or(|id| seen_messages.insert(id.to_string()));
+                if let (Some(name), Some(usage), true, true) = (
+                    &active_command,
+                    message.get("usage"),
+                    first_line,
+                    at >= cutoff,
+                ) {
+                    turns
+                        .entry((InvocationKind::Command, name.clone()))
+                        .or_default()
+                        .push(Turn {
+                            model: model.to_string(),
+                            at,
+                            usage: usage.clone(),
+                        });
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The cheaper model worth suggesting for turns on `family`, if any
+fn suggest(family: &str, low_share: f64) -> Option<&'static str> {
+    match family {
+        "opus" | "sonnet" if low_share >= HAIKU_SHARE => Some("haiku"),
+        "opus" if low_share >= SONNET_SHARE => Some("sonnet"),
+        _ => None,
+    }
+}
+
+fn suggestion(
+    kind: InvocationKind,
+    name: String,
+    path: String,
+    turns: &[Turn],
+) -> Option<ModelRoutingSuggestion> {
+    let current_model = fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| frontmatter_value(&content, "model"))
+        .filter(|model| model != "inherit");
+
+    // An agent with a pinned model ran on it, not on its caller's model
+    let model_of = |turn: &Turn| match (&current_model, kind) {
+        (Some(model), InvocationKind::Agent) => priced_as(model).to_string(),
+        _ => turn.model.clone(),
+    };
+    let mut by_model: HashMap<String, usize> = HashMap::new();
+    for turn in turns {
+        *by_model.entry(model_of(turn)).or_default() += 1;
+    }
+    let observed_model = by_model
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))?
+        .0;
+
+    let output = |turn: &Turn| {
+        turn.usage
+            .get("output_tokens")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0)
+    };
+    let low_token_turns = turns
+        .iter()
+        .filter(|t| output(t) < LOW_TOKEN_OUTPUT)
+        .count();
+    let low_share = low_token_turns as f64 / turns.len() as f64;
+    let suggested = suggest(family(&observed_model)?, low_share)?;
+    if current_model.as_deref() == Some(suggested) {
+        return None;
+    }
+
+    let cost: f64 = turns
+        .iter()
+        .map(|t| invocations::usage_cost(&model_of(t), t.at, &t.usage))
+        .sum();
+    let suggested_cost: f64 = turns
+        .iter()
+        .map(|t| invocations::usage_cost(priced_as(suggested), t.at, &t.usage))
+        .sum();
+
+    Some(ModelRoutingSuggestion {
+        kind,
+        name,
+        path,
+        current_model,
+        observed_model,
+        turns: turns.len(),
+        low_token_turns,
+        average_output_tokens: turns.iter().map(output).sum::<u64>() as f64 / turns.len() as f64,
+        suggested_model: suggested.to_string(),
+        cost,
+        suggested_cost,
+        estimated_saving: (cost - suggested_cost).max(0.0),
+    })
+}
+
+/// Model overrides worth setting, from the last `days` days of turns
+pub fn suggestions(days: u32) -> ModelRoutingReport {
+    let cutoff = Utc::now() - Duration::days(days as i64);
+    let reader = UsageReader::new();
+    let mut turns: HashMap<Key, Vec<Turn>> = HashMap::new();
+    for file in reader.find_jsonl_files() {
+        scan_file(&file, cutoff, &mut turns);
+    }
+
+    let mut checked = 0;
+    let mut suggestions = Vec::new();
+    for kind in [InvocationKind::Agent, InvocationKind::Command] {
+        for (name, path) in invocations::definitions(kind) {
+            let Some(turns) = turns.get(&(kind, name.clone())) else {
+                continue;
+            };
+            checked += 1;
+            if turns.len() < MIN_TURNS {
+                continue;
+            }
+            suggestions.extend(suggestion(kind, name, path, turns));
+        }
+    }
+    suggestions.sort_by(|a, b| b.estimated_saving.total_cmp(&a.estimated_saving));
+
+    ModelRoutingReport {
+        days,
+        checked,
+        suggestions,
+    }
+}
+
+/// Set `model:` in the frontmatter of an agent or command definition
+pub fn apply(path: &str, model: &str, dry_run: bool) -> Result<Vec<FileOperation>, String> {
+    if !MODEL_ALIASES.contains(&model) {
+        return Err(format!("Unknown model: {}", model));
+    }
+    let known = [InvocationKind::Agent, InvocationKind::Command]
+        .into_iter()
+        .any(|kind| invocations::definitions(kind).values().any(|p| p == path));
+    if !known {
+        return Err(format!("Not an agent or command definition: {}", path));
+    }
+
+    let path = Path::new(path);
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut plan = FilePlan::new("set_model_override");
+    plan.write(
+        path,
+        with_model(&content, model),
+        &format!("set model: {}", model),
+    );
+    plan.run(dry_run)
+}
@@ -0,0 +1,663 @@
+//! Notification rules engine
+//!
+//! All system notifications go through `dispatch`, which applies the user's
+//! notification settings, per-project overrides and Do Not Disturb / Focus
+//! state before showing them. With a digest interval set, non-urgent
+//! notifications are held and delivered as one summary per interval instead.
+//! A temporary mute (`mute_for`) silences everything until it runs out, without
+//! touching the persistent `notifications_enabled` setting.
+
+use super::activity::{self, ActivityEntry};
+use super::format;
+use super::metadata;
+use crate::services::{SettingsService, UsageReader};
+use crate::types::AppSettings;
+use chrono::{DateTime, Duration, Local, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Runtime};
+use tauri_plugin_notification::NotificationExt;
+
+/// A notification waiting to be delivered
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingNotification {
+    pub title: String,
+    pub body: String,
+    /// Hook event that triggered the notification
+    pub event: String,
+    pub project: Option<String>,
+    pub session_id: Option<String>,
+}
+
+/// What happened to a dispatched notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DispatchOutcome {
+    Shown,
+    /// Notifications are turned off
+    Disabled,
+    /// The project is muted
+    Muted,
+    /// All notifications are muted for a while
+    MutedUntil,
+    /// The session hasn't reached the project's cost threshold yet
+    BelowCostThreshold,
+    /// Suppressed by Do Not Disturb and recorded in the activity feed
+    Queued,
+    /// Suppressed by Do Not Disturb and dropped
+    Dropped,
+    /// Held for the next digest
+    Digested,
+    Failed,
+}
+
+/// Events that can wait for the digest; anything else (Claude waiting for
+/// input, error spikes, long-running sessions) is shown right away
+const DIGEST_EVENTS: &[&str] = &["Stop", "SubagentStop", "PostToolUse"];
+
+/// A notification held for the digest
+struct DigestItem {
+    event: String,
+    project: Option<String>,
+    session_id: Option<String>,
+    cost: Option<f64>,
+}
+
+/// Notifications held since `started`
+struct Digest {
+    started: DateTime<Utc>,
+    items: Vec<DigestItem>,
+}
+
+static DIGEST: Mutex<Option<Digest>> = Mutex::new(None);
+
+/// How long to mute notifications for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MuteDuration {
+    OneHour,
+    FourHours,
+    /// Until the next morning
+    UntilTomorrow,
+}
+
+/// Local hour at which "until tomorrow" ends
+const TOMORROW_HOUR: u32 = 8;
+
+/// End of the temporary mute. Kept in memory only, so a restart never leaves
+/// notifications silenced.
+static MUTED_UNTIL: Mutex<Option<DateTime<Local>>> = Mutex::new(None);
+
+impl MuteDuration {
+    fn end(self, now: DateTime<Local>) -> DateTime<Local> {
+        match self {
+            MuteDuration::OneHour => now + Duration::hours(1),
+            MuteDuration::FourHours => now + Duration::hours(4),
+            MuteDuration::UntilTomorrow => (now.date_naive() + Duration::days(1))
+                .and_time(NaiveTime::from_hms_opt(TOMORROW_HOUR, 0, 0).unwrap_or_default())
+                .and_local_timezone(Local)
+                .earliest()
+                .unwrap_or(now + Duration::hours(12)),
+        }
+    }
+}
+
+/// Mute all notifications for a while; returns when the mute ends
+pub fn mute_for(duration: MuteDuration) -> DateTime<Local> {
+    let until = duration.end(Local::now());
+    *MUTED_UNTIL.lock().unwrap() = Some(until);
+    until
+}
+
+/// End the temporary mute early
+pub fn unmute() {
+    *MUTED_UNTIL.lock().unwrap() = None;
+}
+
+/// When the temporary mute ends, if one is active
+pub fn muted_until() -> Option<DateTime<Local>> {
+    let mut muted = MUTED_UNTIL.lock().unwrap();
+    if muted.is_some_and(|until| until <= Local::now()) {
+        *muted = None;
+    }
+    *muted
+}
+
+/// Time left on the mute, e.g. "3h 12m" or "8m"
+pub fn mute_remaining(until: DateTime<Local>) -> String {
+    // Round up so the countdown never shows 0m while still muted
+    let minutes = ((until - Local::now()).num_seconds().max(0) + 59) / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h {}m", h, m),
+    }
+}
+
+/// Check whether macOS Focus / Do Not Disturb is active
+#[cfg(target_os = "macos")]
+pub fn is_do_not_disturb() -> bool {
+    // Focus modes (macOS 12+) record active assertions in this file
+    if let Some(home) = dirs::home_dir() {
+        let assertions = home
+            .join("Library")
+            .join("DoNotDisturb")
+            .join("DB")
+            .join("Assertions.json");
+        if let Ok(contents) = std::fs::read_to_string(&assertions) {
+            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&contents) {
+                let active = json
+                    .get("data")
+                    .and_then(|d| d.as_array())
+                    .map(|data| {
+                        data.iter().any(|store| {
+                            store
+                                .get("storeAssertionRecords")
+                                .and_then(|r| r.as_array())
+                                .is_some_and(|records| !records.is_empty())
+                        })
+                    })
+                    .unwrap_or(false);
+                if active {
+                    return true;
+                }
+            }
+        }
+    }
+
+    // Pre-Monterey Do Not Disturb flag
+    std::process::Command::new("defaults")
+        .args(["-currentHost", "read", "com.apple.notificationcenterui", "doNotDisturb"])
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim() == "1")
+        .unwrap_or(false)
+}
+
+/// Value of a REG_DWORD in `reg query` output, e.g.
+/// `    NOC_GLOBAL_SETTING_TOASTS_ENABLED    REG_DWORD    0x0`
+#[cfg(any(target_os = "windows", test))]
+fn reg_dword(output: &str, name: &str) -> Option<u32> {
+    output.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        if fields.next()? != name || fields.next()? != "REG_DWORD" {
+            return None;
+        }
+        u32::from_str_radix(fields.next()?.strip_prefix("0x")?, 16).ok()
+    })
+}
+
+/// Check whether Windows notifications are silenced: Focus Assist (quiet
+/// hours, or a full-screen app or presentation) or toasts switched off
+#[cfg(target_os = "windows")]
+pub fn is_do_not_disturb() -> bool {
+    // QUERY_USER_NOTIFICATION_STATE values that hold notifications back
+    const QUNS_BUSY: i32 = 2;
+    const QUNS_RUNNING_D3D_FULL_SCREEN: i32 = 3;
+    const QUNS_PRESENTATION_MODE: i32 = 4;
+    const QUNS_QUIET_TIME: i32 = 6;
+
+    #[link(name = "shell32")]
+    extern "system" {
+        fn SHQueryUserNotificationState(state: *mut i32) -> i32;
+    }
+
+    let mut state = 0;
+    // SAFETY: the call only writes the state to the pointer it is given
+    let quiet = unsafe { SHQueryUserNotificationState(&mut state) } == 0
+        && matches!(
+            state,
+            QUNS_BUSY | QUNS_RUNNING_D3D_FULL_SCREEN | QUNS_PRESENTATION_MODE | QUNS_QUIET_TIME
+        );
+    if quiet {
+        return true;
+    }
+
+    const TOASTS_ENABLED: &str = "NOC_GLOBAL_SETTING_TOASTS_ENABLED";
+    std::process::Command::new("reg")
+        .args([
+            "query",
+            r"HKCU\Software\Microsoft\Windows\CurrentVersion\Notifications\Settings",
+            "/v",
+            TOASTS_ENABLED,
+        ])
+        .output()
+        .ok()
+        .and_then(|output| reg_dword(&String::from_utf8_lossy(&output.stdout), TOASTS_ENABLED))
+        == Some(0)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn is_do_not_disturb() -> bool {
+    false
+}
+
+/// What Do Not Disturb does to a notification, if it is active and respected
+fn do_not_disturb_outcome(settings: &AppSettings, dnd_active: bool) -> Option<DispatchOutcome> {
+    if !settings.respect_do_not_disturb || !dnd_active {
+        return None;
+    }
+    Some(if settings.queue_suppressed_notifications {
+        DispatchOutcome::Queued
+    } else {
+        DispatchOutcome::Dropped
+    })
+}
+
+/// Activity feed entry for a notification that wasn't shown
+fn suppressed_entry(notification: &PendingNotification) -> ActivityEntry {
+    ActivityEntry {
+        id: uuid::Uuid::new_v4().to_string(),
+        event: "NotificationSuppressed".to_string(),
+        tool: None,
+        session_id: notification.session_id.clone(),
+        project: notification.project.clone(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        spooled: false,
+        title: Some(notification.title.clone()),
+        body: Some(notification.body.clone()),
+    }
+}
+
+/// Record a notification that wasn't shown in the activity feed
+fn queue_to_activity<R: Runtime>(app: &AppHandle<R>, notification: &PendingNotification) {
+    let entry = suppressed_entry(notification);
+    if activity::record(&entry).is_ok() {
+        let _ = app.emit("activity-added", &entry);
+    }
+}
+
+/// Total cost so far of the session a notification belongs to
+fn notification_session_cost(notification: &PendingNotification) -> Option<f64> {
+    let reader = UsageReader::new();
+    let file = reader.find_session_file(
+        None,
+        notification.session_id.as_deref(),
+        notification.project.as_deref(),
+    )?;
+    Some(reader.session_cost(&file, None))
+}
+
+/// Apply the project's notification overrides, if any
+fn check_project_rules(notification: &PendingNotification) -> Option<DispatchOutcome> {
+    let project = metadata::project_for_path(notification.project.as_deref()?)?;
+    let rules = project.notifications;
+
+    if rules.muted {
+        return Some(DispatchOutcome::Muted);
+    }
+
+    if let Some(threshold) = rules.min_session_cost {
+        // Sessions whose cost can't be determined are let through
+        if notification_session_cost(notification).is_some_and(|cost| cost < threshold) {
+            return Some(DispatchOutcome::BelowCostThreshold);
+        }
+    }
+
+    None
+}
+
+/// Emoji before a notification's title when emoji prefixes are on
+fn event_emoji(event: &str) -> &'static str {
+    match event {
+        "Stop" | "SubagentStop" => "✅",
+        "PostToolUse" => "🛠️",
+        "Digest" => "📋",
+        "ApiErrorSpike" | "DataGap" | "HooksRepaired" => "⚠️",
+        "LongRunningSession" => "⏱️",
+        "ConfigSync" => "🔄",
+        _ => "🔔",
+    }
+}
+
+/// Show a notification, unless Do Not Disturb suppresses it
+fn show<R: Runtime>(app: &AppHandle<R>, notification: &PendingNotification) -> DispatchOutcome {
+    let settings = SettingsService::shared().get();
+
+    let dnd_active = settings.respect_do_not_disturb && is_do_not_disturb();
+    if let Some(outcome) = do_not_disturb_outcome(&settings, dnd_active) {
+        println!("Do Not Disturb active - suppressing notification");
+        if outcome == DispatchOutcome::Queued {
+            queue_to_activity(app, notification);
+        }
+        return outcome;
+    }
+
+    match app
+        .notification()
+        .builder()
+        .title(format::notification_text(&format::prefixed(
+            event_emoji(&notification.event),
+            &notification.title,
+        )))
+        .body(format::notification_text(&notification.body))
+        .show()
+    {
+        Ok(_) => DispatchOutcome::Shown,
+        Err(e) => {
+            eprintln!("Failed to send notification: {}", e);
+            DispatchOutcome::Failed
+        }
+    }
+}
+
+/// Hold a notification for the next digest
+fn add_to_digest(notification: &PendingNotification) {
+    let item = DigestItem {
+        event: notification.event.clone(),
+        project: notification.project.clone(),
+        session_id: notification.session_id.clone(),
+        cost: notification_session_cost(notification),
+    };
+    let mut digest = DIGEST.lock().unwrap();
+    digest
+        .get_or_insert_with(|| Digest {
+            started: Utc::now(),
+            items: Vec::new(),
+        })
+        .items
+        .push(item);
+}
+
+/// Summary notification for the held items
+fn digest_notification(items: &[DigestItem], minutes: i64) -> PendingNotification {
+    let finished = items.iter().filter(|i| i.event.ends_with("Stop")).count();
+    let tools = items.iter().filter(|i| i.event == "PostToolUse").count();
+    let projects: HashSet<&str> = items.iter().filter_map(|i| i.project.as_deref()).collect();
+    // A session that finished several times is counted once, at its latest cost
+    let mut seen = HashSet::new();
+    let cost: f64 = items
+        .iter()
+        .rev()
+        .filter(|i| i.session_id.is_none() || seen.insert(i.session_id.clone()))
+        .filter_map(|i| i.cost)
+        .sum();
+
+    let mut parts = Vec::new();
+    if finished > 0 {
+        parts.push(format!(
+            "{} session{} finished",
+            finished,
+            if finished == 1 { "" } else { "s" }
+        ));
+    }
+    if tools > 0 {
+        parts.push(format!(
+            "{} tool alert{}",
+            tools,
+            if tools == 1 { "" } else { "s" }
+        ));
+    }
+    if projects.len() > 1 {
+        parts.push(format!("{} projects", projects.len()));
+    }
+    if cost > 0.0 {
+        parts.push(format!("{} total", format::cost(cost)));
+    }
+
+    PendingNotification {
+        title: format!("Claude Code · last {} minutes", minutes),
+        body: parts.join(" · "),
+        event: "Digest".to_string(),
+        project: None,
+        session_id: None,
+    }
+}
+
+/// Deliver the digest once its interval has passed (or right away if the
+/// digest was turned off). Called periodically.
+pub fn flush_digest<R: Runtime>(app: &AppHandle<R>) {
+    // Held items go out once the mute is over
+    if muted_until().is_some() {
+        return;
+    }
+    let minutes = SettingsService::shared().get().notification_digest_minutes as i64;
+    let items = {
+        let mut digest = DIGEST.lock().unwrap();
+        let due = digest
+            .as_ref()
+            .is_some_and(|d| minutes == 0 || Utc::now() - d.started >= Duration::minutes(minutes));
+        if !due {
+            return;
+        }
+        digest.take().map(|d| d.items).unwrap_or_default()
+    };
+    if items.is_empty() {
+        return;
+    }
+
+    let outcome = show(app, &digest_notification(&items, minutes.max(1)));
+    println!("Digest of {} notifications: {:?}", items.len(), outcome);
+}
+
+/// Apply notification rules and show the notification if allowed
+pub fn dispatch<R: Runtime>(app: &AppHandle<R>, notification: PendingNotification) -> DispatchOutcome {
+    let settings = SettingsService::shared().get();
+
+    match held_back(&settings, muted_until().is_some(), &notification) {
+        Some(DispatchOutcome::Digested) => {
+            add_to_digest(&notification);
+            DispatchOutcome::Digested
+        }
+        Some(outcome) => outcome,
+        None => show(app, &notification),
+    }
+}
+
+/// Why the rules keep a notification from being shown right away, if they do
+fn held_back(
+    settings: &AppSettings,
+    muted: bool,
+    notification: &PendingNotification,
+) -> Option<DispatchOutcome> {
+    if !settings.notifications_enabled {
+        return Some(DispatchOutcome::Disabled);
+    }
+
+    if muted {
+        return Some(DispatchOutcome::MutedUntil);
+    }
+
+    if let Some(outcome) = check_project_rules(notification) {
+        return Some(outcome);
+    }
+
+    if settings.notification_digest_minutes > 0
+        && DIGEST_EVENTS.contains(&notification.event.as_str())
+    {
+        return Some(DispatchOutcome::Digested);
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reg_dword() {
+        let output = "\r\nHKEY_CURRENT_USER\\Software\\Microsoft\\Windows\\CurrentVersion\\Notifications\\Settings\r\n    NOC_GLOBAL_SETTING_TOASTS_ENABLED    REG_DWORD    0x0\r\n\r\n";
+        assert_eq!(
+            reg_dword(output, "NOC_GLOBAL_SETTING_TOASTS_ENABLED"),
+            Some(0)
+        );
+        let output = "    NOC_GLOBAL_SETTING_TOASTS_ENABLED    REG_DWORD    0x00000001\r\n";
+        assert_eq!(
+            reg_dword(output, "NOC_GLOBAL_SETTING_TOASTS_ENABLED"),
+            Some(1)
+        );
+        assert_eq!(reg_dword(output, "OTHER_VALUE"), None);
+        assert_eq!(
+            reg_dword(
+                "ERROR: The system was unable to find the specified registry key",
+                "NOC_GLOBAL_SETTING_TOASTS_ENABLED"
+            ),
+            None
+        );
+    }
+
+    fn notification(event: &str, project: Option<&str>) -> PendingNotification {
+        PendingNotification {
+            title: "Session finished".to_string(),
+            body: "claudit".to_string(),
+            event: event.to_string(),
+            project: project.map(|p| p.to_string()),
+            session_id: Some("session-1".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_do_not_disturb_outcome() {
+        let mut settings = AppSettings::default();
+        assert_eq!(do_not_disturb_outcome(&settings, false), None);
+        assert_eq!(
+            do_not_disturb_outcome(&settings, true),
+            Some(DispatchOutcome::Queued)
+        );
+
+        settings.queue_suppressed_notifications = false;
+        assert_eq!(
+            do_not_disturb_outcome(&settings, true),
+            Some(DispatchOutcome::Dropped)
+        );
+
+        settings.respect_do_not_disturb = false;
+        assert_eq!(do_not_disturb_outcome(&settings, true), None);
+    }
+
+    #[test]
+    fn test_suppressed_entry() {
+        let entry = suppressed_entry(&notification("Stop", Some("/work/claudit")));
+        assert_eq!(entry.event, "NotificationSuppressed");
+        assert_eq!(entry.project.as_deref(), Some("/work/claudit"));
+        assert_eq!(entry.session_id.as_deref(), Some("session-1"));
+        assert_eq!(entry.title.as_deref(), Some("Session finished"));
+        assert_eq!(entry.body.as_deref(), Some("claudit"));
+        assert!(!entry.spooled);
+    }
+
+    #[test]
+    fn test_held_back() {
+        let mut settings = AppSettings::default();
+        let stop = notification("Stop", None);
+        assert_eq!(held_back(&settings, false, &stop), None);
+        assert_eq!(
+            held_back(&settings, true, &stop),
+            Some(DispatchOutcome::MutedUntil)
+        );
+
+        settings.notification_digest_minutes = 15;
+        assert_eq!(
+            held_back(&settings, false, &stop),
+            Some(DispatchOutcome::Digested)
+        );
+        // Urgent events skip the digest
+        assert_eq!(
+            held_back(&settings, false, &notification("ApiErrorSpike", None)),
+            None
+        );
+
+        settings.notifications_enabled = false;
+        assert_eq!(
+            held_back(&settings, true, &stop),
+            Some(DispatchOutcome::Disabled)
+        );
+    }
+
+    #[test]
+    fn test_project_rules() {
+        let root = std::env::temp_dir()
+            .join(format!("claudit-rules-{}", uuid::Uuid::new_v4()))
+            .to_string_lossy()
+            .to_string();
+        let muted = format!("{}{}muted", root, std::path::MAIN_SEPARATOR);
+        let costly = format!("{}{}costly", root, std::path::MAIN_SEPARATOR);
+        metadata::set_project_notifications(
+            &muted,
+            metadata::ProjectNotificationSettings {
+                muted: true,
+                min_session_cost: None,
+            },
+        )
+        .unwrap();
+        metadata::set_project_notifications(
+            &costly,
+            metadata::ProjectNotificationSettings {
+                muted: false,
+                min_session_cost: Some(5.0),
+            },
+        )
+        .unwrap();
+
+        let settings = AppSettings::default();
+        let in_muted = format!("{}{}src", muted, std::path::MAIN_SEPARATOR);
+        assert_eq!(
+            held_back(&settings, false, &notification("Stop", Some(&in_muted))),
+            Some(DispatchOutcome::Muted)
+        );
+        // A sibling that only shares the prefix isn't covered
+        assert_eq!(
+            held_back(
+                &settings,
+                false,
+                &notification("Stop", Some(&format!("{}-other", muted)))
+            ),
+            None
+        );
+        // The session's cost can't be found, so the threshold lets it through
+        assert_eq!(
+            held_back(&settings, false, &notification("Stop", Some(&costly))),
+            None
+        );
+    }
+
+    #[test]
+    fn test_digest_notification() {
+        let item = |event: &str, project: &str, session: &str, cost: f64| DigestItem {
+            event: event.to_string(),
+            project: Some(project.to_string()),
+            session_id: Some(session.to_string()),
+            cost: Some(cost),
+        };
+        let items = vec![
+            item("Stop", "/a", "s1", 1.0),
+            item("Stop", "/a", "s1", 2.0),
+            item("SubagentStop", "/b", "s2", 0.5),
+            item("PostToolUse", "/b", "s3", 0.0),
+        ];
+        let digest = digest_notification(&items, 30);
+        assert_eq!(digest.event, "Digest");
+        assert_eq!(digest.title, "Claude Code · last 30 minutes");
+        assert_eq!(
+            digest.body,
+            format!(
+                "3 sessions finished · 1 tool alert · 2 projects · {} total",
+                format::cost(2.5)
+            )
+        );
+    }
+
+    #[test]
+    fn test_mute() {
+        let now = Local::now();
+        assert_eq!(MuteDuration::OneHour.end(now), now + Duration::hours(1));
+        assert_eq!(MuteDuration::FourHours.end(now), now + Duration::hours(4));
+        let tomorrow = MuteDuration::UntilTomorrow.end(now);
+        assert_eq!(tomorrow.date_naive(), now.date_naive() + Duration::days(1));
+        assert_eq!(
+            tomorrow.time(),
+            NaiveTime::from_hms_opt(TOMORROW_HOUR, 0, 0).unwrap()
+        );
+
+        assert_eq!(mute_remaining(now - Duration::minutes(5)), "0m");
+        assert_eq!(mute_remaining(now + Duration::seconds(30)), "1m");
+        assert_eq!(
+            mute_remaining(now + Duration::hours(2) + Duration::seconds(5)),
+            "2h 1m"
+        );
+        assert_eq!(
+            mute_remaining(now + Duration::hours(3) - Duration::seconds(1)),
+            "3h"
+        );
+    }
+}
@@ -0,0 +1,103 @@
+//! Path mappings for devcontainers and remote development
+//!
+//! When Claude Code runs in a container or over SSH, the paths in its logs
+//! (`/workspaces/app`) don't exist here, and its session folders are named
+//! after them. `AppSettings::path_mappings` translates those prefixes to the
+//! matching host folders, so sessions and costs attach to the local repos.
+
+use super::paths;
+use super::settings::SettingsService;
+use crate::types::PathMapping;
+use std::path::Path;
+
+/// The configured mappings
+pub fn current() -> Vec<PathMapping> {
+    SettingsService::shared().get().path_mappings
+}
+
+fn trimmed(path: &str) -> &str {
+    match path.trim_end_matches('/') {
+        "" => "/",
+        path => path,
+    }
+}
+
+/// What follows `prefix` in `path` (with its leading slash), if `path` is in it
+fn strip_prefix<'a>(path: &'a str, prefix: &str) -> Option<&'a str> {
+    let prefix = trimmed(prefix);
+    if prefix == "/" {
+        return path.starts_with('/').then_some(path);
+    }
+    let rest = path.strip_prefix(prefix)?;
+    (rest.is_empty() || rest.starts_with('/')).then_some(rest)
+}
+
+/// The host path of a container path, using the longest matching prefix
+pub fn map_path(mappings: &[PathMapping], path: &str) -> Option<String> {
+    mappings
+        .iter()
+        .filter_map(|m| strip_prefix(path, &m.from).map(|rest| (m, rest)))
+        .max_by_key(|(m, _)| trimmed(&m.from).len())
+        .map(|(m, rest)| format!("{}{}", trimmed(&m.to).trim_end_matches('/'), rest))
+}
+
+/// `path` on the host (unchanged when no mapping applies)
+pub fn to_host(mappings: &[PathMapping], path: String) -> String {
+    map_path(mappings, &path).unwrap_or(path)
+}
+
+/// The host project of a session folder named after a container path, e.g.
+/// `-workspaces-my-app` -> `/Users/me/code/my-app`. Folder names lose the
+/// difference between `/` and `-`, so existing host folders are preferred.
+pub fn map_folder(mappings: &[PathMapping], folder: &str) -> Option<String> {
+    let (mapping, rest) = mappings
+        .iter()
+        .filter_map(|m| {
+            let encoded = paths::encode_path_to_folder(trimmed(&m.from));
+            let rest = folder.strip_prefix(encoded.as_str())?;
+            (rest.is_empty() || rest.starts_with('-')).then_some((m, rest))
+        })
+        .max_by_key(|(m, _)| trimmed(&m.from).len())?;
+
+    let to = trimmed(&mapping.to).trim_end_matches('/');
+    let Some(rest) = rest.strip_prefix('-') else {
+        return Some(to.to_string());
+    };
+    let candidates = [
+        format!("{}/{}", to, rest),
+        format!("{}/{}", to, rest.replace('-', "/")),
+    ];
+    candidates
+        .iter()
+        .find(|c| Path::new(c).is_dir())
+        .or(candidates.first())
+        .cloned()
+}
+
+/// Container paths a host project may have been used under (for finding its
+/// session folders)
+pub fn container_paths(mappings: &[PathMapping], host_path: &str) -> Vec<String> {
+    mappings
+        .iter()
+        .filter_map(|m| {
+            strip_prefix(host_path, &m.to)
+                .map(|rest| format!("{}{}", trimmed(&m.from).trim_end_matches('/'), rest))
+        })
+        .collect()
+}
+
+/// Check the mappings before the settings are saved
+pub fn validate(mappings: &[PathMapping]) -> Result<(), String> {
+    for m in mappings {
+        if !m.from.starts_with('/') || !m.to.starts_with('/') {
+            return Err(format!(
+                "Path mappings need absolute paths: {} -> {}",
+                m.from, m.to
+            ));
+        }
+        if trimmed(&m.from) == "/" {
+            return Err("Map a folder, not the whole filesystem".to_string());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,135 @@
+//! Claude data location
+//!
+//! Every path into Claude Code's data goes through `PathProvider`. The location is
+//! the active profile from settings if one is selected, otherwise `CLAUDE_CONFIG_DIR`,
+//! otherwise ~/.claude (with ~/.claude.json next to it, as Claude Code does).
+//! Demo mode overrides all of them with generated sample data, and the benchmark
+//! harness with its own generated directory.
+
+use super::demo;
+use super::settings::SettingsService;
+use crate::types::AppSettings;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Where the resolved location came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PathSource {
+    Profile,
+    Env,
+    Default,
+    Demo,
+    /// Fixed for the whole process by a dev tool (`claudit-bench`)
+    Override,
+}
+
+/// Directory set with `set_override`
+static OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Resolved Claude Code data paths
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathProvider {
+    /// Name of the active profile (None for the env/default location)
+    pub profile: Option<String>,
+    pub source: PathSource,
+    config_dir: PathBuf,
+    claude_json: PathBuf,
+}
+
+impl PathProvider {
+    /// Paths for a Claude config directory other than the default
+    fn custom(config_dir: PathBuf, profile: Option<String>, source: PathSource) -> Self {
+        // With a custom config dir Claude Code keeps .claude.json inside it
+        let claude_json = config_dir.join(".claude.json");
+        Self {
+            profile,
+            source,
+            config_dir,
+            claude_json,
+        }
+    }
+
+    /// Resolve paths for the given settings
+    pub fn resolve(settings: &AppSettings) -> Self {
+        if let Some(dir) = OVERRIDE.get() {
+            return Self::custom(dir.clone(), None, PathSource::Override);
+        }
+        if settings.demo_mode || demo::is_forced() {
+            return Self::custom(demo::data_dir(), None, PathSource::Demo);
+        }
+
+        let active = settings.active_profile.as_ref().and_then(|name| {
+            settings
+                .claude_profiles
+                .iter()
+                .find(|profile| &profile.name == name)
+        });
+        if let Some(profile) = active {
+            return Self::custom(
+                expand_tilde(&profile.config_dir),
+                Some(profile.name.clone()),
+                PathSource::Profile,
+            );
+        }
+
+        if let Some(dir) = std::env::var_os("CLAUDE_CONFIG_DIR").filter(|d| !d.is_empty()) {
+            return Self::custom(expand_tilde(&dir.to_string_lossy()), None, PathSource::Env);
+        }
+
+        let home = dirs::home_dir().unwrap_or_default();
+        Self {
+            profile: None,
+            source: PathSource::Default,
+            config_dir: home.join(".claude"),
+            claude_json: home.join(".claude.json"),
+        }
+    }
+
+    /// Claude config directory (~/.claude by default)
+    pub fn config_dir(&self) -> &Path {
+        &self.config_dir
+    }
+
+    /// Global Claude config file (~/.claude.json by default)
+    pub fn claude_json(&self) -> &Path {
+        &self.claude_json
+    }
+
+    /// Session logs, one folder per project
+    pub fn projects_dir(&self) -> PathBuf {
+        self.config_dir.join("projects")
+    }
+
+    /// User-level Claude Code settings (hooks, permissions)
+    pub fn settings_json(&self) -> PathBuf {
+        self.config_dir.join("settings.json")
+    }
+}
+
+/// Paths for the currently selected location
+pub fn current() -> PathProvider {
+    PathProvider::resolve(&SettingsService::shared().get())
+}
+
+/// Read Claude data from `dir` for the rest of the process, whatever the
+/// settings say. Only the first call has an effect.
+pub fn set_override(dir: PathBuf) {
+    let _ = OVERRIDE.set(dir);
+}
+
+/// Name of a project's folder under projects/, e.g. "/Users/foo/project" ->
+/// "-Users-foo-project". Claude Code just replaces "/" with "-", so hyphens in
+/// paths are ambiguous.
+pub fn encode_path_to_folder(path: &str) -> String {
+    path.replace('/', "-")
+}
+
+fn expand_tilde(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ if path == "~" => dirs::home_dir().unwrap_or_default(),
+        _ => PathBuf::from(path),
+    }
+}
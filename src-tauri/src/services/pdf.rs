@@ -0,0 +1,81 @@
+//! PDF printing
+//!
+//! Prints an HTML page to PDF with a Chromium-based browser in headless mode
+//! (Chrome, Edge, Chromium or Brave), which is already on most machines and
+//! lays out the page exactly like the HTML file does. Used by the PDF session
+//! export.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Browser executables tried in order; bundled apps have a limited PATH, so
+/// the usual install locations come first
+fn browser_candidates() -> Vec<PathBuf> {
+    let mut candidates: Vec<PathBuf> = Vec::new();
+    if cfg!(target_os = "macos") {
+        for app in [
+            "Google Chrome.app/Contents/MacOS/Google Chrome",
+            "Microsoft Edge.app/Contents/MacOS/Microsoft Edge",
+            "Chromium.app/Contents/MacOS/Chromium",
+            "Brave Browser.app/Contents/MacOS/Brave Browser",
+        ] {
+            candidates.push(Path::new("/Applications").join(app));
+            if let Some(home) = dirs::home_dir() {
+                candidates.push(home.join("Applications").join(app));
+            }
+        }
+    } else if cfg!(target_os = "windows") {
+        for var in ["ProgramFiles(x86)", "ProgramFiles", "LOCALAPPDATA"] {
+            let Some(root) = std::env::var_os(var).map(PathBuf::from) else {
+                continue;
+            };
+            candidates.push(root.join("Microsoft/Edge/Application/msedge.exe"));
+            candidates.push(root.join("Google/Chrome/Application/chrome.exe"));
+        }
+    } else {
+        for name in [
+            "google-chrome",
+            "google-chrome-stable",
+            "chromium",
+            "chromium-browser",
+            "microsoft-edge",
+            "brave-browser",
+        ] {
+            for dir in ["/usr/bin", "/usr/local/bin", "/snap/bin"] {
+                candidates.push(Path::new(dir).join(name));
+            }
+        }
+    }
+    candidates
+}
+
+fn find_browser() -> Result<PathBuf, String> {
+    browser_candidates()
+        .into_iter()
+        .find(|path| path.is_file())
+        .ok_or_else(|| {
+            "PDF export needs Google Chrome, Microsoft Edge, Chromium or Brave installed"
+                .to_string()
+        })
+}
+
+/// Print the HTML page at `html` to a PDF at `output`
+pub fn print_to_pdf(html: &Path, output: &Path) -> Result<(), String> {
+    let browser = find_browser()?;
+    let run = Command::new(&browser)
+        .arg("--headless")
+        .arg("--disable-gpu")
+        .arg("--no-pdf-header-footer")
+        .arg("--print-to-pdf-no-header")
+        .arg(format!("--print-to-pdf={}", output.display()))
+        .arg(html)
+        .output()
+        .map_err(|e| format!("Failed to run {}: {}", browser.display(), e))?;
+    if !run.status.success() {
+        return Err(format!(
+            "PDF export failed: {}",
+            String::from_utf8_lossy(&run.stderr).trim()
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,96 @@
+//! Tray popover window
+//!
+//! A small chrome-less window anchored to the tray icon, shown on a left click
+//! instead of the native menu when `AppSettings::tray_popover` is on. It shows
+//! a stats card, recent sessions and quick actions, none of which fit in a
+//! native menu. The frontend renders the popover view when its window label is
+//! `popover`; the window hides again when it loses focus.
+
+use tauri::{
+    AppHandle, Manager, PhysicalPosition, Rect, Runtime, WebviewUrl, WebviewWindow,
+    WebviewWindowBuilder,
+};
+
+/// Label of the popover window
+pub const POPOVER_WINDOW: &str = "popover";
+
+/// Logical size of the popover
+const WIDTH: f64 = 340.0;
+const HEIGHT: f64 = 460.0;
+
+/// Gap between the tray icon and the popover, in logical pixels
+const GAP: f64 = 6.0;
+
+fn build<R: Runtime>(app: &AppHandle<R>) -> Result<WebviewWindow<R>, String> {
+    WebviewWindowBuilder::new(app, POPOVER_WINDOW, WebviewUrl::App("index.html".into()))
+        .title("Claudit")
+        .inner_size(WIDTH, HEIGHT)
+        .resizable(false)
+        .decorations(false)
+        .transparent(true)
+        .shadow(true)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .visible(false)
+        .build()
+        .map_err(|e| format!("Failed to open tray popover: {}", e))
+}
+
+/// Top-left corner that centers the popover under the tray icon, or above it
+/// when the icon sits at the bottom of the screen (Windows taskbar), kept
+/// inside the monitor's work area
+fn anchor_position<R: Runtime>(
+    window: &WebviewWindow<R>,
+    rect: Rect,
+) -> Option<PhysicalPosition<f64>> {
+    let scale = window.scale_factor().ok()?;
+    let icon_position = rect.position.to_physical::<f64>(scale);
+    let icon_size = rect.size.to_physical::<f64>(scale);
+    let monitor = window
+        .monitor_from_point(icon_position.x, icon_position.y)
+        .ok()
+        .flatten()?;
+    let scale = monitor.scale_factor();
+    let (width, height, gap) = (WIDTH * scale, HEIGHT * scale, GAP * scale);
+
+    let area = monitor.work_area();
+    let left = area.position.x as f64;
+    let top = area.position.y as f64;
+    let right = left + area.size.width as f64;
+    let bottom = top + area.size.height as f64;
+
+    let x = (icon_position.x + icon_size.width / 2.0 - width / 2.0)
+        .min(right - width)
+        .max(left);
+    let y = if icon_position.y > top + (bottom - top) / 2.0 {
+        icon_position.y - height - gap
+    } else {
+        icon_position.y + icon_size.height + gap
+    };
+    Some(PhysicalPosition::new(x, y.min(bottom - height).max(top)))
+}
+
+/// Show the popover under the tray icon at `rect`, or hide it if it is showing
+pub fn toggle<R: Runtime>(app: &AppHandle<R>, rect: Option<Rect>) -> Result<(), String> {
+    let window = match app.get_webview_window(POPOVER_WINDOW) {
+        Some(window) => window,
+        None => build(app)?,
+    };
+    if window.is_visible().unwrap_or(false) {
+        return window.hide().map_err(|e| e.to_string());
+    }
+
+    if let Some(position) = rect.and_then(|rect| anchor_position(&window, rect)) {
+        window.set_position(position).map_err(|e| e.to_string())?;
+    }
+    window.show().map_err(|e| e.to_string())?;
+    window.set_focus().map_err(|e| e.to_string())
+}
+
+/// Hide the popover if it is showing
+pub fn hide<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window(POPOVER_WINDOW) {
+        window.hide().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
@@ -4,7 +4,8 @@
 //! Cache write prices are for 5-minute TTL cache.
 //! Cache read prices are for cache hits and refreshes.
 
-use crate::types::TokenCosts;
+use crate::types::{CostBreakdown, PricingTier, TokenCosts};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
 /// Model pricing info for display in UI
@@ -15,138 +16,249 @@ pub struct ModelPricing {
     pub output: f64,
     pub cache_read: f64,
     pub cache_write: f64,
+    /// First day (UTC, YYYY-MM-DD) this price applied; None = since the model's release
+    pub effective_from: Option<String>,
+    /// Day the price stopped applying (exclusive); None = current price
+    pub effective_until: Option<String>,
+    /// Rates for prompts above 200k tokens, on models with a 1M context window
+    pub long_context: Option<TokenCosts>,
 }
 
-/// Get all model pricing for display
-pub fn get_all_pricing() -> Vec<ModelPricing> {
-    vec![
-        ModelPricing {
-            model_name: "Claude Opus 4.5".to_string(),
-            input: 5.0,
-            output: 25.0,
-            cache_read: 0.50,
-            cache_write: 6.25,
-        },
-        ModelPricing {
-            model_name: "Claude Opus 4 / 4.1 / 3".to_string(),
-            input: 15.0,
-            output: 75.0,
-            cache_read: 1.50,
-            cache_write: 18.75,
-        },
-        ModelPricing {
-            model_name: "Claude Sonnet 4 / 4.5 / 3.7".to_string(),
-            input: 3.0,
-            output: 15.0,
-            cache_read: 0.30,
-            cache_write: 3.75,
-        },
-        ModelPricing {
-            model_name: "Claude Haiku 4.5".to_string(),
-            input: 1.0,
-            output: 5.0,
-            cache_read: 0.10,
-            cache_write: 1.25,
-        },
-        ModelPricing {
-            model_name: "Claude Haiku 3.5".to_string(),
-            input: 0.80,
-            output: 4.0,
-            cache_read: 0.08,
-            cache_write: 1.0,
-        },
-        ModelPricing {
-            model_name: "Claude Haiku 3".to_string(),
-            input: 0.25,
-            output: 1.25,
-            cache_read: 0.03,
-            cache_write: 0.30,
-        },
-    ]
+/// Prompts larger than this bill at the long-context rates (on models that have them)
+pub const LONG_CONTEXT_THRESHOLD: u64 = 200_000;
+
+/// A price for a group of models over a period of time
+struct PriceRule {
+    name: &'static str,
+    /// Substrings of the model ID, any of which selects this rule; empty matches every model
+    patterns: &'static [&'static str],
+    effective_from: Option<&'static str>,
+    effective_until: Option<&'static str>,
+    costs: TokenCosts,
+    /// Rates for prompts above `LONG_CONTEXT_THRESHOLD` (1M-context models)
+    long_context: Option<TokenCosts>,
 }
 
-/// Get token costs for a specific model
+const fn costs(input: f64, output: f64, cache_read: f64, cache_write: f64) -> TokenCosts {
+    TokenCosts {
+        input,
+        output,
+        cache_read,
+        cache_write,
+    }
+}
+
+/// Pricing rules, most specific model first. Rules for the same models are listed
+/// newest first; a model uses the first rule whose period contains the request time.
 ///
 /// Pricing source: https://docs.anthropic.com/en/docs/about-claude/models
-pub fn get_model_costs(model: &str) -> TokenCosts {
-    let model_lower = model.to_lowercase();
-
+const PRICE_RULES: &[PriceRule] = &[
     // Opus 4.5 (newest, most capable)
-    if model_lower.contains("opus-4-5") || model_lower.contains("opus-4.5") {
-        return TokenCosts {
-            input: 5.0,
-            output: 25.0,
-            cache_read: 0.50,
-            cache_write: 6.25,
-        };
-    }
-
+    PriceRule {
+        name: "Claude Opus 4.5",
+        patterns: &["opus-4-5", "opus-4.5"],
+        effective_from: None,
+        effective_until: None,
+        costs: costs(5.0, 25.0, 0.50, 6.25),
+        long_context: None,
+    },
     // Opus 4.1 / Opus 4 / Opus 3 (all use same pricing)
-    if model_lower.contains("opus") {
-        return TokenCosts {
-            input: 15.0,
-            output: 75.0,
-            cache_read: 1.50,
-            cache_write: 18.75,
-        };
-    }
-
+    PriceRule {
+        name: "Claude Opus 4 / 4.1 / 3",
+        patterns: &["opus"],
+        effective_from: None,
+        effective_until: None,
+        costs: costs(15.0, 75.0, 1.50, 18.75),
+        long_context: None,
+    },
     // Haiku 4.5
-    if model_lower.contains("haiku-4-5") || model_lower.contains("haiku-4.5") {
-        return TokenCosts {
-            input: 1.0,
-            output: 5.0,
-            cache_read: 0.10,
-            cache_write: 1.25,
-        };
+    PriceRule {
+        name: "Claude Haiku 4.5",
+        patterns: &["haiku-4-5", "haiku-4.5"],
+        effective_from: None,
+        effective_until: None,
+        costs: costs(1.0, 5.0, 0.10, 1.25),
+        long_context: None,
+    },
+    // Haiku 3.5 - price cut in December 2024
+    PriceRule {
+        name: "Claude Haiku 3.5",
+        patterns: &["haiku-3-5", "haiku-3.5", "3-5-haiku"],
+        effective_from: Some("2024-12-03"),
+        effective_until: None,
+        costs: costs(0.80, 4.0, 0.08, 1.0),
+        long_context: None,
+    },
+    PriceRule {
+        name: "Claude Haiku 3.5",
+        patterns: &["haiku-3-5", "haiku-3.5", "3-5-haiku"],
+        effective_from: None,
+        effective_until: Some("2024-12-03"),
+        costs: costs(1.0, 5.0, 0.10, 1.25),
+        long_context: None,
+    },
+    // Haiku 3 (original)
+    PriceRule {
+        name: "Claude Haiku 3",
+        patterns: &["haiku"],
+        effective_from: None,
+        effective_until: None,
+        costs: costs(0.25, 1.25, 0.03, 0.30),
+        long_context: None,
+    },
+    // Default to Sonnet pricing (Sonnet 4.5, 4, 3.7, 3.5). Only Sonnet 4 / 4.5
+    // accept prompts above 200k tokens, which bill at the long-context rates.
+    PriceRule {
+        name: "Claude Sonnet 4 / 4.5 / 3.7",
+        patterns: &[],
+        effective_from: None,
+        effective_until: None,
+        costs: costs(3.0, 15.0, 0.30, 3.75),
+        long_context: Some(costs(6.0, 22.50, 0.60, 7.50)),
+    },
+];
+
+impl PriceRule {
+    fn matches(&self, model: &str) -> bool {
+        self.patterns.is_empty() || self.patterns.iter().any(|p| model.contains(p))
     }
 
-    // Haiku 3.5
-    if model_lower.contains("haiku-3-5") || model_lower.contains("haiku-3.5") {
-        return TokenCosts {
-            input: 0.80,
-            output: 4.0,
-            cache_read: 0.08,
-            cache_write: 1.0,
+    /// Whether the rule applies at a point in time (None = now)
+    fn is_effective(&self, at: Option<DateTime<Utc>>) -> bool {
+        let Some(at) = at else {
+            return self.effective_until.is_none();
         };
+        let date = at.date_naive();
+        let parse = |d: &str| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok();
+        self.effective_from
+            .and_then(parse)
+            .is_none_or(|from| date >= from)
+            && self
+                .effective_until
+                .and_then(parse)
+                .is_none_or(|until| date < until)
     }
 
-    // Haiku 3 (original)
-    if model_lower.contains("haiku") {
-        return TokenCosts {
-            input: 0.25,
-            output: 1.25,
-            cache_read: 0.03,
-            cache_write: 0.30,
-        };
+    fn to_pricing(&self) -> ModelPricing {
+        ModelPricing {
+            model_name: self.name.to_string(),
+            input: self.costs.input,
+            output: self.costs.output,
+            cache_read: self.costs.cache_read,
+            cache_write: self.costs.cache_write,
+            effective_from: self.effective_from.map(str::to_string),
+            effective_until: self.effective_until.map(str::to_string),
+            long_context: self.long_context.clone(),
+        }
     }
+}
 
-    // Default to Sonnet pricing (Sonnet 4.5, 4, 3.7, 3.5)
-    TokenCosts {
-        input: 3.0,
-        output: 15.0,
-        cache_read: 0.30,
-        cache_write: 3.75,
+/// Get current model pricing for display
+pub fn get_all_pricing() -> Vec<ModelPricing> {
+    PRICE_RULES
+        .iter()
+        .filter(|rule| rule.effective_until.is_none())
+        .map(PriceRule::to_pricing)
+        .collect()
+}
+
+/// Get every price period, including superseded prices
+pub fn get_pricing_history() -> Vec<ModelPricing> {
+    PRICE_RULES.iter().map(PriceRule::to_pricing).collect()
+}
+
+/// Current pricing for one model
+pub fn get_pricing_for_model(model: &str) -> ModelPricing {
+    find_rule(model, None).to_pricing()
+}
+
+/// The rule that prices a model at a point in time (None = now)
+fn find_rule(model: &str, at: Option<DateTime<Utc>>) -> &'static PriceRule {
+    let model_lower = model.to_lowercase();
+    let mut matching = PRICE_RULES.iter().filter(|rule| rule.matches(&model_lower));
+    matching
+        .clone()
+        .find(|rule| rule.is_effective(at))
+        .or_else(|| matching.next())
+        .unwrap_or(&PRICE_RULES[PRICE_RULES.len() - 1])
+}
+
+/// Get token costs for a model at a point in time (None = current prices)
+pub fn get_model_costs_at(model: &str, at: Option<DateTime<Utc>>) -> TokenCosts {
+    find_rule(model, at).costs.clone()
+}
+
+/// Get current token costs for a specific model
+pub fn get_model_costs(model: &str) -> TokenCosts {
+    get_model_costs_at(model, None)
+}
+
+/// Price multiplier for a service tier: the Batch API bills at half price,
+/// priority and standard requests at the regular rates
+pub fn service_tier_multiplier(service_tier: Option<&str>) -> f64 {
+    match service_tier {
+        Some("batch") => 0.5,
+        _ => 1.0,
     }
 }
 
-/// Calculate the total cost for a usage entry
-pub fn calculate_cost(
+/// Price a single response: the rates valid at its timestamp, at the long-context
+/// tier when its prompt exceeds `LONG_CONTEXT_THRESHOLD`, with the service tier's discount
+pub fn cost_breakdown(
     model: &str,
+    timestamp: Option<DateTime<Utc>>,
+    service_tier: Option<&str>,
     input_tokens: u64,
     output_tokens: u64,
     cache_creation_tokens: u64,
     cache_read_tokens: u64,
-) -> f64 {
-    let costs = get_model_costs(model);
+) -> CostBreakdown {
+    let rule = find_rule(model, timestamp);
+    let context_tokens = input_tokens + cache_creation_tokens + cache_read_tokens;
+    let (tier, rates) = match &rule.long_context {
+        Some(long_context) if context_tokens > LONG_CONTEXT_THRESHOLD => {
+            (PricingTier::LongContext, long_context.clone())
+        }
+        _ => (PricingTier::Standard, rule.costs.clone()),
+    };
     let per_million = 1_000_000.0;
 
-    let input_cost = (input_tokens as f64 / per_million) * costs.input;
-    let output_cost = (output_tokens as f64 / per_million) * costs.output;
-    let cache_read_cost = (cache_read_tokens as f64 / per_million) * costs.cache_read;
-    let cache_write_cost = (cache_creation_tokens as f64 / per_million) * costs.cache_write;
+    let input_cost = (input_tokens as f64 / per_million) * rates.input;
+    let output_cost = (output_tokens as f64 / per_million) * rates.output;
+    let cache_read_cost = (cache_read_tokens as f64 / per_million) * rates.cache_read;
+    let cache_write_cost = (cache_creation_tokens as f64 / per_million) * rates.cache_write;
+    let multiplier = service_tier_multiplier(service_tier);
+
+    CostBreakdown {
+        cost: (input_cost + output_cost + cache_read_cost + cache_write_cost) * multiplier,
+        tier,
+        context_tokens,
+        rates,
+        service_tier: service_tier.unwrap_or("standard").to_string(),
+        service_tier_multiplier: multiplier,
+    }
+}
 
-    input_cost + output_cost + cache_read_cost + cache_write_cost
+/// Calculate the total cost for a usage entry (see `cost_breakdown`)
+pub fn calculate_cost(
+    model: &str,
+    timestamp: Option<DateTime<Utc>>,
+    service_tier: Option<&str>,
+    input_tokens: u64,
+    output_tokens: u64,
+    cache_creation_tokens: u64,
+    cache_read_tokens: u64,
+) -> f64 {
+    cost_breakdown(
+        model,
+        timestamp,
+        service_tier,
+        input_tokens,
+        output_tokens,
+        cache_creation_tokens,
+        cache_read_tokens,
+    )
+    .cost
 }
 
 #[cfg(test)]
@@ -184,7 +296,69 @@ mod tests {
     #[test]
     fn test_cost_calculation() {
         // 1M input tokens + 1M output tokens with sonnet = $3 + $15 = $18
-        let cost = calculate_cost("claude-sonnet-4-20250514", 1_000_000, 1_000_000, 0, 0);
+        let cost = calculate_cost(
+            "claude-sonnet-4-20250514",
+            None,
+            None,
+            1_000_000,
+            1_000_000,
+            0,
+            0,
+        );
         assert_eq!(cost, 18.0);
+
+        // Batch API requests are billed at half price
+        let cost = calculate_cost(
+            "claude-sonnet-4-20250514",
+            None,
+            Some("batch"),
+            1_000_000,
+            1_000_000,
+            0,
+            0,
+        );
+        assert_eq!(cost, 9.0);
+    }
+
+    #[test]
+    fn test_long_context_pricing() {
+        // 250k-token prompt on Sonnet 4 bills at $6 / $22.50
+        let breakdown = cost_breakdown(
+            "claude-sonnet-4-20250514",
+            None,
+            None,
+            250_000,
+            1_000_000,
+            0,
+            0,
+        );
+        assert_eq!(breakdown.tier, PricingTier::LongContext);
+        assert_eq!(breakdown.cost, 1.5 + 22.5);
+
+        let breakdown = cost_breakdown(
+            "claude-sonnet-4-20250514",
+            None,
+            None,
+            150_000,
+            0,
+            0,
+            50_000,
+        );
+        assert_eq!(breakdown.tier, PricingTier::Standard);
+    }
+
+    #[test]
+    fn test_historical_pricing() {
+        let before = "2024-11-20T12:00:00Z".parse::<DateTime<Utc>>().ok();
+        let after = "2025-01-10T12:00:00Z".parse::<DateTime<Utc>>().ok();
+        assert_eq!(
+            get_model_costs_at("claude-3-5-haiku-20241022", before).input,
+            1.0
+        );
+        assert_eq!(
+            get_model_costs_at("claude-3-5-haiku-20241022", after).input,
+            0.80
+        );
+        assert_eq!(get_model_costs("claude-3-5-haiku-20241022").input, 0.80);
     }
 }
@@ -0,0 +1,152 @@
+//! Project activity
+//!
+//! When each project was last used and how many sessions it has, from the
+//! modification times of its session files. The projects folder is scanned
+//! once, then kept current by the file watcher (`record`), so listing projects
+//! doesn't stat every file again.
+//!
+//! Folder names are lossy encodings of project paths (every `/`, `.` and so on
+//! becomes `-`), so each folder's project path is sniffed from the `cwd` field
+//! near the top of one of its session files, with the encoded name as fallback.
+
+use super::paths;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Lines read from the top of a session file when looking for `cwd`
+const SNIFF_LINES: usize = 20;
+
+/// Session files of one project folder
+#[derive(Default)]
+struct FolderActivity {
+    /// Project path from the session files, if one was found
+    project: Option<String>,
+    files: HashMap<PathBuf, SystemTime>,
+}
+
+/// Activity by folder name (None until the first scan)
+static ACTIVITY: Mutex<Option<HashMap<String, FolderActivity>>> = Mutex::new(None);
+
+/// Last use and session count of a project
+#[derive(Debug, Clone)]
+pub struct ProjectActivity {
+    pub last_used: Option<String>,
+    pub session_count: usize,
+}
+
+fn is_session_file(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == "jsonl")
+}
+
+/// `cwd` of the first line in the head of `path` that has one
+fn sniff_project(path: &Path) -> Option<String> {
+    let file = File::open(path).ok()?;
+    BufReader::new(file)
+        .lines()
+        .take(SNIFF_LINES)
+        .map_while(Result::ok)
+        .filter(|line| line.contains("\"cwd\""))
+        .find_map(|line| {
+            let entry: Value = serde_json::from_str(&line).ok()?;
+            entry.get("cwd")?.as_str().map(String::from)
+        })
+}
+
+fn scan_folder(folder: &Path) -> FolderActivity {
+    let mut activity = FolderActivity::default();
+    let Ok(entries) = fs::read_dir(folder) else {
+        return activity;
+    };
+    for path in entries.flatten().map(|e| e.path()) {
+        if !is_session_file(&path) {
+            continue;
+        }
+        if let Ok(modified) = path.metadata().and_then(|m| m.modified()) {
+            activity.files.insert(path, modified);
+        }
+    }
+    activity.project = activity.files.keys().find_map(|path| sniff_project(path));
+    activity
+}
+
+fn scan() -> HashMap<String, FolderActivity> {
+    let Ok(folders) = fs::read_dir(paths::current().projects_dir()) else {
+        return HashMap::new();
+    };
+    folders
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            (name, scan_folder(&e.path()))
+        })
+        .collect()
+}
+
+/// Activity of `project_path`, scanning the projects folder on first use
+pub fn get(project_path: &str) -> Option<ProjectActivity> {
+    let mut cache = ACTIVITY.lock().unwrap();
+    let folders = cache.get_or_insert_with(scan);
+
+    let encoded = paths::encode_path_to_folder(project_path);
+    let folder = folders
+        .values()
+        .find(|f| f.project.as_deref() == Some(project_path))
+        .or_else(|| folders.get(&encoded))?;
+
+    Some(ProjectActivity {
+        last_used: folder
+            .files
+            .values()
+            .max()
+            .map(|t| DateTime::<Utc>::from(*t).to_rfc3339()),
+        session_count: folder.files.len(),
+    })
+}
+
+/// Update the cache for a session file the watcher saw change
+pub fn record(path: &Path) {
+    let projects_dir = paths::current().projects_dir();
+    let Some(folder) = path
+        .parent()
+        .filter(|p| p.parent() == Some(projects_dir.as_path()))
+    else {
+        return;
+    };
+    if !is_session_file(path) {
+        return;
+    }
+    let mut cache = ACTIVITY.lock().unwrap();
+    // Not scanned yet: the first scan will see the change
+    let Some(folders) = cache.as_mut() else {
+        return;
+    };
+
+    let name = folder
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let activity = folders.entry(name).or_default();
+    match path.metadata().and_then(|m| m.modified()) {
+        Ok(modified) => {
+            activity.files.insert(path.to_path_buf(), modified);
+            if activity.project.is_none() {
+                activity.project = sniff_project(path);
+            }
+        }
+        Err(_) => {
+            activity.files.remove(path);
+        }
+    }
+}
+
+/// Forget the cache (the Claude data location changed)
+pub fn reset() {
+    *ACTIVITY.lock().unwrap() = None;
+}
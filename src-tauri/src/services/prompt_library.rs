@@ -0,0 +1,103 @@
+//! Starred-prompt library
+//!
+//! User prompts starred from any session, kept in claudit's metadata so they
+//! can be searched and reused. A prompt can also be turned into a slash
+//! command file, for the user or for one project.
+
+use super::audit::{FileOperation, FilePlan};
+use super::metadata::{self, StarredPrompt};
+use super::paths;
+use std::path::PathBuf;
+
+/// Star a prompt; starring the same text again returns the existing entry
+pub fn star(
+    text: &str,
+    project: Option<String>,
+    session_id: Option<String>,
+) -> Result<StarredPrompt, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Nothing to star".to_string());
+    }
+    let mut data = metadata::load();
+    if let Some(existing) = data.prompts.iter().find(|p| p.text == text) {
+        return Ok(existing.clone());
+    }
+    let prompt = StarredPrompt {
+        id: uuid::Uuid::new_v4().to_string(),
+        text: text.to_string(),
+        project,
+        session_id,
+        starred_at: chrono::Utc::now().to_rfc3339(),
+    };
+    data.prompts.push(prompt.clone());
+    metadata::save(&data)?;
+    Ok(prompt)
+}
+
+/// Remove a prompt from the library
+pub fn unstar(id: &str) -> Result<(), String> {
+    let mut data = metadata::load();
+    data.prompts.retain(|p| p.id != id);
+    metadata::save(&data)
+}
+
+/// Starred prompts, newest first, optionally filtered by words that must all
+/// appear in the prompt or its project
+pub fn list(query: Option<&str>) -> Vec<StarredPrompt> {
+    let words: Vec<String> = query
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect();
+    let mut prompts: Vec<StarredPrompt> = metadata::load()
+        .prompts
+        .into_iter()
+        .filter(|p| {
+            let haystack =
+                format!("{} {}", p.text, p.project.as_deref().unwrap_or_default()).to_lowercase();
+            words.iter().all(|w| haystack.contains(w.as_str()))
+        })
+        .collect();
+    prompts.sort_by(|a, b| b.starred_at.cmp(&a.starred_at));
+    prompts
+}
+
+/// Save a starred prompt as `<name>.md` in the project's `.claude/commands`
+/// (or the user's, without a project). With `dry_run` nothing is written.
+pub fn to_slash_command(
+    id: &str,
+    name: &str,
+    project: Option<&str>,
+    dry_run: bool,
+) -> Result<Vec<FileOperation>, String> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(format!(
+            "Command names may only use letters, digits, - and _: {}",
+            name
+        ));
+    }
+    let prompt = metadata::load()
+        .prompts
+        .into_iter()
+        .find(|p| p.id == id)
+        .ok_or_else(|| format!("Starred prompt {} not found", id))?;
+
+    let dir = match project {
+        Some(project) => PathBuf::from(project).join(".claude").join("commands"),
+        None => paths::current().config_dir().join("commands"),
+    };
+    let path = dir.join(format!("{}.md", name));
+    if path.exists() {
+        return Err(format!("/{} already exists: {}", name, path.display()));
+    }
+
+    let mut plan = FilePlan::new(&format!("prompt_to_command: {}", name));
+    plan.create_dir(&dir, "commands folder");
+    plan.write(&path, format!("{}\n", prompt.text), "slash command");
+    plan.run(dry_run)
+}
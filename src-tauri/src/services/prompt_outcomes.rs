@@ -0,0 +1,219 @@
+//! Outcomes of library prompts
+//!
+//! Finds the sessions where a user prompt closely matches a prompt from the
+//! library (word overlap, so small edits to a template still count) and sums
+//! up what each of those sessions took and produced: tokens, cost, duration
+//! and file edits. Averages per prompt make two variants of a template
+//! comparable. Outcomes cover the whole session the prompt appeared in.
+
+use super::invocations::{timestamp, usage_cost, user_text};
+use super::metadata::StarredPrompt;
+use super::prompt_library;
+use super::usage::UsageReader;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Share of words two prompts must have in common to count as the same
+const MIN_SIMILARITY: f64 = 0.6;
+/// Tools whose calls count as edits
+const EDIT_TOOLS: &[&str] = &["Edit", "MultiEdit", "Write", "NotebookEdit"];
+
+/// A session that used a library prompt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptSessionOutcome {
+    pub session_id: String,
+    pub project: String,
+    pub started_at: Option<String>,
+    /// Word overlap with the library prompt (1.0 is identical)
+    pub similarity: f64,
+    pub tokens: u64,
+    pub cost: f64,
+    pub duration_ms: u64,
+    pub edits: u64,
+}
+
+/// A library prompt with the sessions that used it and their averages
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptOutcome {
+    pub prompt_id: String,
+    pub sessions: Vec<PromptSessionOutcome>,
+    pub avg_tokens: f64,
+    pub avg_cost: f64,
+    pub avg_duration_ms: f64,
+    pub avg_edits: f64,
+}
+
+/// What a session took and produced, and the prompts it was given
+#[derive(Default)]
+struct SessionScan {
+    prompts: Vec<HashSet<String>>,
+    tokens: u64,
+    cost: f64,
+    edits: u64,
+    first: Option<DateTime<Utc>>,
+    last: Option<DateTime<Utc>>,
+}
+
+fn words(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Jaccard similarity of two word sets
+fn similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+fn scan(file: &Path) -> SessionScan {
+    let mut session = SessionScan::default();
+    let Ok(handle) = File::open(file) else {
+        return session;
+    };
+    let mut seen_messages: HashSet<String> = HashSet::new();
+
+    for line in BufReader::new(handle).lines().map_while(Result::ok) {
+        let Ok(entry) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        let (Some(at), Some(message)) = (timestamp(&entry), entry.get("message")) else {
+            continue;
+        };
+        session.first = Some(session.first.map_or(at, |first| first.min(at)));
+        session.last = session.last.max(Some(at));
+
+        match entry.get("type").and_then(|t| t.as_str()) {
+            Some("user") => {
+                let flag = |key: &str| entry.get(key).and_then(|v| v.as_bool()) == Some(true);
+                if flag("isSidechain") || flag("isMeta") {
+                    continue;
+                }
+                if let Some(text) = user_text(message) {
+                    session.prompts.push(words(&text));
+                }
+            }
+            Some("assistant") => {
+                let blocks = message.get("content").and_then(|c| c.as_array());
+                session.edits += blocks
+                    .into_iter()
+                    .flatten()
+                    .filter(|b| b.get("type").and_then(|t| t.as_str()) == Some("tool_use"))
+                    .filter(|b| {
+                        let name = b.get("name").and_then(|n| n.as_str()).unwrap_or_default();
+                        EDIT_TOOLS.contains(&name)
+                    })
+                    .count() as u64;
+
+                // Streamed responses repeat their usage on every chunk
+                let id = message
+                    .get("id")
+                    .and_then(|i| i.as_str())
+                    .unwrap_or_default();
+                let Some(usage) = message.get("usage") else {
+                    continue;
+                };
+                if !id.is_empty() && !seen_messages.insert(id.to_string()) {
+                    continue;
+                }
+                let model = message
+                    .get("model")
+                    .and_then(|m| m.as_str())
+                    .unwrap_or_default();
+                session.cost += usage_cost(model, at, usage);
+                session.tokens += [
+                    "input_tokens",
+                    "output_tokens",
+                    "cache_creation_input_tokens",
+                    "cache_read_input_tokens",
+                ]
+                .iter()
+                .filter_map(|key| usage.get(key).and_then(|v| v.as_u64()))
+                .sum::<u64>();
+            }
+            _ => {}
+        }
+    }
+    session
+}
+
+fn average(sessions: &[PromptSessionOutcome], value: impl Fn(&PromptSessionOutcome) -> f64) -> f64 {
+    if sessions.is_empty() {
+        return 0.0;
+    }
+    sessions.iter().map(value).sum::<f64>() / sessions.len() as f64
+}
+
+/// Outcomes of every prompt in the library
+pub fn outcomes() -> Vec<PromptOutcome> {
+    let prompts: Vec<(StarredPrompt, HashSet<String>)> = prompt_library::list(None)
+        .into_iter()
+        .map(|p| {
+            let words = words(&p.text);
+            (p, words)
+        })
+        .filter(|(_, words)| !words.is_empty())
+        .collect();
+    if prompts.is_empty() {
+        return Vec::new();
+    }
+    let mut matched: Vec<Vec<PromptSessionOutcome>> = vec![Vec::new(); prompts.len()];
+
+    let reader = UsageReader::new();
+    for file in reader.find_jsonl_files() {
+        let session = scan(&file);
+        let session_id = file
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        for ((_, prompt_words), found) in prompts.iter().zip(matched.iter_mut()) {
+            let best = session
+                .prompts
+                .iter()
+                .map(|words| similarity(prompt_words, words))
+                .fold(0.0, f64::max);
+            if best < MIN_SIMILARITY {
+                continue;
+            }
+            let duration_ms = match (session.first, session.last) {
+                (Some(first), Some(last)) => (last - first).num_milliseconds().max(0) as u64,
+                _ => 0,
+            };
+            found.push(PromptSessionOutcome {
+                session_id: session_id.clone(),
+                project: reader.extract_project_path(&file),
+                started_at: session.first.map(|t| t.to_rfc3339()),
+                similarity: best,
+                tokens: session.tokens,
+                cost: session.cost,
+                duration_ms,
+                edits: session.edits,
+            });
+        }
+    }
+
+    prompts
+        .into_iter()
+        .zip(matched)
+        .map(|((prompt, _), mut sessions)| {
+            sessions.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+            PromptOutcome {
+                prompt_id: prompt.id,
+                avg_tokens: average(&sessions, |s| s.tokens as f64),
+                avg_cost: average(&sessions, |s| s.cost),
+                avg_duration_ms: average(&sessions, |s| s.duration_ms as f64),
+                avg_edits: average(&sessions, |s| s.edits as f64),
+                sessions,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,357 @@
+//! Quick search across claudit
+//!
+//! Backs the ⌘K command palette: one query is matched against project names,
+//! session titles, agents, commands and MCP servers, and each hit carries the
+//! jump action the frontend performs when it is picked. Sessions are matched on
+//! their summary or first prompt only, read from the head of each file, so the
+//! search stays fast enough to run on every keystroke.
+
+use super::config::{self, AgentInfo};
+use super::sessions::get_project_folder;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Results returned per kind, so one kind cannot crowd out the others
+const MAX_PER_KIND: usize = 8;
+/// Lines read from the start of a session file to find its title
+const SESSION_HEAD_LINES: usize = 50;
+
+/// What a search result points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchResultKind {
+    Project,
+    Session,
+    Agent,
+    Command,
+    McpServer,
+}
+
+/// Where the UI jumps to when a result is picked
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JumpAction {
+    Project {
+        project_path: String,
+    },
+    Session {
+        project_path: String,
+        session_id: String,
+    },
+    Agent {
+        path: String,
+        name: String,
+        is_directory: bool,
+    },
+    Command {
+        path: String,
+        name: String,
+        is_directory: bool,
+    },
+    /// `project_path` is None for servers configured globally
+    McpServer {
+        name: String,
+        project_path: Option<String>,
+    },
+}
+
+/// One quick search hit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuickSearchResult {
+    pub kind: SearchResultKind,
+    pub title: String,
+    pub subtitle: Option<String>,
+    /// Higher is a better match
+    pub score: u32,
+    pub action: JumpAction,
+}
+
+/// How well `text` matches an already lowercased `query`
+fn score(query: &str, text: &str) -> Option<u32> {
+    let text = text.to_lowercase();
+    if text == query {
+        Some(100)
+    } else if text.starts_with(query) {
+        Some(80)
+    } else if text
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| word.starts_with(query))
+    {
+        Some(60)
+    } else if text.contains(query) {
+        Some(40)
+    } else if text.len() <= 64 && is_subsequence(query, &text) {
+        // Fuzzy matching only for short names ("mcpfs" -> "mcp-filesystem")
+        Some(20)
+    } else {
+        None
+    }
+}
+
+fn is_subsequence(query: &str, text: &str) -> bool {
+    let mut chars = text.chars();
+    query.chars().all(|q| chars.any(|c| c == q))
+}
+
+fn project_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// Text of a user message: a plain string or the first text block
+fn message_text(message: &serde_json::Value) -> Option<String> {
+    let content = message.get("content")?;
+    if let Some(text) = content.as_str() {
+        return Some(text.to_string());
+    }
+    content.as_array()?.iter().find_map(|block| {
+        (block.get("type").and_then(|t| t.as_str()) == Some("text"))
+            .then(|| block.get("text").and_then(|t| t.as_str()).map(String::from))
+            .flatten()
+    })
+}
+
+/// Summary and first prompt from the start of a session file
+fn session_head(path: &Path) -> (Option<String>, Option<String>) {
+    let Ok(file) = File::open(path) else {
+        return (None, None);
+    };
+    let mut summary = None;
+    let mut first_prompt = None;
+
+    for line in BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .take(SESSION_HEAD_LINES)
+    {
+        let Ok(entry) = serde_json::from_str::<serde_json::Value>(&line) else {
+            continue;
+        };
+        match entry.get("type").and_then(|t| t.as_str()) {
+            Some("summary") if summary.is_none() => {
+                summary = entry
+                    .get("summary")
+                    .and_then(|s| s.as_str())
+                    .map(String::from);
+            }
+            Some("user") if first_prompt.is_none() => {
+                first_prompt = entry.get("message").and_then(message_text);
+            }
+            _ => {}
+        }
+        if summary.is_some() && first_prompt.is_some() {
+            break;
+        }
+    }
+    (summary, first_prompt)
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    let text = text.trim().replace('\n', " ");
+    if text.chars().count() > max_chars {
+        format!("{}...", text.chars().take(max_chars).collect::<String>())
+    } else {
+        text
+    }
+}
+
+fn search_projects(query: &str, projects: &[String]) -> Vec<QuickSearchResult> {
+    projects
+        .iter()
+        .filter_map(|path| {
+            let name = project_name(path);
+            // The name counts more than a match somewhere in the path
+            let score = score(query, &name).or_else(|| score(query, path).map(|s| s / 2))?;
+            Some(QuickSearchResult {
+                kind: SearchResultKind::Project,
+                title: name,
+                subtitle: Some(path.clone()),
+                score,
+                action: JumpAction::Project {
+                    project_path: path.clone(),
+                },
+            })
+        })
+        .collect()
+}
+
+fn search_sessions(query: &str, projects: &[String]) -> Vec<QuickSearchResult> {
+    let mut results = Vec::new();
+    for project in projects {
+        let Some(folder) = get_project_folder(project) else {
+            continue;
+        };
+        let Ok(entries) = fs::read_dir(&folder) else {
+            continue;
+        };
+        for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            if !path.is_file() || path.extension().is_none_or(|ext| ext != "jsonl") {
+                continue;
+            }
+            let Some(session_id) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+                continue;
+            };
+
+            let (summary, first_prompt) = session_head(&path);
+            let title = summary.or(first_prompt);
+            let matched = title
+                .as_deref()
+                .and_then(|title| score(query, title))
+                .or_else(|| session_id.starts_with(query).then_some(50));
+            let Some(score) = matched else {
+                continue;
+            };
+
+            results.push(QuickSearchResult {
+                kind: SearchResultKind::Session,
+                title: title
+                    .map(|t| truncate(&t, 80))
+                    .unwrap_or_else(|| session_id.clone()),
+                subtitle: Some(project_name(project)),
+                score,
+                action: JumpAction::Session {
+                    project_path: project.clone(),
+                    session_id,
+                },
+            });
+        }
+    }
+    results
+}
+
+fn search_items(
+    query: &str,
+    kind: SearchResultKind,
+    items: Vec<(AgentInfo, Option<String>)>,
+) -> Vec<QuickSearchResult> {
+    items
+        .into_iter()
+        .filter_map(|(item, project)| {
+            let score = score(query, &item.name).or_else(|| {
+                item.description
+                    .as_deref()
+                    .and_then(|d| score(query, d))
+                    .map(|s| s / 2)
+            })?;
+            let subtitle = match project {
+                Some(project) => Some(format!("{} (project)", project_name(&project))),
+                None => item.description.as_deref().map(|d| truncate(d, 80)),
+            };
+            let action = if kind == SearchResultKind::Agent {
+                JumpAction::Agent {
+                    path: item.path,
+                    name: item.name.clone(),
+                    is_directory: item.is_directory,
+                }
+            } else {
+                JumpAction::Command {
+                    path: item.path,
+                    name: item.name.clone(),
+                    is_directory: item.is_directory,
+                }
+            };
+            Some(QuickSearchResult {
+                kind,
+                title: item.name,
+                subtitle,
+                score,
+                action,
+            })
+        })
+        .collect()
+}
+
+fn search_mcp_servers(query: &str) -> Vec<QuickSearchResult> {
+    config::get_mcp_servers()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|server| {
+            let score = score(query, &server.name)?;
+            let subtitle = match &server.project_path {
+                Some(project) => format!("{} · {}", server.server_type, project_name(project)),
+                None => format!("{} · global", server.server_type),
+            };
+            Some(QuickSearchResult {
+                kind: SearchResultKind::McpServer,
+                title: server.name.clone(),
+                subtitle: Some(subtitle),
+                score,
+                action: JumpAction::McpServer {
+                    name: server.name,
+                    project_path: server.project_path,
+                },
+            })
+        })
+        .collect()
+}
+
+/// Search projects, sessions, agents, commands and MCP servers, best matches first
+pub fn quick_search(query: &str, limit: usize) -> Vec<QuickSearchResult> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let projects = config::registered_project_paths();
+
+    let mut agents: Vec<(AgentInfo, Option<String>)> = config::list_agents()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|a| (a, None))
+        .collect();
+    let mut commands: Vec<(AgentInfo, Option<String>)> = config::list_commands()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|c| {
+            let item = AgentInfo {
+                name: c.name,
+                path: c.path,
+                description: c.description,
+                is_directory: c.is_directory,
+            };
+            (item, None)
+        })
+        .collect();
+    for project in &projects {
+        for agent in config::get_project_agents(project).unwrap_or_default() {
+            agents.push((agent, Some(project.clone())));
+        }
+        for command in config::get_project_commands(project).unwrap_or_default() {
+            let item = AgentInfo {
+                name: command.name,
+                path: command.path,
+                description: command.description,
+                is_directory: command.is_directory,
+            };
+            commands.push((item, Some(project.clone())));
+        }
+    }
+
+    let groups = [
+        search_projects(&query, &projects),
+        // Sessions need at least two characters to be worth scanning
+        if query.len() >= 2 {
+            search_sessions(&query, &projects)
+        } else {
+            Vec::new()
+        },
+        search_items(&query, SearchResultKind::Agent, agents),
+        search_items(&query, SearchResultKind::Command, commands),
+        search_mcp_servers(&query),
+    ];
+
+    let mut results: Vec<QuickSearchResult> = groups
+        .into_iter()
+        .flat_map(|mut group| {
+            group.sort_by_key(|r| std::cmp::Reverse(r.score));
+            group.truncate(MAX_PER_KIND);
+            group
+        })
+        .collect();
+    results.sort_by(|a, b| b.score.cmp(&a.score).then(a.kind.cmp(&b.kind)));
+    results.truncate(limit);
+    results
+}
@@ -0,0 +1,461 @@
+//! Cost reconciliation against the Anthropic Console
+//!
+//! Imports the usage/cost CSV export from the Console and compares the billed
+//! amounts with claudit's computed costs per UTC day and model. Imported rows
+//! are kept in `console-costs.json` in claudit's config directory.
+
+use super::pricing::calculate_cost;
+use super::settings::app_config_dir;
+use super::UsageReader;
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Differences below this are never flagged (USD)
+const MIN_GAP: f64 = 0.05;
+/// Differences above this share of the billed amount are flagged
+const GAP_TOLERANCE: f64 = 0.05;
+
+/// Billed cost for one day and model, summed over the CSV's rows
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BilledCost {
+    /// YYYY-MM-DD (UTC)
+    pub date: String,
+    pub model: String,
+    pub cost: f64,
+}
+
+/// Stored Console import
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConsoleImport {
+    pub source: String,
+    pub imported_at: String,
+    pub rows: Vec<BilledCost>,
+}
+
+/// Result of importing a CSV
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsoleImportSummary {
+    pub rows_read: usize,
+    pub rows_skipped: usize,
+    pub days: usize,
+    pub models: Vec<String>,
+    pub total_billed: f64,
+}
+
+/// Computed vs billed cost for one day and model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationRow {
+    pub date: String,
+    pub model: String,
+    pub computed: f64,
+    pub billed: f64,
+    /// computed - billed
+    pub difference: f64,
+    /// Difference relative to the billed amount (None when nothing was billed)
+    pub difference_percent: Option<f64>,
+    /// Gap large enough to look into
+    pub flagged: bool,
+}
+
+/// Reconciliation over the imported date range
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationReport {
+    pub source: Option<String>,
+    pub imported_at: Option<String>,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub total_computed: f64,
+    pub total_billed: f64,
+    pub rows: Vec<ReconciliationRow>,
+    /// Per-day totals across models
+    pub daily: Vec<ReconciliationRow>,
+}
+
+fn import_path() -> PathBuf {
+    app_config_dir().join("console-costs.json")
+}
+
+fn load_import() -> Option<ConsoleImport> {
+    fs::read_to_string(import_path())
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+}
+
+/// Split one CSV line, honoring quotes ("" is an escaped quote)
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields.into_iter().map(|f| f.trim().to_string()).collect()
+}
+
+/// Reduce model names from the Console ("Claude Sonnet 4", "claude-sonnet-4-20250514")
+/// and from logs to a common key
+fn normalize_model(model: &str) -> String {
+    let key = model.trim().to_lowercase().replace([' ', '_', '.'], "-");
+    // Drop a trailing -YYYYMMDD snapshot date
+    match key.rsplit_once('-') {
+        Some((base, date)) if date.len() == 8 && date.chars().all(|c| c.is_ascii_digit()) => {
+            base.to_string()
+        }
+        _ => key,
+    }
+}
+
+fn parse_date(text: &str) -> Option<NaiveDate> {
+    let text = text.trim();
+    let date_part = text.get(..10).unwrap_or(text);
+    NaiveDate::parse_from_str(date_part, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(text, "%m/%d/%Y"))
+        .ok()
+}
+
+fn parse_amount(text: &str) -> Option<f64> {
+    text.trim()
+        .trim_start_matches('$')
+        .replace(',', "")
+        .parse()
+        .ok()
+}
+
+/// Find the first column whose header contains one of the given words
+fn find_column(headers: &[String], words: &[&str]) -> Option<usize> {
+    words
+        .iter()
+        .find_map(|word| headers.iter().position(|h| h.to_lowercase().contains(word)))
+}
+
+/// Parse a Console cost export. Columns are matched by header name, so both the
+/// cost report and usage exports with a cost column work.
+fn parse_console_csv(content: &str) -> Result<(Vec<BilledCost>, usize, usize), String> {
+    let mut lines = content.lines().filter(|l| !l.trim().is_empty());
+    let headers = split_csv_line(
+        lines
+            .next()
+            .ok_or_else(|| "The CSV file is empty".to_string())?
+            .trim_start_matches('\u{feff}'),
+    );
+
+    let date_col = find_column(&headers, &["date", "day"])
+        .ok_or_else(|| "No date column found in CSV".to_string())?;
+    let model_col = find_column(&headers, &["model"])
+        .ok_or_else(|| "No model column found in CSV".to_string())?;
+    let cost_col = find_column(&headers, &["cost", "amount", "usd"])
+        .ok_or_else(|| "No cost column found in CSV".to_string())?;
+
+    let mut totals: BTreeMap<(String, String), f64> = BTreeMap::new();
+    let mut read = 0;
+    let mut skipped = 0;
+    for line in lines {
+        read += 1;
+        let fields = split_csv_line(line);
+        let date = fields.get(date_col).and_then(|d| parse_date(d));
+        let model = fields.get(model_col).filter(|m| !m.is_empty());
+        let cost = fields.get(cost_col).and_then(|c| parse_amount(c));
+        match (date, model, cost) {
+            (Some(date), Some(model), Some(cost)) => {
+                *totals
+                    .entry((date.format("%Y-%m-%d").to_string(), normalize_model(model)))
+                    .or_default() += cost;
+            }
+            _ => skipped += 1,
+        }
+    }
+
+    let rows = totals
+        .into_iter()
+        .map(|((date, model), cost)| BilledCost { date, model, cost })
+        .collect();
+    Ok((rows, read, skipped))
+}
+
+/// Import a Console CSV export, replacing any earlier import
+pub fn import_csv(path: &Path) -> Result<ConsoleImportSummary, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let (rows, rows_read, rows_skipped) = parse_console_csv(&content)?;
+    if rows.is_empty() {
+        return Err("No cost rows found in CSV".to_string());
+    }
+
+    let summary = ConsoleImportSummary {
+        rows_read,
+        rows_skipped,
+        days: rows.iter().map(|r| &r.date).collect::<BTreeSet<_>>().len(),
+        models: rows
+            .iter()
+            .map(|r| r.model.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect(),
+        total_billed: rows.iter().map(|r| r.cost).sum(),
+    };
+
+    let import = ConsoleImport {
+        source: path.to_string_lossy().to_string(),
+        imported_at: Utc::now().to_rfc3339(),
+        rows,
+    };
+    let contents =
+        serde_json::to_string_pretty(&import).map_err(|e| format!("Serialize error: {}", e))?;
+    fs::write(import_path(), contents).map_err(|e| format!("Write error: {}", e))?;
+
+    Ok(summary)
+}
+
+fn reconciliation_row(
+    date: String,
+    model: String,
+    computed: f64,
+    billed: f64,
+) -> ReconciliationRow {
+    let difference = computed - billed;
+    let difference_percent = (billed > 0.0).then(|| difference / billed * 100.0);
+    ReconciliationRow {
+        date,
+        model,
+        computed,
+        billed,
+        difference,
+        difference_percent,
+        flagged: difference.abs() >= MIN_GAP
+            && (billed <= 0.0 || difference.abs() / billed > GAP_TOLERANCE),
+    }
+}
+
+/// Rows per day and model, and per day across models, from the billed costs
+/// and the computed ones (date, normalized model, cost)
+fn compare(
+    billed: &[BilledCost],
+    computed: impl IntoIterator<Item = (String, String, f64)>,
+) -> (Vec<ReconciliationRow>, Vec<ReconciliationRow>) {
+    // (date, model) -> (computed, billed)
+    let mut cells: BTreeMap<(String, String), (f64, f64)> = BTreeMap::new();
+    for row in billed {
+        cells
+            .entry((row.date.clone(), row.model.clone()))
+            .or_default()
+            .1 += row.cost;
+    }
+    for (date, model, cost) in computed {
+        cells.entry((date, model)).or_default().0 += cost;
+    }
+
+    let mut daily_totals: BTreeMap<String, (f64, f64)> = BTreeMap::new();
+    let rows: Vec<ReconciliationRow> = cells
+        .into_iter()
+        .map(|((date, model), (computed, billed))| {
+            let day = daily_totals.entry(date.clone()).or_default();
+            day.0 += computed;
+            day.1 += billed;
+            reconciliation_row(date, model, computed, billed)
+        })
+        .collect();
+    let daily = daily_totals
+        .into_iter()
+        .map(|(date, (computed, billed))| {
+            reconciliation_row(date, "all".to_string(), computed, billed)
+        })
+        .collect();
+    (rows, daily)
+}
+
+/// Compare claudit's computed costs with the imported Console costs
+pub fn report() -> ReconciliationReport {
+    let Some(import) = load_import() else {
+        return ReconciliationReport {
+            source: None,
+            imported_at: None,
+            start_date: None,
+            end_date: None,
+            total_computed: 0.0,
+            total_billed: 0.0,
+            rows: Vec::new(),
+            daily: Vec::new(),
+        };
+    };
+
+    let start_date = import.rows.iter().map(|r| r.date.clone()).min();
+    let end_date = import.rows.iter().map(|r| r.date.clone()).max();
+
+    let mut computed = Vec::new();
+    if let Some(start) = start_date.as_deref().and_then(parse_date) {
+        let days = (Utc::now().date_naive() - start).num_days().max(0) as u32 + 1;
+        for entry in UsageReader::new().read_entries(Some(days)) {
+            let date = entry.timestamp.date_naive().format("%Y-%m-%d").to_string();
+            if Some(&date) < start_date.as_ref() || Some(&date) > end_date.as_ref() {
+                continue;
+            }
+            let cost = calculate_cost(
+                &entry.model,
+                Some(entry.timestamp),
+                entry.service_tier.as_deref(),
+                entry.input_tokens,
+                entry.output_tokens,
+                entry.cache_creation_tokens,
+                entry.cache_read_tokens,
+            );
+            computed.push((date, normalize_model(&entry.model), cost));
+        }
+    }
+    let (rows, daily) = compare(&import.rows, computed);
+
+    ReconciliationReport {
+        source: Some(import.source),
+        imported_at: Some(import.imported_at),
+        start_date,
+        end_date,
+        total_computed: rows.iter().map(|r| r.computed).sum(),
+        total_billed: rows.iter().map(|r| r.billed).sum(),
+        rows,
+        daily,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn billed(date: &str, model: &str, cost: f64) -> BilledCost {
+        BilledCost {
+            date: date.to_string(),
+            model: model.to_string(),
+            cost,
+        }
+    }
+
+    #[test]
+    fn test_split_csv_line() {
+        assert_eq!(split_csv_line("a, b ,c"), ["a", "b", "c"]);
+        assert_eq!(
+            split_csv_line(r#""Claude Sonnet 4","$1,234.50","say ""hi""""#),
+            ["Claude Sonnet 4", "$1,234.50", r#"say "hi""#]
+        );
+        assert_eq!(split_csv_line("a,,"), ["a", "", ""]);
+    }
+
+    #[test]
+    fn test_parse_amount_and_date() {
+        assert_eq!(parse_amount("$1,234.50"), Some(1234.5));
+        assert_eq!(parse_amount(" 0.25 "), Some(0.25));
+        assert_eq!(parse_amount("n/a"), None);
+        let day = NaiveDate::from_ymd_opt(2025, 3, 2);
+        assert_eq!(parse_date("2025-03-02"), day);
+        assert_eq!(parse_date("2025-03-02T23:59:59Z"), day);
+        assert_eq!(parse_date("03/02/2025"), day);
+        assert_eq!(parse_date("March 2"), None);
+    }
+
+    #[test]
+    fn test_normalize_model() {
+        assert_eq!(normalize_model("Claude Sonnet 4"), "claude-sonnet-4");
+        assert_eq!(
+            normalize_model("claude-sonnet-4-20250514"),
+            "claude-sonnet-4"
+        );
+        assert_eq!(normalize_model("claude_3.5_haiku"), "claude-3-5-haiku");
+    }
+
+    #[test]
+    fn test_parse_cost_report() {
+        let csv = "\u{feff}Date,Model,Cost (USD)\n\
+                   2025-03-01,claude-sonnet-4-20250514,\"$1,000.25\"\n\
+                   2025-03-01,Claude Sonnet 4,$0.75\n\
+                   2025-03-02,\"Claude Opus 4\",2.5\n\
+                   \n\
+                   2025-03-02,,1.0\n\
+                   not a date,claude-opus-4,1.0\n";
+        let (rows, read, skipped) = parse_console_csv(csv).unwrap();
+        assert_eq!((read, skipped), (5, 2));
+        assert_eq!(rows.len(), 2);
+        // Rows of one day and model are summed, whichever way the model is written
+        assert_eq!(rows[0].date, "2025-03-01");
+        assert_eq!(rows[0].model, "claude-sonnet-4");
+        assert!((rows[0].cost - 1001.0).abs() < 1e-9);
+        assert_eq!(rows[1].date, "2025-03-02");
+        assert_eq!(rows[1].model, "claude-opus-4");
+        assert!((rows[1].cost - 2.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_header_variants() {
+        let csv = "usage_day,model_name,amount\n03/05/2025,claude-haiku-3-5,0.10\n";
+        let (rows, _, _) = parse_console_csv(csv).unwrap();
+        assert_eq!(rows[0].date, "2025-03-05");
+        assert_eq!(rows[0].model, "claude-haiku-3-5");
+
+        let csv = "Day,Model,Total USD\n2025-03-05,claude-haiku-3-5,$0.10\n";
+        assert_eq!(parse_console_csv(csv).unwrap().0.len(), 1);
+
+        assert!(parse_console_csv("").is_err());
+        assert!(parse_console_csv("model,cost\nx,1\n").is_err());
+        assert!(parse_console_csv("date,cost\n2025-03-05,1\n").is_err());
+        assert!(parse_console_csv("date,model,tokens\n2025-03-05,x,1\n").is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        let imported = [
+            billed("2025-03-01", "claude-sonnet-4", 10.0),
+            billed("2025-03-01", "claude-opus-4", 4.0),
+            billed("2025-03-02", "claude-sonnet-4", 1.0),
+        ];
+        let computed = vec![
+            ("2025-03-01".to_string(), "claude-sonnet-4".to_string(), 6.0),
+            ("2025-03-01".to_string(), "claude-sonnet-4".to_string(), 4.2),
+            ("2025-03-01".to_string(), "claude-opus-4".to_string(), 2.0),
+            (
+                "2025-03-02".to_string(),
+                "claude-haiku-3-5".to_string(),
+                0.02,
+            ),
+        ];
+        let (rows, daily) = compare(&imported, computed);
+
+        let cells: Vec<(&str, &str, bool)> = rows
+            .iter()
+            .map(|r| (r.date.as_str(), r.model.as_str(), r.flagged))
+            .collect();
+        assert_eq!(
+            cells,
+            [
+                ("2025-03-01", "claude-opus-4", true),
+                // 2% over: within tolerance
+                ("2025-03-01", "claude-sonnet-4", false),
+                // Below the minimum gap although nothing was billed
+                ("2025-03-02", "claude-haiku-3-5", false),
+                // Billed but not seen in the logs
+                ("2025-03-02", "claude-sonnet-4", true),
+            ]
+        );
+        assert!((rows[0].difference + 2.0).abs() < 1e-9);
+        assert_eq!(rows[0].difference_percent.map(f64::round), Some(-50.0));
+        assert!((rows[1].computed - 10.2).abs() < 1e-9);
+        assert_eq!(rows[2].difference_percent, None);
+
+        assert_eq!(daily.len(), 2);
+        assert_eq!(daily[0].model, "all");
+        assert!((daily[0].computed - 12.2).abs() < 1e-9);
+        assert!((daily[0].billed - 14.0).abs() < 1e-9);
+        assert!(daily[0].flagged);
+        assert!((daily[1].computed - 0.02).abs() < 1e-9);
+        assert!((daily[1].billed - 1.0).abs() < 1e-9);
+    }
+}
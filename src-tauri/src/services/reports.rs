@@ -0,0 +1,393 @@
+//! Weekly and monthly usage reports
+//!
+//! `generate` aggregates the last 7 or 30 days (ending today, local time) into
+//! totals, per-model and per-project breakdowns and the most expensive
+//! sessions, next to the totals of the period before for the trend. The
+//! report renders to Markdown or to a self-contained HTML page for sharing.
+
+use super::analytics::calculate_entry_cost;
+use super::format;
+use super::usage::UsageReader;
+use chrono::{Duration, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+/// Sessions listed in a report
+const TOP_SESSIONS: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportPeriod {
+    Week,
+    Month,
+}
+
+impl ReportPeriod {
+    fn days(self) -> i64 {
+        match self {
+            ReportPeriod::Week => 7,
+            ReportPeriod::Month => 30,
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            ReportPeriod::Week => "Weekly",
+            ReportPeriod::Month => "Monthly",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReportTotals {
+    pub cost: f64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_creation_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub messages: u64,
+    pub sessions: u64,
+}
+
+/// Usage of one model or project within the period
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReportBreakdown {
+    pub name: String,
+    pub cost: f64,
+    /// Input + output tokens
+    pub tokens: u64,
+    pub messages: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportSession {
+    pub session_id: String,
+    pub project: String,
+    pub started_at: String,
+    pub cost: f64,
+    pub tokens: u64,
+    pub messages: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageReport {
+    pub period: ReportPeriod,
+    /// First and last day covered (YYYY-MM-DD, inclusive)
+    pub start_date: String,
+    pub end_date: String,
+    pub generated_at: String,
+    pub totals: ReportTotals,
+    /// Same-length period just before
+    pub previous: ReportTotals,
+    /// Cost change against the previous period, None when it had no cost
+    pub cost_change_pct: Option<f64>,
+    pub by_model: Vec<ReportBreakdown>,
+    pub by_project: Vec<ReportBreakdown>,
+    pub top_sessions: Vec<ReportSession>,
+}
+
+fn sorted(map: HashMap<String, ReportBreakdown>) -> Vec<ReportBreakdown> {
+    let mut rows: Vec<ReportBreakdown> = map.into_values().collect();
+    rows.sort_by(|a, b| b.cost.total_cmp(&a.cost));
+    rows
+}
+
+/// Aggregate the last week or month of usage
+pub fn generate(period: ReportPeriod) -> UsageReport {
+    let today = Local::now().date_naive();
+    let start = today - Duration::days(period.days() - 1);
+    let previous_start = start - Duration::days(period.days());
+
+    let reader = UsageReader::new();
+    let entries = reader.read_entries(Some(period.days() as u32 * 2 + 1));
+
+    let mut totals = ReportTotals::default();
+    let mut previous = ReportTotals::default();
+    let mut previous_sessions: HashSet<String> = HashSet::new();
+    let mut by_model: HashMap<String, ReportBreakdown> = HashMap::new();
+    let mut by_project: HashMap<String, ReportBreakdown> = HashMap::new();
+    let mut sessions: HashMap<String, ReportSession> = HashMap::new();
+
+    for entry in &entries {
+        let day = entry.timestamp.with_timezone(&Local).date_naive();
+        let cost = calculate_entry_cost(entry);
+        let target = if day >= start && day <= today {
+            &mut totals
+        } else if day >= previous_start && day < start {
+            previous_sessions.insert(entry.session_id.clone());
+            &mut previous
+        } else {
+            continue;
+        };
+        target.cost += cost;
+        target.input_tokens += entry.input_tokens;
+        target.output_tokens += entry.output_tokens;
+        target.cache_creation_tokens += entry.cache_creation_tokens;
+        target.cache_read_tokens += entry.cache_read_tokens;
+        target.messages += 1;
+        if day < start {
+            continue;
+        }
+
+        for (map, name) in [
+            (&mut by_model, &entry.model),
+            (&mut by_project, &entry.project),
+        ] {
+            let row = map.entry(name.clone()).or_insert_with(|| ReportBreakdown {
+                name: name.clone(),
+                ..Default::default()
+            });
+            row.cost += cost;
+            row.tokens += entry.total_tokens();
+            row.messages += 1;
+        }
+
+        let started_at = entry.timestamp.to_rfc3339();
+        let session = sessions
+            .entry(entry.session_id.clone())
+            .or_insert_with(|| ReportSession {
+                session_id: entry.session_id.clone(),
+                project: entry.project.clone(),
+                started_at: started_at.clone(),
+                cost: 0.0,
+                tokens: 0,
+                messages: 0,
+            });
+        session.started_at = session.started_at.clone().min(started_at);
+        session.cost += cost;
+        session.tokens += entry.total_tokens();
+        session.messages += 1;
+    }
+    totals.sessions = sessions.len() as u64;
+    previous.sessions = previous_sessions.len() as u64;
+
+    let mut top_sessions: Vec<ReportSession> = sessions.into_values().collect();
+    top_sessions.sort_by(|a, b| b.cost.total_cmp(&a.cost));
+    top_sessions.truncate(TOP_SESSIONS);
+
+    UsageReport {
+        period,
+        start_date: start.to_string(),
+        end_date: today.to_string(),
+        generated_at: Local::now().to_rfc3339(),
+        cost_change_pct: (previous.cost > 0.0)
+            .then(|| (totals.cost - previous.cost) / previous.cost * 100.0),
+        totals,
+        previous,
+        by_model: sorted(by_model),
+        by_project: sorted(by_project),
+        top_sessions,
+    }
+}
+
+fn format_tokens(tokens: u64) -> String {
+    match tokens {
+        t if t >= 1_000_000 => format!("{:.1}M", t as f64 / 1_000_000.0),
+        t if t >= 1_000 => format!("{:.1}k", t as f64 / 1_000.0),
+        t => t.to_string(),
+    }
+}
+
+fn trend(report: &UsageReport) -> String {
+    match report.cost_change_pct {
+        Some(pct) => format!(
+            "{:+.0}% vs previous period ({})",
+            pct,
+            format::cost(report.previous.cost)
+        ),
+        None => "no usage in the previous period".to_string(),
+    }
+}
+
+fn project_name(path: &str) -> &str {
+    path.rsplit('/').find(|p| !p.is_empty()).unwrap_or(path)
+}
+
+fn title(report: &UsageReport) -> String {
+    format!(
+        "{} usage report: {} to {}",
+        report.period.title(),
+        report.start_date,
+        report.end_date
+    )
+}
+
+/// The report as Markdown
+pub fn to_markdown(report: &UsageReport) -> String {
+    let t = &report.totals;
+    let mut md = String::new();
+    let _ = writeln!(md, "# {}\n", title(report));
+    let _ = writeln!(
+        md,
+        "- **Cost:** {} ({})",
+        format::cost(t.cost),
+        trend(report)
+    );
+    let _ = writeln!(
+        md,
+        "- **Tokens:** {} in, {} out, {} cache write, {} cache read",
+        format_tokens(t.input_tokens),
+        format_tokens(t.output_tokens),
+        format_tokens(t.cache_creation_tokens),
+        format_tokens(t.cache_read_tokens)
+    );
+    let _ = writeln!(
+        md,
+        "- **Activity:** {} messages in {} sessions",
+        t.messages, t.sessions
+    );
+
+    for (heading, rows) in [
+        ("Models", &report.by_model),
+        ("Projects", &report.by_project),
+    ] {
+        let _ = writeln!(md, "\n## {}\n", heading);
+        let _ = writeln!(md, "| Name | Cost | Tokens | Messages |");
+        let _ = writeln!(md, "| --- | ---: | ---: | ---: |");
+        for row in rows {
+            let _ = writeln!(
+                md,
+                "| {} | {} | {} | {} |",
+                row.name.replace('|', "\\|"),
+                format::cost(row.cost),
+                format_tokens(row.tokens),
+                row.messages
+            );
+        }
+    }
+
+    let _ = writeln!(md, "\n## Top sessions\n");
+    let _ = writeln!(md, "| Session | Project | Started | Cost | Messages |");
+    let _ = writeln!(md, "| --- | --- | --- | ---: | ---: |");
+    for s in &report.top_sessions {
+        let _ = writeln!(
+            md,
+            "| `{}` | {} | {} | {} | {} |",
+            s.session_id.get(..8).unwrap_or(&s.session_id),
+            project_name(&s.project).replace('|', "\\|"),
+            s.started_at.get(..10).unwrap_or(&s.started_at),
+            format::cost(s.cost),
+            s.messages
+        );
+    }
+    md
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn html_table(html: &mut String, headers: &[&str], rows: Vec<Vec<String>>) {
+    html.push_str("<table><thead><tr>");
+    for header in headers {
+        let _ = write!(html, "<th>{}</th>", header);
+    }
+    html.push_str("</tr></thead><tbody>");
+    for row in rows {
+        html.push_str("<tr>");
+        for cell in row {
+            let _ = write!(html, "<td>{}</td>", escape(&cell));
+        }
+        html.push_str("</tr>");
+    }
+    html.push_str("</tbody></table>");
+}
+
+/// The report as a self-contained HTML page
+pub fn to_html(report: &UsageReport) -> String {
+    let t = &report.totals;
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>{title}</title><style>\
+         body{{font-family:-apple-system,BlinkMacSystemFont,sans-serif;max-width:860px;margin:40px auto;padding:0 20px;color:#18181b}}\
+         .cards{{display:flex;gap:12px;flex-wrap:wrap}}.card{{border:1px solid #e4e4e7;border-radius:8px;padding:12px 16px;min-width:160px}}\
+         .card b{{display:block;font-size:22px}}.muted{{color:#71717a;font-size:13px}}\
+         table{{width:100%;border-collapse:collapse;margin:8px 0 24px}}th,td{{text-align:left;padding:6px 8px;border-bottom:1px solid #e4e4e7;font-size:14px}}\
+         </style></head><body><h1>{title}</h1>",
+        title = escape(&title(report))
+    );
+    let _ = write!(
+        html,
+        "<div class=\"cards\">\
+         <div class=\"card\"><span class=\"muted\">Cost</span><b>{}</b><span class=\"muted\">{}</span></div>\
+         <div class=\"card\"><span class=\"muted\">Tokens in / out</span><b>{} / {}</b></div>\
+         <div class=\"card\"><span class=\"muted\">Messages</span><b>{}</b></div>\
+         <div class=\"card\"><span class=\"muted\">Sessions</span><b>{}</b></div></div>",
+        escape(&format::cost(t.cost)),
+        escape(&trend(report)),
+        format_tokens(t.input_tokens),
+        format_tokens(t.output_tokens),
+        t.messages,
+        t.sessions
+    );
+
+    for (heading, rows) in [
+        ("Models", &report.by_model),
+        ("Projects", &report.by_project),
+    ] {
+        let _ = write!(html, "<h2>{}</h2>", heading);
+        let rows = rows
+            .iter()
+            .map(|r| {
+                vec![
+                    r.name.clone(),
+                    format::cost(r.cost),
+                    format_tokens(r.tokens),
+                    r.messages.to_string(),
+                ]
+            })
+            .collect();
+        html_table(&mut html, &["Name", "Cost", "Tokens", "Messages"], rows);
+    }
+
+    html.push_str("<h2>Top sessions</h2>");
+    let rows = report
+        .top_sessions
+        .iter()
+        .map(|s| {
+            vec![
+                s.session_id.get(..8).unwrap_or(&s.session_id).to_string(),
+                project_name(&s.project).to_string(),
+                s.started_at.get(..10).unwrap_or(&s.started_at).to_string(),
+                format::cost(s.cost),
+                s.messages.to_string(),
+            ]
+        })
+        .collect();
+    html_table(
+        &mut html,
+        &["Session", "Project", "Started", "Cost", "Messages"],
+        rows,
+    );
+    let _ = write!(
+        html,
+        "<p class=\"muted\">Generated by Claudit on {}</p></body></html>",
+        escape(
+            report
+                .generated_at
+                .get(..10)
+                .unwrap_or(&report.generated_at)
+        )
+    );
+    html
+}
+
+/// Render a report in the given format
+pub fn render(report: &UsageReport, format: ReportFormat) -> String {
+    match format {
+        ReportFormat::Markdown => to_markdown(report),
+        ReportFormat::Html => to_html(report),
+    }
+}
@@ -0,0 +1,208 @@
+//! Factory reset and uninstall
+//!
+//! `factory_reset` removes selected parts of what claudit stores in its config
+//! directory and, optionally, the hooks it installed into Claude Code's
+//! settings.json. `uninstall` does all of that and removes claudit's config
+//! directory, leaving ~/.claude as it was before claudit. Both return the
+//! operations they perform, so a dry run lists them first.
+
+use super::audit::{FileOperation, FileOperationKind};
+use super::settings::app_config_dir;
+use super::{analytics_db, goals, session_archive, HookInstaller, SettingsService};
+use crate::types::AppSettings;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// What to reset
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResetOptions {
+    /// Preferences, goals, profiles (reset to defaults)
+    #[serde(default)]
+    pub settings: bool,
+    /// Project notification rules, goal history, duplicate session resolutions,
+    /// imported Console costs, suggestion feedback, trashed sessions, restore
+    /// points and app data backups
+    #[serde(default)]
+    pub data: bool,
+    /// Window layout, activity feed, hook spool, audit log and its snapshots,
+    /// demo data, analytics cache
+    #[serde(default)]
+    pub caches: bool,
+    /// Claudit's hooks in ~/.claude/settings.json, and the hook and automation
+    /// tokens
+    #[serde(default)]
+    pub hooks: bool,
+}
+
+impl ResetOptions {
+    fn everything() -> Self {
+        Self {
+            settings: true,
+            data: true,
+            caches: true,
+            hooks: true,
+        }
+    }
+}
+
+const DATA_FILES: &[(&str, &str)] = &[
+    ("metadata.json", "project notification rules"),
+    ("duplicate-sessions.json", "duplicate session resolutions"),
+    ("console-costs.json", "imported Console costs"),
+    ("suggestion-feedback.json", "dismissed and snoozed suggestions"),
+    ("session-trash", "deleted sessions in the trash"),
+    ("telemetry.json", "telemetry counts not yet sent"),
+    ("restore-points", "restore points"),
+];
+
+const CACHE_FILES: &[(&str, &str)] = &[
+    ("window-state.json", "window layout"),
+    ("activity.jsonl", "activity feed"),
+    ("hook-spool.jsonl", "queued hook events"),
+    ("audit.jsonl", "config change audit log"),
+    ("audit-snapshots", "audit log snapshots"),
+    ("widget.json", "widget snapshot"),
+    ("demo", "demo mode sample data"),
+];
+
+const TOKEN_FILES: &[(&str, &str)] = &[
+    ("hook-token", "hook auth token"),
+    ("automation-token", "automation auth token"),
+];
+
+/// Removal of one of claudit's own files (not audited: they are not Claude config)
+pub fn removal(path: &Path, description: &str) -> Option<FileOperation> {
+    let metadata = fs::metadata(path).ok()?;
+    Some(FileOperation {
+        kind: FileOperationKind::Remove,
+        path: path.to_string_lossy().to_string(),
+        source: None,
+        bytes: metadata.is_file().then_some(metadata.len()),
+        description: format!("remove {}", description),
+    })
+}
+
+/// Remove a file or directory planned by `removal`
+pub fn remove(operation: &FileOperation) -> Result<(), String> {
+    let path = Path::new(&operation.path);
+    let result = if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    };
+    result.map_err(|e| format!("Failed to remove {}: {}", path.display(), e))
+}
+
+/// Claudit files covered by `options`
+fn planned_removals(options: &ResetOptions) -> Vec<FileOperation> {
+    let dir = app_config_dir();
+    let mut removals = Vec::new();
+
+    if options.data {
+        for (name, description) in DATA_FILES {
+            removals.extend(removal(&dir.join(name), description));
+        }
+        let backups = fs::read_dir(&dir)
+            .into_iter()
+            .flatten()
+            .filter_map(|e| e.ok())
+            .filter(|e| {
+                e.file_name()
+                    .to_string_lossy()
+                    .starts_with("app-data-backup-")
+            });
+        for entry in backups {
+            removals.extend(removal(&entry.path(), "app data backup"));
+        }
+    }
+    if options.caches {
+        for (name, description) in CACHE_FILES {
+            removals.extend(removal(&dir.join(name), description));
+        }
+    }
+    if options.hooks {
+        for (name, description) in TOKEN_FILES {
+            removals.extend(removal(&dir.join(name), description));
+        }
+    }
+    removals
+}
+
+/// Reset the selected parts of claudit. With `dry_run` nothing is changed.
+pub fn factory_reset(options: &ResetOptions, dry_run: bool) -> Result<Vec<FileOperation>, String> {
+    let mut operations = Vec::new();
+
+    // Hooks first: their removal is recorded in the audit log, which a cache
+    // reset may delete afterwards
+    if options.hooks {
+        operations.extend(HookInstaller::uninstall(dry_run)?);
+    }
+
+    if options.settings {
+        let path = app_config_dir().join("settings.json");
+        operations.push(FileOperation {
+            kind: FileOperationKind::Write,
+            path: path.to_string_lossy().to_string(),
+            source: None,
+            bytes: None,
+            description: "reset preferences to defaults".to_string(),
+        });
+        if !dry_run {
+            SettingsService::shared().update(AppSettings::default())?;
+        }
+    }
+
+    for operation in planned_removals(options) {
+        if !dry_run {
+            remove(&operation)?;
+        }
+        operations.push(operation);
+    }
+    // Archived sessions stay listed; the trashed ones went with session-trash
+    if options.data && !dry_run {
+        session_archive::empty_trash(None)?;
+    }
+
+    // The goal history shares the analytics database with the cache
+    let db = analytics_db::db_path().to_string_lossy().to_string();
+    if options.data && !goals::history()?.is_empty() {
+        operations.push(FileOperation {
+            kind: FileOperationKind::Write,
+            path: db.clone(),
+            source: None,
+            bytes: None,
+            description: "remove goal history".to_string(),
+        });
+        if !dry_run {
+            goals::replace_history(&[])?;
+        }
+    }
+    if options.caches {
+        if let Some(operation) = removal(&analytics_db::db_path(), "analytics cache") {
+            if !dry_run {
+                analytics_db::rebuild()?;
+            }
+            operations.push(operation);
+        }
+    }
+    Ok(operations)
+}
+
+/// Revert everything claudit changed in ~/.claude and remove its config
+/// directory. The app can be deleted afterwards.
+pub fn uninstall(dry_run: bool) -> Result<Vec<FileOperation>, String> {
+    let mut operations = factory_reset(&ResetOptions::everything(), dry_run)?;
+
+    // Settings were written back as defaults above; the directory goes entirely
+    let dir = app_config_dir();
+    operations
+        .retain(|op| op.kind != FileOperationKind::Write || !Path::new(&op.path).starts_with(&dir));
+    if let Some(operation) = removal(&dir, "claudit's config directory") {
+        if !dry_run {
+            remove(&operation)?;
+        }
+        operations.push(operation);
+    }
+    Ok(operations)
+}
@@ -0,0 +1,143 @@
+//! Restore points before risky config changes
+//!
+//! Plans marked with `FilePlan::with_restore_point` (config imports, pack and
+//! hook installs, bulk MCP edits) first copy every file they are about to
+//! touch into `restore-points/<id>/` in claudit's config directory.
+//! `rollback_to` puts those files back and removes the ones the operation
+//! created. Rolling back records a restore point of its own, so it can be
+//! undone the same way.
+
+use super::audit;
+use super::demo;
+use super::settings::app_config_dir;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Older restore points are pruned
+const MAX_RESTORE_POINTS: usize = 20;
+const INDEX_FILE: &str = "restore-point.json";
+
+/// A file captured by a restore point
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestorePointFile {
+    pub path: String,
+    /// False when the operation created the file; rolling back removes it
+    pub existed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestorePoint {
+    pub id: String,
+    /// The operation that followed (e.g. "install_hooks")
+    pub name: String,
+    pub created_at: String,
+    pub files: Vec<RestorePointFile>,
+}
+
+fn root() -> PathBuf {
+    app_config_dir().join("restore-points")
+}
+
+/// Folder of a restore point
+pub fn path(id: &str) -> PathBuf {
+    root().join(id)
+}
+
+/// Copy of the file at `index` within a restore point
+fn stored_file(dir: &Path, index: usize) -> PathBuf {
+    dir.join(index.to_string())
+}
+
+fn load(dir: &Path) -> Option<RestorePoint> {
+    let content = fs::read_to_string(dir.join(INDEX_FILE)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Snapshot the given files before an operation changes them
+pub fn create(name: &str, paths: &[PathBuf]) -> Result<RestorePoint, String> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let dir = path(&id);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create restore point: {}", e))?;
+
+    let mut seen = HashSet::new();
+    let mut files = Vec::new();
+    for path in paths.iter().filter(|p| seen.insert(p.as_path())) {
+        let existed = path.is_file();
+        if existed {
+            fs::copy(path, stored_file(&dir, files.len()))
+                .map_err(|e| format!("Failed to snapshot {}: {}", path.display(), e))?;
+        }
+        files.push(RestorePointFile {
+            path: path.to_string_lossy().to_string(),
+            existed,
+        });
+    }
+
+    let point = RestorePoint {
+        id,
+        name: name.to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        files,
+    };
+    let json = serde_json::to_string_pretty(&point).map_err(|e| e.to_string())?;
+    fs::write(dir.join(INDEX_FILE), json)
+        .map_err(|e| format!("Failed to write restore point: {}", e))?;
+
+    prune();
+    Ok(point)
+}
+
+/// Restore points, newest first
+pub fn list() -> Vec<RestorePoint> {
+    let Ok(entries) = fs::read_dir(root()) else {
+        return Vec::new();
+    };
+    let mut points: Vec<RestorePoint> = entries
+        .flatten()
+        .filter_map(|entry| load(&entry.path()))
+        .collect();
+    points.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    points
+}
+
+fn prune() {
+    for point in list().into_iter().skip(MAX_RESTORE_POINTS) {
+        let _ = fs::remove_dir_all(path(&point.id));
+    }
+}
+
+/// Put every file of a restore point back as it was
+pub fn rollback_to(id: &str) -> Result<RestorePoint, String> {
+    demo::ensure_writable()?;
+    if uuid::Uuid::parse_str(id).is_err() {
+        return Err(format!("Invalid restore point: {}", id));
+    }
+    let dir = path(id);
+    let point = load(&dir).ok_or_else(|| format!("Restore point {} not found", id))?;
+
+    // Read the snapshots first: creating the new restore point may prune this one
+    let mut contents = Vec::new();
+    for (index, file) in point.files.iter().enumerate() {
+        contents.push(if file.existed {
+            let bytes = fs::read(stored_file(&dir, index))
+                .map_err(|e| format!("Restore point is missing {}: {}", file.path, e))?;
+            Some(bytes)
+        } else {
+            None
+        });
+    }
+
+    let action = format!("rollback_to: {}", point.name);
+    let paths: Vec<PathBuf> = point.files.iter().map(|f| PathBuf::from(&f.path)).collect();
+    create(&action, &paths)?;
+
+    for (path, contents) in paths.iter().zip(contents) {
+        match contents {
+            Some(bytes) => audit::write_file(path, bytes, &action)?,
+            None => audit::remove_file(path, &action)?,
+        }
+    }
+    Ok(point)
+}
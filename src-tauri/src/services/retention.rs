@@ -0,0 +1,562 @@
+//! Forgetting usage data
+//!
+//! `purge` removes what claudit has stored about one project and/or a date
+//! range: activity and audit entries, queued hook events, notification rules,
+//! duplicate resolutions, goal history, cached usage entries, Console costs,
+//! suggestion feedback and restore points. Records without a project (or
+//! without a date) are only removed when the scope doesn't filter on it.
+//! Claude's own session files are left alone unless `delete_claude_files` is
+//! set; usage cached from the ones that are kept comes back with a full
+//! reindex. Like `factory_reset`, the result lists the operations, so a dry run
+//! previews them.
+
+use super::analytics_db;
+use super::audit::{FileOperation, FileOperationKind};
+use super::goals::{self, GoalRecord};
+use super::paths;
+use super::reset;
+use super::restore_points;
+use super::settings::app_config_dir;
+use super::usage;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// What to forget: a project, a date range (inclusive, YYYY-MM-DD) or both
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PurgeScope {
+    #[serde(default)]
+    pub project: Option<String>,
+    #[serde(default)]
+    pub start_date: Option<String>,
+    #[serde(default)]
+    pub end_date: Option<String>,
+    /// Also delete the matching session transcripts from Claude's projects folder
+    #[serde(default)]
+    pub delete_claude_files: bool,
+}
+
+impl PurgeScope {
+    fn validate(&self) -> Result<(), String> {
+        if self.project.is_none() && self.start_date.is_none() && self.end_date.is_none() {
+            return Err("Choose a project or a date range to forget".to_string());
+        }
+        if let Some(project) = &self.project {
+            if !is_absolute(project) {
+                return Err(format!("Not a project path: {}", project));
+            }
+        }
+        for date in self.start_date.iter().chain(&self.end_date) {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|_| format!("Invalid date (use YYYY-MM-DD): {}", date))?;
+        }
+        if let (Some(start), Some(end)) = (&self.start_date, &self.end_date) {
+            if start > end {
+                return Err("The start date is after the end date".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    fn has_range(&self) -> bool {
+        self.start_date.is_some() || self.end_date.is_some()
+    }
+
+    /// Whether a path is the project or inside it (always true without a project)
+    fn in_project(&self, path: Option<&str>) -> bool {
+        match (&self.project, path) {
+            (None, _) => true,
+            (Some(project), Some(path)) => {
+                path == project
+                    || path
+                        .strip_prefix(project.as_str())
+                        .is_some_and(|rest| rest.starts_with(['/', '\\']))
+            }
+            (Some(_), None) => false,
+        }
+    }
+
+    /// Whether a timestamp or date falls in the range (always true without one)
+    fn in_range(&self, timestamp: Option<&str>) -> bool {
+        if !self.has_range() {
+            return true;
+        }
+        let Some(date) = timestamp.and_then(|t| t.get(..10)) else {
+            return false;
+        };
+        self.start_date.as_deref().is_none_or(|start| date >= start)
+            && self.end_date.as_deref().is_none_or(|end| date <= end)
+    }
+
+    /// Match a record with both a project and a timestamp
+    fn matches(&self, record: &Value, project_key: &str, time_key: &str) -> bool {
+        self.in_project(record[project_key].as_str()) && self.in_range(record[time_key].as_str())
+    }
+
+    /// Match a dated record that has no project
+    fn matches_undated_project(&self, record: &Value, time_key: &str) -> bool {
+        self.project.is_none() && self.in_range(record[time_key].as_str())
+    }
+
+    /// The project's folder in Claude's projects directory
+    fn session_folder(&self) -> Option<PathBuf> {
+        let project = self.project.as_ref()?;
+        Some(
+            paths::current()
+                .projects_dir()
+                .join(paths::encode_path_to_folder(project)),
+        )
+    }
+}
+
+/// Whether a project path is absolute, on Unix (`/home/me/app`) or Windows
+/// (`C:\Users\me\app`, `\\server\share\app`), whatever the host
+fn is_absolute(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    path.starts_with('/')
+        || path.starts_with("\\\\")
+        || (bytes.len() > 2
+            && bytes[0].is_ascii_alphabetic()
+            && bytes[1] == b':'
+            && matches!(bytes[2], b'/' | b'\\'))
+}
+
+/// An in-place removal of records from one of claudit's files
+fn record_removal(path: &Path, removed: usize, description: &str) -> Option<FileOperation> {
+    (removed > 0).then(|| FileOperation {
+        kind: FileOperationKind::Write,
+        path: path.to_string_lossy().to_string(),
+        source: None,
+        bytes: None,
+        description: format!("remove {} {}", removed, description),
+    })
+}
+
+fn write_json(path: &Path, value: &Value) -> Result<(), String> {
+    let contents = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Drop the lines of a JSONL file that `forget` matches; returns the dropped records
+fn purge_jsonl(
+    path: &Path,
+    forget: impl Fn(&Value) -> bool,
+    dry_run: bool,
+) -> Result<Vec<Value>, String> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Ok(Vec::new());
+    };
+    let mut kept = String::new();
+    let mut removed = Vec::new();
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        match serde_json::from_str::<Value>(line) {
+            Ok(record) if forget(&record) => removed.push(record),
+            _ => {
+                kept.push_str(line);
+                kept.push('\n');
+            }
+        }
+    }
+    if !dry_run && !removed.is_empty() {
+        fs::write(path, kept).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    }
+    Ok(removed)
+}
+
+/// Drop the elements of a JSON array (at `pointer` within the file) that `forget` matches
+fn purge_json_array(
+    path: &Path,
+    pointer: &str,
+    forget: impl Fn(&Value) -> bool,
+    dry_run: bool,
+) -> Result<usize, String> {
+    let Some(mut value) = fs::read_to_string(path)
+        .ok()
+        .and_then(|c| serde_json::from_str::<Value>(&c).ok())
+    else {
+        return Ok(0);
+    };
+    let Some(records) = value.pointer_mut(pointer).and_then(Value::as_array_mut) else {
+        return Ok(0);
+    };
+    let before = records.len();
+    records.retain(|record| !forget(record));
+    let removed = before - records.len();
+    if !dry_run && removed > 0 {
+        write_json(path, &value)?;
+    }
+    Ok(removed)
+}
+
+/// Per-project notification rules (undated, so only for a project without a range)
+fn purge_metadata(scope: &PurgeScope, dry_run: bool) -> Result<Option<FileOperation>, String> {
+    let path = app_config_dir().join("metadata.json");
+    if scope.project.is_none() || scope.has_range() {
+        return Ok(None);
+    }
+    let Some(mut value) = fs::read_to_string(&path)
+        .ok()
+        .and_then(|c| serde_json::from_str::<Value>(&c).ok())
+    else {
+        return Ok(None);
+    };
+    let Some(projects) = value["projects"].as_object_mut() else {
+        return Ok(None);
+    };
+    let before = projects.len();
+    projects.retain(|project, _| !scope.in_project(Some(project)));
+    let removed = before - projects.len();
+    if !dry_run && removed > 0 {
+        write_json(&path, &value)?;
+    }
+    Ok(record_removal(
+        &path,
+        removed,
+        "project notification rules, notes and action items",
+    ))
+}
+
+/// First and last message dates of a session transcript
+fn session_dates(path: &Path) -> Option<(String, String)> {
+    let content = fs::read_to_string(path).ok()?;
+    let mut dates = content.lines().filter_map(|line| {
+        let record: Value = serde_json::from_str(line).ok()?;
+        record["timestamp"].as_str()?.get(..10).map(String::from)
+    });
+    let first = dates.next()?;
+    let (min, max) = dates.fold((first.clone(), first), |(min, max), date| {
+        (min.min(date.clone()), max.max(date))
+    });
+    Some((min, max))
+}
+
+/// Session transcripts in Claude's projects folder that fall entirely in the scope
+fn claude_file_removals(scope: &PurgeScope) -> Vec<FileOperation> {
+    let folders: Vec<PathBuf> = match scope.session_folder() {
+        Some(folder) if !scope.has_range() => {
+            return reset::removal(&folder, "Claude session transcripts")
+                .into_iter()
+                .collect();
+        }
+        Some(folder) => vec![folder],
+        None => fs::read_dir(paths::current().projects_dir())
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.is_dir())
+            .collect(),
+    };
+
+    let mut removals = Vec::new();
+    for folder in folders {
+        let files = fs::read_dir(&folder).into_iter().flatten().flatten();
+        for file in files.map(|e| e.path()) {
+            if file.extension().is_none_or(|ext| ext != "jsonl") {
+                continue;
+            }
+            let Some((first, last)) = session_dates(&file) else {
+                continue;
+            };
+            if scope.in_range(Some(&first)) && scope.in_range(Some(&last)) {
+                removals.extend(reset::removal(&file, "Claude session transcript"));
+            }
+        }
+    }
+    removals
+}
+
+/// Forget the data in scope. With `dry_run` nothing is changed.
+pub fn purge(scope: &PurgeScope, dry_run: bool) -> Result<Vec<FileOperation>, String> {
+    scope.validate()?;
+    let dir = app_config_dir();
+    let mut operations = Vec::new();
+
+    let path = dir.join("activity.jsonl");
+    let removed = purge_jsonl(&path, |r| scope.matches(r, "project", "timestamp"), dry_run)?;
+    operations.extend(record_removal(&path, removed.len(), "activity entries"));
+
+    let path = dir.join("hook-spool.jsonl");
+    let removed = purge_jsonl(&path, |r| scope.matches(r, "cwd", "timestamp"), dry_run)?;
+    operations.extend(record_removal(&path, removed.len(), "queued hook events"));
+
+    // Audit entries take their before/after snapshots with them
+    let path = dir.join("audit.jsonl");
+    let removed = purge_jsonl(&path, |r| scope.matches(r, "path", "timestamp"), dry_run)?;
+    operations.extend(record_removal(&path, removed.len(), "config audit entries"));
+    for id in removed.iter().filter_map(|r| r["id"].as_str()) {
+        for stage in ["before", "after"] {
+            let snapshot = dir
+                .join("audit-snapshots")
+                .join(format!("{}.{}", id, stage));
+            if let Some(operation) = reset::removal(&snapshot, "audit snapshot") {
+                if !dry_run {
+                    reset::remove(&operation)?;
+                }
+            }
+        }
+    }
+
+    operations.extend(purge_metadata(scope, dry_run)?);
+
+    let path = dir.join("duplicate-sessions.json");
+    let folder = scope
+        .session_folder()
+        .map(|f| f.to_string_lossy().to_string());
+    let removed = purge_json_array(
+        &path,
+        "",
+        |r| {
+            let file = r["file"].as_str().unwrap_or_default();
+            folder
+                .as_deref()
+                .is_none_or(|folder| file.starts_with(folder))
+                && scope.in_range(r["resolved_at"].as_str())
+        },
+        dry_run,
+    )?;
+    operations.extend(record_removal(
+        &path,
+        removed,
+        "duplicate session resolutions",
+    ));
+
+    let forgotten: Vec<GoalRecord> = goals::history()?
+        .into_iter()
+        .filter(|record| {
+            serde_json::to_value(record)
+                .is_ok_and(|r| scope.matches_undated_project(&r, "period_start"))
+        })
+        .collect();
+    if !dry_run && !forgotten.is_empty() {
+        analytics_db::delete_goal_periods(&forgotten)?;
+    }
+    operations.extend(record_removal(
+        &analytics_db::db_path(),
+        forgotten.len(),
+        "goal history records",
+    ));
+
+    let purged = analytics_db::purge_entries(
+        scope.project.as_deref(),
+        scope.start_date.as_deref(),
+        scope.end_date.as_deref(),
+        dry_run,
+    )?;
+    operations.extend(record_removal(
+        &analytics_db::db_path(),
+        purged,
+        "cached usage entries",
+    ));
+
+    let undated_stores = [
+        (
+            "console-costs.json",
+            "/rows",
+            "date",
+            "imported Console costs",
+        ),
+        ("suggestion-feedback.json", "", "at", "suggestion feedback"),
+    ];
+    for (name, pointer, time_key, description) in undated_stores {
+        let path = dir.join(name);
+        let removed = purge_json_array(
+            &path,
+            pointer,
+            |r| scope.matches_undated_project(r, time_key),
+            dry_run,
+        )?;
+        operations.extend(record_removal(&path, removed, description));
+    }
+
+    let mut removals: Vec<FileOperation> = restore_points::list()
+        .into_iter()
+        .filter(|point| {
+            scope.in_range(Some(&point.created_at))
+                && point.files.iter().any(|f| scope.in_project(Some(&f.path)))
+        })
+        .filter_map(|point| reset::removal(&restore_points::path(&point.id), "restore point"))
+        .collect();
+    if scope.delete_claude_files {
+        removals.extend(claude_file_removals(scope));
+    }
+    for operation in removals {
+        if !dry_run {
+            reset::remove(&operation)?;
+        }
+        operations.push(operation);
+    }
+    if !dry_run {
+        usage::clear_entry_index();
+    }
+    Ok(operations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn scope(project: Option<&str>, start: Option<&str>, end: Option<&str>) -> PurgeScope {
+        PurgeScope {
+            project: project.map(String::from),
+            start_date: start.map(String::from),
+            end_date: end.map(String::from),
+            delete_claude_files: false,
+        }
+    }
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("claudit-retention-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(scope(None, None, None).validate().is_err());
+        assert!(scope(Some("relative/path"), None, None).validate().is_err());
+        assert!(scope(Some("C:relative"), None, None).validate().is_err());
+        assert!(scope(Some("C:\\work\\app"), None, None).validate().is_ok());
+        assert!(scope(Some("\\\\server\\share\\app"), None, None)
+            .validate()
+            .is_ok());
+        assert!(scope(None, Some("2024-13-01"), None).validate().is_err());
+        assert!(scope(None, Some("2024-03-02"), Some("2024-03-01"))
+            .validate()
+            .is_err());
+        assert!(
+            scope(Some("/work/app"), Some("2024-03-01"), Some("2024-03-01"))
+                .validate()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_scope_matching() {
+        let project = scope(Some("/work/app"), None, None);
+        assert!(project.in_project(Some("/work/app")));
+        assert!(project.in_project(Some("/work/app/src")));
+        assert!(!project.in_project(Some("/work/application")));
+        assert!(!project.in_project(None));
+        let windows = scope(Some("C:\\work\\app"), None, None);
+        assert!(windows.in_project(Some("C:\\work\\app\\src")));
+        assert!(!windows.in_project(Some("C:\\work\\application")));
+        // Without a range every record is in it, dated or not
+        assert!(project.in_range(None));
+
+        let range = scope(None, Some("2024-03-01"), Some("2024-03-31"));
+        assert!(range.in_project(None));
+        assert!(range.in_range(Some("2024-03-01T00:00:00Z")));
+        assert!(range.in_range(Some("2024-03-31T23:59:59Z")));
+        assert!(!range.in_range(Some("2024-04-01T00:00:00Z")));
+        assert!(!range.in_range(None));
+
+        let open_ended = scope(None, Some("2024-03-01"), None);
+        assert!(open_ended.in_range(Some("2030-01-01")));
+        assert!(!open_ended.in_range(Some("2024-02-29")));
+
+        let record = json!({ "project": "/work/app", "timestamp": "2024-03-10T12:00:00Z" });
+        assert!(scope(Some("/work/app"), Some("2024-03-01"), None).matches(
+            &record,
+            "project",
+            "timestamp"
+        ));
+        assert!(!scope(Some("/work/app"), Some("2024-03-11"), None).matches(
+            &record,
+            "project",
+            "timestamp"
+        ));
+        // Records without a project only go when the scope has no project
+        assert!(range.matches_undated_project(&json!({ "date": "2024-03-10" }), "date"));
+        assert!(!scope(Some("/work/app"), None, None)
+            .matches_undated_project(&json!({ "date": "2024-03-10" }), "date"));
+    }
+
+    #[test]
+    fn test_purge_jsonl() {
+        let dir = temp_dir();
+        let path = dir.join("activity.jsonl");
+        let lines = [
+            json!({ "project": "/work/app", "timestamp": "2024-03-10T12:00:00Z" }).to_string(),
+            json!({ "project": "/work/other", "timestamp": "2024-03-10T12:00:00Z" }).to_string(),
+            "not json".to_string(),
+            json!({ "project": "/work/app/sub", "timestamp": "2024-03-11T12:00:00Z" }).to_string(),
+        ];
+        fs::write(&path, lines.join("\n") + "\n").unwrap();
+        let scope = scope(Some("/work/app"), None, None);
+        let forget = |r: &Value| scope.matches(r, "project", "timestamp");
+
+        let removed = purge_jsonl(&path, forget, true).unwrap();
+        assert_eq!(removed.len(), 2);
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            lines.join("\n") + "\n",
+            "a dry run leaves the file alone"
+        );
+
+        let removed = purge_jsonl(&path, forget, false).unwrap();
+        assert_eq!(removed.len(), 2);
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            format!("{}\nnot json\n", lines[1])
+        );
+        assert!(purge_jsonl(&path, forget, false).unwrap().is_empty());
+        assert!(purge_jsonl(&dir.join("missing.jsonl"), forget, false)
+            .unwrap()
+            .is_empty());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_purge_json_array() {
+        let dir = temp_dir();
+        let path = dir.join("console-costs.json");
+        let value = json!({
+            "imported_at": "2024-04-01T00:00:00Z",
+            "rows": [
+                { "date": "2024-02-28", "cost": 1.0 },
+                { "date": "2024-03-01", "cost": 2.0 },
+                { "date": "2024-03-31", "cost": 3.0 },
+            ],
+        });
+        write_json(&path, &value).unwrap();
+        let scope = scope(None, Some("2024-03-01"), Some("2024-03-31"));
+        let forget = |r: &Value| scope.matches_undated_project(r, "date");
+
+        assert_eq!(purge_json_array(&path, "/rows", forget, true).unwrap(), 2);
+        let stored: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(stored, value);
+
+        assert_eq!(purge_json_array(&path, "/rows", forget, false).unwrap(), 2);
+        let stored: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(stored["imported_at"], value["imported_at"]);
+        assert_eq!(
+            stored["rows"],
+            json!([{ "date": "2024-02-28", "cost": 1.0 }])
+        );
+
+        // Anything but an array at the pointer is left alone
+        assert_eq!(
+            purge_json_array(&path, "/imported_at", forget, false).unwrap(),
+            0
+        );
+        assert_eq!(
+            purge_json_array(&dir.join("missing.json"), "", forget, false).unwrap(),
+            0
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_record_removal() {
+        assert!(record_removal(Path::new("/tmp/a.jsonl"), 0, "activity entries").is_none());
+        let operation = record_removal(Path::new("/tmp/a.jsonl"), 3, "activity entries").unwrap();
+        assert_eq!(operation.kind, FileOperationKind::Write);
+        assert_eq!(operation.description, "remove 3 activity entries");
+    }
+}
@@ -0,0 +1,202 @@
+//! Path validation for file-access commands
+//!
+//! Commands that take a path from the frontend resolve it through `allowed_path`,
+//! which only accepts paths inside the Claude config dir (plus .claude.json), registered
+//! project directories, and files or folders the user picked through a native
+//! dialog in this run of the app (`pick_path`).
+
+use super::{config, paths};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+
+/// Paths the user explicitly picked through a dialog (valid until the app quits)
+static USER_PICKED: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+/// Remember a path the user picked through a dialog
+pub fn allow_user_picked(path: &Path) {
+    if let Ok(path) = normalize(path) {
+        USER_PICKED.lock().unwrap().push(path);
+    }
+}
+
+/// Expand `~` and make the path absolute without `.`/`..` components.
+/// Symlinks are resolved for the part of the path that exists.
+fn normalize(path: &Path) -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or_else(|| "Could not find home directory".to_string())?;
+
+    let expanded = match path.strip_prefix("~") {
+        Ok(rest) => home.join(rest),
+        Err(_) => path.to_path_buf(),
+    };
+    if !expanded.is_absolute() {
+        return Err(format!("Path must be absolute: {}", path.display()));
+    }
+    if expanded.components().any(|c| c == Component::ParentDir) {
+        return Err(format!("Path traversal is not allowed: {}", path.display()));
+    }
+
+    // Canonicalize the longest existing ancestor, then re-append the rest
+    let mut existing = expanded.as_path();
+    let mut rest = Vec::new();
+    while !existing.exists() {
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                rest.push(name.to_os_string());
+                existing = parent;
+            }
+            _ => break,
+        }
+    }
+
+    let mut resolved = existing
+        .canonicalize()
+        .unwrap_or_else(|_| existing.to_path_buf());
+    for name in rest.into_iter().rev() {
+        resolved.push(name);
+    }
+    Ok(resolved)
+}
+
+/// Roots that every command may access
+fn allowed_roots() -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+
+    let paths = paths::current();
+    roots.push(paths.config_dir().to_path_buf());
+    roots.push(paths.claude_json().to_path_buf());
+    roots.extend(
+        config::registered_project_paths()
+            .into_iter()
+            .map(PathBuf::from),
+    );
+    roots.extend(USER_PICKED.lock().unwrap().iter().cloned());
+    roots
+}
+
+/// Resolve `path` if it lies inside one of `roots`
+fn within(path: &str, roots: &[PathBuf]) -> Result<PathBuf, String> {
+    let resolved = normalize(Path::new(path))?;
+
+    if roots
+        .iter()
+        .filter_map(|root| normalize(root).ok())
+        .any(|root| resolved.starts_with(root))
+    {
+        Ok(resolved)
+    } else {
+        Err(format!(
+            "Access denied: {} is outside the allowed locations",
+            path
+        ))
+    }
+}
+
+/// Validate a path from the frontend against the allow-list
+pub fn allowed_path(path: &str) -> Result<PathBuf, String> {
+    within(path, &allowed_roots())
+}
+
+/// Resolve `project_path` if it is one of the `registered` projects
+fn registered_in(project_path: &str, registered: &[String]) -> Result<PathBuf, String> {
+    let resolved = normalize(Path::new(project_path))?;
+
+    let registered = registered
+        .iter()
+        .filter_map(|p| normalize(Path::new(p)).ok())
+        .any(|p| p == resolved);
+
+    if registered {
+        Ok(resolved)
+    } else {
+        Err(format!("Unknown project: {}", project_path))
+    }
+}
+
+/// Validate that a path is a project registered in ~/.claude.json
+pub fn registered_project(project_path: &str) -> Result<PathBuf, String> {
+    registered_in(project_path, &config::registered_project_paths())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh directory with a `root` folder and an `outside` folder next to it
+    fn scratch() -> (PathBuf, PathBuf, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("claudit-sandbox-{}", uuid::Uuid::new_v4()));
+        let (root, outside) = (dir.join("root"), dir.join("outside"));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        (dir, root, outside)
+    }
+
+    fn path_str(path: &Path) -> String {
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_paths_inside_roots() {
+        let (dir, root, _) = scratch();
+        let roots = [root.clone()];
+        assert!(within(&path_str(&root.join("CLAUDE.md")), &roots).is_ok());
+        // Not created yet
+        assert!(within(&path_str(&root.join("agents/new.md")), &roots).is_ok());
+        assert!(within("relative/CLAUDE.md", &roots).is_err());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_parent_dir_traversal() {
+        let (dir, root, outside) = scratch();
+        let roots = [root.clone()];
+        let escape = format!("{}/../outside/secret", path_str(&root));
+        assert!(within(&escape, &roots).is_err());
+        // Even when it would end up inside a root
+        let back_in = format!("{}/sub/../CLAUDE.md", path_str(&root));
+        assert!(within(&back_in, &roots).is_err());
+        assert!(within(&path_str(&outside.join("secret")), &roots).is_err());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_unregistered_absolute_paths() {
+        let (dir, root, outside) = scratch();
+        let roots = [root.clone()];
+        assert!(within("/etc/passwd", &roots).is_err());
+        // A sibling whose name starts like a root
+        let sibling = dir.join("root-other");
+        assert!(within(&path_str(&sibling.join("file")), &roots).is_err());
+
+        let registered = vec![path_str(&root)];
+        assert_eq!(
+            registered_in(&path_str(&root), &registered).unwrap(),
+            root.canonicalize().unwrap()
+        );
+        assert!(registered_in(&path_str(&outside), &registered).is_err());
+        assert!(registered_in(&path_str(&root.join("sub")), &registered).is_err());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_escape() {
+        let (dir, root, outside) = scratch();
+        let roots = [root.clone()];
+        std::os::unix::fs::symlink(&outside, root.join("link")).unwrap();
+        assert!(within(&path_str(&root.join("link/secret")), &roots).is_err());
+        assert!(within(&path_str(&root.join("link/new/file")), &roots).is_err());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_user_picked_paths() {
+        let (dir, _, outside) = scratch();
+        let picked = outside.join("export.html");
+        assert!(allowed_path(&path_str(&picked)).is_err());
+        allow_user_picked(&picked);
+        assert!(allowed_path(&path_str(&picked)).is_ok());
+        // Only what was picked, not its folder
+        assert!(allowed_path(&path_str(&outside.join("other.html"))).is_err());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,225 @@
+//! Session archive and trash
+//!
+//! `archive_session` moves a session's JSONL file (and its folder of subagent
+//! transcripts, if any) out of Claude's projects folder into
+//! `session-archive/` in claudit's config directory; `delete_session` moves
+//! it to `session-trash/` instead. Either can be restored to where it was
+//! until the trash is emptied, which is the only permanent deletion.
+//! `removed-sessions.json` lists what was moved and from where.
+
+use super::sessions::{check_session_id, session_file};
+use super::settings::app_config_dir;
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemovalKind {
+    /// Kept until restored
+    Archived,
+    /// Kept until the trash is emptied
+    Trashed,
+}
+
+/// A session moved out of Claude's projects folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemovedSession {
+    pub session_id: String,
+    pub project_path: String,
+    pub kind: RemovalKind,
+    /// Where the JSONL file was, and is restored to
+    pub original_path: String,
+    pub stored_path: String,
+    pub removed_at: String,
+    pub bytes: u64,
+}
+
+fn manifest_path() -> PathBuf {
+    app_config_dir().join("removed-sessions.json")
+}
+
+/// Folder sessions of `kind` are moved to
+pub fn storage_dir(kind: RemovalKind) -> PathBuf {
+    app_config_dir().join(match kind {
+        RemovalKind::Archived => "session-archive",
+        RemovalKind::Trashed => "session-trash",
+    })
+}
+
+fn load() -> Vec<RemovedSession> {
+    fs::read_to_string(manifest_path())
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save(removed: &[RemovedSession]) -> Result<(), String> {
+    let contents =
+        serde_json::to_string_pretty(removed).map_err(|e| format!("Serialize error: {}", e))?;
+    fs::write(manifest_path(), contents).map_err(|e| format!("Write error: {}", e))
+}
+
+/// Folder next to a session file that holds its subagent transcripts
+fn companion_dir(jsonl: &Path) -> PathBuf {
+    jsonl.with_extension("")
+}
+
+/// Move a file or folder, copying when it crosses file systems
+fn move_path(from: &Path, to: &Path) -> Result<(), String> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    if from.is_dir() {
+        copy_dir(from, to)?;
+        fs::remove_dir_all(from)
+    } else {
+        fs::copy(from, to).map_err(|e| format!("Failed to copy {}: {}", from.display(), e))?;
+        fs::remove_file(from)
+    }
+    .map_err(|e| format!("Failed to remove {}: {}", from.display(), e))
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<(), String> {
+    fs::create_dir_all(to).map_err(|e| format!("Failed to create {}: {}", to.display(), e))?;
+    let entries =
+        fs::read_dir(from).map_err(|e| format!("Failed to read {}: {}", from.display(), e))?;
+    for entry in entries.flatten() {
+        let target = to.join(entry.file_name());
+        if entry.path().is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)
+                .map_err(|e| format!("Failed to copy {}: {}", entry.path().display(), e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Move a session out of Claude's projects folder
+fn remove(
+    project_path: &str,
+    session_id: &str,
+    kind: RemovalKind,
+) -> Result<RemovedSession, String> {
+    check_session_id(session_id)?;
+    let original = session_file(project_path, session_id)?;
+    let folder = original
+        .parent()
+        .and_then(|p| p.file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let stored = storage_dir(kind)
+        .join(folder)
+        .join(format!("{}.jsonl", session_id));
+    if stored.exists() {
+        return Err(format!(
+            "{} is already stored at {}",
+            session_id,
+            stored.display()
+        ));
+    }
+
+    let bytes = fs::metadata(&original).map(|m| m.len()).unwrap_or(0);
+    move_path(&original, &stored)?;
+    let companion = companion_dir(&original);
+    if companion.is_dir() {
+        move_path(&companion, &companion_dir(&stored))?;
+    }
+
+    let session = RemovedSession {
+        session_id: session_id.to_string(),
+        project_path: project_path.to_string(),
+        kind,
+        original_path: original.to_string_lossy().to_string(),
+        stored_path: stored.to_string_lossy().to_string(),
+        removed_at: Utc::now().to_rfc3339(),
+        bytes,
+    };
+    let mut removed = load();
+    removed.push(session.clone());
+    save(&removed)?;
+    Ok(session)
+}
+
+/// Move a session to the archive
+pub fn archive(project_path: &str, session_id: &str) -> Result<RemovedSession, String> {
+    remove(project_path, session_id, RemovalKind::Archived)
+}
+
+/// Move a session to the trash
+pub fn trash(project_path: &str, session_id: &str) -> Result<RemovedSession, String> {
+    remove(project_path, session_id, RemovalKind::Trashed)
+}
+
+/// Archived and trashed sessions, newest first
+pub fn list() -> Vec<RemovedSession> {
+    let mut removed = load();
+    removed.retain(|session| Path::new(&session.stored_path).exists());
+    removed.sort_by(|a, b| b.removed_at.cmp(&a.removed_at));
+    removed
+}
+
+/// Move an archived or trashed session back into Claude's projects folder
+pub fn restore(session_id: &str) -> Result<RemovedSession, String> {
+    let mut removed = load();
+    let idx = removed
+        .iter()
+        .rposition(|session| session.session_id == session_id)
+        .ok_or_else(|| format!("Session not archived or in the trash: {}", session_id))?;
+    let session = removed[idx].clone();
+    let (stored, original) = (
+        PathBuf::from(&session.stored_path),
+        PathBuf::from(&session.original_path),
+    );
+    if original.exists() {
+        return Err(format!("{} already exists", original.display()));
+    }
+
+    move_path(&stored, &original)?;
+    let companion = companion_dir(&stored);
+    if companion.is_dir() {
+        move_path(&companion, &companion_dir(&original))?;
+    }
+    removed.remove(idx);
+    save(&removed)?;
+    Ok(session)
+}
+
+/// Permanently delete trashed sessions (those trashed more than
+/// `older_than_days` ago, or all). Returns how many were deleted.
+pub fn empty_trash(older_than_days: Option<u32>) -> Result<usize, String> {
+    let cutoff =
+        older_than_days.map(|days| (Utc::now() - Duration::days(days as i64)).to_rfc3339());
+    let mut removed = load();
+    let mut deleted = 0;
+    let mut kept = Vec::with_capacity(removed.len());
+    for session in removed.drain(..) {
+        let expired = session.kind == RemovalKind::Trashed
+            && cutoff
+                .as_ref()
+                .is_none_or(|cutoff| &session.removed_at < cutoff);
+        if !expired {
+            kept.push(session);
+            continue;
+        }
+        let stored = PathBuf::from(&session.stored_path);
+        if stored.exists() {
+            fs::remove_file(&stored)
+                .map_err(|e| format!("Failed to delete {}: {}", stored.display(), e))?;
+        }
+        let companion = companion_dir(&stored);
+        if companion.is_dir() {
+            fs::remove_dir_all(&companion)
+                .map_err(|e| format!("Failed to delete {}: {}", companion.display(), e))?;
+        }
+        deleted += 1;
+    }
+    save(&kept)?;
+    Ok(deleted)
+}
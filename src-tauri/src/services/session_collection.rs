@@ -0,0 +1,191 @@
+//! Session collections
+//!
+//! Exports the sessions matching a `SessionCollectionFilter` as one document,
+//! e.g. every session tagged `#auth-refactor` for a retrospective: a table of
+//! contents with each session's date and cost, then the sessions oldest
+//! first, each under a heading with its per-model cost summary. Tags are
+//! `#hashtags` in a session's note or custom title. Any session export format
+//! works, through the collection methods of `Exporter`.
+
+use super::metadata::{self, ClauditMetadata};
+use super::session_export::{
+    collection_cost, exporter, CollectionContext, CollectionEntry, SessionExportFormat,
+    SessionExportOptions,
+};
+use super::sessions;
+use crate::types::ExpensiveSession;
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::Path;
+
+/// Which sessions go into a collection; empty fields don't filter
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionCollectionFilter {
+    /// Sessions of this project only
+    pub project_path: Option<String>,
+    /// `#tag` in the session's note or title, with or without the `#`
+    pub tag: Option<String>,
+    /// Sessions active on or after this day (`YYYY-MM-DD`)
+    pub from: Option<String>,
+    /// Sessions active on or before this day (`YYYY-MM-DD`)
+    pub to: Option<String>,
+}
+
+/// What a collection export wrote
+#[derive(Debug, Clone, Serialize)]
+pub struct CollectionExportResult {
+    pub sessions: usize,
+    pub total_cost: f64,
+    pub bytes: u64,
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+fn parse_day(value: &Option<String>) -> Result<Option<NaiveDate>, String> {
+    non_empty(value)
+        .map(|day| {
+            NaiveDate::parse_from_str(day, "%Y-%m-%d")
+                .map_err(|_| format!("Invalid date: {} (expected YYYY-MM-DD)", day))
+        })
+        .transpose()
+}
+
+/// Local day of an RFC 3339 timestamp
+fn local_day(timestamp: Option<&str>) -> Option<NaiveDate> {
+    DateTime::parse_from_rfc3339(timestamp?)
+        .ok()
+        .map(|t| t.with_timezone(&Local).date_naive())
+}
+
+/// Whether `text` contains `#tag` as a word (case-insensitive)
+fn has_tag(text: &str, tag: &str) -> bool {
+    text.split(|c: char| c.is_whitespace() || matches!(c, ',' | ';' | '(' | ')'))
+        .filter_map(|word| word.strip_prefix('#'))
+        .any(|word| {
+            word.trim_end_matches(['.', '!', '?', ':'])
+                .eq_ignore_ascii_case(tag)
+        })
+}
+
+fn tagged(metadata: &ClauditMetadata, session: &ExpensiveSession, tag: &str) -> bool {
+    let note = metadata
+        .projects
+        .get(&session.project_path)
+        .and_then(|project| project.session_notes.get(&session.session.session_id));
+    note.is_some_and(|note| has_tag(note, tag))
+        || session
+            .session
+            .title
+            .as_deref()
+            .is_some_and(|title| has_tag(title, tag))
+}
+
+/// The sessions matching `filter`, oldest first
+pub fn matching_sessions(filter: &SessionCollectionFilter) -> Result<Vec<CollectionEntry>, String> {
+    let tag = non_empty(&filter.tag).map(|tag| tag.trim_start_matches('#'));
+    let (from, to) = (parse_day(&filter.from)?, parse_day(&filter.to)?);
+    let metadata = metadata::load();
+
+    let mut sessions: Vec<ExpensiveSession> =
+        sessions::list_all_sessions(non_empty(&filter.project_path))?
+            .into_iter()
+            .filter(|session| tag.is_none_or(|tag| tagged(&metadata, session, tag)))
+            // Sessions overlapping the range
+            .filter(|session| {
+                from.is_none_or(|from| {
+                    local_day(session.session.last_message_at.as_deref())
+                        .is_some_and(|last| last >= from)
+                })
+            })
+            .filter(|session| {
+                to.is_none_or(|to| {
+                    local_day(session.session.first_message_at.as_deref())
+                        .is_some_and(|first| first <= to)
+                })
+            })
+            .collect();
+    sessions.sort_by(|a, b| a.session.first_message_at.cmp(&b.session.first_message_at));
+
+    Ok(sessions
+        .into_iter()
+        .map(|session| {
+            let info = session.session;
+            let title = info
+                .title
+                .clone()
+                .or_else(|| info.summary.clone())
+                .or_else(|| {
+                    info.first_user_message
+                        .as_deref()
+                        .map(|prompt| super::format::excerpt(prompt, 80))
+                })
+                .unwrap_or_else(|| info.session_id.clone());
+            CollectionEntry {
+                project_path: session.project_path,
+                title,
+                info,
+            }
+        })
+        .collect())
+}
+
+/// Document title describing the filter, e.g. `Sessions tagged #auth-refactor in claudit`
+fn collection_title(filter: &SessionCollectionFilter) -> String {
+    let mut title = "Claude Code sessions".to_string();
+    if let Some(tag) = non_empty(&filter.tag) {
+        title = format!("Sessions tagged #{}", tag.trim_start_matches('#'));
+    }
+    if let Some(project) = non_empty(&filter.project_path) {
+        let name = Path::new(project)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.to_string());
+        title.push_str(&format!(" in {}", name));
+    }
+    match (non_empty(&filter.from), non_empty(&filter.to)) {
+        (Some(from), Some(to)) => title.push_str(&format!(", {} – {}", from, to)),
+        (Some(from), None) => title.push_str(&format!(", since {}", from)),
+        (None, Some(to)) => title.push_str(&format!(", until {}", to)),
+        (None, None) => {}
+    }
+    title
+}
+
+/// Export the sessions matching `filter` to `path` as one document
+pub fn export(
+    filter: &SessionCollectionFilter,
+    format: SessionExportFormat,
+    options: &SessionExportOptions,
+    path: &Path,
+) -> Result<CollectionExportResult, String> {
+    let entries = matching_sessions(filter)?;
+    if entries.is_empty() {
+        return Err("No sessions match the filter".to_string());
+    }
+    let title = collection_title(filter);
+    let ctx = CollectionContext {
+        title: &title,
+        entries: &entries,
+        options,
+    };
+
+    let file =
+        File::create(path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    let mut out = BufWriter::new(file);
+    exporter(format).write_collection(&ctx, &mut out)?;
+    drop(out);
+    let bytes = fs::metadata(path)
+        .map(|m| m.len())
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+    Ok(CollectionExportResult {
+        sessions: entries.len(),
+        total_cost: collection_cost(&entries),
+        bytes,
+    })
+}
@@ -0,0 +1,1270 @@
+//! Session export
+//!
+//! Each format implements `Exporter`, rendering the document header, the
+//! start and end of a message and a single content block. `export` does the
+//! traversal for all of them: it loads the conversation, drops the blocks that
+//! `SessionExportOptions` leaves out and streams the document to the output a
+//! message at a time, so long sessions never sit in memory as one string.
+//! `EXPORTERS` is the registry the commands and the save dialog filters are
+//! built from; a new format is one more implementation there. The collection
+//! methods render several sessions as one document (see `session_collection`).
+
+use super::sessions::get_session_conversation;
+use super::{format, pdf};
+use crate::types::{ConversationMessage, MessageContentBlock, SessionConversation, SessionInfo};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Session export formats
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionExportFormat {
+    #[default]
+    Html,
+    Markdown,
+    Json,
+    Pdf,
+}
+
+/// Color scheme of HTML and PDF session exports
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportTheme {
+    #[default]
+    Dark,
+    Light,
+}
+
+/// Options shared by all formats
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionExportOptions {
+    /// Colors of HTML and PDF exports
+    pub theme: ExportTheme,
+    pub include_thinking: bool,
+    /// Tool calls and their results
+    pub include_tools: bool,
+    /// Tokens and cost of each response
+    pub include_usage: bool,
+}
+
+impl Default for SessionExportOptions {
+    fn default() -> Self {
+        Self {
+            theme: ExportTheme::Dark,
+            include_thinking: true,
+            include_tools: true,
+            include_usage: true,
+        }
+    }
+}
+
+/// What is being exported
+pub struct ExportContext<'a> {
+    pub project_path: &'a str,
+    pub conversation: &'a SessionConversation,
+    pub options: &'a SessionExportOptions,
+}
+
+/// A session in a collection export
+#[derive(Debug, Clone, Serialize)]
+pub struct CollectionEntry {
+    pub project_path: String,
+    /// Custom title, summary or first prompt
+    pub title: String,
+    pub info: SessionInfo,
+}
+
+/// Several sessions exported as one document
+pub struct CollectionContext<'a> {
+    pub title: &'a str,
+    pub entries: &'a [CollectionEntry],
+    pub options: &'a SessionExportOptions,
+}
+
+/// A session export format. The rendering methods append to `out`, which
+/// `export` writes out after the header and after each message.
+pub trait Exporter: Sync {
+    fn format(&self) -> SessionExportFormat;
+    /// Name of the format in save dialogs
+    fn label(&self) -> &'static str;
+    fn extension(&self) -> &'static str;
+
+    fn header(&self, ctx: &ExportContext, out: &mut String);
+    /// `index` counts the exported messages (skipped ones don't count)
+    fn message_start(
+        &self,
+        index: usize,
+        message: &ConversationMessage,
+        ctx: &ExportContext,
+        out: &mut String,
+    );
+    /// `index` counts the exported blocks of the message
+    fn block(
+        &self,
+        index: usize,
+        block: &MessageContentBlock,
+        ctx: &ExportContext,
+        out: &mut String,
+    );
+    fn message_end(&self, message: &ConversationMessage, ctx: &ExportContext, out: &mut String);
+    fn footer(&self, ctx: &ExportContext, out: &mut String);
+
+    /// Title and table of contents of a collection
+    fn collection_header(&self, ctx: &CollectionContext, out: &mut String);
+    /// Heading and cost summary of the `index`th session of a collection,
+    /// before its messages
+    fn session_start(
+        &self,
+        index: usize,
+        entry: &CollectionEntry,
+        ctx: &ExportContext,
+        out: &mut String,
+    );
+    fn session_end(&self, ctx: &ExportContext, out: &mut String);
+    fn collection_footer(&self, ctx: &CollectionContext, out: &mut String);
+
+    /// Write the whole export to `out`; formats that can't be streamed
+    /// (PDF) override this
+    fn write(&self, ctx: &ExportContext, out: &mut dyn Write) -> Result<(), String> {
+        render(self, ctx, out)
+    }
+
+    /// Write a whole collection to `out`, like `write`
+    fn write_collection(&self, ctx: &CollectionContext, out: &mut dyn Write) -> Result<(), String> {
+        render_collection(self, ctx, out)
+    }
+}
+
+/// All formats, in the order save dialogs list them
+pub static EXPORTERS: [&dyn Exporter; 4] = [
+    &HtmlExporter,
+    &MarkdownExporter,
+    &JsonExporter,
+    &PdfExporter,
+];
+
+pub fn exporter(format: SessionExportFormat) -> &'static dyn Exporter {
+    EXPORTERS
+        .iter()
+        .copied()
+        .find(|e| e.format() == format)
+        .unwrap_or(&HtmlExporter)
+}
+
+/// A format as listed for the frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportFormatInfo {
+    pub format: SessionExportFormat,
+    pub label: &'static str,
+    pub extension: &'static str,
+}
+
+pub fn formats() -> Vec<ExportFormatInfo> {
+    EXPORTERS
+        .iter()
+        .map(|e| ExportFormatInfo {
+            format: e.format(),
+            label: e.label(),
+            extension: e.extension(),
+        })
+        .collect()
+}
+
+fn included(block: &MessageContentBlock, options: &SessionExportOptions) -> bool {
+    match block {
+        MessageContentBlock::Text { .. } => true,
+        MessageContentBlock::Thinking { .. } => options.include_thinking,
+        MessageContentBlock::ToolUse { .. }
+        | MessageContentBlock::ToolResult { .. }
+        | MessageContentBlock::ToolInvocation { .. } => options.include_tools,
+        MessageContentBlock::Other => false,
+    }
+}
+
+/// Move what has been rendered so far to `out`
+fn flush(buf: &mut String, out: &mut dyn Write) -> Result<(), String> {
+    out.write_all(buf.as_bytes())
+        .map_err(|e| format!("Failed to write export: {}", e))?;
+    buf.clear();
+    Ok(())
+}
+
+/// Walk the conversation with `exporter`, skipping messages left without
+/// any block
+fn render<E: Exporter + ?Sized>(
+    exporter: &E,
+    ctx: &ExportContext,
+    out: &mut dyn Write,
+) -> Result<(), String> {
+    let mut buf = String::new();
+    exporter.header(ctx, &mut buf);
+    flush(&mut buf, out)?;
+    render_messages(exporter, ctx, &mut buf, out)?;
+    exporter.footer(ctx, &mut buf);
+    flush(&mut buf, out)?;
+    out.flush()
+        .map_err(|e| format!("Failed to write export: {}", e))
+}
+
+/// Render and write out the messages, one at a time
+fn render_messages<E: Exporter + ?Sized>(
+    exporter: &E,
+    ctx: &ExportContext,
+    buf: &mut String,
+    out: &mut dyn Write,
+) -> Result<(), String> {
+    let mut index = 0;
+    for message in &ctx.conversation.messages {
+        let blocks: Vec<&MessageContentBlock> = message
+            .content
+            .iter()
+            .filter(|block| included(block, ctx.options))
+            .collect();
+        if blocks.is_empty() {
+            continue;
+        }
+        exporter.message_start(index, message, ctx, buf);
+        for (i, block) in blocks.into_iter().enumerate() {
+            exporter.block(i, block, ctx, buf);
+        }
+        exporter.message_end(message, ctx, buf);
+        flush(buf, out)?;
+        index += 1;
+    }
+    Ok(())
+}
+
+/// Walk the sessions of a collection, loading one conversation at a time
+fn render_collection<E: Exporter + ?Sized>(
+    exporter: &E,
+    ctx: &CollectionContext,
+    out: &mut dyn Write,
+) -> Result<(), String> {
+    let mut buf = String::new();
+    exporter.collection_header(ctx, &mut buf);
+    flush(&mut buf, out)?;
+
+    for (index, entry) in ctx.entries.iter().enumerate() {
+        let conversation = get_session_conversation(&entry.project_path, &entry.info.session_id)?;
+        let session = ExportContext {
+            project_path: &entry.project_path,
+            conversation: &conversation,
+            options: ctx.options,
+        };
+        exporter.session_start(index, entry, &session, &mut buf);
+        render_messages(exporter, &session, &mut buf, out)?;
+        exporter.session_end(&session, &mut buf);
+        flush(&mut buf, out)?;
+    }
+
+    exporter.collection_footer(ctx, &mut buf);
+    flush(&mut buf, out)?;
+    out.flush()
+        .map_err(|e| format!("Failed to write export: {}", e))
+}
+
+/// Total cost of a collection
+pub fn collection_cost(entries: &[CollectionEntry]) -> f64 {
+    entries.iter().map(|entry| entry.info.total_cost).sum()
+}
+
+/// Day (or first and last day) of a session, e.g. `2025-03-02 – 2025-03-04`
+fn session_dates(info: &SessionInfo) -> String {
+    let day = |t: &Option<String>| t.as_deref().and_then(|t| t.get(..10)).map(str::to_string);
+    match (day(&info.first_message_at), day(&info.last_message_at)) {
+        (Some(first), Some(last)) if first != last => format!("{} – {}", first, last),
+        (Some(day), _) | (None, Some(day)) => day,
+        (None, None) => String::new(),
+    }
+}
+
+/// Cost summary of a session in a collection, e.g.
+/// `84 messages · 1.2M tok · $3.20 (claude-opus-4 $2.90, claude-sonnet-4 $0.30)`
+fn session_cost_summary(info: &SessionInfo) -> String {
+    let mut summary = format!(
+        "{} messages · {} · {}",
+        info.message_count,
+        format::token_count(info.total_input_tokens + info.total_output_tokens),
+        format::cost(info.total_cost)
+    );
+    if !info.model_breakdown.is_empty() {
+        let models: Vec<String> = info
+            .model_breakdown
+            .iter()
+            .map(|m| format!("{} {}", m.model, format::cost(m.stats.cost)))
+            .collect();
+        summary.push_str(&format!(" ({})", models.join(", ")));
+    }
+    summary
+}
+
+fn project_name(project_path: &str) -> &str {
+    project_path
+        .rsplit(['/', '\\'])
+        .find(|part| !part.is_empty())
+        .unwrap_or(project_path)
+}
+
+/// Export a session in `format` to `out`
+pub fn export(
+    project_path: &str,
+    session_id: &str,
+    format: SessionExportFormat,
+    options: &SessionExportOptions,
+    out: &mut dyn Write,
+) -> Result<(), String> {
+    let conversation = get_session_conversation(project_path, session_id)?;
+    let ctx = ExportContext {
+        project_path,
+        conversation: &conversation,
+        options,
+    };
+    exporter(format).write(&ctx, out)
+}
+
+/// Export a text format (not PDF) to a string
+pub fn export_to_string(
+    project_path: &str,
+    session_id: &str,
+    format: SessionExportFormat,
+    options: &SessionExportOptions,
+) -> Result<String, String> {
+    let mut out = Vec::new();
+    export(project_path, session_id, format, options, &mut out)?;
+    String::from_utf8(out).map_err(|e| format!("Invalid UTF-8 in export: {}", e))
+}
+
+/// Export a session straight to `path`; returns the bytes written
+pub fn export_to_file(
+    project_path: &str,
+    session_id: &str,
+    format: SessionExportFormat,
+    options: &SessionExportOptions,
+    path: &Path,
+) -> Result<u64, String> {
+    let file =
+        File::create(path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    let mut out = BufWriter::new(file);
+    export(project_path, session_id, format, options, &mut out)?;
+    drop(out);
+    fs::metadata(path)
+        .map(|m| m.len())
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+/// Basic HTML escaping
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Standalone HTML page
+pub struct HtmlExporter;
+
+const HTML_CSS: &str = r#"
+        * { box-sizing: border-box; margin: 0; padding: 0; }
+        body { font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; background: #18181b; color: #fafafa; line-height: 1.6; padding: 2rem; -webkit-print-color-adjust: exact; print-color-adjust: exact; }
+        .container { max-width: 900px; margin: 0 auto; }
+        h1 { font-size: 1.5rem; margin-bottom: 0.5rem; color: #a1a1aa; }
+        .summary { font-size: 1.1rem; color: #71717a; margin-bottom: 2rem; padding-bottom: 1rem; border-bottom: 1px solid #27272a; }
+        .message { margin-bottom: 1.5rem; padding: 1rem; border-radius: 0.5rem; }
+        .user { background: #27272a; border-left: 3px solid #3b82f6; }
+        .assistant { background: #1f1f23; border-left: 3px solid #10b981; }
+        .role { font-size: 0.75rem; font-weight: 600; text-transform: uppercase; margin-bottom: 0.5rem; }
+        .user .role { color: #3b82f6; }
+        .assistant .role { color: #10b981; }
+        .content { white-space: pre-wrap; }
+        .thinking { background: #292524; padding: 0.75rem; border-radius: 0.25rem; margin: 0.5rem 0; font-size: 0.9rem; color: #a8a29e; border-left: 2px solid #78716c; }
+        .thinking-label { font-size: 0.7rem; color: #78716c; margin-bottom: 0.25rem; }
+        .tool-use { background: #1e1b4b; padding: 0.75rem; border-radius: 0.25rem; margin: 0.5rem 0; font-size: 0.85rem; border-left: 2px solid #6366f1; }
+        .tool-name { color: #818cf8; font-weight: 600; }
+        .tool-duration { font-size: 0.7rem; color: #71717a; margin-left: 0.5rem; }
+        .tool-result-label { font-size: 0.7rem; color: #71717a; margin-top: 0.5rem; }
+        .timestamp { font-size: 0.7rem; color: #52525b; float: right; }
+        .tokens { font-size: 0.7rem; color: #52525b; margin-top: 0.5rem; }
+        code { background: #27272a; padding: 0.1rem 0.3rem; border-radius: 0.2rem; font-family: 'SF Mono', Monaco, monospace; font-size: 0.9em; }
+        pre { background: #27272a; padding: 1rem; border-radius: 0.25rem; overflow-x: auto; margin: 0.5rem 0; }
+        pre code { background: none; padding: 0; }
+        .toc { width: 100%; border-collapse: collapse; margin-bottom: 2rem; font-size: 0.85rem; }
+        .toc th, .toc td { text-align: left; padding: 0.4rem 0.5rem; border-bottom: 1px solid #27272a; }
+        .toc th { color: #71717a; font-weight: 600; }
+        .toc .num { text-align: right; white-space: nowrap; }
+        .toc a { color: #60a5fa; text-decoration: none; }
+        .session { margin-top: 3rem; }
+        .session h2 { font-size: 1.25rem; margin-bottom: 0.25rem; }
+        .session-meta { font-size: 0.85rem; color: #71717a; margin-bottom: 1.5rem; }
+        @media print { pre { white-space: pre-wrap; overflow-wrap: anywhere; } .session { break-before: page; } }
+"#;
+
+/// Overrides of `HTML_CSS` for the light theme
+const LIGHT_THEME_CSS: &str = r#"
+        body { background: #ffffff; color: #18181b; }
+        h1 { color: #52525b; }
+        .summary { border-bottom-color: #e4e4e7; }
+        .user { background: #f4f4f5; }
+        .assistant { background: #fafafa; }
+        .user .role { color: #2563eb; }
+        .assistant .role { color: #059669; }
+        .thinking { background: #f5f5f4; color: #57534e; border-left-color: #a8a29e; }
+        .tool-use { background: #eef2ff; }
+        .tool-name { color: #4f46e5; }
+        .timestamp, .tokens { color: #71717a; }
+        code, pre { background: #f4f4f5; }
+        .toc th, .toc td { border-bottom-color: #e4e4e7; }
+        .toc a { color: #2563eb; }
+"#;
+
+fn json_block(value: &serde_json::Value) -> String {
+    format!(
+        "<pre><code>{}</code></pre>",
+        html_escape(&serde_json::to_string_pretty(value).unwrap_or_default())
+    )
+}
+
+/// How long a tool call took, e.g. `850ms`, `12.4s` or `3m`
+fn tool_duration(ms: u64) -> String {
+    match ms {
+        0..1_000 => format!("{}ms", ms),
+        1_000..60_000 => format!("{:.1}s", ms as f64 / 1000.0),
+        _ => format::elapsed((ms / 1000) as i64),
+    }
+}
+
+/// Everything up to the opened container
+fn html_head(title: &str, options: &SessionExportOptions, out: &mut String) {
+    out.push_str(&format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n    <meta charset=\"UTF-8\">\n    \
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">\n    \
+         <title>{}</title>\n    <style>{}",
+        html_escape(title),
+        HTML_CSS
+    ));
+    if options.theme == ExportTheme::Light {
+        out.push_str(LIGHT_THEME_CSS);
+    }
+    out.push_str("    </style>\n</head>\n<body>\n    <div class=\"container\">");
+}
+
+impl Exporter for HtmlExporter {
+    fn format(&self) -> SessionExportFormat {
+        SessionExportFormat::Html
+    }
+
+    fn label(&self) -> &'static str {
+        "HTML"
+    }
+
+    fn extension(&self) -> &'static str {
+        "html"
+    }
+
+    fn header(&self, ctx: &ExportContext, out: &mut String) {
+        let conversation = ctx.conversation;
+        let title = conversation
+            .summary
+            .as_deref()
+            .unwrap_or(&conversation.session_id);
+        html_head(
+            &format!("Claude Code Session - {}", title),
+            ctx.options,
+            out,
+        );
+        out.push_str(&format!(
+            "\n        <h1>Claude Code Session</h1>\n        <div class=\"summary\">{}</div>",
+            html_escape(conversation.summary.as_deref().unwrap_or("No summary"))
+        ));
+    }
+
+    fn message_start(
+        &self,
+        _index: usize,
+        message: &ConversationMessage,
+        _ctx: &ExportContext,
+        out: &mut String,
+    ) {
+        let role_class = if message.role == "user" {
+            "user"
+        } else {
+            "assistant"
+        };
+        out.push_str(&format!(
+            "\n        <div class=\"message {}\">\n            <div class=\"role\">{}",
+            role_class, message.role
+        ));
+        if let Some(ts) = &message.timestamp {
+            out.push_str(&format!(
+                "<span class=\"timestamp\">{}</span>",
+                html_escape(ts)
+            ));
+        }
+        out.push_str("</div>\n            <div class=\"content\">");
+    }
+
+    fn block(
+        &self,
+        _index: usize,
+        block: &MessageContentBlock,
+        _ctx: &ExportContext,
+        out: &mut String,
+    ) {
+        match block {
+            MessageContentBlock::Text { text } => out.push_str(&html_escape(text)),
+            MessageContentBlock::Thinking { thinking } => {
+                out.push_str(
+                    "<div class=\"thinking\"><div class=\"thinking-label\">Thinking</div>",
+                );
+                out.push_str(&html_escape(thinking));
+                out.push_str("</div>");
+            }
+            MessageContentBlock::ToolUse { name, input, .. } => {
+                out.push_str("<div class=\"tool-use\"><span class=\"tool-name\">");
+                out.push_str(&html_escape(name.as_deref().unwrap_or("Unknown tool")));
+                out.push_str("</span>");
+                if let Some(input) = input {
+                    out.push_str(&json_block(input));
+                }
+                out.push_str("</div>");
+            }
+            MessageContentBlock::ToolResult { content, .. } => {
+                if let Some(content) = content {
+                    out.push_str(&format!(
+                        "<div class=\"tool-use\">{}</div>",
+                        json_block(content)
+                    ));
+                }
+            }
+            MessageContentBlock::ToolInvocation {
+                name,
+                input,
+                result,
+                duration_ms,
+                ..
+            } => {
+                out.push_str("<div class=\"tool-use\"><span class=\"tool-name\">");
+                out.push_str(&html_escape(name.as_deref().unwrap_or("Unknown tool")));
+                out.push_str("</span>");
+                if let Some(ms) = duration_ms {
+                    out.push_str(&format!(
+                        "<span class=\"tool-duration\">{}</span>",
+                        tool_duration(*ms)
+                    ));
+                }
+                if let Some(input) = input {
+                    out.push_str(&json_block(input));
+                }
+                if let Some(result) = result {
+                    let (text, _) = tool_result_text(result);
+                    out.push_str(&format!(
+                        "<div class=\"tool-result-label\">Result</div><pre><code>{}</code></pre>",
+                        html_escape(&text)
+                    ));
+                }
+                out.push_str("</div>");
+            }
+            MessageContentBlock::Other => {}
+        }
+    }
+
+    fn message_end(&self, message: &ConversationMessage, ctx: &ExportContext, out: &mut String) {
+        out.push_str("</div>");
+        if let (true, Some(input), Some(output)) = (
+            ctx.options.include_usage,
+            message.input_tokens,
+            message.output_tokens,
+        ) {
+            out.push_str(&format!(
+                "<div class=\"tokens\">Tokens: {} in / {} out</div>",
+                input, output
+            ));
+        }
+        out.push_str("</div>");
+    }
+
+    fn footer(&self, _ctx: &ExportContext, out: &mut String) {
+        out.push_str("\n    </div>\n</body>\n</html>");
+    }
+
+    fn collection_header(&self, ctx: &CollectionContext, out: &mut String) {
+        html_head(ctx.title, ctx.options, out);
+        out.push_str(&format!(
+            "\n        <h1>{}</h1>\n        <div class=\"summary\">{} sessions · {}</div>\n        \
+             <table class=\"toc\">\n            <tr><th>#</th><th>Session</th><th>Project</th>\
+             <th>Date</th><th class=\"num\">Messages</th><th class=\"num\">Cost</th></tr>",
+            html_escape(ctx.title),
+            ctx.entries.len(),
+            format::cost(collection_cost(ctx.entries))
+        ));
+        for (index, entry) in ctx.entries.iter().enumerate() {
+            out.push_str(&format!(
+                "\n            <tr><td>{}</td><td><a href=\"#session-{}\">{}</a></td><td>{}</td>\
+                 <td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>",
+                index + 1,
+                index + 1,
+                html_escape(&entry.title),
+                html_escape(project_name(&entry.project_path)),
+                html_escape(&session_dates(&entry.info)),
+                entry.info.message_count,
+                format::cost(entry.info.total_cost)
+            ));
+        }
+        out.push_str("\n        </table>");
+    }
+
+    fn session_start(
+        &self,
+        index: usize,
+        entry: &CollectionEntry,
+        _ctx: &ExportContext,
+        out: &mut String,
+    ) {
+        out.push_str(&format!(
+            "\n        <section class=\"session\" id=\"session-{}\">\n        <h2>{}. {}</h2>\n        \
+             <div class=\"session-meta\">{} · {}<br>{}</div>",
+            index + 1,
+            index + 1,
+            html_escape(&entry.title),
+            html_escape(project_name(&entry.project_path)),
+            html_escape(&session_dates(&entry.info)),
+            html_escape(&session_cost_summary(&entry.info))
+        ));
+    }
+
+    fn session_end(&self, _ctx: &ExportContext, out: &mut String) {
+        out.push_str("\n        </section>");
+    }
+
+    fn collection_footer(&self, _ctx: &CollectionContext, out: &mut String) {
+        out.push_str("\n    </div>\n</body>\n</html>");
+    }
+}
+
+/// Markdown with fenced tool inputs and outputs, thinking in collapsible
+/// `<details>` and a token footer under each response
+pub struct MarkdownExporter;
+
+/// A fenced code block, with a fence longer than any backtick run in `code`
+fn fenced(code: &str, lang: &str) -> String {
+    let longest = code.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest.max(2) + 1);
+    format!("{}{}\n{}\n{}\n\n", fence, lang, code.trim_end(), fence)
+}
+
+/// Text of a tool result: plain strings and text blocks as-is, anything else as JSON
+fn tool_result_text(content: &serde_json::Value) -> (String, &'static str) {
+    match content {
+        serde_json::Value::String(text) => (text.clone(), "text"),
+        serde_json::Value::Array(blocks)
+            if blocks
+                .iter()
+                .all(|b| b.get("text").and_then(|t| t.as_str()).is_some()) =>
+        {
+            let texts: Vec<&str> = blocks
+                .iter()
+                .filter_map(|b| b.get("text").and_then(|t| t.as_str()))
+                .collect();
+            (texts.join("\n"), "text")
+        }
+        other => (
+            serde_json::to_string_pretty(other).unwrap_or_default(),
+            "json",
+        ),
+    }
+}
+
+impl Exporter for MarkdownExporter {
+    fn format(&self) -> SessionExportFormat {
+        SessionExportFormat::Markdown
+    }
+
+    fn label(&self) -> &'static str {
+        "Markdown"
+    }
+
+    fn extension(&self) -> &'static str {
+        "md"
+    }
+
+    fn header(&self, ctx: &ExportContext, out: &mut String) {
+        out.push_str(&format!(
+            "# {}\n\nClaude Code session `{}` in `{}`\n\n",
+            ctx.conversation
+                .summary
+                .as_deref()
+                .unwrap_or("Claude Code Session"),
+            ctx.conversation.session_id,
+            ctx.project_path
+        ));
+    }
+
+    fn message_start(
+        &self,
+        _index: usize,
+        message: &ConversationMessage,
+        _ctx: &ExportContext,
+        out: &mut String,
+    ) {
+        let role = if message.role == "user" {
+            "User"
+        } else {
+            "Assistant"
+        };
+        out.push_str(&format!("---\n\n### {}", role));
+        if let Some(model) = &message.model {
+            out.push_str(&format!(" · `{}`", model));
+        }
+        if let Some(ts) = &message.timestamp {
+            out.push_str(&format!(" · {}", ts));
+        }
+        out.push_str("\n\n");
+    }
+
+    fn block(
+        &self,
+        _index: usize,
+        block: &MessageContentBlock,
+        _ctx: &ExportContext,
+        out: &mut String,
+    ) {
+        match block {
+            MessageContentBlock::Text { text } => {
+                out.push_str(text.trim_end());
+                out.push_str("\n\n");
+            }
+            MessageContentBlock::Thinking { thinking } => {
+                out.push_str("<details>\n<summary>Thinking</summary>\n\n");
+                out.push_str(thinking.trim_end());
+                out.push_str("\n\n</details>\n\n");
+            }
+            MessageContentBlock::ToolUse { name, input, .. } => {
+                out.push_str(&format!(
+                    "**Tool: {}**\n\n",
+                    name.as_deref().unwrap_or("Unknown tool")
+                ));
+                if let Some(input) = input {
+                    out.push_str(&fenced(
+                        &serde_json::to_string_pretty(input).unwrap_or_default(),
+                        "json",
+                    ));
+                }
+            }
+            MessageContentBlock::ToolResult { content, .. } => {
+                if let Some(content) = content {
+                    let (text, lang) = tool_result_text(content);
+                    out.push_str("**Result**\n\n");
+                    out.push_str(&fenced(&text, lang));
+                }
+            }
+            MessageContentBlock::ToolInvocation {
+                name,
+                input,
+                result,
+                duration_ms,
+                ..
+            } => {
+                out.push_str(&format!(
+                    "**Tool: {}**",
+                    name.as_deref().unwrap_or("Unknown tool")
+                ));
+                if let Some(ms) = duration_ms {
+                    out.push_str(&format!(" _({})_", tool_duration(*ms)));
+                }
+                out.push_str("\n\n");
+                if let Some(input) = input {
+                    out.push_str(&fenced(
+                        &serde_json::to_string_pretty(input).unwrap_or_default(),
+                        "json",
+                    ));
+                }
+                if let Some(result) = result {
+                    let (text, lang) = tool_result_text(result);
+                    out.push_str("**Result**\n\n");
+                    out.push_str(&fenced(&text, lang));
+                }
+            }
+            MessageContentBlock::Other => {}
+        }
+    }
+
+    fn message_end(&self, message: &ConversationMessage, ctx: &ExportContext, out: &mut String) {
+        if !ctx.options.include_usage {
+            return;
+        }
+        if let (Some(input), Some(output)) = (message.input_tokens, message.output_tokens) {
+            out.push_str(&format!("<sub>Tokens: {} in / {} out", input, output));
+            if let Some(cost) = &message.cost {
+                out.push_str(&format!(" · {}", format::cost(cost.cost)));
+            }
+            out.push_str("</sub>\n\n");
+        }
+    }
+
+    fn footer(&self, _ctx: &ExportContext, _out: &mut String) {}
+
+    fn collection_header(&self, ctx: &CollectionContext, out: &mut String) {
+        out.push_str(&format!(
+            "# {}\n\n{} sessions · {}\n\n\
+             | # | Session | Project | Date | Messages | Cost |\n\
+             |--:|---|---|---|--:|--:|\n",
+            ctx.title,
+            ctx.entries.len(),
+            format::cost(collection_cost(ctx.entries))
+        ));
+        for (index, entry) in ctx.entries.iter().enumerate() {
+            out.push_str(&format!(
+                "| {} | [{}](#session-{}) | {} | {} | {} | {} |\n",
+                index + 1,
+                entry.title.replace('|', "\\|"),
+                index + 1,
+                project_name(&entry.project_path).replace('|', "\\|"),
+                session_dates(&entry.info),
+                entry.info.message_count,
+                format::cost(entry.info.total_cost)
+            ));
+        }
+        out.push('\n');
+    }
+
+    fn session_start(
+        &self,
+        index: usize,
+        entry: &CollectionEntry,
+        ctx: &ExportContext,
+        out: &mut String,
+    ) {
+        out.push_str(&format!(
+            "<a id=\"session-{}\"></a>\n\n## {}. {}\n\n`{}` in `{}` · {}\n\n{}\n\n",
+            index + 1,
+            index + 1,
+            entry.title,
+            ctx.conversation.session_id,
+            ctx.project_path,
+            session_dates(&entry.info),
+            session_cost_summary(&entry.info)
+        ));
+    }
+
+    fn session_end(&self, _ctx: &ExportContext, out: &mut String) {
+        out.push('\n');
+    }
+
+    fn collection_footer(&self, _ctx: &CollectionContext, _out: &mut String) {}
+}
+
+/// The conversation as JSON, one message per line, for scripts and archives
+pub struct JsonExporter;
+
+fn json(value: impl Serialize) -> String {
+    serde_json::to_string(&value).unwrap_or_else(|_| "null".to_string())
+}
+
+impl Exporter for JsonExporter {
+    fn format(&self) -> SessionExportFormat {
+        SessionExportFormat::Json
+    }
+
+    fn label(&self) -> &'static str {
+        "JSON"
+    }
+
+    fn extension(&self) -> &'static str {
+        "json"
+    }
+
+    fn header(&self, ctx: &ExportContext, out: &mut String) {
+        out.push_str(&format!(
+            "{{\"session_id\":{},\"project_path\":{},\"summary\":{},\"messages\":[",
+            json(&ctx.conversation.session_id),
+            json(ctx.project_path),
+            json(&ctx.conversation.summary)
+        ));
+    }
+
+    fn message_start(
+        &self,
+        index: usize,
+        message: &ConversationMessage,
+        _ctx: &ExportContext,
+        out: &mut String,
+    ) {
+        out.push_str(if index > 0 { ",\n" } else { "\n" });
+        out.push_str(&format!(
+            "{{\"uuid\":{},\"role\":{},\"timestamp\":{},\"model\":{},\"content\":[",
+            json(&message.uuid),
+            json(&message.role),
+            json(&message.timestamp),
+            json(&message.model)
+        ));
+    }
+
+    fn block(
+        &self,
+        index: usize,
+        block: &MessageContentBlock,
+        _ctx: &ExportContext,
+        out: &mut String,
+    ) {
+        if index > 0 {
+            out.push(',');
+        }
+        out.push_str(&json(block));
+    }
+
+    fn message_end(&self, message: &ConversationMessage, ctx: &ExportContext, out: &mut String) {
+        out.push(']');
+        if ctx.options.include_usage {
+            out.push_str(&format!(
+                ",\"input_tokens\":{},\"output_tokens\":{},\"cost\":{}",
+                json(message.input_tokens),
+                json(message.output_tokens),
+                json(&message.cost)
+            ));
+        }
+        out.push('}');
+    }
+
+    fn footer(&self, _ctx: &ExportContext, out: &mut String) {
+        out.push_str("\n]}\n");
+    }
+
+    fn collection_header(&self, ctx: &CollectionContext, out: &mut String) {
+        out.push_str(&format!(
+            "{{\"title\":{},\"total_cost\":{},\"sessions\":[",
+            json(ctx.title),
+            json(collection_cost(ctx.entries))
+        ));
+    }
+
+    fn session_start(
+        &self,
+        index: usize,
+        entry: &CollectionEntry,
+        ctx: &ExportContext,
+        out: &mut String,
+    ) {
+        out.push_str(if index > 0 { ",\n" } else { "\n" });
+        out.push_str(&format!(
+            "{{\"project_path\":{},\"title\":{},\"info\":{},\"summary\":{},\"messages\":[",
+            json(ctx.project_path),
+            json(&entry.title),
+            json(&entry.info),
+            json(&ctx.conversation.summary)
+        ));
+    }
+
+    fn session_end(&self, _ctx: &ExportContext, out: &mut String) {
+        out.push_str("\n]}");
+    }
+
+    fn collection_footer(&self, _ctx: &CollectionContext, out: &mut String) {
+        out.push_str("\n]}\n");
+    }
+}
+
+/// The HTML export printed to PDF by a headless browser
+pub struct PdfExporter;
+
+impl Exporter for PdfExporter {
+    fn format(&self) -> SessionExportFormat {
+        SessionExportFormat::Pdf
+    }
+
+    fn label(&self) -> &'static str {
+        "PDF"
+    }
+
+    fn extension(&self) -> &'static str {
+        "pdf"
+    }
+
+    fn header(&self, ctx: &ExportContext, out: &mut String) {
+        HtmlExporter.header(ctx, out)
+    }
+
+    fn message_start(
+        &self,
+        index: usize,
+        message: &ConversationMessage,
+        ctx: &ExportContext,
+        out: &mut String,
+    ) {
+        HtmlExporter.message_start(index, message, ctx, out)
+    }
+
+    fn block(
+        &self,
+        index: usize,
+        block: &MessageContentBlock,
+        ctx: &ExportContext,
+        out: &mut String,
+    ) {
+        HtmlExporter.block(index, block, ctx, out)
+    }
+
+    fn message_end(&self, message: &ConversationMessage, ctx: &ExportContext, out: &mut String) {
+        HtmlExporter.message_end(message, ctx, out)
+    }
+
+    fn footer(&self, ctx: &ExportContext, out: &mut String) {
+        HtmlExporter.footer(ctx, out)
+    }
+
+    fn collection_header(&self, ctx: &CollectionContext, out: &mut String) {
+        HtmlExporter.collection_header(ctx, out)
+    }
+
+    fn session_start(
+        &self,
+        index: usize,
+        entry: &CollectionEntry,
+        ctx: &ExportContext,
+        out: &mut String,
+    ) {
+        HtmlExporter.session_start(index, entry, ctx, out)
+    }
+
+    fn session_end(&self, ctx: &ExportContext, out: &mut String) {
+        HtmlExporter.session_end(ctx, out)
+    }
+
+    fn collection_footer(&self, ctx: &CollectionContext, out: &mut String) {
+        HtmlExporter.collection_footer(ctx, out)
+    }
+
+    fn write(&self, ctx: &ExportContext, out: &mut dyn Write) -> Result<(), String> {
+        print_pdf(out, |html| render(self, ctx, html))
+    }
+
+    fn write_collection(&self, ctx: &CollectionContext, out: &mut dyn Write) -> Result<(), String> {
+        print_pdf(out, |html| render_collection(self, ctx, html))
+    }
+}
+
+/// Render HTML to a temporary file with `render`, print it and copy the PDF to `out`
+fn print_pdf(
+    out: &mut dyn Write,
+    render: impl FnOnce(&mut dyn Write) -> Result<(), String>,
+) -> Result<(), String> {
+    let temp = std::env::temp_dir().join(format!("claudit-export-{}", uuid::Uuid::new_v4()));
+    let (html_path, pdf_path) = (temp.with_extension("html"), temp.with_extension("pdf"));
+    let result = File::create(&html_path)
+        .map_err(|e| format!("Failed to create {}: {}", html_path.display(), e))
+        .and_then(|file| render(&mut BufWriter::new(file)))
+        .and_then(|_| pdf::print_to_pdf(&html_path, &pdf_path))
+        .and_then(|_| {
+            let mut pdf = File::open(&pdf_path)
+                .map_err(|_| "The browser didn't write the PDF".to_string())?;
+            std::io::copy(&mut pdf, out).map_err(|e| format!("Failed to write export: {}", e))?;
+            out.flush()
+                .map_err(|e| format!("Failed to write export: {}", e))
+        });
+    let _ = fs::remove_file(&html_path);
+    let _ = fs::remove_file(&pdf_path);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn message(role: &str, content: Vec<MessageContentBlock>) -> ConversationMessage {
+        ConversationMessage {
+            uuid: format!("{}-{}", role, content.len()),
+            role: role.to_string(),
+            timestamp: Some("2025-03-02T10:00:00Z".to_string()),
+            content,
+            model: (role == "assistant").then(|| "claude-sonnet-4".to_string()),
+            input_tokens: (role == "assistant").then_some(120),
+            output_tokens: (role == "assistant").then_some(45),
+            cost: None,
+            context_window: None,
+        }
+    }
+
+    /// A prompt, a response with thinking, text and a tool call, and a
+    /// message holding only a tool result
+    fn fixture() -> SessionConversation {
+        SessionConversation {
+            session_id: "abc-123".to_string(),
+            summary: Some("Fix <script> & \"quotes\"".to_string()),
+            messages: vec![
+                message(
+                    "user",
+                    vec![MessageContentBlock::Text {
+                        text: "Why does <b>a</b> & b fail?".to_string(),
+                    }],
+                ),
+                message(
+                    "assistant",
+                    vec![
+                        MessageContentBlock::Thinking {
+                            thinking: "Check the tag first".to_string(),
+                        },
+                        MessageContentBlock::Text {
+                            text: "Run this:\n```sh\nls\n```".to_string(),
+                        },
+                        MessageContentBlock::ToolInvocation {
+                            id: Some("tool-1".to_string()),
+                            name: Some("Bash".to_string()),
+                            input: Some(json!({ "command": "cat README.md" })),
+                            result: Some(json!("# Title\n```\ncode\n```")),
+                            duration_ms: Some(850),
+                        },
+                    ],
+                ),
+                message(
+                    "user",
+                    vec![MessageContentBlock::ToolResult {
+                        tool_use_id: Some("tool-1".to_string()),
+                        content: Some(json!("done")),
+                    }],
+                ),
+            ],
+            total_messages: 3,
+            offset: 0,
+            subagents: Vec::new(),
+        }
+    }
+
+    fn export_fixture(format: SessionExportFormat, options: &SessionExportOptions) -> String {
+        let conversation = fixture();
+        let ctx = ExportContext {
+            project_path: "/work/app",
+            conversation: &conversation,
+            options,
+        };
+        let mut out = Vec::new();
+        exporter(format).write(&ctx, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn without_extras() -> SessionExportOptions {
+        SessionExportOptions {
+            include_thinking: false,
+            include_tools: false,
+            include_usage: false,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_included() {
+        let text = MessageContentBlock::Text {
+            text: "hi".to_string(),
+        };
+        let thinking = MessageContentBlock::Thinking {
+            thinking: "hmm".to_string(),
+        };
+        let tool = MessageContentBlock::ToolUse {
+            id: None,
+            name: Some("Read".to_string()),
+            input: None,
+        };
+        let result = MessageContentBlock::ToolResult {
+            tool_use_id: None,
+            content: None,
+        };
+
+        let all = SessionExportOptions::default();
+        assert!([&text, &thinking, &tool, &result]
+            .iter()
+            .all(|block| included(block, &all)));
+        assert!(!included(&MessageContentBlock::Other, &all));
+
+        let none = without_extras();
+        assert!(included(&text, &none));
+        assert!(!included(&thinking, &none));
+        assert!(!included(&tool, &none));
+        assert!(!included(&result, &none));
+    }
+
+    #[test]
+    fn test_html_escaping() {
+        let html = export_fixture(SessionExportFormat::Html, &SessionExportOptions::default());
+        assert!(html.contains("Why does &lt;b&gt;a&lt;/b&gt; &amp; b fail?"));
+        assert!(html.contains(
+            "<title>Claude Code Session - Fix &lt;script&gt; &amp; &quot;quotes&quot;</title>"
+        ));
+        assert!(!html.contains("<script>"));
+        assert!(!html.contains("<b>a</b>"));
+        assert!(html.contains("&quot;command&quot;: &quot;cat README.md&quot;"));
+        assert!(html.contains("<div class=\"thinking-label\">Thinking</div>Check the tag first"));
+        assert!(html.contains("<span class=\"tool-duration\">850ms</span>"));
+        assert!(html.contains("Tokens: 120 in / 45 out"));
+        assert!(html.ends_with("</html>"));
+    }
+
+    #[test]
+    fn test_markdown_fences_and_thinking() {
+        let markdown = export_fixture(
+            SessionExportFormat::Markdown,
+            &SessionExportOptions::default(),
+        );
+        assert!(markdown.starts_with("# Fix <script> & \"quotes\"\n\n"));
+        assert!(markdown.contains(
+            "<details>\n<summary>Thinking</summary>\n\nCheck the tag first\n\n</details>"
+        ));
+        // Text is kept as written, fences and all
+        assert!(markdown.contains("Run this:\n```sh\nls\n```\n\n"));
+        assert!(markdown.contains("**Tool: Bash** _(850ms)_"));
+        assert!(markdown.contains("```json\n{\n  \"command\": \"cat README.md\"\n}\n```"));
+        // A result containing a fence gets a longer one
+        assert!(markdown.contains("````text\n# Title\n```\ncode\n```\n````"));
+        assert!(markdown.contains("<sub>Tokens: 120 in / 45 out</sub>"));
+
+        let plain = export_fixture(SessionExportFormat::Markdown, &without_extras());
+        assert!(!plain.contains("<details>"));
+        assert!(!plain.contains("**Tool:"));
+        assert!(!plain.contains("<sub>"));
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let conversation = fixture();
+        let export: Value = serde_json::from_str(&export_fixture(
+            SessionExportFormat::Json,
+            &SessionExportOptions::default(),
+        ))
+        .unwrap();
+        assert_eq!(export["session_id"], "abc-123");
+        assert_eq!(export["project_path"], "/work/app");
+        assert_eq!(export["summary"], "Fix <script> & \"quotes\"");
+
+        let messages = export["messages"].as_array().unwrap();
+        assert_eq!(messages.len(), conversation.messages.len());
+        for (exported, original) in messages.iter().zip(&conversation.messages) {
+            assert_eq!(exported["uuid"], original.uuid.as_str());
+            assert_eq!(exported["role"], original.role.as_str());
+            let content: Vec<MessageContentBlock> =
+                serde_json::from_value(exported["content"].clone()).unwrap();
+            assert_eq!(
+                serde_json::to_value(&content).unwrap(),
+                serde_json::to_value(&original.content).unwrap()
+            );
+        }
+        assert_eq!(messages[1]["input_tokens"], 120);
+        assert_eq!(messages[1]["output_tokens"], 45);
+    }
+
+    #[test]
+    fn test_json_option_filtering() {
+        let export: Value = serde_json::from_str(&export_fixture(
+            SessionExportFormat::Json,
+            &without_extras(),
+        ))
+        .unwrap();
+        let messages = export["messages"].as_array().unwrap();
+        // The tool-result-only message has nothing left and is skipped
+        assert_eq!(messages.len(), 2);
+        assert_eq!(
+            messages[1]["content"],
+            json!([{ "type": "text", "text": "Run this:\n```sh\nls\n```" }])
+        );
+        assert!(messages[1].get("input_tokens").is_none());
+    }
+}
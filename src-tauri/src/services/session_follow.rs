@@ -0,0 +1,171 @@
+//! Session follow mode
+//!
+//! Tails a session's JSONL file while Claude is writing it and emits
+//! `session-messages` with every message added or updated since the last
+//! read, for a live transcript of a session running in the terminal. Each
+//! followed session has its own file watcher; dropping it in `unfollow`
+//! disconnects the channel and ends the reader thread.
+
+use super::sessions::{session_file, ConversationBuilder};
+use crate::types::{ConversationMessage, SessionConversation};
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Runtime};
+
+/// Claude appends several lines per response - read them together
+const DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// Watchers of the followed sessions, by session id
+static FOLLOWED: Mutex<Option<HashMap<String, RecommendedWatcher>>> = Mutex::new(None);
+
+/// Payload of the `session-messages` event
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionMessages {
+    pub session_id: String,
+    /// New messages, and earlier ones with more content (same uuid)
+    pub messages: Vec<ConversationMessage>,
+    /// The file was rewritten: `messages` is the whole conversation
+    pub reset: bool,
+}
+
+/// A session file read up to `offset`
+pub(super) struct Tail {
+    path: PathBuf,
+    offset: u64,
+    pub(super) builder: ConversationBuilder,
+}
+
+impl Tail {
+    pub(super) fn new(path: PathBuf) -> Self {
+        Self {
+            builder: ConversationBuilder::for_file(&path),
+            path,
+            offset: 0,
+        }
+    }
+
+    /// Read the complete lines appended since the last read. Returns the
+    /// indices of the messages they added or updated, and whether the file
+    /// was rewritten and read from the start.
+    pub(super) fn read(&mut self) -> Result<(BTreeSet<usize>, bool), String> {
+        let mut file = File::open(&self.path)
+            .map_err(|e| format!("Failed to open {}: {}", self.path.display(), e))?;
+        let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+        let reset = len < self.offset;
+        if reset {
+            *self = Tail::new(self.path.clone());
+        }
+
+        file.seek(SeekFrom::Start(self.offset))
+            .map_err(|e| format!("Failed to read {}: {}", self.path.display(), e))?;
+        let mut lines = BufReader::new(file);
+        let mut line = String::new();
+        let mut changed = BTreeSet::new();
+        loop {
+            line.clear();
+            let read = lines.read_line(&mut line).unwrap_or(0);
+            // Stop at the end, or before a line that is still being written
+            if read == 0 || !line.ends_with('\n') {
+                break;
+            }
+            self.offset += read as u64;
+            changed.extend(self.builder.push_line(&line));
+        }
+        Ok((changed, reset))
+    }
+}
+
+/// Start following a session. Returns the conversation so far; later
+/// messages arrive as `session-messages` events until `unfollow`.
+pub fn follow<R: Runtime>(
+    app: &AppHandle<R>,
+    project_path: &str,
+    session_id: &str,
+) -> Result<SessionConversation, String> {
+    let path = session_file(project_path, session_id)?;
+    let mut tail = Tail::new(path.clone());
+    tail.read()?;
+    let conversation = SessionConversation {
+        session_id: session_id.to_string(),
+        summary: tail.builder.summary.clone(),
+        messages: tail.builder.messages.clone(),
+        total_messages: tail.builder.messages.len(),
+        offset: 0,
+        subagents: tail.builder.subagent_threads(),
+    };
+
+    let (tx, rx) = mpsc::channel::<notify::Result<Event>>();
+    let mut watcher =
+        notify::recommended_watcher(tx).map_err(|e| format!("Failed to create watcher: {}", e))?;
+    watcher
+        .watch(&path, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch {}: {}", path.display(), e))?;
+
+    // Replacing an earlier watcher of the session ends its thread
+    FOLLOWED
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(session_id.to_string(), watcher);
+
+    let app = app.clone();
+    let session_id = session_id.to_string();
+    std::thread::spawn(move || {
+        let mut pending = false;
+        loop {
+            match rx.recv_timeout(DEBOUNCE) {
+                Ok(Ok(event)) => pending |= event.kind.is_modify() || event.kind.is_create(),
+                Ok(Err(e)) => eprintln!("Session follow error: {}", e),
+                Err(RecvTimeoutError::Timeout) if pending => {
+                    pending = false;
+                    let (changed, reset) = match tail.read() {
+                        Ok(read) => read,
+                        Err(e) => {
+                            eprintln!("{}", e);
+                            continue;
+                        }
+                    };
+                    let messages: Vec<ConversationMessage> = if reset {
+                        tail.builder.messages.clone()
+                    } else {
+                        changed
+                            .into_iter()
+                            .filter_map(|idx| tail.builder.messages.get(idx).cloned())
+                            .collect()
+                    };
+                    if reset || !messages.is_empty() {
+                        let _ = app.emit(
+                            "session-messages",
+                            &SessionMessages {
+                                session_id: session_id.clone(),
+                                messages,
+                                reset,
+                            },
+                        );
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    });
+
+    Ok(conversation)
+}
+
+/// Stop following a session. Returns false if it wasn't followed.
+pub fn unfollow(session_id: &str) -> bool {
+    FOLLOWED
+        .lock()
+        .unwrap()
+        .as_mut()
+        .and_then(|followed| followed.remove(session_id))
+        .is_some()
+}
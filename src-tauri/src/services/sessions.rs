@@ -1,32 +1,40 @@
-use crate::types::{ConversationMessage, MessageContentBlock, SessionConversation, SessionInfo, SessionSearchResult};
+use super::{path_mapping, paths};
+use crate::types::{
+    ConversationMessage, ExpensiveSession, MessageContentBlock, ModelStats, ReplayChunk,
+    ReplayMessage, SessionConversation, SessionInfo, SessionModelBreakdown, SessionReplay,
+    SessionSearchResult, SubagentThread,
+};
+use chrono::{DateTime, Datelike, Duration, Utc};
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
 /// Get the Claude projects directory
 fn get_claude_projects_dir() -> Option<PathBuf> {
-    dirs::home_dir().map(|h| h.join(".claude").join("projects"))
+    Some(paths::current().projects_dir())
 }
 
-/// Encode a project path to its folder name (same logic as usage.rs)
-fn encode_path_to_folder(path: &str) -> String {
-    path.replace('/', "-")
-}
-
-/// Get the project folder path for a given project path
-fn get_project_folder(project_path: &str) -> Option<PathBuf> {
+/// Get the project folder path for a given project path.
+/// Falls back to the folder of a container path mapped to the project.
+pub fn get_project_folder(project_path: &str) -> Option<PathBuf> {
     let projects_dir = get_claude_projects_dir()?;
-    let folder_name = encode_path_to_folder(project_path);
+    let folder_name = paths::encode_path_to_folder(project_path);
     let folder_path = projects_dir.join(&folder_name);
 
     if folder_path.exists() {
-        Some(folder_path)
-    } else {
-        None
+        return Some(folder_path);
     }
+    path_mapping::container_paths(&path_mapping::current(), project_path)
+        .into_iter()
+        .map(|path| projects_dir.join(paths::encode_path_to_folder(&path)))
+        .find(|folder| folder.exists())
 }
 
+use super::metadata::ClauditMetadata;
+use super::model_catalog;
 use super::pricing;
 
 /// List all sessions for a project
@@ -34,6 +42,7 @@ pub fn list_sessions(project_path: &str) -> Result<Vec<SessionInfo>, String> {
     let folder = get_project_folder(project_path)
         .ok_or_else(|| format!("Project folder not found for: {}", project_path))?;
 
+    let mut titles = super::metadata::get_project(project_path).session_titles;
     let mut sessions: Vec<SessionInfo> = Vec::new();
 
     // Find all .jsonl files in the folder
@@ -46,7 +55,8 @@ pub fn list_sessions(project_path: &str) -> Result<Vec<SessionInfo>, String> {
             if let Some(session_id) = path.file_stem().and_then(|s| s.to_str()) {
                 // Skip if it's a directory with same name
                 if path.is_file() {
-                    if let Ok(info) = parse_session_info(&path, session_id) {
+                    if let Ok(mut info) = parse_session_info(&path, session_id) {
+                        info.title = titles.remove(session_id);
                         sessions.push(info);
                     }
                 }
@@ -64,8 +74,235 @@ pub fn list_sessions(project_path: &str) -> Result<Vec<SessionInfo>, String> {
     Ok(sessions)
 }
 
+/// Start of a leaderboard time range, in calendar periods (UTC): "today", "week" (since
+/// Monday), "month" (since the 1st) or "all"
+fn range_start(range: &str) -> Result<Option<DateTime<Utc>>, String> {
+    let today = Utc::now().date_naive();
+    let start = match range {
+        "today" => today,
+        "week" => today - Duration::days(today.weekday().num_days_from_monday() as i64),
+        "month" => today.with_day(1).unwrap_or(today),
+        "all" => return Ok(None),
+        _ => return Err(format!("Unknown range: {}", range)),
+    };
+    Ok(start.and_hms_opt(0, 0, 0).map(|d| d.and_utc()))
+}
+
+/// (project path, session folder) pairs for every project with sessions
+fn all_session_folders() -> Result<Vec<(String, PathBuf)>, String> {
+    let projects_dir = get_claude_projects_dir()
+        .ok_or_else(|| "Claude projects directory not found".to_string())?;
+    let known: HashMap<String, String> = super::config::registered_project_paths()
+        .into_iter()
+        .map(|path| (paths::encode_path_to_folder(&path), path))
+        .collect();
+    let mappings = path_mapping::current();
+
+    Ok(std::fs::read_dir(&projects_dir)
+        .map_err(|e| format!("Failed to read directory: {}", e))?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|path| path.is_dir())
+        .map(|folder| {
+            let folder_name = folder
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let project = match known.get(&folder_name) {
+                Some(path) => path_mapping::to_host(&mappings, path.clone()),
+                None => path_mapping::map_folder(&mappings, &folder_name).unwrap_or(folder_name),
+            };
+            (project, folder)
+        })
+        .collect())
+}
+
+/// Title set in claudit for a session, if any
+fn session_title(metadata: &ClauditMetadata, project_path: &str, session_id: &str) -> Option<String> {
+    metadata
+        .projects
+        .get(project_path)
+        .and_then(|project| project.session_titles.get(session_id))
+        .cloned()
+}
+
+/// The most recently active sessions across all projects
+pub fn list_recent_sessions(limit: usize) -> Result<Vec<ExpensiveSession>, String> {
+    // Newest session files first; only those get parsed
+    let mut files: Vec<(std::time::SystemTime, String, PathBuf)> = Vec::new();
+    for (project, folder) in all_session_folders()? {
+        let Ok(entries) = std::fs::read_dir(&folder) else {
+            continue;
+        };
+        for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            if path.extension().is_none_or(|e| e != "jsonl") {
+                continue;
+            }
+            if let Ok(modified) = path.metadata().and_then(|m| m.modified()) {
+                files.push((modified, project.clone(), path));
+            }
+        }
+    }
+    files.sort_by_key(|(modified, _, _)| std::cmp::Reverse(*modified));
+
+    let metadata = super::metadata::load();
+    let mut sessions = Vec::new();
+    for (_, project, path) in files {
+        if sessions.len() >= limit {
+            break;
+        }
+        let Some(session_id) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let Ok(mut info) = parse_session_info(&path, session_id) else {
+            continue;
+        };
+        info.title = session_title(&metadata, &project, session_id);
+        if info.message_count == 0 {
+            continue;
+        }
+        sessions.push(ExpensiveSession {
+            project_name: PathBuf::from(&project)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| project.clone()),
+            project_path: project,
+            session: info,
+        });
+    }
+    Ok(sessions)
+}
+
+/// Every session with messages in one project (or all projects), unordered
+pub fn list_all_sessions(project_path: Option<&str>) -> Result<Vec<ExpensiveSession>, String> {
+    let folders: Vec<(String, PathBuf)> = match project_path {
+        Some(project_path) => {
+            let folder = get_project_folder(project_path)
+                .ok_or_else(|| format!("Project folder not found for: {}", project_path))?;
+            vec![(project_path.to_string(), folder)]
+        }
+        None => all_session_folders()?,
+    };
+
+    let metadata = super::metadata::load();
+    let mut sessions = Vec::new();
+    for (project, folder) in folders {
+        let project_name = PathBuf::from(&project)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.clone());
+        let Ok(entries) = std::fs::read_dir(&folder) else {
+            continue;
+        };
+        for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            if !path.is_file() || path.extension().is_none_or(|e| e != "jsonl") {
+                continue;
+            }
+            let Some(session_id) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let Ok(mut info) = parse_session_info(&path, session_id) else {
+                continue;
+            };
+            if info.message_count == 0 {
+                continue;
+            }
+            info.title = session_title(&metadata, &project, session_id);
+            sessions.push(ExpensiveSession {
+                project_path: project.clone(),
+                project_name: project_name.clone(),
+                session: info,
+            });
+        }
+    }
+    Ok(sessions)
+}
+
+/// Get the costliest sessions of one project (or all projects) within a time range
+pub fn list_expensive_sessions(
+    project_path: Option<&str>,
+    limit: usize,
+    range: &str,
+) -> Result<Vec<ExpensiveSession>, String> {
+    let since = range_start(range)?;
+
+    // (project path, session folder) pairs to scan
+    let folders: Vec<(String, PathBuf)> = match project_path {
+        Some(project_path) => {
+            let folder = get_project_folder(project_path)
+                .ok_or_else(|| format!("Project folder not found for: {}", project_path))?;
+            vec![(project_path.to_string(), folder)]
+        }
+        None => all_session_folders()?,
+    };
+
+    let metadata = super::metadata::load();
+    let mut sessions: Vec<ExpensiveSession> = Vec::new();
+
+    for (project, folder) in folders {
+        let project_name = PathBuf::from(&project)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.clone());
+
+        let entries = match std::fs::read_dir(&folder) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+
+        for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            if !path.is_file() || path.extension().map(|e| e != "jsonl").unwrap_or(true) {
+                continue;
+            }
+
+            // Files untouched since the start of the range can't have activity in it
+            if let Some(since) = since {
+                let modified = path
+                    .metadata()
+                    .and_then(|m| m.modified())
+                    .map(DateTime::<Utc>::from);
+                if modified.map(|m| m < since).unwrap_or(false) {
+                    continue;
+                }
+            }
+
+            let Some(session_id) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            // Only what happened in the range counts towards the session's cost
+            let Ok(mut info) = parse_session_info_since(&path, session_id, since) else {
+                continue;
+            };
+            info.title = session_title(&metadata, &project, session_id);
+
+            if info.total_cost > 0.0 {
+                sessions.push(ExpensiveSession {
+                    project_path: project.clone(),
+                    project_name: project_name.clone(),
+                    session: info,
+                });
+            }
+        }
+    }
+
+    sessions.sort_by(|a, b| b.session.total_cost.total_cmp(&a.session.total_cost));
+    sessions.truncate(limit);
+
+    Ok(sessions)
+}
+
 /// Parse session info from a JSONL file
 fn parse_session_info(path: &PathBuf, session_id: &str) -> Result<SessionInfo, String> {
+    parse_session_info_since(path, session_id, None)
+}
+
+/// Parse session info, counting only messages and usage from `since` on. The first and
+/// last message times still cover the whole session.
+fn parse_session_info_since(
+    path: &PathBuf,
+    session_id: &str,
+    since: Option<DateTime<Utc>>,
+) -> Result<SessionInfo, String> {
     let file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
     let reader = BufReader::new(file);
 
@@ -78,7 +315,9 @@ fn parse_session_info(path: &PathBuf, session_id: &str) -> Result<SessionInfo, S
     let mut total_output_tokens: u64 = 0;
     let mut total_cache_creation_tokens: u64 = 0;
     let mut total_cache_read_tokens: u64 = 0;
+    let mut total_cost = 0.0;
     let mut model: Option<String> = None;
+    let mut by_model: HashMap<String, ModelStats> = HashMap::new();
 
     for line in reader.lines() {
         let line = match line {
@@ -96,6 +335,11 @@ fn parse_session_info(path: &PathBuf, session_id: &str) -> Result<SessionInfo, S
         };
 
         let entry_type = entry.get("type").and_then(|t| t.as_str());
+        let timestamp = entry
+            .get("timestamp")
+            .and_then(|t| t.as_str())
+            .and_then(|ts| ts.parse::<DateTime<Utc>>().ok());
+        let counted = since.is_none_or(|since| timestamp.is_some_and(|t| t >= since));
 
         match entry_type {
             Some("summary") => {
@@ -104,7 +348,9 @@ fn parse_session_info(path: &PathBuf, session_id: &str) -> Result<SessionInfo, S
                 }
             }
             Some("user") => {
-                message_count += 1;
+                if counted {
+                    message_count += 1;
+                }
 
                 if let Some(ts) = entry.get("timestamp").and_then(|t| t.as_str()) {
                     if first_message_at.is_none() {
@@ -144,7 +390,9 @@ fn parse_session_info(path: &PathBuf, session_id: &str) -> Result<SessionInfo, S
                 }
             }
             Some("assistant") => {
-                message_count += 1;
+                if counted {
+                    message_count += 1;
+                }
 
                 if let Some(ts) = entry.get("timestamp").and_then(|t| t.as_str()) {
                     if first_message_at.is_none() {
@@ -158,24 +406,38 @@ fn parse_session_info(path: &PathBuf, session_id: &str) -> Result<SessionInfo, S
                         model = Some(m.to_string());
                     }
 
-                    if let Some(usage) = msg.get("usage") {
-                        if let Some(input) = usage.get("input_tokens").and_then(|t| t.as_u64()) {
-                            total_input_tokens += input;
-                        }
-                        if let Some(output) = usage.get("output_tokens").and_then(|t| t.as_u64()) {
-                            total_output_tokens += output;
-                        }
-                        if let Some(cache_creation) = usage
-                            .get("cache_creation_input_tokens")
-                            .and_then(|t| t.as_u64())
-                        {
-                            total_cache_creation_tokens += cache_creation;
-                        }
-                        if let Some(cache_read) = usage
-                            .get("cache_read_input_tokens")
-                            .and_then(|t| t.as_u64())
-                        {
-                            total_cache_read_tokens += cache_read;
+                    if let Some(usage) = msg.get("usage").filter(|_| counted) {
+                        let tokens = |key: &str| usage.get(key).and_then(|t| t.as_u64()).unwrap_or(0);
+                        let input = tokens("input_tokens");
+                        let output = tokens("output_tokens");
+                        let cache_creation = tokens("cache_creation_input_tokens");
+                        let cache_read = tokens("cache_read_input_tokens");
+
+                        total_input_tokens += input;
+                        total_output_tokens += output;
+                        total_cache_creation_tokens += cache_creation;
+                        total_cache_read_tokens += cache_read;
+
+                        // Priced per response so each uses the prices valid at its timestamp
+                        if let Some(m) = model.as_deref() {
+                            let cost = pricing::calculate_cost(
+                                m,
+                                timestamp,
+                                usage.get("service_tier").and_then(|t| t.as_str()),
+                                input,
+                                output,
+                                cache_creation,
+                                cache_read,
+                            );
+                            total_cost += cost;
+
+                            let stats = by_model.entry(m.to_string()).or_default();
+                            stats.input_tokens += input;
+                            stats.output_tokens += output;
+                            stats.cache_creation_tokens += cache_creation;
+                            stats.cache_read_tokens += cache_read;
+                            stats.cost += cost;
+                            stats.message_count += 1;
                         }
                     }
                 }
@@ -184,21 +446,15 @@ fn parse_session_info(path: &PathBuf, session_id: &str) -> Result<SessionInfo, S
         }
     }
 
-    let total_cost = model
-        .as_deref()
-        .map(|m| {
-            pricing::calculate_cost(
-                m,
-                total_input_tokens,
-                total_output_tokens,
-                total_cache_creation_tokens,
-                total_cache_read_tokens,
-            )
-        })
-        .unwrap_or(0.0);
+    let mut model_breakdown: Vec<SessionModelBreakdown> = by_model
+        .into_iter()
+        .map(|(model, stats)| SessionModelBreakdown { model, stats })
+        .collect();
+    model_breakdown.sort_by(|a, b| b.stats.cost.total_cmp(&a.stats.cost));
 
     Ok(SessionInfo {
         session_id: session_id.to_string(),
+        title: None,
         summary,
         first_user_message,
         first_message_at,
@@ -210,11 +466,25 @@ fn parse_session_info(path: &PathBuf, session_id: &str) -> Result<SessionInfo, S
         total_cache_read_tokens,
         total_cost,
         model,
+        model_breakdown,
     })
 }
 
-/// Get the full conversation for a session
-pub fn get_session_conversation(project_path: &str, session_id: &str) -> Result<SessionConversation, String> {
+/// Check that a session ID is only letters, digits, `-` and `_`, so it is safe
+/// in file names and shell commands
+pub fn check_session_id(session_id: &str) -> Result<(), String> {
+    if session_id.is_empty()
+        || !session_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!("Not a session ID: {}", session_id));
+    }
+    Ok(())
+}
+
+/// Path of a session's JSONL file
+pub fn session_file(project_path: &str, session_id: &str) -> Result<PathBuf, String> {
     let folder = get_project_folder(project_path)
         .ok_or_else(|| format!("Project folder not found for: {}", project_path))?;
 
@@ -222,156 +492,640 @@ pub fn get_session_conversation(project_path: &str, session_id: &str) -> Result<
     if !file_path.exists() {
         return Err(format!("Session file not found: {}", session_id));
     }
+    Ok(file_path)
+}
 
-    let file = File::open(&file_path).map_err(|e| format!("Failed to open file: {}", e))?;
-    let reader = BufReader::new(file);
-
-    let mut summary: Option<String> = None;
-    let mut messages: Vec<ConversationMessage> = Vec::new();
-
+/// Builds a conversation one JSONL line at a time, so a session file can be
+/// read in full or followed as lines are appended. Sidechain entries (the
+/// work of subagents) go into `subagents` instead of the main thread.
+pub struct ConversationBuilder {
+    pub summary: Option<String>,
+    pub messages: Vec<ConversationMessage>,
+    catalog: Vec<model_catalog::ModelInfo>,
     // Track message UUIDs to deduplicate (assistant messages come in multiple chunks)
-    let mut seen_uuids: HashMap<String, usize> = HashMap::new();
+    seen_uuids: HashMap<String, usize>,
+    /// Subagent threads in the order they started, each with its own uuid index
+    subagents: Vec<(SubagentThread, HashMap<String, usize>)>,
+    /// Thread of each sidechain entry by its uuid, to follow `parentUuid` chains
+    sidechain_uuids: HashMap<String, usize>,
+    /// Thread by agent id
+    agent_threads: HashMap<String, usize>,
+    /// `Task` calls by the agent id their result names
+    agent_calls: HashMap<String, String>,
+    /// Reading a subagent transcript itself: its sidechain is the main thread
+    keep_sidechain: bool,
+}
 
-    for line in reader.lines() {
-        let line = match line {
-            Ok(l) => l,
-            Err(_) => continue,
-        };
+impl ConversationBuilder {
+    pub fn new() -> Self {
+        Self {
+            summary: None,
+            messages: Vec::new(),
+            catalog: model_catalog::catalog(),
+            seen_uuids: HashMap::new(),
+            subagents: Vec::new(),
+            sidechain_uuids: HashMap::new(),
+            agent_threads: HashMap::new(),
+            agent_calls: HashMap::new(),
+            keep_sidechain: false,
+        }
+    }
 
+    /// A builder for the session file at `path`, which may be a subagent transcript
+    pub fn for_file(path: &std::path::Path) -> Self {
+        let keep_sidechain = path.file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with("agent-"));
+        Self { keep_sidechain, ..Self::new() }
+    }
+
+    /// Add a line of the session file (or of a subagent transcript). Returns
+    /// the index of the main-thread message it added or updated.
+    pub fn push_line(&mut self, line: &str) -> Option<usize> {
         if line.trim().is_empty() {
-            continue;
+            return None;
         }
 
-        let entry: serde_json::Value = match serde_json::from_str(&line) {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
-
+        let entry: serde_json::Value = serde_json::from_str(line).ok()?;
         let entry_type = entry.get("type").and_then(|t| t.as_str());
 
-        match entry_type {
-            Some("summary") => {
-                if let Some(s) = entry.get("summary").and_then(|s| s.as_str()) {
-                    summary = Some(s.to_string());
-                }
+        if entry_type == Some("summary") {
+            if let Some(s) = entry.get("summary").and_then(|s| s.as_str()) {
+                self.summary = Some(s.to_string());
             }
-            Some("user") => {
-                let uuid = entry.get("uuid")
-                    .and_then(|u| u.as_str())
-                    .unwrap_or("")
-                    .to_string();
-
-                let timestamp = entry.get("timestamp")
-                    .and_then(|t| t.as_str())
-                    .map(|s| s.to_string());
-
-                let content = if let Some(msg) = entry.get("message") {
-                    if let Some(content_str) = msg.get("content").and_then(|c| c.as_str()) {
-                        vec![MessageContentBlock::Text { text: content_str.to_string() }]
-                    } else if let Some(content_arr) = msg.get("content").and_then(|c| c.as_array()) {
-                        parse_content_blocks(content_arr)
-                    } else {
-                        vec![]
-                    }
-                } else {
-                    vec![]
-                };
+            return None;
+        }
 
-                if !uuid.is_empty() && !content.is_empty() {
-                    messages.push(ConversationMessage {
-                        uuid,
-                        role: "user".to_string(),
-                        timestamp,
-                        content,
-                        model: None,
-                        input_tokens: None,
-                        output_tokens: None,
-                    });
-                }
+        let message = parse_message(&entry, &self.catalog)?;
+        if !self.keep_sidechain && entry.get("isSidechain").and_then(|s| s.as_bool()).unwrap_or(false) {
+            let thread = self.sidechain_thread(&entry);
+            let (subagent, seen_uuids) = &mut self.subagents[thread];
+            add_message(&mut subagent.messages, seen_uuids, message, &self.catalog);
+            return None;
+        }
+
+        // The result of a `Task` call names the agent whose transcript holds its work
+        if let Some(agent_id) = entry
+            .get("toolUseResult")
+            .and_then(|r| r.get("agentId"))
+            .and_then(|a| a.as_str())
+        {
+            let call = message.content.iter().find_map(|block| match block {
+                MessageContentBlock::ToolResult { tool_use_id: Some(id), .. } => Some(id.clone()),
+                _ => None,
+            });
+            if let Some(call) = call {
+                self.agent_calls.insert(agent_id.to_string(), call);
             }
-            Some("assistant") => {
-                let uuid = entry.get("uuid")
-                    .and_then(|u| u.as_str())
-                    .unwrap_or("")
-                    .to_string();
+        }
+        add_message(&mut self.messages, &mut self.seen_uuids, message, &self.catalog)
+    }
 
-                let timestamp = entry.get("timestamp")
-                    .and_then(|t| t.as_str())
-                    .map(|s| s.to_string());
+    /// The thread a sidechain entry belongs to: its agent's, its parent's,
+    /// or a new one
+    fn sidechain_thread(&mut self, entry: &serde_json::Value) -> usize {
+        let agent_id = entry.get("agentId").and_then(|a| a.as_str());
+        let parent = entry.get("parentUuid").and_then(|p| p.as_str());
+        let thread = agent_id
+            .and_then(|id| self.agent_threads.get(id))
+            .or_else(|| parent.and_then(|uuid| self.sidechain_uuids.get(uuid)))
+            .copied()
+            .unwrap_or_else(|| {
+                self.subagents.push((
+                    SubagentThread {
+                        agent_id: agent_id.map(|id| id.to_string()),
+                        tool_use_id: None,
+                        description: None,
+                        subagent_type: None,
+                        messages: Vec::new(),
+                    },
+                    HashMap::new(),
+                ));
+                self.subagents.len() - 1
+            });
+        if let Some(id) = agent_id {
+            self.agent_threads.entry(id.to_string()).or_insert(thread);
+        }
+        if let Some(uuid) = entry.get("uuid").and_then(|u| u.as_str()) {
+            self.sidechain_uuids.insert(uuid.to_string(), thread);
+        }
+        thread
+    }
 
-                let msg = entry.get("message");
+    /// The subagent threads, each linked to the `Task` call that started it:
+    /// by the agent id in the call's result, or else by its prompt
+    pub fn subagent_threads(&self) -> Vec<SubagentThread> {
+        // (call id, input) of every Task call in the main thread
+        let mut calls: Vec<(&str, &serde_json::Value)> = self.messages.iter()
+            .flat_map(|m| &m.content)
+            .filter_map(|block| match block {
+                MessageContentBlock::ToolUse { id: Some(id), name: Some(name), input: Some(input) }
+                    if name == "Task" || name == "Agent" => Some((id.as_str(), input)),
+                _ => None,
+            })
+            .collect();
+
+        let mut threads: Vec<SubagentThread> = self.subagents.iter().map(|(thread, _)| thread.clone()).collect();
+        for thread in &mut threads {
+            let by_agent = thread.agent_id.as_ref()
+                .and_then(|id| self.agent_calls.get(id))
+                .and_then(|call| calls.iter().position(|(id, _)| id == call));
+            let by_prompt = || {
+                let prompt = thread.messages.first().and_then(|m| m.content.first()).and_then(|block| match block {
+                    MessageContentBlock::Text { text } => Some(text.trim()),
+                    _ => None,
+                })?;
+                calls.iter().position(|(_, input)| {
+                    input.get("prompt").and_then(|p| p.as_str()).map(str::trim) == Some(prompt)
+                })
+            };
+            let Some(idx) = by_agent.or_else(by_prompt) else {
+                continue;
+            };
+            let (id, input) = calls.remove(idx);
+            let field = |key: &str| input.get(key).and_then(|v| v.as_str()).map(|v| v.to_string());
+            thread.tool_use_id = Some(id.to_string());
+            thread.description = field("description");
+            thread.subagent_type = field("subagent_type");
+        }
+        threads
+    }
 
-                let model = msg.and_then(|m| m.get("model"))
-                    .and_then(|m| m.as_str())
-                    .map(|s| s.to_string());
+    pub fn finish(self, session_id: &str) -> SessionConversation {
+        let subagents = self.subagent_threads();
+        SessionConversation {
+            session_id: session_id.to_string(),
+            summary: self.summary,
+            total_messages: self.messages.len(),
+            offset: 0,
+            messages: self.messages,
+            subagents,
+        }
+    }
+}
 
-                let (input_tokens, output_tokens) = if let Some(usage) = msg.and_then(|m| m.get("usage")) {
-                    (
-                        usage.get("input_tokens").and_then(|t| t.as_u64()),
-                        usage.get("output_tokens").and_then(|t| t.as_u64()),
-                    )
+/// A user or assistant entry as a message; assistant chunks may come
+/// without content, to be merged into an earlier chunk
+fn parse_message(entry: &serde_json::Value, catalog: &[model_catalog::ModelInfo]) -> Option<ConversationMessage> {
+    let role = entry.get("type").and_then(|t| t.as_str())?;
+    let uuid = entry.get("uuid")
+        .and_then(|u| u.as_str())
+        .unwrap_or("")
+        .to_string();
+
+    let timestamp = entry.get("timestamp")
+        .and_then(|t| t.as_str())
+        .map(|s| s.to_string());
+
+    match role {
+        "user" => {
+            let content = if let Some(msg) = entry.get("message") {
+                if let Some(content_str) = msg.get("content").and_then(|c| c.as_str()) {
+                    vec![MessageContentBlock::Text { text: content_str.to_string() }]
+                } else if let Some(content_arr) = msg.get("content").and_then(|c| c.as_array()) {
+                    parse_content_blocks(content_arr)
                 } else {
-                    (None, None)
-                };
+                    vec![]
+                }
+            } else {
+                vec![]
+            };
 
-                let content = if let Some(m) = msg {
-                    if let Some(content_arr) = m.get("content").and_then(|c| c.as_array()) {
-                        parse_content_blocks(content_arr)
-                    } else {
-                        vec![]
-                    }
+            if uuid.is_empty() || content.is_empty() {
+                return None;
+            }
+            Some(ConversationMessage {
+                uuid,
+                role: "user".to_string(),
+                timestamp,
+                content,
+                model: None,
+                input_tokens: None,
+                output_tokens: None,
+                cost: None,
+                context_window: None,
+            })
+        }
+        "assistant" => {
+            let msg = entry.get("message");
+
+            let model = msg.and_then(|m| m.get("model"))
+                .and_then(|m| m.as_str())
+                .map(|s| s.to_string());
+
+            let (input_tokens, output_tokens) = if let Some(usage) = msg.and_then(|m| m.get("usage")) {
+                (
+                    usage.get("input_tokens").and_then(|t| t.as_u64()),
+                    usage.get("output_tokens").and_then(|t| t.as_u64()),
+                )
+            } else {
+                (None, None)
+            };
+
+            let cost = msg.and_then(|m| m.get("usage")).map(|usage| {
+                let tokens = |key: &str| usage.get(key).and_then(|t| t.as_u64()).unwrap_or(0);
+                pricing::cost_breakdown(
+                    model.as_deref().unwrap_or_default(),
+                    timestamp.as_deref().and_then(|ts| ts.parse::<DateTime<Utc>>().ok()),
+                    usage.get("service_tier").and_then(|t| t.as_str()),
+                    tokens("input_tokens"),
+                    tokens("output_tokens"),
+                    tokens("cache_creation_input_tokens"),
+                    tokens("cache_read_input_tokens"),
+                )
+            });
+
+            let content = if let Some(m) = msg {
+                if let Some(content_arr) = m.get("content").and_then(|c| c.as_array()) {
+                    parse_content_blocks(content_arr)
                 } else {
                     vec![]
-                };
-
-                if uuid.is_empty() {
-                    continue;
                 }
+            } else {
+                vec![]
+            };
 
-                // Check if we've seen this UUID before (assistant messages can have multiple chunks)
-                if let Some(&idx) = seen_uuids.get(&uuid) {
-                    // Merge content blocks into existing message
-                    if let Some(existing) = messages.get_mut(idx) {
-                        for block in content {
-                            // Only add if not already present
-                            if !existing.content.iter().any(|b| blocks_equal(b, &block)) {
-                                existing.content.push(block);
-                            }
-                        }
-                        // Update tokens if present
-                        if input_tokens.is_some() {
-                            existing.input_tokens = input_tokens;
-                        }
-                        if output_tokens.is_some() {
-                            existing.output_tokens = output_tokens;
-                        }
+            if uuid.is_empty() {
+                return None;
+            }
+            let context_window = model.as_deref().zip(cost.as_ref()).map(|(model, cost)| {
+                model_catalog::find(catalog, model).window_for(cost.context_tokens)
+            });
+            Some(ConversationMessage {
+                uuid,
+                role: "assistant".to_string(),
+                timestamp,
+                content,
+                model,
+                input_tokens,
+                output_tokens,
+                cost,
+                context_window,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Add a message to a thread, merging an assistant chunk into the earlier
+/// chunks of the same message. Returns the index of the message.
+fn add_message(
+    messages: &mut Vec<ConversationMessage>,
+    seen_uuids: &mut HashMap<String, usize>,
+    message: ConversationMessage,
+    catalog: &[model_catalog::ModelInfo],
+) -> Option<usize> {
+    if message.role == "user" {
+        messages.push(message);
+        return Some(messages.len() - 1);
+    }
+
+    // Check if we've seen this UUID before (assistant messages can have multiple chunks)
+    if let Some(&idx) = seen_uuids.get(&message.uuid) {
+        // Merge content blocks into existing message
+        let existing = messages.get_mut(idx)?;
+        for block in message.content {
+            // Only add if not already present
+            if !existing.content.iter().any(|b| blocks_equal(b, &block)) {
+                existing.content.push(block);
+            }
+        }
+        // Update tokens if present
+        if message.input_tokens.is_some() {
+            existing.input_tokens = message.input_tokens;
+        }
+        if message.output_tokens.is_some() {
+            existing.output_tokens = message.output_tokens;
+        }
+        if let Some(cost) = message.cost {
+            if let Some(model) = existing.model.as_deref() {
+                existing.context_window =
+                    Some(model_catalog::find(catalog, model).window_for(cost.context_tokens));
+            }
+            existing.cost = Some(cost);
+        }
+        Some(idx)
+    } else if !message.content.is_empty() {
+        let idx = messages.len();
+        seen_uuids.insert(message.uuid.clone(), idx);
+        messages.push(message);
+        Some(idx)
+    } else {
+        None
+    }
+}
+
+/// Replace each tool call that has a result with a `ToolInvocation` holding
+/// both, and drop the result blocks (and the user messages left empty)
+fn pair_tool_results(messages: &mut Vec<ConversationMessage>) {
+    let parse_time = |ts: &Option<String>| ts.as_deref().and_then(|ts| ts.parse::<DateTime<Utc>>().ok());
+
+    // Tool call id -> (message, block) of the call
+    let mut calls: HashMap<String, (usize, usize)> = HashMap::new();
+    let mut paired: Vec<(usize, usize)> = Vec::new();
+    for msg_idx in 0..messages.len() {
+        for block_idx in 0..messages[msg_idx].content.len() {
+            match &messages[msg_idx].content[block_idx] {
+                MessageContentBlock::ToolUse { id: Some(id), .. } => {
+                    calls.insert(id.clone(), (msg_idx, block_idx));
+                }
+                MessageContentBlock::ToolResult { tool_use_id: Some(id), content } => {
+                    let Some((call_msg, call_block)) = calls.remove(id) else {
+                        continue;
+                    };
+                    let result = content.clone();
+                    let duration_ms = parse_time(&messages[call_msg].timestamp)
+                        .zip(parse_time(&messages[msg_idx].timestamp))
+                        .map(|(start, end)| (end - start).num_milliseconds().max(0) as u64);
+                    let call = &mut messages[call_msg].content[call_block];
+                    if let MessageContentBlock::ToolUse { id, name, input } = call {
+                        *call = MessageContentBlock::ToolInvocation {
+                            id: id.take(),
+                            name: name.take(),
+                            input: input.take(),
+                            result,
+                            duration_ms,
+                        };
                     }
-                } else if !content.is_empty() {
-                    let idx = messages.len();
-                    seen_uuids.insert(uuid.clone(), idx);
-                    messages.push(ConversationMessage {
-                        uuid,
-                        role: "assistant".to_string(),
-                        timestamp,
-                        content,
-                        model,
-                        input_tokens,
-                        output_tokens,
-                    });
+                    paired.push((msg_idx, block_idx));
                 }
+                _ => {}
             }
-            _ => {}
         }
     }
 
+    for (msg_idx, block_idx) in paired.into_iter().rev() {
+        messages[msg_idx].content.remove(block_idx);
+    }
+    messages.retain(|m| !m.content.is_empty());
+}
+
+impl Default for ConversationBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A session file as last parsed
+struct ParsedSession {
+    path: PathBuf,
+    len: u64,
+    modified: Option<SystemTime>,
+    conversation: Arc<SessionConversation>,
+}
+
+/// The last session read, reused while its file is unchanged so paging
+/// through a long session doesn't parse it again for every page
+static LAST_PARSED: Mutex<Option<ParsedSession>> = Mutex::new(None);
+
+fn read_conversation(project_path: &str, session_id: &str) -> Result<Arc<SessionConversation>, String> {
+    let file_path = session_file(project_path, session_id)?;
+    let metadata = file_path.metadata().map_err(|e| format!("Failed to open file: {}", e))?;
+    let (len, modified) = (metadata.len(), metadata.modified().ok());
+    if let Some(parsed) = LAST_PARSED.lock().unwrap().as_ref() {
+        if parsed.path == file_path && parsed.len == len && parsed.modified == modified {
+            return Ok(parsed.conversation.clone());
+        }
+    }
+
+    let file = File::open(&file_path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let reader = BufReader::new(file);
+
+    let mut builder = ConversationBuilder::for_file(&file_path);
+    for line in reader.lines() {
+        let line = match line {
+            Ok(l) => l,
+            Err(_) => continue,
+        };
+        builder.push_line(&line);
+    }
+    for transcript in subagent_transcripts(&file_path, session_id) {
+        let Ok(file) = File::open(&transcript) else {
+            continue;
+        };
+        for line in BufReader::new(file).lines().map_while(Result::ok) {
+            builder.push_line(&line);
+        }
+    }
+
+    let mut conversation = builder.finish(session_id);
+    pair_tool_results(&mut conversation.messages);
+    for thread in &mut conversation.subagents {
+        pair_tool_results(&mut thread.messages);
+    }
+    conversation.total_messages = conversation.messages.len();
+    let conversation = Arc::new(conversation);
+    *LAST_PARSED.lock().unwrap() = Some(ParsedSession {
+        path: file_path,
+        len,
+        modified,
+        conversation: conversation.clone(),
+    });
+    Ok(conversation)
+}
+
+/// Transcripts of a session's subagents: `<session id>/subagents/*.jsonl`
+/// next to the session file, or `agent-*.jsonl` files in the project folder
+/// whose entries name the session
+fn subagent_transcripts(session_file: &std::path::Path, session_id: &str) -> Vec<PathBuf> {
+    let Some(folder) = session_file.parent() else {
+        return Vec::new();
+    };
+    let jsonl_files = |dir: &std::path::Path| -> Vec<PathBuf> {
+        std::fs::read_dir(dir)
+            .map(|entries| {
+                entries.filter_map(|e| e.ok())
+                    .map(|e| e.path())
+                    .filter(|p| p.is_file() && p.extension().is_some_and(|e| e == "jsonl"))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let mut transcripts = jsonl_files(&folder.join(session_id).join("subagents"));
+    transcripts.extend(jsonl_files(folder).into_iter().filter(|path| {
+        let is_agent = path.file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with("agent-"));
+        // The session id is on every entry; the first one that has it decides
+        is_agent && File::open(path)
+            .map(|file| {
+                BufReader::new(file).lines().map_while(Result::ok).take(10)
+                    .filter_map(|line| serde_json::from_str::<serde_json::Value>(&line).ok())
+                    .find_map(|entry| entry.get("sessionId").and_then(|s| s.as_str()).map(|s| s == session_id))
+                    .unwrap_or(false)
+            })
+            .unwrap_or(false)
+    }));
+    transcripts.sort();
+    transcripts
+}
+
+/// Get the full conversation for a session
+pub fn get_session_conversation(project_path: &str, session_id: &str) -> Result<SessionConversation, String> {
+    read_conversation(project_path, session_id).map(|conversation| (*conversation).clone())
+}
+
+/// One page of a session: up to `limit` messages (all when None) starting at
+/// `offset`, or right after the message `after_uuid` when given
+pub fn get_session_page(
+    project_path: &str,
+    session_id: &str,
+    offset: usize,
+    limit: Option<usize>,
+    after_uuid: Option<&str>,
+) -> Result<SessionConversation, String> {
+    let conversation = read_conversation(project_path, session_id)?;
+    let total = conversation.messages.len();
+    let start = match after_uuid {
+        Some(uuid) => conversation
+            .messages
+            .iter()
+            .position(|m| m.uuid == uuid)
+            .map(|idx| idx + 1)
+            .ok_or_else(|| format!("Message not found: {}", uuid))?,
+        None => offset.min(total),
+    };
+    let end = limit.map_or(total, |limit| start.saturating_add(limit).min(total));
+    let messages = conversation.messages[start..end].to_vec();
+
+    // Subagents started on this page; unmatched ones come with the first page
+    let started = |id: &str| messages.iter().flat_map(|m| &m.content).any(|block| {
+        matches!(block, MessageContentBlock::ToolUse { id: Some(call), .. }
+            | MessageContentBlock::ToolInvocation { id: Some(call), .. } if call == id)
+    });
+    let subagents = conversation.subagents.iter()
+        .filter(|thread| match thread.tool_use_id.as_deref() {
+            Some(id) => started(id),
+            None => start == 0,
+        })
+        .cloned()
+        .collect();
+
     Ok(SessionConversation {
-        session_id: session_id.to_string(),
-        summary,
+        session_id: conversation.session_id.clone(),
+        summary: conversation.summary.clone(),
         messages,
+        total_messages: total,
+        offset: start,
+        subagents,
     })
 }
 
+/// Number of messages in a session, to size a virtualized list before paging
+pub fn get_session_message_count(project_path: &str, session_id: &str) -> Result<usize, String> {
+    Ok(read_conversation(project_path, session_id)?.messages.len())
+}
+
 /// Parse content blocks from JSON array
+/// Longest pause between two messages during playback
+const MAX_REPLAY_GAP_MS: u64 = 5_000;
+
+/// Split text into chunks of roughly `size` characters, breaking at whitespace
+fn chunk_text(text: &str, size: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_inclusive(char::is_whitespace) {
+        if !current.is_empty() && current.chars().count() + word.chars().count() > size {
+            chunks.push(std::mem::take(&mut current));
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Get a session as a replay timeline: messages with relative timestamps (idle gaps
+/// capped so playback doesn't stall) and text split into `chunk_chars`-sized pieces
+pub fn get_session_replay(
+    project_path: &str,
+    session_id: &str,
+    chunk_chars: usize,
+) -> Result<SessionReplay, String> {
+    let conversation = get_session_conversation(project_path, session_id)?;
+    let chunk_chars = chunk_chars.max(1);
+
+    let parse_time = |ts: &Option<String>| {
+        ts.as_deref()
+            .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+            .map(|dt| dt.with_timezone(&Utc))
+    };
+    let start = conversation.messages.iter().find_map(|m| parse_time(&m.timestamp));
+
+    let mut messages = Vec::new();
+    let mut offset_ms: u64 = 0;
+    let mut previous_real_ms: u64 = 0;
+
+    for message in &conversation.messages {
+        // Messages without a timestamp keep the previous one's position
+        let real_offset_ms = match (start, parse_time(&message.timestamp)) {
+            (Some(start), Some(time)) => (time - start).num_milliseconds().max(0) as u64,
+            _ => previous_real_ms,
+        };
+        offset_ms += real_offset_ms
+            .saturating_sub(previous_real_ms)
+            .min(MAX_REPLAY_GAP_MS);
+        previous_real_ms = previous_real_ms.max(real_offset_ms);
+
+        let chunks: Vec<ReplayChunk> = message
+            .content
+            .iter()
+            .flat_map(|block| {
+                let call = |name: &Option<String>, input: &Option<serde_json::Value>| {
+                    format!(
+                        "{}{}",
+                        name.as_deref().unwrap_or("tool"),
+                        input.as_ref().map(|i| format!(" {}", i)).unwrap_or_default()
+                    )
+                };
+                let result = |content: &Option<serde_json::Value>| match content {
+                    Some(serde_json::Value::String(text)) => text.clone(),
+                    Some(other) => other.to_string(),
+                    None => String::new(),
+                };
+                let parts = match block {
+                    MessageContentBlock::Text { text } => vec![("text", text.clone())],
+                    MessageContentBlock::Thinking { thinking } => vec![("thinking", thinking.clone())],
+                    MessageContentBlock::ToolUse { name, input, .. } => vec![("tool_use", call(name, input))],
+                    MessageContentBlock::ToolResult { content, .. } => vec![("tool_result", result(content))],
+                    MessageContentBlock::ToolInvocation { name, input, result: content, .. } => {
+                        vec![("tool_use", call(name, input)), ("tool_result", result(content))]
+                    }
+                    MessageContentBlock::Other => Vec::new(),
+                };
+                parts
+                    .into_iter()
+                    .flat_map(|(kind, text)| {
+                        chunk_text(&text, chunk_chars)
+                            .into_iter()
+                            .map(move |text| ReplayChunk { kind: kind.to_string(), text })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        messages.push(ReplayMessage {
+            uuid: message.uuid.clone(),
+            role: message.role.clone(),
+            model: message.model.clone(),
+            offset_ms,
+            real_offset_ms,
+            chunks,
+        });
+    }
+
+    Ok(SessionReplay {
+        session_id: conversation.session_id,
+        summary: conversation.summary,
+        duration_ms: offset_ms,
+        real_duration_ms: previous_real_ms,
+        messages,
+    })
+}
+
 fn parse_content_blocks(arr: &[serde_json::Value]) -> Vec<MessageContentBlock> {
     arr.iter().filter_map(|block| {
         let block_type = block.get("type").and_then(|t| t.as_str())?;
@@ -408,125 +1162,11 @@ fn blocks_equal(a: &MessageContentBlock, b: &MessageContentBlock) -> bool {
         (MessageContentBlock::Thinking { thinking: a }, MessageContentBlock::Thinking { thinking: b }) => a == b,
         (MessageContentBlock::ToolUse { id: a_id, .. }, MessageContentBlock::ToolUse { id: b_id, .. }) => a_id == b_id,
         (MessageContentBlock::ToolResult { tool_use_id: a_id, .. }, MessageContentBlock::ToolResult { tool_use_id: b_id, .. }) => a_id == b_id,
+        (MessageContentBlock::ToolInvocation { id: a_id, .. }, MessageContentBlock::ToolInvocation { id: b_id, .. }) => a_id == b_id,
         _ => false,
     }
 }
 
-/// Export a session to HTML format
-pub fn export_session_html(project_path: &str, session_id: &str) -> Result<String, String> {
-    let conversation = get_session_conversation(project_path, session_id)?;
-
-    let mut html = String::new();
-    html.push_str(r#"<!DOCTYPE html>
-<html lang="en">
-<head>
-    <meta charset="UTF-8">
-    <meta name="viewport" content="width=device-width, initial-scale=1.0">
-    <title>Claude Code Session - "#);
-    html.push_str(&conversation.summary.clone().unwrap_or_else(|| session_id.to_string()));
-    html.push_str(r#"</title>
-    <style>
-        * { box-sizing: border-box; margin: 0; padding: 0; }
-        body { font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; background: #18181b; color: #fafafa; line-height: 1.6; padding: 2rem; }
-        .container { max-width: 900px; margin: 0 auto; }
-        h1 { font-size: 1.5rem; margin-bottom: 0.5rem; color: #a1a1aa; }
-        .summary { font-size: 1.1rem; color: #71717a; margin-bottom: 2rem; padding-bottom: 1rem; border-bottom: 1px solid #27272a; }
-        .message { margin-bottom: 1.5rem; padding: 1rem; border-radius: 0.5rem; }
-        .user { background: #27272a; border-left: 3px solid #3b82f6; }
-        .assistant { background: #1f1f23; border-left: 3px solid #10b981; }
-        .role { font-size: 0.75rem; font-weight: 600; text-transform: uppercase; margin-bottom: 0.5rem; }
-        .user .role { color: #3b82f6; }
-        .assistant .role { color: #10b981; }
-        .content { white-space: pre-wrap; }
-        .thinking { background: #292524; padding: 0.75rem; border-radius: 0.25rem; margin: 0.5rem 0; font-size: 0.9rem; color: #a8a29e; border-left: 2px solid #78716c; }
-        .thinking-label { font-size: 0.7rem; color: #78716c; margin-bottom: 0.25rem; }
-        .tool-use { background: #1e1b4b; padding: 0.75rem; border-radius: 0.25rem; margin: 0.5rem 0; font-size: 0.85rem; border-left: 2px solid #6366f1; }
-        .tool-name { color: #818cf8; font-weight: 600; }
-        .timestamp { font-size: 0.7rem; color: #52525b; float: right; }
-        .tokens { font-size: 0.7rem; color: #52525b; margin-top: 0.5rem; }
-        code { background: #27272a; padding: 0.1rem 0.3rem; border-radius: 0.2rem; font-family: 'SF Mono', Monaco, monospace; font-size: 0.9em; }
-        pre { background: #27272a; padding: 1rem; border-radius: 0.25rem; overflow-x: auto; margin: 0.5rem 0; }
-        pre code { background: none; padding: 0; }
-    </style>
-</head>
-<body>
-    <div class="container">
-        <h1>Claude Code Session</h1>
-        <div class="summary">"#);
-
-    html.push_str(&html_escape(&conversation.summary.unwrap_or_else(|| "No summary".to_string())));
-    html.push_str(r#"</div>"#);
-
-    for msg in &conversation.messages {
-        let role_class = if msg.role == "user" { "user" } else { "assistant" };
-        html.push_str(&format!(r#"
-        <div class="message {}">
-            <div class="role">{}"#, role_class, msg.role));
-
-        if let Some(ts) = &msg.timestamp {
-            html.push_str(&format!(r#"<span class="timestamp">{}</span>"#, ts));
-        }
-
-        html.push_str(r#"</div>
-            <div class="content">"#);
-
-        for block in &msg.content {
-            match block {
-                MessageContentBlock::Text { text } => {
-                    html.push_str(&html_escape(text));
-                }
-                MessageContentBlock::Thinking { thinking } => {
-                    html.push_str(r#"<div class="thinking"><div class="thinking-label">Thinking</div>"#);
-                    html.push_str(&html_escape(thinking));
-                    html.push_str(r#"</div>"#);
-                }
-                MessageContentBlock::ToolUse { name, input, .. } => {
-                    html.push_str(r#"<div class="tool-use"><span class="tool-name">"#);
-                    html.push_str(&name.clone().unwrap_or_else(|| "Unknown tool".to_string()));
-                    html.push_str(r#"</span>"#);
-                    if let Some(inp) = input {
-                        html.push_str(r#"<pre><code>"#);
-                        html.push_str(&html_escape(&serde_json::to_string_pretty(inp).unwrap_or_default()));
-                        html.push_str(r#"</code></pre>"#);
-                    }
-                    html.push_str(r#"</div>"#);
-                }
-                MessageContentBlock::ToolResult { content, .. } => {
-                    if let Some(c) = content {
-                        html.push_str(r#"<div class="tool-use"><pre><code>"#);
-                        html.push_str(&html_escape(&serde_json::to_string_pretty(c).unwrap_or_default()));
-                        html.push_str(r#"</code></pre></div>"#);
-                    }
-                }
-                MessageContentBlock::Other => {}
-            }
-        }
-
-        html.push_str(r#"</div>"#);
-
-        if let (Some(input), Some(output)) = (msg.input_tokens, msg.output_tokens) {
-            html.push_str(&format!(r#"<div class="tokens">Tokens: {} in / {} out</div>"#, input, output));
-        }
-
-        html.push_str(r#"</div>"#);
-    }
-
-    html.push_str(r#"
-    </div>
-</body>
-</html>"#);
-
-    Ok(html)
-}
-
-/// Basic HTML escaping
-fn html_escape(s: &str) -> String {
-    s.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-}
-
 /// Search through session messages for a query string
 /// Returns sessions that have matching message content
 pub fn search_sessions(project_path: &str, query: &str) -> Result<Vec<SessionSearchResult>, String> {
@@ -534,6 +1174,7 @@ pub fn search_sessions(project_path: &str, query: &str) -> Result<Vec<SessionSea
         .ok_or_else(|| format!("Project folder not found for: {}", project_path))?;
 
     let query_lower = query.to_lowercase();
+    let titles = super::metadata::get_project(project_path).session_titles;
     let mut results: Vec<SessionSearchResult> = Vec::new();
     let mut seen_sessions: std::collections::HashSet<String> = std::collections::HashSet::new();
 
@@ -614,6 +1255,7 @@ pub fn search_sessions(project_path: &str, query: &str) -> Result<Vec<SessionSea
                                 seen_sessions.insert(session_id.clone());
                                 results.push(SessionSearchResult {
                                     session_id: session_id.clone(),
+                                    title: titles.get(&session_id).cloned(),
                                     summary: summary.clone(),
                                     first_user_message: first_user_message.clone(),
                                     matched_text: match_result.0,
@@ -633,6 +1275,7 @@ pub fn search_sessions(project_path: &str, query: &str) -> Result<Vec<SessionSea
                                         seen_sessions.insert(session_id.clone());
                                         results.push(SessionSearchResult {
                                             session_id: session_id.clone(),
+                                            title: titles.get(&session_id).cloned(),
                                             summary: summary.clone(),
                                             first_user_message: first_user_message.clone(),
                                             matched_text: match_result.0,
@@ -686,3 +1329,73 @@ fn find_match_with_context(text: &str, query_lower: &str) -> Option<(String, Str
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_start() {
+        let today = Utc::now().date_naive();
+        let start = |range| range_start(range).unwrap().unwrap().date_naive();
+        assert_eq!(start("today"), today);
+        assert_eq!(start("week").weekday(), chrono::Weekday::Mon);
+        assert!((today - start("week")).num_days() < 7);
+        assert_eq!(start("month"), today.with_day(1).unwrap());
+        assert_eq!(range_start("all").unwrap(), None);
+        assert!(range_start("year").is_err());
+    }
+
+    #[test]
+    fn test_check_session_id() {
+        assert!(check_session_id("3f2a9c1e-8b7d-4e6f-a5c4-1d2e3f4a5b6c").is_ok());
+        assert!(check_session_id("agent_a1b2").is_ok());
+        assert!(check_session_id("").is_err());
+        assert!(check_session_id("abc; rm -rf ~").is_err());
+        assert!(check_session_id("$(touch /tmp/x)").is_err());
+        assert!(check_session_id("../other").is_err());
+    }
+
+    #[test]
+    fn test_session_info_since() {
+        let path =
+            std::env::temp_dir().join(format!("claudit-session-{}.jsonl", uuid::Uuid::new_v4()));
+        let assistant = |timestamp: &str| {
+            serde_json::json!({
+                "type": "assistant",
+                "timestamp": timestamp,
+                "message": {
+                    "model": "claude-sonnet-4-20250514",
+                    "usage": { "input_tokens": 1000, "output_tokens": 100 },
+                },
+            })
+            .to_string()
+        };
+        let lines = [
+            serde_json::json!({ "type": "user", "timestamp": "2024-02-28T10:00:00Z", "message": { "content": "hi" } }).to_string(),
+            assistant("2024-02-28T10:00:05Z"),
+            serde_json::json!({ "type": "user", "timestamp": "2024-03-01T09:00:00Z", "message": { "content": "more" } }).to_string(),
+            assistant("2024-03-01T09:00:05Z"),
+        ];
+        std::fs::write(&path, lines.join("\n")).unwrap();
+
+        let all = parse_session_info(&path, "s1").unwrap();
+        let since = "2024-03-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let in_range = parse_session_info_since(&path, "s1", Some(since)).unwrap();
+        assert_eq!(all.message_count, 4);
+        assert_eq!(all.total_input_tokens, 2000);
+        assert_eq!(in_range.message_count, 2);
+        assert_eq!(in_range.total_input_tokens, 1000);
+        assert_eq!(in_range.total_output_tokens, 100);
+        assert!((in_range.total_cost * 2.0 - all.total_cost).abs() < 1e-9);
+        assert_eq!(in_range.model_breakdown[0].stats.message_count, 1);
+        // The session's own times are kept
+        assert_eq!(
+            in_range.first_message_at.as_deref(),
+            Some("2024-02-28T10:00:00Z")
+        );
+        assert_eq!(in_range.first_user_message.as_deref(), Some("hi"));
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -1,16 +1,53 @@
 use crate::types::AppSettings;
 use std::fs;
 use std::path::PathBuf;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, OnceLock, RwLock};
+use tokio::sync::broadcast;
+
+/// Get claudit's own config directory (settings, tokens, activity log), creating it if needed
+pub fn app_config_dir() -> PathBuf {
+    // Tests never touch the real settings and logs
+    #[cfg(test)]
+    let config_dir = std::env::temp_dir().join(format!("claudit-test-{}", std::process::id()));
+    #[cfg(not(test))]
+    let config_dir = dirs::config_dir()
+        .or_else(dirs::home_dir)
+        .expect("Could not find config directory");
+
+    let app_config_dir = config_dir.join("claudit");
+
+    // Ensure directory exists
+    if !app_config_dir.exists() {
+        let _ = fs::create_dir_all(&app_config_dir);
+    }
+
+    app_config_dir
+}
+
+/// Process-wide settings instance (see `SettingsService::shared`)
+static SHARED: OnceLock<SettingsService> = OnceLock::new();
+
+/// Updates a subscriber can fall behind by before it misses some
+const CHANGE_CAPACITY: usize = 64;
+
+/// A settings update, as broadcast to subscribers
+#[derive(Debug, Clone)]
+pub struct SettingsChange {
+    pub previous: AppSettings,
+    pub current: AppSettings,
+}
 
 /// Manages application settings persistence
+#[derive(Clone)]
 pub struct SettingsService {
     settings_path: PathBuf,
     cached_settings: Arc<RwLock<AppSettings>>,
+    /// Every update, for the tray, hook server and watchers to react to
+    changes: broadcast::Sender<Arc<SettingsChange>>,
 }
 
 impl SettingsService {
-    pub fn new() -> Self {
+    fn new() -> Self {
         let settings_path = Self::get_settings_path();
 
         // Load settings from disk or use defaults
@@ -19,22 +56,19 @@ impl SettingsService {
         Self {
             settings_path,
             cached_settings: Arc::new(RwLock::new(settings)),
+            changes: broadcast::channel(CHANGE_CAPACITY).0,
         }
     }
 
-    fn get_settings_path() -> PathBuf {
-        let config_dir = dirs::config_dir()
-            .or_else(dirs::home_dir)
-            .expect("Could not find config directory");
-
-        let app_config_dir = config_dir.join("claudit");
-
-        // Ensure directory exists
-        if !app_config_dir.exists() {
-            let _ = fs::create_dir_all(&app_config_dir);
-        }
+    /// Handle to the shared instance, the only one there is. Settings are read
+    /// from disk once, on first use, and every handle sees updates made
+    /// through any other.
+    pub fn shared() -> Self {
+        SHARED.get_or_init(Self::new).clone()
+    }
 
-        app_config_dir.join("settings.json")
+    fn get_settings_path() -> PathBuf {
+        app_config_dir().join("settings.json")
     }
 
     fn load_from_disk(path: &PathBuf) -> Option<AppSettings> {
@@ -56,34 +90,53 @@ impl SettingsService {
 
     /// Update settings
     pub fn update(&self, settings: AppSettings) -> Result<(), String> {
-        self.save_to_disk(&settings)?;
-        *self.cached_settings.write().unwrap() = settings;
-        Ok(())
+        self.modify(|current| *current = settings).map(|_| ())
+    }
+
+    /// Change settings under the write lock, so updates from the tray and the
+    /// UI can't overwrite each other, and broadcast the change. Returns the
+    /// new settings.
+    pub fn modify(&self, change: impl FnOnce(&mut AppSettings)) -> Result<AppSettings, String> {
+        let mut cached = self.cached_settings.write().unwrap();
+        let previous = cached.clone();
+        let mut current = previous.clone();
+        change(&mut current);
+        self.save_to_disk(&current)?;
+        *cached = current.clone();
+        drop(cached);
+
+        // Fails only while nothing is subscribed
+        let _ = self.changes.send(Arc::new(SettingsChange {
+            previous,
+            current: current.clone(),
+        }));
+        Ok(current)
+    }
+
+    /// Receive every settings update from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<SettingsChange>> {
+        self.changes.subscribe()
     }
 
     /// Update a single setting
     pub fn set_notifications_enabled(&self, enabled: bool) -> Result<(), String> {
-        let mut settings = self.get();
-        settings.notifications_enabled = enabled;
-        self.update(settings)
+        self.modify(|settings| settings.notifications_enabled = enabled)
+            .map(|_| ())
     }
 
     pub fn set_compact_mode(&self, enabled: bool) -> Result<(), String> {
-        let mut settings = self.get();
-        settings.compact_mode = enabled;
-        self.update(settings)
+        self.modify(|settings| settings.compact_mode = enabled)
+            .map(|_| ())
     }
 
     pub fn set_auto_start(&self, enabled: bool) -> Result<(), String> {
-        let mut settings = self.get();
-        settings.auto_start = enabled;
-        self.update(settings)
+        self.modify(|settings| settings.auto_start = enabled)
+            .map(|_| ())
     }
 
     pub fn set_hook_port(&self, port: u16) -> Result<(), String> {
-        let mut settings = self.get();
-        settings.hook_port = port;
-        self.update(settings)
+        self.modify(|settings| settings.hook_port = port)
+            .map(|_| ())
     }
 
     /// Toggle visibility settings
@@ -104,8 +157,15 @@ impl SettingsService {
     }
 }
 
-impl Default for SettingsService {
-    fn default() -> Self {
-        Self::new()
-    }
+/// Call `handler` with every settings update, in order, on a thread of its
+/// own. Updates missed by a handler that fell far behind are skipped.
+pub fn on_change(mut handler: impl FnMut(&SettingsChange) + Send + 'static) {
+    let mut changes = SettingsService::shared().subscribe();
+    std::thread::spawn(move || loop {
+        match changes.blocking_recv() {
+            Ok(change) => handler(&change),
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    });
 }
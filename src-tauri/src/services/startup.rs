@@ -0,0 +1,76 @@
+//! Startup timing
+//!
+//! Records how long each startup phase took so slow launches can be diagnosed
+//! (`get_startup_report`). Times are relative to `begin()`, called first thing in `run()`.
+
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+static STARTED_AT: OnceLock<Instant> = OnceLock::new();
+static PHASES: Mutex<Vec<StartupPhase>> = Mutex::new(Vec::new());
+
+/// One timed startup phase
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartupPhase {
+    pub name: String,
+    /// Offset from process start
+    pub started_ms: u64,
+    pub duration_ms: u64,
+    /// Ran on a background thread after the tray appeared
+    pub deferred: bool,
+}
+
+/// Timing of the last app start
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartupReport {
+    pub phases: Vec<StartupPhase>,
+    /// When the tray icon was ready, relative to process start
+    pub tray_ready_ms: Option<u64>,
+    /// End of the last recorded phase
+    pub total_ms: u64,
+}
+
+fn elapsed_ms(since: Instant) -> u64 {
+    since.elapsed().as_millis() as u64
+}
+
+/// Start the startup clock
+pub fn begin() {
+    STARTED_AT.get_or_init(Instant::now);
+}
+
+/// Run `f` and record how long it took
+pub fn time<T>(name: &str, deferred: bool, f: impl FnOnce() -> T) -> T {
+    let started_at = *STARTED_AT.get_or_init(Instant::now);
+    let phase_start = Instant::now();
+    let result = f();
+
+    PHASES.lock().unwrap().push(StartupPhase {
+        name: name.to_string(),
+        started_ms: phase_start.duration_since(started_at).as_millis() as u64,
+        duration_ms: elapsed_ms(phase_start),
+        deferred,
+    });
+    result
+}
+
+/// Phases recorded so far
+pub fn report() -> StartupReport {
+    let phases = PHASES.lock().unwrap().clone();
+    let tray_ready_ms = phases
+        .iter()
+        .find(|p| p.name == "create_tray")
+        .map(|p| p.started_ms + p.duration_ms);
+    let total_ms = phases
+        .iter()
+        .map(|p| p.started_ms + p.duration_ms)
+        .max()
+        .unwrap_or(0);
+
+    StartupReport {
+        phases,
+        tray_ready_ms,
+        total_ms,
+    }
+}
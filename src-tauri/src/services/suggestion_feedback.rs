@@ -0,0 +1,138 @@
+//! Feedback on suggestions
+//!
+//! Dismissed and snoozed suggestions are kept out of `get_ai_suggestions`
+//! results, and what the user accepts or dismisses shifts the ranking of that
+//! suggestion type. A dismissal covers the suggestion as it was shown: trend
+//! insights keep their id between runs but get a new title when the numbers
+//! change, so a different finding shows up again.
+
+use super::config::AiSuggestion;
+use super::settings::app_config_dir;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedbackAction {
+    Accept,
+    Dismiss,
+    Snooze,
+}
+
+/// Latest feedback on one suggestion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuggestionFeedback {
+    pub id: String,
+    pub suggestion_type: String,
+    /// Title when the feedback was given
+    pub title: String,
+    pub action: FeedbackAction,
+    pub at: String,
+    #[serde(default)]
+    pub snooze_until: Option<String>,
+}
+
+fn feedback_path() -> PathBuf {
+    app_config_dir().join("suggestion-feedback.json")
+}
+
+pub fn load() -> Vec<SuggestionFeedback> {
+    fs::read_to_string(feedback_path())
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save(feedback: &[SuggestionFeedback]) -> Result<(), String> {
+    let contents =
+        serde_json::to_string_pretty(feedback).map_err(|e| format!("Serialize error: {}", e))?;
+    fs::write(feedback_path(), contents).map_err(|e| format!("Write error: {}", e))
+}
+
+/// Record feedback, replacing earlier feedback on the same suggestion
+pub fn record(
+    suggestion: &AiSuggestion,
+    action: FeedbackAction,
+    snooze_days: Option<u32>,
+) -> Result<SuggestionFeedback, String> {
+    let now = Utc::now();
+    let feedback = SuggestionFeedback {
+        id: suggestion.id.clone(),
+        suggestion_type: suggestion.suggestion_type.clone(),
+        title: suggestion.title.clone(),
+        action,
+        at: now.to_rfc3339(),
+        snooze_until: (action == FeedbackAction::Snooze)
+            .then(|| (now + Duration::days(snooze_days.unwrap_or(7) as i64)).to_rfc3339()),
+    };
+
+    let mut all = load();
+    all.retain(|f| f.id != feedback.id);
+    all.push(feedback.clone());
+    save(&all)?;
+    Ok(feedback)
+}
+
+/// Forget all feedback, so dismissed suggestions show again
+pub fn clear() -> Result<(), String> {
+    let path = feedback_path();
+    if path.exists() {
+        fs::remove_file(&path)
+            .map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Whether feedback keeps `suggestion` out of the results
+fn hides(feedback: &SuggestionFeedback, suggestion: &AiSuggestion, now: DateTime<Utc>) -> bool {
+    match feedback.action {
+        FeedbackAction::Accept | FeedbackAction::Dismiss => feedback.title == suggestion.title,
+        FeedbackAction::Snooze => feedback
+            .snooze_until
+            .as_deref()
+            .and_then(|until| DateTime::parse_from_rfc3339(until).ok())
+            .is_some_and(|until| until > now),
+    }
+}
+
+/// Score multiplier per suggestion type: each acceptance raises it, each
+/// dismissal lowers it, within 0.5x to 2x
+fn type_weights(feedback: &[SuggestionFeedback]) -> HashMap<&str, f64> {
+    let mut weights: HashMap<&str, f64> = HashMap::new();
+    for f in feedback {
+        let delta = match f.action {
+            FeedbackAction::Accept => 0.25,
+            FeedbackAction::Dismiss => -0.15,
+            FeedbackAction::Snooze => 0.0,
+        };
+        *weights.entry(f.suggestion_type.as_str()).or_insert(1.0) += delta;
+    }
+    for weight in weights.values_mut() {
+        *weight = weight.clamp(0.5, 2.0);
+    }
+    weights
+}
+
+/// Drop suggestions the user dismissed, accepted or snoozed and reweigh the
+/// rest by how their type has been received
+pub fn apply(suggestions: &mut Vec<AiSuggestion>) {
+    let feedback = load();
+    if feedback.is_empty() {
+        return;
+    }
+    let now = Utc::now();
+    let by_id: HashMap<&str, &SuggestionFeedback> =
+        feedback.iter().map(|f| (f.id.as_str(), f)).collect();
+
+    suggestions.retain(|s| by_id.get(s.id.as_str()).is_none_or(|f| !hides(f, s, now)));
+
+    let weights = type_weights(&feedback);
+    for suggestion in suggestions.iter_mut() {
+        if let Some(weight) = weights.get(suggestion.suggestion_type.as_str()) {
+            suggestion.score *= weight;
+        }
+    }
+}
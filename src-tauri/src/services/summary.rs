@@ -0,0 +1,112 @@
+//! Stats summary
+//!
+//! Renders today's and the last 7 days' numbers into a short text block for
+//! pasting into standups and chat. The layout is a template with `{name}`
+//! placeholders (`AppSettings::summary_template`, or `DEFAULT_TEMPLATE`);
+//! unknown placeholders are left as they are.
+
+use super::reports::{self, ReportPeriod};
+use super::{clipboard, format, SettingsService};
+use crate::types::AnalyticsStats;
+use chrono::Local;
+use serde::Serialize;
+
+pub const DEFAULT_TEMPLATE: &str = "**Claude usage - {date}**
+- Today: {today_cost} | {today_tokens} tokens | {today_messages} messages | {today_sessions} sessions
+- Last 7 days: {week_cost} ({week_change}) | {week_tokens} tokens | {week_sessions} sessions
+- Top model: {top_model} | Top project: {top_project}";
+
+/// Placeholders a template can use
+pub const PLACEHOLDERS: [&str; 13] = [
+    "date",
+    "today_cost",
+    "today_tokens",
+    "today_messages",
+    "today_sessions",
+    "burn_rate",
+    "week_cost",
+    "week_tokens",
+    "week_messages",
+    "week_sessions",
+    "week_change",
+    "top_model",
+    "top_project",
+];
+
+/// The default template and the placeholders, for the template editor
+#[derive(Debug, Clone, Serialize)]
+pub struct SummaryTemplateInfo {
+    pub default_template: String,
+    pub placeholders: Vec<String>,
+}
+
+pub fn template_info() -> SummaryTemplateInfo {
+    SummaryTemplateInfo {
+        default_template: DEFAULT_TEMPLATE.to_string(),
+        placeholders: PLACEHOLDERS.iter().map(|p| p.to_string()).collect(),
+    }
+}
+
+fn project_name(path: &str) -> &str {
+    path.rsplit(['/', '\\'])
+        .find(|p| !p.is_empty())
+        .unwrap_or(path)
+}
+
+/// Value of each placeholder for `stats` and the last week of usage
+fn values(stats: &AnalyticsStats) -> Vec<(&'static str, String)> {
+    let week = reports::generate(ReportPeriod::Week);
+    let week_change = match week.cost_change_pct {
+        Some(pct) => format!("{:+.0}%", pct),
+        None => "new".to_string(),
+    };
+    let top_model = week
+        .by_model
+        .first()
+        .map(|m| m.name.clone())
+        .unwrap_or_else(|| "-".to_string());
+    let top_project = week
+        .by_project
+        .first()
+        .map(|p| project_name(&p.name).to_string())
+        .unwrap_or_else(|| "-".to_string());
+
+    vec![
+        ("date", Local::now().format("%Y-%m-%d").to_string()),
+        ("today_cost", format::cost(stats.today_cost)),
+        ("today_tokens", format::tokens(stats.today_tokens())),
+        ("today_messages", stats.today_messages.to_string()),
+        ("today_sessions", stats.today_session_count.to_string()),
+        ("burn_rate", format::rate(stats.cost_per_hour)),
+        ("week_cost", format::cost(week.totals.cost)),
+        (
+            "week_tokens",
+            format::tokens(week.totals.input_tokens + week.totals.output_tokens),
+        ),
+        ("week_messages", week.totals.messages.to_string()),
+        ("week_sessions", week.totals.sessions.to_string()),
+        ("week_change", week_change),
+        ("top_model", top_model),
+        ("top_project", top_project),
+    ]
+}
+
+/// `template` (or the configured one) filled in with `stats`
+pub fn render(stats: &AnalyticsStats, template: Option<&str>) -> String {
+    let configured = SettingsService::shared().get().summary_template;
+    let mut text = template
+        .or(configured.as_deref())
+        .unwrap_or(DEFAULT_TEMPLATE)
+        .to_string();
+    for (name, value) in values(stats) {
+        text = text.replace(&format!("{{{}}}", name), &value);
+    }
+    text
+}
+
+/// Render the configured summary and put it on the clipboard
+pub fn copy(stats: &AnalyticsStats) -> Result<String, String> {
+    let text = render(stats, None);
+    clipboard::copy(&text)?;
+    Ok(text)
+}
@@ -0,0 +1,243 @@
+//! Opt-in telemetry about claudit itself
+//!
+//! Off unless `AppSettings::telemetry_enabled` is switched on. While on,
+//! claudit counts how often its features are used and where it panicked
+//! (source file and line only), and sends that weekly with its version, OS
+//! and architecture. Nothing about Claude usage, projects, paths, prompts or
+//! costs is collected. Settings previews the exact report that would be sent;
+//! switching telemetry off deletes everything collected.
+
+use super::settings::app_config_dir;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// Where reports are sent
+pub const ENDPOINT: &str = "https://claudit.cloud.neschkudla.at/api/telemetry";
+
+/// Days between reports
+const REPORT_INTERVAL_DAYS: i64 = 7;
+
+/// Mirror of the setting, so recording a panic doesn't touch the settings lock
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// What was collected since the last report (loaded on first use)
+static STATE: Mutex<Option<TelemetryState>> = Mutex::new(None);
+
+/// Collected data, stored in telemetry.json until it is sent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TelemetryState {
+    install_id: String,
+    since: DateTime<Utc>,
+    #[serde(default)]
+    features: BTreeMap<String, u64>,
+    #[serde(default)]
+    crashes: BTreeMap<String, u64>,
+    #[serde(default)]
+    last_sent: Option<DateTime<Utc>>,
+}
+
+impl TelemetryState {
+    fn new() -> Self {
+        Self {
+            install_id: uuid::Uuid::new_v4().to_string(),
+            since: Utc::now(),
+            features: BTreeMap::new(),
+            crashes: BTreeMap::new(),
+            last_sent: None,
+        }
+    }
+}
+
+/// Exactly what a report sends
+#[derive(Debug, Clone, Serialize)]
+pub struct TelemetryReport {
+    /// Random id made when telemetry was switched on, not derived from the
+    /// machine or the user
+    pub install_id: String,
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    /// Start of the period the counts cover
+    pub since: String,
+    /// Uses of each feature
+    pub features: BTreeMap<String, u64>,
+    /// Panics by source location
+    pub crashes: BTreeMap<String, u64>,
+}
+
+/// The report that would be sent next, for the preview in Settings
+#[derive(Debug, Clone, Serialize)]
+pub struct TelemetryPreview {
+    pub enabled: bool,
+    pub endpoint: String,
+    pub last_sent: Option<String>,
+    pub report: Option<TelemetryReport>,
+}
+
+fn state_path() -> PathBuf {
+    app_config_dir().join("telemetry.json")
+}
+
+fn load() -> TelemetryState {
+    std::fs::read_to_string(state_path())
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_else(TelemetryState::new)
+}
+
+fn save(state: &TelemetryState) {
+    if let Ok(contents) = serde_json::to_string_pretty(state) {
+        let _ = std::fs::write(state_path(), contents);
+    }
+}
+
+fn report(state: &TelemetryState) -> TelemetryReport {
+    TelemetryReport {
+        install_id: state.install_id.clone(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        since: state.since.to_rfc3339(),
+        features: state.features.clone(),
+        crashes: state.crashes.clone(),
+    }
+}
+
+/// Follow the setting; switching off deletes what was collected
+pub fn set_enabled(enabled: bool) {
+    let was_enabled = ENABLED.swap(enabled, Ordering::Relaxed);
+    if was_enabled && !enabled {
+        *STATE.lock().unwrap() = None;
+        let _ = std::fs::remove_file(state_path());
+    }
+}
+
+/// Count a use of `feature` (no-op while telemetry is off)
+pub fn record(feature: &str) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let Ok(mut guard) = STATE.lock() else {
+        return;
+    };
+    let state = guard.get_or_insert_with(load);
+    *state.features.entry(feature.to_string()).or_default() += 1;
+    save(state);
+}
+
+/// Count panics by where they happened, before the default panic output
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if ENABLED.load(Ordering::Relaxed) {
+            if let Some(location) = info.location() {
+                // Only the last two path components: dependency paths contain the home folder
+                let file: Vec<&str> = location.file().rsplit(['/', '\\']).take(2).collect();
+                let signature = format!(
+                    "{}:{}",
+                    file.into_iter().rev().collect::<Vec<_>>().join("/"),
+                    location.line()
+                );
+                // A panic while the state is locked must not deadlock here
+                if let Ok(mut guard) = STATE.try_lock() {
+                    let state = guard.get_or_insert_with(load);
+                    *state.crashes.entry(signature).or_default() += 1;
+                    save(state);
+                }
+            }
+        }
+        default_hook(info);
+    }));
+}
+
+/// The next report and where it goes
+pub fn preview() -> TelemetryPreview {
+    let enabled = ENABLED.load(Ordering::Relaxed);
+    let mut guard = STATE.lock().unwrap();
+    let state = enabled.then(|| guard.get_or_insert_with(load));
+    TelemetryPreview {
+        enabled,
+        endpoint: ENDPOINT.to_string(),
+        last_sent: state
+            .as_ref()
+            .and_then(|s| s.last_sent)
+            .map(|t| t.to_rfc3339()),
+        report: state.map(|s| report(s)),
+    }
+}
+
+/// POST a report with curl, which every supported OS ships
+fn send(report: &TelemetryReport) -> Result<(), String> {
+    let body = serde_json::to_string(report).map_err(|e| format!("Serialize error: {}", e))?;
+    let mut child = Command::new("curl")
+        .args(["-sSf", "-m", "10", "-H", "Content-Type: application/json"])
+        .args(["--data-binary", "@-", ENDPOINT])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run curl: {}", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(body.as_bytes())
+            .map_err(|e| format!("Failed to send report: {}", e))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to send report: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Telemetry report failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Send the report once a week, then start counting again. Called by the watchdog.
+pub fn send_if_due() {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let pending = {
+        let mut guard = STATE.lock().unwrap();
+        let state = guard.get_or_insert_with(load);
+        let due = Utc::now() - state.last_sent.unwrap_or(state.since)
+            >= Duration::days(REPORT_INTERVAL_DAYS);
+        let empty = state.features.is_empty() && state.crashes.is_empty();
+        (due && !empty).then(|| report(state))
+    };
+    let Some(pending) = pending else {
+        return;
+    };
+
+    // The lock isn't held while curl runs; uses counted meanwhile are kept
+    if let Err(e) = send(&pending) {
+        eprintln!("{}", e);
+        return;
+    }
+    let mut guard = STATE.lock().unwrap();
+    let state = guard.get_or_insert_with(load);
+    for (feature, count) in &pending.features {
+        if let Some(current) = state.features.get_mut(feature) {
+            *current = current.saturating_sub(*count);
+        }
+    }
+    for (crash, count) in &pending.crashes {
+        if let Some(current) = state.crashes.get_mut(crash) {
+            *current = current.saturating_sub(*count);
+        }
+    }
+    state.features.retain(|_, count| *count > 0);
+    state.crashes.retain(|_, count| *count > 0);
+    let now = Utc::now();
+    state.since = now;
+    state.last_sent = Some(now);
+    save(state);
+}
@@ -0,0 +1,182 @@
+//! Terminal launching
+//!
+//! Opens the user's terminal (`AppSettings::terminal_app`, or the first one
+//! found running) with `claude --resume` for a session. On Windows a new
+//! console runs it, inside WSL when the active data directory is a WSL path.
+
+#[cfg(target_os = "macos")]
+use super::SettingsService;
+use super::sessions::check_session_id;
+use super::{paths, wsl};
+
+/// Detect available terminal apps on macOS
+#[cfg(target_os = "macos")]
+fn detect_terminal_app() -> String {
+    // Check for common terminal apps in order of preference
+    let terminals = ["iTerm", "Warp", "Alacritty", "kitty", "Terminal"];
+
+    for terminal in terminals {
+        let check = std::process::Command::new("osascript")
+            .arg("-e")
+            .arg(format!(
+                r#"tell application "System Events" to (name of processes) contains "{}""#,
+                terminal
+            ))
+            .output();
+
+        if let Ok(output) = check {
+            let result = String::from_utf8_lossy(&output.stdout);
+            if result.trim() == "true" {
+                return terminal.to_string();
+            }
+        }
+    }
+
+    // Check if apps exist in /Applications
+    for terminal in terminals {
+        let app_path = format!("/Applications/{}.app", terminal);
+        if std::path::Path::new(&app_path).exists() {
+            return terminal.to_string();
+        }
+    }
+
+    // Default to Terminal
+    "Terminal".to_string()
+}
+
+/// Resume a session of a WSL distribution in a new console
+fn resume_in_wsl(distro: &str, project_path: &str, session_id: &str) -> Result<(), String> {
+    let args = wsl::resume_args(distro, project_path, session_id);
+    #[cfg(windows)]
+    {
+        std::process::Command::new("cmd")
+            .args(["/C", "start", "", "wsl.exe"])
+            .args(&args)
+            .spawn()
+            .map_err(|e| format!("Failed to open WSL ({}): {}", distro, e))?;
+        Ok(())
+    }
+    #[cfg(not(windows))]
+    {
+        Err(format!(
+            "WSL sessions can only be resumed on Windows (wsl.exe {})",
+            args.join(" ")
+        ))
+    }
+}
+
+/// Open a terminal in `project_path` resuming `session_id`
+pub fn resume_session(project_path: &str, session_id: &str) -> Result<(), String> {
+    // The ID ends up in shell commands below
+    check_session_id(session_id)?;
+    let config_dir = paths::current().config_dir().to_string_lossy().to_string();
+    if let Some((distro, _)) = wsl::parse_unc(&config_dir) {
+        return resume_in_wsl(&distro, project_path, session_id);
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let settings = SettingsService::shared().get();
+        let terminal = if settings.terminal_app == "auto" {
+            detect_terminal_app()
+        } else {
+            settings.terminal_app.clone()
+        };
+
+        // Escape single quotes in the path
+        let escaped_path = project_path.replace("'", "'\\''");
+        let command = format!("cd '{}' && claude --resume {}", escaped_path, session_id);
+
+        let script = match terminal.as_str() {
+            "iTerm" => format!(
+                r#"tell application "iTerm"
+                    activate
+                    try
+                        tell current window
+                            create tab with default profile
+                            tell current session
+                                write text "{}"
+                            end tell
+                        end tell
+                    on error
+                        create window with default profile
+                        tell current window
+                            tell current session
+                                write text "{}"
+                            end tell
+                        end tell
+                    end try
+                end tell"#,
+                command, command
+            ),
+            "Warp" => format!(
+                r#"tell application "Warp"
+                    activate
+                    do script "{}"
+                end tell"#,
+                command
+            ),
+            "Alacritty" | "kitty" => {
+                // For these terminals, use open command with shell
+                return std::process::Command::new("open")
+                    .arg("-a")
+                    .arg(&terminal)
+                    .spawn()
+                    .map_err(|e| format!("Failed to open {}: {}", terminal, e))
+                    .and_then(|_| {
+                        // Give the terminal time to open, then use pbcopy + paste approach
+                        std::thread::sleep(std::time::Duration::from_millis(500));
+                        // Copy command to clipboard
+                        let mut child = std::process::Command::new("pbcopy")
+                            .stdin(std::process::Stdio::piped())
+                            .spawn()
+                            .map_err(|e| format!("Failed to copy command: {}", e))?;
+                        if let Some(stdin) = child.stdin.as_mut() {
+                            use std::io::Write;
+                            stdin.write_all(command.as_bytes()).ok();
+                        }
+                        child.wait().ok();
+                        Ok(())
+                    });
+            }
+            _ => format!(
+                r#"tell application "Terminal"
+                    activate
+                    do script "{}"
+                end tell"#,
+                command
+            ),
+        };
+
+        std::process::Command::new("osascript")
+            .arg("-e")
+            .arg(&script)
+            .spawn()
+            .map_err(|e| format!("Failed to open {}: {}", terminal, e))?;
+        Ok(())
+    }
+    #[cfg(windows)]
+    {
+        std::process::Command::new("cmd")
+            .args([
+                "/C",
+                "start",
+                "",
+                "/D",
+                project_path,
+                "cmd",
+                "/K",
+                "claude",
+                "--resume",
+            ])
+            .arg(session_id)
+            .spawn()
+            .map_err(|e| format!("Failed to open a console: {}", e))?;
+        Ok(())
+    }
+    #[cfg(not(any(target_os = "macos", windows)))]
+    {
+        let _ = (project_path, session_id);
+        Err("Terminal launch only supported on macOS and Windows".to_string())
+    }
+}
@@ -0,0 +1,197 @@
+//! Tool use notification rules
+//!
+//! User-defined rules matched against `PostToolUse` hook events, e.g. "Bash
+//! ran `git push`" or "Write touched `migrations/`". A rule has a regex for
+//! the tool name and one for the tool's main input: the command for Bash, the
+//! file path for Read/Write/Edit, the URL for WebFetch and so on. Matching
+//! events are sent through the normal notification rules (and the digest).
+
+use crate::types::{HookEvent, ToolNotificationRule};
+use regex::Regex;
+use serde_json::Value;
+
+/// Input fields that best describe a tool call, in order of preference
+const SUBJECT_FIELDS: &[&str] = &[
+    "command",
+    "file_path",
+    "notebook_path",
+    "path",
+    "url",
+    "pattern",
+    "query",
+];
+
+/// Compile a rule pattern; empty patterns match anything
+fn compile(pattern: &str) -> Result<Option<Regex>, String> {
+    if pattern.trim().is_empty() {
+        return Ok(None);
+    }
+    Regex::new(pattern)
+        .map(Some)
+        .map_err(|e| format!("Invalid pattern \"{}\": {}", pattern, e))
+}
+
+/// Check that every rule's patterns compile
+pub fn validate(rules: &[ToolNotificationRule]) -> Result<(), String> {
+    for rule in rules {
+        compile(&rule.tool).map_err(|e| format!("{}: {}", rule.name, e))?;
+        compile(&rule.input).map_err(|e| format!("{}: {}", rule.name, e))?;
+    }
+    Ok(())
+}
+
+/// Tool name of a hook event
+fn tool_name(event: &HookEvent) -> Option<String> {
+    event.tool.clone().or_else(|| {
+        event
+            .payload
+            .as_ref()?
+            .get("tool_name")?
+            .as_str()
+            .map(String::from)
+    })
+}
+
+/// The main input of the tool call (see `SUBJECT_FIELDS`), or all of it as JSON
+pub fn tool_subject(event: &HookEvent) -> String {
+    let Some(input) = event.payload.as_ref().and_then(|p| p.get("tool_input")) else {
+        return String::new();
+    };
+    SUBJECT_FIELDS
+        .iter()
+        .find_map(|field| input.get(field).and_then(Value::as_str))
+        .map(String::from)
+        .unwrap_or_else(|| input.to_string())
+}
+
+/// First enabled rule matching a PostToolUse event
+pub fn matching_rule<'a>(
+    rules: &'a [ToolNotificationRule],
+    event: &HookEvent,
+) -> Option<&'a ToolNotificationRule> {
+    let tool = tool_name(event)?;
+    let subject = tool_subject(event);
+
+    rules.iter().filter(|rule| rule.enabled).find(|rule| {
+        // Rules are validated when saved; one that fails to compile never matches
+        let (Ok(tool_pattern), Ok(input_pattern)) = (compile(&rule.tool), compile(&rule.input))
+        else {
+            return false;
+        };
+        tool_pattern.is_none_or(|p| p.is_match(&tool))
+            && input_pattern.is_none_or(|p| p.is_match(&subject))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rule(name: &str, tool: &str, input: &str) -> ToolNotificationRule {
+        ToolNotificationRule {
+            id: name.to_lowercase(),
+            name: name.to_string(),
+            enabled: true,
+            tool: tool.to_string(),
+            input: input.to_string(),
+        }
+    }
+
+    /// A PostToolUse event as the hook relay forwards it
+    fn post_tool_use(tool_name: &str, tool_input: Value) -> HookEvent {
+        HookEvent {
+            event: "PostToolUse".to_string(),
+            tool: None,
+            context: None,
+            timestamp: None,
+            session_id: Some("abc-123".to_string()),
+            cwd: Some("/work/app".to_string()),
+            transcript_path: None,
+            payload: Some(json!({
+                "hook_event_name": "PostToolUse",
+                "tool_name": tool_name,
+                "tool_input": tool_input,
+                "tool_response": { "success": true },
+            })),
+        }
+    }
+
+    #[test]
+    fn test_bash_command_rule() {
+        let rules = [rule("Push", "^Bash$", r"git\s+push")];
+        let push = post_tool_use(
+            "Bash",
+            json!({ "command": "git push origin main", "description": "Push" }),
+        );
+        assert_eq!(matching_rule(&rules, &push).unwrap().name, "Push");
+
+        let status = post_tool_use("Bash", json!({ "command": "git status" }));
+        assert!(matching_rule(&rules, &status).is_none());
+        // The tool has to match too
+        let other = post_tool_use("BashOutput", json!({ "command": "git push" }));
+        assert!(matching_rule(&rules, &other).is_none());
+    }
+
+    #[test]
+    fn test_write_path_rule() {
+        let rules = [rule("Migration", "Write|Edit", "migrations/")];
+        let write = post_tool_use(
+            "Write",
+            json!({ "file_path": "/work/app/db/migrations/001.sql", "content": "CREATE TABLE" }),
+        );
+        assert_eq!(matching_rule(&rules, &write).unwrap().name, "Migration");
+
+        let elsewhere = post_tool_use(
+            "Edit",
+            json!({ "file_path": "/work/app/src/main.rs", "old_string": "migrations/" }),
+        );
+        assert!(matching_rule(&rules, &elsewhere).is_none());
+        let read = post_tool_use(
+            "Read",
+            json!({ "file_path": "/work/app/db/migrations/001.sql" }),
+        );
+        assert!(matching_rule(&rules, &read).is_none());
+    }
+
+    #[test]
+    fn test_invalid_and_disabled_rules() {
+        let mut disabled = rule("Disabled", "Bash", "");
+        disabled.enabled = false;
+        let rules = [
+            rule("Broken", "Bash", "git push("),
+            disabled,
+            rule("Any Bash", "Bash", ""),
+        ];
+        let error = validate(&rules).unwrap_err();
+        assert!(
+            error.starts_with("Broken: Invalid pattern \"git push(\""),
+            "{}",
+            error
+        );
+        assert!(validate(&rules[1..]).is_ok());
+
+        // A rule that doesn't compile never matches, and disabled ones are skipped
+        let push = post_tool_use("Bash", json!({ "command": "git push" }));
+        assert_eq!(matching_rule(&rules, &push).unwrap().name, "Any Bash");
+    }
+
+    #[test]
+    fn test_tool_subject() {
+        let fetch = post_tool_use(
+            "WebFetch",
+            json!({ "url": "https://example.com", "prompt": "Summarize" }),
+        );
+        assert_eq!(tool_subject(&fetch), "https://example.com");
+
+        let todo = post_tool_use("TodoWrite", json!({ "todos": [] }));
+        assert_eq!(tool_subject(&todo), r#"{"todos":[]}"#);
+
+        let mut bare = post_tool_use("Bash", json!({}));
+        bare.payload = None;
+        bare.tool = Some("Bash".to_string());
+        assert_eq!(tool_subject(&bare), "");
+        // The relay's tool name is used without a payload
+        assert!(matching_rule(&[rule("Any Bash", "Bash", "")], &bare).is_some());
+    }
+}
@@ -0,0 +1,331 @@
+//! Live session tracking from hook events
+//!
+//! Every hook event carrying a session id updates the tracker. A session's
+//! current "run" starts with the first event after it went idle and ends when
+//! Claude sends Stop (or events stop flowing for a while).
+//!
+//! Sessions without hooks are picked up from recently written JSONL files.
+
+use super::format;
+use super::live_cost;
+use super::notifications::{self, PendingNotification};
+use super::{SettingsService, UsageReader};
+use crate::types::HookEvent;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use tauri::{AppHandle, Emitter, Runtime};
+
+/// A gap without events longer than this ends the current run
+const RUN_IDLE_GAP_MINUTES: i64 = 10;
+
+/// Sessions without events for this long are forgotten
+const SESSION_EXPIRY_MINUTES: i64 = 60;
+
+/// A JSONL file written within this window counts as an active session
+const JSONL_ACTIVE_MINUTES: i64 = 2;
+
+/// A session seen recently through hook events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackedSession {
+    pub session_id: String,
+    /// Working directory reported by the hook (the project path)
+    pub project: Option<String>,
+    pub transcript_path: Option<String>,
+    /// Start of the current continuous run, None while idle waiting for the user
+    pub run_started_at: Option<DateTime<Utc>>,
+    pub last_event_at: DateTime<Utc>,
+    pub last_event: String,
+    /// Claude sent a Notification (permission prompt or idle) and is waiting for the user
+    #[serde(default)]
+    pub needs_attention: bool,
+    /// Whether the long-running notification was already sent for this run
+    #[serde(skip)]
+    long_running_notified: bool,
+}
+
+/// A currently running session, as shown in the tray and returned by `get_active_sessions`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveSession {
+    pub session_id: String,
+    /// Project path (hook cwd, or the project the JSONL file belongs to)
+    pub project: String,
+    pub project_name: String,
+    pub started_at: DateTime<Utc>,
+    pub elapsed_seconds: i64,
+    pub last_activity: DateTime<Utc>,
+    /// Cost of the session so far (USD)
+    pub cost: f64,
+    pub needs_attention: bool,
+    /// Where the session was detected: "hook" or "jsonl"
+    pub source: String,
+}
+
+/// Tracks sessions that are currently (or were recently) active
+#[derive(Default)]
+pub struct SessionTracker {
+    sessions: RwLock<HashMap<String, TrackedSession>>,
+    /// Active sessions from the last refresh (read by the tray without disk I/O)
+    active: RwLock<Vec<ActiveSession>>,
+}
+
+impl SessionTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update the tracker from a hook event.
+    /// Returns true when the set of sessions waiting for the user changed.
+    pub fn record_event(&self, event: &HookEvent) -> bool {
+        let session_id = match &event.session_id {
+            Some(id) if !id.is_empty() => id.clone(),
+            _ => return false,
+        };
+        let now = Utc::now();
+        let is_stop = event.event == "Stop";
+        let is_notification = event.event == "Notification";
+
+        let mut sessions = match self.sessions.write() {
+            Ok(guard) => guard,
+            Err(_) => return false,
+        };
+
+        let session = sessions
+            .entry(session_id.clone())
+            .or_insert_with(|| TrackedSession {
+                session_id,
+                project: None,
+                transcript_path: None,
+                run_started_at: None,
+                last_event_at: now,
+                last_event: String::new(),
+                needs_attention: false,
+                long_running_notified: false,
+            });
+
+        // A long silence means the previous run was interrupted without a Stop
+        if now - session.last_event_at > Duration::minutes(RUN_IDLE_GAP_MINUTES) {
+            session.run_started_at = None;
+        }
+
+        if is_stop {
+            session.run_started_at = None;
+        } else if session.run_started_at.is_none() && !is_notification {
+            session.run_started_at = Some(now);
+            session.long_running_notified = false;
+        }
+
+        if event.cwd.is_some() {
+            session.project = event.cwd.clone();
+        }
+        if event.transcript_path.is_some() {
+            session.transcript_path = event.transcript_path.clone();
+        }
+        session.last_event_at = now;
+        session.last_event = event.event.clone();
+
+        // Any other activity means the user has responded
+        let attention_changed = session.needs_attention != is_notification;
+        session.needs_attention = is_notification;
+
+        sessions.retain(|_, s| now - s.last_event_at < Duration::minutes(SESSION_EXPIRY_MINUTES));
+        attention_changed
+    }
+
+    /// Snapshot of all tracked sessions
+    pub fn sessions(&self) -> Vec<TrackedSession> {
+        self.sessions
+            .read()
+            .map(|sessions| sessions.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Sessions currently waiting for the user
+    pub fn sessions_needing_attention(&self) -> Vec<TrackedSession> {
+        self.sessions()
+            .into_iter()
+            .filter(|session| session.needs_attention)
+            .collect()
+    }
+
+    /// Recompute the active sessions from hook state and JSONL files
+    pub fn refresh_active(&self) -> Vec<ActiveSession> {
+        let now = Utc::now();
+        let reader = UsageReader::new();
+        let mut active: Vec<ActiveSession> = Vec::new();
+
+        // Sessions reporting through hooks
+        for session in self.sessions() {
+            let running = session.run_started_at.is_some()
+                && now - session.last_event_at <= Duration::minutes(RUN_IDLE_GAP_MINUTES);
+            if !running && !session.needs_attention {
+                continue;
+            }
+
+            let file = reader.find_session_file(
+                session.transcript_path.as_deref(),
+                Some(&session.session_id),
+                session.project.as_deref(),
+            );
+            let started_at = session.run_started_at.unwrap_or(session.last_event_at);
+            let project = session.project.clone().unwrap_or_default();
+
+            active.push(ActiveSession {
+                project_name: project_display_name(&project),
+                project,
+                started_at,
+                elapsed_seconds: (now - started_at).num_seconds(),
+                last_activity: session.last_event_at,
+                cost: file
+                    .and_then(|f| live_cost::update(&f))
+                    .map_or(0.0, |live| live.cost),
+                needs_attention: session.needs_attention,
+                source: "hook".to_string(),
+                session_id: session.session_id,
+            });
+        }
+
+        // Sessions without hooks, detected from JSONL files that are still being written
+        for file in reader.recently_modified_files(Duration::minutes(JSONL_ACTIVE_MINUTES)) {
+            let session_id = file
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+            if active.iter().any(|s| s.session_id == session_id) {
+                continue;
+            }
+
+            let entries = reader.read_session_entries(&file);
+            let Some(last) = entries.last() else {
+                continue;
+            };
+
+            // The run started after the last long gap between messages
+            let started_at = entries
+                .windows(2)
+                .rev()
+                .find(|pair| {
+                    pair[1].timestamp - pair[0].timestamp > Duration::minutes(RUN_IDLE_GAP_MINUTES)
+                })
+                .map(|pair| pair[1].timestamp)
+                .unwrap_or(entries[0].timestamp);
+
+            active.push(ActiveSession {
+                session_id,
+                project: last.project.clone(),
+                project_name: project_display_name(&last.project),
+                started_at,
+                elapsed_seconds: (now - started_at).num_seconds(),
+                last_activity: last.timestamp,
+                cost: live_cost::update(&file).map_or(0.0, |live| live.cost),
+                needs_attention: false,
+                source: "jsonl".to_string(),
+            });
+        }
+
+        active.sort_by_key(|s| s.started_at);
+        if let Ok(mut cached) = self.active.write() {
+            *cached = active.clone();
+        }
+        active
+    }
+
+    /// Active sessions from the last refresh
+    pub fn cached_active(&self) -> Vec<ActiveSession> {
+        self.active.read().map(|a| a.clone()).unwrap_or_default()
+    }
+
+    /// Update the cost of a cached active session between refreshes
+    pub fn set_live_cost(&self, session_id: &str, cost: f64) {
+        if let Ok(mut active) = self.active.write() {
+            if let Some(session) = active.iter_mut().find(|s| s.session_id == session_id) {
+                session.cost = cost;
+            }
+        }
+    }
+
+    /// Sessions whose current run exceeds the threshold and haven't been reported yet.
+    /// Returned sessions are marked as reported.
+    pub fn take_long_running(&self, threshold: Duration) -> Vec<TrackedSession> {
+        let now = Utc::now();
+        let mut sessions = match self.sessions.write() {
+            Ok(guard) => guard,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut due = Vec::new();
+        for session in sessions.values_mut() {
+            let Some(started) = session.run_started_at else {
+                continue;
+            };
+            let still_flowing = now - session.last_event_at <= Duration::minutes(RUN_IDLE_GAP_MINUTES);
+            if !session.long_running_notified && still_flowing && now - started >= threshold {
+                session.long_running_notified = true;
+                due.push(session.clone());
+            }
+        }
+        due
+    }
+}
+
+/// Display name of a project, falling back to its path
+fn project_display_name(path: &str) -> String {
+    format::project_name(path).unwrap_or_else(|| path.to_string())
+}
+
+/// Cost of a session's usage since the given time
+fn session_cost_since(session: &TrackedSession, since: DateTime<Utc>) -> Option<f64> {
+    let reader = UsageReader::new();
+    let file = reader.find_session_file(
+        session.transcript_path.as_deref(),
+        Some(&session.session_id),
+        session.project.as_deref(),
+    )?;
+    Some(reader.session_cost(&file, Some(since)))
+}
+
+/// Notify about sessions that have been running longer than the configured threshold.
+/// Called periodically by the watchdog thread.
+pub fn check_long_running<R: Runtime>(app: &AppHandle<R>, tracker: &SessionTracker) {
+    let minutes = SettingsService::shared().get().long_running_minutes;
+    if minutes == 0 {
+        return;
+    }
+
+    for session in tracker.take_long_running(Duration::minutes(minutes as i64)) {
+        let Some(started) = session.run_started_at else {
+            continue;
+        };
+        let elapsed = (Utc::now() - started).num_minutes();
+        let project = session
+            .project
+            .as_deref()
+            .map(project_display_name)
+            .unwrap_or_else(|| "an unknown project".to_string());
+
+        let body = match session_cost_since(&session, started) {
+            Some(cost) => format!(
+                "Claude has been running for {} minutes on {} ({} so far)",
+                elapsed,
+                project,
+                format::cost(cost)
+            ),
+            None => format!("Claude has been running for {} minutes on {}", elapsed, project),
+        };
+
+        // The frontend uses this to link to the live session
+        let _ = app.emit("long-running-session", &session);
+
+        notifications::dispatch(
+            app,
+            PendingNotification {
+                title: "Long-running Claude session".to_string(),
+                body,
+                event: "LongRunningSession".to_string(),
+                project: session.project.clone(),
+                session_id: Some(session.session_id.clone()),
+            },
+        );
+    }
+}
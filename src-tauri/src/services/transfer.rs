@@ -0,0 +1,92 @@
+//! Chunked transfers of large payloads
+//!
+//! A multi-MB `SessionConversation` returned from one `invoke` is serialized,
+//! sent and parsed in one go, which stalls the webview. Instead the payload is
+//! serialized and gzipped here, kept in memory, and read by the frontend in
+//! binary chunks (`read_transfer_chunk`), which it decompresses with
+//! `DecompressionStream`. A transfer is dropped once its last chunk is read, or
+//! after a few minutes if it never is.
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Size of one chunk of compressed data
+const CHUNK_BYTES: usize = 512 * 1024;
+
+/// Unread transfers are dropped after this long
+const EXPIRY: Duration = Duration::from_secs(5 * 60);
+
+/// What the frontend needs to read a transfer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferInfo {
+    pub id: String,
+    /// Size of the JSON payload
+    pub bytes: usize,
+    /// Size of the gzipped payload, split into `chunks` chunks
+    pub compressed_bytes: usize,
+    pub chunks: usize,
+}
+
+struct Transfer {
+    data: Vec<u8>,
+    created: Instant,
+}
+
+static TRANSFERS: Mutex<Option<HashMap<String, Transfer>>> = Mutex::new(None);
+
+/// Serialize and compress `value` and hold it for chunked reads
+pub fn start<T: Serialize>(value: &T) -> Result<TransferInfo, String> {
+    let json = serde_json::to_vec(value).map_err(|e| format!("Serialize error: {}", e))?;
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::fast());
+    encoder
+        .write_all(&json)
+        .map_err(|e| format!("Compression error: {}", e))?;
+    let data = encoder
+        .finish()
+        .map_err(|e| format!("Compression error: {}", e))?;
+
+    let info = TransferInfo {
+        id: uuid::Uuid::new_v4().to_string(),
+        bytes: json.len(),
+        compressed_bytes: data.len(),
+        chunks: data.len().div_ceil(CHUNK_BYTES).max(1),
+    };
+
+    let mut transfers = TRANSFERS.lock().unwrap();
+    let transfers = transfers.get_or_insert_with(HashMap::new);
+    transfers.retain(|_, t| t.created.elapsed() < EXPIRY);
+    transfers.insert(
+        info.id.clone(),
+        Transfer {
+            data,
+            created: Instant::now(),
+        },
+    );
+    Ok(info)
+}
+
+/// Chunk `index` of a transfer. Reading the last chunk ends the transfer.
+pub fn read_chunk(id: &str, index: usize) -> Result<Vec<u8>, String> {
+    let mut transfers = TRANSFERS.lock().unwrap();
+    let transfers = transfers.get_or_insert_with(HashMap::new);
+    let transfer = transfers
+        .get(id)
+        .ok_or_else(|| format!("Unknown or expired transfer: {}", id))?;
+
+    let start = index * CHUNK_BYTES;
+    if start > 0 && start >= transfer.data.len() {
+        return Err(format!("Chunk {} is out of range", index));
+    }
+    let end = (start + CHUNK_BYTES).min(transfer.data.len());
+    let chunk = transfer.data[start..end].to_vec();
+
+    if end == transfer.data.len() {
+        transfers.remove(id);
+    }
+    Ok(chunk)
+}
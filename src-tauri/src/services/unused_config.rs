@@ -0,0 +1,236 @@
+//! Unused config
+//!
+//! Every configured MCP server adds its tools to Claude Code's context, and
+//! every agent and command its description. This cross-references them with
+//! the session logs and lists the ones not used in the last N days, each with
+//! a way to turn it off: MCP servers are removed from their config (undoable
+//! from the audit log), agent and command files are renamed to `*.md.disabled`
+//! so Claude Code skips them.
+
+use super::audit::{FileOperation, FilePlan};
+use super::config::{self, McpScope};
+use super::invocations::{self, InvocationKind};
+use super::usage::UsageReader;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnusedKind {
+    McpServer,
+    Agent,
+    Command,
+}
+
+/// A configured item that wasn't used in the period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnusedItem {
+    /// Stable key, passed back to `disable`
+    pub id: String,
+    pub kind: UnusedKind,
+    pub name: String,
+    /// Where an MCP server is configured
+    pub scope: Option<McpScope>,
+    /// Definition file of an agent or command
+    pub path: Option<String>,
+    /// Last use ever, if any
+    pub last_used: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnusedConfigReport {
+    pub days: u32,
+    pub items: Vec<UnusedItem>,
+    /// How many of each kind were checked
+    pub mcp_servers_checked: usize,
+    pub agents_checked: usize,
+    pub commands_checked: usize,
+}
+
+/// Claude Code names MCP tools `mcp__<server>__<tool>`, with characters other
+/// than letters, digits, `_` and `-` in the server name replaced by `_`
+fn normalize_server_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Last use of each MCP server, overall and per project
+#[derive(Default)]
+struct McpUsage {
+    global: HashMap<String, DateTime<Utc>>,
+    by_project: HashMap<(String, String), DateTime<Utc>>,
+}
+
+fn scan_mcp_usage() -> McpUsage {
+    let reader = UsageReader::new();
+    let mut usage = McpUsage::default();
+
+    for file in reader.find_jsonl_files() {
+        let project = reader.extract_project_path(&file);
+        let Ok(handle) = File::open(&file) else {
+            continue;
+        };
+        for line in BufReader::new(handle).lines().map_while(Result::ok) {
+            // Cheap check before parsing
+            if !line.contains("\"mcp__") {
+                continue;
+            }
+            let Ok(entry) = serde_json::from_str::<Value>(&line) else {
+                continue;
+            };
+            let Some(at) = entry
+                .get("timestamp")
+                .and_then(|t| t.as_str())
+                .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+                .map(|t| t.with_timezone(&Utc))
+            else {
+                continue;
+            };
+            let blocks = entry
+                .pointer("/message/content")
+                .and_then(|c| c.as_array())
+                .cloned()
+                .unwrap_or_default();
+            for block in blocks {
+                let Some(server) = block
+                    .get("name")
+                    .and_then(|n| n.as_str())
+                    .and_then(|n| n.strip_prefix("mcp__"))
+                    .and_then(|n| n.split("__").next())
+                else {
+                    continue;
+                };
+                let latest = usage.global.entry(server.to_string()).or_insert(at);
+                *latest = (*latest).max(at);
+                let latest = usage
+                    .by_project
+                    .entry((project.clone(), server.to_string()))
+                    .or_insert(at);
+                *latest = (*latest).max(at);
+            }
+        }
+    }
+    usage
+}
+
+/// Configured MCP servers, agents and commands unused in the last `days` days
+pub fn unused_config(days: u32) -> UnusedConfigReport {
+    let cutoff = Utc::now() - Duration::days(days as i64);
+    let mut items = Vec::new();
+
+    let servers = config::get_mcp_servers().unwrap_or_default();
+    let usage = scan_mcp_usage();
+    for server in &servers {
+        let key = normalize_server_name(&server.name);
+        let last_used = match &server.project_path {
+            Some(project) => usage.by_project.get(&(project.clone(), key)),
+            None => usage.global.get(&key),
+        };
+        if last_used.is_some_and(|t| *t >= cutoff) {
+            continue;
+        }
+        let scope = match &server.project_path {
+            Some(path) => McpScope::Project { path: path.clone() },
+            None => McpScope::Global,
+        };
+        items.push(UnusedItem {
+            id: format!(
+                "mcp:{}:{}",
+                server.project_path.as_deref().unwrap_or("global"),
+                server.name
+            ),
+            kind: UnusedKind::McpServer,
+            name: server.name.clone(),
+            scope: Some(scope),
+            path: None,
+            last_used: last_used.map(|t| t.to_rfc3339()),
+        });
+    }
+
+    // All-time stats so "last used" reaches back past the period
+    let invocations = invocations::invocation_stats(None);
+    let defined = |stats: &[invocations::InvocationStats]| -> usize {
+        stats.iter().filter(|s| s.defined_at.is_some()).count()
+    };
+    let (agents_checked, commands_checked) =
+        (defined(&invocations.agents), defined(&invocations.commands));
+    for stats in invocations.commands.iter().chain(&invocations.agents) {
+        let Some(path) = &stats.defined_at else {
+            continue;
+        };
+        let last_used = stats
+            .last_used
+            .as_deref()
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok());
+        if last_used.is_some_and(|t| t >= cutoff) {
+            continue;
+        }
+        let kind = match stats.kind {
+            InvocationKind::Command => UnusedKind::Command,
+            InvocationKind::Agent => UnusedKind::Agent,
+        };
+        items.push(UnusedItem {
+            id: format!("file:{}", path),
+            kind,
+            name: stats.name.clone(),
+            scope: None,
+            path: Some(path.clone()),
+            last_used: stats.last_used.clone(),
+        });
+    }
+
+    UnusedConfigReport {
+        days,
+        items,
+        mcp_servers_checked: servers.len(),
+        agents_checked,
+        commands_checked,
+    }
+}
+
+/// Turn off the unused items with the given ids. Only items the report for
+/// `days` still lists are touched.
+pub fn disable(ids: &[String], days: u32, dry_run: bool) -> Result<Vec<FileOperation>, String> {
+    let ids: HashSet<&str> = ids.iter().map(|id| id.as_str()).collect();
+    let report = unused_config(days);
+    let mut operations = Vec::new();
+    let mut plan = FilePlan::new("disable_unused_config");
+
+    for item in report.items.iter().filter(|i| ids.contains(i.id.as_str())) {
+        match (&item.scope, &item.path) {
+            (Some(scope), _) => {
+                let pattern = glob::Pattern::escape(&item.name);
+                let result = config::bulk_remove_mcp_servers(
+                    &pattern,
+                    Some(std::slice::from_ref(scope)),
+                    dry_run,
+                )?;
+                operations.extend(result.operations);
+            }
+            (None, Some(path)) => {
+                let path = Path::new(path);
+                let contents = fs::read(path)
+                    .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+                let disabled = path.with_extension("md.disabled");
+                plan.write(&disabled, contents, &format!("disable {}", item.name));
+                plan.remove(path, &format!("disable {}", item.name));
+            }
+            (None, None) => {}
+        }
+    }
+
+    operations.extend(plan.run(dry_run)?);
+    Ok(operations)
+}
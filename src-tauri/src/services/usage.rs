@@ -1,24 +1,24 @@
-use crate::types::{RawLogEntry, UsageEntry};
+use super::{duplicates, path_mapping, paths};
+use super::pricing::calculate_cost;
+use crate::types::{
+    ApiErrorEntry, ApiErrorKind, CompactionEvent, ContentBlock, PathMapping, RawLogEntry,
+    UsageEntry,
+};
 use chrono::{DateTime, Utc};
 use glob::glob;
 use std::collections::{HashMap, HashSet};
 use std::fs::File;
-use std::io::{BufRead, BufReader, Read};
-use std::path::PathBuf;
-
-/// Encode a path to its folder name (same logic Claude uses)
-fn encode_path_to_folder(path: &str) -> String {
-    path.replace('/', "-")
-}
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
 /// Build a lookup map from encoded folder names to actual project paths
 fn build_project_path_map() -> HashMap<String, String> {
     let mut map = HashMap::new();
 
     // Read .claude.json to get registered projects
-    let claude_json_path = dirs::home_dir()
-        .map(|h| h.join(".claude.json"))
-        .unwrap_or_else(|| PathBuf::from("/.claude.json"));
+    let claude_json_path = paths::current().claude_json().to_path_buf();
 
     if let Ok(mut file) = File::open(&claude_json_path) {
         let mut contents = String::new();
@@ -27,7 +27,7 @@ fn build_project_path_map() -> HashMap<String, String> {
                 // Extract project paths from projects object
                 if let Some(projects) = json.get("projects").and_then(|p| p.as_object()) {
                     for (path, _) in projects {
-                        let encoded = encode_path_to_folder(path);
+                        let encoded = paths::encode_path_to_folder(path);
                         map.insert(encoded, path.clone());
                     }
                 }
@@ -38,24 +38,109 @@ fn build_project_path_map() -> HashMap<String, String> {
     map
 }
 
+/// Classify an API error from its message or error payload
+fn classify_api_error(text: &str) -> ApiErrorKind {
+    let text = text.to_lowercase();
+    if text.contains("overloaded") || text.contains("529") {
+        ApiErrorKind::Overloaded
+    } else if text.contains("rate_limit") || text.contains("rate limit") || text.contains("429") {
+        ApiErrorKind::RateLimited
+    } else if ["500", "502", "503", "504", "api_error", "internal server error"]
+        .iter()
+        .any(|needle| text.contains(needle))
+    {
+        ApiErrorKind::ServerError
+    } else {
+        ApiErrorKind::Other
+    }
+}
+
+/// Encoded folder name -> project path, with the .claude.json it was read from
+type ProjectPathMap = (PathBuf, Arc<HashMap<String, String>>);
+
+/// A session file parsed up to `offset`
+#[derive(Default)]
+struct IndexedFile {
+    offset: u64,
+    modified: Option<SystemTime>,
+    /// Project the entries were attributed to when parsed
+    project: String,
+    /// Every usage entry in the file, before deduplication and date filters
+    entries: Arc<Vec<UsageEntry>>,
+    /// `fingerprint` of the indexed bytes, to notice a file rewritten in place
+    fingerprint: Vec<u8>,
+}
+
+/// Bytes at either end of the indexed part of a file compared by `fingerprint`
+const FINGERPRINT_BYTES: u64 = 128;
+
+/// The first and last `FINGERPRINT_BYTES` of a file's first `len` bytes
+fn fingerprint(path: &Path, len: u64) -> Option<Vec<u8>> {
+    let mut file = File::open(path).ok()?;
+    let mut bytes = Vec::new();
+    (&mut file).take(len.min(FINGERPRINT_BYTES)).read_to_end(&mut bytes).ok()?;
+    let tail_start = len.saturating_sub(FINGERPRINT_BYTES).max(FINGERPRINT_BYTES);
+    if tail_start < len {
+        file.seek(SeekFrom::Start(tail_start)).ok()?;
+        file.take(len - tail_start).read_to_end(&mut bytes).ok()?;
+    }
+    Some(bytes)
+}
+
+/// Parsed session files shared by all readers, so `read_entries` only parses
+/// lines appended since the previous call
+#[derive(Default)]
+struct EntryIndex {
+    /// Mappings the entries' projects and cwds were translated with
+    path_mappings: Vec<PathMapping>,
+    files: HashMap<PathBuf, IndexedFile>,
+}
+
+static ENTRY_INDEX: Mutex<Option<EntryIndex>> = Mutex::new(None);
+
+/// Drop the parsed session files; the next read parses them again
+pub fn clear_entry_index() {
+    *ENTRY_INDEX.lock().unwrap() = None;
+}
+
 /// Reads and parses Claude Code usage logs from JSONL files
 pub struct UsageReader {
-    claude_dir: PathBuf,
-    project_path_map: HashMap<String, String>,
+    /// Built on first use - reading ~/.claude.json is too slow for every reader.
+    /// Keyed by the .claude.json it was read from, so switching profiles rebuilds it.
+    project_path_map: Mutex<Option<ProjectPathMap>>,
+    /// Container/remote path prefixes translated to host folders
+    path_mappings: Vec<PathMapping>,
 }
 
 impl UsageReader {
     pub fn new() -> Self {
-        let claude_dir = dirs::home_dir()
-            .map(|h| h.join(".claude").join("projects"))
-            .unwrap_or_else(|| PathBuf::from("/tmp/.claude/projects"));
-        let project_path_map = build_project_path_map();
-        Self { claude_dir, project_path_map }
+        Self {
+            project_path_map: Mutex::new(None),
+            path_mappings: path_mapping::current(),
+        }
+    }
+
+    /// Session log directory of the current Claude data location
+    fn claude_dir(&self) -> PathBuf {
+        paths::current().projects_dir()
+    }
+
+    fn project_path_map(&self) -> Arc<HashMap<String, String>> {
+        let claude_json = paths::current().claude_json().to_path_buf();
+        let mut cache = self.project_path_map.lock().unwrap();
+        match cache.as_ref() {
+            Some((path, map)) if *path == claude_json => map.clone(),
+            _ => {
+                let map = Arc::new(build_project_path_map());
+                *cache = Some((claude_json, map.clone()));
+                map
+            }
+        }
     }
 
     /// Find all JSONL files in the Claude projects directory
-    fn find_jsonl_files(&self) -> Vec<PathBuf> {
-        let pattern = self.claude_dir.join("**").join("*.jsonl");
+    pub fn find_jsonl_files(&self) -> Vec<PathBuf> {
+        let pattern = self.claude_dir().join("**").join("*.jsonl");
         let pattern_str = pattern.to_string_lossy();
 
         let mut files: Vec<PathBuf> = match glob(&pattern_str) {
@@ -76,18 +161,41 @@ impl UsageReader {
         files
     }
 
+    /// Session files modified within the given window (newest first)
+    pub fn recently_modified_files(&self, within: chrono::Duration) -> Vec<PathBuf> {
+        let Ok(within) = within.to_std() else {
+            return Vec::new();
+        };
+        let is_recent = |path: &PathBuf| {
+            path.metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .is_some_and(|age| age <= within)
+        };
+
+        self.find_jsonl_files().into_iter().take_while(is_recent).collect()
+    }
+
     /// Extract project path from file path
     /// Returns the actual path (e.g., "/Users/foo/Development/my-project")
-    /// by looking up the encoded folder name in the project map
-    fn extract_project_path(&self, file_path: &PathBuf) -> String {
+    /// by looking up the encoded folder name in the project map.
+    /// Container paths are translated to the host through the path mappings.
+    pub fn extract_project_path(&self, file_path: &Path) -> String {
         // Path structure: ~/.claude/projects/{encoded-path}/{file}.jsonl
         if let Some(parent) = file_path.parent() {
             if let Some(folder_name) = parent.file_name() {
                 let folder_str = folder_name.to_string_lossy().to_string();
 
                 // Look up the actual path in our map
-                if let Some(actual_path) = self.project_path_map.get(&folder_str) {
-                    return actual_path.clone();
+                let project_path_map = self.project_path_map();
+                if let Some(actual_path) = project_path_map.get(&folder_str) {
+                    return path_mapping::to_host(&self.path_mappings, actual_path.clone());
+                }
+
+                // A session folder named after a mapped container path
+                if let Some(host_path) = path_mapping::map_folder(&self.path_mappings, &folder_str) {
+                    return host_path;
                 }
 
                 // Fallback: return the encoded folder name if not in map
@@ -98,7 +206,7 @@ impl UsageReader {
     }
 
     /// Parse a single JSONL line
-    fn parse_line(&self, line: &str, project: &str) -> Option<UsageEntry> {
+    pub fn parse_line(&self, line: &str, project: &str) -> Option<UsageEntry> {
         let raw: RawLogEntry = serde_json::from_str(line).ok()?;
 
         // Only process assistant messages with usage data
@@ -113,6 +221,17 @@ impl UsageReader {
 
         let usage = message.usage?;
         let model = message.model?;
+        let tools = message
+            .content
+            .into_iter()
+            .flatten()
+            .filter_map(|block| match block {
+                ContentBlock::ToolUse {
+                    block_type, name, ..
+                } if block_type == "tool_use" => name,
+                _ => None,
+            })
+            .collect();
 
         // Parse timestamp
         let timestamp = raw.timestamp.as_ref().and_then(|ts| {
@@ -131,63 +250,332 @@ impl UsageReader {
             cache_read_tokens: usage.cache_read_input_tokens.unwrap_or(0),
             uuid: raw.uuid.unwrap_or_default(),
             project: project.to_string(),
+            ttft_ms: raw.ttft_ms,
+            duration_ms: raw.duration_ms,
+            message_id: message.id,
+            service_tier: usage.service_tier,
+            cwd: raw.cwd.map(|cwd| path_mapping::to_host(&self.path_mappings, cwd)),
+            tools,
         })
     }
 
-    /// Read all usage entries, optionally filtered by days
+    /// Read all usage entries, optionally filtered by days.
+    /// Duplicate session files the user ignored are skipped; merged ones are read
+    /// last and only contribute responses the primary session doesn't have.
     pub fn read_entries(&self, days: Option<u32>) -> Vec<UsageEntry> {
-        let files = self.find_jsonl_files();
+        let rules = duplicates::rules();
+        let mut files: Vec<PathBuf> = self
+            .find_jsonl_files()
+            .into_iter()
+            .filter(|file| !rules.ignored.contains(file))
+            .collect();
+        files.sort_by_key(|file| rules.merged.contains_key(file));
+
         let mut entries = Vec::new();
         let mut seen_uuids: HashSet<String> = HashSet::new();
+        let mut seen_message_ids: HashSet<String> = HashSet::new();
 
         // Calculate cutoff date if days is specified
         let cutoff = days.map(|d| Utc::now() - chrono::Duration::days(d as i64));
 
-        for file_path in files {
-            let project = self.extract_project_path(&file_path);
+        let indexed = self.indexed_entries(&files);
+        for (file_path, file_entries) in files.iter().zip(indexed) {
+            let merged_into = rules.merged.get(file_path);
+
+            for entry in file_entries.iter() {
+                // Apply date filter
+                if let Some(cutoff_date) = cutoff {
+                    if entry.timestamp < cutoff_date {
+                        continue;
+                    }
+                }
+
+                // Deduplicate by UUID
+                if !entry.uuid.is_empty() {
+                    if seen_uuids.contains(&entry.uuid) {
+                        continue;
+                    }
+                    seen_uuids.insert(entry.uuid.clone());
+                }
+
+                let mut entry = entry.clone();
+                if let Some(primary_session) = merged_into {
+                    if entry.message_id.as_ref().is_some_and(|id| seen_message_ids.contains(id)) {
+                        continue;
+                    }
+                    entry.session_id = primary_session.clone();
+                } else if let Some(id) = &entry.message_id {
+                    seen_message_ids.insert(id.clone());
+                }
+
+                entries.push(entry);
+            }
+        }
+
+        // Sort by timestamp (oldest first)
+        entries.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+
+        entries
+    }
+
+    /// Parsed entries of each file. Files are parsed once and afterwards only
+    /// the lines appended since the previous call are read.
+    fn indexed_entries(&self, files: &[PathBuf]) -> Vec<Arc<Vec<UsageEntry>>> {
+        let mut index = ENTRY_INDEX.lock().unwrap();
+        let index = index.get_or_insert_with(EntryIndex::default);
+        if index.path_mappings != self.path_mappings {
+            index.files.clear();
+            index.path_mappings = self.path_mappings.clone();
+        }
+        // Forget deleted files (and those of another profile)
+        let wanted: HashSet<&PathBuf> = files.iter().collect();
+        index.files.retain(|path, _| wanted.contains(path));
+
+        files
+            .iter()
+            .map(|path| {
+                let project = self.extract_project_path(path);
+                let indexed = index.files.entry(path.clone()).or_default();
+                // Attributed to another project now (e.g. newly registered); parse again
+                if indexed.project != project {
+                    *indexed = IndexedFile {
+                        project,
+                        ..Default::default()
+                    };
+                }
+                self.index_appended(path, indexed);
+                indexed.entries.clone()
+            })
+            .collect()
+    }
 
-            let file = match File::open(&file_path) {
-                Ok(f) => f,
-                Err(_) => continue,
+    /// Parse the lines appended to a session file since it was last indexed
+    fn index_appended(&self, path: &Path, indexed: &mut IndexedFile) {
+        let Ok(metadata) = path.metadata() else {
+            return;
+        };
+        let modified = metadata.modified().ok();
+        if metadata.len() == indexed.offset && modified == indexed.modified {
+            return;
+        }
+        // Rewritten rather than appended to (shorter, or the indexed bytes changed); start over
+        if metadata.len() < indexed.offset
+            || fingerprint(path, indexed.offset).as_ref() != Some(&indexed.fingerprint)
+        {
+            indexed.offset = 0;
+            indexed.entries = Arc::default();
+        }
+        indexed.modified = modified;
+
+        if let Some((entries, offset)) = self.read_appended(path, indexed.offset, &indexed.project) {
+            Arc::make_mut(&mut indexed.entries).extend(entries);
+            indexed.offset = offset;
+            indexed.fingerprint = fingerprint(path, offset).unwrap_or_default();
+        }
+    }
+
+    /// Usage entries in the lines of a session file from byte `offset` on, with
+    /// the offset reading stopped at (before a line that is still being written).
+    /// Lines that aren't UTF-8 or JSON are skipped.
+    pub fn read_appended(
+        &self,
+        path: &Path,
+        offset: u64,
+        project: &str,
+    ) -> Option<(Vec<UsageEntry>, u64)> {
+        let mut file = File::open(path).ok()?;
+        file.seek(SeekFrom::Start(offset)).ok()?;
+
+        let mut entries = Vec::new();
+        let mut offset = offset;
+        let mut reader = BufReader::new(file);
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            let Ok(read) = reader.read_until(b'\n', &mut line) else {
+                break;
             };
+            if read == 0 {
+                break;
+            }
+            // A last line without a newline may still be being written
+            if !line.ends_with(b"\n") && serde_json::from_slice::<serde_json::Value>(&line).is_err() {
+                break;
+            }
+            offset += read as u64;
 
-            let reader = BufReader::new(file);
+            let Ok(line) = std::str::from_utf8(&line) else {
+                continue;
+            };
+            if let Some(entry) = self.parse_line(line.trim_end(), project) {
+                entries.push(entry);
+            }
+        }
+        Some((entries, offset))
+    }
 
-            for line in reader.lines() {
-                let line = match line {
-                    Ok(l) => l,
-                    Err(_) => continue,
-                };
+    /// Read failed API requests, optionally filtered by days (oldest first).
+    /// Both retried attempts (system "api_error" entries) and the final error
+    /// message shown to the user count as one error each.
+    pub fn read_api_errors(&self, days: Option<u32>) -> Vec<ApiErrorEntry> {
+        self.read_api_errors_in(&self.find_jsonl_files(), days)
+    }
 
+    /// Read failed API requests from the given session files
+    pub fn read_api_errors_in(&self, files: &[PathBuf], days: Option<u32>) -> Vec<ApiErrorEntry> {
+        let cutoff = days.map(|d| Utc::now() - chrono::Duration::days(d as i64));
+        let mut errors = Vec::new();
+        let mut seen_uuids: HashSet<String> = HashSet::new();
+
+        for file_path in files {
+            let Ok(file) = File::open(file_path) else {
+                continue;
+            };
+            let project = self.extract_project_path(file_path);
+            // Error messages carry a placeholder model - attribute them to the last real one
+            let mut last_model: Option<String> = None;
+
+            for line in BufReader::new(file).lines().map_while(Result::ok) {
                 if line.trim().is_empty() {
                     continue;
                 }
+                let Ok(raw) = serde_json::from_str::<RawLogEntry>(&line) else {
+                    continue;
+                };
 
-                if let Some(entry) = self.parse_line(&line, &project) {
-                    // Apply date filter
-                    if let Some(cutoff_date) = cutoff {
-                        if entry.timestamp < cutoff_date {
-                            continue;
+                let message_text = raw.message.as_ref().and_then(|m| m.content.as_ref()).map(|blocks| {
+                    blocks.iter().filter_map(|b| b.as_text()).collect::<Vec<_>>().join(" ")
+                });
+
+                let error_text = if raw.is_api_error_message == Some(true) {
+                    message_text.unwrap_or_default()
+                } else if raw.entry_type.as_deref() == Some("system")
+                    && raw.subtype.as_deref() == Some("api_error")
+                {
+                    raw.error.as_ref().map(|e| e.to_string()).unwrap_or_default()
+                } else {
+                    if let Some(model) = raw.message.as_ref().and_then(|m| m.model.as_ref()) {
+                        if !model.starts_with('<') {
+                            last_model = Some(model.clone());
                         }
                     }
+                    continue;
+                };
+
+                let Some(timestamp) = raw.timestamp.as_ref().and_then(|ts| {
+                    DateTime::parse_from_rfc3339(ts)
+                        .ok()
+                        .map(|dt| dt.with_timezone(&Utc))
+                }) else {
+                    continue;
+                };
+                if cutoff.is_some_and(|cutoff| timestamp < cutoff) {
+                    continue;
+                }
+                if let Some(uuid) = &raw.uuid {
+                    if !seen_uuids.insert(uuid.clone()) {
+                        continue;
+                    }
+                }
+
+                errors.push(ApiErrorEntry {
+                    timestamp,
+                    session_id: raw.session_id.unwrap_or_default(),
+                    model: last_model.clone().unwrap_or_else(|| "unknown".to_string()),
+                    kind: classify_api_error(&error_text),
+                    project: project.clone(),
+                });
+            }
+        }
 
-                    // Deduplicate by UUID
-                    if !entry.uuid.is_empty() {
-                        if seen_uuids.contains(&entry.uuid) {
-                            continue;
+        errors.sort_by_key(|error| error.timestamp);
+        errors
+    }
+
+    /// Read context compactions, optionally filtered by days (oldest first).
+    /// A compaction is a "compact_boundary" system entry, or a compact summary
+    /// message without a preceding boundary (older Claude Code versions).
+    pub fn read_compactions(&self, days: Option<u32>) -> Vec<CompactionEvent> {
+        let cutoff = days.map(|d| Utc::now() - chrono::Duration::days(d as i64));
+        let mut events = Vec::new();
+
+        for file_path in self.find_jsonl_files() {
+            let Ok(file) = File::open(&file_path) else {
+                continue;
+            };
+            let project = self.extract_project_path(&file_path);
+            let mut file_events: Vec<CompactionEvent> = Vec::new();
+            // Context size of the latest response, and whether the last event awaits its "after" size
+            let mut last_context: Option<u64> = None;
+            let mut awaiting_after = false;
+            let mut boundary_seen = false;
+
+            for line in BufReader::new(file).lines().map_while(Result::ok) {
+                if line.trim().is_empty() {
+                    continue;
+                }
+
+                if let Some(entry) = self.parse_line(&line, &project) {
+                    if entry.model.starts_with('<') {
+                        continue;
+                    }
+                    let context =
+                        entry.input_tokens + entry.cache_creation_tokens + entry.cache_read_tokens;
+                    if awaiting_after {
+                        if let Some(event) = file_events.last_mut() {
+                            event.tokens_after = Some(context);
                         }
-                        seen_uuids.insert(entry.uuid.clone());
+                        awaiting_after = false;
                     }
+                    last_context = Some(context);
+                    boundary_seen = false;
+                    continue;
+                }
 
-                    entries.push(entry);
+                let Ok(raw) = serde_json::from_str::<RawLogEntry>(&line) else {
+                    continue;
+                };
+                let is_boundary = raw.entry_type.as_deref() == Some("system")
+                    && raw.subtype.as_deref() == Some("compact_boundary");
+                let is_summary = raw.is_compact_summary == Some(true);
+
+                // The summary message right after a boundary belongs to the same compaction
+                if !(is_boundary || (is_summary && !boundary_seen)) {
+                    continue;
                 }
+                boundary_seen = is_boundary;
+
+                let Some(timestamp) = raw.timestamp.as_ref().and_then(|ts| {
+                    DateTime::parse_from_rfc3339(ts)
+                        .ok()
+                        .map(|dt| dt.with_timezone(&Utc))
+                }) else {
+                    continue;
+                };
+
+                let metadata = raw.compact_metadata.as_ref();
+                file_events.push(CompactionEvent {
+                    timestamp,
+                    session_id: raw.session_id.clone().unwrap_or_default(),
+                    project: project.clone(),
+                    trigger: metadata
+                        .and_then(|m| m.trigger.clone())
+                        .unwrap_or_else(|| "unknown".to_string()),
+                    tokens_before: metadata.and_then(|m| m.pre_tokens).or(last_context),
+                    tokens_after: None,
+                });
+                awaiting_after = true;
             }
-        }
 
-        // Sort by timestamp (oldest first)
-        entries.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+            events.extend(
+                file_events
+                    .into_iter()
+                    .filter(|e| cutoff.is_none_or(|cutoff| e.timestamp >= cutoff)),
+            );
+        }
 
-        entries
+        events.sort_by_key(|event| event.timestamp);
+        events
     }
 
     /// Read all entries (for total stats)
@@ -195,69 +583,287 @@ impl UsageReader {
         self.read_entries(None)
     }
 
+    /// Read the usage entries of a single session file (deduplicated, oldest first)
+    pub fn read_session_entries(&self, file_path: &Path) -> Vec<UsageEntry> {
+        let file = match File::open(file_path) {
+            Ok(f) => f,
+            Err(_) => return Vec::new(),
+        };
+
+        let project = self.extract_project_path(file_path);
+        let mut seen_uuids: HashSet<String> = HashSet::new();
+
+        let mut entries: Vec<UsageEntry> = BufReader::new(file)
+            .lines()
+            .map_while(Result::ok)
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| self.parse_line(&line, &project))
+            .filter(|entry| entry.uuid.is_empty() || seen_uuids.insert(entry.uuid.clone()))
+            .collect();
+
+        entries.sort_by_key(|entry| entry.timestamp);
+        entries
+    }
+
+    /// Total cost of a session file, optionally only counting usage since a point in time
+    pub fn session_cost(&self, file_path: &Path, since: Option<DateTime<Utc>>) -> f64 {
+        self.read_session_entries(file_path)
+            .iter()
+            .filter(|entry| since.is_none_or(|since| entry.timestamp >= since))
+            .map(|entry| {
+                calculate_cost(
+                    &entry.model,
+                    Some(entry.timestamp),
+                    entry.service_tier.as_deref(),
+                    entry.input_tokens,
+                    entry.output_tokens,
+                    entry.cache_creation_tokens,
+                    entry.cache_read_tokens,
+                )
+            })
+            .sum()
+    }
+
     /// Get the latest assistant text response (for notifications)
     /// Returns an excerpt of the most recent assistant message's text content
     pub fn get_latest_response(&self, max_chars: usize) -> Option<String> {
         let files = self.find_jsonl_files();
 
         // Check the most recently modified files first
-        for file_path in files.iter().take(5) {
-            let file = match File::open(file_path) {
-                Ok(f) => f,
-                Err(_) => continue,
-            };
+        files
+            .iter()
+            .take(5)
+            .find_map(|file_path| latest_response_in_file(file_path, max_chars))
+    }
 
-            let reader = BufReader::new(file);
-            let lines: Vec<String> = reader.lines().filter_map(|l| l.ok()).collect();
+    /// Get the latest assistant response for a specific session.
+    /// Resolves the session file from the hook's transcript path, or from the
+    /// session's cwd and id, falling back to a search across all projects.
+    pub fn get_session_response(
+        &self,
+        transcript_path: Option<&str>,
+        session_id: Option<&str>,
+        cwd: Option<&str>,
+        max_chars: usize,
+    ) -> Option<String> {
+        let session_file = self.find_session_file(transcript_path, session_id, cwd)?;
+        latest_response_in_file(&session_file, max_chars)
+    }
 
-            // Read from the end to find the latest assistant message with text
-            for line in lines.iter().rev() {
-                if line.trim().is_empty() {
-                    continue;
-                }
+    /// Locate the JSONL file for a session
+    pub fn find_session_file(
+        &self,
+        transcript_path: Option<&str>,
+        session_id: Option<&str>,
+        cwd: Option<&str>,
+    ) -> Option<PathBuf> {
+        if let Some(path) = transcript_path.map(PathBuf::from) {
+            if path.is_file() {
+                return Some(path);
+            }
+        }
 
-                if let Ok(raw) = serde_json::from_str::<RawLogEntry>(line) {
-                    // Only process assistant messages
-                    if raw.entry_type.as_deref() != Some("assistant") {
-                        continue;
-                    }
+        let session_id = session_id?;
+        let file_name = format!("{}.jsonl", session_id);
+
+        if let Some(cwd) = cwd {
+            let candidate = self
+                .claude_dir()
+                .join(paths::encode_path_to_folder(cwd))
+                .join(&file_name);
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+        }
 
-                    if let Some(message) = raw.message {
-                        if message.role.as_deref() != Some("assistant") {
-                            continue;
+        // Session may have been started from a different directory than its project root
+        self.find_jsonl_files()
+            .into_iter()
+            .find(|path| path.file_name().is_some_and(|name| name.to_string_lossy() == file_name))
+    }
+}
+
+/// Find the latest response in a single JSONL file: the last natural-language
+/// paragraph of the current turn, or a summary of its tool uses when Claude
+/// wrote no prose (e.g. "Edited 3 files, ran 2 commands")
+fn latest_response_in_file(file_path: &Path, max_chars: usize) -> Option<String> {
+    let file = File::open(file_path).ok()?;
+    let reader = BufReader::new(file);
+    let lines: Vec<String> = reader.lines().map_while(Result::ok).collect();
+    let mut tools = TurnTools::default();
+
+    // Read from the end back to the prompt that started the turn
+    for line in lines.iter().rev() {
+        let Ok(entry) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        match entry["type"].as_str() {
+            Some("user") if is_prompt(&entry) => break,
+            Some("assistant") => {
+                let blocks = entry["message"]["content"].as_array();
+                for block in blocks.into_iter().flatten().rev() {
+                    match block["type"].as_str() {
+                        Some("text") => {
+                            let text = block["text"].as_str().unwrap_or_default();
+                            if let Some(excerpt) = prose_excerpt(text, max_chars) {
+                                return Some(excerpt);
+                            }
                         }
+                        Some("tool_use") => tools.record(block),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
 
-                        // Extract text content
-                        if let Some(content) = message.content {
-                            let text_parts: Vec<&str> = content
-                                .iter()
-                                .filter_map(|block| block.as_text())
-                                .collect();
+    tools.summary()
+}
 
-                            if !text_parts.is_empty() {
-                                let full_text = text_parts.join(" ");
-                                let trimmed = full_text.trim();
+/// A user entry the user typed (rather than a tool result or injected context)
+fn is_prompt(entry: &serde_json::Value) -> bool {
+    if entry["isMeta"].as_bool() == Some(true) {
+        return false;
+    }
+    match &entry["message"]["content"] {
+        serde_json::Value::String(_) => true,
+        serde_json::Value::Array(blocks) => blocks.iter().any(|b| b["type"] == "text"),
+        _ => false,
+    }
+}
+
+/// The last natural-language paragraph of a response, without code blocks,
+/// tables, headings or markdown markup, truncated to `max_chars`
+fn prose_excerpt(text: &str, max_chars: usize) -> Option<String> {
+    let mut paragraphs = Vec::new();
+    let mut current: Vec<String> = Vec::new();
+    let mut in_fence = false;
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        let skip = if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            true
+        } else {
+            in_fence
+                || trimmed.is_empty()
+                || trimmed.starts_with('#')
+                || trimmed.starts_with('|')
+                || trimmed.chars().all(|c| matches!(c, '-' | '*' | '_' | '='))
+        };
+        if skip {
+            if !current.is_empty() {
+                paragraphs.push(current.join(" "));
+                current.clear();
+            }
+        } else {
+            current.push(strip_markdown(trimmed));
+        }
+    }
+    if !current.is_empty() {
+        paragraphs.push(current.join(" "));
+    }
 
-                                if trimmed.is_empty() {
-                                    continue;
-                                }
+    let paragraph = paragraphs.into_iter().rev().find(|p| is_prose(p))?;
+    Some(super::format::excerpt(&paragraph, max_chars))
+}
 
-                                // Truncate to max_chars with ellipsis
-                                let excerpt = if trimmed.len() > max_chars {
-                                    format!("{}...", &trimmed[..max_chars])
-                                } else {
-                                    trimmed.to_string()
-                                };
+/// Remove list markers, quotes, emphasis, inline code and link targets from a line
+fn strip_markdown(line: &str) -> String {
+    let mut line = line.trim_start_matches('>').trim_start();
+    for marker in ["- ", "* ", "+ "] {
+        line = line.strip_prefix(marker).unwrap_or(line);
+    }
+    if let Some((number, rest)) = line.split_once(". ") {
+        if !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) {
+            line = rest;
+        }
+    }
 
-                                return Some(excerpt);
-                            }
-                        }
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' | '`' => {}
+            '_' if chars.peek() == Some(&'_') => {
+                chars.next();
+            }
+            // [text](url) keeps only the text
+            ']' if chars.peek() == Some(&'(') => {
+                for c in chars.by_ref() {
+                    if c == ')' {
+                        break;
                     }
                 }
             }
+            '[' => {}
+            _ => out.push(c),
+        }
+    }
+    out.trim().to_string()
+}
+
+/// Reads like a sentence: a few words, mostly letters
+fn is_prose(text: &str) -> bool {
+    let letters = text.chars().filter(|c| c.is_alphabetic()).count();
+    let visible = text.chars().filter(|c| !c.is_whitespace()).count();
+    text.split_whitespace().count() >= 2 && letters * 2 >= visible
+}
+
+/// Tool uses in one turn, for a summary when the response has no prose
+#[derive(Default)]
+struct TurnTools {
+    edited: HashSet<String>,
+    read: HashSet<String>,
+    commands: usize,
+    searches: usize,
+    agents: usize,
+    other: usize,
+}
+
+impl TurnTools {
+    fn record(&mut self, block: &serde_json::Value) {
+        let input = &block["input"];
+        let file = input["file_path"]
+            .as_str()
+            .or(input["notebook_path"].as_str())
+            .unwrap_or_default()
+            .to_string();
+        match block["name"].as_str().unwrap_or_default() {
+            "Edit" | "MultiEdit" | "Write" | "NotebookEdit" => {
+                self.edited.insert(file);
+            }
+            "Read" => {
+                self.read.insert(file);
+            }
+            "Bash" => self.commands += 1,
+            "Grep" | "Glob" | "WebSearch" | "WebFetch" => self.searches += 1,
+            "Task" => self.agents += 1,
+            _ => self.other += 1,
         }
+    }
 
-        None
+    fn summary(&self) -> Option<String> {
+        let count = |n: usize, one: &str, many: &str| {
+            (n > 0).then(|| format!("{} {}", n, if n == 1 { one } else { many }))
+        };
+        let parts: Vec<String> = [
+            count(self.edited.len(), "file", "files").map(|c| format!("edited {}", c)),
+            count(self.commands, "command", "commands").map(|c| format!("ran {}", c)),
+            count(self.read.len(), "file", "files").map(|c| format!("read {}", c)),
+            count(self.searches, "search", "searches").map(|c| format!("ran {}", c)),
+            count(self.agents, "agent", "agents").map(|c| format!("started {}", c)),
+            count(self.other, "other tool", "other tools").map(|c| format!("used {}", c)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        let summary = parts.join(", ");
+        let mut chars = summary.chars();
+        let first = chars.next()?;
+        Some(first.to_uppercase().chain(chars).collect())
     }
 }
 
@@ -271,9 +877,110 @@ impl Default for UsageReader {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_classify_api_error() {
+        assert_eq!(
+            classify_api_error(r#"API Error: 529 {"type":"error","error":{"type":"overloaded_error"}}"#),
+            ApiErrorKind::Overloaded
+        );
+        assert_eq!(
+            classify_api_error(r#"{"type":"rate_limit_error","message":"Number of requests..."}"#),
+            ApiErrorKind::RateLimited
+        );
+        assert_eq!(classify_api_error("API Error: 500 Internal server error"), ApiErrorKind::ServerError);
+        assert_eq!(classify_api_error("Invalid API key · Please run /login"), ApiErrorKind::Other);
+    }
+
+    #[test]
+    fn test_prose_excerpt() {
+        let text = "Here is the fix:\n\n```rust\nfn main() {}\n```\n\n| a | b |\n\nI updated **`main.rs`** and [the docs](https://example.com).";
+        assert_eq!(
+            prose_excerpt(text, 120).as_deref(),
+            Some("I updated main.rs and the docs.")
+        );
+        assert_eq!(prose_excerpt("```\nls -la\n```", 120), None);
+
+        let mut tools = TurnTools::default();
+        for block in [
+            serde_json::json!({"name": "Edit", "input": {"file_path": "/a.rs"}}),
+            serde_json::json!({"name": "Write", "input": {"file_path": "/b.rs"}}),
+            serde_json::json!({"name": "Edit", "input": {"file_path": "/a.rs"}}),
+            serde_json::json!({"name": "Bash", "input": {"command": "cargo test"}}),
+        ] {
+            tools.record(&block);
+        }
+        assert_eq!(tools.summary().as_deref(), Some("Edited 2 files, ran 1 command"));
+    }
+
+    fn usage_line(uuid: &str) -> String {
+        serde_json::json!({
+            "type": "assistant",
+            "uuid": uuid,
+            "sessionId": "s1",
+            "timestamp": "2025-01-01T00:00:00Z",
+            "message": {
+                "role": "assistant",
+                "model": "claude-sonnet-4-20250514",
+                "usage": { "input_tokens": 10, "output_tokens": 5 }
+            }
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_index_appended() {
+        use std::io::Write;
+
+        let dir = std::env::temp_dir().join(format!("claudit-usage-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session.jsonl");
+        let append = |bytes: &[u8]| {
+            let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&path).unwrap();
+            file.write_all(bytes).unwrap();
+        };
+        let uuids = |indexed: &IndexedFile| -> Vec<String> {
+            indexed.entries.iter().map(|e| e.uuid.clone()).collect()
+        };
+        let reader = UsageReader::new();
+        let mut indexed = IndexedFile::default();
+
+        // Appended lines
+        append(format!("{}\n{}\n", usage_line("a"), usage_line("b")).as_bytes());
+        reader.index_appended(&path, &mut indexed);
+        assert_eq!(uuids(&indexed), ["a", "b"]);
+
+        // A line still being written waits for its newline
+        let line = usage_line("c");
+        let (start, rest) = line.split_at(20);
+        append(start.as_bytes());
+        reader.index_appended(&path, &mut indexed);
+        assert_eq!(uuids(&indexed), ["a", "b"]);
+        append(format!("{}\n", rest).as_bytes());
+        reader.index_appended(&path, &mut indexed);
+        assert_eq!(uuids(&indexed), ["a", "b", "c"]);
+
+        // Lines that aren't UTF-8 or JSON are skipped, not stopped at
+        append(b"\xff\xfe not utf-8\n{not json\n");
+        append(format!("{}\n", usage_line("d")).as_bytes());
+        reader.index_appended(&path, &mut indexed);
+        assert_eq!(uuids(&indexed), ["a", "b", "c", "d"]);
+        reader.index_appended(&path, &mut indexed);
+        assert_eq!(uuids(&indexed), ["a", "b", "c", "d"]);
+
+        // Truncated, and rewritten to a longer file with other contents
+        std::fs::write(&path, format!("{}\n", usage_line("e"))).unwrap();
+        reader.index_appended(&path, &mut indexed);
+        assert_eq!(uuids(&indexed), ["e"]);
+        std::fs::write(&path, format!("{}\n{}\n", usage_line("f"), usage_line("g"))).unwrap();
+        reader.index_appended(&path, &mut indexed);
+        assert_eq!(uuids(&indexed), ["f", "g"]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn test_usage_reader_creation() {
         let reader = UsageReader::new();
-        assert!(reader.claude_dir.to_string_lossy().contains(".claude/projects"));
+        assert!(reader.claude_dir().to_string_lossy().contains(".claude/projects"));
     }
 }
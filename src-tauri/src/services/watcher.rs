@@ -0,0 +1,152 @@
+//! Config file watcher
+//!
+//! Watches Claude Code's config (settings.json, .claude.json, CLAUDE.md, agents and
+//! commands) and emits `config-changed` with what changed, so config pages update
+//! when files are edited outside the app. Session files under projects/ are passed
+//! to `project_activity` instead. Restarted when the Claude profile changes.
+
+use super::paths::{self, PathProvider};
+use super::project_activity;
+use super::settings;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Runtime};
+
+/// Editors write files in several steps - wait this long for things to settle
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// The running watcher (dropping it stops the event thread)
+static WATCHER: Mutex<Option<RecommendedWatcher>> = Mutex::new(None);
+
+/// Payload of the `config-changed` event
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct ConfigChange {
+    /// "settings", "claude_json", "claude_md", "agents" or "commands"
+    pub kind: &'static str,
+    pub path: String,
+    /// "created", "modified" or "removed"
+    pub change: &'static str,
+}
+
+/// Which part of the config a path belongs to (None for files we don't care about)
+fn classify(paths: &PathProvider, path: &Path) -> Option<&'static str> {
+    let config_dir = paths.config_dir();
+    if path == paths.claude_json() {
+        Some("claude_json")
+    } else if path == paths.settings_json() || path == config_dir.join("settings.local.json") {
+        Some("settings")
+    } else if path == config_dir.join("CLAUDE.md") {
+        Some("claude_md")
+    } else if path.starts_with(config_dir.join("agents")) {
+        Some("agents")
+    } else if path.starts_with(config_dir.join("commands")) {
+        Some("commands")
+    } else {
+        None
+    }
+}
+
+fn change_name(kind: &EventKind) -> Option<&'static str> {
+    match kind {
+        EventKind::Create(_) => Some("created"),
+        EventKind::Modify(_) => Some("modified"),
+        EventKind::Remove(_) => Some("removed"),
+        _ => None,
+    }
+}
+
+/// Restart watching when a settings change moves the Claude data location
+pub fn follow_settings<R: Runtime>(app: &AppHandle<R>) {
+    let app = app.clone();
+    settings::on_change(move |change| {
+        let previous = PathProvider::resolve(&change.previous);
+        let current = PathProvider::resolve(&change.current);
+        if previous.config_dir() != current.config_dir()
+            || previous.claude_json() != current.claude_json()
+        {
+            if let Err(e) = start(&app) {
+                eprintln!("Failed to restart config watcher: {}", e);
+            }
+        }
+    });
+}
+
+/// Start (or restart) watching the current Claude data location
+pub fn start<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+    let paths = paths::current();
+    let (tx, rx) = mpsc::channel::<notify::Result<Event>>();
+    let mut watcher =
+        notify::recommended_watcher(tx).map_err(|e| format!("Failed to create watcher: {}", e))?;
+
+    // .claude.json may live outside the config dir (in the home directory)
+    let mut targets: Vec<(PathBuf, RecursiveMode)> = vec![
+        (
+            paths.config_dir().to_path_buf(),
+            RecursiveMode::NonRecursive,
+        ),
+        (paths.config_dir().join("agents"), RecursiveMode::Recursive),
+        (
+            paths.config_dir().join("commands"),
+            RecursiveMode::Recursive,
+        ),
+        (paths.projects_dir(), RecursiveMode::Recursive),
+    ];
+    if let Some(parent) = paths.claude_json().parent() {
+        if parent != paths.config_dir() {
+            targets.push((parent.to_path_buf(), RecursiveMode::NonRecursive));
+        }
+    }
+    for (target, mode) in targets.iter().filter(|(target, _)| target.exists()) {
+        watcher
+            .watch(target, *mode)
+            .map_err(|e| format!("Failed to watch {}: {}", target.display(), e))?;
+    }
+
+    // Replacing the previous watcher disconnects its channel, ending its thread
+    *WATCHER.lock().unwrap() = Some(watcher);
+    project_activity::reset();
+
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let mut pending: Vec<ConfigChange> = Vec::new();
+        loop {
+            match rx.recv_timeout(DEBOUNCE) {
+                Ok(Ok(event)) => {
+                    let Some(change) = change_name(&event.kind) else {
+                        continue;
+                    };
+                    for path in &event.paths {
+                        if path.starts_with(paths.projects_dir()) {
+                            project_activity::record(path);
+                            continue;
+                        }
+                        if let Some(kind) = classify(&paths, path) {
+                            pending.push(ConfigChange {
+                                kind,
+                                path: path.to_string_lossy().to_string(),
+                                change,
+                            });
+                        }
+                    }
+                }
+                Ok(Err(e)) => eprintln!("Config watcher error: {}", e),
+                Err(RecvTimeoutError::Timeout) => {
+                    let mut seen = HashSet::new();
+                    for change in pending.drain(..) {
+                        if seen.insert(change.clone()) {
+                            let _ = app.emit("config-changed", &change);
+                        }
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    });
+
+    Ok(())
+}
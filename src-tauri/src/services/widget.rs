@@ -0,0 +1,143 @@
+//! Companion widget data
+//!
+//! With `AppSettings::widget_api` on, a small versioned snapshot of today's
+//! usage and the running sessions is published for menu bar and desktop
+//! widgets (WidgetKit, Übersicht, SwiftBar): written to `widget.json` in the
+//! app config folder whenever it changes, and served as
+//! `GET /api/v1/widget` by the hook server. Fields are only ever added within
+//! a version; anything else bumps `WIDGET_API_VERSION`.
+
+use super::settings::{app_config_dir, SettingsService};
+use super::tracker::ActiveSession;
+use crate::types::AnalyticsStats;
+use crate::AppState;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, Runtime};
+
+/// Version of the snapshot format
+pub const WIDGET_API_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WidgetToday {
+    pub cost: f64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub messages: u64,
+    pub sessions: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WidgetBurnRate {
+    pub tokens_per_minute: f64,
+    pub cost_per_hour: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WidgetSession {
+    pub session_id: String,
+    pub project: String,
+    pub project_name: String,
+    pub started_at: String,
+    pub cost: f64,
+    pub needs_attention: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WidgetSnapshot {
+    pub version: u32,
+    pub generated_at: String,
+    /// When the usage stats were last computed
+    pub stats_updated_at: Option<String>,
+    pub today: WidgetToday,
+    pub burn_rate: WidgetBurnRate,
+    pub active_sessions: Vec<WidgetSession>,
+}
+
+/// Last snapshot written, to skip writes when nothing changed
+static LAST_WRITTEN: Mutex<Option<WidgetSnapshot>> = Mutex::new(None);
+
+/// Where the snapshot file is written
+pub fn snapshot_path() -> PathBuf {
+    app_config_dir().join("widget.json")
+}
+
+/// Snapshot of `stats` and the running `sessions`
+fn build(stats: &AnalyticsStats, sessions: &[ActiveSession]) -> WidgetSnapshot {
+    WidgetSnapshot {
+        version: WIDGET_API_VERSION,
+        generated_at: Utc::now().to_rfc3339(),
+        stats_updated_at: stats.last_updated.map(|t| t.to_rfc3339()),
+        today: WidgetToday {
+            cost: stats.today_cost,
+            input_tokens: stats.today_input_tokens,
+            output_tokens: stats.today_output_tokens,
+            messages: stats.today_messages,
+            sessions: stats.today_session_count,
+        },
+        burn_rate: WidgetBurnRate {
+            tokens_per_minute: stats.tokens_per_minute,
+            cost_per_hour: stats.cost_per_hour,
+        },
+        active_sessions: sessions
+            .iter()
+            .map(|s| WidgetSession {
+                session_id: s.session_id.clone(),
+                project: s.project.clone(),
+                project_name: s.project_name.clone(),
+                started_at: s.started_at.to_rfc3339(),
+                cost: s.cost,
+                needs_attention: s.needs_attention,
+            })
+            .collect(),
+    }
+}
+
+/// Snapshot from the cached stats and sessions (no disk I/O)
+pub fn current<R: Runtime>(app: &AppHandle<R>) -> Option<WidgetSnapshot> {
+    let state = app.try_state::<AppState>()?;
+    Some(build(
+        &state.analytics.cached_stats(),
+        &state.session_tracker.cached_active(),
+    ))
+}
+
+/// Write the snapshot file if it changed, or remove it when the widget API is off
+pub fn publish<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+    let path = snapshot_path();
+    let mut last = LAST_WRITTEN.lock().unwrap();
+
+    if !SettingsService::shared().get().widget_api {
+        *last = None;
+        if path.exists() {
+            fs::remove_file(&path)
+                .map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+        }
+        return Ok(());
+    }
+
+    let Some(snapshot) = current(app) else {
+        return Ok(());
+    };
+    let unchanged = last.as_ref().is_some_and(|previous| {
+        WidgetSnapshot {
+            generated_at: snapshot.generated_at.clone(),
+            ..previous.clone()
+        } == snapshot
+    });
+    if unchanged && path.exists() {
+        return Ok(());
+    }
+
+    // Write to a temporary file first so widgets never read a partial snapshot
+    let json = serde_json::to_string_pretty(&snapshot)
+        .map_err(|e| format!("Failed to serialize widget snapshot: {}", e))?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, json).map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+    fs::rename(&tmp, &path).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    *last = Some(snapshot);
+    Ok(())
+}
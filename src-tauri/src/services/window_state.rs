@@ -0,0 +1,143 @@
+//! Analytics window state
+//!
+//! Remembers the analytics window's size, position, maximized state and last
+//! open page across launches, in `window-state.json` in claudit's config
+//! directory. Geometry is tracked in memory while the window moves and written
+//! when it is hidden or loses focus.
+
+use super::settings::app_config_dir;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{Manager, PhysicalPosition, PhysicalSize, Runtime, WebviewWindow};
+
+/// Label of the main window
+pub const ANALYTICS_WINDOW: &str = "analytics";
+
+/// Size from tauri.conf.json, used by `reset_layout`
+const DEFAULT_WIDTH: f64 = 1200.0;
+const DEFAULT_HEIGHT: f64 = 850.0;
+
+/// Saved window state (physical pixels)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WindowState {
+    pub x: Option<i32>,
+    pub y: Option<i32>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    #[serde(default)]
+    pub maximized: bool,
+    /// Page that was open last (e.g. "projects")
+    pub route: Option<String>,
+}
+
+static STATE: Mutex<Option<WindowState>> = Mutex::new(None);
+
+fn state_path() -> PathBuf {
+    app_config_dir().join("window-state.json")
+}
+
+/// Current state (loaded from disk on first use)
+pub fn get() -> WindowState {
+    let mut state = STATE.lock().unwrap();
+    state
+        .get_or_insert_with(|| {
+            fs::read_to_string(state_path())
+                .ok()
+                .and_then(|contents| serde_json::from_str(&contents).ok())
+                .unwrap_or_default()
+        })
+        .clone()
+}
+
+fn update(f: impl FnOnce(&mut WindowState)) {
+    let mut state = get();
+    f(&mut state);
+    *STATE.lock().unwrap() = Some(state);
+}
+
+/// Write the current state to disk
+pub fn save() -> Result<(), String> {
+    let contents =
+        serde_json::to_string_pretty(&get()).map_err(|e| format!("Serialize error: {}", e))?;
+    fs::write(state_path(), contents).map_err(|e| format!("Write error: {}", e))
+}
+
+/// Record the window's geometry after a move or resize
+pub fn track<R: Runtime>(window: &tauri::Window<R>) {
+    let maximized = window.is_maximized().unwrap_or(false);
+    let position = window.outer_position().ok();
+    let size = window.inner_size().ok();
+    update(|state| {
+        state.maximized = maximized;
+        // Keep the restored geometry while maximized
+        if maximized {
+            return;
+        }
+        if let Some(position) = position {
+            state.x = Some(position.x);
+            state.y = Some(position.y);
+        }
+        if let Some(size) = size {
+            state.width = Some(size.width);
+            state.height = Some(size.height);
+        }
+    });
+}
+
+/// Remember the page that is open
+pub fn set_route(route: Option<String>) -> Result<(), String> {
+    update(|state| state.route = route);
+    save()
+}
+
+/// Apply the saved state to the window. The position is only restored when it
+/// is still on a connected monitor.
+pub fn restore<R: Runtime>(window: &WebviewWindow<R>) {
+    let state = get();
+
+    if let (Some(width), Some(height)) = (state.width, state.height) {
+        let _ = window.set_size(PhysicalSize::new(width, height));
+    }
+
+    if let (Some(x), Some(y)) = (state.x, state.y) {
+        let on_screen = window
+            .available_monitors()
+            .unwrap_or_default()
+            .iter()
+            .any(|monitor| {
+                let origin = monitor.position();
+                let size = monitor.size();
+                x >= origin.x
+                    && y >= origin.y
+                    && x < origin.x + size.width as i32
+                    && y < origin.y + size.height as i32
+            });
+        if on_screen {
+            let _ = window.set_position(PhysicalPosition::new(x, y));
+        }
+    }
+
+    if state.maximized {
+        let _ = window.maximize();
+    }
+}
+
+/// Forget the saved layout and put the window back to its default size, centered
+pub fn reset_layout<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<(), String> {
+    *STATE.lock().unwrap() = Some(WindowState::default());
+    if state_path().exists() {
+        fs::remove_file(state_path())
+            .map_err(|e| format!("Failed to remove window state: {}", e))?;
+    }
+
+    if let Some(window) = app.get_webview_window(ANALYTICS_WINDOW) {
+        window.unmaximize().map_err(|e| e.to_string())?;
+        window
+            .set_size(tauri::LogicalSize::new(DEFAULT_WIDTH, DEFAULT_HEIGHT))
+            .map_err(|e| e.to_string())?;
+        window.center().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
@@ -0,0 +1,146 @@
+//! Workspaces
+//!
+//! In a monorepo Claude Code is often started in the repository root and then
+//! `cd`s into packages, or started in a package directly. Every log entry
+//! records its `cwd`, so usage of a project can be split by the package it
+//! happened in: the deepest directory between the project root and the `cwd`
+//! that has its own package manifest.
+
+use super::analytics::calculate_entry_cost;
+use super::usage::UsageReader;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Files that make a directory a package of its own
+const MANIFESTS: &[&str] = &[
+    "package.json",
+    "Cargo.toml",
+    "go.mod",
+    "pyproject.toml",
+    "setup.py",
+    "pom.xml",
+    "build.gradle",
+    "build.gradle.kts",
+    "Gemfile",
+    "composer.json",
+    "mix.exs",
+    "Package.swift",
+];
+
+/// Usage of one package of a project
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SubprojectStats {
+    /// Path relative to the project root ("." for the root itself), or the
+    /// absolute `cwd` for work outside the project
+    pub path: String,
+    pub cost: f64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub messages: u64,
+    pub sessions: usize,
+    pub last_used: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkspaceBreakdown {
+    pub project: String,
+    pub days: Option<u32>,
+    /// Costliest first
+    pub subprojects: Vec<SubprojectStats>,
+}
+
+/// Finds the package a directory belongs to, remembering manifest lookups
+struct PackageResolver {
+    root: PathBuf,
+    has_manifest: HashMap<PathBuf, bool>,
+}
+
+impl PackageResolver {
+    fn new(root: &str) -> Self {
+        Self {
+            root: PathBuf::from(root),
+            has_manifest: HashMap::new(),
+        }
+    }
+
+    fn is_package(&mut self, dir: &Path) -> bool {
+        *self
+            .has_manifest
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| MANIFESTS.iter().any(|m| dir.join(m).is_file()))
+    }
+
+    /// Package path (relative to the root) that `cwd` is in
+    fn resolve(&mut self, cwd: Option<&str>) -> String {
+        let Some(cwd) = cwd.map(Path::new) else {
+            return ".".to_string();
+        };
+        let Ok(relative) = cwd.strip_prefix(&self.root) else {
+            return cwd.to_string_lossy().to_string();
+        };
+
+        // Deepest directory below the root with a manifest
+        let mut package = None;
+        let mut dir = self.root.clone();
+        for component in relative.components() {
+            dir.push(component);
+            if self.is_package(&dir) {
+                package = Some(dir.clone());
+            }
+        }
+        package
+            .and_then(|p| {
+                p.strip_prefix(&self.root)
+                    .ok()
+                    .map(|p| p.to_string_lossy().to_string())
+            })
+            .unwrap_or_else(|| ".".to_string())
+    }
+}
+
+/// Usage of `project` split by package, over the last `days` days (all time if None)
+pub fn breakdown(project: &str, days: Option<u32>) -> WorkspaceBreakdown {
+    let entries = UsageReader::new().read_entries(days);
+    let mut resolver = PackageResolver::new(project);
+    let mut stats: HashMap<String, SubprojectStats> = HashMap::new();
+    let mut sessions: HashMap<String, HashSet<String>> = HashMap::new();
+
+    for entry in entries.iter().filter(|e| e.project == project) {
+        let path = resolver.resolve(entry.cwd.as_deref());
+        sessions
+            .entry(path.clone())
+            .or_default()
+            .insert(entry.session_id.clone());
+
+        let sub = stats
+            .entry(path.clone())
+            .or_insert_with(|| SubprojectStats {
+                path,
+                ..Default::default()
+            });
+        sub.cost += calculate_entry_cost(entry);
+        sub.input_tokens += entry.input_tokens;
+        sub.output_tokens += entry.output_tokens;
+        sub.messages += 1;
+        let at = entry.timestamp.to_rfc3339();
+        if sub.last_used.as_ref().is_none_or(|last| &at > last) {
+            sub.last_used = Some(at);
+        }
+    }
+
+    let mut subprojects: Vec<SubprojectStats> = stats
+        .into_values()
+        .map(|mut sub| {
+            sub.sessions = sessions.get(&sub.path).map(|s| s.len()).unwrap_or(0);
+            sub
+        })
+        .collect();
+    subprojects.sort_by(|a, b| b.cost.total_cmp(&a.cost));
+
+    WorkspaceBreakdown {
+        project: project.to_string(),
+        days,
+        subprojects,
+    }
+}
@@ -0,0 +1,122 @@
+//! Windows Subsystem for Linux
+//!
+//! On Windows, Claude Code often runs inside WSL, so its data lives in the
+//! Linux filesystem. Windows reaches it through `\\wsl.localhost\<distro>\...`
+//! (or the older `\\wsl$\<distro>\...`), which works as a profile's config
+//! directory. `detect` finds the distributions that have a `~/.claude`, and
+//! `parse_unc` tells `terminal` to resume such sessions through `wsl.exe`.
+
+use serde::{Deserialize, Serialize};
+
+/// A WSL distribution and the Windows path of its Claude data, if any
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WslDistro {
+    pub name: String,
+    /// `\\wsl.localhost\<distro>\home\<user>\.claude`
+    pub claude_dir: Option<String>,
+}
+
+/// The distribution and Linux path of a `\\wsl.localhost\` or `\\wsl$\` path,
+/// e.g. `\\wsl$\Ubuntu\home\me\.claude` -> (`Ubuntu`, `/home/me/.claude`)
+pub fn parse_unc(path: &str) -> Option<(String, String)> {
+    let path = path.replace('/', "\\");
+    let rest = path.strip_prefix("\\\\")?;
+    let (host, rest) = rest.split_once('\\')?;
+    if !host.eq_ignore_ascii_case("wsl$") && !host.eq_ignore_ascii_case("wsl.localhost") {
+        return None;
+    }
+    let (distro, linux) = rest.split_once('\\').unwrap_or((rest, ""));
+    if distro.is_empty() {
+        return None;
+    }
+    Some((distro.to_string(), format!("/{}", linux.replace('\\', "/"))))
+}
+
+/// Output of `wsl.exe`, which is UTF-16 unless `WSL_UTF8` is honoured
+#[cfg(windows)]
+fn decode_output(bytes: &[u8]) -> String {
+    if bytes.len() % 2 == 0 && bytes.iter().skip(1).step_by(2).any(|b| *b == 0) {
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    } else {
+        String::from_utf8_lossy(bytes).to_string()
+    }
+}
+
+#[cfg(windows)]
+fn wsl(args: &[&str]) -> Option<String> {
+    use std::os::windows::process::CommandExt;
+    /// Don't flash a console window
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    let output = std::process::Command::new("wsl.exe")
+        .args(args)
+        .env("WSL_UTF8", "1")
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| decode_output(&output.stdout).trim().to_string())
+}
+
+/// Windows path of `~/.claude` in a distribution, if it exists
+#[cfg(windows)]
+fn claude_dir(distro: &str) -> Option<String> {
+    let home = wsl(&["-d", distro, "-e", "sh", "-c", "echo $HOME"])?;
+    if !home.starts_with('/') {
+        return None;
+    }
+    let linux = format!("{}/.claude", home.trim_end_matches('/')).replace('/', "\\");
+    ["wsl.localhost", "wsl$"]
+        .iter()
+        .map(|host| format!("\\\\{}\\{}{}", host, distro, linux))
+        .find(|dir| std::path::Path::new(dir).is_dir())
+}
+
+/// Installed distributions with their Claude data (empty outside Windows)
+pub fn detect() -> Vec<WslDistro> {
+    #[cfg(windows)]
+    {
+        let Some(list) = wsl(&["--list", "--quiet"]) else {
+            return Vec::new();
+        };
+        list.lines()
+            .map(|line| line.trim_matches(|c: char| c.is_whitespace() || c == '\0'))
+            .filter(|name| !name.is_empty() && !name.starts_with("docker-desktop"))
+            .map(|name| WslDistro {
+                name: name.to_string(),
+                claude_dir: claude_dir(name),
+            })
+            .collect()
+    }
+    #[cfg(not(windows))]
+    {
+        Vec::new()
+    }
+}
+
+/// `wsl.exe` arguments that resume a session in its project inside `distro`.
+/// A login shell, so `claude` is on the PATH set up by the user's profile. The
+/// session ID reaches it as `$1`, never as part of the script.
+pub fn resume_args(distro: &str, project_path: &str, session_id: &str) -> Vec<String> {
+    [
+        "-d",
+        distro,
+        "--cd",
+        project_path,
+        "--",
+        "bash",
+        "-lic",
+        r#"claude --resume "$1""#,
+        "bash",
+        session_id,
+    ]
+    .iter()
+    .map(|arg| arg.to_string())
+    .collect()
+}
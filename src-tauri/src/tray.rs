@@ -1,27 +1,35 @@
-use crate::services::{config, get_claude_status, SettingsService};
-use crate::types::AppSettings;
+use crate::services::notifications::{self, MuteDuration};
+use crate::services::{
+    config, demo, format, get_claude_status, goals, kiosk, paths, popover, settings, summary, widget,
+    AnalyticsService, SettingsService,
+};
+use crate::types::GoalKind;
 use crate::AppState;
 use std::process::Command;
 use std::sync::Mutex;
 use tauri::{
     include_image,
     menu::{CheckMenuItem, Menu, MenuBuilder, MenuItem, SubmenuBuilder},
-    tray::{TrayIcon, TrayIconBuilder},
+    tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent},
     AppHandle, Emitter, Manager, Runtime,
 };
 
-/// Cached data for building the tray menu - avoids disk I/O on every interaction
+/// Cached data for building the tray menu - avoids disk I/O on every interaction.
+/// Settings aren't cached here: `SettingsService` already holds them in memory.
 #[derive(Default)]
 struct MenuCache {
     /// Project names and paths for the submenu
     projects: Vec<(String, String)>, // (name, path)
-    /// Settings cache
-    settings: Option<AppSettings>,
+    /// Cost per day for the last 7 days (oldest first)
+    weekly_costs: Vec<f64>,
+    /// Goal progress line (None when goal mode is off)
+    goal_line: Option<String>,
 }
 
 static MENU_CACHE: Mutex<MenuCache> = Mutex::new(MenuCache {
     projects: Vec::new(),
-    settings: None,
+    weekly_costs: Vec::new(),
+    goal_line: None,
 });
 
 /// Refresh the menu cache from disk (call in background thread)
@@ -35,16 +43,109 @@ pub fn refresh_menu_cache() {
             cache.projects.push((project.name.clone(), project.path.clone()));
         }
     }
-
-    // Refresh settings
-    let settings_service = SettingsService::new();
-    cache.settings = Some(settings_service.get());
 }
 
-/// Update just the settings in the cache (call after settings change from UI)
-pub fn update_cached_settings(settings: &AppSettings) {
+/// Refresh the 7-day cost trend from daily chart data (call in background thread)
+pub fn refresh_trend_cache(analytics: &AnalyticsService) {
+    let chart = analytics.get_chart_data(7);
+    let today = chrono::Utc::now().date_naive();
+
+    let weekly_costs: Vec<f64> = (0..7)
+        .rev()
+        .map(|days_ago| {
+            let date = (today - chrono::Duration::days(days_ago))
+                .format("%Y-%m-%d")
+                .to_string();
+            chart
+                .daily
+                .iter()
+                .find(|d| d.date == date)
+                .map(|d| d.cost)
+                .unwrap_or(0.0)
+        })
+        .collect();
+
+    let goal_line = SettingsService::shared()
+        .get()
+        .goal
+        .and_then(|goal| goals::progress(analytics, &goal).ok())
+        .map(|progress| format_goal_line(&progress));
+
     let mut cache = MENU_CACHE.lock().unwrap();
-    cache.settings = Some(settings.clone());
+    cache.weekly_costs = weekly_costs;
+    cache.goal_line = goal_line;
+}
+
+/// e.g. "Goal: $3.10 / $5.00 today (62%) · 4-day streak"
+fn format_goal_line(progress: &goals::GoalProgress) -> String {
+    let (current, target, period) = match progress.goal.kind {
+        GoalKind::DailySpend => (
+            format::cost(progress.current.actual),
+            format::cost(progress.goal.target),
+            "today",
+        ),
+        GoalKind::WeeklySpend => (
+            format::cost(progress.current.actual),
+            format::cost(progress.goal.target),
+            "this week",
+        ),
+        GoalKind::DailyOutput => (
+            format::token_count(progress.current.actual as u64),
+            format::token_count(progress.goal.target as u64),
+            "today",
+        ),
+    };
+    let unit = match progress.goal.kind {
+        GoalKind::WeeklySpend => "week",
+        GoalKind::DailySpend | GoalKind::DailyOutput => "day",
+    };
+
+    let mut line = format!(
+        "Goal: {} / {} {} ({:.0}%)",
+        current, target, period, progress.percent
+    );
+    if progress.current_streak > 0 {
+        line.push_str(&format!(
+            "{}{}-{} streak",
+            format::separator(),
+            progress.current_streak,
+            unit
+        ));
+    }
+    line
+}
+
+/// Keep the tray in step with settings, wherever they were changed, and
+/// pass them on to the frontend
+pub fn follow_settings<R: Runtime>(app: &AppHandle<R>) {
+    let app = app.clone();
+    settings::on_change(move |change| {
+        let (previous, current) = (&change.previous, &change.current);
+        if previous.tray_popover != current.tray_popover {
+            if let Err(e) = apply_popover_setting(&app, current.tray_popover) {
+                eprintln!("Failed to apply popover setting: {}", e);
+            }
+        }
+
+        // The goal line is formatted when cached
+        let goal_changed = previous.goal.as_ref().map(|g| (g.kind, g.target))
+            != current.goal.as_ref().map(|g| (g.kind, g.target));
+        let format_changed =
+            previous.cost_format != current.cost_format || previous.text_style != current.text_style;
+        if goal_changed || format_changed {
+            if let Some(state) = app.try_state::<AppState>() {
+                refresh_trend_cache(&state.analytics);
+            }
+        }
+
+        let _ = update_tray_menu(&app);
+        let _ = app.emit("settings-changed", current);
+    });
+}
+
+/// Whether left clicks open the popover instead of the menu
+fn popover_enabled() -> bool {
+    SettingsService::shared().get().tray_popover
 }
 
 pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> Result<TrayIcon<R>, tauri::Error> {
@@ -54,46 +155,64 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> Result<TrayIcon<R>, tauri:
         .icon(include_image!("icons/tray-icon.png"))
         .icon_as_template(true)
         .menu(&menu)
-        .show_menu_on_left_click(true)
+        .show_menu_on_left_click(!popover_enabled())
         .on_menu_event(|app, event| {
             handle_menu_event(app, event.id().as_ref());
         })
+        .on_tray_icon_event(|tray, event| {
+            // The menu stays on right click
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                rect,
+                ..
+            } = event
+            {
+                if popover_enabled() {
+                    if let Err(e) = popover::toggle(tray.app_handle(), Some(rect)) {
+                        eprintln!("Failed to toggle tray popover: {}", e);
+                    }
+                }
+            }
+        })
         .build(app)
 }
 
-/// Format large numbers with K/M suffixes
-fn format_number(n: u64) -> String {
-    if n >= 1_000_000 {
-        format!("{:.1}M", n as f64 / 1_000_000.0)
-    } else if n >= 1_000 {
-        format!("{:.1}K", n as f64 / 1_000.0)
-    } else {
-        n.to_string()
+/// Switch left clicks between the menu and the popover after the setting changed
+pub fn apply_popover_setting<R: Runtime>(app: &AppHandle<R>, enabled: bool) -> Result<(), String> {
+    if let Some(tray) = app.tray_by_id("main-tray") {
+        tray.set_show_menu_on_left_click(!enabled).map_err(|e| e.to_string())?;
+    }
+    if !enabled {
+        popover::hide(app)?;
     }
+    Ok(())
 }
 
-/// Format cost as dollars
-fn format_cost(cost: f64) -> String {
-    if cost >= 1.0 {
-        format!("${:.2}", cost)
-    } else if cost >= 0.01 {
-        format!("${:.3}", cost)
-    } else {
-        format!("${:.4}", cost)
-    }
+/// Render values as a unicode block sparkline (e.g. "▁▃▅█▂▄▆")
+fn sparkline(values: &[f64]) -> String {
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max = values.iter().cloned().fold(0.0, f64::max);
+
+    values
+        .iter()
+        .map(|&v| {
+            if max <= 0.0 {
+                BLOCKS[0]
+            } else {
+                BLOCKS[((v / max) * (BLOCKS.len() - 1) as f64).round() as usize]
+            }
+        })
+        .collect()
 }
 
-/// Format burn rate as $/hour
-fn format_burn_rate(rate: f64) -> String {
-    if rate >= 1.0 {
-        format!("${:.2}/hr", rate)
-    } else if rate >= 0.01 {
-        format!("${:.3}/hr", rate)
-    } else if rate > 0.0 {
-        format!("${:.4}/hr", rate)
-    } else {
-        "$0/hr".to_string()
-    }
+/// Model name for display, e.g. "claude-opus-4-1-20250805" -> "opus-4"
+fn short_model_name(name: &str) -> String {
+    name.replace("claude-", "")
+        .split('-')
+        .take(2)
+        .collect::<Vec<_>>()
+        .join("-")
 }
 
 /// Get the primary model (most used by output tokens)
@@ -102,31 +221,40 @@ fn get_primary_model(stats: &crate::types::AnalyticsStats) -> Option<String> {
         .by_model
         .iter()
         .max_by_key(|(_, model_stats)| model_stats.output_tokens)
-        .map(|(name, _)| {
-            // Clean up model name for display
-            name.replace("claude-", "")
-                .split('-')
-                .take(2)
-                .collect::<Vec<_>>()
-                .join("-")
-        })
+        .map(|(name, _)| short_model_name(name))
+}
+
+/// Today's cost per model (merged by display name), most expensive first
+fn today_model_costs(stats: &crate::types::AnalyticsStats) -> Vec<(String, f64)> {
+    let mut costs: Vec<(String, f64)> = Vec::new();
+    for (name, model_stats) in &stats.today_by_model {
+        let name = short_model_name(name);
+        match costs.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, cost)) => *cost += model_stats.cost,
+            None => costs.push((name, model_stats.cost)),
+        }
+    }
+    costs.retain(|(_, cost)| *cost >= 0.01);
+    costs.sort_by(|a, b| b.1.total_cmp(&a.1));
+    costs
 }
 
 fn build_tray_menu<R: Runtime>(app: &AppHandle<R>) -> Result<Menu<R>, tauri::Error> {
-    // Get cached stats from AppState (fast - no disk I/O)
+    // Get cached stats from AppState (fast - no disk I/O, the refresh timer keeps them current)
     let stats = app
         .try_state::<AppState>()
-        .map(|state| state.analytics.get_stats())
+        .map(|state| state.analytics.cached_stats())
         .unwrap_or_default();
 
     // Detect whether Claude Code data/config exists (used to show a friendly hint instead of silent zeros)
     let claude_status = get_claude_status();
 
-    // Get settings from cache (fast - no disk I/O)
-    // If cache is empty, use defaults - cache will be populated on first refresh
+    // Settings are in memory; projects and trend come from the cache (no disk I/O)
+    let settings = SettingsService::shared().get();
     let cache = MENU_CACHE.lock().unwrap();
-    let settings = cache.settings.clone().unwrap_or_default();
     let cached_projects = cache.projects.clone();
+    let weekly_costs = cache.weekly_costs.clone();
+    let goal_line = cache.goal_line.clone();
     drop(cache); // Release lock early
 
     let mut builder = MenuBuilder::new(app);
@@ -145,8 +273,69 @@ fn build_tray_menu<R: Runtime>(app: &AppHandle<R>) -> Result<Menu<R>, tauri::Err
         None::<&str>,
     )?);
 
+    if demo::is_active() {
+        builder = builder.item(&MenuItem::with_id(
+            app,
+            "demo",
+            "Demo Mode  ·  sample data",
+            false,
+            None::<&str>,
+        )?);
+    }
+
     builder = builder.separator();
 
+    // Sessions waiting for the user (permission prompt or idle)
+    let waiting = app
+        .try_state::<AppState>()
+        .map(|state| state.session_tracker.sessions_needing_attention())
+        .unwrap_or_default();
+    if !waiting.is_empty() {
+        let text = match waiting.as_slice() {
+            [session] => match session.project.as_deref().and_then(format::project_name) {
+                Some(project) => format::warning(&format!(
+                    "Claude needs your attention{}{}",
+                    format::separator(),
+                    project
+                )),
+                None => format::warning("Claude needs your attention"),
+            },
+            sessions => format::warning(&format!("{} sessions need your attention", sessions.len())),
+        };
+        builder = builder.item(&MenuItem::with_id(app, "attention", text, true, None::<&str>)?);
+        builder = builder.separator();
+    }
+
+    // Sessions running right now (refreshed by the background timer)
+    let active = app
+        .try_state::<AppState>()
+        .map(|state| state.session_tracker.cached_active())
+        .unwrap_or_default();
+    if !active.is_empty() {
+        let title = format::prefixed("▶️", &if active.len() == 1 {
+            "1 session running".to_string()
+        } else {
+            format!("{} sessions running", active.len())
+        });
+        let mut sessions_submenu = SubmenuBuilder::new(app, title);
+        for (i, session) in active.iter().enumerate() {
+            sessions_submenu = sessions_submenu.item(&MenuItem::with_id(
+                app,
+                format!("active_session_{}", i),
+                [
+                    session.project_name.clone(),
+                    format::elapsed(session.elapsed_seconds),
+                    format::cost(session.cost),
+                ]
+                .join(format::separator()),
+                true,
+                None::<&str>,
+            )?);
+        }
+        builder = builder.item(&sessions_submenu.build()?);
+        builder = builder.separator();
+    }
+
     // Claude Code not installed / no data banner
     if !claude_status.projects_dir_exists && !claude_status.claude_json_exists {
         builder = builder.item(&MenuItem::with_id(
@@ -159,129 +348,225 @@ fn build_tray_menu<R: Runtime>(app: &AppHandle<R>) -> Result<Menu<R>, tauri::Err
         builder = builder.separator();
     }
 
+    // Stat sections - shown inline, or collapsed into a "Details" submenu in compact mode
+    let mut sections: Vec<Vec<MenuItem<R>>> = Vec::new();
+
     // Today's Messages (if enabled)
     if settings.show_messages {
-        builder = builder.item(&MenuItem::with_id(
+        sections.push(vec![MenuItem::with_id(
             app,
             "today_messages",
-            format!(
-                "Messages: {} today / {} total",
-                stats.today_messages, stats.total_messages_count
+            format::prefixed(
+                "💬",
+                &if format::verbose() {
+                    format!(
+                        "Messages: {} today, {} in total",
+                        stats.today_messages, stats.total_messages_count
+                    )
+                } else {
+                    format!(
+                        "Messages: {} today / {} total",
+                        stats.today_messages, stats.total_messages_count
+                    )
+                },
             ),
             false,
             None::<&str>,
-        )?);
-        builder = builder.separator();
+        )?]);
     }
 
     // Token Stats (if enabled)
     if settings.show_tokens {
-        builder = builder.item(&MenuItem::with_id(
-            app,
-            "tokens_title",
-            "Tokens",
-            false,
-            None::<&str>,
-        )?);
-
-        builder = builder.item(&MenuItem::with_id(
-            app,
-            "today_tokens",
-            format!(
-                "  Today: {} in / {} out",
-                format_number(stats.today_input_tokens),
-                format_number(stats.today_output_tokens)
-            ),
-            false,
-            None::<&str>,
-        )?);
-
-        builder = builder.item(&MenuItem::with_id(
-            app,
-            "total_tokens",
-            format!(
-                "  Total: {} in / {} out",
-                format_number(stats.total_input_tokens),
-                format_number(stats.total_output_tokens)
-            ),
-            false,
-            None::<&str>,
-        )?);
-        builder = builder.separator();
+        sections.push(vec![
+            MenuItem::with_id(
+                app,
+                "tokens_title",
+                format::prefixed("🔢", "Tokens"),
+                false,
+                None::<&str>,
+            )?,
+            MenuItem::with_id(
+                app,
+                "today_tokens",
+                format!(
+                    "  Today: {}",
+                    format::in_out(stats.today_input_tokens, stats.today_output_tokens)
+                ),
+                false,
+                None::<&str>,
+            )?,
+            MenuItem::with_id(
+                app,
+                "total_tokens",
+                format!(
+                    "  Total: {}",
+                    format::in_out(stats.total_input_tokens, stats.total_output_tokens)
+                ),
+                false,
+                None::<&str>,
+            )?,
+        ]);
     }
 
     // Cost Stats with burn rate (if enabled)
     if settings.show_cost {
-        builder = builder.item(&MenuItem::with_id(
+        let mut items = vec![MenuItem::with_id(
             app,
             "cost_title",
-            "Cost",
+            format::prefixed("💰", "Cost"),
             false,
             None::<&str>,
-        )?);
+        )?];
 
         // Today's cost with burn rate
         let today_cost_text = if settings.show_burn_rate && stats.cost_per_hour > 0.0 {
             format!(
                 "  Today: {} ({})",
-                format_cost(stats.today_cost),
-                format_burn_rate(stats.cost_per_hour)
+                format::cost(stats.today_cost),
+                format::rate(stats.cost_per_hour)
             )
         } else {
-            format!("  Today: {}", format_cost(stats.today_cost))
+            format!("  Today: {}", format::cost(stats.today_cost))
         };
-        builder = builder.item(&MenuItem::with_id(
+        items.push(MenuItem::with_id(
             app,
             "today_cost",
             today_cost_text,
             false,
             None::<&str>,
         )?);
+        // Split by model once more than one was used, e.g. "opus-4 today: $6.90"
+        let model_costs = today_model_costs(&stats);
+        if model_costs.len() > 1 {
+            for (i, (model, cost)) in model_costs.iter().take(3).enumerate() {
+                items.push(MenuItem::with_id(
+                    app,
+                    format!("today_model_{}", i),
+                    format!("    {} today: {}", model, format::cost(*cost)),
+                    false,
+                    None::<&str>,
+                )?);
+            }
+        }
 
-        builder = builder.item(&MenuItem::with_id(
+        items.push(MenuItem::with_id(
             app,
             "total_cost",
-            format!("  Total: {}", format_cost(stats.total_cost)),
+            format!("  Total: {}", format::cost(stats.total_cost)),
             false,
             None::<&str>,
         )?);
-        builder = builder.separator();
+
+        // Quick trend read without opening the app (the sparkline means nothing read out)
+        if !weekly_costs.is_empty() {
+            let total = format::cost(weekly_costs.iter().sum());
+            items.push(MenuItem::with_id(
+                app,
+                "weekly_trend",
+                if format::verbose() {
+                    format!("  Last 7 days: {}", total)
+                } else {
+                    format!("  Last 7 days: {}  {}", sparkline(&weekly_costs), total)
+                },
+                false,
+                None::<&str>,
+            )?);
+        }
+        if let Some(goal_line) = &goal_line {
+            items.push(MenuItem::with_id(
+                app,
+                "goal_progress",
+                format!("  {}", goal_line),
+                false,
+                None::<&str>,
+            )?);
+        }
+        sections.push(items);
     }
 
     // Session block info (if enabled)
     if settings.show_sessions && stats.current_session_tokens > 0 {
-        builder = builder.item(&MenuItem::with_id(
-            app,
-            "session_title",
-            "Current Session",
-            false,
-            None::<&str>,
-        )?);
+        sections.push(vec![
+            MenuItem::with_id(
+                app,
+                "session_title",
+                format::prefixed("⏱️", "Current Session"),
+                false,
+                None::<&str>,
+            )?,
+            MenuItem::with_id(
+                app,
+                "session_tokens",
+                format!("  Tokens: {}", format::count(stats.current_session_tokens)),
+                false,
+                None::<&str>,
+            )?,
+            MenuItem::with_id(
+                app,
+                "session_cost",
+                format!("  Cost: {}", format::cost(stats.current_session_cost)),
+                false,
+                None::<&str>,
+            )?,
+        ]);
+    }
 
+    if settings.compact_mode {
+        // Single summary line, e.g. "Today: $4.12 · 1.2M tok · 3 sessions"
+        let sessions_text = if stats.today_session_count == 1 {
+            "1 session".to_string()
+        } else {
+            format!("{} sessions", stats.today_session_count)
+        };
         builder = builder.item(&MenuItem::with_id(
             app,
-            "session_tokens",
+            "compact_summary",
             format!(
-                "  Tokens: {}",
-                format_number(stats.current_session_tokens)
+                "Today: {}",
+                [
+                    format::cost(stats.today_cost),
+                    format::token_count(stats.today_tokens()),
+                    sessions_text
+                ]
+                .join(format::separator())
             ),
             false,
             None::<&str>,
         )?);
+        if let Some(goal_line) = &goal_line {
+            builder = builder.item(&MenuItem::with_id(
+                app,
+                "compact_goal",
+                goal_line,
+                false,
+                None::<&str>,
+            )?);
+        }
 
-        builder = builder.item(&MenuItem::with_id(
-            app,
-            "session_cost",
-            format!("  Cost: {}", format_cost(stats.current_session_cost)),
-            false,
-            None::<&str>,
-        )?);
-
+        if !sections.is_empty() {
+            let mut details_submenu = SubmenuBuilder::new(app, "Details");
+            for (i, section) in sections.iter().enumerate() {
+                if i > 0 {
+                    details_submenu = details_submenu.separator();
+                }
+                for item in section {
+                    details_submenu = details_submenu.item(item);
+                }
+            }
+            builder = builder.item(&details_submenu.build()?);
+        }
         builder = builder.separator();
+    } else {
+        for section in &sections {
+            for item in section {
+                builder = builder.item(item);
+            }
+            builder = builder.separator();
+        }
     }
 
     // Recent Projects submenu (from cache - no disk I/O)
-    if !cached_projects.is_empty() {
+    let projects_submenu = if !cached_projects.is_empty() {
         let mut projects_submenu = SubmenuBuilder::new(app, "Recent Projects");
 
         for (i, (name, _path)) in cached_projects.iter().enumerate() {
@@ -294,9 +579,10 @@ fn build_tray_menu<R: Runtime>(app: &AppHandle<R>) -> Result<Menu<R>, tauri::Err
             )?);
         }
 
-        builder = builder.item(&projects_submenu.build()?);
-        builder = builder.separator();
-    }
+        Some(projects_submenu.build()?)
+    } else {
+        None
+    };
 
     // Navigation submenu
     let nav_submenu = SubmenuBuilder::new(app, "Open Section")
@@ -310,8 +596,6 @@ fn build_tray_menu<R: Runtime>(app: &AppHandle<R>) -> Result<Menu<R>, tauri::Err
         ])
         .build()?;
 
-    builder = builder.item(&nav_submenu);
-
     // Quick Actions submenu
     let actions_submenu = SubmenuBuilder::new(app, "Quick Actions")
         .items(&[
@@ -321,7 +605,50 @@ fn build_tray_menu<R: Runtime>(app: &AppHandle<R>) -> Result<Menu<R>, tauri::Err
         ])
         .build()?;
 
-    builder = builder.item(&actions_submenu);
+    if settings.compact_mode {
+        // Fold the navigation submenus into one
+        let mut more_submenu = SubmenuBuilder::new(app, "Go To");
+        if let Some(projects_submenu) = &projects_submenu {
+            more_submenu = more_submenu.item(projects_submenu);
+        }
+        more_submenu = more_submenu.item(&nav_submenu).item(&actions_submenu);
+        builder = builder.item(&more_submenu.build()?);
+    } else {
+        if let Some(projects_submenu) = &projects_submenu {
+            builder = builder.item(projects_submenu);
+            builder = builder.separator();
+        }
+        builder = builder.item(&nav_submenu);
+        builder = builder.item(&actions_submenu);
+    }
+
+    // Claude data location switcher (only when profiles are configured)
+    if !settings.claude_profiles.is_empty() {
+        let active = settings.active_profile.as_deref();
+        let mut profiles_submenu = SubmenuBuilder::new(
+            app,
+            format!("Profile: {}", active.unwrap_or("Default")),
+        )
+        .item(&CheckMenuItem::with_id(
+            app,
+            "profile_default",
+            "Default",
+            true,
+            active.is_none(),
+            None::<&str>,
+        )?);
+        for (i, profile) in settings.claude_profiles.iter().enumerate() {
+            profiles_submenu = profiles_submenu.item(&CheckMenuItem::with_id(
+                app,
+                format!("profile_{}", i),
+                &profile.name,
+                true,
+                active == Some(profile.name.as_str()),
+                None::<&str>,
+            )?);
+        }
+        builder = builder.item(&profiles_submenu.build()?);
+    }
 
     builder = builder.separator();
 
@@ -334,6 +661,54 @@ fn build_tray_menu<R: Runtime>(app: &AppHandle<R>) -> Result<Menu<R>, tauri::Err
         settings.notifications_enabled,
         None::<&str>,
     )?);
+    // Temporary mute with the time left; the 30s refresh keeps it counting down
+    if settings.notifications_enabled {
+        let mute_submenu = match notifications::muted_until() {
+            Some(until) => SubmenuBuilder::new(
+                app,
+                format!(
+                    "Muted{}{} left",
+                    format::separator(),
+                    notifications::mute_remaining(until)
+                ),
+            )
+            .item(&MenuItem::with_id(
+                app,
+                "mute_until_info",
+                format!("Until {}", until.format("%a %H:%M")),
+                false,
+                None::<&str>,
+            )?)
+            .item(&MenuItem::with_id(
+                app,
+                "unmute",
+                "Unmute Now",
+                true,
+                None::<&str>,
+            )?),
+            None => SubmenuBuilder::new(app, "Mute Notifications"),
+        };
+        let mute_submenu = mute_submenu
+            .item(&MenuItem::with_id(app, "mute_1h", "For 1 Hour", true, None::<&str>)?)
+            .item(&MenuItem::with_id(app, "mute_4h", "For 4 Hours", true, None::<&str>)?)
+            .item(&MenuItem::with_id(
+                app,
+                "mute_tomorrow",
+                "Until Tomorrow",
+                true,
+                None::<&str>,
+            )?)
+            .build()?;
+        builder = builder.item(&mute_submenu);
+    }
+    builder = builder.item(&CheckMenuItem::with_id(
+        app,
+        "toggle_compact_mode",
+        "Compact Menu",
+        true,
+        settings.compact_mode,
+        None::<&str>,
+    )?);
 
     builder = builder.separator();
 
@@ -346,6 +721,22 @@ fn build_tray_menu<R: Runtime>(app: &AppHandle<R>) -> Result<Menu<R>, tauri::Err
         Some("CmdOrCtrl+O"),
     )?);
 
+    builder = builder.item(&MenuItem::with_id(
+        app,
+        "search",
+        "Search…",
+        true,
+        Some("CmdOrCtrl+K"),
+    )?);
+
+    builder = builder.item(&MenuItem::with_id(
+        app,
+        "kiosk",
+        "Open Dashboard Display",
+        true,
+        None::<&str>,
+    )?);
+
     builder = builder.item(&MenuItem::with_id(
         app,
         "refresh",
@@ -354,6 +745,14 @@ fn build_tray_menu<R: Runtime>(app: &AppHandle<R>) -> Result<Menu<R>, tauri::Err
         Some("CmdOrCtrl+R"),
     )?);
 
+    builder = builder.item(&MenuItem::with_id(
+        app,
+        "copy_summary",
+        "Copy Stats Summary",
+        true,
+        None::<&str>,
+    )?);
+
     builder = builder.separator();
 
     builder = builder.item(&MenuItem::with_id(
@@ -367,15 +766,118 @@ fn build_tray_menu<R: Runtime>(app: &AppHandle<R>) -> Result<Menu<R>, tauri::Err
     builder.build()
 }
 
-/// Update the tray menu with fresh data
+/// Switch the Claude data location and reload everything read from it.
+/// `None` selects CLAUDE_CONFIG_DIR or ~/.claude. The config watcher follows
+/// the settings change on its own.
+pub fn switch_profile<R: Runtime>(app: &AppHandle<R>, profile: Option<String>) -> Result<(), String> {
+    let settings_service = SettingsService::shared();
+    if let Some(name) = &profile {
+        if !settings_service.get().claude_profiles.iter().any(|p| &p.name == name) {
+            return Err(format!("Unknown profile: {}", name));
+        }
+    }
+    settings_service.modify(|settings| settings.active_profile = profile)?;
+
+    // Stats, projects and the trend all come from the new location
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        refresh_menu_cache();
+        if let Some(state) = app_handle.try_state::<AppState>() {
+            state.analytics.refresh_stats();
+            refresh_trend_cache(&state.analytics);
+            state.session_tracker.refresh_active();
+        }
+        let _ = update_tray_menu(&app_handle);
+        let _ = app_handle.emit("refresh-stats", ());
+    });
+    Ok(())
+}
+
+/// Update the tray menu (and the widget snapshot) with fresh data
 pub fn update_tray_menu<R: Runtime>(app: &AppHandle<R>) -> Result<(), tauri::Error> {
     if let Some(tray) = app.tray_by_id("main-tray") {
         let menu = build_tray_menu(app)?;
         tray.set_menu(Some(menu))?;
     }
+    if let Err(e) = widget::publish(app) {
+        eprintln!("Failed to publish widget snapshot: {}", e);
+    }
     Ok(())
 }
 
+/// Reflect sessions waiting for input in the tray icon title/tooltip and menu
+pub fn update_attention_indicator<R: Runtime>(app: &AppHandle<R>) -> Result<(), tauri::Error> {
+    let waiting = app
+        .try_state::<AppState>()
+        .map(|state| state.session_tracker.sessions_needing_attention().len())
+        .unwrap_or(0);
+
+    if let Some(tray) = app.tray_by_id("main-tray") {
+        if waiting > 0 {
+            // Title shows next to the icon in the macOS menu bar
+            tray.set_title(Some("●"))?;
+            tray.set_tooltip(Some(format!(
+                "Claudit{}Claude needs your attention",
+                format::separator()
+            )))?;
+        } else {
+            tray.set_title(None::<&str>)?;
+            tray.set_tooltip(Some("Claudit"))?;
+        }
+    }
+
+    update_tray_menu(app)
+}
+
+/// Rescan stats, trend and menu data in the background, then update the tray
+pub fn refresh_all<R: Runtime>(app: &AppHandle<R>) {
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        // Refresh analytics stats
+        if let Some(state) = app_handle.try_state::<AppState>() {
+            let _ = state.analytics.refresh_stats();
+            refresh_trend_cache(&state.analytics);
+        }
+        // Refresh menu cache (projects) from disk
+        refresh_menu_cache();
+        // Update menu with fresh cached data
+        let _ = update_tray_menu(&app_handle);
+        let _ = app_handle.emit("stats-refreshed", ());
+    });
+}
+
+/// Turn notifications on or off, returning the new state. The menu and the
+/// frontend follow the settings change.
+pub fn toggle_notifications() -> bool {
+    match SettingsService::shared()
+        .modify(|settings| settings.notifications_enabled = !settings.notifications_enabled)
+    {
+        Ok(settings) => settings.notifications_enabled,
+        Err(e) => {
+            eprintln!("Failed to toggle notifications: {}", e);
+            SettingsService::shared().get().notifications_enabled
+        }
+    }
+}
+
+/// Mute notifications for a while, or unmute with `None`. Returns when the
+/// mute ends (RFC 3339).
+pub fn set_notification_mute<R: Runtime>(
+    app: &AppHandle<R>,
+    duration: Option<MuteDuration>,
+) -> Option<String> {
+    let until = match duration {
+        Some(duration) => Some(notifications::mute_for(duration).to_rfc3339()),
+        None => {
+            notifications::unmute();
+            None
+        }
+    };
+    let _ = update_tray_menu(app);
+    let _ = app.emit("notifications-muted", &until);
+    until
+}
+
 fn handle_menu_event<R: Runtime>(app: &AppHandle<R>, id: &str) {
     println!("Menu event: {}", id);
 
@@ -390,6 +892,34 @@ fn handle_menu_event<R: Runtime>(app: &AppHandle<R>, id: &str) {
         return;
     }
 
+    // Handle running session clicks - open the session in the app
+    if let Some(index) = id.strip_prefix("active_session_").and_then(|i| i.parse::<usize>().ok()) {
+        let session = app
+            .try_state::<AppState>()
+            .and_then(|state| state.session_tracker.cached_active().get(index).cloned());
+        if let (Some(session), Some(window)) = (session, app.get_webview_window("analytics")) {
+            let _ = window.show();
+            let _ = window.set_focus();
+            let _ = app.emit("open-session", session);
+        }
+        return;
+    }
+
+    // Handle profile switching
+    if let Some(profile) = id.strip_prefix("profile_") {
+        let profile = match profile {
+            "default" => None,
+            index => index.parse::<usize>().ok().and_then(|i| {
+                let settings = SettingsService::shared().get();
+                settings.claude_profiles.get(i).map(|p| p.name.clone())
+            }),
+        };
+        if let Err(e) = switch_profile(app, profile) {
+            eprintln!("Failed to switch Claude profile: {}", e);
+        }
+        return;
+    }
+
     // Handle navigation
     if id.starts_with("nav_") {
         let section = id.replace("nav_", "");
@@ -402,68 +932,67 @@ fn handle_menu_event<R: Runtime>(app: &AppHandle<R>, id: &str) {
     }
 
     match id {
-        "analytics" => {
+        "analytics" | "attention" => {
+            if let Some(window) = app.get_webview_window("analytics") {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }
+        "search" => {
             if let Some(window) = app.get_webview_window("analytics") {
                 let _ = window.show();
                 let _ = window.set_focus();
+                let _ = app.emit("open-search", ());
+            }
+        }
+        "kiosk" => {
+            if let Err(e) = kiosk::open(app) {
+                eprintln!("Failed to open kiosk dashboard: {}", e);
             }
         }
-        "refresh" => {
+        "refresh" => refresh_all(app),
+        "copy_summary" => {
             let app_handle = app.clone();
             std::thread::spawn(move || {
-                // Refresh analytics stats
                 if let Some(state) = app_handle.try_state::<AppState>() {
-                    let _ = state.analytics.refresh_stats();
+                    if let Err(e) = summary::copy(&state.analytics.cached_stats()) {
+                        eprintln!("Failed to copy stats summary: {}", e);
+                    }
                 }
-                // Refresh menu cache (projects, settings) from disk
-                refresh_menu_cache();
-                // Update menu with fresh cached data
-                let _ = update_tray_menu(&app_handle);
-                let _ = app_handle.emit("stats-refreshed", ());
             });
         }
         "toggle_notifications" => {
-            // Toggle using cached value for instant response
-            let (new_state, cached_settings) = {
-                let mut cache = MENU_CACHE.lock().unwrap();
-                if let Some(ref mut settings) = cache.settings {
-                    settings.notifications_enabled = !settings.notifications_enabled;
-                    (settings.notifications_enabled, Some(settings.clone()))
-                } else {
-                    // No cached settings, default to enabling
-                    (true, None)
-                }
-            };
-
-            // Update menu immediately with new cached state
-            let _ = update_tray_menu(app);
-
-            // Notify the frontend immediately with cached settings
-            if let Some(settings) = &cached_settings {
-                let _ = app.emit("settings-changed", settings);
+            toggle_notifications();
+        }
+        "mute_1h" => {
+            set_notification_mute(app, Some(MuteDuration::OneHour));
+        }
+        "mute_4h" => {
+            set_notification_mute(app, Some(MuteDuration::FourHours));
+        }
+        "mute_tomorrow" => {
+            set_notification_mute(app, Some(MuteDuration::UntilTomorrow));
+        }
+        "unmute" => {
+            set_notification_mute(app, None);
+        }
+        "toggle_compact_mode" => {
+            if let Err(e) = SettingsService::shared()
+                .modify(|settings| settings.compact_mode = !settings.compact_mode)
+            {
+                eprintln!("Failed to toggle compact mode: {}", e);
             }
-
-            // Persist to disk in background (no need to emit again)
-            std::thread::spawn(move || {
-                let settings_service = SettingsService::new();
-                let mut settings = settings_service.get();
-                settings.notifications_enabled = new_state;
-                let _ = settings_service.update(settings);
-            });
         }
         "action_open_config" => {
-            let home = dirs::home_dir().unwrap_or_default();
-            let config_path = home.join(".claude.json");
+            let config_path = paths::current().claude_json().to_path_buf();
             let _ = Command::new("open").arg(config_path).spawn();
         }
         "action_open_claude_dir" => {
-            let home = dirs::home_dir().unwrap_or_default();
-            let claude_dir = home.join(".claude");
+            let claude_dir = paths::current().config_dir().to_path_buf();
             let _ = Command::new("open").arg(claude_dir).spawn();
         }
         "action_open_settings_json" => {
-            let home = dirs::home_dir().unwrap_or_default();
-            let settings_path = home.join(".claude").join("settings.json");
+            let settings_path = paths::current().settings_json();
             let _ = Command::new("open").arg(settings_path).spawn();
         }
         "quit" => {
@@ -24,10 +24,38 @@ pub struct RawLogEntry {
     pub message: Option<MessageData>,
     pub uuid: Option<String>,
     pub cwd: Option<String>,
+    /// Time to first token, present on some assistant entries
+    #[serde(rename = "ttftMs")]
+    pub ttft_ms: Option<u64>,
+    /// Total request duration, present on some assistant entries
+    #[serde(rename = "durationMs")]
+    pub duration_ms: Option<u64>,
+    /// Set on the synthetic assistant message Claude Code writes when a request fails
+    #[serde(rename = "isApiErrorMessage")]
+    pub is_api_error_message: Option<bool>,
+    /// System entry subtype (e.g. "api_error" for a failed attempt before a retry)
+    pub subtype: Option<String>,
+    pub error: Option<serde_json::Value>,
+    /// On "compact_boundary" system entries: trigger and context size before compaction
+    #[serde(rename = "compactMetadata")]
+    pub compact_metadata: Option<CompactMetadata>,
+    /// Set on the user message that carries the summary after a compaction
+    #[serde(rename = "isCompactSummary")]
+    pub is_compact_summary: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CompactMetadata {
+    /// "auto" or "manual"
+    pub trigger: Option<String>,
+    #[serde(rename = "preTokens")]
+    pub pre_tokens: Option<u64>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct MessageData {
+    /// API message ID, shared by every log line of one response
+    pub id: Option<String>,
     pub role: Option<String>,
     pub model: Option<String>,
     pub usage: Option<UsageData>,
@@ -79,6 +107,8 @@ pub struct UsageData {
     pub output_tokens: Option<u64>,
     pub cache_creation_input_tokens: Option<u64>,
     pub cache_read_input_tokens: Option<u64>,
+    /// "standard", "priority" or "batch"
+    pub service_tier: Option<String>,
 }
 
 /// Parsed and validated usage entry
@@ -93,6 +123,20 @@ pub struct UsageEntry {
     pub cache_read_tokens: u64,
     pub uuid: String,
     pub project: String,
+    #[serde(default)]
+    pub ttft_ms: Option<u64>,
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
+    #[serde(default)]
+    pub message_id: Option<String>,
+    #[serde(default)]
+    pub service_tier: Option<String>,
+    /// Working directory of the request (can be below the project in monorepos)
+    #[serde(default)]
+    pub cwd: Option<String>,
+    /// Tools the response called (names of its tool_use blocks)
+    #[serde(default)]
+    pub tools: Vec<String>,
 }
 
 impl UsageEntry {
@@ -101,6 +145,30 @@ impl UsageEntry {
     }
 }
 
+/// Price tier a request was billed at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PricingTier {
+    Standard,
+    /// Prompt above 200k tokens on a 1M-context model
+    LongContext,
+}
+
+/// How the cost of a single response was computed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostBreakdown {
+    pub cost: f64,
+    pub tier: PricingTier,
+    /// Prompt size (input + cache creation + cache read tokens) that decides the tier
+    pub context_tokens: u64,
+    /// Rates applied, per 1M tokens (before the service tier multiplier)
+    pub rates: TokenCosts,
+    /// Service tier from the usage data ("standard" when not recorded)
+    pub service_tier: String,
+    /// Multiplier applied for the service tier (0.5 for batch)
+    pub service_tier_multiplier: f64,
+}
+
 /// Token costs per 1M tokens
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenCosts {
@@ -180,9 +248,23 @@ pub struct AnalyticsStats {
     // Per-model breakdown
     pub by_model: HashMap<String, ModelStats>,
 
+    // Per-model breakdown of today's usage
+    #[serde(default)]
+    pub today_by_model: HashMap<String, ModelStats>,
+
+    // Per-model breakdown of the last `range_days` days, when requested
+    #[serde(default)]
+    pub range_days: Option<u32>,
+    #[serde(default)]
+    pub range_by_model: HashMap<String, ModelStats>,
+
     // Per-project breakdown
     pub by_project: HashMap<String, ProjectStats>,
 
+    // Per-service-tier breakdown (standard / priority / batch)
+    #[serde(default)]
+    pub by_service_tier: HashMap<String, ServiceTierStats>,
+
     // Message counts
     pub today_messages_count: u64,
     pub total_messages_count: u64,
@@ -191,6 +273,18 @@ pub struct AnalyticsStats {
     pub last_updated: Option<DateTime<Utc>>,
 }
 
+/// Usage billed at one service tier
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ServiceTierStats {
+    pub requests: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Cost after the tier's discount
+    pub cost: f64,
+    /// What the same usage costs at standard rates
+    pub standard_cost: f64,
+}
+
 impl AnalyticsStats {
     pub fn total_tokens(&self) -> u64 {
         self.total_input_tokens + self.total_output_tokens
@@ -217,6 +311,20 @@ pub struct DailyStats {
     pub output_tokens: u64,
     pub cost: f64,
     pub messages: u64,
+    /// Per-model split of the day. Every day lists the same models in the same
+    /// order as `ChartData.by_model` (zeros where unused), so series line up for stacking.
+    #[serde(default)]
+    pub by_model: Vec<DailyModelStats>,
+}
+
+/// One model's share of a day's usage
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DailyModelStats {
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost: f64,
+    pub messages: u64,
 }
 
 /// Hourly distribution for chart data
@@ -227,13 +335,107 @@ pub struct HourlyStats {
     pub messages: u64,
 }
 
+/// What `drill_down` groups usage by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DrillDimension {
+    Project,
+    Model,
+    /// YYYY-MM-DD (UTC)
+    Day,
+    /// 00-23 (UTC)
+    Hour,
+    Session,
+    /// Tool called by the response; a response calling several tools counts
+    /// toward each of them
+    Tool,
+    ServiceTier,
+}
+
+/// Usage `drill_down` includes; empty lists don't filter
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DrillDownFilters {
+    #[serde(default)]
+    pub projects: Vec<String>,
+    #[serde(default)]
+    pub models: Vec<String>,
+    #[serde(default)]
+    pub sessions: Vec<String>,
+    #[serde(default)]
+    pub tools: Vec<String>,
+    /// First and last day (YYYY-MM-DD, inclusive)
+    #[serde(default)]
+    pub since: Option<String>,
+    #[serde(default)]
+    pub until: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DrillMeasure {
+    #[default]
+    Cost,
+    Tokens,
+    Requests,
+    ToolUses,
+    /// The group keys, in dimension order
+    Keys,
+}
+
+/// Row order of `drill_down`, largest first unless `ascending`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DrillSort {
+    #[serde(default)]
+    pub by: DrillMeasure,
+    #[serde(default)]
+    pub ascending: bool,
+}
+
+/// Usage of one combination of dimension values
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DrillDownRow {
+    /// One value per dimension, in the order requested
+    pub keys: Vec<String>,
+    pub cost: f64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_creation_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub requests: u64,
+    pub tool_uses: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DrillDownResult {
+    pub dimensions: Vec<DrillDimension>,
+    pub rows: Vec<DrillDownRow>,
+    /// Groups before the limit was applied
+    pub total_rows: u64,
+}
+
 /// Chart data bundle for frontend
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChartData {
+    /// One point per `bucket` (per day unless downsampled)
     pub daily: Vec<DailyStats>,
     pub hourly: Vec<HourlyStats>,
     pub by_model: Vec<ModelChartData>,
     pub by_project: Vec<ProjectChartData>,
+    #[serde(default)]
+    pub bucket: ChartBucket,
+}
+
+/// Period each point of `ChartData.daily` covers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChartBucket {
+    #[default]
+    Day,
+    /// Weeks starting on Monday
+    Week,
+    Month,
+    /// Day up to 90 days, week up to a year, month beyond
+    Auto,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -250,6 +452,191 @@ pub struct ProjectChartData {
     pub cost: f64,
 }
 
+/// Prompt caching savings estimate for a period
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CacheSimulation {
+    pub days: u32,
+    pub uncached_input_tokens: u64,
+    pub cache_creation_tokens: u64,
+    pub cache_read_tokens: u64,
+    /// Share of input tokens served from cache (0-100)
+    pub cache_hit_rate: f64,
+    /// What was actually spent
+    pub actual_cost: f64,
+    /// What the same usage would have cost without prompt caching
+    pub cost_without_cache: f64,
+    pub caching_savings: f64,
+    /// Cache writes of context that an earlier request of the session had already cached
+    pub rewritten_cache_tokens: u64,
+    /// How re-cached context is told apart from new context
+    pub assumption: String,
+    /// Assumed share of re-cached context that better cache locality would turn into reads (0-1)
+    pub locality_improvement: f64,
+    /// Extra savings if that share of re-cached context were read from cache instead
+    pub potential_additional_savings: f64,
+    pub by_model: Vec<ModelCacheSimulation>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelCacheSimulation {
+    pub model: String,
+    pub actual_cost: f64,
+    pub cost_without_cache: f64,
+    pub caching_savings: f64,
+    pub potential_additional_savings: f64,
+}
+
+/// Category of a failed API request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiErrorKind {
+    /// 529 / overloaded_error
+    Overloaded,
+    /// 429 / rate_limit_error
+    RateLimited,
+    /// 500-504 / api_error
+    ServerError,
+    /// Anything else (auth, invalid request, network) - usually local config
+    Other,
+}
+
+/// A failed API request found in a session log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiErrorEntry {
+    pub timestamp: DateTime<Utc>,
+    pub session_id: String,
+    /// Model of the session's last successful response ("unknown" if none yet)
+    pub model: String,
+    pub kind: ApiErrorKind,
+    pub project: String,
+}
+
+/// Daily API error counts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DailyErrorStats {
+    pub date: String,
+    /// Successful requests
+    pub requests: u64,
+    pub errors: u64,
+    pub overloaded: u64,
+    pub rate_limited: u64,
+    pub server_error: u64,
+    pub other: u64,
+    pub by_model: Vec<ModelErrorCount>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelErrorCount {
+    pub model: String,
+    pub errors: u64,
+}
+
+/// One day of a project's spend
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectDailyCost {
+    pub date: String,
+    pub cost: f64,
+    pub messages: u64,
+}
+
+/// A project's spend over the last `days` days, next to the `days` before
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectCostHistory {
+    pub project: String,
+    pub days: u32,
+    /// Every day of the period, oldest first, including days without spend
+    pub daily: Vec<ProjectDailyCost>,
+    pub total_cost: f64,
+    pub previous_cost: f64,
+    /// total_cost / previous_cost (None when the previous period had no spend)
+    pub trend: Option<f64>,
+    /// Share of all spend in the period (0-100)
+    pub share_of_spend: f64,
+}
+
+/// API error rate for a period
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ErrorStats {
+    pub days: u32,
+    pub requests: u64,
+    pub errors: u64,
+    /// errors / (requests + errors), 0-100
+    pub error_rate: f64,
+    pub daily: Vec<DailyErrorStats>,
+}
+
+/// A context compaction found in a session log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompactionEvent {
+    pub timestamp: DateTime<Utc>,
+    pub session_id: String,
+    pub project: String,
+    /// "auto", "manual" or "unknown"
+    pub trigger: String,
+    /// Context size before compaction (None if it couldn't be determined)
+    pub tokens_before: Option<u64>,
+    /// Context size of the first response after compaction
+    pub tokens_after: Option<u64>,
+}
+
+/// Compaction counts for one project
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectCompaction {
+    pub project: String,
+    pub compactions: u64,
+    pub sessions: u64,
+    pub avg_tokens_before: f64,
+}
+
+/// How often context gets compacted and how much it shrinks
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompactionReport {
+    pub days: u32,
+    pub compactions: u64,
+    pub auto_compactions: u64,
+    pub manual_compactions: u64,
+    /// Sessions that compacted at least once
+    pub sessions_compacted: u64,
+    /// Averages over compactions where both sizes are known
+    pub avg_tokens_before: f64,
+    pub avg_tokens_after: f64,
+    /// Sum of (before - after) context tokens
+    pub tokens_reclaimed: u64,
+    /// Projects that compact most, descending
+    pub by_project: Vec<ProjectCompaction>,
+    /// Most recent compactions, newest first (up to 50)
+    pub recent: Vec<CompactionEvent>,
+}
+
+/// Distribution of response timings in milliseconds
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LatencySummary {
+    pub samples: u64,
+    pub avg_ms: f64,
+    pub p50_ms: u64,
+    pub p90_ms: u64,
+    pub p99_ms: u64,
+}
+
+/// Time to first token and total duration for one model or day.
+/// `None` when no entries in the group carried that timing.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LatencyGroup {
+    /// Model name or date (YYYY-MM-DD)
+    pub key: String,
+    pub ttft: Option<LatencySummary>,
+    pub duration: Option<LatencySummary>,
+}
+
+/// Response latency metrics for a period
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LatencyStats {
+    pub days: u32,
+    pub overall: LatencyGroup,
+    pub by_model: Vec<LatencyGroup>,
+    pub daily: Vec<LatencyGroup>,
+}
+
 /// Hook event from Claude Code
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HookEvent {
@@ -257,6 +644,16 @@ pub struct HookEvent {
     pub tool: Option<String>,
     pub context: Option<String>,
     pub timestamp: Option<String>,
+    /// Fields below are forwarded by the claudit-hook relay from Claude's hook payload
+    #[serde(default)]
+    pub session_id: Option<String>,
+    #[serde(default)]
+    pub cwd: Option<String>,
+    #[serde(default)]
+    pub transcript_path: Option<String>,
+    /// Raw hook payload as received from Claude Code on stdin
+    #[serde(default)]
+    pub payload: Option<serde_json::Value>,
 }
 
 /// Application settings
@@ -272,17 +669,296 @@ pub struct AppSettings {
     pub compact_mode: bool,
     pub auto_start: bool,
     pub hook_port: u16,
+    /// Address the hook server listens on: 127.0.0.1, ::1, or 0.0.0.0 / :: so
+    /// hooks can arrive from a devcontainer, VM or another machine (applies on restart)
+    #[serde(default = "default_hook_bind_address")]
+    pub hook_bind_address: String,
     #[serde(default)]
     pub claude_cli_path: Option<String>,
     /// Terminal app to use: "auto", "Terminal", "iTerm", "Warp", "Alacritty", "kitty"
     #[serde(default = "default_terminal_app")]
     pub terminal_app: String,
+    /// Skip notifications while macOS Focus / Windows Focus Assist is active
+    #[serde(default = "default_true")]
+    pub respect_do_not_disturb: bool,
+    /// Record notifications suppressed by Do Not Disturb in the activity feed instead of dropping them
+    #[serde(default = "default_true")]
+    pub queue_suppressed_notifications: bool,
+    /// Notify when a session has been running continuously for this many minutes (0 = off)
+    #[serde(default = "default_long_running_minutes")]
+    pub long_running_minutes: u32,
+    /// Warn when no usage was recorded for this many days while hooks keep
+    /// firing (0 = off)
+    #[serde(default = "default_data_gap_days")]
+    pub data_gap_days: u32,
+    /// Optional spend/output goal ("goal mode"), None = off
+    #[serde(default)]
+    pub goal: Option<GoalSettings>,
+    /// Notify when the API error rate spikes
+    #[serde(default)]
+    pub notify_error_spikes: bool,
+    /// Named Claude data locations (e.g. work and personal)
+    #[serde(default)]
+    pub claude_profiles: Vec<ClaudeProfile>,
+    /// Name of the profile in use (None = CLAUDE_CONFIG_DIR or ~/.claude)
+    #[serde(default)]
+    pub active_profile: Option<String>,
+    /// Fullscreen dashboard for a spare monitor
+    #[serde(default)]
+    pub kiosk: KioskSettings,
+    /// Show generated sample data instead of the real Claude data (read-only)
+    #[serde(default)]
+    pub demo_mode: bool,
+    /// Left-clicking the tray icon opens the popover window instead of the menu
+    #[serde(default)]
+    pub tray_popover: bool,
+    /// Batch non-urgent notifications (sessions finishing) into one summary
+    /// every this many minutes (0 = deliver each one)
+    #[serde(default)]
+    pub notification_digest_minutes: u32,
+    /// Collapse a session's Stop/SubagentStop notifications arriving within
+    /// this many seconds into one (0 = notify for each)
+    #[serde(default = "default_notification_collapse_seconds")]
+    pub notification_collapse_seconds: u32,
+    /// Publish a stats snapshot for companion widgets (widget.json and GET /api/v1/widget)
+    #[serde(default)]
+    pub widget_api: bool,
+    /// Accept actions from Stream Deck / Shortcuts on the hook server (POST /api/v1/actions/...)
+    #[serde(default)]
+    pub automation_api: bool,
+    /// Notify when a tool use matches one of these rules (PostToolUse hook)
+    #[serde(default)]
+    pub tool_notification_rules: Vec<ToolNotificationRule>,
+    /// Reinstall claudit's hooks when Claude Code drops or rewrites them
+    #[serde(default)]
+    pub auto_repair_hooks: bool,
+    /// Keep the raw bodies of recent /hook requests for debugging
+    #[serde(default)]
+    pub hook_inspector: bool,
+    /// minisign public keys whose config pack signatures are trusted
+    #[serde(default)]
+    pub trusted_pack_keys: Vec<TrustedPackKey>,
+    /// Refuse config packs that aren't signed by a trusted key
+    #[serde(default)]
+    pub require_signed_packs: bool,
+    /// Sync ~/.claude with a GitHub repository, None = off
+    #[serde(default)]
+    pub config_sync: Option<ConfigSyncSettings>,
+    /// Corrections and additions to the bundled model catalog
+    #[serde(default)]
+    pub model_catalog_overrides: Vec<ModelCatalogOverride>,
+    /// Container/remote path prefixes and the host folders they correspond to
+    #[serde(default)]
+    pub path_mappings: Vec<PathMapping>,
+    /// How costs are rounded and written in the tray, notifications, exports and reports
+    #[serde(default)]
+    pub cost_format: CostFormat,
+    /// Layout of the copied stats summary, None = the built-in template
+    #[serde(default)]
+    pub summary_template: Option<String>,
+    /// Send anonymous, weekly usage counts of claudit's own features
+    #[serde(default)]
+    pub telemetry_enabled: bool,
+    /// Wording of tray labels and notifications (terse or verbose, emoji,
+    /// screen-reader phrasing)
+    #[serde(default)]
+    pub text_style: TextStyle,
+}
+
+/// Notify when a tool use matches, e.g. Bash running `git push`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolNotificationRule {
+    pub id: String,
+    /// Shown as the notification title
+    pub name: String,
+    pub enabled: bool,
+    /// Regex on the tool name, e.g. `Bash` or `Write|Edit` (empty = any tool)
+    pub tool: String,
+    /// Regex on the tool's main input: the Bash command, the file path, the URL, ...
+    /// (empty = any input)
+    #[serde(default)]
+    pub input: String,
+}
+
+/// A minisign public key trusted to sign config packs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrustedPackKey {
+    /// Who the key belongs to, e.g. "Platform team"
+    pub name: String,
+    /// The key line of a minisign.pub file (`RW...`)
+    pub public_key: String,
+}
+
+/// GitHub repository the Claude config is synced with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigSyncSettings {
+    /// HTTPS URL of the repository (or `owner/repo` on GitHub)
+    pub remote: String,
+    pub branch: String,
+    /// Commit config changes every this many minutes (0 = only when pushing)
+    pub auto_commit_minutes: u32,
+    /// Push after each automatic commit
+    pub auto_push: bool,
+}
+
+/// Override of a model's limits in the catalog; unset fields keep the bundled values.
+/// A model the catalog doesn't know is added with these values.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelCatalogOverride {
+    /// Model ID, e.g. `claude-sonnet-4-5`
+    pub model: String,
+    #[serde(default)]
+    pub context_window: Option<u64>,
+    #[serde(default)]
+    pub max_output: Option<u64>,
+    /// YYYY-MM
+    #[serde(default)]
+    pub knowledge_cutoff: Option<String>,
+}
+
+/// Where a path seen inside a devcontainer or over SSH lives on this machine,
+/// e.g. `/workspaces` -> `/Users/me/code`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PathMapping {
+    /// Path prefix as Claude sees it
+    pub from: String,
+    /// The same folder on this machine
+    pub to: String,
+}
+
+/// How a cost is rounded and written, e.g. `$1,234.57`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CostFormat {
+    /// Decimal places; None = 2 from $1, 3 from $0.01 and 4 below that
+    pub precision: Option<u8>,
+    pub rounding: RoundingMode,
+    /// Put between groups of thousands, e.g. "," or " " (empty = none)
+    pub thousands_separator: String,
+    pub decimal_separator: String,
+}
+
+impl Default for CostFormat {
+    fn default() -> Self {
+        Self {
+            precision: None,
+            rounding: RoundingMode::HalfUp,
+            thousands_separator: String::new(),
+            decimal_separator: ".".to_string(),
+        }
+    }
+}
+
+/// How tray labels and notifications are worded
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextStyle {
+    #[serde(default)]
+    pub labels: LabelStyle,
+    /// Start tray sections and notification titles with an emoji
+    #[serde(default)]
+    pub emoji_prefixes: bool,
+    /// Notifications without symbols, and excerpts cut between words
+    #[serde(default)]
+    pub screen_reader_notifications: bool,
+}
+
+/// Abbreviated ("1.2M tok") or spelled-out ("1.2 million tokens") labels
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LabelStyle {
+    #[default]
+    Terse,
+    Verbose,
+}
+
+/// How a cost is rounded to the display precision
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    /// Halves away from zero ($0.125 -> $0.13)
+    HalfUp,
+    /// Halves to the even digit, "banker's rounding" ($0.125 -> $0.12)
+    HalfEven,
+    /// Toward zero
+    Down,
+    /// Away from zero
+    Up,
+}
+
+/// Kiosk dashboard window settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KioskSettings {
+    /// Seconds between stats refreshes
+    pub refresh_seconds: u32,
+    /// Seconds each page is shown before rotating (0 = no rotation)
+    pub rotate_seconds: u32,
+    /// Pages to rotate through: "overview", "cost", "tokens", "models", "projects", "hourly"
+    pub pages: Vec<String>,
+}
+
+impl Default for KioskSettings {
+    fn default() -> Self {
+        Self {
+            refresh_seconds: 30,
+            rotate_seconds: 20,
+            pages: ["overview", "cost", "models", "projects", "hourly"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        }
+    }
+}
+
+/// A named Claude config directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaudeProfile {
+    pub name: String,
+    /// Directory Claude Code uses as CLAUDE_CONFIG_DIR for this profile
+    pub config_dir: String,
+}
+
+/// What a goal measures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GoalKind {
+    /// Spend at most `target` USD per day
+    DailySpend,
+    /// Spend at most `target` USD per week (Monday-Sunday)
+    WeeklySpend,
+    /// Produce at least `target` output tokens per day
+    DailyOutput,
+}
+
+/// A daily/weekly spend or output target
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalSettings {
+    pub kind: GoalKind,
+    pub target: f64,
+}
+
+fn default_long_running_minutes() -> u32 {
+    45
+}
+
+fn default_data_gap_days() -> u32 {
+    3
+}
+
+fn default_notification_collapse_seconds() -> u32 {
+    5
 }
 
 fn default_terminal_app() -> String {
     "auto".to_string()
 }
 
+fn default_hook_bind_address() -> String {
+    "127.0.0.1".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
@@ -296,8 +972,36 @@ impl Default for AppSettings {
             compact_mode: false,
             auto_start: false,
             hook_port: 3456,
+            hook_bind_address: default_hook_bind_address(),
             claude_cli_path: None,
             terminal_app: default_terminal_app(),
+            respect_do_not_disturb: true,
+            queue_suppressed_notifications: true,
+            long_running_minutes: default_long_running_minutes(),
+            data_gap_days: default_data_gap_days(),
+            goal: None,
+            notify_error_spikes: false,
+            claude_profiles: Vec::new(),
+            active_profile: None,
+            kiosk: KioskSettings::default(),
+            demo_mode: false,
+            tray_popover: false,
+            notification_digest_minutes: 0,
+            notification_collapse_seconds: default_notification_collapse_seconds(),
+            widget_api: false,
+            automation_api: false,
+            tool_notification_rules: Vec::new(),
+            auto_repair_hooks: false,
+            hook_inspector: false,
+            trusted_pack_keys: Vec::new(),
+            require_signed_packs: false,
+            config_sync: None,
+            model_catalog_overrides: Vec::new(),
+            path_mappings: Vec::new(),
+            cost_format: CostFormat::default(),
+            summary_template: None,
+            telemetry_enabled: false,
+            text_style: TextStyle::default(),
         }
     }
 }
@@ -308,6 +1012,9 @@ impl Default for AppSettings {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionInfo {
     pub session_id: String,
+    /// Title set in claudit, shown instead of the summary
+    #[serde(default)]
+    pub title: Option<String>,
     pub summary: Option<String>,
     pub first_user_message: Option<String>,
     pub first_message_at: Option<String>,
@@ -319,6 +1026,26 @@ pub struct SessionInfo {
     pub total_cache_read_tokens: u64,
     pub total_cost: f64,
     pub model: Option<String>,
+    /// Tokens and cost of each model used, costliest first
+    #[serde(default)]
+    pub model_breakdown: Vec<SessionModelBreakdown>,
+}
+
+/// One model's share of a session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionModelBreakdown {
+    pub model: String,
+    #[serde(flatten)]
+    pub stats: ModelStats,
+}
+
+/// A session in the cost leaderboard, with the project it belongs to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpensiveSession {
+    pub project_path: String,
+    pub project_name: String,
+    #[serde(flatten)]
+    pub session: SessionInfo,
 }
 
 /// A single message in a conversation
@@ -331,6 +1058,10 @@ pub struct ConversationMessage {
     pub model: Option<String>,
     pub input_tokens: Option<u64>,
     pub output_tokens: Option<u64>,
+    /// Cost of an assistant response, with the pricing tier it was billed at
+    pub cost: Option<CostBreakdown>,
+    /// Context window the response's prompt ran in (from the model catalog)
+    pub context_window: Option<u64>,
 }
 
 /// Content block within a message
@@ -352,22 +1083,88 @@ pub enum MessageContentBlock {
         tool_use_id: Option<String>,
         content: Option<serde_json::Value>,
     },
+    /// A tool call together with its result, paired by tool use id
+    #[serde(rename = "tool_invocation")]
+    ToolInvocation {
+        id: Option<String>,
+        name: Option<String>,
+        input: Option<serde_json::Value>,
+        result: Option<serde_json::Value>,
+        /// From the call to its result
+        duration_ms: Option<u64>,
+    },
     #[serde(other)]
     Other,
 }
 
-/// Full session with all messages
+/// A session's messages, all of them or one page
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionConversation {
     pub session_id: String,
     pub summary: Option<String>,
     pub messages: Vec<ConversationMessage>,
+    /// Messages in the whole session
+    #[serde(default)]
+    pub total_messages: usize,
+    /// Index of the first of `messages` in the session
+    #[serde(default)]
+    pub offset: usize,
+    /// Work of the subagents started in `messages`
+    #[serde(default)]
+    pub subagents: Vec<SubagentThread>,
+}
+
+/// A subagent's messages, from sidechain entries in the session file or
+/// from the agent's own transcript
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubagentThread {
+    pub agent_id: Option<String>,
+    /// `Task` tool call that started the subagent, when it could be matched
+    pub tool_use_id: Option<String>,
+    pub description: Option<String>,
+    pub subagent_type: Option<String>,
+    pub messages: Vec<ConversationMessage>,
+}
+
+/// Session prepared for playback
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionReplay {
+    pub session_id: String,
+    pub summary: Option<String>,
+    /// Playback length at 1x (idle gaps are capped)
+    pub duration_ms: u64,
+    /// Wall-clock length of the session
+    pub real_duration_ms: u64,
+    pub messages: Vec<ReplayMessage>,
+}
+
+/// One message on the replay timeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayMessage {
+    pub uuid: String,
+    pub role: String,
+    pub model: Option<String>,
+    /// When the message appears, relative to the start of playback
+    pub offset_ms: u64,
+    /// Offset from the first message in real time
+    pub real_offset_ms: u64,
+    pub chunks: Vec<ReplayChunk>,
+}
+
+/// A piece of a message revealed at once during playback
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayChunk {
+    /// "text", "thinking", "tool_use" or "tool_result"
+    pub kind: String,
+    pub text: String,
 }
 
 /// Search result for a session
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionSearchResult {
     pub session_id: String,
+    #[serde(default)]
+    pub title: Option<String>,
     pub summary: Option<String>,
     pub first_user_message: Option<String>,
     pub matched_text: String,